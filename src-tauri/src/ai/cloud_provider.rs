@@ -1,9 +1,11 @@
 use crate::ai::provider::AiProvider;
 use crate::ai::types::*;
 use crate::ai::errors::AiProviderError;
-use crate::ai::settings::CloudProvider;
+use crate::language::output_language_instruction;
+use crate::ai::settings::{CloudProvider, SamplingParams};
 use crate::ai::retry::{retry_with_backoff, RetryConfig};
-use crate::ai::rate_limiter::RateLimiter;
+use crate::ai::rate_limiter::{acquire_request_permit, RateLimiter};
+use crate::ai::usage_log::{report_token_usage, token_usage_from_response};
 use crate::ai::validation::{validate_parsed_job, validate_resume_suggestions, validate_cover_letter, validate_skill_suggestions};
 use async_trait::async_trait;
 use serde_json::{json, Value};
@@ -11,13 +13,139 @@ use reqwest::Client;
 use std::sync::Arc;
 
 /// Cloud AI Provider
-/// Supports multiple cloud providers (OpenAI, Anthropic, etc.)
+/// Supports multiple cloud providers (OpenAI, Anthropic, etc.). With
+/// `CloudProvider::Anthropic` it talks to the Claude messages API, using the configured
+/// `model_name` like OpenAI does.
 pub struct CloudAiProvider {
     provider: CloudProvider,
     api_key: String,
     model_name: String,
     client: Client,
     rate_limiter: Arc<RateLimiter>,
+    sampling: SamplingParams,
+}
+
+/// Temperature sent to cloud providers when none is configured; low, for consistent JSON
+pub const DEFAULT_CLOUD_TEMPERATURE: f32 = 0.3;
+
+/// Request body for the Anthropic messages API. Anthropic has no seed parameter, so
+/// only the temperature is passed through. `max_tokens` is required by the API and
+/// defaults to 4096.
+pub fn anthropic_request_body(model_name: &str, system_prompt: &str, user_prompt: &str, sampling: &SamplingParams) -> Value {
+    json!({
+        "model": model_name,
+        "max_tokens": sampling.max_tokens.unwrap_or(4096),
+        "system": system_prompt,
+        "messages": [
+            {
+                "role": "user",
+                "content": user_prompt
+            }
+        ],
+        "temperature": sampling.temperature.unwrap_or(DEFAULT_CLOUD_TEMPERATURE)
+    })
+}
+
+/// OpenAI model used for embeddings; chat models can't embed
+pub const OPENAI_EMBEDDING_MODEL: &str = "text-embedding-3-small";
+
+/// The first vector in an OpenAI `/v1/embeddings` response
+pub fn openai_embedding(response: &Value) -> Option<Vec<f32>> {
+    response
+        .get("data")
+        .and_then(|d| d.get(0))
+        .and_then(|d| d.get("embedding"))
+        .and_then(|e| e.as_array())
+        .map(|values| values.iter().filter_map(|v| v.as_f64()).map(|v| v as f32).collect())
+}
+
+/// Map an Anthropic error response onto `AiProviderError`.
+///
+/// Anthropic reports failures as `{"type": "error", "error": {"type": "...", "message": "..."}}`.
+/// The error type is more specific than the status code; overloaded (529) and server
+/// errors map to network errors so they are retried.
+pub fn anthropic_error(status: u16, body: &str) -> AiProviderError {
+    let parsed: Option<Value> = serde_json::from_str(body).ok();
+    let error = parsed.as_ref().and_then(|v| v.get("error"));
+    let error_type = error.and_then(|e| e.get("type")).and_then(|t| t.as_str());
+    let message = error
+        .and_then(|e| e.get("message"))
+        .and_then(|m| m.as_str())
+        .unwrap_or(body)
+        .to_string();
+
+    match (error_type, status) {
+        (Some("authentication_error"), _) | (Some("permission_error"), _) | (None, 401) | (None, 403) => {
+            AiProviderError::InvalidApiKey
+        }
+        (Some("rate_limit_error"), _) | (None, 429) => AiProviderError::RateLimitExceeded,
+        (Some("not_found_error"), _) | (None, 404) => AiProviderError::ModelNotFound,
+        (Some("overloaded_error"), _) | (Some("api_error"), _) | (None, 500..=599) => {
+            AiProviderError::NetworkError(format!("Anthropic API unavailable: {}", message))
+        }
+        (Some("invalid_request_error"), _) | (Some("request_too_large"), _) => {
+            AiProviderError::Unknown(format!("Anthropic rejected the request: {}", message))
+        }
+        _ => AiProviderError::NetworkError(format!("API error: {}", message)),
+    }
+}
+
+/// JSON payload of an Anthropic messages response.
+///
+/// The text blocks in `content` are joined. Claude has no JSON mode and sometimes wraps
+/// its answer in a Markdown code fence, which is stripped before parsing.
+pub fn anthropic_response_json(response: &Value) -> Result<Value, AiProviderError> {
+    let text: String = response
+        .get("content")
+        .and_then(|c| c.as_array())
+        .map(|blocks| {
+            blocks
+                .iter()
+                .filter(|block| block.get("type").and_then(|t| t.as_str()).unwrap_or("text") == "text")
+                .filter_map(|block| block.get("text").and_then(|t| t.as_str()))
+                .collect()
+        })
+        .filter(|text: &String| !text.trim().is_empty())
+        .ok_or_else(|| AiProviderError::InvalidResponse("Missing content in response".to_string()))?;
+
+    let trimmed = text.trim();
+    let unfenced = trimmed
+        .strip_prefix("```json")
+        .or_else(|| trimmed.strip_prefix("```"))
+        .and_then(|rest| rest.trim_end().strip_suffix("```"))
+        .unwrap_or(trimmed)
+        .trim();
+
+    serde_json::from_str(unfenced)
+        .map_err(|e| AiProviderError::InvalidResponse(format!("Failed to parse JSON: {}", e)))
+}
+
+/// Request body for the OpenAI chat completions API
+pub fn openai_request_body(model_name: &str, system_prompt: &str, user_prompt: &str, sampling: &SamplingParams) -> Value {
+    let mut body = json!({
+        "model": model_name,
+        "messages": [
+            {
+                "role": "system",
+                "content": system_prompt
+            },
+            {
+                "role": "user",
+                "content": user_prompt
+            }
+        ],
+        "response_format": {
+            "type": "json_object"
+        },
+        "temperature": sampling.temperature.unwrap_or(DEFAULT_CLOUD_TEMPERATURE)
+    });
+    if let Some(seed) = sampling.seed {
+        body["seed"] = json!(seed);
+    }
+    if let Some(max_tokens) = sampling.max_tokens {
+        body["max_tokens"] = json!(max_tokens);
+    }
+    body
 }
 
 impl CloudAiProvider {
@@ -34,6 +162,29 @@ impl CloudAiProvider {
             model_name,
             client: Client::new(),
             rate_limiter: Arc::new(rate_limiter),
+            sampling: SamplingParams::default(),
+        }
+    }
+
+    /// Use the given temperature and seed instead of the provider's defaults
+    pub fn with_sampling(mut self, sampling: SamplingParams) -> Self {
+        self.sampling = sampling;
+        self
+    }
+
+    pub fn provider(&self) -> CloudProvider {
+        self.provider
+    }
+
+    pub fn model_name(&self) -> &str {
+        &self.model_name
+    }
+
+    /// The model `embed` uses, or `None` when this provider has no embeddings endpoint
+    pub fn embedding_model(&self) -> Option<&'static str> {
+        match self.provider {
+            CloudProvider::OpenAI => Some(OPENAI_EMBEDDING_MODEL),
+            CloudProvider::Anthropic => None,
         }
     }
     
@@ -45,6 +196,7 @@ impl CloudAiProvider {
         let client = &self.client;
         let api_key = &self.api_key;
         let model_name = &self.model_name;
+        let sampling = self.sampling;
         
         // Use retry logic for the API call
         let retry_config = RetryConfig {
@@ -64,58 +216,34 @@ impl CloudAiProvider {
                 let user_prompt = user_prompt.to_string();
                 
                 async move {
+                    // Held until this attempt finishes, so backoff waits don't block other calls
+                    let _permit = acquire_request_permit().await;
                     let response = client
                         .post(&url)
                         .header("x-api-key", api_key)
                         .header("anthropic-version", "2023-06-01")
                         .header("Content-Type", "application/json")
-                        .json(&json!({
-                            "model": model_name,
-                            "max_tokens": 4096,
-                            "system": system_prompt,
-                            "messages": [
-                                {
-                                    "role": "user",
-                                    "content": user_prompt
-                                }
-                            ],
-                            "temperature": 0.3
-                        }))
+                        .json(&anthropic_request_body(&model_name, &system_prompt, &user_prompt, &sampling))
                         .send()
                         .await
                         .map_err(|e| AiProviderError::NetworkError(e.to_string()))?;
                     
-                    if response.status() == 401 {
-                        return Err(AiProviderError::InvalidApiKey);
-                    }
-                    
-                    if response.status() == 429 {
-                        return Err(AiProviderError::RateLimitExceeded);
-                    }
-                    
                     if !response.status().is_success() {
+                        let status = response.status().as_u16();
                         let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-                        return Err(AiProviderError::NetworkError(format!("API error: {}", error_text)));
+                        return Err(anthropic_error(status, &error_text));
                     }
                     
                     let json_response: Value = response
                         .json()
                         .await
                         .map_err(|e| AiProviderError::InvalidResponse(e.to_string()))?;
+                    if let Some(usage) = token_usage_from_response(&json_response) {
+                        report_token_usage(usage);
+                    }
                     
-                    // Extract content from Anthropic response format
                     // Anthropic returns: { "content": [{"type": "text", "text": "..."}] }
-                    let content = json_response
-                        .get("content")
-                        .and_then(|c| c.as_array())
-                        .and_then(|arr| arr.get(0))
-                        .and_then(|item| item.get("text"))
-                        .and_then(|t| t.as_str())
-                        .ok_or_else(|| AiProviderError::InvalidResponse("Missing content in response".to_string()))?;
-                    
-                    // Parse the JSON content
-                    serde_json::from_str(content)
-                        .map_err(|e| AiProviderError::InvalidResponse(format!("Failed to parse JSON: {}", e)))
+                    anthropic_response_json(&json_response)
                 }
             },
             retry_config,
@@ -131,6 +259,7 @@ impl CloudAiProvider {
         let client = &self.client;
         let api_key = &self.api_key;
         let model_name = &self.model_name;
+        let sampling = self.sampling;
         
         // Use retry logic for the API call
         let retry_config = RetryConfig {
@@ -150,27 +279,13 @@ impl CloudAiProvider {
                 let user_prompt = user_prompt.to_string();
                 
                 async move {
+                    // Held until this attempt finishes, so backoff waits don't block other calls
+                    let _permit = acquire_request_permit().await;
                     let response = client
                         .post(&url)
                         .header("Authorization", format!("Bearer {}", api_key))
                         .header("Content-Type", "application/json")
-                        .json(&json!({
-                            "model": model_name,
-                            "messages": [
-                                {
-                                    "role": "system",
-                                    "content": system_prompt
-                                },
-                                {
-                                    "role": "user",
-                                    "content": user_prompt
-                                }
-                            ],
-                            "temperature": 0.3, // Lower temperature for more deterministic output
-                            "response_format": {
-                                "type": "json_object"
-                            }
-                        }))
+                        .json(&openai_request_body(&model_name, &system_prompt, &user_prompt, &sampling))
                         .send()
                         .await
                         .map_err(|e| AiProviderError::NetworkError(e.to_string()))?;
@@ -192,6 +307,9 @@ impl CloudAiProvider {
                         .json()
                         .await
                         .map_err(|e| AiProviderError::InvalidResponse(e.to_string()))?;
+                    if let Some(usage) = token_usage_from_response(&json_response) {
+                        report_token_usage(usage);
+                    }
                     
                     // Extract content from OpenAI response format
                     let content = json_response
@@ -211,8 +329,64 @@ impl CloudAiProvider {
         )
         .await
     }
+
+    async fn call_openai_embeddings(&self, text: &str) -> Result<Vec<f32>, AiProviderError> {
+        self.rate_limiter.acquire().await;
+        let client = &self.client;
+        let api_key = &self.api_key;
+
+        let retry_config = RetryConfig {
+            max_retries: 3,
+            initial_delay_ms: 500,
+            max_delay_ms: 10000,
+            backoff_multiplier: 2.0,
+        };
+
+        retry_with_backoff(
+            || {
+                let client = client.clone();
+                let api_key = api_key.clone();
+                let text = text.to_string();
+
+                async move {
+                    let _permit = acquire_request_permit().await;
+                    let response = client
+                        .post("https://api.openai.com/v1/embeddings")
+                        .header("Authorization", format!("Bearer {}", api_key))
+                        .header("Content-Type", "application/json")
+                        .json(&json!({ "model": OPENAI_EMBEDDING_MODEL, "input": text }))
+                        .send()
+                        .await
+                        .map_err(|e| AiProviderError::NetworkError(e.to_string()))?;
+
+                    if response.status() == 401 {
+                        return Err(AiProviderError::InvalidApiKey);
+                    }
+
+                    if response.status() == 429 {
+                        return Err(AiProviderError::RateLimitExceeded);
+                    }
+
+                    if !response.status().is_success() {
+                        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+                        return Err(AiProviderError::NetworkError(format!("API error: {}", error_text)));
+                    }
+
+                    let json_response: Value = response
+                        .json()
+                        .await
+                        .map_err(|e| AiProviderError::InvalidResponse(e.to_string()))?;
+                    openai_embedding(&json_response)
+                        .filter(|embedding| !embedding.is_empty())
+                        .ok_or_else(|| AiProviderError::InvalidResponse("Missing embedding in response".to_string()))
+                }
+            },
+            retry_config,
+        )
+        .await
+    }
     
-    fn build_resume_system_prompt() -> String {
+    pub(crate) fn build_resume_system_prompt() -> String {
         "You are a resume writing assistant. Your task is to help reorganize and improve existing resume content. 
 CRITICAL RULES:
 - NEVER invent skills, companies, dates, or experiences that don't exist in the input
@@ -222,7 +396,7 @@ CRITICAL RULES:
 - Focus on achievements and impact".to_string()
     }
     
-    fn build_cover_letter_system_prompt() -> String {
+    pub(crate) fn build_cover_letter_system_prompt() -> String {
         "You are a cover letter writing assistant. Your task is to write a professional cover letter based on the user's profile and job description.
 CRITICAL RULES:
 - NEVER invent skills, companies, dates, or experiences
@@ -231,7 +405,7 @@ CRITICAL RULES:
 - Be professional and tailored to the specific job".to_string()
     }
     
-    fn build_skill_suggestions_system_prompt() -> String {
+    pub(crate) fn build_skill_suggestions_system_prompt() -> String {
         "You are a career advisor. Your task is to analyze skill gaps between the user's current skills and job requirements.
 CRITICAL RULES:
 - Identify missing skills that are mentioned in the job description
@@ -240,13 +414,14 @@ CRITICAL RULES:
 - Output MUST be valid JSON matching the SkillSuggestions schema".to_string()
     }
     
-    fn build_job_parsing_system_prompt() -> String {
+    pub(crate) fn build_job_parsing_system_prompt() -> String {
         "You are a job description parser. Your task is to extract structured information from job postings.
 CRITICAL RULES:
 - Extract only information that is explicitly stated in the job description
 - NEVER invent or infer skills, responsibilities, or requirements that aren't mentioned
 - Output MUST be valid JSON matching the ParsedJob schema
-- Be thorough but accurate - only extract what you can clearly identify".to_string()
+- Be thorough but accurate - only extract what you can clearly identify
+- For compensation, set salaryMin and salaryMax to the stated annual range as plain numbers (e.g. 120000), using the same number for both when only one figure is given, and salaryCurrency to its ISO 4217 code. Leave all three null when no salary is stated".to_string()
     }
 }
 
@@ -256,9 +431,10 @@ impl AiProvider for CloudAiProvider {
     async fn generate_resume_suggestions(&self, input: ResumeInput) -> Result<ResumeSuggestions, AiProviderError> {
         let system_prompt = Self::build_resume_system_prompt();
         let user_prompt = format!(
-            "Profile data:\n{}\n\nJob description:\n{}\n\nGenerate resume suggestions in JSON format.",
+            "Profile data:\n{}\n\nJob description:\n{}\n\nGenerate resume suggestions in JSON format.{}",
             serde_json::to_string_pretty(&input.profile_data).unwrap_or_default(),
-            input.job_description
+            input.job_description,
+            output_language_instruction(input.output_language)
         );
         
         let json_response = match self.provider {
@@ -277,10 +453,11 @@ impl AiProvider for CloudAiProvider {
     async fn generate_cover_letter(&self, input: CoverLetterInput) -> Result<CoverLetter, AiProviderError> {
         let system_prompt = Self::build_cover_letter_system_prompt();
         let user_prompt = format!(
-            "Profile data:\n{}\n\nJob description:\n{}\n\nCompany: {}\n\nGenerate a cover letter in JSON format.",
+            "Profile data:\n{}\n\nJob description:\n{}\n\nCompany: {}\n\nGenerate a cover letter in JSON format.{}",
             serde_json::to_string_pretty(&input.profile_data).unwrap_or_default(),
             input.job_description,
-            input.company_name.as_deref().unwrap_or("the company")
+            input.company_name.as_deref().unwrap_or("the company"),
+            output_language_instruction(input.output_language)
         );
         
         let json_response = match self.provider {
@@ -370,5 +547,94 @@ impl AiProvider for CloudAiProvider {
                 .map_err(|e| AiProviderError::InvalidResponse(format!("Failed to serialize response: {}", e)))
         }
     }
+
+    async fn embed(&self, text: &str) -> Result<Vec<f32>, AiProviderError> {
+        match self.provider {
+            CloudProvider::OpenAI => self.call_openai_embeddings(text).await,
+            // Anthropic has no embeddings endpoint
+            CloudProvider::Anthropic => Err(AiProviderError::Unknown(
+                "Anthropic does not provide embeddings".to_string(),
+            )),
+        }
+    }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::settings::AiSettings;
+
+    #[test]
+    fn test_openai_embedding_response() {
+        let response = json!({"data": [{"object": "embedding", "index": 0, "embedding": [0.25, -0.5, 1.0]}]});
+        assert_eq!(openai_embedding(&response), Some(vec![0.25, -0.5, 1.0]));
+        assert_eq!(openai_embedding(&json!({"data": []})), None);
+    }
+
+    #[test]
+    fn test_request_bodies_use_settings_sampling() {
+        let settings = AiSettings {
+            temperature: Some(0.0),
+            seed: Some(42),
+            ..AiSettings::default()
+        };
+
+        let openai = openai_request_body("gpt-4o-mini", "system", "user", &settings.sampling());
+        assert_eq!(openai["temperature"], json!(0.0));
+        assert_eq!(openai["seed"], json!(42));
+
+        let anthropic = anthropic_request_body("claude-3-5-sonnet-20241022", "system", "user", &settings.sampling());
+        assert_eq!(anthropic["temperature"], json!(0.0));
+        assert!(anthropic.get("seed").is_none());
+    }
+
+    #[test]
+    fn test_request_bodies_use_max_tokens() {
+        let sampling = AiSettings { max_tokens: Some(800), ..AiSettings::default() }.sampling();
+        assert_eq!(openai_request_body("gpt-4o-mini", "system", "user", &sampling)["max_tokens"], json!(800));
+        assert_eq!(anthropic_request_body("claude", "system", "user", &sampling)["max_tokens"], json!(800));
+        let unset = AiSettings::default().sampling();
+        assert!(openai_request_body("gpt-4o-mini", "system", "user", &unset).get("max_tokens").is_none());
+        assert_eq!(anthropic_request_body("claude", "system", "user", &unset)["max_tokens"], json!(4096));
+    }
+
+    #[test]
+    fn test_request_bodies_default_unset_sampling() {
+        let sampling = AiSettings::default().sampling();
+        let openai = openai_request_body("gpt-4o-mini", "system", "user", &sampling);
+        assert_eq!(openai["temperature"], json!(DEFAULT_CLOUD_TEMPERATURE));
+        assert!(openai.get("seed").is_none());
+        let anthropic = anthropic_request_body("claude", "system", "user", &sampling);
+        assert_eq!(anthropic["temperature"], json!(DEFAULT_CLOUD_TEMPERATURE));
+    }
+
+    #[test]
+    fn test_anthropic_error_body_mapping() {
+        let body = |kind: &str| format!(r#"{{"type":"error","error":{{"type":"{}","message":"details"}}}}"#, kind);
+
+        assert!(matches!(anthropic_error(401, &body("authentication_error")), AiProviderError::InvalidApiKey));
+        assert!(matches!(anthropic_error(429, &body("rate_limit_error")), AiProviderError::RateLimitExceeded));
+        assert!(matches!(anthropic_error(404, &body("not_found_error")), AiProviderError::ModelNotFound));
+        match anthropic_error(529, &body("overloaded_error")) {
+            AiProviderError::NetworkError(msg) => assert!(msg.contains("details")),
+            other => panic!("unexpected error: {:?}", other),
+        }
+        match anthropic_error(400, &body("invalid_request_error")) {
+            AiProviderError::Unknown(msg) => assert!(msg.contains("details")),
+            other => panic!("unexpected error: {:?}", other),
+        }
+        // Bodies that aren't Anthropic-shaped fall back to the status code
+        assert!(matches!(anthropic_error(429, "slow down"), AiProviderError::RateLimitExceeded));
+    }
+
+    #[test]
+    fn test_anthropic_response_json_strips_code_fence() {
+        let response = json!({
+            "content": [
+                {"type": "text", "text": "```json\n{\"title\": \"Engineer\"}\n```"}
+            ]
+        });
+        assert_eq!(anthropic_response_json(&response).unwrap(), json!({"title": "Engineer"}));
+        assert!(anthropic_response_json(&json!({"content": []})).is_err());
+    }
+}
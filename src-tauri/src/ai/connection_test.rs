@@ -0,0 +1,112 @@
+//! Structured AI connection test used by the settings screen
+
+use crate::ai::errors::AiProviderError;
+use crate::ai::provider::AiProvider;
+use crate::ai::types::SkillSuggestionsInput;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// How long the connection test waits before reporting a timeout
+pub const DEFAULT_CONNECTION_TEST_TIMEOUT_SECS: u64 = 30;
+
+/// Why a connection test failed, so the UI can suggest a specific fix
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionErrorKind {
+    NotConfigured,
+    NetworkError,
+    Timeout,
+    InvalidApiKey,
+    ModelNotFound,
+    RateLimitExceeded,
+    InvalidResponse,
+    ValidationError,
+    Unknown,
+}
+
+impl From<&AiProviderError> for ConnectionErrorKind {
+    fn from(error: &AiProviderError) -> Self {
+        match error {
+            AiProviderError::NetworkError(_) => ConnectionErrorKind::NetworkError,
+            AiProviderError::InvalidResponse(_) => ConnectionErrorKind::InvalidResponse,
+            AiProviderError::RateLimitExceeded => ConnectionErrorKind::RateLimitExceeded,
+            AiProviderError::InvalidApiKey => ConnectionErrorKind::InvalidApiKey,
+            AiProviderError::ModelNotFound => ConnectionErrorKind::ModelNotFound,
+            AiProviderError::ValidationError(_) => ConnectionErrorKind::ValidationError,
+            AiProviderError::Unknown(_) => ConnectionErrorKind::Unknown,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectionTestResult {
+    pub ok: bool,
+    pub error_kind: Option<ConnectionErrorKind>,
+    pub latency_ms: Option<u64>,
+    pub model: Option<String>,
+    /// Human-readable summary (same text as `Display`)
+    pub message: String,
+}
+
+impl ConnectionTestResult {
+    pub fn success(latency_ms: u64, model: Option<String>) -> Self {
+        let mut result = Self {
+            ok: true,
+            error_kind: None,
+            latency_ms: Some(latency_ms),
+            model,
+            message: String::new(),
+        };
+        result.message = result.to_string();
+        result
+    }
+
+    pub fn failure(kind: ConnectionErrorKind, detail: &str, latency_ms: Option<u64>, model: Option<String>) -> Self {
+        Self {
+            ok: false,
+            error_kind: Some(kind),
+            latency_ms,
+            model,
+            message: format!("Connection test failed: {}", detail),
+        }
+    }
+}
+
+impl fmt::Display for ConnectionTestResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.ok {
+            write!(f, "Connection successful!")
+        } else {
+            write!(f, "{}", self.message)
+        }
+    }
+}
+
+/// Send a small skill suggestions request and classify the outcome
+pub async fn run_connection_test(
+    provider: &dyn AiProvider,
+    model: Option<String>,
+    timeout: Duration,
+) -> ConnectionTestResult {
+    let test_input = SkillSuggestionsInput {
+        current_skills: vec!["Rust".to_string(), "TypeScript".to_string()],
+        job_description: "Looking for a software engineer with Python and React experience.".to_string(),
+        experience: None,
+    };
+
+    let started = Instant::now();
+    let outcome = tokio::time::timeout(timeout, provider.generate_skill_suggestions(test_input)).await;
+    let latency_ms = started.elapsed().as_millis() as u64;
+
+    match outcome {
+        Ok(Ok(_)) => ConnectionTestResult::success(latency_ms, model),
+        Ok(Err(e)) => ConnectionTestResult::failure((&e).into(), &e.to_string(), Some(latency_ms), model),
+        Err(_) => ConnectionTestResult::failure(
+            ConnectionErrorKind::Timeout,
+            &format!("no response within {} seconds", timeout.as_secs()),
+            Some(latency_ms),
+            model,
+        ),
+    }
+}
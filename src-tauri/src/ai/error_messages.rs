@@ -1,4 +1,9 @@
 use crate::ai::errors::AiProviderError;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Seconds the UI should wait before retrying after a rate limit
+const RATE_LIMIT_RETRY_AFTER_SECONDS: u64 = 60;
 
 /// User-friendly error message with recovery suggestions
 #[derive(Debug, Clone)]
@@ -145,6 +150,127 @@ pub fn to_user_friendly_error(error: &AiProviderError) -> UserFriendlyError {
     }
 }
 
+/// The fix the UI can offer next to an error
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecoveryAction {
+    OpenSettings,
+    Retry,
+    CheckNetwork,
+}
+
+impl RecoveryAction {
+    /// Button text for the action
+    pub fn label(&self) -> &'static str {
+        match self {
+            RecoveryAction::OpenSettings => "Open settings",
+            RecoveryAction::Retry => "Retry",
+            RecoveryAction::CheckNetwork => "Check network",
+        }
+    }
+}
+
+/// Error returned by the generation commands, so the UI can render a fix button
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserFacingError {
+    pub message: String,
+    pub recovery_action: Option<RecoveryAction>,
+    /// `recovery_action`'s button text
+    pub recovery_label: Option<String>,
+    /// Whether trying the same request again may succeed
+    pub retryable: bool,
+    /// How long to wait before retrying, when the provider asked us to slow down
+    pub retry_after_seconds: Option<u64>,
+}
+
+impl UserFacingError {
+    fn new(message: String, recovery_action: Option<RecoveryAction>, retryable: bool) -> Self {
+        Self {
+            message,
+            recovery_action,
+            recovery_label: recovery_action.map(|action| action.label().to_string()),
+            retryable,
+            retry_after_seconds: None,
+        }
+    }
+
+    /// Wrap an error message from outside the provider, recognizing provider failures
+    /// that were already turned into strings
+    pub fn from_message(message: &str) -> Self {
+        match classify_error_message(message) {
+            Some(error) => to_user_facing_error(&error),
+            None => Self::new(message.to_string(), None, false),
+        }
+    }
+}
+
+impl fmt::Display for UserFacingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl From<String> for UserFacingError {
+    fn from(message: String) -> Self {
+        Self::from_message(&message)
+    }
+}
+
+/// Whether a message reports HTTP status `code`, e.g. "status 401", "HTTP 429" or
+/// "status code: 429", rather than just containing the digits somewhere
+fn mentions_http_status(message: &str, code: u16) -> bool {
+    let pattern = format!(r"(?i)\b(?:status(?:\s+code)?|http(?:/[\d.]+)?)\s*[:=]?\s*{}\b", code);
+    regex::Regex::new(&pattern).map(|re| re.is_match(message)).unwrap_or(false)
+}
+
+/// Recover the provider error behind a stringified failure, if it looks like one
+pub fn classify_error_message(message: &str) -> Option<AiProviderError> {
+    if message.contains("Invalid API key") || message.contains("InvalidApiKey") || mentions_http_status(message, 401) {
+        Some(AiProviderError::InvalidApiKey)
+    } else if message.contains("Rate limit") || message.contains("RateLimitExceeded") || mentions_http_status(message, 429) {
+        Some(AiProviderError::RateLimitExceeded)
+    } else if message.contains("Network error") || message.contains("NetworkError") {
+        Some(AiProviderError::NetworkError(message.to_string()))
+    } else if message.contains("Invalid response") || message.contains("InvalidResponse") {
+        Some(AiProviderError::InvalidResponse(message.to_string()))
+    } else if message.contains("Model not found") {
+        Some(AiProviderError::ModelNotFound)
+    } else if message.contains("not configured") || message.contains("not set up") {
+        Some(AiProviderError::Unknown(message.to_string()))
+    } else {
+        None
+    }
+}
+
+/// Map an AI provider error to a message plus the action that fixes it
+pub fn to_user_facing_error(error: &AiProviderError) -> UserFacingError {
+    let friendly = to_user_friendly_error(error);
+    match error {
+        AiProviderError::InvalidApiKey | AiProviderError::ModelNotFound => {
+            UserFacingError::new(friendly.message, Some(RecoveryAction::OpenSettings), false)
+        }
+        AiProviderError::RateLimitExceeded => UserFacingError {
+            retry_after_seconds: Some(RATE_LIMIT_RETRY_AFTER_SECONDS),
+            ..UserFacingError::new(
+                format!("{}. Wait a minute, then retry", friendly.message),
+                Some(RecoveryAction::Retry),
+                true,
+            )
+        },
+        AiProviderError::NetworkError(_) => {
+            UserFacingError::new(friendly.message, Some(RecoveryAction::CheckNetwork), true)
+        }
+        AiProviderError::InvalidResponse(_) | AiProviderError::ValidationError(_) => {
+            UserFacingError::new(friendly.message, Some(RecoveryAction::Retry), true)
+        }
+        AiProviderError::Unknown(_) if friendly.requires_action => {
+            UserFacingError::new(friendly.message, Some(RecoveryAction::OpenSettings), false)
+        }
+        AiProviderError::Unknown(_) => UserFacingError::new(friendly.message, Some(RecoveryAction::Retry), true),
+    }
+}
+
 /// Format error message for display in UI
 #[allow(dead_code)]
 pub fn format_error_for_ui(error: &AiProviderError) -> String {
@@ -200,6 +326,46 @@ mod tests {
         assert!(friendly.recoverable);
     }
 
+    #[test]
+    fn test_invalid_api_key_opens_settings() {
+        let facing = to_user_facing_error(&AiProviderError::InvalidApiKey);
+
+        assert_eq!(facing.recovery_action, Some(RecoveryAction::OpenSettings));
+        assert_eq!(facing.recovery_label.as_deref(), Some("Open settings"));
+        assert!(!facing.retryable);
+    }
+
+    #[test]
+    fn test_rate_limit_is_retryable_with_wait_hint() {
+        let facing = to_user_facing_error(&AiProviderError::RateLimitExceeded);
+
+        assert_eq!(facing.recovery_action, Some(RecoveryAction::Retry));
+        assert!(facing.retryable);
+        assert_eq!(facing.retry_after_seconds, Some(RATE_LIMIT_RETRY_AFTER_SECONDS));
+        assert!(facing.message.contains("Wait a minute"));
+    }
+
+    #[test]
+    fn test_user_facing_error_from_message() {
+        let network = UserFacingError::from_message("AI generation failed: Network error: connection refused");
+        assert_eq!(network.recovery_action, Some(RecoveryAction::CheckNetwork));
+        assert!(network.retryable);
+
+        let rate_limited = UserFacingError::from_message("Ollama request failed: HTTP 429 Too Many Requests");
+        assert_eq!(rate_limited.recovery_action, Some(RecoveryAction::Retry));
+        let unauthorized = UserFacingError::from_message("API error: status code: 401");
+        assert_eq!(unauthorized.recovery_action, Some(RecoveryAction::OpenSettings));
+
+        // Bare numbers such as a 401(k) or a job id aren't status codes
+        assert!(classify_error_message("Failed to parse job 4291: missing 401(k) field").is_none());
+        assert!(classify_error_message("Application 429 not found").is_none());
+
+        let profile = UserFacingError::from_message("User profile not found. Please set up your profile first.");
+        assert_eq!(profile.message, "User profile not found. Please set up your profile first.");
+        assert_eq!(profile.recovery_action, None);
+        assert!(!profile.retryable);
+    }
+
     #[test]
     fn test_unknown_error_with_not_configured() {
         let error = AiProviderError::Unknown("AI provider not configured".to_string());
@@ -0,0 +1,158 @@
+use crate::ai::provider::AiProvider;
+use crate::ai::errors::AiProviderError;
+use crate::ai::retry::is_retryable_error;
+use crate::ai::types::*;
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// Fallback AI Provider
+/// Tries an ordered list of providers, moving to the next one when a provider fails
+/// with a transient error (rate limit or network). Other errors are returned as-is,
+/// since the next provider would most likely fail the same way.
+pub struct FallbackProvider {
+    providers: Vec<(String, Arc<dyn AiProvider>)>,
+}
+
+impl FallbackProvider {
+    /// Create a chain from `(name, provider)` pairs, tried in order; names are only used in logs
+    pub fn new(providers: Vec<(String, Arc<dyn AiProvider>)>) -> Self {
+        Self { providers }
+    }
+
+    async fn try_each<F, Fut, T>(&self, operation: &str, run: F) -> Result<T, AiProviderError>
+    where
+        F: Fn(Arc<dyn AiProvider>) -> Fut,
+        Fut: std::future::Future<Output = Result<T, AiProviderError>>,
+    {
+        let mut last_error = None;
+        for (index, (name, provider)) in self.providers.iter().enumerate() {
+            match run(provider.clone()).await {
+                Ok(result) => {
+                    log::info!("[FallbackProvider] {} served by {}", operation, name);
+                    return Ok(result);
+                }
+                Err(error) if is_retryable_error(&error) && index + 1 < self.providers.len() => {
+                    log::warn!("[FallbackProvider] {} failed on {}: {}. Trying the next provider.", operation, name, error);
+                    last_error = Some(error);
+                }
+                Err(error) => {
+                    log::error!("[FallbackProvider] {} failed on {}: {}", operation, name, error);
+                    return Err(error);
+                }
+            }
+        }
+        Err(last_error.unwrap_or_else(|| {
+            AiProviderError::Unknown(
+                "No AI provider available. Please configure at least one provider in Settings.".to_string(),
+            )
+        }))
+    }
+}
+
+#[async_trait]
+impl AiProvider for FallbackProvider {
+    async fn generate_resume_suggestions(&self, input: ResumeInput) -> Result<ResumeSuggestions, AiProviderError> {
+        self.try_each("generate_resume_suggestions", |provider| {
+            let input = input.clone();
+            async move { provider.generate_resume_suggestions(input).await }
+        })
+        .await
+    }
+
+    async fn generate_cover_letter(&self, input: CoverLetterInput) -> Result<CoverLetter, AiProviderError> {
+        self.try_each("generate_cover_letter", |provider| {
+            let input = input.clone();
+            async move { provider.generate_cover_letter(input).await }
+        })
+        .await
+    }
+
+    async fn generate_skill_suggestions(&self, input: SkillSuggestionsInput) -> Result<SkillSuggestions, AiProviderError> {
+        self.try_each("generate_skill_suggestions", |provider| {
+            let input = input.clone();
+            async move { provider.generate_skill_suggestions(input).await }
+        })
+        .await
+    }
+
+    async fn parse_job(&self, input: JobParsingInput) -> Result<ParsedJobOutput, AiProviderError> {
+        self.try_each("parse_job", |provider| {
+            let input = input.clone();
+            async move { provider.parse_job(input).await }
+        })
+        .await
+    }
+
+    async fn call_llm(&self, system_prompt: Option<&str>, user_prompt: &str) -> Result<String, AiProviderError> {
+        self.try_each("call_llm", |provider| {
+            let system_prompt = system_prompt.map(|s| s.to_string());
+            let user_prompt = user_prompt.to_string();
+            async move { provider.call_llm(system_prompt.as_deref(), &user_prompt).await }
+        })
+        .await
+    }
+
+    async fn embed(&self, text: &str) -> Result<Vec<f32>, AiProviderError> {
+        self.try_each("embed", |provider| {
+            let text = text.to_string();
+            async move { provider.embed(&text).await }
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::mock_provider::MockProvider;
+
+    fn chain(providers: &[(&str, &Arc<MockProvider>)]) -> FallbackProvider {
+        FallbackProvider::new(
+            providers
+                .iter()
+                .map(|(name, provider)| (name.to_string(), (*provider).clone() as Arc<dyn AiProvider>))
+                .collect(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_provider_falls_back() {
+        let cloud = Arc::new(MockProvider::new());
+        let local = Arc::new(MockProvider::new());
+        cloud.fail_next(AiProviderError::RateLimitExceeded, 1);
+        cloud.register_llm_responses(vec!["from cloud".to_string()]);
+        local.register_llm_responses(vec!["from local".to_string()]);
+        let provider = chain(&[("cloud", &cloud), ("local", &local)]);
+
+        assert_eq!(provider.call_llm(None, "hi").await.unwrap(), "from local");
+        // The cloud provider recovered, so it serves the next request again
+        assert_eq!(provider.call_llm(None, "hi").await.unwrap(), "from cloud");
+        assert_eq!(local.llm_call_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_permanent_errors_do_not_fall_back() {
+        let cloud = Arc::new(MockProvider::new());
+        let local = Arc::new(MockProvider::new());
+        cloud.fail_with(AiProviderError::InvalidApiKey);
+        let provider = chain(&[("cloud", &cloud), ("local", &local)]);
+
+        assert!(matches!(provider.call_llm(None, "hi").await, Err(AiProviderError::InvalidApiKey)));
+        assert_eq!(local.llm_call_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_last_error_returned_when_every_provider_fails() {
+        let cloud = Arc::new(MockProvider::new());
+        let local = Arc::new(MockProvider::new());
+        cloud.fail_with(AiProviderError::RateLimitExceeded);
+        local.fail_with(AiProviderError::NetworkError("offline".to_string()));
+        let provider = chain(&[("cloud", &cloud), ("local", &local)]);
+
+        let input = JobParsingInput {
+            job_description: "Engineer".to_string(),
+            job_meta: None,
+        };
+        assert!(matches!(provider.parse_job(input).await, Err(AiProviderError::NetworkError(_))));
+    }
+}
@@ -2,6 +2,7 @@ use crate::ai::provider::AiProvider;
 use crate::ai::errors::AiProviderError;
 use crate::ai::types::*;
 use crate::ai::local_provider::LocalProvider;
+use crate::ai::llama_wrapper::LlamaSamplingParams;
 use crate::ai::cloud_provider::CloudAiProvider;
 use crate::ai::settings::{CloudProvider, load_ai_settings};
 use async_trait::async_trait;
@@ -24,6 +25,7 @@ impl HybridProvider {
     pub fn new(prefer_cloud: bool) -> Result<Self, String> {
         let settings = load_ai_settings()
             .map_err(|e| format!("Failed to load AI settings: {}", e))?;
+        let sampling = settings.sampling();
         
         // Initialize cloud provider if configured
         let cloud_provider = if let Some(api_key) = &settings.api_key {
@@ -32,7 +34,9 @@ impl HybridProvider {
                 .unwrap_or_else(|| "gpt-4o-mini".to_string());
             
             log::info!("[HybridProvider] Cloud provider configured: {:?}, model: {}", provider, model_name);
-            Some(Arc::new(CloudAiProvider::new(provider, api_key.clone(), model_name)))
+            Some(Arc::new(
+                CloudAiProvider::new(provider, api_key.clone(), model_name).with_sampling(sampling),
+            ))
         } else {
             log::info!("[HybridProvider] Cloud provider not configured (no API key)");
             None
@@ -43,7 +47,10 @@ impl HybridProvider {
             let model_path = PathBuf::from(model_path_str);
             if model_path.exists() {
                 log::info!("[HybridProvider] Local provider configured: {}", model_path.display());
-                Some(Arc::new(LocalProvider::with_model_path(model_path)))
+                Some(Arc::new(
+                    LocalProvider::with_model_path(model_path)
+                        .with_sampling(LlamaSamplingParams::from_sampling(&sampling)),
+                ))
             } else {
                 log::warn!("[HybridProvider] Local model path configured but file not found: {}", model_path.display());
                 None
@@ -68,6 +75,12 @@ impl HybridProvider {
         })
     }
     
+    /// The model `embed` uses. Local models can't embed, so embeddings only ever
+    /// come from the cloud provider
+    pub fn embedding_model(&self) -> Option<&'static str> {
+        self.cloud_provider.as_ref().and_then(|provider| provider.embedding_model())
+    }
+
     /// Try an operation with fallback logic
     /// 
     /// Attempts the operation with the preferred provider first,
@@ -206,6 +219,14 @@ impl AiProvider for HybridProvider {
         })
         .await
     }
+
+    async fn embed(&self, text: &str) -> Result<Vec<f32>, AiProviderError> {
+        self.try_with_fallback(|provider| {
+            let text = text.to_string();
+            async move { provider.embed(&text).await }
+        })
+        .await
+    }
 }
 
 
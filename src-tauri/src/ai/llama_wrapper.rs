@@ -2,6 +2,7 @@
 //! Provides a simple async interface for GGUF model inference
 
 use crate::ai::errors::AiProviderError;
+use crate::ai::settings::SamplingParams;
 use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::Mutex;
@@ -19,8 +20,46 @@ use llama_cpp_sys_3::{
     llama_batch_get_one, llama_kv_cache_clear,
     llama_token_data, llama_token_data_array,
     llama_token_eos,
+    llama_sample_temp, llama_sample_token, llama_set_rng_seed,
 };
 
+/// llama.cpp's "pick a random seed" value
+const LLAMA_RANDOM_SEED: u32 = u32::MAX;
+
+/// Sampling settings for local inference.
+///
+/// Without a temperature (or with 0) decoding stays greedy, which is already
+/// deterministic. With one, tokens are sampled from an RNG seeded with `seed`, so the
+/// same seed and prompt give the same output.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LlamaSamplingParams {
+    pub temperature: Option<f32>,
+    pub seed: u32,
+    /// Overrides the per-operation token limit when set
+    pub max_tokens: Option<usize>,
+}
+
+impl Default for LlamaSamplingParams {
+    fn default() -> Self {
+        Self {
+            temperature: None,
+            seed: LLAMA_RANDOM_SEED,
+            max_tokens: None,
+        }
+    }
+}
+
+impl LlamaSamplingParams {
+    pub fn from_sampling(sampling: &SamplingParams) -> Self {
+        Self {
+            temperature: sampling.temperature.filter(|t| *t > 0.0),
+            // llama.cpp seeds are 32-bit; keep the low bits of larger seeds
+            seed: sampling.seed.map(|s| (s & u64::from(u32::MAX)) as u32).unwrap_or(LLAMA_RANDOM_SEED),
+            max_tokens: sampling.max_tokens.map(|m| m as usize),
+        }
+    }
+}
+
 /// Wrapper for llama.cpp model and context
 /// Handles model loading and inference in an async-friendly way
 /// 
@@ -132,6 +171,16 @@ impl LlamaModel {
     /// Generate text from a prompt
     /// Returns the generated text (which should contain JSON)
     pub async fn generate(&self, prompt: &str, max_tokens: usize) -> Result<String, AiProviderError> {
+        self.generate_with_sampling(prompt, max_tokens, LlamaSamplingParams::default()).await
+    }
+
+    /// Generate text from a prompt using the given temperature and seed
+    pub async fn generate_with_sampling(
+        &self,
+        prompt: &str,
+        max_tokens: usize,
+        sampling: LlamaSamplingParams,
+    ) -> Result<String, AiProviderError> {
         // Acquire lock to serialize inference (llama.cpp contexts are not thread-safe)
         let _lock = self._inference_lock.lock().await;
         
@@ -153,6 +202,9 @@ impl LlamaModel {
             unsafe {
                 // Clear KV cache for fresh inference
                 llama_kv_cache_clear(ctx);
+                if sampling.temperature.is_some() {
+                    llama_set_rng_seed(ctx, sampling.seed);
+                }
 
                 // Convert prompt to C string
                 let c_prompt = CString::new(prompt.as_str())
@@ -320,8 +372,14 @@ impl LlamaModel {
                         sorted: false,
                     };
 
-                    // Sample next token (greedy for deterministic JSON)
-                    let next_token = llama_sample_token_greedy(ctx, &mut candidates_array);
+                    // Greedy unless a temperature is configured, for deterministic JSON
+                    let next_token = match sampling.temperature {
+                        Some(temperature) => {
+                            llama_sample_temp(ctx, &mut candidates_array, temperature);
+                            llama_sample_token(ctx, &mut candidates_array)
+                        }
+                        None => llama_sample_token_greedy(ctx, &mut candidates_array),
+                    };
                     log::debug!("[llama_wrapper] Generated token {} at position {}", next_token, current_pos);
 
                     // Check for EOS token
@@ -633,3 +691,26 @@ pub async fn get_or_load_model(
     
     Ok(model_arc)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::settings::AiSettings;
+
+    #[test]
+    fn test_llama_sampling_from_settings() {
+        let settings = AiSettings {
+            temperature: Some(0.7),
+            seed: Some(42),
+            ..AiSettings::default()
+        };
+        let params = LlamaSamplingParams::from_sampling(&settings.sampling());
+        assert_eq!(params.temperature, Some(0.7));
+        assert_eq!(params.seed, 42);
+
+        // Unset or zero temperature keeps greedy decoding
+        assert_eq!(LlamaSamplingParams::from_sampling(&AiSettings::default().sampling()), LlamaSamplingParams::default());
+        let zero = AiSettings { temperature: Some(0.0), ..AiSettings::default() };
+        assert_eq!(LlamaSamplingParams::from_sampling(&zero.sampling()).temperature, None);
+    }
+}
@@ -1,7 +1,8 @@
 use crate::ai::provider::AiProvider;
 use crate::ai::types::*;
 use crate::ai::errors::AiProviderError;
-use crate::ai::llama_wrapper::{LlamaModel, SharedModel, get_or_load_model};
+use crate::language::output_language_instruction;
+use crate::ai::llama_wrapper::{LlamaModel, LlamaSamplingParams, SharedModel, get_or_load_model};
 use async_trait::async_trait;
 use std::sync::Arc;
 use tokio::sync::Mutex;
@@ -27,6 +28,7 @@ pub struct LocalProvider {
     // Lazy-loaded model instance (wrapped in Arc<Mutex> for thread safety)
     // This will be populated on first use
     model_cache: SharedModel,
+    sampling: LlamaSamplingParams,
 }
 
 impl LocalProvider {
@@ -34,6 +36,7 @@ impl LocalProvider {
         Self {
             model_path: None,
             model_cache: Arc::new(Mutex::new(None)),
+            sampling: LlamaSamplingParams::default(),
         }
     }
     
@@ -42,9 +45,16 @@ impl LocalProvider {
         Self {
             model_path: Some(path),
             model_cache: Arc::new(Mutex::new(None)),
+            sampling: LlamaSamplingParams::default(),
         }
     }
     
+    /// Sample with the given temperature and seed instead of greedy decoding
+    pub fn with_sampling(mut self, sampling: LlamaSamplingParams) -> Self {
+        self.sampling = sampling;
+        self
+    }
+    
     /// Load the model if not already loaded
     /// This is called lazily on first inference request
     async fn ensure_model_loaded(&self) -> Result<Arc<LlamaModel>, AiProviderError> {
@@ -95,8 +105,9 @@ impl LocalProvider {
         
         // Run inference
         // Use a reasonable max_tokens for JSON output (typically 500-1000 tokens is enough)
-        log::info!("[LocalProvider] Running inference (max_tokens=1000)...");
-        let response = match model.generate(&full_prompt, 1000).await {
+        let max_tokens = self.sampling.max_tokens.unwrap_or(1000);
+        log::info!("[LocalProvider] Running inference (max_tokens={})...", max_tokens);
+        let response = match model.generate_with_sampling(&full_prompt, max_tokens, self.sampling).await {
             Ok(r) => {
                 log::info!("[LocalProvider] Inference completed. Response length: {} chars", r.len());
                 r
@@ -194,7 +205,8 @@ CRITICAL RULES:
 - Extract only information that is explicitly stated in the job description
 - NEVER invent or infer skills, responsibilities, or requirements that aren't mentioned
 - Output MUST be valid JSON matching the ParsedJob schema
-- Be thorough but accurate - only extract what you can clearly identify".to_string()
+- Be thorough but accurate - only extract what you can clearly identify
+- For compensation, set salaryMin and salaryMax to the stated annual range as plain numbers (e.g. 120000), using the same number for both when only one figure is given, and salaryCurrency to its ISO 4217 code. Leave all three null when no salary is stated".to_string()
     }
 }
 
@@ -210,9 +222,10 @@ impl AiProvider for LocalProvider {
         log::info!("[LocalProvider] generate_resume_suggestions called");
         let system_prompt = Self::build_resume_system_prompt();
         let user_prompt = format!(
-            "Profile data:\n{}\n\nJob description:\n{}\n\nGenerate resume suggestions in JSON format.",
+            "Profile data:\n{}\n\nJob description:\n{}\n\nGenerate resume suggestions in JSON format.{}",
             serde_json::to_string_pretty(&input.profile_data).unwrap_or_default(),
-            input.job_description
+            input.job_description,
+            output_language_instruction(input.output_language)
         );
         
         let json_response = self.run_inference(&system_prompt, &user_prompt).await?;
@@ -231,10 +244,11 @@ impl AiProvider for LocalProvider {
     async fn generate_cover_letter(&self, input: CoverLetterInput) -> Result<CoverLetter, AiProviderError> {
         let system_prompt = Self::build_cover_letter_system_prompt();
         let user_prompt = format!(
-            "Profile data:\n{}\n\nJob description:\n{}\n\nCompany: {}\n\nGenerate a cover letter in JSON format.",
+            "Profile data:\n{}\n\nJob description:\n{}\n\nCompany: {}\n\nGenerate a cover letter in JSON format.{}",
             serde_json::to_string_pretty(&input.profile_data).unwrap_or_default(),
             input.job_description,
-            input.company_name.as_deref().unwrap_or("the company")
+            input.company_name.as_deref().unwrap_or("the company"),
+            output_language_instruction(input.output_language)
         );
         
         let json_response = self.run_inference(&system_prompt, &user_prompt).await?;
@@ -286,8 +300,9 @@ impl AiProvider for LocalProvider {
         // Run inference directly to get raw text response
         // Use 300 tokens max - enough for JSON but prevents long prose generation
         // This helps prevent the model from generating repetitive text
-        log::info!("[LocalProvider] Running inference with max_tokens=300");
-        let response = model.generate(&full_prompt, 300).await?;
+        let max_tokens = self.sampling.max_tokens.unwrap_or(300);
+        log::info!("[LocalProvider] Running inference with max_tokens={}", max_tokens);
+        let response = model.generate_with_sampling(&full_prompt, max_tokens, self.sampling).await?;
         
         // Extract JSON from response (handles markdown code blocks)
         let json_str = Self::extract_json_from_response(&response);
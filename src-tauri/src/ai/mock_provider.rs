@@ -2,7 +2,8 @@ use crate::ai::provider::AiProvider;
 use crate::ai::types::*;
 use crate::ai::errors::AiProviderError;
 use async_trait::async_trait;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 
 /// Mock AI Provider for testing
@@ -13,6 +14,12 @@ pub struct MockProvider {
     resume_responses: Arc<Mutex<HashMap<String, ResumeSuggestions>>>,
     cover_letter_responses: Arc<Mutex<HashMap<String, CoverLetter>>>,
     skill_suggestions_responses: Arc<Mutex<HashMap<String, SkillSuggestions>>>,
+    llm_calls: Arc<AtomicUsize>,
+    llm_responses: Arc<Mutex<VecDeque<String>>>,
+    received_requests: Arc<Mutex<Vec<String>>>,
+    failure: Arc<Mutex<Option<AiProviderError>>>,
+    /// Calls left to fail before `failure` clears; `None` fails forever
+    failures_left: Arc<Mutex<Option<usize>>>,
 }
 
 impl MockProvider {
@@ -22,9 +29,32 @@ impl MockProvider {
             resume_responses: Arc::new(Mutex::new(HashMap::new())),
             cover_letter_responses: Arc::new(Mutex::new(HashMap::new())),
             skill_suggestions_responses: Arc::new(Mutex::new(HashMap::new())),
+            llm_calls: Arc::new(AtomicUsize::new(0)),
+            llm_responses: Arc::new(Mutex::new(VecDeque::new())),
+            received_requests: Arc::new(Mutex::new(Vec::new())),
+            failure: Arc::new(Mutex::new(None)),
+            failures_left: Arc::new(Mutex::new(None)),
         }
     }
 
+    /// Number of `call_llm` calls made so far
+    #[allow(dead_code)]
+    pub fn llm_call_count(&self) -> usize {
+        self.llm_calls.load(Ordering::SeqCst)
+    }
+
+    /// Every cover letter input (as JSON) and `call_llm` prompt received, in order
+    #[allow(dead_code)]
+    pub fn received_requests(&self) -> Vec<String> {
+        self.received_requests.lock().unwrap().clone()
+    }
+
+    /// Queue raw `call_llm` responses, returned in order before any prompt-based response
+    #[allow(dead_code)]
+    pub fn register_llm_responses(&self, responses: Vec<String>) {
+        self.llm_responses.lock().unwrap().extend(responses);
+    }
+
     /// Register a response for job parsing
     /// The key should be a hash or identifier for the job description
     #[allow(dead_code)]
@@ -32,6 +62,38 @@ impl MockProvider {
         self.parse_job_responses.lock().unwrap().insert(key.to_string(), response);
     }
 
+    /// Make every subsequent call fail with this error
+    #[allow(dead_code)]
+    pub fn fail_with(&self, error: AiProviderError) {
+        *self.failure.lock().unwrap() = Some(error);
+        *self.failures_left.lock().unwrap() = None;
+    }
+
+    /// Make the next `times` calls fail with this error, then succeed again
+    #[allow(dead_code)]
+    pub fn fail_next(&self, error: AiProviderError, times: usize) {
+        *self.failure.lock().unwrap() = Some(error);
+        *self.failures_left.lock().unwrap() = Some(times);
+    }
+
+    fn check_failure(&self) -> Result<(), AiProviderError> {
+        let mut failure = self.failure.lock().unwrap();
+        let error = match failure.clone() {
+            Some(error) => error,
+            None => return Ok(()),
+        };
+        let mut failures_left = self.failures_left.lock().unwrap();
+        if let Some(left) = failures_left.as_mut() {
+            if *left == 0 {
+                *failure = None;
+                *failures_left = None;
+                return Ok(());
+            }
+            *left -= 1;
+        }
+        Err(error)
+    }
+
     /// Register a response for resume generation
     #[allow(dead_code)]
     pub fn register_resume(&self, key: &str, response: ResumeSuggestions) {
@@ -66,6 +128,7 @@ impl Default for MockProvider {
 #[async_trait]
 impl AiProvider for MockProvider {
     async fn generate_resume_suggestions(&self, input: ResumeInput) -> Result<ResumeSuggestions, AiProviderError> {
+        self.check_failure()?;
         let key = Self::job_key(&input.job_description);
         let responses = self.resume_responses.lock().unwrap();
         
@@ -83,6 +146,10 @@ impl AiProvider for MockProvider {
     }
 
     async fn generate_cover_letter(&self, input: CoverLetterInput) -> Result<CoverLetter, AiProviderError> {
+        self.check_failure()?;
+        if let Ok(json) = serde_json::to_string(&input) {
+            self.received_requests.lock().unwrap().push(json);
+        }
         let key = Self::job_key(&input.job_description);
         let responses = self.cover_letter_responses.lock().unwrap();
         
@@ -101,6 +168,7 @@ impl AiProvider for MockProvider {
     }
 
     async fn generate_skill_suggestions(&self, input: SkillSuggestionsInput) -> Result<SkillSuggestions, AiProviderError> {
+        self.check_failure()?;
         let key = Self::job_key(&input.job_description);
         let responses = self.skill_suggestions_responses.lock().unwrap();
         
@@ -117,6 +185,7 @@ impl AiProvider for MockProvider {
     }
 
     async fn parse_job(&self, input: JobParsingInput) -> Result<ParsedJobOutput, AiProviderError> {
+        self.check_failure()?;
         let key = Self::job_key(&input.job_description);
         let responses = self.parse_job_responses.lock().unwrap();
         
@@ -136,14 +205,31 @@ impl AiProvider for MockProvider {
                 remote_friendly: Some(false),
                 summary: None,
                 seniority_score: None,
+                salary_min: None,
+                salary_max: None,
+                salary_currency: None,
             })
         }
     }
     
     async fn call_llm(&self, _system_prompt: Option<&str>, user_prompt: &str) -> Result<String, AiProviderError> {
+        self.check_failure()?;
         // For mock provider, return a simple JSON response based on prompt content
         // This is mainly for testing
-        if user_prompt.contains("Extract professional profile") {
+        self.llm_calls.fetch_add(1, Ordering::SeqCst);
+        self.received_requests.lock().unwrap().push(user_prompt.to_string());
+        if let Some(response) = self.llm_responses.lock().unwrap().pop_front() {
+            return Ok(response);
+        }
+        if user_prompt.contains("in STAR format") {
+            // Echo each listed achievement back with a STAR prefix
+            let bullets: Vec<String> = user_prompt
+                .lines()
+                .filter_map(|line| line.strip_prefix("- "))
+                .map(|bullet| format!("STAR: {}", bullet))
+                .collect();
+            Ok(serde_json::json!({ "bullets": bullets }).to_string())
+        } else if user_prompt.contains("Extract professional profile") {
             // Return a mock profile extraction response
             Ok(r#"{
   "profile": {
@@ -161,6 +247,16 @@ impl AiProvider for MockProvider {
             Ok(r#"{"result": "mock response"}"#.to_string())
         }
     }
+
+    async fn embed(&self, text: &str) -> Result<Vec<f32>, AiProviderError> {
+        self.check_failure()?;
+        // Letter counts: texts with near-identical wording get near-identical vectors
+        let mut counts = vec![0.0f32; 26];
+        for c in text.chars().filter(|c| c.is_ascii_alphabetic()) {
+            counts[(c.to_ascii_lowercase() as u8 - b'a') as usize] += 1.0;
+        }
+        Ok(counts)
+    }
 }
 
 #[cfg(test)]
@@ -182,6 +278,9 @@ mod tests {
             remote_friendly: Some(true),
             summary: None,
             seniority_score: None,
+            salary_min: None,
+            salary_max: None,
+            salary_currency: None,
         };
         
         // Register with the key that will be generated from the job description
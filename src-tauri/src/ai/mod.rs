@@ -3,7 +3,9 @@ pub mod provider;
 pub mod errors;
 pub mod local_provider;
 pub mod cloud_provider;
+pub mod ollama_provider;
 pub mod hybrid_provider;
+pub mod fallback_provider;
 pub mod settings;
 pub mod resolver;
 pub mod retry;
@@ -12,6 +14,10 @@ pub mod error_messages;
 pub mod validation;
 pub mod llama_wrapper;
 pub mod key_rotation;
+pub mod token_budget;
+pub mod pii_scrubber;
+pub mod usage_log;
+pub mod connection_test;
 
 // Mock provider for testing - always available for integration tests
 pub mod mock_provider;
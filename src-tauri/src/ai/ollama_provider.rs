@@ -0,0 +1,347 @@
+use crate::ai::provider::AiProvider;
+use crate::ai::types::*;
+use crate::ai::errors::AiProviderError;
+use crate::language::output_language_instruction;
+use crate::ai::cloud_provider::CloudAiProvider;
+use crate::ai::rate_limiter::acquire_request_permit;
+use crate::ai::settings::SamplingParams;
+use crate::ai::usage_log::{report_token_usage, token_usage_from_response};
+use crate::ai::validation::{validate_parsed_job, validate_resume_suggestions, validate_cover_letter, validate_skill_suggestions};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use reqwest::Client;
+use std::time::Duration;
+
+/// Where Ollama listens unless configured otherwise
+pub const DEFAULT_OLLAMA_BASE_URL: &str = "http://localhost:11434";
+
+/// Ollama Provider
+/// Talks to an Ollama server over HTTP, so models already pulled into Ollama can be
+/// used without a second copy for the embedded llama wrapper
+pub struct OllamaProvider {
+    base_url: String,
+    model: String,
+    client: Client,
+    sampling: SamplingParams,
+}
+
+/// Request body for Ollama's `/api/chat`. `json_mode` asks Ollama to constrain the
+/// reply to valid JSON.
+pub fn ollama_chat_body(model: &str, system_prompt: &str, user_prompt: &str, json_mode: bool, sampling: &SamplingParams) -> Value {
+    let mut body = json!({
+        "model": model,
+        "messages": [
+            {
+                "role": "system",
+                "content": system_prompt
+            },
+            {
+                "role": "user",
+                "content": user_prompt
+            }
+        ],
+        "stream": false
+    });
+    if json_mode {
+        body["format"] = json!("json");
+    }
+    let mut options = serde_json::Map::new();
+    if let Some(temperature) = sampling.temperature {
+        options.insert("temperature".to_string(), json!(temperature));
+    }
+    if let Some(seed) = sampling.seed {
+        options.insert("seed".to_string(), json!(seed));
+    }
+    if let Some(max_tokens) = sampling.max_tokens {
+        options.insert("num_predict".to_string(), json!(max_tokens));
+    }
+    if !options.is_empty() {
+        body["options"] = Value::Object(options);
+    }
+    body
+}
+
+/// Whether a `/api/tags` response lists `model`. Tags without a version match
+/// their `:latest` entry, as they do in the Ollama CLI.
+pub fn ollama_model_listed(tags: &Value, model: &str) -> bool {
+    let wanted = if model.contains(':') {
+        model.to_string()
+    } else {
+        format!("{}:latest", model)
+    };
+    tags.get("models")
+        .and_then(|m| m.as_array())
+        .map(|models| {
+            models.iter().any(|entry| {
+                ["name", "model"]
+                    .iter()
+                    .filter_map(|key| entry.get(*key).and_then(|v| v.as_str()))
+                    .any(|name| name == model || name == wanted)
+            })
+        })
+        .unwrap_or(false)
+}
+
+/// The first vector in an `/api/embed` response (`{"embeddings": [[...]]}`)
+pub fn ollama_embedding(response: &Value) -> Option<Vec<f32>> {
+    response
+        .get("embeddings")
+        .and_then(|e| e.get(0))
+        .and_then(|e| e.as_array())
+        .map(|values| values.iter().filter_map(|v| v.as_f64()).map(|v| v as f32).collect())
+}
+
+/// Whether `base_url` points at this machine (localhost or a loopback address).
+/// Anything else, including URLs that don't parse, counts as remote.
+pub fn is_loopback_url(base_url: &str) -> bool {
+    let host = match reqwest::Url::parse(base_url).ok().and_then(|url| url.host_str().map(str::to_string)) {
+        Some(host) => host,
+        None => return false,
+    };
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    host.eq_ignore_ascii_case("localhost")
+        || host
+            .parse::<std::net::IpAddr>()
+            .map(|ip| ip.is_loopback())
+            .unwrap_or(false)
+}
+
+/// Map an Ollama error response (`{"error": "..."}`) onto `AiProviderError`
+pub fn ollama_error(status: u16, body: &str) -> AiProviderError {
+    let message = serde_json::from_str::<Value>(body)
+        .ok()
+        .and_then(|v| v.get("error").and_then(|e| e.as_str()).map(str::to_string))
+        .unwrap_or_else(|| body.to_string());
+    if status == 404 || message.contains("not found") {
+        AiProviderError::ModelNotFound
+    } else {
+        AiProviderError::NetworkError(format!("Ollama error: {}", message))
+    }
+}
+
+impl OllamaProvider {
+    pub fn new(base_url: Option<String>, model: String) -> Self {
+        let base_url = base_url
+            .map(|url| url.trim().trim_end_matches('/').to_string())
+            .filter(|url| !url.is_empty())
+            .unwrap_or_else(|| DEFAULT_OLLAMA_BASE_URL.to_string());
+        Self {
+            base_url,
+            model,
+            client: Client::new(),
+            sampling: SamplingParams::default(),
+        }
+    }
+
+    /// Use the given temperature and seed instead of the model's defaults
+    pub fn with_sampling(mut self, sampling: SamplingParams) -> Self {
+        self.sampling = sampling;
+        self
+    }
+
+    pub fn model_name(&self) -> &str {
+        &self.model
+    }
+
+    /// Whether prompts leave this machine, i.e. the server is not on a loopback address
+    pub fn is_remote(&self) -> bool {
+        !is_loopback_url(&self.base_url)
+    }
+
+    /// Check that the server answers `/api/tags` and has the configured model pulled
+    pub async fn is_available(&self) -> bool {
+        let response = self
+            .client
+            .get(format!("{}/api/tags", self.base_url))
+            .timeout(Duration::from_secs(5))
+            .send()
+            .await;
+        let response = match response {
+            Ok(response) if response.status().is_success() => response,
+            Ok(response) => {
+                log::warn!("[OllamaProvider] /api/tags returned {}", response.status());
+                return false;
+            }
+            Err(e) => {
+                log::warn!("[OllamaProvider] Ollama not reachable at {}: {}", self.base_url, e);
+                return false;
+            }
+        };
+        match response.json::<Value>().await {
+            Ok(tags) => ollama_model_listed(&tags, &self.model),
+            Err(_) => false,
+        }
+    }
+
+    /// Send one chat request and return the assistant's reply text
+    async fn chat(&self, system_prompt: &str, user_prompt: &str, json_mode: bool) -> Result<String, AiProviderError> {
+        let _permit = acquire_request_permit().await;
+        let response = self
+            .client
+            .post(format!("{}/api/chat", self.base_url))
+            .json(&ollama_chat_body(&self.model, system_prompt, user_prompt, json_mode, &self.sampling))
+            .send()
+            .await
+            .map_err(|e| AiProviderError::NetworkError(format!("Could not reach Ollama at {}: {}", self.base_url, e)))?;
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(ollama_error(status, &error_text));
+        }
+
+        let json_response: Value = response
+            .json()
+            .await
+            .map_err(|e| AiProviderError::InvalidResponse(e.to_string()))?;
+        if let Some(usage) = token_usage_from_response(&json_response) {
+            report_token_usage(usage);
+        }
+
+        // Ollama returns: { "message": { "role": "assistant", "content": "..." }, "done": true }
+        json_response
+            .get("message")
+            .and_then(|m| m.get("content"))
+            .and_then(|c| c.as_str())
+            .map(str::to_string)
+            .ok_or_else(|| AiProviderError::InvalidResponse("Missing message content in response".to_string()))
+    }
+
+    async fn chat_json(&self, system_prompt: &str, user_prompt: &str) -> Result<Value, AiProviderError> {
+        let content = self.chat(system_prompt, user_prompt, true).await?;
+        serde_json::from_str(&content)
+            .map_err(|e| AiProviderError::InvalidResponse(format!("Failed to parse JSON: {}", e)))
+    }
+}
+
+#[async_trait]
+impl AiProvider for OllamaProvider {
+    async fn generate_resume_suggestions(&self, input: ResumeInput) -> Result<ResumeSuggestions, AiProviderError> {
+        let user_prompt = format!(
+            "Profile data:\n{}\n\nJob description:\n{}\n\nGenerate resume suggestions in JSON format.{}",
+            serde_json::to_string_pretty(&input.profile_data).unwrap_or_default(),
+            input.job_description,
+            output_language_instruction(input.output_language)
+        );
+        let json_response = self.chat_json(&CloudAiProvider::build_resume_system_prompt(), &user_prompt).await?;
+        validate_resume_suggestions(&json_response)
+    }
+
+    async fn generate_cover_letter(&self, input: CoverLetterInput) -> Result<CoverLetter, AiProviderError> {
+        let user_prompt = format!(
+            "Profile data:\n{}\n\nJob description:\n{}\n\nCompany: {}\n\nGenerate a cover letter in JSON format.{}",
+            serde_json::to_string_pretty(&input.profile_data).unwrap_or_default(),
+            input.job_description,
+            input.company_name.as_deref().unwrap_or("the company"),
+            output_language_instruction(input.output_language)
+        );
+        let json_response = self.chat_json(&CloudAiProvider::build_cover_letter_system_prompt(), &user_prompt).await?;
+        validate_cover_letter(&json_response)
+    }
+
+    async fn generate_skill_suggestions(&self, input: SkillSuggestionsInput) -> Result<SkillSuggestions, AiProviderError> {
+        let user_prompt = format!(
+            "Current skills: {}\n\nJob description:\n{}\n\nGenerate skill suggestions in JSON format.",
+            input.current_skills.join(", "),
+            input.job_description
+        );
+        let json_response = self.chat_json(&CloudAiProvider::build_skill_suggestions_system_prompt(), &user_prompt).await?;
+        validate_skill_suggestions(&json_response)
+    }
+
+    async fn parse_job(&self, input: JobParsingInput) -> Result<ParsedJobOutput, AiProviderError> {
+        let user_prompt = format!(
+            "Job description:\n{}\n\nParse this job description and extract structured information in JSON format.",
+            input.job_description
+        );
+        let json_response = self.chat_json(&CloudAiProvider::build_job_parsing_system_prompt(), &user_prompt).await?;
+        validate_parsed_job(&json_response)
+    }
+
+    async fn call_llm(&self, system_prompt: Option<&str>, user_prompt: &str) -> Result<String, AiProviderError> {
+        let system = system_prompt.unwrap_or("You are a helpful AI assistant. Always respond with valid JSON when requested.");
+        self.chat(system, user_prompt, false).await
+    }
+
+    async fn embed(&self, text: &str) -> Result<Vec<f32>, AiProviderError> {
+        let _permit = acquire_request_permit().await;
+        let response = self
+            .client
+            .post(format!("{}/api/embed", self.base_url))
+            .json(&json!({ "model": self.model, "input": text }))
+            .send()
+            .await
+            .map_err(|e| AiProviderError::NetworkError(format!("Could not reach Ollama at {}: {}", self.base_url, e)))?;
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(ollama_error(status, &error_text));
+        }
+
+        let json_response: Value = response
+            .json()
+            .await
+            .map_err(|e| AiProviderError::InvalidResponse(e.to_string()))?;
+        ollama_embedding(&json_response)
+            .filter(|embedding| !embedding.is_empty())
+            .ok_or_else(|| AiProviderError::InvalidResponse("Missing embedding in response".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chat_body_options() {
+        let sampling = SamplingParams { temperature: Some(0.2), seed: Some(7), max_tokens: Some(512) };
+        let body = ollama_chat_body("llama3.1", "system", "user", true, &sampling);
+        assert_eq!(body["stream"], json!(false));
+        assert_eq!(body["format"], json!("json"));
+        assert_eq!(body["options"]["seed"], json!(7));
+        assert_eq!(body["options"]["num_predict"], json!(512));
+
+        let plain = ollama_chat_body("llama3.1", "system", "user", false, &SamplingParams::default());
+        assert!(plain.get("format").is_none());
+        assert!(plain.get("options").is_none());
+    }
+
+    #[test]
+    fn test_model_listed_in_tags() {
+        let tags = json!({"models": [{"name": "llama3.1:latest"}, {"name": "qwen2.5:7b"}]});
+        assert!(ollama_model_listed(&tags, "llama3.1"));
+        assert!(ollama_model_listed(&tags, "qwen2.5:7b"));
+        assert!(!ollama_model_listed(&tags, "qwen2.5"));
+        assert!(!ollama_model_listed(&json!({}), "llama3.1"));
+    }
+
+    #[test]
+    fn test_loopback_urls() {
+        assert!(is_loopback_url(DEFAULT_OLLAMA_BASE_URL));
+        assert!(is_loopback_url("http://127.0.0.1:11434"));
+        assert!(is_loopback_url("http://[::1]:11434"));
+        assert!(!is_loopback_url("http://gpu-box.lan:11434"));
+        assert!(!is_loopback_url("https://10.0.0.5"));
+        assert!(!is_loopback_url("not a url"));
+
+        assert!(!OllamaProvider::new(None, "llama3.1".to_string()).is_remote());
+        assert!(OllamaProvider::new(Some("http://gpu-box.lan:11434".to_string()), "llama3.1".to_string()).is_remote());
+    }
+
+    #[test]
+    fn test_embedding_response() {
+        let response = json!({"model": "llama3.1", "embeddings": [[0.5, 0.25], [1.0, 0.0]]});
+        assert_eq!(ollama_embedding(&response), Some(vec![0.5, 0.25]));
+        assert_eq!(ollama_embedding(&json!({})), None);
+    }
+
+    #[test]
+    fn test_error_mapping() {
+        assert!(matches!(
+            ollama_error(404, r#"{"error":"model 'mistral' not found, try pulling it first"}"#),
+            AiProviderError::ModelNotFound
+        ));
+        assert!(matches!(ollama_error(500, "boom"), AiProviderError::NetworkError(_)));
+    }
+}
@@ -0,0 +1,251 @@
+//! Optional PII scrubbing for prompts sent to cloud providers
+//!
+//! When enabled, email addresses, phone numbers and street addresses are replaced
+//! with placeholders (`[EMAIL_1]`, ...) before a request leaves the machine, and the
+//! placeholders in the response are swapped back for the real values locally.
+
+use crate::ai::errors::AiProviderError;
+use crate::ai::provider::AiProvider;
+use crate::ai::types::*;
+use crate::app_settings::{get_setting, set_setting};
+use crate::db::get_connection;
+use async_trait::async_trait;
+use regex::Regex;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use std::sync::{Arc, LazyLock};
+
+/// Settings key for scrubbing PII from cloud prompts
+pub const SCRUB_PII_SETTING: &str = "scrub_pii_before_cloud";
+
+/// Phone matches need at least this many digits, so years and IDs aren't redacted
+const MIN_PHONE_DIGITS: usize = 10;
+const MAX_PHONE_DIGITS: usize = 15;
+
+static EMAIL_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}").unwrap());
+static ADDRESS_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"\b\d{1,5}\s+(?:[A-Z][A-Za-z]*\.?\s+){1,4}(?:Street|St|Avenue|Ave|Road|Rd|Boulevard|Blvd|Lane|Ln|Drive|Dr|Court|Ct|Way|Place|Pl|Terrace|Parkway|Pkwy)\b\.?(?:,?\s+(?:Apt|Suite|Unit|#)\.?\s*[A-Za-z0-9-]+)?",
+    )
+    .unwrap()
+});
+static PHONE_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\+?\(?\d[\d \t().-]{7,}\d").unwrap());
+static YEAR_MONTH_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\b(?:19|20)\d{2}[-/.]\d{1,2}\b").unwrap());
+
+/// Replaces PII with numbered placeholders and remembers the originals
+#[derive(Debug, Default)]
+pub struct PiiScrubber {
+    replacements: Vec<(String, String)>,
+}
+
+impl PiiScrubber {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn placeholder_for(&mut self, kind: &str, original: &str) -> String {
+        if let Some((placeholder, _)) = self.replacements.iter().find(|(_, value)| value == original) {
+            return placeholder.clone();
+        }
+        let index = self
+            .replacements
+            .iter()
+            .filter(|(placeholder, _)| placeholder.starts_with(&format!("[{}_", kind)))
+            .count()
+            + 1;
+        let placeholder = format!("[{}_{}]", kind, index);
+        self.replacements.push((placeholder.clone(), original.to_string()));
+        placeholder
+    }
+
+    fn replace_matches(&mut self, text: &str, re: &Regex, kind: &str, accept: impl Fn(&str) -> bool) -> String {
+        let mut output = String::with_capacity(text.len());
+        let mut last = 0;
+        for m in re.find_iter(text) {
+            if !accept(m.as_str()) {
+                continue;
+            }
+            output.push_str(&text[last..m.start()]);
+            output.push_str(&self.placeholder_for(kind, m.as_str()));
+            last = m.end();
+        }
+        output.push_str(&text[last..]);
+        output
+    }
+
+    /// Redact emails, street addresses and phone numbers from text
+    pub fn scrub(&mut self, text: &str) -> String {
+        // Emails first so their digits can't be mistaken for phone numbers
+        let text = self.replace_matches(text, &EMAIL_RE, "EMAIL", |_| true);
+        let text = self.replace_matches(&text, &ADDRESS_RE, "ADDRESS", |_| true);
+        self.replace_matches(&text, &PHONE_RE, "PHONE", |candidate| {
+            let digits = candidate.chars().filter(|c| c.is_ascii_digit()).count();
+            // Date ranges like "2019-01 - 2023-05" have phone-like digit counts
+            (MIN_PHONE_DIGITS..=MAX_PHONE_DIGITS).contains(&digits) && !YEAR_MONTH_RE.is_match(candidate)
+        })
+    }
+
+    /// Redact every string in a JSON value
+    pub fn scrub_json(&mut self, value: &Value) -> Value {
+        match value {
+            Value::String(text) => Value::String(self.scrub(text)),
+            Value::Array(items) => Value::Array(items.iter().map(|item| self.scrub_json(item)).collect()),
+            Value::Object(map) => Value::Object(
+                map.iter()
+                    .map(|(key, item)| (key.clone(), self.scrub_json(item)))
+                    .collect(),
+            ),
+            other => other.clone(),
+        }
+    }
+
+    /// Put the original values back in place of placeholders
+    pub fn restore(&self, text: &str) -> String {
+        self.replacements
+            .iter()
+            .fold(text.to_string(), |text, (placeholder, original)| text.replace(placeholder, original))
+    }
+
+    pub fn restore_json(&self, value: &Value) -> Value {
+        match value {
+            Value::String(text) => Value::String(self.restore(text)),
+            Value::Array(items) => Value::Array(items.iter().map(|item| self.restore_json(item)).collect()),
+            Value::Object(map) => Value::Object(
+                map.iter()
+                    .map(|(key, item)| (key.clone(), self.restore_json(item)))
+                    .collect(),
+            ),
+            other => other.clone(),
+        }
+    }
+
+    /// Number of distinct values that were redacted
+    pub fn redaction_count(&self) -> usize {
+        self.replacements.len()
+    }
+}
+
+fn map_serde_error(e: serde_json::Error) -> AiProviderError {
+    AiProviderError::Unknown(format!("PII scrubbing failed: {}", e))
+}
+
+fn scrub_typed<T: Serialize + DeserializeOwned>(scrubber: &mut PiiScrubber, value: &T) -> Result<T, AiProviderError> {
+    let json = serde_json::to_value(value).map_err(map_serde_error)?;
+    serde_json::from_value(scrubber.scrub_json(&json)).map_err(map_serde_error)
+}
+
+fn restore_typed<T: Serialize + DeserializeOwned>(scrubber: &PiiScrubber, value: T) -> Result<T, AiProviderError> {
+    if scrubber.redaction_count() == 0 {
+        return Ok(value);
+    }
+    let json = serde_json::to_value(&value).map_err(map_serde_error)?;
+    serde_json::from_value(scrubber.restore_json(&json)).map_err(map_serde_error)
+}
+
+/// Wraps a provider so each request is scrubbed and each response restored
+pub struct ScrubbingProvider {
+    inner: Arc<dyn AiProvider>,
+}
+
+impl ScrubbingProvider {
+    pub fn new(inner: Arc<dyn AiProvider>) -> Self {
+        Self { inner }
+    }
+}
+
+#[async_trait]
+impl AiProvider for ScrubbingProvider {
+    async fn generate_resume_suggestions(&self, input: ResumeInput) -> Result<ResumeSuggestions, AiProviderError> {
+        let mut scrubber = PiiScrubber::new();
+        let input = scrub_typed(&mut scrubber, &input)?;
+        let output = self.inner.generate_resume_suggestions(input).await?;
+        restore_typed(&scrubber, output)
+    }
+
+    async fn generate_cover_letter(&self, input: CoverLetterInput) -> Result<CoverLetter, AiProviderError> {
+        let mut scrubber = PiiScrubber::new();
+        let input = scrub_typed(&mut scrubber, &input)?;
+        let output = self.inner.generate_cover_letter(input).await?;
+        restore_typed(&scrubber, output)
+    }
+
+    async fn generate_skill_suggestions(&self, input: SkillSuggestionsInput) -> Result<SkillSuggestions, AiProviderError> {
+        let mut scrubber = PiiScrubber::new();
+        let input = scrub_typed(&mut scrubber, &input)?;
+        let output = self.inner.generate_skill_suggestions(input).await?;
+        restore_typed(&scrubber, output)
+    }
+
+    async fn parse_job(&self, input: JobParsingInput) -> Result<ParsedJobOutput, AiProviderError> {
+        let mut scrubber = PiiScrubber::new();
+        let input = scrub_typed(&mut scrubber, &input)?;
+        let output = self.inner.parse_job(input).await?;
+        restore_typed(&scrubber, output)
+    }
+
+    async fn call_llm(&self, system_prompt: Option<&str>, user_prompt: &str) -> Result<String, AiProviderError> {
+        let mut scrubber = PiiScrubber::new();
+        let system_prompt = system_prompt.map(|prompt| scrubber.scrub(prompt));
+        let user_prompt = scrubber.scrub(user_prompt);
+        let response = self.inner.call_llm(system_prompt.as_deref(), &user_prompt).await?;
+        Ok(scrubber.restore(&response))
+    }
+
+    async fn embed(&self, text: &str) -> Result<Vec<f32>, AiProviderError> {
+        let mut scrubber = PiiScrubber::new();
+        self.inner.embed(&scrubber.scrub(text)).await
+    }
+}
+
+/// Whether prompts to cloud providers should be scrubbed (off by default)
+pub fn load_scrub_pii_before_cloud() -> bool {
+    get_connection()
+        .ok()
+        .and_then(|conn| get_setting(&conn, SCRUB_PII_SETTING).ok().flatten())
+        .map(|value| value == "true")
+        .unwrap_or(false)
+}
+
+/// Enable or disable PII scrubbing for cloud prompts
+pub fn save_scrub_pii_before_cloud(enabled: bool) -> Result<(), String> {
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    set_setting(&conn, SCRUB_PII_SETTING, if enabled { "true" } else { "false" })
+        .map_err(|e| e.to_string_for_tauri())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scrub_and_restore_round_trip() {
+        let mut scrubber = PiiScrubber::new();
+        let text = "Jane Doe | jane.doe@example.com | +1 (415) 555-0123 | 742 Evergreen Terrace, Springfield";
+        let scrubbed = scrubber.scrub(text);
+
+        assert!(!scrubbed.contains("jane.doe@example.com"));
+        assert!(!scrubbed.contains("555-0123"));
+        assert!(!scrubbed.contains("742 Evergreen Terrace"));
+        assert!(scrubbed.contains("[EMAIL_1]"));
+        assert!(scrubbed.contains("[PHONE_1]"));
+        assert!(scrubbed.contains("[ADDRESS_1]"));
+        assert!(scrubbed.contains("Springfield"));
+        assert_eq!(scrubber.restore(&scrubbed), text);
+    }
+
+    #[test]
+    fn test_dates_and_metrics_are_not_phones() {
+        let mut scrubber = PiiScrubber::new();
+        let text = "2019-01 - 2023-05: cut latency by 40% across 1,200 services";
+        assert_eq!(scrubber.scrub(text), text);
+    }
+
+    #[test]
+    fn test_repeated_value_reuses_placeholder() {
+        let mut scrubber = PiiScrubber::new();
+        let scrubbed = scrubber.scrub("a@b.io then a@b.io and c@d.io");
+        assert_eq!(scrubbed, "[EMAIL_1] then [EMAIL_1] and [EMAIL_2]");
+    }
+}
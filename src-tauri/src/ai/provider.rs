@@ -22,5 +22,11 @@ pub trait AiProvider: Send + Sync {
     /// system_prompt: Optional system message to set context
     /// user_prompt: The main user prompt/question
     async fn call_llm(&self, system_prompt: Option<&str>, user_prompt: &str) -> Result<String, AiProviderError>;
+
+    /// Embed text as a vector, used by the semantic cache to find near-identical inputs.
+    /// Providers without an embeddings endpoint keep this default.
+    async fn embed(&self, _text: &str) -> Result<Vec<f32>, AiProviderError> {
+        Err(AiProviderError::Unknown("Embeddings are not supported by this provider".to_string()))
+    }
 }
 
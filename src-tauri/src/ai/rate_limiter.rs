@@ -1,6 +1,40 @@
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, OwnedSemaphorePermit, Semaphore};
+
+/// Provider requests allowed in flight at once unless `max_concurrent_requests` is set
+pub const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 2;
+
+/// Shared by every provider, with the permit count it was created for
+static REQUEST_SEMAPHORE: std::sync::Mutex<Option<(usize, Arc<Semaphore>)>> = std::sync::Mutex::new(None);
+
+/// Set how many provider requests may run at once. Requests already holding a permit
+/// finish under the old limit.
+pub fn configure_max_concurrent_requests(limit: usize) {
+    let limit = limit.max(1);
+    let mut current = REQUEST_SEMAPHORE.lock().unwrap_or_else(|e| e.into_inner());
+    if current.as_ref().map(|(permits, _)| *permits) != Some(limit) {
+        *current = Some((limit, Arc::new(Semaphore::new(limit))));
+    }
+}
+
+/// Wait until a provider request may start. The request runs while the permit is
+/// held; dropping it, on success or error, lets the next request in.
+pub async fn acquire_request_permit() -> OwnedSemaphorePermit {
+    let semaphore = {
+        let mut current = REQUEST_SEMAPHORE.lock().unwrap_or_else(|e| e.into_inner());
+        current
+            .get_or_insert_with(|| {
+                (DEFAULT_MAX_CONCURRENT_REQUESTS, Arc::new(Semaphore::new(DEFAULT_MAX_CONCURRENT_REQUESTS)))
+            })
+            .1
+            .clone()
+    };
+    semaphore
+        .acquire_owned()
+        .await
+        .expect("the request semaphore is never closed")
+}
 
 /// Rate limiter using token bucket algorithm
 /// 
@@ -161,6 +195,20 @@ mod tests {
     use super::*;
     use tokio::time::{sleep, Instant};
 
+    #[tokio::test]
+    async fn test_request_permits_limit_concurrency() {
+        configure_max_concurrent_requests(2);
+        let first = acquire_request_permit().await;
+        let _second = acquire_request_permit().await;
+
+        let third = tokio::time::timeout(Duration::from_millis(50), acquire_request_permit()).await;
+        assert!(third.is_err(), "a third request should wait for a permit");
+
+        drop(first);
+        let third = tokio::time::timeout(Duration::from_millis(50), acquire_request_permit()).await;
+        assert!(third.is_ok(), "releasing a permit should let the next request in");
+    }
+
     #[tokio::test]
     async fn test_rate_limiter_acquires_tokens() {
         let limiter = RateLimiter::new(2, 1); // 2 requests per second
@@ -1,8 +1,14 @@
 use crate::ai::provider::AiProvider;
-use crate::ai::settings::{AiMode, CloudProvider, load_ai_settings};
+use crate::ai::settings::{AiMode, AiSettings, CloudProvider, LocalBackend, load_ai_settings};
 use crate::ai::local_provider::LocalProvider;
+use crate::ai::llama_wrapper::LlamaSamplingParams;
 use crate::ai::cloud_provider::CloudAiProvider;
+use crate::ai::ollama_provider::OllamaProvider;
 use crate::ai::hybrid_provider::HybridProvider;
+use crate::ai::fallback_provider::FallbackProvider;
+use crate::ai::pii_scrubber::{load_scrub_pii_before_cloud, ScrubbingProvider};
+use crate::ai::usage_log::UsageLoggingProvider;
+use crate::ai::rate_limiter::{configure_max_concurrent_requests, DEFAULT_MAX_CONCURRENT_REQUESTS};
 use std::sync::Arc;
 
 /// Provider resolver
@@ -10,25 +16,90 @@ use std::sync::Arc;
 pub enum ResolvedProvider {
     Local(Arc<LocalProvider>),
     Cloud(Arc<CloudAiProvider>),
+    Ollama(Arc<OllamaProvider>),
     Hybrid(Arc<HybridProvider>),
+    /// Providers tried in order when one is rate limited or unreachable
+    Fallback(Vec<ResolvedProvider>),
 }
 
 impl ResolvedProvider {
     /// Resolve the provider based on current settings
     pub fn resolve() -> Result<Self, String> {
+        Self::resolve_with(Self::load_settings()?)
+    }
+
+    /// Resolve the provider for one purpose (e.g. "job_parse"), using the model
+    /// override for that purpose when one is set
+    pub fn resolve_for(purpose: &str) -> Result<Self, String> {
+        let mut settings = Self::load_settings()?;
+        if let Some(model) = settings.model_overrides.get(purpose).cloned() {
+            log::info!("[ResolvedProvider] Using model override for {}: {}", purpose, model);
+            // The override replaces the model of the main mode only; hybrid mode reads
+            // its settings itself and keeps the global model
+            if settings.mode == AiMode::Local && settings.local_backend == LocalBackend::Ollama {
+                settings.ollama_model = Some(model);
+            } else {
+                settings.model_name = Some(model);
+            }
+        }
+        Self::resolve_with(settings)
+    }
+
+    fn load_settings() -> Result<AiSettings, String> {
         log::info!("[ResolvedProvider] Resolving AI provider from settings...");
-        let settings = match load_ai_settings() {
+        match load_ai_settings() {
             Ok(s) => {
                 log::debug!("[ResolvedProvider] Settings loaded: mode={:?}", s.mode);
-                s
+                Ok(s)
             }
             Err(e) => {
                 log::error!("[ResolvedProvider] Failed to load settings: {}", e);
-                return Err(format!("Failed to load AI settings: {}", e));
+                Err(format!("Failed to load AI settings: {}", e))
+            }
+        }
+    }
+
+    fn resolve_with(settings: AiSettings) -> Result<Self, String> {
+        configure_max_concurrent_requests(
+            settings.max_concurrent_requests
+                .map(|limit| limit as usize)
+                .unwrap_or(DEFAULT_MAX_CONCURRENT_REQUESTS),
+        );
+        let primary = Self::resolve_mode(settings.clone(), settings.mode)?;
+        match settings.fallback_mode.filter(|mode| *mode != settings.mode) {
+            Some(fallback_mode) => match Self::resolve_mode(settings.clone(), fallback_mode) {
+                Ok(fallback) => {
+                    log::info!("[ResolvedProvider] Falling back to {:?} mode when {:?} fails", fallback_mode, settings.mode);
+                    Ok(ResolvedProvider::Fallback(vec![primary, fallback]))
+                }
+                Err(e) => {
+                    // A misconfigured fallback shouldn't stop the primary provider from working
+                    log::warn!("[ResolvedProvider] Fallback {:?} mode is not usable: {}", fallback_mode, e);
+                    Ok(primary)
+                }
+            },
+            None => Ok(primary),
+        }
+    }
+
+    /// Build the provider for one mode from the loaded settings
+    fn resolve_mode(settings: AiSettings, mode: AiMode) -> Result<Self, String> {
+        match mode {
+            AiMode::Local if settings.local_backend == LocalBackend::Ollama => {
+                log::info!("[ResolvedProvider] Local mode selected (Ollama)");
+                let sampling = settings.sampling();
+                let model = settings.ollama_model
+                    .filter(|model| !model.trim().is_empty())
+                    .ok_or_else(|| {
+                        let msg = "Ollama requires a model to be configured. Please go to Settings and enter the tag of a model you have pulled (for example llama3.1).";
+                        log::error!("[ResolvedProvider] {}", msg);
+                        msg.to_string()
+                    })?;
+                let provider = OllamaProvider::new(settings.ollama_base_url, model.trim().to_string())
+                    .with_sampling(sampling);
+                log::info!("[ResolvedProvider] Using Ollama model: {}", provider.model_name());
+                Ok(ResolvedProvider::Ollama(Arc::new(provider)))
             }
-        };
-        
-        match settings.mode {
             AiMode::Local => {
                 log::info!("[ResolvedProvider] Local mode selected");
                 // Check if local model path is configured
@@ -64,12 +135,14 @@ impl ResolvedProvider {
                     return Err(msg);
                 }
                 
-                let provider = LocalProvider::with_model_path(path);
+                let provider = LocalProvider::with_model_path(path)
+                    .with_sampling(LlamaSamplingParams::from_sampling(&settings.sampling()));
                 log::info!("[ResolvedProvider] Local provider initialized successfully");
                 Ok(ResolvedProvider::Local(Arc::new(provider)))
             }
             AiMode::Cloud => {
                 log::info!("[ResolvedProvider] Cloud mode selected");
+                let sampling = settings.sampling();
                 let api_key = settings.api_key
                     .ok_or_else(|| {
                         let msg = "AI provider is not set up. Please go to Settings and add an OpenAI API key to use Cloud mode.";
@@ -83,7 +156,7 @@ impl ResolvedProvider {
                 
                 log::info!("[ResolvedProvider] Using cloud provider: {:?}, model: {}", provider, model_name);
                 Ok(ResolvedProvider::Cloud(Arc::new(
-                    CloudAiProvider::new(provider, api_key, model_name)
+                    CloudAiProvider::new(provider, api_key, model_name).with_sampling(sampling)
                 )))
             }
             AiMode::Hybrid => {
@@ -103,11 +176,82 @@ impl ResolvedProvider {
     }
     
     /// Get the provider as a trait object
+    ///
+    /// Providers that send prompts off this machine (cloud, hybrid, and Ollama on a
+    /// non-loopback `base_url`) are wrapped in a PII scrubber when the
+    /// `scrub_pii_before_cloud` setting is on. Every provider is wrapped in the usage log.
+    ///
+    /// A fallback chain wraps each of its providers this way, so every attempt is
+    /// logged under the provider that made it.
     pub fn as_provider(&self) -> Arc<dyn AiProvider> {
-        match self {
+        let provider = match self {
+            ResolvedProvider::Fallback(chain) => {
+                let providers = chain
+                    .iter()
+                    .map(|resolved| (resolved.usage_labels().0, resolved.as_provider()))
+                    .collect();
+                return Arc::new(FallbackProvider::new(providers));
+            }
             ResolvedProvider::Local(provider) => provider.clone() as Arc<dyn AiProvider>,
             ResolvedProvider::Cloud(provider) => provider.clone() as Arc<dyn AiProvider>,
+            ResolvedProvider::Ollama(provider) => provider.clone() as Arc<dyn AiProvider>,
             ResolvedProvider::Hybrid(provider) => provider.clone() as Arc<dyn AiProvider>,
+        };
+        let provider = if self.leaves_device() && load_scrub_pii_before_cloud() {
+            Arc::new(ScrubbingProvider::new(provider)) as Arc<dyn AiProvider>
+        } else {
+            provider
+        };
+        let (provider_name, model) = self.usage_labels();
+        Arc::new(UsageLoggingProvider::new(provider, provider_name, model))
+    }
+
+    /// The provider to embed with and the name of its embedding model, or `None`
+    /// when this provider can't embed. A fallback chain embeds with its first member
+    /// that can, without falling back, so a stored vector always comes from the
+    /// embedding model recorded next to it.
+    pub fn embedding_provider(&self) -> Option<(Arc<dyn AiProvider>, String)> {
+        let model = match self {
+            ResolvedProvider::Fallback(chain) => {
+                return chain.iter().find_map(|resolved| resolved.embedding_provider());
+            }
+            ResolvedProvider::Local(_) => None,
+            ResolvedProvider::Cloud(provider) => provider.embedding_model().map(str::to_string),
+            ResolvedProvider::Ollama(provider) => Some(provider.model_name().to_string()),
+            ResolvedProvider::Hybrid(provider) => provider.embedding_model().map(str::to_string),
+        }?;
+        Some((self.as_provider(), model))
+    }
+
+    /// Whether prompts sent to this provider may leave the machine
+    fn leaves_device(&self) -> bool {
+        match self {
+            ResolvedProvider::Local(_) => false,
+            ResolvedProvider::Ollama(provider) => provider.is_remote(),
+            ResolvedProvider::Cloud(_) | ResolvedProvider::Hybrid(_) | ResolvedProvider::Fallback(_) => true,
+        }
+    }
+
+    /// Provider and model names recorded in the AI call log
+    fn usage_labels(&self) -> (String, Option<String>) {
+        match self {
+            ResolvedProvider::Local(_) => (
+                "local".to_string(),
+                load_ai_settings().ok().and_then(|settings| settings.local_model_file_name()),
+            ),
+            ResolvedProvider::Cloud(provider) => {
+                let name = match provider.provider() {
+                    CloudProvider::OpenAI => "openai",
+                    CloudProvider::Anthropic => "anthropic",
+                };
+                (name.to_string(), Some(provider.model_name().to_string()))
+            }
+            ResolvedProvider::Ollama(provider) => ("ollama".to_string(), Some(provider.model_name().to_string())),
+            ResolvedProvider::Fallback(_) => ("fallback".to_string(), None),
+            ResolvedProvider::Hybrid(_) => (
+                "hybrid".to_string(),
+                load_ai_settings().ok().and_then(|settings| settings.model_name),
+            ),
         }
     }
 }
@@ -186,7 +186,7 @@ mod tests {
                 let attempts = &attempts;
                 async move {
                     attempts.fetch_add(1, Ordering::SeqCst);
-                    Err(AiProviderError::InvalidApiKey)
+                    Err::<(), _>(AiProviderError::InvalidApiKey)
                 }
             },
             config,
@@ -214,7 +214,7 @@ mod tests {
                 let attempts = &attempts;
                 async move {
                     attempts.fetch_add(1, Ordering::SeqCst);
-                    Err(AiProviderError::NetworkError("Persistent failure".to_string()))
+                    Err::<(), _>(AiProviderError::NetworkError("Persistent failure".to_string()))
                 }
             },
             config,
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use crate::db::get_connection;
 use crate::secure_storage::{store_secret, get_secret, remove_secret};
 
@@ -10,6 +11,160 @@ pub struct AiSettings {
     pub api_key: Option<String>, // Encrypted when stored in database
     pub model_name: Option<String>,
     pub local_model_path: Option<String>, // Path to local GGUF model file
+    /// Sampling temperature; when unset cloud providers use 0.3 and local models their own default
+    #[serde(default)]
+    pub temperature: Option<f32>,
+    /// Seed for reproducible output. Best effort only: OpenAI and the local model honor
+    /// it, Anthropic has no seed parameter and ignores it.
+    #[serde(default)]
+    pub seed: Option<u64>,
+    /// Cap on generated tokens per request; each provider's own limit is used when unset
+    #[serde(default)]
+    pub max_tokens: Option<u32>,
+    /// Provider requests allowed in flight at once; 2 when unset
+    #[serde(default)]
+    pub max_concurrent_requests: Option<u32>,
+    /// Which engine Local mode runs on
+    #[serde(default)]
+    pub local_backend: LocalBackend,
+    /// Ollama server URL; `http://localhost:11434` when unset
+    #[serde(default)]
+    pub ollama_base_url: Option<String>,
+    /// Ollama model tag, e.g. "llama3.1" or "qwen2.5:7b"
+    #[serde(default)]
+    pub ollama_model: Option<String>,
+    /// Mode to try when the main one is rate limited or unreachable, e.g. Local behind Cloud
+    #[serde(default)]
+    pub fallback_mode: Option<AiMode>,
+    /// Model to use instead of `model_name` for a purpose in `AI_PURPOSES`
+    #[serde(default)]
+    pub model_overrides: HashMap<String, String>,
+    /// Cache lifetime in days for a purpose, replacing its built-in TTL
+    #[serde(default)]
+    pub cache_ttls: HashMap<String, u32>,
+}
+
+/// Longest cache TTL a purpose can be given (about ten years)
+pub const MAX_CACHE_TTL_DAYS: u32 = 3650;
+
+/// Purposes that can have their own model, matching the AI cache purposes
+pub const AI_PURPOSES: &[&str] = &[
+    "job_parse",
+    "job_tldr",
+    "resume_generation",
+    "jd_summary",
+    "professional_summary",
+    "bullet_rewrite",
+    "star_rewrite",
+    "tighten_content",
+    "cover_letter_generation",
+    "cover_letter_adaptation",
+    "skill_suggestions",
+    "profile_summary",
+    "profile_extract",
+    "company_extract",
+    "learning_content",
+    "weekly_digest",
+];
+
+/// Engine behind Local mode: the bundled llama wrapper with a GGUF file, or an Ollama server
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Copy, Default)]
+pub enum LocalBackend {
+    #[default]
+    #[serde(rename = "embedded")]
+    Embedded,
+    #[serde(rename = "ollama")]
+    Ollama,
+}
+
+/// Temperature, seed and output limit handed to a provider with each request
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SamplingParams {
+    pub temperature: Option<f32>,
+    pub seed: Option<u64>,
+    pub max_tokens: Option<u32>,
+}
+
+impl AiSettings {
+    pub fn sampling(&self) -> SamplingParams {
+        SamplingParams {
+            temperature: self.temperature,
+            seed: self.seed,
+            max_tokens: self.max_tokens,
+        }
+    }
+
+    /// Model that serves `purpose`, as recorded in the AI cache. Follows
+    /// `ResolvedProvider::resolve_for`: an override replaces the Ollama tag or the cloud
+    /// model, the embedded local model is named by its file, and hybrid mode ignores
+    /// overrides. With a fallback mode set, this is the main mode's model.
+    pub fn model_for(&self, purpose: &str) -> Option<String> {
+        let model_override = self.model_overrides.get(purpose).cloned();
+        match self.mode {
+            AiMode::Local if self.local_backend == LocalBackend::Ollama => {
+                model_override.or_else(|| self.ollama_model.clone())
+            }
+            AiMode::Local => self.local_model_file_name(),
+            AiMode::Cloud => model_override.or_else(|| self.model_name.clone()),
+            // Hybrid prefers the cloud whenever it has a key
+            AiMode::Hybrid if self.api_key.is_none() => self.local_model_file_name(),
+            AiMode::Hybrid => self.model_name.clone(),
+        }
+    }
+
+    /// File name of the embedded local model, e.g. "llama-3.2-3b-instruct-q4_k_m.gguf"
+    pub fn local_model_file_name(&self) -> Option<String> {
+        self.local_model_path.as_deref().and_then(|path| {
+            std::path::Path::new(path)
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+        })
+    }
+
+    /// Check values a provider would reject
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(temperature) = self.temperature {
+            if !(0.0..=2.0).contains(&temperature) {
+                return Err(format!("Temperature must be between 0.0 and 2.0, got {}", temperature));
+            }
+        }
+        if self.max_tokens == Some(0) {
+            return Err("Max tokens must be at least 1".to_string());
+        }
+        if self.max_concurrent_requests == Some(0) {
+            return Err("Max concurrent requests must be at least 1".to_string());
+        }
+        for (purpose, model) in &self.model_overrides {
+            if !AI_PURPOSES.contains(&purpose.as_str()) {
+                return Err(format!(
+                    "Unknown AI purpose '{}' in model overrides. Known purposes: {}",
+                    purpose,
+                    AI_PURPOSES.join(", ")
+                ));
+            }
+            if model.trim().is_empty() {
+                return Err(format!("The model override for '{}' is empty", purpose));
+            }
+        }
+        for (purpose, days) in &self.cache_ttls {
+            let known = AI_PURPOSES.contains(&purpose.as_str())
+                || crate::ai_cache::default_cache_ttl_days(purpose).is_some();
+            if !known {
+                return Err(format!(
+                    "Unknown AI purpose '{}' in cache TTLs. Known purposes: {}",
+                    purpose,
+                    AI_PURPOSES.join(", ")
+                ));
+            }
+            if *days == 0 || *days > MAX_CACHE_TTL_DAYS {
+                return Err(format!(
+                    "The cache TTL for '{}' must be between 1 and {} days",
+                    purpose, MAX_CACHE_TTL_DAYS
+                ));
+            }
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Copy)]
@@ -26,7 +181,7 @@ pub enum CloudProvider {
     #[serde(rename = "openai")]
     OpenAI,
     #[serde(rename = "anthropic")]
-    Anthropic, // Future support
+    Anthropic,
 }
 
 impl Default for AiSettings {
@@ -37,6 +192,16 @@ impl Default for AiSettings {
             api_key: None,
             model_name: None,
             local_model_path: None,
+            temperature: None,
+            seed: None,
+            max_tokens: None,
+            max_concurrent_requests: None,
+            local_backend: LocalBackend::Embedded,
+            ollama_base_url: None,
+            ollama_model: None,
+            fallback_mode: None,
+            model_overrides: HashMap::new(),
+            cache_ttls: HashMap::new(),
         }
     }
 }
@@ -64,6 +229,16 @@ pub fn load_ai_settings() -> Result<AiSettings, String> {
                 api_key TEXT,
                 model_name TEXT,
                 local_model_path TEXT,
+                temperature REAL,
+                seed INTEGER,
+                max_tokens INTEGER,
+                local_backend TEXT,
+                ollama_base_url TEXT,
+                ollama_model TEXT,
+                fallback_mode TEXT,
+                model_overrides TEXT,
+                max_concurrent_requests INTEGER,
+                cache_ttls TEXT,
                 updated_at TEXT NOT NULL DEFAULT (datetime('now'))
             )",
             [],
@@ -97,10 +272,36 @@ pub fn load_ai_settings() -> Result<AiSettings, String> {
         )
         .map_err(|e| format!("Failed to add local_model_path column: {}", e))?;
     }
+
+    // Sampling and Ollama columns were added later; add them to older tables
+    for (column, column_type) in [
+        ("temperature", "REAL"),
+        ("seed", "INTEGER"),
+        ("max_tokens", "INTEGER"),
+        ("local_backend", "TEXT"),
+        ("ollama_base_url", "TEXT"),
+        ("ollama_model", "TEXT"),
+        ("fallback_mode", "TEXT"),
+        ("model_overrides", "TEXT"),
+        ("max_concurrent_requests", "INTEGER"),
+        ("cache_ttls", "TEXT"),
+    ] {
+        let exists: bool = conn
+            .query_row(
+                "SELECT COUNT(*) FROM pragma_table_info('ai_settings') WHERE name = ?",
+                [column],
+                |row| Ok(row.get::<_, i64>(0)? > 0),
+            )
+            .unwrap_or(false);
+        if !exists {
+            conn.execute(&format!("ALTER TABLE ai_settings ADD COLUMN {} {}", column, column_type), [])
+                .map_err(|e| format!("Failed to add {} column: {}", column, e))?;
+        }
+    }
     
     // Load settings
     let mut stmt = conn
-        .prepare("SELECT mode, cloud_provider, api_key, model_name, local_model_path FROM ai_settings WHERE id = 1")
+        .prepare("SELECT mode, cloud_provider, api_key, model_name, local_model_path, temperature, seed, local_backend, ollama_base_url, ollama_model, fallback_mode, max_tokens, model_overrides, max_concurrent_requests, cache_ttls FROM ai_settings WHERE id = 1")
         .map_err(|e| format!("Failed to prepare query: {}", e))?;
     
     let settings_result = stmt.query_row([], |row| {
@@ -109,6 +310,16 @@ pub fn load_ai_settings() -> Result<AiSettings, String> {
         let api_key_encrypted: Option<String> = row.get(2)?;
         let model_name: Option<String> = row.get(3)?;
         let local_model_path: Option<String> = row.get(4)?;
+        let temperature: Option<f64> = row.get(5)?;
+        let seed: Option<i64> = row.get(6)?;
+        let local_backend_str: Option<String> = row.get(7)?;
+        let ollama_base_url: Option<String> = row.get(8)?;
+        let ollama_model: Option<String> = row.get(9)?;
+        let fallback_mode_str: Option<String> = row.get(10)?;
+        let max_tokens: Option<i64> = row.get(11)?;
+        let model_overrides_json: Option<String> = row.get(12)?;
+        let max_concurrent_requests: Option<i64> = row.get(13)?;
+        let cache_ttls_json: Option<String> = row.get(14)?;
         
         // Try to get API key from secure storage first, then fall back to database
         let api_key = if let Ok(Some(secret)) = get_secret("ai_api_key") {
@@ -134,6 +345,23 @@ pub fn load_ai_settings() -> Result<AiSettings, String> {
             api_key,
             model_name,
             local_model_path,
+            temperature: temperature.map(|t| t as f32),
+            seed: seed.map(|s| s as u64),
+            max_tokens: max_tokens.and_then(|m| u32::try_from(m).ok()),
+            max_concurrent_requests: max_concurrent_requests.and_then(|m| u32::try_from(m).ok()),
+            local_backend: local_backend_str
+                .and_then(|s| serde_json::from_str::<LocalBackend>(&format!("\"{}\"", s)).ok())
+                .unwrap_or_default(),
+            ollama_base_url,
+            ollama_model,
+            fallback_mode: fallback_mode_str
+                .and_then(|s| serde_json::from_str::<AiMode>(&format!("\"{}\"", s)).ok()),
+            model_overrides: model_overrides_json
+                .and_then(|json| serde_json::from_str(&json).ok())
+                .unwrap_or_default(),
+            cache_ttls: cache_ttls_json
+                .and_then(|json| serde_json::from_str(&json).ok())
+                .unwrap_or_default(),
         })
     });
     
@@ -161,6 +389,7 @@ pub fn load_ai_settings() -> Result<AiSettings, String> {
 
 /// Save AI settings to database
 pub fn save_ai_settings(settings: &AiSettings) -> Result<(), String> {
+    settings.validate()?;
     let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
     let now = chrono::Utc::now().to_rfc3339();
     
@@ -168,6 +397,22 @@ pub fn save_ai_settings(settings: &AiSettings) -> Result<(), String> {
         .map_err(|e| format!("Failed to serialize mode: {}", e))?;
     let cloud_provider_str = settings.cloud_provider.as_ref()
         .and_then(|p| serde_json::to_string(p).ok());
+    let local_backend_str = serde_json::to_string(&settings.local_backend)
+        .map_err(|e| format!("Failed to serialize local backend: {}", e))?;
+    let fallback_mode_str = settings.fallback_mode.as_ref()
+        .and_then(|m| serde_json::to_string(m).ok());
+    let model_overrides_json = if settings.model_overrides.is_empty() {
+        None
+    } else {
+        Some(serde_json::to_string(&settings.model_overrides)
+            .map_err(|e| format!("Failed to serialize model overrides: {}", e))?)
+    };
+    let cache_ttls_json = if settings.cache_ttls.is_empty() {
+        None
+    } else {
+        Some(serde_json::to_string(&settings.cache_ttls)
+            .map_err(|e| format!("Failed to serialize cache TTLs: {}", e))?)
+    };
     
     // Store API key in secure storage (OS keychain when available)
     if let Some(api_key) = &settings.api_key {
@@ -187,14 +432,24 @@ pub fn save_ai_settings(settings: &AiSettings) -> Result<(), String> {
     };
     
     conn.execute(
-        "INSERT INTO ai_settings (id, mode, cloud_provider, api_key, model_name, local_model_path, updated_at)
-         VALUES (1, ?, ?, ?, ?, ?, ?)
+        "INSERT INTO ai_settings (id, mode, cloud_provider, api_key, model_name, local_model_path, temperature, seed, local_backend, ollama_base_url, ollama_model, fallback_mode, max_tokens, model_overrides, max_concurrent_requests, cache_ttls, updated_at)
+         VALUES (1, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
          ON CONFLICT(id) DO UPDATE SET
             mode = excluded.mode,
             cloud_provider = excluded.cloud_provider,
             api_key = excluded.api_key,
             model_name = excluded.model_name,
             local_model_path = excluded.local_model_path,
+            temperature = excluded.temperature,
+            seed = excluded.seed,
+            local_backend = excluded.local_backend,
+            ollama_base_url = excluded.ollama_base_url,
+            ollama_model = excluded.ollama_model,
+            fallback_mode = excluded.fallback_mode,
+            max_tokens = excluded.max_tokens,
+            model_overrides = excluded.model_overrides,
+            max_concurrent_requests = excluded.max_concurrent_requests,
+            cache_ttls = excluded.cache_ttls,
             updated_at = excluded.updated_at",
         rusqlite::params![
             mode_str.trim_matches('"'),
@@ -202,6 +457,16 @@ pub fn save_ai_settings(settings: &AiSettings) -> Result<(), String> {
             api_key_placeholder,
             settings.model_name,
             settings.local_model_path,
+            settings.temperature.map(|t| t as f64),
+            settings.seed.map(|s| s as i64),
+            local_backend_str.trim_matches('"'),
+            settings.ollama_base_url,
+            settings.ollama_model,
+            fallback_mode_str.as_ref().map(|s| s.trim_matches('"')),
+            settings.max_tokens.map(i64::from),
+            model_overrides_json,
+            settings.max_concurrent_requests.map(i64::from),
+            cache_ttls_json,
             now
         ],
     )
@@ -210,3 +475,88 @@ pub fn save_ai_settings(settings: &AiSettings) -> Result<(), String> {
     Ok(())
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_sampling_ranges() {
+        let with = |temperature: Option<f32>, max_tokens: Option<u32>| AiSettings {
+            temperature,
+            max_tokens,
+            ..AiSettings::default()
+        };
+        assert!(with(Some(0.0), Some(1)).validate().is_ok());
+        assert!(with(Some(2.0), None).validate().is_ok());
+        assert!(with(Some(2.5), None).validate().is_err());
+        assert!(with(Some(-0.1), None).validate().is_err());
+        assert!(with(None, Some(0)).validate().is_err());
+    }
+
+    #[test]
+    fn test_model_overrides() {
+        let mut settings = AiSettings {
+            model_name: Some("gpt-4o".to_string()),
+            ..AiSettings::default()
+        };
+        settings.model_overrides.insert("job_parse".to_string(), "gpt-4o-mini".to_string());
+        assert_eq!(settings.model_for("job_parse").as_deref(), Some("gpt-4o-mini"));
+        assert_eq!(settings.model_for("resume_generation").as_deref(), Some("gpt-4o"));
+        assert!(settings.validate().is_ok());
+
+        // The model recorded is the one that runs, not the override name
+        let local = AiSettings {
+            mode: AiMode::Local,
+            local_model_path: Some("/models/phi-3-mini.Q4_K_M.gguf".to_string()),
+            ..settings.clone()
+        };
+        assert_eq!(local.model_for("job_parse").as_deref(), Some("phi-3-mini.Q4_K_M.gguf"));
+        let ollama = AiSettings {
+            local_backend: LocalBackend::Ollama,
+            ollama_model: Some("llama3.1".to_string()),
+            ..local.clone()
+        };
+        assert_eq!(ollama.model_for("job_parse").as_deref(), Some("gpt-4o-mini"));
+        assert_eq!(ollama.model_for("resume_generation").as_deref(), Some("llama3.1"));
+        let hybrid = AiSettings { mode: AiMode::Hybrid, ..local };
+        assert_eq!(hybrid.model_for("job_parse").as_deref(), Some("phi-3-mini.Q4_K_M.gguf"));
+        let hybrid = AiSettings { api_key: Some("sk-test".to_string()), ..hybrid };
+        assert_eq!(hybrid.model_for("job_parse").as_deref(), Some("gpt-4o"));
+
+        settings.model_overrides.insert("resume_writing".to_string(), "gpt-4o".to_string());
+        let err = settings.validate().unwrap_err();
+        assert!(err.contains("Unknown AI purpose 'resume_writing'"));
+        assert!(err.contains("resume_generation"));
+    }
+
+    #[test]
+    fn test_cache_ttls_must_be_positive() {
+        let mut settings = AiSettings::default();
+        settings.cache_ttls.insert("cover_letter_generation".to_string(), 7);
+        assert!(settings.validate().is_ok());
+        settings.cache_ttls.insert("job_parse".to_string(), 0);
+        assert!(settings.validate().unwrap_err().contains("'job_parse'"));
+        settings.cache_ttls.insert("job_parse".to_string(), u32::MAX);
+        assert!(settings.validate().unwrap_err().contains("3650"));
+        settings.cache_ttls.insert("job_parse".to_string(), 3650);
+        assert!(settings.validate().is_ok());
+    }
+
+    #[test]
+    fn test_cache_ttls_need_a_known_purpose() {
+        let mut settings = AiSettings::default();
+        settings.cache_ttls.insert("company_fetch".to_string(), 30);
+        assert!(settings.validate().is_ok());
+        settings.cache_ttls.insert("resume_writing".to_string(), 30);
+        assert!(settings.validate().unwrap_err().contains("Unknown AI purpose 'resume_writing'"));
+    }
+
+    #[test]
+    fn test_settings_saved_before_new_fields_still_load() {
+        let settings: AiSettings = serde_json::from_str(r#"{"mode":"cloud","cloudProvider":"openai","modelName":"gpt-4o-mini"}"#).unwrap();
+        assert_eq!(settings.mode, AiMode::Cloud);
+        assert_eq!(settings.temperature, None);
+        assert_eq!(settings.max_tokens, None);
+        assert_eq!(settings.local_backend, LocalBackend::Embedded);
+    }
+}
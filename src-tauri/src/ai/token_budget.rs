@@ -0,0 +1,76 @@
+use crate::app_settings::{get_setting, set_setting};
+use crate::db::get_connection;
+
+/// Settings key for the prompt size warning threshold (in estimated tokens)
+pub const PROMPT_TOKEN_WARN_THRESHOLD_SETTING: &str = "prompt_token_warn_threshold";
+
+/// Default warning threshold, roughly where cloud calls start to get noticeably expensive
+pub const DEFAULT_PROMPT_TOKEN_WARN_THRESHOLD: usize = 8000;
+
+/// Estimate the number of tokens in a prompt.
+///
+/// This is an approximation (~4 characters per token for English text), not a
+/// real tokenizer count. It is only meant to give a rough sense of cost.
+pub fn estimate_prompt_tokens(text: &str) -> usize {
+    (text.chars().count() + 3) / 4
+}
+
+/// Build a warning when the estimated prompt size exceeds the threshold
+pub fn prompt_token_warning(prompt: &str, threshold: usize) -> Option<String> {
+    let estimated = estimate_prompt_tokens(prompt);
+    if estimated > threshold {
+        Some(format!(
+            "Large prompt: ~{} tokens (warning threshold {}). Cloud usage may be costly.",
+            estimated, threshold
+        ))
+    } else {
+        None
+    }
+}
+
+/// Load the configured warning threshold, falling back to the default
+pub fn load_prompt_token_warn_threshold() -> usize {
+    get_connection()
+        .map(|conn| load_prompt_token_warn_threshold_with_conn(&conn))
+        .unwrap_or(DEFAULT_PROMPT_TOKEN_WARN_THRESHOLD)
+}
+
+pub fn load_prompt_token_warn_threshold_with_conn(conn: &rusqlite::Connection) -> usize {
+    get_setting(conn, PROMPT_TOKEN_WARN_THRESHOLD_SETTING)
+        .ok()
+        .flatten()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_PROMPT_TOKEN_WARN_THRESHOLD)
+}
+
+/// Save the warning threshold
+pub fn save_prompt_token_warn_threshold(threshold: usize) -> Result<(), String> {
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    set_setting(&conn, PROMPT_TOKEN_WARN_THRESHOLD_SETTING, &threshold.to_string())
+        .map_err(|e| e.to_string_for_tauri())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_prompt_tokens() {
+        assert_eq!(estimate_prompt_tokens(""), 0);
+        assert_eq!(estimate_prompt_tokens("abcd"), 1);
+        assert_eq!(estimate_prompt_tokens("abcde"), 2);
+    }
+
+    #[test]
+    fn test_long_prompt_triggers_warning() {
+        let prompt = "word ".repeat(10_000);
+        let warning = prompt_token_warning(&prompt, 8000);
+        assert!(warning.is_some());
+        assert!(warning.unwrap().contains("~12500 tokens"));
+    }
+
+    #[test]
+    fn test_short_prompt_has_no_warning() {
+        assert!(prompt_token_warning("Summarize this job description.", 8000).is_none());
+    }
+}
@@ -1,3 +1,4 @@
+use crate::language::Lang;
 use serde::{Deserialize, Serialize};
 
 /// Input for generating resume suggestions
@@ -6,6 +7,9 @@ pub struct ResumeInput {
     pub profile_data: serde_json::Value, // User profile JSON
     pub job_description: String,
     pub options: Option<ResumeOptions>,
+    /// Language the suggestions are written in; unset leaves it to the model
+    #[serde(default)]
+    pub output_language: Option<Lang>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -44,6 +48,9 @@ pub struct CoverLetterInput {
     pub job_description: String,
     pub company_name: Option<String>,
     pub options: Option<CoverLetterOptions>,
+    /// Language the letter is written in; unset leaves it to the model
+    #[serde(default)]
+    pub output_language: Option<Lang>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -125,5 +132,13 @@ pub struct ParsedJobOutput {
     pub seniority_score: Option<f32>,
     #[serde(default)]
     pub remote_friendly: Option<bool>,
+    /// Annual compensation range as stated in the posting
+    #[serde(default)]
+    pub salary_min: Option<f64>,
+    #[serde(default)]
+    pub salary_max: Option<f64>,
+    /// ISO 4217 code such as "USD"
+    #[serde(default)]
+    pub salary_currency: Option<String>,
 }
 
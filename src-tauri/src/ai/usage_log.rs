@@ -0,0 +1,474 @@
+//! AI call log for usage and cost auditing
+//!
+//! Every provider call (and every cache hit that saved one) is recorded with
+//! token counts and estimated cost, so usage can be reconciled against the
+//! provider's billing. Providers report the counts from the API response through
+//! `report_token_usage`; when they don't, the same ~4 chars/token estimate as the
+//! prompt size warning is used. Local models are never billed.
+
+use crate::ai::errors::AiProviderError;
+use crate::ai::provider::AiProvider;
+use crate::ai::token_budget::estimate_prompt_tokens;
+use crate::ai::types::*;
+use crate::db::get_connection;
+use crate::errors::CareerBenchError;
+use async_trait::async_trait;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::cell::Cell;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Instant;
+
+/// USD per million (prompt, completion) tokens, matched by model name prefix.
+/// More specific prefixes come first ("gpt-4o-mini" before "gpt-4o").
+const MODEL_PRICING: &[(&str, f64, f64)] = &[
+    ("gpt-4o-mini", 0.15, 0.60),
+    ("gpt-4o", 2.50, 10.00),
+    ("gpt-4-turbo", 10.00, 30.00),
+    ("gpt-4", 30.00, 60.00),
+    ("gpt-3.5-turbo", 0.50, 1.50),
+    ("claude-3-5-haiku", 0.80, 4.00),
+    ("claude-3-haiku", 0.25, 1.25),
+    ("claude-3-5-sonnet", 3.00, 15.00),
+    ("claude-3-opus", 15.00, 75.00),
+];
+
+pub const AI_USAGE_CSV_HEADER: &str =
+    "timestamp,purpose,model,provider,prompt_tokens,completion_tokens,estimated_cost_usd,latency_ms,cache_hit";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AiCallRecord {
+    pub created_at: String,
+    pub purpose: String,
+    pub provider: String,
+    pub model: Option<String>,
+    pub prompt_tokens: i64,
+    pub completion_tokens: i64,
+    pub estimated_cost: f64,
+    pub latency_ms: i64,
+    pub cache_hit: bool,
+}
+
+/// Token counts the provider reported for a call
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TokenUsage {
+    pub prompt_tokens: i64,
+    pub completion_tokens: i64,
+}
+
+tokio::task_local! {
+    /// Usage reported during the call currently being logged
+    static REPORTED_USAGE: Cell<Option<TokenUsage>>;
+}
+
+/// Token counts in an API response, in OpenAI (`usage.prompt_tokens`), Anthropic
+/// (`usage.input_tokens`) or Ollama (`prompt_eval_count`/`eval_count`) form
+pub fn token_usage_from_response(response: &Value) -> Option<TokenUsage> {
+    let count = |value: Option<&Value>| value.and_then(|v| v.as_i64());
+    if let Some(usage) = response.get("usage") {
+        let prompt = count(usage.get("prompt_tokens")).or_else(|| count(usage.get("input_tokens")));
+        let completion = count(usage.get("completion_tokens")).or_else(|| count(usage.get("output_tokens")));
+        if prompt.is_some() || completion.is_some() {
+            return Some(TokenUsage {
+                prompt_tokens: prompt.unwrap_or(0),
+                completion_tokens: completion.unwrap_or(0),
+            });
+        }
+    }
+    let prompt = count(response.get("prompt_eval_count"));
+    let completion = count(response.get("eval_count"));
+    if prompt.is_none() && completion.is_none() {
+        return None;
+    }
+    Some(TokenUsage {
+        prompt_tokens: prompt.unwrap_or(0),
+        completion_tokens: completion.unwrap_or(0),
+    })
+}
+
+/// Report the provider's token counts for the call being logged; counts from
+/// several requests in one call (e.g. a retry after a parse failure) add up.
+/// Does nothing outside a logged call.
+pub fn report_token_usage(usage: TokenUsage) {
+    let _ = REPORTED_USAGE.try_with(|reported| {
+        let total = reported.get().unwrap_or_default();
+        reported.set(Some(TokenUsage {
+            prompt_tokens: total.prompt_tokens + usage.prompt_tokens,
+            completion_tokens: total.completion_tokens + usage.completion_tokens,
+        }));
+    });
+}
+
+/// Run a provider call, collecting whatever token usage it reports
+async fn with_reported_usage<F: Future>(call: F) -> (F::Output, Option<TokenUsage>) {
+    REPORTED_USAGE
+        .scope(Cell::new(None), async move {
+            let output = call.await;
+            (output, REPORTED_USAGE.with(|reported| reported.get()))
+        })
+        .await
+}
+
+/// Providers that run on the user's machine and are never billed
+fn is_billed_provider(provider: &str) -> bool {
+    !matches!(provider, "local" | "ollama")
+}
+
+/// Estimated USD cost of a call; unknown and local models cost nothing
+pub fn estimate_cost(model: Option<&str>, prompt_tokens: i64, completion_tokens: i64) -> f64 {
+    let model = match model {
+        Some(model) => model.to_lowercase(),
+        None => return 0.0,
+    };
+    MODEL_PRICING
+        .iter()
+        .find(|(prefix, _, _)| model.starts_with(prefix))
+        .map(|(_, prompt_price, completion_price)| {
+            (prompt_tokens as f64 * prompt_price + completion_tokens as f64 * completion_price) / 1_000_000.0
+        })
+        .unwrap_or(0.0)
+}
+
+pub fn record_ai_call(conn: &Connection, record: &AiCallRecord) -> Result<(), CareerBenchError> {
+    conn.execute(
+        "INSERT INTO ai_call_log (created_at, purpose, provider, model, prompt_tokens, completion_tokens, estimated_cost, latency_ms, cache_hit)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        rusqlite::params![
+            record.created_at,
+            record.purpose,
+            record.provider,
+            record.model,
+            record.prompt_tokens,
+            record.completion_tokens,
+            record.estimated_cost,
+            record.latency_ms,
+            record.cache_hit as i32,
+        ],
+    )?;
+    Ok(())
+}
+
+/// Record a cache hit (a call that didn't reach the provider)
+pub fn record_cache_hit(conn: &Connection, purpose: &str, model: Option<&str>, now: &str) -> Result<(), CareerBenchError> {
+    record_ai_call(
+        conn,
+        &AiCallRecord {
+            created_at: now.to_string(),
+            purpose: purpose.to_string(),
+            provider: "cache".to_string(),
+            model: model.map(|m| m.to_string()),
+            prompt_tokens: 0,
+            completion_tokens: 0,
+            estimated_cost: 0.0,
+            latency_ms: 0,
+            cache_hit: true,
+        },
+    )
+}
+
+/// Quote a CSV field when it contains a delimiter, quote or newline
+pub fn csv_field(value: &str) -> String {
+    if value.contains(',') || value.contains('"') || value.contains('\n') || value.contains('\r') {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// One CSV row per logged call between `start_date` and `end_date` (inclusive, `YYYY-MM-DD`)
+pub fn export_ai_usage_csv_with_conn(
+    conn: &Connection,
+    start_date: &str,
+    end_date: &str,
+) -> Result<String, CareerBenchError> {
+    let mut stmt = conn.prepare(
+        "SELECT created_at, purpose, model, provider, prompt_tokens, completion_tokens, estimated_cost, latency_ms, cache_hit
+         FROM ai_call_log
+         WHERE substr(created_at, 1, 10) BETWEEN ? AND ?
+         ORDER BY created_at ASC, id ASC",
+    )?;
+    let rows = stmt.query_map([start_date, end_date], |row| {
+        Ok(AiCallRecord {
+            created_at: row.get(0)?,
+            purpose: row.get(1)?,
+            model: row.get(2)?,
+            provider: row.get(3)?,
+            prompt_tokens: row.get(4)?,
+            completion_tokens: row.get(5)?,
+            estimated_cost: row.get(6)?,
+            latency_ms: row.get(7)?,
+            cache_hit: row.get::<_, i32>(8)? != 0,
+        })
+    })?;
+
+    let mut csv = String::from(AI_USAGE_CSV_HEADER);
+    csv.push('\n');
+    for row_result in rows {
+        let record = row_result?;
+        csv.push_str(&format!(
+            "{},{},{},{},{},{},{:.6},{},{}\n",
+            csv_field(&record.created_at),
+            csv_field(&record.purpose),
+            csv_field(record.model.as_deref().unwrap_or("")),
+            csv_field(&record.provider),
+            record.prompt_tokens,
+            record.completion_tokens,
+            record.estimated_cost,
+            record.latency_ms,
+            record.cache_hit,
+        ));
+    }
+    Ok(csv)
+}
+
+/// Calls, tokens and cost for one day or purpose
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AiUsageBucket {
+    /// The day (`YYYY-MM-DD`) or purpose
+    pub key: String,
+    /// Calls that reached a provider
+    pub calls: i64,
+    pub cache_hits: i64,
+    pub prompt_tokens: i64,
+    pub completion_tokens: i64,
+    pub estimated_cost: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AiUsageSummary {
+    pub start_date: String,
+    pub end_date: String,
+    pub calls: i64,
+    pub cache_hits: i64,
+    pub prompt_tokens: i64,
+    pub completion_tokens: i64,
+    pub estimated_cost: f64,
+    /// Oldest day first
+    pub by_day: Vec<AiUsageBucket>,
+    /// Most expensive purpose first
+    pub by_purpose: Vec<AiUsageBucket>,
+}
+
+fn usage_buckets(
+    conn: &Connection,
+    key: &str,
+    order_by: &str,
+    start_date: &str,
+    end_date: &str,
+) -> Result<Vec<AiUsageBucket>, CareerBenchError> {
+    let sql = format!(
+        "SELECT {0} AS bucket,
+                COALESCE(SUM(CASE WHEN cache_hit = 0 THEN 1 ELSE 0 END), 0),
+                COALESCE(SUM(cache_hit), 0),
+                COALESCE(SUM(prompt_tokens), 0),
+                COALESCE(SUM(completion_tokens), 0),
+                COALESCE(SUM(estimated_cost), 0.0)
+         FROM ai_call_log
+         WHERE substr(created_at, 1, 10) BETWEEN ? AND ?
+         GROUP BY bucket
+         ORDER BY {1}",
+        key, order_by
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt.query_map([start_date, end_date], |row| {
+        Ok(AiUsageBucket {
+            key: row.get(0)?,
+            calls: row.get(1)?,
+            cache_hits: row.get(2)?,
+            prompt_tokens: row.get(3)?,
+            completion_tokens: row.get(4)?,
+            estimated_cost: row.get(5)?,
+        })
+    })?;
+    Ok(rows.collect::<Result<Vec<_>, _>>()?)
+}
+
+/// Usage between `start_date` and `end_date` (inclusive, `YYYY-MM-DD`), in total,
+/// per day and per purpose
+pub fn get_ai_usage_summary_with_conn(
+    conn: &Connection,
+    start_date: &str,
+    end_date: &str,
+) -> Result<AiUsageSummary, CareerBenchError> {
+    let by_day = usage_buckets(conn, "substr(created_at, 1, 10)", "bucket ASC", start_date, end_date)?;
+    let by_purpose = usage_buckets(conn, "purpose", "6 DESC, bucket ASC", start_date, end_date)?;
+    Ok(AiUsageSummary {
+        start_date: start_date.to_string(),
+        end_date: end_date.to_string(),
+        calls: by_day.iter().map(|b| b.calls).sum(),
+        cache_hits: by_day.iter().map(|b| b.cache_hits).sum(),
+        prompt_tokens: by_day.iter().map(|b| b.prompt_tokens).sum(),
+        completion_tokens: by_day.iter().map(|b| b.completion_tokens).sum(),
+        estimated_cost: by_day.iter().map(|b| b.estimated_cost).sum(),
+        by_day,
+        by_purpose,
+    })
+}
+
+/// Wraps a provider and logs each call's tokens, estimated cost and latency
+pub struct UsageLoggingProvider {
+    inner: Arc<dyn AiProvider>,
+    provider: String,
+    model: Option<String>,
+}
+
+impl UsageLoggingProvider {
+    pub fn new(inner: Arc<dyn AiProvider>, provider: String, model: Option<String>) -> Self {
+        Self { inner, provider, model }
+    }
+
+    fn log<O: Serialize>(
+        &self,
+        purpose: &str,
+        prompt: &str,
+        (output, reported): &(Result<O, AiProviderError>, Option<TokenUsage>),
+        started: Instant,
+    ) {
+        let (prompt_tokens, completion_tokens) = match reported {
+            Some(usage) => (usage.prompt_tokens, usage.completion_tokens),
+            None => {
+                let completion_tokens = match output {
+                    Ok(output) => serde_json::to_string(output)
+                        .map(|json| estimate_prompt_tokens(&json) as i64)
+                        .unwrap_or(0),
+                    Err(_) => 0,
+                };
+                (estimate_prompt_tokens(prompt) as i64, completion_tokens)
+            }
+        };
+        let estimated_cost = if is_billed_provider(&self.provider) {
+            estimate_cost(self.model.as_deref(), prompt_tokens, completion_tokens)
+        } else {
+            0.0
+        };
+        let record = AiCallRecord {
+            created_at: chrono::Utc::now().to_rfc3339(),
+            purpose: purpose.to_string(),
+            provider: self.provider.clone(),
+            model: self.model.clone(),
+            prompt_tokens,
+            completion_tokens,
+            estimated_cost,
+            latency_ms: started.elapsed().as_millis() as i64,
+            cache_hit: false,
+        };
+        // Logging must never fail the AI call itself
+        if let Err(e) = get_connection()
+            .map_err(CareerBenchError::from)
+            .and_then(|conn| record_ai_call(&conn, &record))
+        {
+            log::warn!("[UsageLog] Failed to record AI call: {}", e);
+        }
+    }
+}
+
+fn input_text<T: Serialize>(input: &T) -> String {
+    serde_json::to_string(input).unwrap_or_default()
+}
+
+#[async_trait]
+impl AiProvider for UsageLoggingProvider {
+    async fn generate_resume_suggestions(&self, input: ResumeInput) -> Result<ResumeSuggestions, AiProviderError> {
+        let prompt = input_text(&input);
+        let started = Instant::now();
+        let output = with_reported_usage(self.inner.generate_resume_suggestions(input)).await;
+        self.log("resume_generation", &prompt, &output, started);
+        output.0
+    }
+
+    async fn generate_cover_letter(&self, input: CoverLetterInput) -> Result<CoverLetter, AiProviderError> {
+        let prompt = input_text(&input);
+        let started = Instant::now();
+        let output = with_reported_usage(self.inner.generate_cover_letter(input)).await;
+        self.log("cover_letter", &prompt, &output, started);
+        output.0
+    }
+
+    async fn generate_skill_suggestions(&self, input: SkillSuggestionsInput) -> Result<SkillSuggestions, AiProviderError> {
+        let prompt = input_text(&input);
+        let started = Instant::now();
+        let output = with_reported_usage(self.inner.generate_skill_suggestions(input)).await;
+        self.log("skill_suggestions", &prompt, &output, started);
+        output.0
+    }
+
+    async fn parse_job(&self, input: JobParsingInput) -> Result<ParsedJobOutput, AiProviderError> {
+        let prompt = input_text(&input);
+        let started = Instant::now();
+        let output = with_reported_usage(self.inner.parse_job(input)).await;
+        self.log("job_parse", &prompt, &output, started);
+        output.0
+    }
+
+    async fn call_llm(&self, system_prompt: Option<&str>, user_prompt: &str) -> Result<String, AiProviderError> {
+        let prompt = format!("{}{}", system_prompt.unwrap_or(""), user_prompt);
+        let started = Instant::now();
+        let output = with_reported_usage(self.inner.call_llm(system_prompt, user_prompt)).await;
+        self.log("llm_call", &prompt, &output, started);
+        output.0
+    }
+
+    async fn embed(&self, text: &str) -> Result<Vec<f32>, AiProviderError> {
+        // Not logged: embedding calls aren't priced by the chat model's rates
+        self.inner.embed(text).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_field_quoting() {
+        assert_eq!(csv_field("job_parse"), "job_parse");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn test_token_usage_from_response_shapes() {
+        let openai = serde_json::json!({"usage": {"prompt_tokens": 120, "completion_tokens": 45, "total_tokens": 165}});
+        assert_eq!(token_usage_from_response(&openai), Some(TokenUsage { prompt_tokens: 120, completion_tokens: 45 }));
+        let anthropic = serde_json::json!({"usage": {"input_tokens": 80, "output_tokens": 20}});
+        assert_eq!(token_usage_from_response(&anthropic), Some(TokenUsage { prompt_tokens: 80, completion_tokens: 20 }));
+        let ollama = serde_json::json!({"prompt_eval_count": 30, "eval_count": 12, "done": true});
+        assert_eq!(token_usage_from_response(&ollama), Some(TokenUsage { prompt_tokens: 30, completion_tokens: 12 }));
+        assert_eq!(token_usage_from_response(&serde_json::json!({"choices": []})), None);
+    }
+
+    #[tokio::test]
+    async fn test_reported_usage_is_collected_per_call() {
+        let (_, reported) = with_reported_usage(async {
+            report_token_usage(TokenUsage { prompt_tokens: 10, completion_tokens: 2 });
+            report_token_usage(TokenUsage { prompt_tokens: 5, completion_tokens: 1 });
+        })
+        .await;
+        assert_eq!(reported, Some(TokenUsage { prompt_tokens: 15, completion_tokens: 3 }));
+        let (_, empty) = with_reported_usage(async {}).await;
+        assert_eq!(empty, None);
+        // Reporting outside a logged call is ignored
+        report_token_usage(TokenUsage { prompt_tokens: 1, completion_tokens: 1 });
+    }
+
+    #[test]
+    fn test_local_providers_are_not_billed() {
+        assert!(!is_billed_provider("local"));
+        assert!(!is_billed_provider("ollama"));
+        assert!(is_billed_provider("openai"));
+    }
+
+    #[test]
+    fn test_estimate_cost_uses_most_specific_prefix() {
+        let mini = estimate_cost(Some("gpt-4o-mini"), 1_000_000, 1_000_000);
+        assert!((mini - 0.75).abs() < 1e-9);
+        let full = estimate_cost(Some("gpt-4o-2024-08-06"), 1_000_000, 0);
+        assert!((full - 2.50).abs() < 1e-9);
+        assert_eq!(estimate_cost(Some("llama-3-8b.gguf"), 1_000, 1_000), 0.0);
+        assert_eq!(estimate_cost(None, 1_000, 1_000), 0.0);
+    }
+}
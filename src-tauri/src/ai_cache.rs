@@ -1,11 +1,53 @@
+use crate::app_settings::{get_json_setting, set_json_setting};
+use crate::errors::{CareerBenchError, ValidationError};
 use rusqlite::Connection;
 use serde_json::Value;
 use sha2::{Digest, Sha256};
 use chrono::DateTime;
+use std::collections::HashMap;
 
 pub const CACHE_TTL_JOB_PARSE_DAYS: i64 = 90;
 pub const CACHE_TTL_RESUME_DAYS: i64 = 30;
 pub const CACHE_TTL_COVER_LETTER_DAYS: i64 = 30;
+pub const CACHE_TTL_WEEKLY_DIGEST_DAYS: i64 = 1;
+
+/// Built-in TTL for a purpose, used unless `AiSettings::cache_ttls` sets one
+pub fn default_cache_ttl_days(purpose: &str) -> Option<i64> {
+    match purpose {
+        "job_parse" | "jd_summary" | "job_tldr" | "company_fetch" => Some(CACHE_TTL_JOB_PARSE_DAYS),
+        "resume_generation" | "professional_summary" | "bullet_rewrite" | "star_rewrite"
+        | "profile_summary" | "profile_extract" => Some(CACHE_TTL_RESUME_DAYS),
+        "cover_letter_generation" | "cover_letter_adaptation" | "tighten_content" => Some(CACHE_TTL_COVER_LETTER_DAYS),
+        "weekly_digest" => Some(CACHE_TTL_WEEKLY_DIGEST_DAYS),
+        _ => None,
+    }
+}
+
+/// TTL in days for each purpose that has one: the configured value, else the built-in one
+pub fn effective_cache_ttls(configured: &HashMap<String, u32>) -> HashMap<String, u32> {
+    let mut ttls: HashMap<String, u32> = crate::ai::settings::AI_PURPOSES
+        .iter()
+        .chain(["company_fetch"].iter())
+        .filter_map(|purpose| {
+            default_cache_ttl_days(purpose).map(|days| (purpose.to_string(), days as u32))
+        })
+        .collect();
+    ttls.extend(configured.iter().map(|(purpose, days)| (purpose.clone(), *days)));
+    ttls
+}
+
+/// The TTL saved in AI settings for `purpose`, if any. Read straight from `ai_settings`
+/// so writes don't go through the full settings load (and the keychain).
+fn configured_ttl_days(conn: &Connection, purpose: &str) -> Option<i64> {
+    conn.query_row("SELECT cache_ttls FROM ai_settings WHERE id = 1", [], |row| {
+        row.get::<_, Option<String>>(0)
+    })
+    .ok()
+    .flatten()
+    .and_then(|json| serde_json::from_str::<HashMap<String, u32>>(&json).ok())
+    .and_then(|ttls| ttls.get(purpose).copied())
+    .map(i64::from)
+}
 
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
@@ -80,6 +122,8 @@ pub fn ai_cache_get(
 
     for row_result in rows {
         if let Ok(Some(entry)) = row_result {
+            // Best effort: the usage log may not exist in older or test databases
+            let _ = crate::ai::usage_log::record_cache_hit(conn, purpose, Some(&entry.model_name), now_iso);
             return Ok(Some(entry));
         }
     }
@@ -87,6 +131,8 @@ pub fn ai_cache_get(
     Ok(None)
 }
 
+/// Store a response. A TTL configured for `purpose` in AI settings takes precedence
+/// over `ttl_days`, which callers pass as the built-in default.
 pub fn ai_cache_put(
     conn: &Connection,
     purpose: &str,
@@ -102,10 +148,12 @@ pub fn ai_cache_put(
     let response_json = serde_json::to_string(response_payload)
         .map_err(|e| format!("Failed to serialize response: {}", e))?;
 
-    let expires_at = if let Some(days) = ttl_days {
+    let expires_at = if let Some(days) = configured_ttl_days(conn, purpose).or(ttl_days) {
         let now = DateTime::parse_from_rfc3339(now_iso)
             .map_err(|e| format!("Invalid date: {}", e))?;
-        let expires = now + chrono::Duration::days(days);
+        let expires = chrono::Duration::try_days(days)
+            .and_then(|ttl| now.checked_add_signed(ttl))
+            .ok_or_else(|| format!("Cache TTL of {} days is out of range", days))?;
         Some(expires.to_rfc3339())
     } else {
         None
@@ -128,6 +176,137 @@ pub fn ai_cache_put(
     Ok(())
 }
 
+/// Settings key for the semantic cache
+pub const SEMANTIC_CACHE_SETTING: &str = "semantic_cache";
+
+/// Optional second cache lookup by embedding similarity, for inputs that differ only
+/// slightly from a cached one. Off by default since it costs an embeddings call per miss.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SemanticCacheConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Lowest cosine similarity that counts as a hit
+    #[serde(default = "default_min_similarity")]
+    pub min_similarity: f32,
+}
+
+fn default_min_similarity() -> f32 {
+    0.97
+}
+
+impl Default for SemanticCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_similarity: default_min_similarity(),
+        }
+    }
+}
+
+pub fn load_semantic_cache_config(conn: &Connection) -> Result<SemanticCacheConfig, CareerBenchError> {
+    get_json_setting(conn, SEMANTIC_CACHE_SETTING)
+}
+
+pub fn save_semantic_cache_config(conn: &Connection, config: &SemanticCacheConfig) -> Result<(), CareerBenchError> {
+    if !(config.min_similarity > 0.0 && config.min_similarity <= 1.0) {
+        return Err(ValidationError::OutOfRange(
+            "Minimum similarity must be greater than 0 and at most 1".to_string(),
+        )
+        .into());
+    }
+    set_json_setting(conn, SEMANTIC_CACHE_SETTING, config)
+}
+
+/// Cosine similarity of two vectors; `None` when their lengths differ or either is all zeros
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> Option<f32> {
+    if a.len() != b.len() || a.is_empty() {
+        return None;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return None;
+    }
+    Some(dot / (norm_a * norm_b))
+}
+
+/// Attach the input embedding, and the embedding model that computed it, to the
+/// entries for `input_hash`
+pub fn ai_cache_set_embedding(
+    conn: &Connection,
+    purpose: &str,
+    input_hash: &str,
+    embedding: &[f32],
+    embedding_model: &str,
+) -> Result<(), String> {
+    let embedding_json = serde_json::to_string(embedding)
+        .map_err(|e| format!("Failed to serialize embedding: {}", e))?;
+    conn.execute(
+        "UPDATE ai_cache SET embedding = ?, embedding_model = ? WHERE purpose = ? AND input_hash = ?",
+        rusqlite::params![embedding_json, embedding_model, purpose, input_hash],
+    ).map_err(|e| format!("Failed to store embedding: {}", e))?;
+    Ok(())
+}
+
+/// Find the unexpired entry for `purpose` whose embedding is most similar to
+/// `embedding`, if that similarity is at least `min_similarity`. Meant for after
+/// `ai_cache_get` misses. Only entries produced by `model_name` and embedded by
+/// `embedding_model` are compared; entries stored without an embedding never match.
+pub fn ai_cache_get_similar(
+    conn: &Connection,
+    purpose: &str,
+    model_name: &str,
+    embedding_model: &str,
+    embedding: &[f32],
+    min_similarity: f32,
+    now_iso: &str,
+) -> Result<Option<(AiCacheEntry, f32)>, String> {
+    let mut stmt = conn.prepare(
+        "SELECT id, purpose, input_hash, model_name, request_payload, response_payload, created_at, expires_at, embedding
+         FROM ai_cache
+         WHERE purpose = ? AND model_name = ? AND embedding_model = ? AND embedding IS NOT NULL
+           AND (expires_at IS NULL OR expires_at >= ?)"
+    ).map_err(|e| format!("DB error: {}", e))?;
+
+    let rows = stmt.query_map([purpose, model_name, embedding_model, now_iso], |row| {
+        let stored: Vec<f32> = serde_json::from_str(row.get::<_, String>(8)?.as_str()).unwrap_or_default();
+        Ok((
+            AiCacheEntry {
+                id: row.get(0)?,
+                purpose: row.get(1)?,
+                input_hash: row.get(2)?,
+                model_name: row.get(3)?,
+                request_payload: serde_json::from_str(row.get::<_, String>(4)?.as_str())
+                    .unwrap_or(Value::Null),
+                response_payload: serde_json::from_str(row.get::<_, String>(5)?.as_str())
+                    .unwrap_or(Value::Null),
+                created_at: row.get(6)?,
+                expires_at: row.get(7)?,
+            },
+            stored,
+        ))
+    }).map_err(|e| format!("DB error: {}", e))?;
+
+    let mut best: Option<(AiCacheEntry, f32)> = None;
+    for row in rows {
+        let (entry, stored) = row.map_err(|e| format!("Failed to read row: {}", e))?;
+        let similarity = match cosine_similarity(embedding, &stored) {
+            Some(similarity) if similarity >= min_similarity => similarity,
+            _ => continue,
+        };
+        if best.as_ref().map_or(true, |(_, best_similarity)| similarity > *best_similarity) {
+            best = Some((entry, similarity));
+        }
+    }
+
+    if let Some((entry, _)) = &best {
+        let _ = crate::ai::usage_log::record_cache_hit(conn, purpose, Some(&entry.model_name), now_iso);
+    }
+    Ok(best)
+}
+
 /// Clear all cache entries for a specific purpose
 pub fn ai_cache_clear_purpose(conn: &Connection, purpose: &str) -> Result<u64, String> {
     let count = conn.execute(
@@ -137,6 +316,30 @@ pub fn ai_cache_clear_purpose(conn: &Connection, purpose: &str) -> Result<u64, S
     Ok(count as u64)
 }
 
+/// Make a purpose's entries expire within `within_minutes` instead of deleting them,
+/// so reads already in flight still hit. Entries that already expire sooner are left alone.
+/// Returns the number of entries whose expiry was moved.
+pub fn ai_cache_expire_purpose_soon(
+    conn: &Connection,
+    purpose: &str,
+    within_minutes: i64,
+    now_iso: &str,
+) -> Result<u64, String> {
+    if within_minutes < 0 {
+        return Err("within_minutes must not be negative".to_string());
+    }
+    let now = DateTime::parse_from_rfc3339(now_iso)
+        .map_err(|e| format!("Invalid date: {}", e))?;
+    let expires_at = (now + chrono::Duration::minutes(within_minutes)).to_rfc3339();
+
+    let count = conn.execute(
+        "UPDATE ai_cache SET expires_at = ?
+         WHERE purpose = ? AND (expires_at IS NULL OR expires_at > ?)",
+        rusqlite::params![expires_at, purpose, expires_at],
+    ).map_err(|e| format!("Failed to update cache expiry: {}", e))?;
+    Ok(count as u64)
+}
+
 /// Clear all cache entries
 pub fn ai_cache_clear_all(conn: &Connection) -> Result<u64, String> {
     let count = conn.execute("DELETE FROM ai_cache", [])
@@ -162,6 +365,19 @@ pub fn ai_cache_invalidate_job(conn: &Connection, _job_id: i64) -> Result<u64, S
     ai_cache_clear_purpose(conn, "job_parse")
 }
 
+/// Cache purposes derived from the user's experience, cleared whenever experience changes
+pub const EXPERIENCE_DEPENDENT_PURPOSES: &[&str] = &["profile_summary", "elevator_pitch"];
+
+/// Clear every entry for each of the given purposes
+/// Returns the total number of entries deleted
+pub fn ai_cache_invalidate_purposes(conn: &Connection, purposes: &[&str]) -> Result<u64, String> {
+    let mut total = 0u64;
+    for purpose in purposes {
+        total += ai_cache_clear_purpose(conn, purpose)?;
+    }
+    Ok(total)
+}
+
 /// Invalidate cache entries related to profile changes
 /// This clears resume and cover letter caches that depend on profile data
 pub fn ai_cache_invalidate_profile(conn: &Connection) -> Result<u64, String> {
@@ -179,6 +395,68 @@ pub fn ai_cache_invalidate_profile(conn: &Connection) -> Result<u64, String> {
     Ok(total)
 }
 
+/// Per-purpose breakdown of the cache, for deciding what to evict
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct PurposeCacheReport {
+    pub purpose: String,
+    pub entry_count: u64,
+    /// Stored request + response payload bytes
+    pub total_bytes: u64,
+    pub expired_count: u64,
+    pub oldest_entry: Option<String>,
+    pub newest_entry: Option<String>,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct CacheReport {
+    /// Largest purposes first
+    pub purposes: Vec<PurposeCacheReport>,
+    pub total_entries: u64,
+    pub total_bytes: u64,
+    pub expired_entries: u64,
+}
+
+/// Build a per-purpose cache report
+pub fn ai_cache_get_report(conn: &Connection, now_iso: &str) -> Result<CacheReport, String> {
+    let mut stmt = conn.prepare(
+        "SELECT purpose,
+                COUNT(*),
+                COALESCE(SUM(LENGTH(request_payload) + LENGTH(response_payload)), 0),
+                COALESCE(SUM(CASE WHEN expires_at IS NOT NULL AND expires_at < ?1 THEN 1 ELSE 0 END), 0),
+                MIN(created_at),
+                MAX(created_at)
+         FROM ai_cache
+         GROUP BY purpose
+         ORDER BY 3 DESC, purpose ASC"
+    ).map_err(|e| format!("Failed to prepare query: {}", e))?;
+
+    let rows = stmt.query_map([now_iso], |row| {
+        Ok(PurposeCacheReport {
+            purpose: row.get(0)?,
+            entry_count: row.get::<_, i64>(1)? as u64,
+            total_bytes: row.get::<_, i64>(2)? as u64,
+            expired_count: row.get::<_, i64>(3)? as u64,
+            oldest_entry: row.get(4)?,
+            newest_entry: row.get(5)?,
+        })
+    }).map_err(|e| format!("Failed to query: {}", e))?;
+
+    let mut purposes = Vec::new();
+    for row in rows {
+        purposes.push(row.map_err(|e| format!("Failed to read row: {}", e))?);
+    }
+
+    Ok(CacheReport {
+        total_entries: purposes.iter().map(|p| p.entry_count).sum(),
+        total_bytes: purposes.iter().map(|p| p.total_bytes).sum(),
+        expired_entries: purposes.iter().map(|p| p.expired_count).sum(),
+        purposes,
+    })
+}
+
+/// Cache usage for one purpose, as reported by `ai_cache_get_report`
+pub type PurposeStat = PurposeCacheReport;
+
 /// Get cache statistics
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 pub struct CacheStats {
@@ -188,6 +466,10 @@ pub struct CacheStats {
     pub expired_entries: u64,
     pub oldest_entry: Option<String>,
     pub newest_entry: Option<String>,
+    /// Largest purposes first, from `ai_cache_get_report`. Sizes there count request
+    /// and response payloads, unlike `total_size_bytes`.
+    #[serde(default)]
+    pub by_purpose: Vec<PurposeStat>,
 }
 
 /// Get statistics about the cache
@@ -206,20 +488,12 @@ pub fn ai_cache_get_stats(conn: &Connection, now_iso: &str) -> Result<CacheStats
         |row| row.get(0),
     ).map_err(|e| format!("Failed to calculate size: {}", e))?;
     
-    // Entries by purpose
-    let mut entries_by_purpose = std::collections::HashMap::new();
-    let mut stmt = conn.prepare(
-        "SELECT purpose, COUNT(*) FROM ai_cache GROUP BY purpose"
-    ).map_err(|e| format!("Failed to prepare query: {}", e))?;
-    
-    let rows = stmt.query_map([], |row| {
-        Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
-    }).map_err(|e| format!("Failed to query: {}", e))?;
-    
-    for row in rows {
-        let (purpose, count) = row.map_err(|e| format!("Failed to read row: {}", e))?;
-        entries_by_purpose.insert(purpose, count as u64);
-    }
+    // Entries, size and expired entries by purpose
+    let by_purpose = ai_cache_get_report(conn, now_iso)?.purposes;
+    let entries_by_purpose = by_purpose
+        .iter()
+        .map(|stat| (stat.purpose.clone(), stat.entry_count))
+        .collect();
     
     // Expired entries
     let expired_entries: i64 = conn.query_row(
@@ -249,6 +523,7 @@ pub fn ai_cache_get_stats(conn: &Connection, now_iso: &str) -> Result<CacheStats
         expired_entries: expired_entries as u64,
         oldest_entry,
         newest_entry,
+        by_purpose,
     })
 }
 
@@ -354,13 +629,58 @@ mod tests {
                 request_payload TEXT NOT NULL,
                 response_payload TEXT NOT NULL,
                 created_at TEXT NOT NULL,
-                expires_at TEXT
+                expires_at TEXT,
+                embedding TEXT,
+                embedding_model TEXT
             )",
             [],
         ).unwrap();
         conn
     }
 
+    #[test]
+    fn test_cosine_similarity() {
+        assert!((cosine_similarity(&[1.0, 2.0], &[2.0, 4.0]).unwrap() - 1.0).abs() < 1e-6);
+        assert!(cosine_similarity(&[1.0, 0.0], &[0.0, 1.0]).unwrap().abs() < 1e-6);
+        assert_eq!(cosine_similarity(&[1.0], &[1.0, 0.0]), None);
+        assert_eq!(cosine_similarity(&[0.0, 0.0], &[1.0, 0.0]), None);
+    }
+
+    #[test]
+    fn test_ai_cache_get_similar() {
+        let conn = setup_test_db();
+        let now = Utc::now();
+        let now_iso = now.to_rfc3339();
+        let request = json!({"input": "test"});
+
+        ai_cache_put(&conn, "job_parse", "a", "m", &request, &json!({"title": "A"}), Some(90), &now_iso).unwrap();
+        ai_cache_set_embedding(&conn, "job_parse", "a", &[1.0, 0.0, 0.0], "e").unwrap();
+        ai_cache_put(&conn, "job_parse", "b", "m", &request, &json!({"title": "B"}), Some(90), &now_iso).unwrap();
+        ai_cache_set_embedding(&conn, "job_parse", "b", &[0.9, 0.1, 0.0], "e").unwrap();
+        // No embedding stored, so never a semantic match
+        ai_cache_put(&conn, "job_parse", "c", "m", &request, &json!({"title": "C"}), Some(90), &now_iso).unwrap();
+        ai_cache_put(&conn, "resume_generation", "d", "m", &request, &json!({"title": "D"}), Some(90), &now_iso).unwrap();
+        ai_cache_set_embedding(&conn, "resume_generation", "d", &[0.0, 1.0, 0.0], "e").unwrap();
+
+        // Closest entry wins
+        let (entry, similarity) = ai_cache_get_similar(&conn, "job_parse", "m", "e", &[0.95, 0.05, 0.0], 0.9, &now_iso).unwrap().unwrap();
+        assert!(similarity > 0.99);
+        assert_eq!(entry.input_hash, "a");
+        assert_eq!(entry.response_payload, json!({"title": "A"}));
+
+        // Below the threshold, or in another purpose, is a miss
+        assert!(ai_cache_get_similar(&conn, "job_parse", "m", "e", &[0.0, 1.0, 0.0], 0.9, &now_iso).unwrap().is_none());
+        assert!(ai_cache_get_similar(&conn, "resume_generation", "m", "e", &[1.0, 0.0, 0.0], 0.9, &now_iso).unwrap().is_none());
+
+        // Entries from another model, or embedded by another embedding model, are a miss
+        assert!(ai_cache_get_similar(&conn, "job_parse", "other", "e", &[1.0, 0.0, 0.0], 0.9, &now_iso).unwrap().is_none());
+        assert!(ai_cache_get_similar(&conn, "job_parse", "m", "other", &[1.0, 0.0, 0.0], 0.9, &now_iso).unwrap().is_none());
+
+        // Expired entries are skipped
+        let later = (now + chrono::Duration::days(91)).to_rfc3339();
+        assert!(ai_cache_get_similar(&conn, "job_parse", "m", "e", &[1.0, 0.0, 0.0], 0.9, &later).unwrap().is_none());
+    }
+
     #[test]
     fn test_compute_input_hash() {
         let payload = json!({"test": "data"});
@@ -440,6 +760,42 @@ mod tests {
         assert!(entry.is_none());
     }
 
+    #[test]
+    fn test_configured_ttl_overrides_default() {
+        let conn = setup_test_db();
+        let now = Utc::now();
+        let now_iso = now.to_rfc3339();
+        let request = json!({"input": "test"});
+        let response = json!({"output": "result"});
+
+        // No settings table yet: the passed default applies
+        ai_cache_put(&conn, "cover_letter_generation", "a", "m", &request, &response, Some(CACHE_TTL_COVER_LETTER_DAYS), &now_iso).unwrap();
+        let in_two_weeks = (now + chrono::Duration::days(14)).to_rfc3339();
+        assert!(ai_cache_get(&conn, "cover_letter_generation", "a", &in_two_weeks).unwrap().is_some());
+
+        conn.execute("CREATE TABLE ai_settings (id INTEGER PRIMARY KEY, cache_ttls TEXT)", []).unwrap();
+        conn.execute(r#"INSERT INTO ai_settings (id, cache_ttls) VALUES (1, '{"cover_letter_generation": 7}')"#, []).unwrap();
+        ai_cache_put(&conn, "cover_letter_generation", "b", "m", &request, &response, Some(CACHE_TTL_COVER_LETTER_DAYS), &now_iso).unwrap();
+        ai_cache_put(&conn, "job_parse", "c", "m", &request, &response, Some(CACHE_TTL_JOB_PARSE_DAYS), &now_iso).unwrap();
+        assert!(ai_cache_get(&conn, "cover_letter_generation", "b", &in_two_weeks).unwrap().is_none());
+        assert!(ai_cache_get(&conn, "job_parse", "c", &in_two_weeks).unwrap().is_some());
+
+        // A TTL saved before it was capped can't overflow the expiry date
+        conn.execute(r#"UPDATE ai_settings SET cache_ttls = '{"job_parse": 4294967295}' WHERE id = 1"#, []).unwrap();
+        let err = ai_cache_put(&conn, "job_parse", "d", "m", &request, &response, None, &now_iso).unwrap_err();
+        assert!(err.contains("out of range"));
+    }
+
+    #[test]
+    fn test_effective_cache_ttls() {
+        let mut configured = HashMap::new();
+        configured.insert("cover_letter_generation".to_string(), 7);
+        let ttls = effective_cache_ttls(&configured);
+        assert_eq!(ttls.get("cover_letter_generation"), Some(&7));
+        assert_eq!(ttls.get("job_parse"), Some(&(CACHE_TTL_JOB_PARSE_DAYS as u32)));
+        assert_eq!(ttls.get("company_fetch"), Some(&(CACHE_TTL_JOB_PARSE_DAYS as u32)));
+    }
+
     #[test]
     fn test_ai_cache_miss() {
         let conn = setup_test_db();
@@ -449,4 +805,102 @@ mod tests {
         let entry = ai_cache_get(&conn, "nonexistent", "hash", &now).unwrap();
         assert!(entry.is_none());
     }
+
+    #[test]
+    fn test_expire_purpose_soon() {
+        let conn = setup_test_db();
+        let now = Utc::now();
+        let now_iso = now.to_rfc3339();
+        let request = json!({"input": "test"});
+        let response = json!({"output": "result"});
+
+        ai_cache_put(&conn, "resume_generation", "a", "m", &request, &response, Some(30), &now_iso).unwrap();
+        ai_cache_put(&conn, "resume_generation", "b", "m", &request, &response, None, &now_iso).unwrap();
+        ai_cache_put(&conn, "job_parse", "c", "m", &request, &response, Some(90), &now_iso).unwrap();
+
+        let touched = ai_cache_expire_purpose_soon(&conn, "resume_generation", 10, &now_iso).unwrap();
+        assert_eq!(touched, 2);
+
+        // Still readable inside the window
+        let soon = (now + chrono::Duration::minutes(5)).to_rfc3339();
+        assert!(ai_cache_get(&conn, "resume_generation", "a", &soon).unwrap().is_some());
+        assert!(ai_cache_get(&conn, "resume_generation", "b", &soon).unwrap().is_some());
+
+        // Expired once the window has passed; other purposes are untouched
+        let later = (now + chrono::Duration::minutes(11)).to_rfc3339();
+        assert!(ai_cache_get(&conn, "resume_generation", "a", &later).unwrap().is_none());
+        assert!(ai_cache_get(&conn, "resume_generation", "b", &later).unwrap().is_none());
+        assert!(ai_cache_get(&conn, "job_parse", "c", &later).unwrap().is_some());
+
+        // A longer window never extends an earlier expiry
+        assert_eq!(ai_cache_expire_purpose_soon(&conn, "resume_generation", 60, &now_iso).unwrap(), 0);
+        assert!(ai_cache_expire_purpose_soon(&conn, "resume_generation", -1, &now_iso).is_err());
+    }
+
+    #[test]
+    fn test_cache_stats_by_purpose() {
+        let conn = setup_test_db();
+        let now = Utc::now();
+        let now_iso = now.to_rfc3339();
+        let last_week = (now - chrono::Duration::days(7)).to_rfc3339();
+        let request = json!({"input": "test"});
+        let small = json!({"output": "short"});
+        let large = json!({"output": "a much longer response payload than the other one"});
+
+        ai_cache_put(&conn, "job_parse", "a", "m", &request, &small, Some(90), &now_iso).unwrap();
+        ai_cache_put(&conn, "resume_generation", "b", "m", &request, &large, Some(1), &last_week).unwrap();
+        ai_cache_put(&conn, "resume_generation", "c", "m", &request, &large, None, &now_iso).unwrap();
+
+        let stats = ai_cache_get_stats(&conn, &now_iso).unwrap();
+        assert_eq!(stats.by_purpose.len(), 2);
+        let resume = &stats.by_purpose[0];
+        assert_eq!(resume.purpose, "resume_generation");
+        assert_eq!(resume.entry_count, 2);
+        assert_eq!(resume.expired_count, 1);
+        assert_eq!(stats.by_purpose[1].expired_count, 0);
+
+        // The breakdown is the cache report's and adds up to the existing totals
+        let report = ai_cache_get_report(&conn, &now_iso).unwrap();
+        assert_eq!(stats.by_purpose.iter().map(|s| s.total_bytes).sum::<u64>(), report.total_bytes);
+        assert!(report.total_bytes > stats.total_size_bytes);
+        assert_eq!(stats.by_purpose.iter().map(|s| s.expired_count).sum::<u64>(), stats.expired_entries);
+        assert_eq!(stats.entries_by_purpose.get("resume_generation"), Some(&2));
+    }
+
+    #[test]
+    fn test_cache_report_by_purpose() {
+        let conn = setup_test_db();
+        let now = Utc::now();
+        let now_iso = now.to_rfc3339();
+        let last_week = (now - chrono::Duration::days(7)).to_rfc3339();
+        let request = json!({"input": "test"});
+        let response = json!({"output": "a much longer response payload"});
+
+        // Two job_parse entries, one of them written a week ago with a 1-day TTL
+        ai_cache_put(&conn, "job_parse", "a", "m", &request, &response, Some(1), &last_week).unwrap();
+        ai_cache_put(&conn, "job_parse", "b", "m", &request, &response, Some(90), &now_iso).unwrap();
+        // Three resume entries, two expired
+        ai_cache_put(&conn, "resume_generation", "c", "m", &request, &response, Some(1), &last_week).unwrap();
+        ai_cache_put(&conn, "resume_generation", "d", "m", &request, &response, Some(2), &last_week).unwrap();
+        ai_cache_put(&conn, "resume_generation", "e", "m", &request, &response, None, &now_iso).unwrap();
+
+        let report = ai_cache_get_report(&conn, &now_iso).unwrap();
+        assert_eq!(report.total_entries, 5);
+        assert_eq!(report.expired_entries, 3);
+        assert_eq!(report.purposes.len(), 2);
+
+        // Largest purpose first
+        let resume = &report.purposes[0];
+        assert_eq!(resume.purpose, "resume_generation");
+        assert_eq!(resume.entry_count, 3);
+        assert_eq!(resume.expired_count, 2);
+        assert_eq!(resume.oldest_entry.as_deref(), Some(last_week.as_str()));
+        assert_eq!(resume.newest_entry.as_deref(), Some(now_iso.as_str()));
+
+        let job_parse = &report.purposes[1];
+        assert_eq!(job_parse.entry_count, 2);
+        assert_eq!(job_parse.expired_count, 1);
+        assert_eq!(report.total_bytes, resume.total_bytes + job_parse.total_bytes);
+        assert!(resume.total_bytes > job_parse.total_bytes);
+    }
 }
@@ -1,8 +1,11 @@
 //! Analytics and insights module for job search metrics
 
 use crate::db::get_connection;
-use crate::errors::CareerBenchError;
+use crate::errors::{CareerBenchError, DatabaseError};
+use rusqlite::{Connection, OptionalExtension};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use chrono::NaiveDate;
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -49,6 +52,24 @@ pub struct Insight {
     pub actionable: bool,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SeniorityTimeToOffer {
+    pub seniority: String,
+    pub median_days: f64,
+    pub sample_size: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeToOfferStats {
+    pub min_days: Option<f64>,
+    pub median_days: Option<f64>,
+    pub max_days: Option<f64>,
+    pub sample_size: i64,
+    pub by_seniority: Vec<SeniorityTimeToOffer>,
+}
+
 /// Calculate conversion rates for applications
 pub fn calculate_conversion_rates(
     start_date: Option<&str>,
@@ -428,3 +449,475 @@ pub fn generate_insights(
 
     Ok(insights)
 }
+
+fn median_of_sorted(values: &[f64]) -> f64 {
+    let mid = values.len() / 2;
+    if values.len() % 2 == 0 {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    }
+}
+
+/// Nearest-rank percentile (0–100) of sorted values
+fn percentile_of_sorted(values: &[f64], percentile: f64) -> f64 {
+    let rank = ((percentile / 100.0) * values.len() as f64).ceil() as usize;
+    values[rank.clamp(1, values.len()) - 1]
+}
+
+/// Days from application to first OfferReceived event, overall and by job seniority
+pub fn get_time_to_offer_stats() -> Result<TimeToOfferStats, CareerBenchError> {
+    let conn = get_connection()?;
+    calculate_time_to_offer_stats(&conn)
+}
+
+pub fn calculate_time_to_offer_stats(conn: &Connection) -> Result<TimeToOfferStats, CareerBenchError> {
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT
+            j.seniority,
+            julianday(MIN(e.event_date)) - julianday(a.date_applied) as days_to_offer
+        FROM applications a
+        JOIN application_events e ON e.application_id = a.id AND e.event_type = 'OfferReceived'
+        LEFT JOIN jobs j ON a.job_id = j.id
+        WHERE a.date_applied IS NOT NULL
+        GROUP BY a.id
+        "#,
+    )?;
+
+    let rows = stmt.query_map([], |row| {
+        Ok((row.get::<_, Option<String>>(0)?, row.get::<_, Option<f64>>(1)?))
+    })?;
+
+    let mut all_days: Vec<f64> = Vec::new();
+    let mut by_seniority: BTreeMap<String, Vec<f64>> = BTreeMap::new();
+    for row_result in rows {
+        let (seniority, days) = row_result?;
+        // Skip unparseable dates and offers recorded before the application date
+        let days = match days {
+            Some(d) if d >= 0.0 => d,
+            _ => continue,
+        };
+        all_days.push(days);
+        let seniority = seniority
+            .filter(|s| !s.trim().is_empty())
+            .unwrap_or_else(|| "Unknown".to_string());
+        by_seniority.entry(seniority).or_default().push(days);
+    }
+
+    all_days.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let by_seniority = by_seniority
+        .into_iter()
+        .map(|(seniority, mut days)| {
+            days.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            SeniorityTimeToOffer {
+                seniority,
+                median_days: median_of_sorted(&days).round(),
+                sample_size: days.len() as i64,
+            }
+        })
+        .collect();
+
+    Ok(TimeToOfferStats {
+        min_days: all_days.first().map(|d| d.round()),
+        median_days: if all_days.is_empty() { None } else { Some(median_of_sorted(&all_days).round()) },
+        max_days: all_days.last().map(|d| d.round()),
+        sample_size: all_days.len() as i64,
+        by_seniority,
+    })
+}
+
+/// Statuses that end an application's pipeline
+const TERMINAL_STATUSES: &[&str] = &["Rejected", "Ghosted", "Withdrawn"];
+
+/// Age bucket bounds in days (inclusive); the last bucket is open-ended
+const AGE_BUCKETS: &[(&str, i64, Option<i64>)] = &[
+    ("0-7", 0, Some(7)),
+    ("8-14", 8, Some(14)),
+    ("15-30", 15, Some(30)),
+    ("30+", 31, None),
+];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AgeBucket {
+    pub label: String,
+    pub min_days: i64,
+    /// None for the open-ended oldest bucket
+    pub max_days: Option<i64>,
+    pub count: i64,
+}
+
+/// How long active applications have been open, by age bucket
+pub fn get_application_age_distribution(today: NaiveDate) -> Result<Vec<AgeBucket>, CareerBenchError> {
+    let conn = get_connection()?;
+    calculate_application_age_distribution(&conn, today)
+}
+
+/// Bucket active, non-terminal applications by days since `date_applied`
+/// (or `date_saved` for applications not yet sent), relative to `today`
+pub fn calculate_application_age_distribution(
+    conn: &Connection,
+    today: NaiveDate,
+) -> Result<Vec<AgeBucket>, CareerBenchError> {
+    let placeholders = vec!["?"; TERMINAL_STATUSES.len()].join(", ");
+    let mut stmt = conn.prepare(&format!(
+        "SELECT COALESCE(date_applied, date_saved) FROM applications
+         WHERE archived = 0 AND status NOT IN ({})",
+        placeholders
+    ))?;
+    let rows = stmt.query_map(rusqlite::params_from_iter(TERMINAL_STATUSES.iter()), |row| {
+        row.get::<_, Option<String>>(0)
+    })?;
+
+    let mut buckets: Vec<AgeBucket> = AGE_BUCKETS
+        .iter()
+        .map(|(label, min_days, max_days)| AgeBucket {
+            label: label.to_string(),
+            min_days: *min_days,
+            max_days: *max_days,
+            count: 0,
+        })
+        .collect();
+
+    for row_result in rows {
+        let started = match row_result?
+            .as_deref()
+            .and_then(|date| date.get(..10))
+            .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+        {
+            Some(date) => date,
+            None => continue,
+        };
+        // Dates in the future (clock skew, typos) count as brand new
+        let age = (today - started).num_days().max(0);
+        if let Some(bucket) = buckets
+            .iter_mut()
+            .find(|b| age >= b.min_days && !matches!(b.max_days, Some(max) if age > max))
+        {
+            bucket.count += 1;
+        }
+    }
+
+    Ok(buckets)
+}
+
+/// Prior stage rates used until there is enough history to trust the user's own
+const PRIOR_INTERVIEW_RATE: f64 = 0.15;
+const PRIOR_OFFER_RATE: f64 = 0.25;
+/// How many applications' worth of weight the priors carry
+const PRIOR_WEIGHT: f64 = 5.0;
+/// Resolved applications needed per stage before an estimate is considered reliable
+const MIN_STAGE_HISTORY: i64 = 5;
+/// Bounds on how far the channel can move the interview rate
+const CHANNEL_FACTOR_RANGE: (f64, f64) = (0.5, 2.0);
+/// Share of the normal chance kept by an application that has gone quiet
+const GHOSTED_FACTOR: f64 = 0.25;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OfferProbabilityFactor {
+    pub name: String,
+    /// A rate, or a multiplier for the channel and ghosted factors
+    pub value: f64,
+    /// Past applications behind the value; 0 for fixed factors
+    pub sample_size: i64,
+    pub description: String,
+}
+
+/// Estimated chance that an application ends in an offer.
+///
+/// The estimate multiplies the stages still ahead of the application:
+/// - interview rate: past applications that reached an interview, out of those
+///   that reached one or closed without one (still-pending ones are left out)
+/// - channel factor: the interview rate for this application's channel over the
+///   overall rate, clamped to 0.5–2.0
+/// - offer rate: past interviews that became offers, out of those resolved
+/// - ghosted factor: 0.25 for applications marked Ghosted
+///
+/// Each rate is blended with a fixed prior (15% interview, 25% offer) weighted as
+/// five applications, so it leans on the prior until the user has history of their
+/// own. Offers score 1 and rejected or withdrawn applications 0.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OfferProbability {
+    pub application_id: i64,
+    pub status: String,
+    pub probability: f64,
+    /// Set when a stage rate rests on fewer than five resolved applications
+    pub low_confidence: bool,
+    pub factors: Vec<OfferProbabilityFactor>,
+}
+
+/// Successes over resolved attempts for one stage
+#[derive(Debug, Default, Clone, Copy)]
+struct StageHistory {
+    reached: i64,
+    resolved: i64,
+}
+
+impl StageHistory {
+    fn record(&mut self, reached: bool, closed: bool) {
+        if reached || closed {
+            self.resolved += 1;
+        }
+        if reached {
+            self.reached += 1;
+        }
+    }
+
+    fn smoothed_rate(&self, prior: f64) -> f64 {
+        (self.reached as f64 + PRIOR_WEIGHT * prior) / (self.resolved as f64 + PRIOR_WEIGHT)
+    }
+}
+
+/// Offer probability for one application
+pub fn get_offer_probability(application_id: i64) -> Result<OfferProbability, CareerBenchError> {
+    let conn = get_connection()?;
+    estimate_offer_probability(&conn, application_id)
+}
+
+/// Offer probability for one application from the user's other applications
+pub fn estimate_offer_probability(
+    conn: &Connection,
+    application_id: i64,
+) -> Result<OfferProbability, CareerBenchError> {
+    let (status, channel): (String, Option<String>) = conn
+        .query_row(
+            "SELECT status, channel FROM applications WHERE id = ?",
+            [application_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()?
+        .ok_or_else(|| DatabaseError::NotFound(format!("Application {} not found", application_id)))?;
+    let channel = channel.map(|c| c.trim().to_string()).filter(|c| !c.is_empty());
+
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT
+            a.status,
+            a.channel,
+            a.date_applied IS NOT NULL OR a.status NOT IN ('Saved', 'Draft'),
+            a.status IN ('Interviewing', 'Offer') OR EXISTS (
+                SELECT 1 FROM application_events e
+                WHERE e.application_id = a.id
+                  AND (e.to_status IN ('Interviewing', 'Offer') OR e.event_type = 'OfferReceived')
+            ),
+            a.status = 'Offer' OR EXISTS (
+                SELECT 1 FROM application_events e
+                WHERE e.application_id = a.id
+                  AND (e.to_status = 'Offer' OR e.event_type = 'OfferReceived')
+            )
+        FROM applications a
+        WHERE a.archived = 0 AND a.id != ?
+        "#,
+    )?;
+    let rows = stmt.query_map([application_id], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, Option<String>>(1)?,
+            row.get::<_, bool>(2)?,
+            row.get::<_, bool>(3)?,
+            row.get::<_, bool>(4)?,
+        ))
+    })?;
+
+    let mut interviews = StageHistory::default();
+    let mut channel_interviews = StageHistory::default();
+    let mut offers = StageHistory::default();
+    for row_result in rows {
+        let (past_status, past_channel, applied, interviewed, offered) = row_result?;
+        let closed = TERMINAL_STATUSES.contains(&past_status.as_str());
+        if applied {
+            interviews.record(interviewed, closed);
+            if channel.is_some() && past_channel.as_deref().map(str::trim) == channel.as_deref() {
+                channel_interviews.record(interviewed, closed);
+            }
+        }
+        if interviewed {
+            offers.record(offered, closed);
+        }
+    }
+
+    let interview_rate = interviews.smoothed_rate(PRIOR_INTERVIEW_RATE);
+    let offer_rate = offers.smoothed_rate(PRIOR_OFFER_RATE);
+    let offer_factor = OfferProbabilityFactor {
+        name: "Interview to offer rate".to_string(),
+        value: offer_rate,
+        sample_size: offers.resolved,
+        description: format!("{} of {} resolved interviews led to an offer", offers.reached, offers.resolved),
+    };
+
+    let mut factors = Vec::new();
+    let (probability, low_confidence) = match status.as_str() {
+        "Offer" => (1.0, false),
+        "Rejected" | "Withdrawn" => (0.0, false),
+        "Interviewing" => {
+            factors.push(offer_factor);
+            (offer_rate, offers.resolved < MIN_STAGE_HISTORY)
+        }
+        _ => {
+            factors.push(OfferProbabilityFactor {
+                name: "Application to interview rate".to_string(),
+                value: interview_rate,
+                sample_size: interviews.resolved,
+                description: format!(
+                    "{} of {} resolved applications reached an interview",
+                    interviews.reached, interviews.resolved
+                ),
+            });
+            let mut probability = interview_rate * offer_rate;
+
+            if let Some(channel) = &channel {
+                let channel_rate = channel_interviews.smoothed_rate(interview_rate);
+                let factor = (channel_rate / interview_rate).clamp(CHANNEL_FACTOR_RANGE.0, CHANNEL_FACTOR_RANGE.1);
+                probability *= factor;
+                factors.push(OfferProbabilityFactor {
+                    name: "Channel".to_string(),
+                    value: factor,
+                    sample_size: channel_interviews.resolved,
+                    description: format!(
+                        "'{}': {} of {} resolved applications reached an interview",
+                        channel, channel_interviews.reached, channel_interviews.resolved
+                    ),
+                });
+            }
+            factors.push(offer_factor);
+
+            if status == "Ghosted" {
+                probability *= GHOSTED_FACTOR;
+                factors.push(OfferProbabilityFactor {
+                    name: "Ghosted".to_string(),
+                    value: GHOSTED_FACTOR,
+                    sample_size: 0,
+                    description: "No response for a while; few of these come back".to_string(),
+                });
+            }
+
+            (
+                probability,
+                interviews.resolved < MIN_STAGE_HISTORY || offers.resolved < MIN_STAGE_HISTORY,
+            )
+        }
+    };
+
+    Ok(OfferProbability {
+        application_id,
+        status,
+        probability: probability.clamp(0.0, 1.0),
+        low_confidence,
+        factors,
+    })
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ResponseTimeStats {
+    pub median_days: Option<f64>,
+    pub p90_days: Option<f64>,
+    /// Applications with a response, which the stats are based on
+    pub sample_size: i64,
+    /// Applications still in Applied with no response; not part of the stats
+    pub still_waiting: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ChannelResponseTimes {
+    pub channel: String,
+    pub stats: ResponseTimeStats,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ResponseTimeReport {
+    pub overall: ResponseTimeStats,
+    pub by_channel: Vec<ChannelResponseTimes>,
+}
+
+impl ResponseTimeStats {
+    fn from_days(mut days: Vec<f64>, still_waiting: i64) -> Self {
+        days.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let (median_days, p90_days) = if days.is_empty() {
+            (None, None)
+        } else {
+            (Some(median_of_sorted(&days).round()), Some(percentile_of_sorted(&days, 90.0).round()))
+        };
+        Self {
+            median_days,
+            p90_days,
+            sample_size: days.len() as i64,
+            still_waiting,
+        }
+    }
+}
+
+/// How long companies take to respond, overall and by channel
+pub fn get_response_times() -> Result<ResponseTimeReport, CareerBenchError> {
+    let conn = get_connection()?;
+    calculate_response_times(&conn)
+}
+
+/// Days from `date_applied` to the first response: a status change to Interviewing
+/// or a scheduled or completed interview
+pub fn calculate_response_times(conn: &Connection) -> Result<ResponseTimeReport, CareerBenchError> {
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT
+            a.channel,
+            a.status,
+            julianday((
+                SELECT MIN(e.event_date) FROM application_events e
+                WHERE e.application_id = a.id
+                  AND ((e.event_type = 'StatusChanged' AND e.to_status = 'Interviewing')
+                       OR e.event_type IN ('InterviewScheduled', 'InterviewCompleted'))
+            )) - julianday(a.date_applied) as days_to_response
+        FROM applications a
+        WHERE a.archived = 0 AND a.date_applied IS NOT NULL
+        "#,
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, Option<String>>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, Option<f64>>(2)?,
+        ))
+    })?;
+
+    let mut all_days: Vec<f64> = Vec::new();
+    let mut waiting = 0;
+    let mut by_channel: BTreeMap<String, (Vec<f64>, i64)> = BTreeMap::new();
+    for row_result in rows {
+        let (channel, status, days) = row_result?;
+        let channel = channel
+            .map(|c| c.trim().to_string())
+            .filter(|c| !c.is_empty())
+            .unwrap_or_else(|| "Unknown".to_string());
+        match days {
+            Some(d) if d >= 0.0 => {
+                all_days.push(d);
+                by_channel.entry(channel).or_default().0.push(d);
+            }
+            // Responses recorded before the application date are typos; skip them
+            Some(_) => {}
+            None if status == "Applied" => {
+                waiting += 1;
+                by_channel.entry(channel).or_default().1 += 1;
+            }
+            // Closed without an interview: they answered with a no, or never will
+            None => {}
+        }
+    }
+
+    Ok(ResponseTimeReport {
+        overall: ResponseTimeStats::from_days(all_days, waiting),
+        by_channel: by_channel
+            .into_iter()
+            .map(|(channel, (days, still_waiting))| ChannelResponseTimes {
+                channel,
+                stats: ResponseTimeStats::from_days(days, still_waiting),
+            })
+            .collect(),
+    })
+}
@@ -0,0 +1,64 @@
+//! General application settings stored as key/value pairs
+//!
+//! Values are stored as JSON so each feature can keep its own typed settings
+//! struct without needing a schema change.
+
+use crate::errors::{CareerBenchError, ConfigurationError};
+use rusqlite::Connection;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Get a raw setting value
+pub fn get_setting(conn: &Connection, key: &str) -> Result<Option<String>, CareerBenchError> {
+    let result = conn.query_row(
+        "SELECT value FROM app_settings WHERE key = ?",
+        [key],
+        |row| row.get::<_, String>(0),
+    );
+
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Set a raw setting value, replacing any existing value
+pub fn set_setting(conn: &Connection, key: &str, value: &str) -> Result<(), CareerBenchError> {
+    let now = chrono::Utc::now().to_rfc3339();
+
+    conn.execute(
+        "INSERT INTO app_settings (key, value, updated_at) VALUES (?, ?, ?)
+         ON CONFLICT(key) DO UPDATE SET
+            value = excluded.value,
+            updated_at = excluded.updated_at",
+        rusqlite::params![key, value, now],
+    )?;
+
+    Ok(())
+}
+
+/// Load a JSON setting, falling back to the type's default when unset or unreadable
+pub fn get_json_setting<T: DeserializeOwned + Default>(
+    conn: &Connection,
+    key: &str,
+) -> Result<T, CareerBenchError> {
+    match get_setting(conn, key)? {
+        Some(value) => Ok(serde_json::from_str(&value).unwrap_or_else(|e| {
+            log::warn!("Ignoring invalid value for setting '{}': {}", key, e);
+            T::default()
+        })),
+        None => Ok(T::default()),
+    }
+}
+
+/// Store a setting as JSON
+pub fn set_json_setting<T: Serialize>(
+    conn: &Connection,
+    key: &str,
+    value: &T,
+) -> Result<(), CareerBenchError> {
+    let json = serde_json::to_string(value)
+        .map_err(|e| ConfigurationError::InvalidValue(format!("Failed to serialize setting '{}': {}", key, e)))?;
+    set_setting(conn, key, &json)
+}
@@ -0,0 +1,212 @@
+//! Suggested application priority from match score, deadline and salary fit
+//!
+//! Purely code-based: each signal adds points and the total maps to a
+//! High/Medium/Low suggestion with a human-readable rationale.
+
+use crate::app_settings::get_json_setting;
+use crate::commands::ParsedJob;
+use crate::compensation::{
+    parse_and_normalize_comp, CurrencyRates, SalaryExpectation, CURRENCY_RATES_SETTING,
+    SALARY_EXPECTATION_SETTING,
+};
+use crate::errors::{CareerBenchError, DatabaseError};
+use crate::job_match::{calculate_job_match, load_profile_skill_names};
+use chrono::NaiveDate;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+
+/// Match scores at or above these earn 2 and 1 points respectively
+const STRONG_MATCH_SCORE: f64 = 70.0;
+const FAIR_MATCH_SCORE: f64 = 40.0;
+/// Deadlines within these many days earn 2 and 1 points respectively
+const URGENT_DEADLINE_DAYS: i64 = 7;
+const NEAR_DEADLINE_DAYS: i64 = 21;
+/// Point totals needed for each suggestion
+const HIGH_PRIORITY_POINTS: i32 = 4;
+const MEDIUM_PRIORITY_POINTS: i32 = 2;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PrioritySuggestion {
+    pub application_id: i64,
+    /// "High", "Medium" or "Low"
+    pub priority: String,
+    pub points: i32,
+    pub match_score: Option<f64>,
+    pub days_until_deadline: Option<i64>,
+    /// None when either the job's salary or the user's expectation is unknown
+    pub salary_meets_expectation: Option<bool>,
+    pub rationale: String,
+}
+
+/// Combine the individual signals into a suggestion
+pub fn score_priority(
+    application_id: i64,
+    match_score: Option<f64>,
+    days_until_deadline: Option<i64>,
+    salary_meets_expectation: Option<bool>,
+) -> PrioritySuggestion {
+    let mut points = 0;
+    let mut reasons = Vec::new();
+
+    match match_score {
+        Some(score) if score >= STRONG_MATCH_SCORE => {
+            points += 2;
+            reasons.push(format!("strong skills match ({:.0}%)", score));
+        }
+        Some(score) if score >= FAIR_MATCH_SCORE => {
+            points += 1;
+            reasons.push(format!("partial skills match ({:.0}%)", score));
+        }
+        Some(score) => reasons.push(format!("weak skills match ({:.0}%)", score)),
+        None => {
+            // Don't punish jobs that simply haven't been parsed yet
+            points += 1;
+            reasons.push("match score unknown (job not parsed)".to_string());
+        }
+    }
+
+    match days_until_deadline {
+        Some(days) if days < 0 => reasons.push("application deadline has passed".to_string()),
+        Some(days) if days <= URGENT_DEADLINE_DAYS => {
+            points += 2;
+            reasons.push(format!("deadline in {} day{}", days, if days == 1 { "" } else { "s" }));
+        }
+        Some(days) if days <= NEAR_DEADLINE_DAYS => {
+            points += 1;
+            reasons.push(format!("deadline in {} days", days));
+        }
+        Some(days) => reasons.push(format!("deadline is {} days away", days)),
+        None => {}
+    }
+
+    match salary_meets_expectation {
+        Some(true) => {
+            points += 1;
+            reasons.push("salary meets your expectation".to_string());
+        }
+        Some(false) => {
+            points -= 1;
+            reasons.push("salary is below your expectation".to_string());
+        }
+        None => {}
+    }
+
+    let priority = if points >= HIGH_PRIORITY_POINTS {
+        "High"
+    } else if points >= MEDIUM_PRIORITY_POINTS {
+        "Medium"
+    } else {
+        "Low"
+    };
+
+    let mut rationale = reasons.join("; ");
+    if let Some(first) = rationale.get(..1) {
+        rationale = format!("{}{}", first.to_uppercase(), &rationale[1..]);
+    }
+
+    PrioritySuggestion {
+        application_id,
+        priority: priority.to_string(),
+        points,
+        match_score,
+        days_until_deadline,
+        salary_meets_expectation,
+        rationale,
+    }
+}
+
+/// Suggest a priority for an application from its job's derived metrics
+pub fn suggest_application_priority(
+    conn: &Connection,
+    application_id: i64,
+    today: NaiveDate,
+) -> Result<PrioritySuggestion, CareerBenchError> {
+    let row = conn.query_row(
+        "SELECT j.parsed_json, j.application_deadline, j.salary_text
+         FROM applications a
+         JOIN jobs j ON a.job_id = j.id
+         WHERE a.id = ?",
+        [application_id],
+        |row| {
+            Ok((
+                row.get::<_, Option<String>>(0)?,
+                row.get::<_, Option<String>>(1)?,
+                row.get::<_, Option<String>>(2)?,
+            ))
+        },
+    );
+    let (parsed_json, deadline, salary_text) = match row {
+        Ok(row) => row,
+        Err(rusqlite::Error::QueryReturnedNoRows) => {
+            return Err(DatabaseError::NotFound(format!("Application {} not found", application_id)).into())
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let match_score = match parsed_json.and_then(|json| serde_json::from_str::<ParsedJob>(&json).ok()) {
+        Some(parsed_job) => {
+            let profile_skills = load_profile_skill_names(conn)?;
+            Some(calculate_job_match(&parsed_job, &profile_skills).score)
+        }
+        None => None,
+    };
+
+    let days_until_deadline = deadline
+        .and_then(|date| NaiveDate::parse_from_str(&date, "%Y-%m-%d").ok())
+        .map(|date| (date - today).num_days());
+
+    let expectation: SalaryExpectation = get_json_setting(conn, SALARY_EXPECTATION_SETTING)?;
+    let salary_meets_expectation = match (expectation.minimum, salary_text) {
+        (Some(minimum), Some(salary_text)) => {
+            let rates: CurrencyRates = get_json_setting(conn, CURRENCY_RATES_SETTING)?;
+            let currency = expectation
+                .currency
+                .clone()
+                .unwrap_or_else(|| rates.reference_currency.clone());
+            parse_and_normalize_comp(&salary_text, &currency, &rates)
+                .normalized_amount
+                .map(|amount| amount >= minimum)
+        }
+        _ => None,
+    };
+
+    Ok(score_priority(application_id, match_score, days_until_deadline, salary_meets_expectation))
+}
+
+/// Store a suggested priority on the application
+pub fn accept_priority_suggestion(
+    conn: &Connection,
+    application_id: i64,
+    priority: &str,
+) -> Result<(), CareerBenchError> {
+    let now = chrono::Utc::now().to_rfc3339();
+    let updated = conn.execute(
+        "UPDATE applications SET priority = ?, updated_at = ? WHERE id = ?",
+        rusqlite::params![priority, now, application_id],
+    )?;
+    if updated == 0 {
+        return Err(DatabaseError::NotFound(format!("Application {} not found", application_id)).into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unknown_signals_are_neutral() {
+        let suggestion = score_priority(1, None, None, None);
+        assert_eq!(suggestion.priority, "Low");
+        assert_eq!(suggestion.points, 1);
+        assert!(suggestion.rationale.starts_with("Match score unknown"));
+    }
+
+    #[test]
+    fn test_salary_below_expectation_lowers_priority() {
+        assert_eq!(score_priority(1, Some(80.0), Some(30), None).priority, "Medium");
+        assert_eq!(score_priority(1, Some(80.0), Some(30), Some(false)).priority, "Low");
+        assert_eq!(score_priority(1, Some(80.0), Some(10), Some(true)).priority, "High");
+    }
+}
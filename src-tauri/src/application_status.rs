@@ -0,0 +1,157 @@
+//! Allowed application status transitions
+//!
+//! Applications move forward through the pipeline:
+//!
+//! | From         | Allowed next statuses                                  |
+//! |--------------|--------------------------------------------------------|
+//! | Saved        | Draft, Applied, Withdrawn                              |
+//! | Draft        | Saved, Applied, Withdrawn                              |
+//! | Applied      | Interviewing, Offer, Rejected, Ghosted, Withdrawn      |
+//! | Interviewing | Offer, Rejected, Ghosted, Withdrawn                    |
+//! | Offer        | Rejected, Withdrawn                                    |
+//! | Ghosted      | Interviewing, Offer, Rejected, Withdrawn               |
+//! | Rejected     | (none)                                                 |
+//! | Withdrawn    | (none)                                                 |
+//!
+//! Anything else needs an explicit override, meant for correcting mistakes.
+
+use crate::errors::{CareerBenchError, ValidationError};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ApplicationStatus {
+    Saved,
+    Draft,
+    Applied,
+    Interviewing,
+    Offer,
+    Rejected,
+    Ghosted,
+    Withdrawn,
+}
+
+impl ApplicationStatus {
+    pub const ALL: [ApplicationStatus; 8] = [
+        Self::Saved,
+        Self::Draft,
+        Self::Applied,
+        Self::Interviewing,
+        Self::Offer,
+        Self::Rejected,
+        Self::Ghosted,
+        Self::Withdrawn,
+    ];
+
+    /// Parse a stored status name; names are case-sensitive, as stored
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|status| status.as_str() == value)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Saved => "Saved",
+            Self::Draft => "Draft",
+            Self::Applied => "Applied",
+            Self::Interviewing => "Interviewing",
+            Self::Offer => "Offer",
+            Self::Rejected => "Rejected",
+            Self::Ghosted => "Ghosted",
+            Self::Withdrawn => "Withdrawn",
+        }
+    }
+
+    /// SQL expression ranking `column` by pipeline position, for ORDER BY; statuses
+    /// outside `ALL` sort last
+    pub fn pipeline_rank_sql(column: &str) -> String {
+        let cases: Vec<String> = Self::ALL
+            .iter()
+            .enumerate()
+            .map(|(rank, status)| format!("WHEN '{}' THEN {}", status.as_str(), rank))
+            .collect();
+        format!("CASE {} {} ELSE {} END", column, cases.join(" "), Self::ALL.len())
+    }
+
+    /// Statuses an application can move to without an override
+    pub fn allowed_next(&self) -> &'static [ApplicationStatus] {
+        use ApplicationStatus::*;
+        match self {
+            Saved => &[Draft, Applied, Withdrawn],
+            Draft => &[Saved, Applied, Withdrawn],
+            Applied => &[Interviewing, Offer, Rejected, Ghosted, Withdrawn],
+            Interviewing => &[Offer, Rejected, Ghosted, Withdrawn],
+            Offer => &[Rejected, Withdrawn],
+            Ghosted => &[Interviewing, Offer, Rejected, Withdrawn],
+            Rejected | Withdrawn => &[],
+        }
+    }
+}
+
+/// Check that an application may move from `from` to `to`.
+///
+/// The target must be a known status. Staying put is always fine, and applications
+/// whose current status predates this list can move anywhere. `allow_override`
+/// permits any move between known statuses.
+pub fn validate_status_transition(from: &str, to: &str, allow_override: bool) -> Result<(), CareerBenchError> {
+    let target = match ApplicationStatus::parse(to) {
+        Some(status) => status,
+        None => {
+            let known: Vec<&str> = ApplicationStatus::ALL.iter().map(|s| s.as_str()).collect();
+            return Err(ValidationError::InvalidFormat(format!(
+                "Unknown status '{}'. Expected one of: {}",
+                to,
+                known.join(", ")
+            ))
+            .into());
+        }
+    };
+    let current = match ApplicationStatus::parse(from) {
+        Some(status) => status,
+        None => return Ok(()),
+    };
+    if current == target || allow_override || current.allowed_next().contains(&target) {
+        return Ok(());
+    }
+
+    let allowed: Vec<&str> = current.allowed_next().iter().map(|s| s.as_str()).collect();
+    let allowed = if allowed.is_empty() {
+        format!("{} is a final status", current.as_str())
+    } else {
+        format!("From {} it can move to {}", current.as_str(), allowed.join(", "))
+    };
+    Err(ValidationError::BusinessRule(format!(
+        "Can't move an application from {} to {}. {}. Use an override to correct a mistake.",
+        current.as_str(),
+        target.as_str(),
+        allowed
+    ))
+    .into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_forward_transitions_are_allowed() {
+        assert!(validate_status_transition("Saved", "Applied", false).is_ok());
+        assert!(validate_status_transition("Applied", "Interviewing", false).is_ok());
+        assert!(validate_status_transition("Interviewing", "Offer", false).is_ok());
+        assert!(validate_status_transition("Ghosted", "Interviewing", false).is_ok());
+        assert!(validate_status_transition("Rejected", "Rejected", false).is_ok());
+    }
+
+    #[test]
+    fn test_backwards_transition_needs_override() {
+        let err = validate_status_transition("Rejected", "Saved", false).unwrap_err();
+        assert!(err.to_string().contains("Rejected is a final status"));
+        assert!(validate_status_transition("Interviewing", "Saved", false).is_err());
+        assert!(validate_status_transition("Rejected", "Saved", true).is_ok());
+    }
+
+    #[test]
+    fn test_unknown_statuses() {
+        assert!(validate_status_transition("Applied", "Hired", true).is_err());
+        // Legacy statuses can still move to a known one
+        assert!(validate_status_transition("Phone Screen", "Interviewing", false).is_ok());
+    }
+}
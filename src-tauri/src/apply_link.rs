@@ -0,0 +1,138 @@
+//! "Apply now" links: a job's posting URL with an optional tracking param appended
+//!
+//! The param is added only to the link handed to the browser; the stored posting URL
+//! is never changed.
+
+use crate::app_settings::{get_json_setting, set_json_setting};
+use crate::errors::{CareerBenchError, DatabaseError, ValidationError};
+use reqwest::Url;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+
+/// Settings key for the tracking param added to apply links
+pub const APPLY_LINK_TRACKING_SETTING: &str = "apply_link_tracking";
+const DEFAULT_TRACKING_PARAM: &str = "utm_source";
+
+/// Tracking param appended to apply links; nothing is appended while `value` is unset
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplyLinkTracking {
+    #[serde(default = "default_tracking_param")]
+    pub param_name: String,
+    #[serde(default)]
+    pub value: Option<String>,
+}
+
+fn default_tracking_param() -> String {
+    DEFAULT_TRACKING_PARAM.to_string()
+}
+
+impl Default for ApplyLinkTracking {
+    fn default() -> Self {
+        Self {
+            param_name: default_tracking_param(),
+            value: None,
+        }
+    }
+}
+
+pub fn load_apply_link_tracking(conn: &Connection) -> Result<ApplyLinkTracking, CareerBenchError> {
+    get_json_setting(conn, APPLY_LINK_TRACKING_SETTING)
+}
+
+pub fn save_apply_link_tracking(conn: &Connection, tracking: &ApplyLinkTracking) -> Result<(), CareerBenchError> {
+    let param_name = tracking.param_name.trim();
+    if param_name.is_empty() {
+        return Err(ValidationError::MissingField("Tracking param name is required".to_string()).into());
+    }
+    if !param_name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '.') {
+        return Err(ValidationError::InvalidFormat(format!(
+            "Tracking param name '{}' may only contain letters, digits, '_', '-' and '.'",
+            param_name
+        ))
+        .into());
+    }
+
+    let tracking = ApplyLinkTracking {
+        param_name: param_name.to_string(),
+        value: tracking
+            .value
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string),
+    };
+    set_json_setting(conn, APPLY_LINK_TRACKING_SETTING, &tracking)
+}
+
+/// Validate a posting URL and append the tracking param, unless the URL already has it
+pub fn with_tracking_param(posting_url: &str, tracking: &ApplyLinkTracking) -> Result<String, CareerBenchError> {
+    let posting_url = posting_url.trim();
+    if posting_url.is_empty() {
+        return Err(ValidationError::MissingField("This job has no posting URL".to_string()).into());
+    }
+
+    let mut url = Url::parse(posting_url)
+        .map_err(|e| ValidationError::InvalidFormat(format!("Invalid posting URL '{}': {}", posting_url, e)))?;
+    if !matches!(url.scheme(), "http" | "https") || url.host_str().is_none() {
+        return Err(ValidationError::InvalidFormat(format!(
+            "Posting URL '{}' must be an http(s) web address",
+            posting_url
+        ))
+        .into());
+    }
+
+    if let Some(value) = tracking.value.as_deref().filter(|value| !value.is_empty()) {
+        let already_tagged = url.query_pairs().any(|(name, _)| name == tracking.param_name.as_str());
+        if !already_tagged {
+            url.query_pairs_mut().append_pair(&tracking.param_name, value);
+        }
+    }
+    Ok(url.to_string())
+}
+
+/// The URL to open when applying to a job, with the configured tracking param
+pub fn build_apply_url(conn: &Connection, job_id: i64) -> Result<String, CareerBenchError> {
+    let posting_url: Option<String> = match conn.query_row(
+        "SELECT posting_url FROM jobs WHERE id = ?",
+        [job_id],
+        |row| row.get(0),
+    ) {
+        Ok(url) => url,
+        Err(rusqlite::Error::QueryReturnedNoRows) => {
+            return Err(DatabaseError::NotFound(format!("Job {} not found", job_id)).into())
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let tracking = load_apply_link_tracking(conn)?;
+    with_tracking_param(posting_url.as_deref().unwrap_or(""), &tracking)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tracking(value: &str) -> ApplyLinkTracking {
+        ApplyLinkTracking {
+            param_name: "utm_source".to_string(),
+            value: Some(value.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_with_tracking_param_keeps_existing_query() {
+        let url = with_tracking_param("https://jobs.example.com/roles/42?ref=board", &tracking("careerbench")).unwrap();
+        assert_eq!(url, "https://jobs.example.com/roles/42?ref=board&utm_source=careerbench");
+
+        let tagged = with_tracking_param("https://jobs.example.com/roles/42?utm_source=linkedin", &tracking("careerbench")).unwrap();
+        assert_eq!(tagged, "https://jobs.example.com/roles/42?utm_source=linkedin");
+    }
+
+    #[test]
+    fn test_with_tracking_param_rejects_non_web_urls() {
+        assert!(with_tracking_param("mailto:jobs@example.com", &tracking("careerbench")).is_err());
+        assert!(with_tracking_param("javascript:alert(1)", &tracking("careerbench")).is_err());
+        assert!(with_tracking_param("jobs.example.com/roles/42", &tracking("careerbench")).is_err());
+    }
+}
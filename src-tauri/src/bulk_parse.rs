@@ -0,0 +1,115 @@
+//! Bulk parsing of jobs that have a description but were never parsed
+//!
+//! `parse_jobs_concurrently` runs a bounded number of parses at a time, paces them
+//! through a `RateLimiter`, and reports each finished job as soon as it completes so
+//! the UI can drive a live progress bar.
+
+use crate::ai::rate_limiter::RateLimiter;
+use crate::commands::JobSummary;
+use crate::errors::CareerBenchError;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+/// Tauri event emitted once per job by `parse_all_pending`
+pub const PARSE_PROGRESS_EVENT: &str = "parse-progress";
+/// Parses running at the same time
+pub const PARSE_ALL_CONCURRENCY: usize = 3;
+/// Provider calls allowed per minute while bulk parsing
+pub const PARSE_ALL_REQUESTS_PER_MINUTE: u32 = 20;
+
+/// Outcome of one job in a bulk parse
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ParseProgress {
+    pub job_id: i64,
+    pub ok: bool,
+    pub error: Option<String>,
+    /// Jobs finished so far, including this one
+    pub completed: usize,
+    pub total: usize,
+}
+
+/// Jobs with a non-empty description and no parsed data, newest first
+pub fn get_jobs_needing_parse(conn: &Connection) -> Result<Vec<JobSummary>, CareerBenchError> {
+    let mut stmt = conn.prepare(
+        "SELECT id, title, company, location, seniority, domain_tags, date_added FROM jobs
+         WHERE raw_description IS NOT NULL AND TRIM(raw_description) != ''
+           AND (parsed_json IS NULL OR TRIM(parsed_json) = '')
+         ORDER BY date_added DESC, id DESC",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok(JobSummary {
+            id: row.get(0)?,
+            title: row.get(1)?,
+            company: row.get(2)?,
+            location: row.get(3)?,
+            seniority: row.get(4)?,
+            domain_tags: row.get(5)?,
+            date_added: row.get(6)?,
+            tags: Vec::new(),
+        })
+    })?;
+
+    let mut jobs = Vec::new();
+    for row_result in rows {
+        jobs.push(row_result?);
+    }
+    Ok(jobs)
+}
+
+/// Run `parse` for each job with at most `concurrency` in flight, waiting on `limiter`
+/// before each one. `on_progress` is called as each job finishes, in completion order;
+/// the same updates are returned at the end.
+pub async fn parse_jobs_concurrently<F, Fut, P>(
+    job_ids: Vec<i64>,
+    concurrency: usize,
+    limiter: &RateLimiter,
+    parse: F,
+    mut on_progress: P,
+) -> Vec<ParseProgress>
+where
+    F: Fn(i64) -> Fut,
+    Fut: Future<Output = Result<(), String>> + Send + 'static,
+    P: FnMut(&ParseProgress),
+{
+    let total = job_ids.len();
+    let permits = Arc::new(Semaphore::new(concurrency.max(1)));
+    let mut tasks = JoinSet::new();
+
+    for job_id in job_ids {
+        let permits = Arc::clone(&permits);
+        let limiter = limiter.clone();
+        let parse_job = parse(job_id);
+        tasks.spawn(async move {
+            // The semaphore is never closed, so acquiring can't fail
+            let _permit = permits.acquire_owned().await.ok();
+            limiter.acquire().await;
+            (job_id, parse_job.await)
+        });
+    }
+
+    let mut results = Vec::with_capacity(total);
+    while let Some(joined) = tasks.join_next().await {
+        let (job_id, outcome) = match joined {
+            Ok(result) => result,
+            Err(e) => {
+                log::error!("[bulk_parse] Parse task failed: {}", e);
+                continue;
+            }
+        };
+        let progress = ParseProgress {
+            job_id,
+            ok: outcome.is_ok(),
+            error: outcome.err(),
+            completed: results.len() + 1,
+            total,
+        };
+        on_progress(&progress);
+        results.push(progress);
+    }
+    results
+}
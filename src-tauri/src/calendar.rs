@@ -2,7 +2,9 @@
 
 use crate::db::get_connection;
 use crate::errors::CareerBenchError;
-use chrono::DateTime;
+use crate::ics::{render_calendar, IcsEvent, IcsTime};
+use chrono::{DateTime, NaiveDate};
+use rusqlite::{Connection, OptionalExtension};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -18,20 +20,33 @@ pub struct CalendarEvent {
     pub details: Option<String>,
     pub next_action_date: Option<String>,
     pub next_action_note: Option<String>,
+    /// RRULE of a recurring event; each occurrence is listed with its own `event_date`
+    pub recurrence: Option<String>,
 }
 
+/// Event types that show on the calendar
+const CALENDAR_EVENT_TYPES: &str = "('InterviewScheduled', 'InterviewCompleted', 'FollowUpSent', 'OfferReceived')";
+
 /// Get all calendar events (interviews, follow-ups, etc.) for a date range
 pub fn get_calendar_events(
     start_date: &str,
     end_date: &str,
 ) -> Result<Vec<CalendarEvent>, CareerBenchError> {
     let conn = get_connection()?;
+    get_calendar_events_with_conn(&conn, start_date, end_date)
+}
 
+/// Calendar events in a date range, with recurring events listed once per occurrence
+pub fn get_calendar_events_with_conn(
+    conn: &Connection,
+    start_date: &str,
+    end_date: &str,
+) -> Result<Vec<CalendarEvent>, CareerBenchError> {
     // Get interview events and next action dates from applications
     let mut events = Vec::new();
 
-    // Get interview events from application_events
-    let mut stmt = conn.prepare(
+    // Get one-off interview events from application_events
+    let mut stmt = conn.prepare(&format!(
         "SELECT 
             e.id,
             e.application_id,
@@ -44,11 +59,13 @@ pub fn get_calendar_events(
         FROM application_events e
         JOIN applications a ON e.application_id = a.id
         LEFT JOIN jobs j ON a.job_id = j.id
-        WHERE e.event_type IN ('InterviewScheduled', 'InterviewCompleted', 'FollowUpSent', 'OfferReceived')
+        WHERE e.event_type IN {}
+          AND e.recurrence IS NULL
           AND e.event_date >= ? AND e.event_date <= ?
           AND a.archived = 0
-        ORDER BY e.event_date ASC"
-    )?;
+        ORDER BY e.event_date ASC",
+        CALENDAR_EVENT_TYPES
+    ))?;
 
     let rows = stmt.query_map([start_date, end_date], |row| {
         Ok(CalendarEvent {
@@ -62,6 +79,7 @@ pub fn get_calendar_events(
             details: row.get(5)?,
             next_action_date: None,
             next_action_note: None,
+            recurrence: None,
         })
     })?;
 
@@ -69,6 +87,8 @@ pub fn get_calendar_events(
         events.push(row_result?);
     }
 
+    events.extend(recurring_event_occurrences(conn, start_date, end_date)?);
+
     // Get next action dates from applications
     let mut stmt = conn.prepare(
         "SELECT 
@@ -96,6 +116,7 @@ pub fn get_calendar_events(
             details: row.get(2)?,
             next_action_date: Some(row.get::<_, String>(1)?),
             next_action_note: row.get(2)?,
+            recurrence: None,
         })
     })?;
 
@@ -109,13 +130,161 @@ pub fn get_calendar_events(
     Ok(events)
 }
 
+/// Expand recurring events into one entry per occurrence in the range. Each
+/// occurrence keeps the first event's time of day, so only the date part changes.
+fn recurring_event_occurrences(
+    conn: &Connection,
+    start_date: &str,
+    end_date: &str,
+) -> Result<Vec<CalendarEvent>, CareerBenchError> {
+    let (range_start, range_end) = match (parse_day(start_date), parse_day(end_date)) {
+        (Some(start), Some(end)) => (start, end),
+        _ => return Ok(Vec::new()),
+    };
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT e.id, e.application_id, e.event_type, e.event_date, e.title, e.details, e.recurrence,
+                j.title, j.company
+         FROM application_events e
+         JOIN applications a ON e.application_id = a.id
+         LEFT JOIN jobs j ON a.job_id = j.id
+         WHERE e.event_type IN {}
+           AND e.recurrence IS NOT NULL
+           AND substr(e.event_date, 1, 10) <= ?
+           AND a.archived = 0",
+        CALENDAR_EVENT_TYPES
+    ))?;
+    let rows = stmt.query_map([&range_end.format("%Y-%m-%d").to_string()], |row| {
+        Ok(CalendarEvent {
+            id: row.get(0)?,
+            application_id: row.get(1)?,
+            event_type: row.get(2)?,
+            event_date: row.get(3)?,
+            title: row.get(4)?,
+            details: row.get(5)?,
+            recurrence: row.get(6)?,
+            job_title: row.get(7)?,
+            company: row.get(8)?,
+            next_action_date: None,
+            next_action_note: None,
+        })
+    })?;
+
+    let mut occurrences = Vec::new();
+    for row_result in rows {
+        let event = row_result?;
+        let (Some(first_day), Some(rule)) = (parse_day(&event.event_date), event.recurrence.as_deref()) else {
+            continue;
+        };
+        let rule = match crate::recurrence::RecurrenceRule::parse(rule) {
+            Ok(rule) => rule,
+            Err(e) => {
+                log::warn!("[calendar] Skipping event {:?} with an invalid recurrence: {}", event.id, e);
+                continue;
+            }
+        };
+        let time_of_day = &event.event_date[10..];
+        for day in rule.occurrences_between(first_day, range_start, range_end) {
+            occurrences.push(CalendarEvent {
+                event_date: format!("{}{}", day.format("%Y-%m-%d"), time_of_day),
+                id: event.id,
+                application_id: event.application_id,
+                job_title: event.job_title.clone(),
+                company: event.company.clone(),
+                event_type: event.event_type.clone(),
+                title: event.title.clone(),
+                details: event.details.clone(),
+                next_action_date: None,
+                next_action_note: None,
+                recurrence: event.recurrence.clone(),
+            });
+        }
+    }
+    Ok(occurrences)
+}
+
+/// Minutes before each event that exported calendars remind you, unless configured
+pub const DEFAULT_ICS_ALARM_MINUTES: u32 = 30;
+
+/// All calendar events in the range as one VCALENDAR. `alarm_minutes_before` sets the
+/// reminder on every event (default 30; 0 for none). Recurring events are written once,
+/// starting on their first occurrence, with their RRULE.
+pub fn export_calendar_ics_with_conn(
+    conn: &Connection,
+    start_date: &str,
+    end_date: &str,
+    alarm_minutes_before: Option<u32>,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Result<String, CareerBenchError> {
+    let alarm = match alarm_minutes_before.unwrap_or(DEFAULT_ICS_ALARM_MINUTES) {
+        0 => None,
+        minutes => Some(minutes),
+    };
+
+    let mut exported_series = Vec::new();
+    let mut ics_events = Vec::new();
+    for event in get_calendar_events_with_conn(conn, start_date, end_date)? {
+        let event_date = match (event.id, &event.recurrence) {
+            (Some(id), Some(_)) => {
+                if exported_series.contains(&id) {
+                    continue;
+                }
+                exported_series.push(id);
+                conn.query_row("SELECT event_date FROM application_events WHERE id = ?", [id], |row| {
+                    row.get::<_, String>(0)
+                })?
+            }
+            _ => event.event_date.clone(),
+        };
+        let Some(start) = IcsTime::parse(&event_date) else {
+            log::warn!("[calendar] Skipping event with an unreadable date: {}", event_date);
+            continue;
+        };
+
+        let uid = match event.id {
+            Some(id) => format!("careerbench-interview-{}-{}", event.application_id, id),
+            None => format!("careerbench-next-action-{}", event.application_id),
+        };
+        let mut summary = event.title.clone().unwrap_or_else(|| event.event_type.clone());
+        match (&event.job_title, &event.company) {
+            (Some(job), Some(company)) => summary.push_str(&format!(" – {} at {}", job, company)),
+            (Some(job), None) => summary.push_str(&format!(" – {}", job)),
+            (None, Some(company)) => summary.push_str(&format!(" – {}", company)),
+            (None, None) => {}
+        }
+
+        ics_events.push(IcsEvent {
+            uid,
+            end: start.default_end(),
+            start,
+            summary,
+            location: None,
+            description: event.details.clone().or_else(|| event.next_action_note.clone()),
+            recurrence: event.recurrence.clone(),
+            alarm_minutes_before: alarm,
+        });
+    }
+
+    Ok(render_calendar(&ics_events, now))
+}
+
+/// The calendar day at the start of a stored date or timestamp
+fn parse_day(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value.get(..10)?, "%Y-%m-%d").ok()
+}
+
 /// Get events for a specific date
 pub fn get_events_for_date(date: &str) -> Result<Vec<CalendarEvent>, CareerBenchError> {
     get_calendar_events(date, date)
 }
 
 /// Sync an interview event to system calendar
-/// Returns the calendar event ID if successful, or ICS content as fallback
+/// Returns the calendar event ID if successful, or ICS content as fallback.
+/// `recurrence` is an RRULE (e.g. "FREQ=WEEKLY;COUNT=4"). With an `event_id` it is
+/// also saved on the event so the in-app calendar shows every occurrence; an empty
+/// rule clears it, and `None` exports the event with the rule it already has.
+/// `timezone` is an IANA name the ICS times are written in; see `generate_ics_content`.
+#[allow(clippy::too_many_arguments)]
 pub fn sync_interview_to_calendar(
     application_id: i64,
     event_id: Option<i64>,
@@ -124,7 +293,14 @@ pub fn sync_interview_to_calendar(
     end_time: Option<&str>,
     location: Option<&str>,
     notes: Option<&str>,
+    recurrence: Option<&str>,
+    timezone: Option<&str>,
 ) -> Result<String, CareerBenchError> {
+    let timezone = timezone
+        .filter(|name| !name.trim().is_empty())
+        .map(crate::ics::parse_timezone)
+        .transpose()?;
+
     let start_dt = DateTime::parse_from_rfc3339(start_time)
         .map_err(|e| CareerBenchError::Validation(crate::errors::ValidationError::InvalidFormat(
             format!("Invalid start time: {}", e)
@@ -140,27 +316,79 @@ pub fn sync_interview_to_calendar(
         start_dt + chrono::Duration::hours(1)
     };
 
+    let recurrence = match (recurrence.map(str::trim), event_id) {
+        (Some(rule), event_id) => {
+            let rule = Some(rule)
+                .filter(|rule| !rule.is_empty())
+                .map(crate::recurrence::validate_rrule)
+                .transpose()?;
+            if let Some(event_id) = event_id {
+                set_event_recurrence(&get_connection()?, application_id, event_id, rule.as_deref())?;
+            }
+            rule
+        }
+        (None, Some(event_id)) => event_recurrence(&get_connection()?, event_id)?,
+        (None, None) => None,
+    };
+
     // Try platform-specific calendar sync first
     #[cfg(target_os = "macos")]
     {
-        if let Ok(_) = sync_to_macos_calendar(title, &start_dt, &end_dt, location, notes) {
+        if let Ok(_) = sync_to_macos_calendar(title, &start_dt, &end_dt, location, notes, recurrence.as_deref()) {
             return Ok(format!("Synced to macOS Calendar: {}", title));
         }
     }
 
+    // Outlook needs a structured recurrence pattern, so recurring events go through ICS
     #[cfg(target_os = "windows")]
-    {
+    if recurrence.is_none() {
         if let Ok(_) = sync_to_windows_calendar(title, &start_dt, &end_dt, location, notes) {
             return Ok(format!("Synced to Windows Calendar: {}", title));
         }
     }
 
     // Fallback to ICS file generation
-    generate_ics_content(application_id, event_id, title, &start_dt, &end_dt, location, notes)
+    generate_ics_content(application_id, event_id, title, &start_dt, &end_dt, location, notes, recurrence.as_deref(), timezone)
+}
+
+/// Save (or clear, with `None`) the RRULE of one of an application's events
+pub fn set_event_recurrence(
+    conn: &Connection,
+    application_id: i64,
+    event_id: i64,
+    recurrence: Option<&str>,
+) -> Result<(), CareerBenchError> {
+    let updated = conn.execute(
+        "UPDATE application_events SET recurrence = ? WHERE id = ? AND application_id = ?",
+        rusqlite::params![recurrence, event_id, application_id],
+    )?;
+    if updated == 0 {
+        return Err(crate::errors::ValidationError::InvalidFormat(format!(
+            "Event {} not found for application {}",
+            event_id, application_id
+        ))
+        .into());
+    }
+    Ok(())
+}
+
+/// The saved RRULE of an event, if it recurs
+pub fn event_recurrence(conn: &Connection, event_id: i64) -> Result<Option<String>, CareerBenchError> {
+    let recurrence = conn
+        .query_row(
+            "SELECT recurrence FROM application_events WHERE id = ?",
+            [event_id],
+            |row| row.get::<_, Option<String>>(0),
+        )
+        .optional()?;
+    Ok(recurrence.flatten())
 }
 
-/// Generate ICS file content for calendar import
-fn generate_ics_content(
+/// Generate ICS file content for calendar import; `recurrence` must already be validated.
+/// With a `timezone` the times are written as wall-clock times in that zone (TZID plus a
+/// VTIMEZONE block); without one they are floating times in this machine's local zone.
+#[allow(clippy::too_many_arguments)]
+pub fn generate_ics_content(
     application_id: i64,
     event_id: Option<i64>,
     title: &str,
@@ -168,39 +396,25 @@ fn generate_ics_content(
     end_dt: &DateTime<chrono::FixedOffset>,
     location: Option<&str>,
     notes: Option<&str>,
+    recurrence: Option<&str>,
+    timezone: Option<chrono_tz::Tz>,
 ) -> Result<String, CareerBenchError> {
-    let ics_content = format!(
-        "BEGIN:VCALENDAR\r\n\
-        VERSION:2.0\r\n\
-        PRODID:-//CareerBench//Interview Calendar//EN\r\n\
-        BEGIN:VEVENT\r\n\
-        UID:careerbench-interview-{}-{}\r\n\
-        DTSTART:{}\r\n\
-        DTEND:{}\r\n\
-        SUMMARY:{}\r\n\
-        {}\
-        {}\
-        STATUS:CONFIRMED\r\n\
-        END:VEVENT\r\n\
-        END:VCALENDAR\r\n",
-        application_id,
-        event_id.unwrap_or(0),
-        start_dt.format("%Y%m%dT%H%M%SZ"),
-        end_dt.format("%Y%m%dT%H%M%SZ"),
-        title,
-        if let Some(loc) = location {
-            format!("LOCATION:{}\r\n", loc)
-        } else {
-            String::new()
-        },
-        if let Some(n) = notes {
-            format!("DESCRIPTION:{}\r\n", n.replace("\r\n", "\\n").replace("\n", "\\n"))
-        } else {
-            String::new()
-        }
-    );
+    let ics_time = |dt: &DateTime<chrono::FixedOffset>| match timezone {
+        Some(tz) => IcsTime::Zoned(dt.with_timezone(&tz).naive_local(), tz),
+        None => IcsTime::Floating(dt.with_timezone(&chrono::Local).naive_local()),
+    };
+    let event = IcsEvent {
+        uid: format!("careerbench-interview-{}-{}", application_id, event_id.unwrap_or(0)),
+        start: ics_time(start_dt),
+        end: ics_time(end_dt),
+        summary: title.to_string(),
+        location: location.map(str::to_string),
+        description: notes.map(str::to_string),
+        recurrence: recurrence.map(str::to_string),
+        alarm_minutes_before: None,
+    };
 
-    Ok(ics_content)
+    Ok(render_calendar(&[event], chrono::Utc::now()))
 }
 
 /// Sync to macOS Calendar using AppleScript
@@ -211,6 +425,7 @@ fn sync_to_macos_calendar(
     end_dt: &DateTime<chrono::FixedOffset>,
     location: Option<&str>,
     notes: Option<&str>,
+    recurrence: Option<&str>,
 ) -> Result<(), CareerBenchError> {
     use std::process::Command;
 
@@ -234,7 +449,7 @@ fn sync_to_macos_calendar(
                     start date: date "{}",
                     end date: date "{}",
                     location: "{}",
-                    description: "{}"
+                    description: "{}"{}
                 }}
             end tell
         end tell
@@ -243,7 +458,9 @@ fn sync_to_macos_calendar(
         start_str,
         end_str,
         location_str.replace("\"", "\\\""),
-        notes_str.replace("\"", "\\\"")
+        notes_str.replace("\"", "\\\""),
+        // Calendar takes the RRULE value as the event's recurrence
+        recurrence.map(|rule| format!(",\n                    recurrence: \"{}\"", rule)).unwrap_or_default()
     );
 
     let output = Command::new("osascript")
@@ -309,3 +526,88 @@ fn sync_to_windows_calendar(
     }
 }
 
+
+/// A dated action coming up soon: an application's next action or a job's application deadline
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpcomingAction {
+    /// "NextAction" or "ApplicationDeadline"
+    pub action_type: String,
+    pub date: String,
+    pub job_id: Option<i64>,
+    pub application_id: Option<i64>,
+    pub job_title: Option<String>,
+    pub company: Option<String>,
+    pub note: Option<String>,
+}
+
+/// Get next actions and application deadlines due within `days_ahead` days (default 14)
+pub fn get_upcoming_actions(days_ahead: Option<i64>) -> Result<Vec<UpcomingAction>, CareerBenchError> {
+    let conn = get_connection()?;
+    let today = chrono::Local::now().date_naive();
+    get_upcoming_actions_with_conn(&conn, today, days_ahead.unwrap_or(14))
+}
+
+pub fn get_upcoming_actions_with_conn(
+    conn: &Connection,
+    today: NaiveDate,
+    days_ahead: i64,
+) -> Result<Vec<UpcomingAction>, CareerBenchError> {
+    let start = today.format("%Y-%m-%d").to_string();
+    let end = (today + chrono::Duration::days(days_ahead.max(0))).format("%Y-%m-%d").to_string();
+
+    let mut actions = Vec::new();
+
+    let mut stmt = conn.prepare(
+        "SELECT a.id, a.job_id, a.next_action_date, a.next_action_note, j.title, j.company
+         FROM applications a
+         LEFT JOIN jobs j ON a.job_id = j.id
+         WHERE a.next_action_date IS NOT NULL
+           AND substr(a.next_action_date, 1, 10) >= ? AND substr(a.next_action_date, 1, 10) <= ?
+           AND a.archived = 0",
+    )?;
+    let rows = stmt.query_map([&start, &end], |row| {
+        Ok(UpcomingAction {
+            action_type: "NextAction".to_string(),
+            application_id: Some(row.get(0)?),
+            job_id: row.get(1)?,
+            date: row.get(2)?,
+            note: row.get(3)?,
+            job_title: row.get(4)?,
+            company: row.get(5)?,
+        })
+    })?;
+    for row_result in rows {
+        actions.push(row_result?);
+    }
+
+    // Deadlines only matter for jobs that haven't been applied to yet
+    let mut stmt = conn.prepare(
+        "SELECT j.id, j.application_deadline, j.title, j.company
+         FROM jobs j
+         WHERE j.is_active = 1
+           AND j.application_deadline IS NOT NULL
+           AND j.application_deadline >= ? AND j.application_deadline <= ?
+           AND NOT EXISTS (
+               SELECT 1 FROM applications a WHERE a.job_id = j.id AND a.date_applied IS NOT NULL
+           )",
+    )?;
+    let rows = stmt.query_map([&start, &end], |row| {
+        Ok(UpcomingAction {
+            action_type: "ApplicationDeadline".to_string(),
+            job_id: Some(row.get(0)?),
+            application_id: None,
+            date: row.get(1)?,
+            job_title: row.get(2)?,
+            company: row.get(3)?,
+            note: Some("Application deadline".to_string()),
+        })
+    })?;
+    for row_result in rows {
+        actions.push(row_result?);
+    }
+
+    actions.sort_by(|a, b| a.date.cmp(&b.date));
+
+    Ok(actions)
+}
@@ -1,5 +1,7 @@
+use crate::ai::error_messages::{to_user_facing_error, UserFacingError};
 use crate::db::get_connection;
 use crate::errors::CareerBenchError;
+use crate::language::{language_mismatch_warning, Lang};
 use chrono::Utc;
 use rusqlite;
 use serde::{Deserialize, Serialize};
@@ -39,6 +41,80 @@ pub struct FunnelStep {
 pub struct DateRange {
     pub start_date: String,
     pub end_date: String,
+    /// Bucket size of the activity points: "day", "week" or "month"
+    #[serde(default = "default_granularity")]
+    pub granularity: String,
+}
+
+fn default_granularity() -> String {
+    ActivityGranularity::Day.as_str().to_string()
+}
+
+/// Bucket size for dashboard activity points.
+///
+/// Labels match SQLite's strftime output: `YYYY-MM-DD` for days, `YYYY-WW` for weeks
+/// (Monday-first week of the year, `00`-`53`) and `YYYY-MM` for months.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActivityGranularity {
+    Day,
+    Week,
+    Month,
+}
+
+impl ActivityGranularity {
+    /// Parse "day", "week" or "month"; defaults to days when unset
+    pub fn parse(value: Option<&str>) -> Result<Self, String> {
+        match value.map(|v| v.trim().to_ascii_lowercase()).as_deref() {
+            None | Some("") | Some("day") => Ok(Self::Day),
+            Some("week") => Ok(Self::Week),
+            Some("month") => Ok(Self::Month),
+            Some(other) => Err(format!("Unknown granularity '{}': expected day, week or month", other)),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Day => "day",
+            Self::Week => "week",
+            Self::Month => "month",
+        }
+    }
+
+    /// strftime format grouping dates into this bucket (same in SQLite and chrono)
+    pub fn sql_format(&self) -> &'static str {
+        match self {
+            Self::Day => "%Y-%m-%d",
+            Self::Week => "%Y-%W",
+            Self::Month => "%Y-%m",
+        }
+    }
+
+    pub fn bucket_label(&self, date: chrono::NaiveDate) -> String {
+        date.format(self.sql_format()).to_string()
+    }
+
+    /// First day of the bucket after the one containing `date`. Weeks end early at
+    /// the new year, since `%W` restarts at 00 on January 1st.
+    pub fn next_bucket_start(&self, date: chrono::NaiveDate) -> Option<chrono::NaiveDate> {
+        use chrono::Datelike;
+        match self {
+            Self::Day => date.succ_opt(),
+            Self::Week => {
+                let days_to_monday = 7 - i64::from(date.weekday().num_days_from_monday());
+                let next_monday = date.checked_add_signed(chrono::Duration::days(days_to_monday))?;
+                let next_year = chrono::NaiveDate::from_ymd_opt(date.year() + 1, 1, 1)?;
+                Some(next_monday.min(next_year))
+            }
+            Self::Month => {
+                let (year, month) = if date.month() == 12 {
+                    (date.year() + 1, 1)
+                } else {
+                    (date.year(), date.month() + 1)
+                };
+                chrono::NaiveDate::from_ymd_opt(year, month, 1)
+            }
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -48,33 +124,190 @@ pub struct DashboardData {
     pub activity_last_30_days: Vec<DailyActivityPoint>,
     pub funnel: Vec<FunnelStep>,
     pub date_range: Option<DateRange>,
+    /// The same figures for the `compare_to` window; always `None` on the comparison itself
+    #[serde(default)]
+    pub comparison: Option<Box<DashboardData>>,
+    /// Change in each windowed figure since the `compare_to` window; empty when not comparing
+    #[serde(default)]
+    pub deltas: Vec<MetricDelta>,
+}
+
+/// Change in one dashboard figure from the comparison window to the main one
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct MetricDelta {
+    /// "applications", "interviews_completed", "offers_received", or "funnel:<step label>"
+    pub metric: String,
+    pub current: i64,
+    pub previous: i64,
+    pub change: i64,
+    /// `change` as a percentage of `previous`, rounded; `None` when `previous` is 0
+    pub percent_change: Option<i64>,
+}
+
+impl MetricDelta {
+    fn new(metric: String, current: i64, previous: i64) -> Self {
+        let change = current - previous;
+        Self {
+            metric,
+            current,
+            previous,
+            change,
+            percent_change: (previous != 0).then(|| (change as f64 * 100.0 / previous as f64).round() as i64),
+        }
+    }
+}
+
+/// Attach `previous` as the comparison of `current`, with per-metric deltas for the
+/// figures that depend on the window. All-time KPIs are the same in both windows and
+/// are left out. Funnel steps are matched by label.
+pub fn compare_dashboard_data(current: &mut DashboardData, previous: DashboardData) {
+    let activity_total = |data: &DashboardData, count: fn(&DailyActivityPoint) -> i64| -> i64 {
+        data.activity_last_30_days.iter().map(count).sum()
+    };
+    let mut deltas = vec![
+        MetricDelta::new(
+            "applications".to_string(),
+            current.kpis.applications_last_30_days,
+            previous.kpis.applications_last_30_days,
+        ),
+        MetricDelta::new(
+            "interviews_completed".to_string(),
+            activity_total(current, |point| point.interviews_completed),
+            activity_total(&previous, |point| point.interviews_completed),
+        ),
+        MetricDelta::new(
+            "offers_received".to_string(),
+            activity_total(current, |point| point.offers_received),
+            activity_total(&previous, |point| point.offers_received),
+        ),
+    ];
+    for step in &current.funnel {
+        let previous_count = previous
+            .funnel
+            .iter()
+            .find(|previous_step| previous_step.label == step.label)
+            .map(|previous_step| previous_step.count)
+            .unwrap_or(0);
+        deltas.push(MetricDelta::new(format!("funnel:{}", step.label), step.count, previous_count));
+    }
+
+    current.deltas = deltas;
+    current.comparison = Some(Box::new(previous));
 }
 
 #[tauri::command]
 pub async fn get_dashboard_data(
     start_date: Option<String>,
     end_date: Option<String>,
+    granularity: Option<String>,
+    timezone: Option<String>,
+    compare_to: Option<DateRange>,
 ) -> Result<DashboardData, String> {
     let conn = get_connection()
         .map_err(|e| CareerBenchError::from(e).to_string_for_tauri())?;
+    let granularity = ActivityGranularity::parse(granularity.as_deref())?;
+    let timezone = parse_dashboard_timezone(timezone.as_deref())?;
 
-    // Default to last 30 days if no dates provided
+    // Default to last 30 days if no dates provided, ending today in the user's timezone
+    let today = match timezone {
+        Some(tz) => Utc::now().with_timezone(&tz).date_naive(),
+        None => Utc::now().date_naive(),
+    };
     let start_date_str = start_date.unwrap_or_else(|| {
-        (Utc::now() - chrono::Duration::days(30)).format("%Y-%m-%d").to_string()
+        (today - chrono::Duration::days(30)).format("%Y-%m-%d").to_string()
     });
     let end_date_str = end_date.unwrap_or_else(|| {
-        Utc::now().format("%Y-%m-%d").to_string()
+        today.format("%Y-%m-%d").to_string()
     });
 
+    let mut data = build_dashboard_data(&conn, &start_date_str, &end_date_str, granularity, timezone)?;
+    if let Some(range) = compare_to {
+        let comparison = build_dashboard_data(&conn, &range.start_date, &range.end_date, granularity, timezone)?;
+        compare_dashboard_data(&mut data, comparison);
+    }
+    Ok(data)
+}
+
+/// Parse an IANA timezone name such as "America/New_York"; `None` means UTC
+pub fn parse_dashboard_timezone(name: Option<&str>) -> Result<Option<chrono_tz::Tz>, String> {
+    match name.map(str::trim).filter(|name| !name.is_empty()) {
+        Some(name) => name
+            .parse::<chrono_tz::Tz>()
+            .map(Some)
+            .map_err(|_| format!("Unknown timezone '{}'", name)),
+        None => Ok(None),
+    }
+}
+
+/// Calendar day of a stored timestamp in `tz`, as `YYYY-MM-DD`.
+///
+/// Timestamps with an offset are converted; ones without are taken as UTC, matching
+/// SQLite's `date()`. Bare dates have no time of day to shift and are kept as-is.
+pub fn local_day(timestamp: &str, tz: chrono_tz::Tz) -> Option<String> {
+    let timestamp = timestamp.trim();
+    let utc = if let Ok(parsed) = chrono::DateTime::parse_from_rfc3339(timestamp) {
+        parsed.with_timezone(&Utc)
+    } else if let Ok(naive) = chrono::NaiveDateTime::parse_from_str(timestamp, "%Y-%m-%d %H:%M:%S")
+        .or_else(|_| chrono::NaiveDateTime::parse_from_str(timestamp, "%Y-%m-%dT%H:%M:%S%.f"))
+    {
+        naive.and_utc()
+    } else {
+        return timestamp
+            .get(..10)
+            .and_then(|date| chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+            .map(|date| date.format("%Y-%m-%d").to_string());
+    };
+    Some(utc.with_timezone(&tz).format("%Y-%m-%d").to_string())
+}
+
+/// Register `local_day(timestamp)` on the connection so queries can bucket by the user's day
+fn register_local_day_function(conn: &rusqlite::Connection, tz: chrono_tz::Tz) -> rusqlite::Result<()> {
+    use rusqlite::functions::FunctionFlags;
+    conn.create_scalar_function(
+        "local_day",
+        1,
+        FunctionFlags::SQLITE_UTF8 | FunctionFlags::SQLITE_DETERMINISTIC,
+        move |ctx| {
+            let timestamp: Option<String> = ctx.get(0)?;
+            Ok(timestamp.and_then(|timestamp| local_day(&timestamp, tz)))
+        },
+    )
+}
+
+/// Dashboard data for `start_date..=end_date` (`YYYY-MM-DD`), with activity bucketed by
+/// `granularity`. Days are UTC days unless a `timezone` is given.
+pub fn build_dashboard_data(
+    conn: &rusqlite::Connection,
+    start_date: &str,
+    end_date: &str,
+    granularity: ActivityGranularity,
+    timezone: Option<chrono_tz::Tz>,
+) -> Result<DashboardData, String> {
+    let start_date_str = start_date.to_string();
+    let end_date_str = end_date.to_string();
+
+    // SQL expression giving the calendar day of a timestamp column
+    let day = match timezone {
+        Some(tz) => {
+            register_local_day_function(conn, tz)
+                .map_err(|e| format!("Failed to set up timezone handling: {}", e))?;
+            "local_day"
+        }
+        None => "date",
+    };
+
     // KPIs - Optimized: Single query with conditional aggregation
     let kpi_row = conn
         .query_row(
-            "SELECT 
-                (SELECT COUNT(*) FROM jobs) as total_jobs,
-                (SELECT COUNT(*) FROM applications) as total_applications,
-                (SELECT COUNT(*) FROM applications WHERE archived = 0) as active_applications,
-                (SELECT COUNT(*) FROM applications WHERE date_saved >= ? AND date_saved <= ?) as applications_in_range,
-                (SELECT COUNT(*) FROM applications WHERE status = 'Offer') as offers_received",
+            &format!(
+                "SELECT
+                    (SELECT COUNT(*) FROM jobs) as total_jobs,
+                    (SELECT COUNT(*) FROM applications) as total_applications,
+                    (SELECT COUNT(*) FROM applications WHERE archived = 0) as active_applications,
+                    (SELECT COUNT(*) FROM applications WHERE {0}(date_saved) >= ? AND {0}(date_saved) <= ?) as applications_in_range,
+                    (SELECT COUNT(*) FROM applications WHERE status = 'Offer') as offers_received",
+                day
+            ),
             [&start_date_str, &end_date_str],
             |row| {
                 Ok((
@@ -131,43 +364,46 @@ pub async fn get_dashboard_data(
     let end = chrono::NaiveDate::parse_from_str(&end_date_str, "%Y-%m-%d")
         .map_err(|e| format!("Invalid end date: {}", e))?;
     
+    // One empty point per bucket, so gaps in activity still show on the chart
     let mut current = start;
     while current <= end {
-        let date_str = current.format("%Y-%m-%d").to_string();
+        let label = granularity.bucket_label(current);
         activity_map.insert(
-            date_str.clone(),
+            label.clone(),
             DailyActivityPoint {
-                date: date_str,
+                date: label,
                 applications_created: 0,
                 interviews_completed: 0,
                 offers_received: 0,
             },
         );
-        current = current.succ_opt()
+        current = granularity.next_bucket_start(current)
             .ok_or_else(|| "Date range too large".to_string())?;
     }
 
     // Activity data - Optimized: Single query with UNION ALL for all activity types
     // This reduces database round trips from 3 to 1
     let mut stmt = conn
-        .prepare(
-            "SELECT date(date_saved) as day, COUNT(*) as count, 'applications' as type
+        .prepare(&format!(
+            "SELECT strftime('{0}', {1}(date_saved)) as day, COUNT(*) as count, 'applications' as type
              FROM applications
-             WHERE date_saved >= ? AND date_saved <= ?
+             WHERE {1}(date_saved) >= ? AND {1}(date_saved) <= ?
              GROUP BY day
              UNION ALL
-             SELECT date(event_date) as day, COUNT(*) as count, 'interviews' as type
+             SELECT strftime('{0}', {1}(event_date)) as day, COUNT(*) as count, 'interviews' as type
              FROM application_events
              WHERE event_type = 'InterviewCompleted'
-               AND event_date >= ? AND event_date <= ?
+               AND {1}(event_date) >= ? AND {1}(event_date) <= ?
              GROUP BY day
              UNION ALL
-             SELECT date(event_date) as day, COUNT(*) as count, 'offers' as type
+             SELECT strftime('{0}', {1}(event_date)) as day, COUNT(*) as count, 'offers' as type
              FROM application_events
              WHERE event_type = 'OfferReceived'
-               AND event_date >= ? AND event_date <= ?
+               AND {1}(event_date) >= ? AND {1}(event_date) <= ?
              GROUP BY day",
-        )
+            granularity.sql_format(),
+            day
+        ))
         .map_err(|e| format!("Failed to prepare activity query: {}", e))?;
 
     let activity_rows = stmt
@@ -198,15 +434,53 @@ pub async fn get_dashboard_data(
     let mut activity_last_30_days: Vec<DailyActivityPoint> = activity_map.into_values().collect();
     activity_last_30_days.sort_by_key(|p| p.date.clone());
 
-    // Funnel - Optimized: Single query with conditional aggregation
+    // Funnel: the user's custom stages when configured, otherwise the built-in three
+    let funnel_stages = crate::dashboard_funnel::load_dashboard_funnel(conn)
+        .map_err(|e| e.to_string_for_tauri())?
+        .stages;
+    let funnel = if funnel_stages.is_empty() {
+        default_funnel(conn, day, &start_date_str, &end_date_str)?
+    } else {
+        crate::dashboard_funnel::count_custom_funnel(conn, &funnel_stages, day, &start_date_str, &end_date_str)
+            .map_err(|e| e.to_string_for_tauri())?
+    };
+
+    Ok(DashboardData {
+        kpis,
+        status_breakdown,
+        activity_last_30_days,
+        funnel,
+        date_range: Some(DateRange {
+            start_date: start_date_str.clone(),
+            end_date: end_date_str.clone(),
+            granularity: granularity.as_str().to_string(),
+        }),
+        comparison: None,
+        deltas: Vec::new(),
+    })
+}
+
+/// The built-in Applied → Interviewing → Offer funnel over applications saved in the window
+fn default_funnel(
+    conn: &rusqlite::Connection,
+    day: &str,
+    start_date_str: &str,
+    end_date_str: &str,
+) -> Result<Vec<FunnelStep>, String> {
+    // Funnel - Optimized: Single query with conditional aggregation, over applications
+    // saved in the window so compared periods differ
     let funnel_row = conn
         .query_row(
-            "SELECT 
-                COUNT(CASE WHEN status IN ('Applied', 'Interviewing', 'Offer', 'Rejected', 'Ghosted', 'Withdrawn') THEN 1 END) as applied,
-                COUNT(CASE WHEN status IN ('Interviewing', 'Offer', 'Rejected', 'Ghosted', 'Withdrawn') THEN 1 END) as interviewing,
-                COUNT(CASE WHEN status = 'Offer' THEN 1 END) as offer
-             FROM applications",
-            [],
+            &format!(
+                "SELECT
+                    COUNT(CASE WHEN status IN ('Applied', 'Interviewing', 'Offer', 'Rejected', 'Ghosted', 'Withdrawn') THEN 1 END) as applied,
+                    COUNT(CASE WHEN status IN ('Interviewing', 'Offer', 'Rejected', 'Ghosted', 'Withdrawn') THEN 1 END) as interviewing,
+                    COUNT(CASE WHEN status = 'Offer' THEN 1 END) as offer
+                 FROM applications
+                 WHERE {0}(date_saved) >= ? AND {0}(date_saved) <= ?",
+                day
+            ),
+            [start_date_str, end_date_str],
             |row| {
                 Ok((
                     row.get::<_, i64>(0)?,  // applied
@@ -219,7 +493,7 @@ pub async fn get_dashboard_data(
 
     let (applied, interviewing, offer) = funnel_row;
 
-    let funnel = vec![
+    Ok(vec![
         FunnelStep {
             label: "Applied".to_string(),
             count: applied,
@@ -232,18 +506,39 @@ pub async fn get_dashboard_data(
             label: "Offer".to_string(),
             count: offer,
         },
-    ];
+    ])
+}
 
-    Ok(DashboardData {
-        kpis,
-        status_breakdown,
-        activity_last_30_days,
-        funnel,
-        date_range: Some(DateRange {
-            start_date: start_date_str.clone(),
-            end_date: end_date_str.clone(),
-        }),
-    })
+/// The custom dashboard funnel stages; empty when the built-in funnel is used
+#[tauri::command]
+pub async fn get_dashboard_funnel() -> Result<crate::dashboard_funnel::DashboardFunnelConfig, String> {
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    crate::dashboard_funnel::load_dashboard_funnel(&conn).map_err(|e| e.to_string_for_tauri())
+}
+
+#[tauri::command]
+pub async fn save_dashboard_funnel(config: crate::dashboard_funnel::DashboardFunnelConfig) -> Result<(), String> {
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    crate::dashboard_funnel::save_dashboard_funnel(&conn, &config).map_err(|e| e.to_string_for_tauri())
+}
+
+/// Export the AI call log between two dates (inclusive, `YYYY-MM-DD`) as CSV
+#[tauri::command]
+pub async fn export_ai_usage_csv(start_date: String, end_date: String) -> Result<String, String> {
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    crate::ai::usage_log::export_ai_usage_csv_with_conn(&conn, &start_date, &end_date)
+        .map_err(|e| e.to_string_for_tauri())
+}
+
+/// AI calls, tokens and estimated cost between two dates (inclusive, `YYYY-MM-DD`), per day and per purpose
+#[tauri::command]
+pub async fn get_ai_usage_summary(
+    start_date: String,
+    end_date: String,
+) -> Result<crate::ai::usage_log::AiUsageSummary, String> {
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    crate::ai::usage_log::get_ai_usage_summary_with_conn(&conn, &start_date, &end_date)
+        .map_err(|e| e.to_string_for_tauri())
 }
 
 /// Export dashboard data as CSV
@@ -252,7 +547,7 @@ pub async fn export_dashboard_data(
     start_date: Option<String>,
     end_date: Option<String>,
 ) -> Result<String, String> {
-    let dashboard_data = get_dashboard_data(start_date, end_date).await?;
+    let dashboard_data = get_dashboard_data(start_date, end_date, None, None, None).await?;
     
     let mut csv = String::new();
     
@@ -304,6 +599,19 @@ pub async fn export_dashboard_data(
     Ok(csv)
 }
 
+/// Export dashboard data as pretty-printed JSON, built from the same data as the CSV export
+#[tauri::command]
+pub async fn export_dashboard_json(
+    start_date: Option<String>,
+    end_date: Option<String>,
+    granularity: Option<String>,
+    timezone: Option<String>,
+) -> Result<String, String> {
+    let dashboard_data = get_dashboard_data(start_date, end_date, granularity, timezone, None).await?;
+    serde_json::to_string_pretty(&dashboard_data)
+        .map_err(|e| format!("Failed to serialize dashboard data: {}", e))
+}
+
 /// Get calendar events for a date range
 #[tauri::command]
 pub async fn get_calendar_events(
@@ -314,6 +622,19 @@ pub async fn get_calendar_events(
         .map_err(|e| e.to_string_for_tauri())
 }
 
+/// Every calendar event in the range as a single ICS file's content, with a reminder
+/// `alarm_minutes_before` each event (30 by default, 0 for none)
+#[tauri::command]
+pub async fn export_calendar_ics(
+    start_date: String,
+    end_date: String,
+    alarm_minutes_before: Option<u32>,
+) -> Result<String, String> {
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    crate::calendar::export_calendar_ics_with_conn(&conn, &start_date, &end_date, alarm_minutes_before, Utc::now())
+        .map_err(|e| e.to_string_for_tauri())
+}
+
 /// Get calendar events for a specific date
 #[tauri::command]
 pub async fn get_events_for_date(date: String) -> Result<Vec<crate::calendar::CalendarEvent>, String> {
@@ -321,8 +642,20 @@ pub async fn get_events_for_date(date: String) -> Result<Vec<crate::calendar::Ca
         .map_err(|e| e.to_string_for_tauri())
 }
 
-/// Generate ICS file content for an interview event
+/// Get next actions and application deadlines coming up in the next `days_ahead` days
+#[tauri::command]
+pub async fn get_upcoming_actions(
+    days_ahead: Option<i64>,
+) -> Result<Vec<crate::calendar::UpcomingAction>, String> {
+    crate::calendar::get_upcoming_actions(days_ahead)
+        .map_err(|e| e.to_string_for_tauri())
+}
+
+/// Generate ICS file content for an interview event. `recurrence` is an optional
+/// RRULE such as "FREQ=WEEKLY;COUNT=4"; `timezone` an optional IANA name such as
+/// "America/New_York" (floating local time when omitted).
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn sync_interview_to_calendar(
     application_id: i64,
     event_id: Option<i64>,
@@ -331,6 +664,8 @@ pub async fn sync_interview_to_calendar(
     end_time: Option<String>,
     location: Option<String>,
     notes: Option<String>,
+    recurrence: Option<String>,
+    timezone: Option<String>,
 ) -> Result<String, String> {
     crate::calendar::sync_interview_to_calendar(
         application_id,
@@ -340,6 +675,8 @@ pub async fn sync_interview_to_calendar(
         end_time.as_deref(),
         location.as_deref(),
         notes.as_deref(),
+        recurrence.as_deref(),
+        timezone.as_deref(),
     )
     .map_err(|e| e.to_string_for_tauri())
 }
@@ -356,6 +693,7 @@ pub async fn create_reminder(
     reminder_type: String,
     reminder_date: String,
     message: Option<String>,
+    recurrence: Option<String>,
 ) -> Result<i64, String> {
     crate::reminders::create_reminder(
         application_id,
@@ -363,6 +701,7 @@ pub async fn create_reminder(
         &reminder_type,
         &reminder_date,
         message.as_deref(),
+        recurrence.as_deref(),
     )
     .map_err(|e| e.to_string_for_tauri())
 }
@@ -401,6 +740,13 @@ pub async fn mark_reminder_sent(reminder_id: i64) -> Result<(), String> {
         .map_err(|e| e.to_string_for_tauri())
 }
 
+/// Push a reminder to a later date without creating a new one
+#[tauri::command]
+pub async fn snooze_reminder(reminder_id: i64, new_date: String) -> Result<(), String> {
+    crate::reminders::snooze_reminder(reminder_id, &new_date)
+        .map_err(|e| e.to_string_for_tauri())
+}
+
 /// Delete a reminder
 #[tauri::command]
 pub async fn delete_reminder(reminder_id: i64) -> Result<(), String> {
@@ -408,6 +754,38 @@ pub async fn delete_reminder(reminder_id: i64) -> Result<(), String> {
         .map_err(|e| e.to_string_for_tauri())
 }
 
+/// Get the configured reminder quiet hours
+#[tauri::command]
+pub async fn get_quiet_hours() -> Result<crate::reminders::QuietHours, String> {
+    crate::reminders::get_quiet_hours()
+        .map_err(|e| e.to_string_for_tauri())
+}
+
+/// Save reminder quiet hours (local time, HH:MM)
+#[tauri::command]
+pub async fn save_quiet_hours(
+    quiet_hours: crate::reminders::QuietHours,
+) -> Result<(), String> {
+    crate::reminders::save_quiet_hours(&quiet_hours)
+        .map_err(|e| e.to_string_for_tauri())
+}
+
+/// Get the rules for reminders created automatically on status changes
+#[tauri::command]
+pub async fn get_follow_up_rules() -> Result<crate::reminders::FollowUpRules, String> {
+    crate::reminders::get_follow_up_rules()
+        .map_err(|e| e.to_string_for_tauri())
+}
+
+/// Save the rules for reminders created automatically on status changes
+#[tauri::command]
+pub async fn save_follow_up_rules(
+    rules: crate::reminders::FollowUpRules,
+) -> Result<(), String> {
+    crate::reminders::save_follow_up_rules(&rules)
+        .map_err(|e| e.to_string_for_tauri())
+}
+
 // ============================================================================
 // Portfolio Export Commands
 // ============================================================================
@@ -520,6 +898,42 @@ pub async fn export_portfolio_text(
     Ok(crate::portfolio_export::export_portfolio_text(&items, include_highlighted_only))
 }
 
+/// Export portfolio as PDF bytes
+#[tauri::command]
+pub async fn export_portfolio_pdf(
+    include_highlighted_only: bool,
+) -> Result<Vec<u8>, String> {
+    let conn = get_connection()
+        .map_err(|e| CareerBenchError::from(e).to_string_for_tauri())?;
+
+    let mut stmt = conn.prepare(
+        "SELECT id, title, url, description, role, tech_stack, highlighted
+         FROM portfolio_items WHERE user_profile_id = 1
+         ORDER BY highlighted DESC, id DESC"
+    )
+    .map_err(|e| format!("Failed to prepare query: {}", e))?;
+
+    let rows = stmt.query_map([], |row| {
+        Ok(crate::portfolio_export::PortfolioItem {
+            id: row.get(0)?,
+            title: row.get(1)?,
+            url: row.get(2)?,
+            description: row.get(3)?,
+            role: row.get(4)?,
+            tech_stack: row.get(5)?,
+            highlighted: row.get::<_, i64>(6)? != 0,
+        })
+    })
+    .map_err(|e| format!("Failed to query portfolio: {}", e))?;
+
+    let mut items = Vec::new();
+    for row_result in rows {
+        items.push(row_result.map_err(|e| format!("Failed to parse portfolio item: {}", e))?);
+    }
+
+    Ok(crate::portfolio_export::export_portfolio_pdf(&items, include_highlighted_only))
+}
+
 /// Get portfolio items linked to an application
 #[tauri::command]
 pub async fn get_portfolio_for_application(
@@ -539,6 +953,16 @@ pub async fn link_portfolio_to_application(
         .map_err(|e| e.to_string_for_tauri())
 }
 
+/// Link one portfolio item to many applications; returns the number of new links
+#[tauri::command]
+pub async fn bulk_link_portfolio(
+    portfolio_item_id: i64,
+    application_ids: Vec<i64>,
+) -> Result<u64, String> {
+    crate::portfolio_export::bulk_link_portfolio(portfolio_item_id, &application_ids)
+        .map_err(|e| e.to_string_for_tauri())
+}
+
 /// Get applications linked to a portfolio item
 #[tauri::command]
 pub async fn get_applications_for_portfolio(
@@ -604,107 +1028,377 @@ pub async fn get_analytics_insights(
     .map_err(|e| e.to_string_for_tauri())
 }
 
-// ============================================================================
-// Email Integration Commands
-// ============================================================================
-
-/// Save an email account
+/// Get apply-to-offer duration benchmarks
 #[tauri::command]
-pub async fn save_email_account(
-    account: crate::email::EmailAccount,
-) -> Result<i64, String> {
-    crate::email::save_email_account(&account)
+pub async fn get_time_to_offer_stats() -> Result<crate::analytics::TimeToOfferStats, String> {
+    crate::analytics::get_time_to_offer_stats()
         .map_err(|e| e.to_string_for_tauri())
 }
 
-/// Get all email accounts
+/// Estimated chance of an offer for an application, with the factors behind it
 #[tauri::command]
-pub async fn get_email_accounts() -> Result<Vec<crate::email::EmailAccount>, String> {
-    crate::email::get_email_accounts()
+pub async fn estimate_offer_probability(
+    application_id: i64,
+) -> Result<crate::analytics::OfferProbability, String> {
+    crate::analytics::get_offer_probability(application_id)
         .map_err(|e| e.to_string_for_tauri())
 }
 
-/// Delete an email account
+/// Days from applying to the first response, overall and by channel
 #[tauri::command]
-pub async fn delete_email_account(account_id: i64) -> Result<(), String> {
-    crate::email::delete_email_account(account_id)
+pub async fn calculate_response_times() -> Result<crate::analytics::ResponseTimeReport, String> {
+    crate::analytics::get_response_times()
         .map_err(|e| e.to_string_for_tauri())
 }
 
-/// Get email threads for an application
+/// A short prioritized summary of the week: actions, follow-ups, stale applications and goals
 #[tauri::command]
-pub async fn get_email_threads_for_application(
-    application_id: i64,
-) -> Result<Vec<crate::email::EmailThread>, String> {
-    crate::email::get_threads_for_application(application_id)
+pub async fn generate_weekly_digest() -> Result<String, String> {
+    use crate::ai::resolver::ResolvedProvider;
+    use crate::weekly_digest::{cache_weekly_digest, compose_weekly_digest, gather_weekly_digest_data, get_cached_weekly_digest};
+
+    let now = Utc::now();
+    let now_iso = now.to_rfc3339();
+    let today = chrono::Local::now().date_naive();
+
+    let data = {
+        let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+        if let Some(digest) = get_cached_weekly_digest(&conn, today, &now_iso)? {
+            return Ok(digest);
+        }
+        gather_weekly_digest_data(&conn, now.naive_utc(), today).map_err(|e| e.to_string_for_tauri())?
+    };
+
+    let provider = ResolvedProvider::resolve_for("weekly_digest").ok().map(|resolved| resolved.as_provider());
+    let (digest, from_ai) = compose_weekly_digest(provider.as_deref(), &data, today).await;
+
+    // Only AI digests are cached, so a fallback doesn't hide the AI one once a provider is set up
+    if from_ai {
+        let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+        cache_weekly_digest(&conn, today, &digest, &now_iso)?;
+    }
+    Ok(digest)
+}
+
+/// The one thing most worth doing next, with the reason and what to open
+#[tauri::command]
+pub async fn get_next_best_action() -> Result<crate::next_action::NextAction, String> {
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    crate::next_action::get_next_best_action(&conn, Utc::now().naive_utc(), chrono::Local::now().date_naive())
         .map_err(|e| e.to_string_for_tauri())
 }
 
-/// Link an email thread to an application
+/// Zip of redacted logs, migration status, a health report and row counts for bug reports.
+/// The database itself is not included.
 #[tauri::command]
-pub async fn link_email_thread_to_application(
-    thread_id: i64,
-    application_id: i64,
-) -> Result<(), String> {
-    crate::email::link_thread_to_application(thread_id, application_id)
+pub async fn generate_support_bundle() -> Result<Vec<u8>, String> {
+    use crate::support_bundle::{build_support_bundle, read_log_tails};
+
+    let logs = read_log_tails(&crate::db::get_app_data_dir());
+    let ai_settings = crate::ai::settings::load_ai_settings().ok();
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    build_support_bundle(&conn, &logs, ai_settings.as_ref(), chrono::Local::now().naive_local())
         .map_err(|e| e.to_string_for_tauri())
 }
 
-/// Get email messages for a thread
+/// Age histogram of active applications, to spot a stagnant pipeline
 #[tauri::command]
-pub async fn get_email_messages_for_thread(
-    thread_id: i64,
-) -> Result<Vec<crate::email::EmailMessage>, String> {
-    crate::email::get_messages_for_thread(thread_id)
+pub async fn get_application_age_distribution() -> Result<Vec<crate::analytics::AgeBucket>, String> {
+    let today = chrono::Local::now().date_naive();
+    crate::analytics::get_application_age_distribution(today)
         .map_err(|e| e.to_string_for_tauri())
 }
 
-/// Test email account connection (stub - full IMAP implementation would go here)
+// ============================================================================
+// Job Match Commands
+// ============================================================================
+
+/// Short plain-English TL;DR of a job posting, cached per description
 #[tauri::command]
-pub async fn test_email_connection(
-    email: String,
-    _password: String,
-    _provider: String,
-) -> Result<String, String> {
-    // This is a placeholder - full IMAP connection would be implemented here
-    // For now, we'll just validate the email format
-    if email.contains('@') && email.contains('.') {
-        Ok(format!("Email account '{}' format is valid. IMAP connection testing requires additional setup.", email))
-    } else {
-        Err("Invalid email format".to_string())
-    }
+pub async fn summarize_job_tldr(job_id: i64) -> Result<String, String> {
+    use crate::ai::resolver::ResolvedProvider;
+    use crate::job_tldr::{cache_job_tldr, prepare_job_tldr, request_job_tldr, JobTldrRequest};
+
+    let now = Utc::now().to_rfc3339();
+    let request = {
+        let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+        prepare_job_tldr(&conn, job_id, &now)?
+    };
+    let (input_hash, request_payload, prompt) = match request {
+        JobTldrRequest::Cached(tldr) => return Ok(tldr),
+        JobTldrRequest::Needed { input_hash, request_payload, prompt } => (input_hash, request_payload, prompt),
+    };
+
+    let provider = ResolvedProvider::resolve_for("job_tldr").map_err(|e| format!("Failed to resolve provider: {}", e))?;
+    let tldr = request_job_tldr(provider.as_provider().as_ref(), &prompt).await?;
+
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    cache_job_tldr(&conn, &input_hash, &request_payload, &tldr, &now)?;
+    Ok(tldr)
 }
 
-/// Sync emails from an account (stub - full IMAP sync would go here)
+/// Profile skills least relevant to a parsed job, so a tailored resume can leave them out
 #[tauri::command]
-pub async fn sync_email_account(account_id: i64) -> Result<String, String> {
-    // This is a placeholder - full IMAP sync would be implemented here
-    // The implementation would:
-    // 1. Connect to IMAP server
-    // 2. Fetch recent emails
-    // 3. Parse emails for application events
-    // 4. Create/update email threads and messages
-    // 5. Auto-link to applications when possible
-    Ok(format!("Email sync for account {} would be implemented here. This requires IMAP library setup and OAuth/app password configuration.", account_id))
+pub async fn suggest_skills_to_hide(job_id: i64) -> Result<Vec<String>, String> {
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    crate::job_match::suggest_skills_to_hide(&conn, job_id).map_err(|e| e.to_string_for_tauri())
 }
 
-// ============================================================================
-// Learning Plan Commands
-// ============================================================================
+/// Saved jobs similar to the given one, by shared skills, tags and seniority
+#[tauri::command]
+pub async fn get_related_jobs(job_id: i64, limit: i64) -> Result<Vec<JobSummary>, String> {
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    crate::job_match::find_related_jobs(&conn, job_id, limit)
+        .map_err(|e| e.to_string_for_tauri())
+}
 
-/// Analyze skill gaps comparing user skills to job requirements
+/// Past cover letters for similar jobs, as starting points for a new letter
 #[tauri::command]
-pub async fn analyze_skill_gaps(
-    job_id: Option<i64>,
-    include_all_jobs: bool,
-) -> Result<Vec<crate::learning::SkillGap>, String> {
-    crate::learning::analyze_skill_gaps(job_id, include_all_jobs)
+pub async fn find_similar_cover_letters(job_id: i64, limit: i64) -> Result<Vec<ArtifactMatch>, String> {
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    crate::job_match::find_similar_cover_letters(&conn, job_id, limit)
         .map_err(|e| e.to_string_for_tauri())
 }
 
-/// Create a learning plan from skill gaps
+/// Rank saved jobs by how well they match the profile's skills
 #[tauri::command]
-pub async fn create_learning_plan(
+pub async fn rank_saved_jobs(
+    limit: Option<i64>,
+) -> Result<Vec<crate::job_match::RankedJob>, String> {
+    crate::job_match::rank_saved_jobs(limit)
+        .map_err(|e| e.to_string_for_tauri())
+}
+
+// ============================================================================
+// Compensation Commands
+// ============================================================================
+
+/// Compare offers with compensation normalized to a base currency
+#[tauri::command]
+pub async fn get_offer_comparison(
+    base_currency: Option<String>,
+) -> Result<Vec<crate::compensation::OfferComparison>, String> {
+    crate::compensation::get_offer_comparison(base_currency.as_deref())
+        .map_err(|e| e.to_string_for_tauri())
+}
+
+/// Get the static currency rate table used for comp normalization
+#[tauri::command]
+pub async fn get_currency_rates() -> Result<crate::compensation::CurrencyRates, String> {
+    crate::compensation::get_currency_rates()
+        .map_err(|e| e.to_string_for_tauri())
+}
+
+/// Save the static currency rate table
+#[tauri::command]
+pub async fn save_currency_rates(
+    rates: crate::compensation::CurrencyRates,
+) -> Result<(), String> {
+    crate::compensation::save_currency_rates(&rates)
+        .map_err(|e| e.to_string_for_tauri())
+}
+
+/// Get the minimum acceptable salary used for salary fit
+#[tauri::command]
+pub async fn get_salary_expectation() -> Result<crate::compensation::SalaryExpectation, String> {
+    crate::compensation::get_salary_expectation()
+        .map_err(|e| e.to_string_for_tauri())
+}
+
+/// Save the minimum acceptable salary
+#[tauri::command]
+pub async fn save_salary_expectation(
+    expectation: crate::compensation::SalaryExpectation,
+) -> Result<(), String> {
+    crate::compensation::save_salary_expectation(&expectation)
+        .map_err(|e| e.to_string_for_tauri())
+}
+
+/// Suggest High/Medium/Low priority from match score, deadline and salary fit
+#[tauri::command]
+pub async fn suggest_application_priority(
+    application_id: i64,
+) -> Result<crate::application_priority::PrioritySuggestion, String> {
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    let today = chrono::Local::now().date_naive();
+    crate::application_priority::suggest_application_priority(&conn, application_id, today)
+        .map_err(|e| e.to_string_for_tauri())
+}
+
+/// Accept a suggested priority into the application
+#[tauri::command]
+pub async fn accept_priority_suggestion(application_id: i64, priority: String) -> Result<(), String> {
+    if !["High", "Medium", "Low"].contains(&priority.as_str()) {
+        return Err(format!("Invalid priority: {}", priority));
+    }
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    crate::application_priority::accept_priority_suggestion(&conn, application_id, &priority)
+        .map_err(|e| e.to_string_for_tauri())
+}
+
+/// Save a self-debrief after an interview round; rating is 1-5
+#[tauri::command]
+pub async fn save_interview_debrief(
+    application_id: i64,
+    round: String,
+    went_well: String,
+    to_improve: String,
+    rating: i32,
+) -> Result<i64, String> {
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    let now = Utc::now().to_rfc3339();
+    crate::interview_debriefs::save_interview_debrief(&conn, application_id, &round, &went_well, &to_improve, rating, &now)
+        .map_err(|e| e.to_string_for_tauri())
+}
+
+/// Interview debriefs for an application, most recent first
+#[tauri::command]
+pub async fn get_debriefs_for_application(
+    application_id: i64,
+) -> Result<Vec<crate::interview_debriefs::InterviewDebrief>, String> {
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    crate::interview_debriefs::get_debriefs_for_application(&conn, application_id)
+        .map_err(|e| e.to_string_for_tauri())
+}
+
+// ============================================================================
+// Email Integration Commands
+// ============================================================================
+
+/// Save an email account; `password` (an app password) is kept in secure storage
+#[tauri::command]
+pub async fn save_email_account(
+    account: crate::email::EmailAccount,
+    password: Option<String>,
+) -> Result<i64, String> {
+    let account_id = crate::email::save_email_account(&account)
+        .map_err(|e| e.to_string_for_tauri())?;
+    if let Some(password) = password.filter(|p| !p.is_empty()) {
+        crate::email_sync::save_account_password(account_id, &password)
+            .map_err(|e| e.to_string_for_tauri())?;
+    }
+    Ok(account_id)
+}
+
+/// Get all email accounts
+#[tauri::command]
+pub async fn get_email_accounts() -> Result<Vec<crate::email::EmailAccount>, String> {
+    crate::email::get_email_accounts()
+        .map_err(|e| e.to_string_for_tauri())
+}
+
+/// Delete an email account
+#[tauri::command]
+pub async fn delete_email_account(account_id: i64) -> Result<(), String> {
+    crate::email::delete_email_account(account_id)
+        .map_err(|e| e.to_string_for_tauri())
+}
+
+/// Get email threads for an application
+#[tauri::command]
+pub async fn get_email_threads_for_application(
+    application_id: i64,
+) -> Result<Vec<crate::email::EmailThread>, String> {
+    crate::email::get_threads_for_application(application_id)
+        .map_err(|e| e.to_string_for_tauri())
+}
+
+/// Link an email thread to an application
+#[tauri::command]
+pub async fn link_email_thread_to_application(
+    thread_id: i64,
+    application_id: i64,
+) -> Result<(), String> {
+    crate::email::link_thread_to_application(thread_id, application_id)
+        .map_err(|e| e.to_string_for_tauri())
+}
+
+/// Get email messages for a thread
+#[tauri::command]
+pub async fn get_email_messages_for_thread(
+    thread_id: i64,
+) -> Result<Vec<crate::email::EmailMessage>, String> {
+    crate::email::get_messages_for_thread(thread_id)
+        .map_err(|e| e.to_string_for_tauri())
+}
+
+/// Test email account connection (stub - full IMAP implementation would go here)
+#[tauri::command]
+pub async fn test_email_connection(
+    email: String,
+    _password: String,
+    _provider: String,
+) -> Result<String, String> {
+    // This is a placeholder - full IMAP connection would be implemented here
+    // For now, we'll just validate the email format
+    if email.contains('@') && email.contains('.') {
+        Ok(format!("Email account '{}' format is valid. IMAP connection testing requires additional setup.", email))
+    } else {
+        Err("Invalid email format".to_string())
+    }
+}
+
+/// Fetch new INBOX mail for an account over IMAP and link new threads to applications
+#[tauri::command]
+pub async fn sync_email_account(account_id: i64) -> Result<crate::email_sync::EmailSyncResult, String> {
+    crate::email_sync::sync_account(account_id)
+        .await
+        .map_err(|e| e.to_string_for_tauri())
+}
+
+/// Link unlinked email threads to applications by sender and subject
+#[tauri::command]
+pub async fn auto_link_email_threads() -> Result<crate::email_matching::AutoLinkResult, String> {
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    crate::email_matching::auto_link_threads(&conn)
+        .map_err(|e| e.to_string_for_tauri())
+}
+
+/// Suggest status changes from offer, rejection and interview emails linked to an application
+#[tauri::command]
+pub async fn suggest_status_from_emails(
+    application_id: i64,
+) -> Result<Vec<crate::email_signals::EmailStatusSuggestion>, String> {
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    crate::email_signals::suggest_status_from_emails(&conn, application_id)
+        .map_err(|e| e.to_string_for_tauri())
+}
+
+/// Get the phrases used to detect status signals in emails
+#[tauri::command]
+pub async fn get_email_signal_patterns() -> Result<crate::email_signals::EmailSignalPatterns, String> {
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    crate::email_signals::get_email_signal_patterns(&conn)
+        .map_err(|e| e.to_string_for_tauri())
+}
+
+/// Save the phrases used to detect status signals in emails
+#[tauri::command]
+pub async fn save_email_signal_patterns(
+    patterns: crate::email_signals::EmailSignalPatterns,
+) -> Result<(), String> {
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    crate::email_signals::save_email_signal_patterns(&conn, &patterns)
+        .map_err(|e| e.to_string_for_tauri())
+}
+
+// ============================================================================
+// Learning Plan Commands
+// ============================================================================
+
+/// Analyze skill gaps comparing user skills to job requirements
+#[tauri::command]
+pub async fn analyze_skill_gaps(
+    job_id: Option<i64>,
+    include_all_jobs: bool,
+) -> Result<Vec<crate::learning::SkillGap>, String> {
+    crate::learning::analyze_skill_gaps(job_id, include_all_jobs)
+        .map_err(|e| e.to_string_for_tauri())
+}
+
+/// Create a learning plan from skill gaps
+#[tauri::command]
+pub async fn create_learning_plan(
     title: String,
     description: Option<String>,
     target_job_id: Option<i64>,
@@ -1083,8 +1777,10 @@ pub async fn get_companies(
 #[tauri::command]
 pub async fn get_companies_with_stats(
     search_query: Option<String>,
-) -> Result<Vec<crate::companies::CompanyWithStats>, String> {
-    crate::companies::get_companies_with_stats(search_query.as_deref())
+    page: Option<i64>,
+    page_size: Option<i64>,
+) -> Result<crate::companies::PaginatedCompanyList, String> {
+    crate::companies::get_companies_with_stats(search_query.as_deref(), page, page_size)
         .map_err(|e| e.to_string_for_tauri())
 }
 
@@ -1295,6 +1991,16 @@ pub struct UserProfile {
     pub open_to_roles: Option<String>,
     pub created_at: Option<String>,
     pub updated_at: Option<String>,
+    #[serde(default)]
+    pub email: Option<String>,
+    #[serde(default)]
+    pub phone: Option<String>,
+    #[serde(default)]
+    pub website: Option<String>,
+    #[serde(default)]
+    pub github: Option<String>,
+    #[serde(default)]
+    pub linkedin: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -1369,11 +2075,15 @@ pub struct UserProfileData {
 #[tauri::command]
 pub async fn get_user_profile_data() -> Result<UserProfileData, String> {
     let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    load_user_profile_data(&conn)
+}
 
+/// Load the full profile (id = 1) with all of its sections
+pub fn load_user_profile_data(conn: &rusqlite::Connection) -> Result<UserProfileData, String> {
     // Get or create user profile (id = 1)
     let profile: Option<UserProfile> = {
         let mut stmt = conn
-            .prepare("SELECT id, full_name, headline, location, summary, current_role_title, current_company, seniority, open_to_roles, created_at, updated_at FROM user_profile WHERE id = 1")
+            .prepare("SELECT id, full_name, headline, location, summary, current_role_title, current_company, seniority, open_to_roles, created_at, updated_at, email, phone, website, github, linkedin FROM user_profile WHERE id = 1")
             .map_err(|e| format!("Failed to prepare profile query: {}", e))?;
 
         let profile_result = stmt.query_row([], |row| {
@@ -1389,6 +2099,11 @@ pub async fn get_user_profile_data() -> Result<UserProfileData, String> {
                 open_to_roles: row.get(8)?,
                 created_at: row.get(9)?,
                 updated_at: row.get(10)?,
+                email: row.get(11)?,
+                phone: row.get(12)?,
+                website: row.get(13)?,
+                github: row.get(14)?,
+                linkedin: row.get(15)?,
             })
         });
 
@@ -1536,57 +2251,118 @@ pub async fn get_user_profile_data() -> Result<UserProfileData, String> {
 #[tauri::command]
 pub async fn save_user_profile_data(data: UserProfileData) -> Result<UserProfileData, String> {
     let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    store_user_profile_data(&conn, &data)?;
+
+    // Return updated data
+    get_user_profile_data().await
+}
+
+/// Replace the stored profile with `data`, rejecting it if the profile changed since it was loaded
+pub fn store_user_profile_data(conn: &rusqlite::Connection, data: &UserProfileData) -> Result<(), String> {
     let now = Utc::now().to_rfc3339();
-    
-    // Invalidate profile-related caches before saving
-    // This ensures resume/cover letter caches are cleared when profile changes
-    let _ = crate::ai_cache::ai_cache_invalidate_profile(&conn);
+    let tx = conn
+        .unchecked_transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
 
-    // Save or update profile
+    // The profile row carries the version check, so a stale save fails before anything else is written
     if let Some(profile) = &data.profile {
-        if profile.id.is_some() {
-            // Update existing
-            conn.execute(
-                "UPDATE user_profile SET full_name = ?, headline = ?, location = ?, summary = ?, current_role_title = ?, current_company = ?, seniority = ?, open_to_roles = ?, updated_at = ? WHERE id = 1",
-                rusqlite::params![
-                    profile.full_name,
-                    profile.headline,
-                    profile.location,
-                    profile.summary,
-                    profile.current_role_title,
-                    profile.current_company,
-                    profile.seniority,
-                    profile.open_to_roles,
-                    now
-                ],
-            )
-            .map_err(|e| format!("Failed to update profile: {}", e))?;
-        } else {
-            // Insert new
-            conn.execute(
-                "INSERT INTO user_profile (id, full_name, headline, location, summary, current_role_title, current_company, seniority, open_to_roles, created_at, updated_at) VALUES (1, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
-                rusqlite::params![
-                    profile.full_name,
-                    profile.headline,
-                    profile.location,
-                    profile.summary,
-                    profile.current_role_title,
-                    profile.current_company,
-                    profile.seniority,
-                    profile.open_to_roles,
-                    now,
-                    now
-                ],
-            )
-            .map_err(|e| format!("Failed to insert profile: {}", e))?;
+        save_profile_row(&tx, profile, &now)?;
+    }
+
+    // Invalidate profile-related caches
+    // This ensures resume/cover letter caches are cleared when profile changes
+    let _ = crate::ai_cache::ai_cache_invalidate_profile(&tx);
+    let _ = crate::ai_cache::ai_cache_invalidate_purposes(&tx, crate::ai_cache::EXPERIENCE_DEPENDENT_PURPOSES);
+
+    replace_experience(&tx, &data.experience, &now)?;
+    replace_skills(&tx, &data.skills)?;
+    replace_education(&tx, &data.education)?;
+    replace_certifications(&tx, &data.certifications)?;
+    replace_portfolio(&tx, &data.portfolio)?;
+
+    tx.commit().map_err(|e| format!("Failed to commit profile: {}", e))
+}
+
+/// Conflict error for a guarded write (`... AND updated_at IS COALESCE(?, updated_at)`)
+/// that changed no rows because the stored row was updated after the client loaded it.
+pub fn modified_since_loaded_error(conn: &rusqlite::Connection, table: &str, id: i64) -> String {
+    let stored: Option<String> = conn
+        .query_row(&format!("SELECT updated_at FROM {} WHERE id = ?", table), [id], |row| row.get(0))
+        .unwrap_or_default();
+    let message = format!(
+        "{} {} was modified at {} after it was loaded",
+        table,
+        id,
+        stored.unwrap_or_default()
+    );
+    CareerBenchError::Database(crate::errors::DatabaseError::Conflict(message)).to_string_for_tauri()
+}
+
+/// Save or update the profile row (id = 1).
+///
+/// An update carrying `updated_at` only applies if the stored row still has that
+/// version; otherwise a conflict is returned.
+fn save_profile_row(conn: &rusqlite::Connection, profile: &UserProfile, now: &str) -> Result<(), String> {
+    if profile.id.is_some() {
+        // Update existing
+        let changed = conn.execute(
+            "UPDATE user_profile SET full_name = ?, headline = ?, location = ?, summary = ?, current_role_title = ?, current_company = ?, seniority = ?, open_to_roles = ?, email = ?, phone = ?, website = ?, github = ?, linkedin = ?, updated_at = ? WHERE id = 1 AND updated_at IS COALESCE(?, updated_at)",
+            rusqlite::params![
+                profile.full_name,
+                profile.headline,
+                profile.location,
+                profile.summary,
+                profile.current_role_title,
+                profile.current_company,
+                profile.seniority,
+                profile.open_to_roles,
+                profile.email,
+                profile.phone,
+                profile.website,
+                profile.github,
+                profile.linkedin,
+                now,
+                profile.updated_at
+            ],
+        )
+        .map_err(|e| format!("Failed to update profile: {}", e))?;
+        if changed == 0 && profile.updated_at.is_some() {
+            return Err(modified_since_loaded_error(conn, "user_profile", 1));
         }
+    } else {
+        // Insert new
+        conn.execute(
+            "INSERT INTO user_profile (id, full_name, headline, location, summary, current_role_title, current_company, seniority, open_to_roles, email, phone, website, github, linkedin, created_at, updated_at) VALUES (1, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            rusqlite::params![
+                profile.full_name,
+                profile.headline,
+                profile.location,
+                profile.summary,
+                profile.current_role_title,
+                profile.current_company,
+                profile.seniority,
+                profile.open_to_roles,
+                profile.email,
+                profile.phone,
+                profile.website,
+                profile.github,
+                profile.linkedin,
+                now,
+                now
+            ],
+        )
+        .map_err(|e| format!("Failed to insert profile: {}", e))?;
     }
 
-    // Save experience (delete all and reinsert for simplicity in MVP)
+    Ok(())
+}
+
+/// Save experience (delete all and reinsert for simplicity in MVP)
+fn replace_experience(conn: &rusqlite::Connection, experience: &[Experience], now: &str) -> Result<(), String> {
     conn.execute("DELETE FROM experience WHERE user_profile_id = 1", [])
         .map_err(|e| format!("Failed to delete experience: {}", e))?;
 
-    for exp in &data.experience {
+    for exp in experience {
         conn.execute(
             "INSERT INTO experience (user_profile_id, company, title, location, start_date, end_date, is_current, description, achievements, tech_stack, created_at, updated_at) VALUES (1, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
             rusqlite::params![
@@ -1606,11 +2382,14 @@ pub async fn save_user_profile_data(data: UserProfileData) -> Result<UserProfile
         .map_err(|e| format!("Failed to insert experience: {}", e))?;
     }
 
-    // Save skills
+    Ok(())
+}
+
+fn replace_skills(conn: &rusqlite::Connection, skills: &[Skill]) -> Result<(), String> {
     conn.execute("DELETE FROM skills WHERE user_profile_id = 1", [])
         .map_err(|e| format!("Failed to delete skills: {}", e))?;
 
-    for skill in &data.skills {
+    for skill in skills {
         conn.execute(
             "INSERT INTO skills (user_profile_id, name, category, self_rating, priority, years_experience, notes) VALUES (1, ?, ?, ?, ?, ?, ?)",
             rusqlite::params![
@@ -1625,11 +2404,14 @@ pub async fn save_user_profile_data(data: UserProfileData) -> Result<UserProfile
         .map_err(|e| format!("Failed to insert skill: {}", e))?;
     }
 
-    // Save education
+    Ok(())
+}
+
+fn replace_education(conn: &rusqlite::Connection, education: &[Education]) -> Result<(), String> {
     conn.execute("DELETE FROM education WHERE user_profile_id = 1", [])
         .map_err(|e| format!("Failed to delete education: {}", e))?;
 
-    for edu in &data.education {
+    for edu in education {
         conn.execute(
             "INSERT INTO education (user_profile_id, institution, degree, field_of_study, start_date, end_date, grade, description) VALUES (1, ?, ?, ?, ?, ?, ?, ?)",
             rusqlite::params![
@@ -1645,11 +2427,14 @@ pub async fn save_user_profile_data(data: UserProfileData) -> Result<UserProfile
         .map_err(|e| format!("Failed to insert education: {}", e))?;
     }
 
-    // Save certifications
+    Ok(())
+}
+
+fn replace_certifications(conn: &rusqlite::Connection, certifications: &[Certification]) -> Result<(), String> {
     conn.execute("DELETE FROM certifications WHERE user_profile_id = 1", [])
         .map_err(|e| format!("Failed to delete certifications: {}", e))?;
 
-    for cert in &data.certifications {
+    for cert in certifications {
         conn.execute(
             "INSERT INTO certifications (user_profile_id, name, issuing_organization, issue_date, expiration_date, credential_id, credential_url) VALUES (1, ?, ?, ?, ?, ?, ?)",
             rusqlite::params![
@@ -1664,11 +2449,14 @@ pub async fn save_user_profile_data(data: UserProfileData) -> Result<UserProfile
         .map_err(|e| format!("Failed to insert certification: {}", e))?;
     }
 
-    // Save portfolio
+    Ok(())
+}
+
+fn replace_portfolio(conn: &rusqlite::Connection, portfolio: &[PortfolioItem]) -> Result<(), String> {
     conn.execute("DELETE FROM portfolio_items WHERE user_profile_id = 1", [])
         .map_err(|e| format!("Failed to delete portfolio: {}", e))?;
 
-    for item in &data.portfolio {
+    for item in portfolio {
         conn.execute(
             "INSERT INTO portfolio_items (user_profile_id, title, url, description, role, tech_stack, highlighted) VALUES (1, ?, ?, ?, ?, ?, ?)",
             rusqlite::params![
@@ -1683,47 +2471,413 @@ pub async fn save_user_profile_data(data: UserProfileData) -> Result<UserProfile
         .map_err(|e| format!("Failed to insert portfolio item: {}", e))?;
     }
 
-    // Return updated data
-    get_user_profile_data().await
-}
-
-// Job types
-#[derive(Debug, Serialize, Deserialize)]
-pub struct Job {
-    pub id: Option<i64>,
-    pub title: Option<String>,
-    pub company: Option<String>,
-    pub location: Option<String>,
-    pub job_source: Option<String>,
-    pub posting_url: Option<String>,
-    pub raw_description: Option<String>,
-    pub parsed_json: Option<String>,
-    pub seniority: Option<String>,
-    pub domain_tags: Option<String>,
-    pub is_active: bool,
-    pub date_added: String,
-    pub last_updated: String,
+    Ok(())
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct JobSummary {
-    pub id: i64,
-    pub title: Option<String>,
-    pub company: Option<String>,
-    pub location: Option<String>,
-    pub seniority: Option<String>,
-    pub domain_tags: Option<String>,
-    pub date_added: String,
+/// Partial profile update. Only sections that are present are changed.
+///
+/// `profile` is a JSON merge patch over the profile fields (a `null` value clears
+/// a field); list sections replace the stored list wholesale when provided.
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct ProfilePatch {
+    #[serde(default)]
+    pub profile: Option<serde_json::Value>,
+    #[serde(default)]
+    pub experience: Option<Vec<Experience>>,
+    #[serde(default)]
+    pub skills: Option<Vec<Skill>>,
+    #[serde(default)]
+    pub education: Option<Vec<Education>>,
+    #[serde(default)]
+    pub certifications: Option<Vec<Certification>>,
+    #[serde(default)]
+    pub portfolio: Option<Vec<PortfolioItem>>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct CreateJobInput {
-    pub title: Option<String>,
-    pub company: Option<String>,
+/// Apply an RFC 7386 JSON merge patch to `target`
+fn json_merge_patch(target: &mut serde_json::Value, patch: &serde_json::Value) {
+    match patch {
+        serde_json::Value::Object(patch_map) => {
+            if !target.is_object() {
+                *target = serde_json::Value::Object(serde_json::Map::new());
+            }
+            if let Some(target_map) = target.as_object_mut() {
+                for (key, value) in patch_map {
+                    if value.is_null() {
+                        target_map.remove(key);
+                    } else {
+                        json_merge_patch(target_map.entry(key.clone()).or_insert(serde_json::Value::Null), value);
+                    }
+                }
+            }
+        }
+        _ => *target = patch.clone(),
+    }
+}
+
+/// Apply selected profile sections without replacing the rest
+#[tauri::command]
+pub async fn apply_profile_patch(patch: ProfilePatch) -> Result<UserProfileData, String> {
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    patch_user_profile_data(&conn, &patch)
+}
+
+pub fn patch_user_profile_data(
+    conn: &rusqlite::Connection,
+    patch: &ProfilePatch,
+) -> Result<UserProfileData, String> {
+    let now = Utc::now().to_rfc3339();
+    let current = load_user_profile_data(conn)?;
+
+    // Resolve the profile patch before writing anything so an invalid patch changes nothing
+    let patched_profile = match &patch.profile {
+        Some(profile_patch) => {
+            let mut merged = match &current.profile {
+                Some(profile) => serde_json::to_value(profile)
+                    .map_err(|e| format!("Failed to serialize profile: {}", e))?,
+                None => serde_json::json!({ "full_name": "" }),
+            };
+            json_merge_patch(&mut merged, profile_patch);
+            // Identity and timestamps are managed here, not by the patch
+            if let Some(map) = merged.as_object_mut() {
+                map.insert("id".to_string(), serde_json::json!(current.profile.as_ref().and_then(|p| p.id)));
+            }
+
+            let profile: UserProfile = serde_json::from_value(merged)
+                .map_err(|e| format!("Invalid profile patch: {}", e))?;
+            if profile.full_name.trim().is_empty() {
+                return Err("Invalid profile patch: full_name is required".to_string());
+            }
+            Some(profile)
+        }
+        None => None,
+    };
+
+    let changed = patched_profile.is_some()
+        || patch.experience.is_some()
+        || patch.skills.is_some()
+        || patch.education.is_some()
+        || patch.certifications.is_some()
+        || patch.portfolio.is_some();
+    if !changed {
+        return Ok(current);
+    }
+
+    let tx = conn
+        .unchecked_transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    // A patch carrying `updated_at` is checked against the stored profile like a full save
+    if let Some(profile) = &patched_profile {
+        save_profile_row(&tx, profile, &now)?;
+    }
+
+    // Profile changes make cached resumes and letters stale
+    let _ = crate::ai_cache::ai_cache_invalidate_profile(&tx);
+
+    if patch.experience.is_some() {
+        let _ = crate::ai_cache::ai_cache_invalidate_purposes(&tx, crate::ai_cache::EXPERIENCE_DEPENDENT_PURPOSES);
+    }
+    if let Some(experience) = &patch.experience {
+        replace_experience(&tx, experience, &now)?;
+    }
+    if let Some(skills) = &patch.skills {
+        replace_skills(&tx, skills)?;
+    }
+    if let Some(education) = &patch.education {
+        replace_education(&tx, education)?;
+    }
+    if let Some(certifications) = &patch.certifications {
+        replace_certifications(&tx, certifications)?;
+    }
+    if let Some(portfolio) = &patch.portfolio {
+        replace_portfolio(&tx, portfolio)?;
+    }
+    tx.commit().map_err(|e| format!("Failed to commit profile: {}", e))?;
+
+    load_user_profile_data(conn)
+}
+
+/// Parse an experience date (`YYYY-MM` or `YYYY-MM-DD`) as the first or last day of its period
+fn parse_experience_date(date: &str, end_of_period: bool) -> Option<chrono::NaiveDate> {
+    let date = date.trim();
+    if let Ok(day) = chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d") {
+        return Some(day);
+    }
+    let first = chrono::NaiveDate::parse_from_str(&format!("{}-01", date), "%Y-%m-%d").ok()?;
+    if end_of_period {
+        first.checked_add_months(chrono::Months::new(1))?.pred_opt()
+    } else {
+        Some(first)
+    }
+}
+
+/// Split one experience row into two roles at the same company (e.g. a promotion).
+///
+/// The original row ends at `split_date`; a new row with `new_title` starts there,
+/// copying company and location and inheriting the original end date / current flag.
+/// Returns `(original_id, new_id)`.
+pub fn split_experience_entry(
+    conn: &rusqlite::Connection,
+    experience_id: i64,
+    split_date: &str,
+    new_title: &str,
+    now: &str,
+) -> Result<(i64, i64), String> {
+    let new_title = new_title.trim();
+    if new_title.is_empty() {
+        return Err("New title is required".to_string());
+    }
+    let split_date = split_date.trim();
+    let split_day = parse_experience_date(split_date, false)
+        .ok_or_else(|| format!("Invalid split date '{}'; use YYYY-MM or YYYY-MM-DD", split_date))?;
+
+    let (user_profile_id, company, location, start_date, end_date, is_current) = match conn.query_row(
+        "SELECT user_profile_id, company, location, start_date, end_date, is_current FROM experience WHERE id = ?",
+        [experience_id],
+        |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, Option<String>>(2)?,
+                row.get::<_, Option<String>>(3)?,
+                row.get::<_, Option<String>>(4)?,
+                row.get::<_, i32>(5)? != 0,
+            ))
+        },
+    ) {
+        Ok(row) => row,
+        Err(rusqlite::Error::QueryReturnedNoRows) => return Err("Experience not found".to_string()),
+        Err(e) => return Err(format!("Failed to load experience: {}", e)),
+    };
+
+    // The split must fall strictly inside the original role
+    let start = start_date.as_deref().and_then(|date| parse_experience_date(date, false));
+    let end = end_date.as_deref().and_then(|date| parse_experience_date(date, true));
+    let starts_after = !matches!(start, Some(start) if split_day <= start);
+    let ends_before = is_current || !matches!(end, Some(end) if split_day >= end);
+    if !starts_after || !ends_before {
+        return Err(format!(
+            "Split date {} must fall between the role's start ({}) and end ({})",
+            split_date,
+            start_date.as_deref().unwrap_or("unknown"),
+            if is_current { "present" } else { end_date.as_deref().unwrap_or("unknown") }
+        ));
+    }
+
+    conn.execute(
+        "UPDATE experience SET end_date = ?, is_current = 0, updated_at = ? WHERE id = ?",
+        rusqlite::params![split_date, now, experience_id],
+    )
+    .map_err(|e| format!("Failed to update experience: {}", e))?;
+
+    conn.execute(
+        "INSERT INTO experience (user_profile_id, company, title, location, start_date, end_date, is_current, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        rusqlite::params![
+            user_profile_id,
+            company,
+            new_title,
+            location,
+            split_date,
+            end_date,
+            if is_current { 1 } else { 0 },
+            now,
+            now
+        ],
+    )
+    .map_err(|e| format!("Failed to insert experience: {}", e))?;
+    let new_id = conn.last_insert_rowid();
+
+    let _ = crate::ai_cache::ai_cache_invalidate_purposes(conn, crate::ai_cache::EXPERIENCE_DEPENDENT_PURPOSES);
+
+    // Experience is part of the profile, so clients holding the old profile must reload
+    conn.execute(
+        "UPDATE user_profile SET updated_at = ? WHERE id = ?",
+        rusqlite::params![now, user_profile_id],
+    )
+    .map_err(|e| format!("Failed to update profile: {}", e))?;
+
+    Ok((experience_id, new_id))
+}
+
+/// Split an experience entry in two at `split_date` to model a promotion
+#[tauri::command]
+pub async fn split_experience(experience_id: i64, split_date: String, new_title: String) -> Result<(i64, i64), String> {
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    let now = Utc::now().to_rfc3339();
+    split_experience_entry(&conn, experience_id, &split_date, &new_title, &now)
+}
+
+/// Suggest STAR-format rewrites of an experience's achievements (not saved automatically)
+#[tauri::command]
+pub async fn convert_to_star(experience_id: i64) -> Result<Vec<String>, String> {
+    use crate::ai::resolver::ResolvedProvider;
+    use crate::resume_generator::{cache_star_rewrite, get_cached_star_rewrite, request_star_rewrite, split_achievement_bullets};
+
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    let now = Utc::now().to_rfc3339();
+
+    let achievements: Option<String> = match conn.query_row(
+        "SELECT achievements FROM experience WHERE id = ?",
+        [experience_id],
+        |row| row.get(0),
+    ) {
+        Ok(achievements) => achievements,
+        Err(rusqlite::Error::QueryReturnedNoRows) => {
+            return Err(format!("Experience {} not found", experience_id))
+        }
+        Err(e) => return Err(format!("Failed to get experience: {}", e)),
+    };
+
+    let bullets = split_achievement_bullets(achievements.as_deref().unwrap_or(""));
+    if bullets.is_empty() {
+        return Err(
+            "This role has no achievements to rewrite yet. Add a few bullet points about what you did and the outcome, then try again."
+                .to_string(),
+        );
+    }
+
+    // Cached on the achievements themselves, so unchanged bullets never hit the provider twice
+    if let Some(cached) = get_cached_star_rewrite(&conn, &bullets, &now)? {
+        return Ok(cached);
+    }
+
+    let provider = ResolvedProvider::resolve_for("star_rewrite")
+        .map_err(|e| format!("Failed to resolve provider: {}", e))?;
+    let rewritten = request_star_rewrite(provider.as_provider().as_ref(), &bullets).await?;
+
+    cache_star_rewrite(&conn, &bullets, &rewritten, &now)?;
+
+    Ok(rewritten)
+}
+
+/// Show which roles resume generation would tailor for a job, without rewriting anything
+#[tauri::command]
+pub async fn preview_role_selection(job_id: i64) -> Result<Vec<crate::resume_generator::RoleRelevance>, String> {
+    use crate::resume_generator::{preview_top_roles, summarize_job_description, RESUME_TOP_ROLES};
+
+    let ctx = GenerationContext::app()?;
+    let profile_data = load_user_profile_data(&ctx.conn())?;
+    let job = load_job(&ctx.conn(), job_id)?;
+    let parsed_job = job
+        .parsed_json
+        .as_deref()
+        .and_then(|json| serde_json::from_str::<ParsedJob>(json).ok());
+
+    // Shares the generation pipeline's cached JD summary
+    let jd_summary =
+        summarize_job_description(&ctx, job.raw_description.as_deref().unwrap_or(""), parsed_job.as_ref()).await?;
+
+    Ok(preview_top_roles(&profile_data.experience, &jd_summary, RESUME_TOP_ROLES))
+}
+
+/// Shorten artifact text (e.g. a long cover letter) to at most `max_words` words
+#[tauri::command]
+pub async fn tighten_content(
+    content: String,
+    max_words: usize,
+) -> Result<crate::resume_generator::TightenedContent, String> {
+    use crate::ai::resolver::ResolvedProvider;
+    use crate::resume_generator::{
+        cache_tightened_content, count_words, get_cached_tightened_content, request_tightened_content,
+        TightenedContent,
+    };
+
+    if max_words == 0 {
+        return Err("Word limit must be at least 1".to_string());
+    }
+    let word_count = count_words(&content);
+    if word_count <= max_words {
+        return Ok(TightenedContent {
+            content,
+            word_count,
+            max_words,
+            warning: None,
+        });
+    }
+
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    let now = Utc::now().to_rfc3339();
+
+    if let Some(cached) = get_cached_tightened_content(&conn, &content, max_words, &now)? {
+        return Ok(cached);
+    }
+
+    let provider = ResolvedProvider::resolve_for("tighten_content")
+        .map_err(|e| format!("Failed to resolve provider: {}", e))?;
+    let tightened = request_tightened_content(provider.as_provider().as_ref(), &content, max_words).await?;
+
+    // Only cache results that fit, so a later attempt can do better than a best effort
+    if tightened.warning.is_none() {
+        cache_tightened_content(&conn, &content, max_words, &tightened, &now)?;
+    }
+
+    Ok(tightened)
+}
+
+// Job types
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Job {
+    pub id: Option<i64>,
+    pub title: Option<String>,
+    pub company: Option<String>,
+    pub location: Option<String>,
+    pub job_source: Option<String>,
+    pub posting_url: Option<String>,
+    pub raw_description: Option<String>,
+    pub parsed_json: Option<String>,
+    pub seniority: Option<String>,
+    pub domain_tags: Option<String>,
+    pub is_active: bool,
+    pub date_added: String,
+    pub last_updated: String,
+    #[serde(default)]
+    pub salary_text: Option<String>,
+    /// `YYYY-MM-DD`, extracted from the description when the job is parsed
+    #[serde(default)]
+    pub application_deadline: Option<String>,
+    /// Annual range from the AI parse; both ends are equal when the posting gives one figure
+    #[serde(default)]
+    pub salary_min: Option<f64>,
+    #[serde(default)]
+    pub salary_max: Option<f64>,
+    #[serde(default)]
+    pub salary_currency: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JobSummary {
+    pub id: i64,
+    pub title: Option<String>,
+    pub company: Option<String>,
+    pub location: Option<String>,
+    pub seniority: Option<String>,
+    pub domain_tags: Option<String>,
+    pub date_added: String,
+    /// User tags, alphabetically; only filled in by the job list
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateJobInput {
+    pub title: Option<String>,
+    pub company: Option<String>,
     pub location: Option<String>,
     pub job_source: Option<String>,
     pub posting_url: Option<String>,
     pub raw_description: Option<String>,
+    /// Insert even when the job looks like one that is already saved
+    #[serde(default)]
+    pub force: bool,
+}
+
+/// Outcome of `create_job`. When the input matches a saved job and `force` is off,
+/// nothing is inserted: `job` is the existing job and `duplicate_of` is its id.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateJobResult {
+    pub job: Job,
+    pub duplicate_of: Option<i64>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -1738,8 +2892,15 @@ pub struct UpdateJobInput {
 }
 
 #[tauri::command]
-pub async fn create_job(input: CreateJobInput) -> Result<Job, String> {
+pub async fn create_job(input: CreateJobInput) -> Result<CreateJobResult, String> {
     let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    create_job_with_conn(&conn, &input)
+}
+
+pub fn create_job_with_conn(
+    conn: &rusqlite::Connection,
+    input: &CreateJobInput,
+) -> Result<CreateJobResult, String> {
     let now = Utc::now().to_rfc3339();
 
     // Validate that at least title, company, or raw_description is provided
@@ -1747,6 +2908,15 @@ pub async fn create_job(input: CreateJobInput) -> Result<Job, String> {
         return Err("At least one of title, company, or description must be provided".to_string());
     }
 
+    if !input.force {
+        if let Some(existing_id) = find_duplicate_job(conn, input)? {
+            return Ok(CreateJobResult {
+                job: load_job(conn, existing_id)?,
+                duplicate_of: Some(existing_id),
+            });
+        }
+    }
+
     conn.execute(
         "INSERT INTO jobs (title, company, location, job_source, posting_url, raw_description, is_active, date_added, last_updated) VALUES (?, ?, ?, ?, ?, ?, 1, ?, ?)",
         rusqlite::params![
@@ -1763,7 +2933,148 @@ pub async fn create_job(input: CreateJobInput) -> Result<Job, String> {
     .map_err(|e| format!("Failed to create job: {}", e))?;
 
     let id = conn.last_insert_rowid();
-    get_job_detail(id).await
+    Ok(CreateJobResult {
+        job: load_job(conn, id)?,
+        duplicate_of: None,
+    })
+}
+
+/// Lowercased words of a title or company, so "Senior Engineer " and "senior  engineer"
+/// compare equal. Punctuation is dropped along with the extra whitespace.
+fn job_match_key(value: &str) -> String {
+    value
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// A saved job with the same posting URL (ignoring tracking params and trailing slashes),
+/// or failing that the same title and company
+fn find_duplicate_job(
+    conn: &rusqlite::Connection,
+    input: &CreateJobInput,
+) -> Result<Option<i64>, String> {
+    let url_key = input.posting_url.as_deref().and_then(crate::job_urls::duplicate_check_url);
+    let title_key = input.title.as_deref().map(job_match_key).filter(|key| !key.is_empty());
+    let company_key = input.company.as_deref().map(job_match_key).filter(|key| !key.is_empty());
+
+    let mut stmt = conn
+        .prepare("SELECT id, posting_url, title, company FROM jobs ORDER BY id ASC")
+        .map_err(|e| format!("Failed to check for existing job: {}", e))?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, Option<String>>(1)?,
+                row.get::<_, Option<String>>(2)?,
+                row.get::<_, Option<String>>(3)?,
+            ))
+        })
+        .map_err(|e| format!("Failed to check for existing job: {}", e))?;
+
+    let mut title_match = None;
+    for row_result in rows {
+        let (id, posting_url, title, company) =
+            row_result.map_err(|e| format!("Failed to check for existing job: {}", e))?;
+        if url_key.is_some()
+            && posting_url.as_deref().and_then(crate::job_urls::duplicate_check_url) == url_key
+        {
+            return Ok(Some(id));
+        }
+        if title_match.is_none() && title_key.is_some() && company_key.is_some()
+            && title.as_deref().map(job_match_key) == title_key
+            && company.as_deref().map(job_match_key) == company_key
+        {
+            title_match = Some(id);
+        }
+    }
+    Ok(title_match)
+}
+
+/// Job captured by an external tool such as a browser extension
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct JobImportPayload {
+    pub title: Option<String>,
+    pub company: Option<String>,
+    pub location: Option<String>,
+    pub url: Option<String>,
+    pub description: Option<String>,
+    pub source: Option<String>,
+    pub salary: Option<String>,
+}
+
+/// Import a job from an external tool, returning the existing job if the URL was already captured
+#[tauri::command]
+pub async fn import_job_payload(payload: JobImportPayload) -> Result<Job, String> {
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    import_job_from_payload(&conn, &payload)
+}
+
+pub fn import_job_from_payload(
+    conn: &rusqlite::Connection,
+    payload: &JobImportPayload,
+) -> Result<Job, String> {
+    let clean = |value: &Option<String>| {
+        value
+            .as_deref()
+            .map(|s| s.trim())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string())
+    };
+
+    let title = clean(&payload.title);
+    let company = clean(&payload.company);
+    let description = clean(&payload.description);
+    let url = clean(&payload.url);
+
+    if title.is_none() && company.is_none() && description.is_none() {
+        return Err("At least one of title, company, or description must be provided".to_string());
+    }
+
+    // Dedupe on the posting URL, ignoring a trailing slash
+    if let Some(url) = &url {
+        let existing = conn.query_row(
+            "SELECT id FROM jobs WHERE RTRIM(posting_url, '/') = RTRIM(?, '/') LIMIT 1",
+            [url],
+            |row| row.get::<_, i64>(0),
+        );
+        match existing {
+            Ok(id) => return load_job(conn, id),
+            Err(rusqlite::Error::QueryReturnedNoRows) => {}
+            Err(e) => return Err(format!("Failed to check for existing job: {}", e)),
+        }
+    }
+
+    let now = Utc::now().to_rfc3339();
+    let source = clean(&payload.source).unwrap_or_else(|| "browser_extension".to_string());
+
+    conn.execute(
+        "INSERT INTO jobs (title, company, location, job_source, posting_url, raw_description, salary_text, is_active, date_added, last_updated) VALUES (?, ?, ?, ?, ?, ?, ?, 1, ?, ?)",
+        rusqlite::params![
+            title,
+            company,
+            clean(&payload.location),
+            source,
+            url,
+            description,
+            clean(&payload.salary),
+            now,
+            now
+        ],
+    )
+    .map_err(|e| format!("Failed to import job: {}", e))?;
+
+    load_job(conn, conn.last_insert_rowid())
+}
+
+/// Normalize every job's posting URL and merge jobs that turn out to be duplicates
+#[tauri::command]
+pub async fn normalize_job_urls() -> Result<u64, String> {
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    crate::job_urls::normalize_job_urls(&conn).map_err(|e| e.to_string_for_tauri())
 }
 
 #[tauri::command]
@@ -1879,6 +3190,100 @@ pub async fn update_job(id: i64, input: UpdateJobInput) -> Result<Job, String> {
     get_job_detail(id).await
 }
 
+/// Jobs older than this are archived by `archive_stale_jobs` unless told otherwise
+pub const DEFAULT_STALE_JOB_DAYS: i64 = 60;
+
+/// Result of `archive_stale_jobs`. In a dry run `job_ids` lists the jobs that would
+/// be archived and `archived` is 0.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StaleJobArchive {
+    pub archived: u64,
+    pub job_ids: Vec<i64>,
+    pub dry_run: bool,
+}
+
+/// Archive active jobs added more than `older_than_days` ago (default 60) that were
+/// never applied to. With `dry_run` set, only reports which jobs would be archived.
+#[tauri::command]
+pub async fn archive_stale_jobs(older_than_days: Option<i64>, dry_run: Option<bool>) -> Result<StaleJobArchive, String> {
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    archive_stale_jobs_with_conn(
+        &conn,
+        older_than_days.unwrap_or(DEFAULT_STALE_JOB_DAYS),
+        dry_run.unwrap_or(false),
+        Utc::now(),
+    )
+}
+
+pub fn archive_stale_jobs_with_conn(
+    conn: &rusqlite::Connection,
+    older_than_days: i64,
+    dry_run: bool,
+    now: chrono::DateTime<Utc>,
+) -> Result<StaleJobArchive, String> {
+    if older_than_days < 1 {
+        return Err("older_than_days must be at least 1".to_string());
+    }
+    let cutoff = (now - chrono::Duration::days(older_than_days)).format("%Y-%m-%d").to_string();
+
+    let job_ids: Vec<i64> = {
+        let mut stmt = conn
+            .prepare(
+                "SELECT j.id FROM jobs j
+                 WHERE j.is_active = 1
+                   AND date(j.date_added) < ?
+                   AND NOT EXISTS (SELECT 1 FROM applications a WHERE a.job_id = j.id)
+                 ORDER BY j.id ASC",
+            )
+            .map_err(|e| format!("Failed to prepare query: {}", e))?;
+        let rows = stmt
+            .query_map([&cutoff], |row| row.get(0))
+            .map_err(|e| format!("Failed to find stale jobs: {}", e))?;
+        rows.collect::<Result<_, _>>()
+            .map_err(|e| format!("Failed to find stale jobs: {}", e))?
+    };
+
+    if dry_run {
+        log::info!(
+            "Dry run: {} job(s) added before {} with no application would be archived",
+            job_ids.len(),
+            cutoff
+        );
+        return Ok(StaleJobArchive {
+            archived: 0,
+            job_ids,
+            dry_run: true,
+        });
+    }
+
+    let now_str = now.to_rfc3339();
+    let tx = conn
+        .unchecked_transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    let mut archived = 0;
+    for job_id in &job_ids {
+        archived += tx
+            .execute(
+                "UPDATE jobs SET is_active = 0, last_updated = ? WHERE id = ?",
+                rusqlite::params![now_str, job_id],
+            )
+            .map_err(|e| format!("Failed to archive job {}: {}", job_id, e))? as u64;
+    }
+    tx.commit().map_err(|e| format!("Failed to archive stale jobs: {}", e))?;
+    log::info!(
+        "Archived {} stale job(s) added before {} with no application: {:?}",
+        archived,
+        cutoff,
+        job_ids
+    );
+
+    Ok(StaleJobArchive {
+        archived,
+        job_ids,
+        dry_run: false,
+    })
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PaginatedJobList {
     pub jobs: Vec<JobSummary>,
@@ -1893,11 +3298,75 @@ pub async fn get_job_list(
     search: Option<String>,
     active_only: Option<bool>,
     source: Option<String>,
+    location_contains: Option<Vec<String>>,
     page: Option<i64>,
     page_size: Option<i64>,
+    needs_visa_sponsorship: Option<bool>,
+    min_salary: Option<f64>,
+    tags: Option<Vec<String>>,
 ) -> Result<PaginatedJobList, String> {
     let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    list_jobs(&conn, search, active_only, source, location_contains, page, page_size, needs_visa_sponsorship, min_salary, tags)
+}
+
+/// Whether a search word relies on characters the FTS tokenizer drops: "C++", "C#"
+/// and ".NET" would otherwise shrink to the prefixes `c*` and `net*`.
+fn has_search_symbols(word: &str) -> bool {
+    word.contains(['+', '#', '.'])
+}
 
+/// Non-empty search words with quotes removed.
+fn search_words(search: &str) -> impl Iterator<Item = String> + '_ {
+    search
+        .split_whitespace()
+        .map(|word| word.replace('"', ""))
+        .filter(|word| !word.is_empty())
+}
+
+/// FTS5 query for a free-text search: each word is quoted (so stray punctuation
+/// can't break the query syntax) and prefix-matched, and all words must match.
+/// Words containing symbols are left to [`symbol_search_terms`].
+/// Returns `None` when nothing searchable is left.
+pub fn fts_match_query(search: &str) -> Option<String> {
+    let terms: Vec<String> = search_words(search)
+        .filter(|word| !has_search_symbols(word))
+        .map(|word| format!("\"{}\"*", word))
+        .collect();
+    if terms.is_empty() {
+        None
+    } else {
+        Some(terms.join(" "))
+    }
+}
+
+/// Search words such as "C++" or ".NET" that are matched as case-insensitive
+/// substrings of the title, company, location or description instead of via FTS.
+pub fn symbol_search_terms(search: &str) -> Vec<String> {
+    search_words(search).filter(|word| has_search_symbols(word)).collect()
+}
+
+/// Paginated job summaries.
+///
+/// `location_contains` matches jobs whose location contains any of the keywords
+/// (case-insensitive); jobs parsed as remote-friendly always match.
+/// `needs_visa_sponsorship` excludes jobs that explicitly rule out sponsorship;
+/// jobs that don't mention it are kept.
+/// `min_salary` keeps jobs whose range reaches the amount, compared as-is without
+/// currency conversion; jobs with no parsed salary are left out.
+/// `tags` keeps jobs that have every one of the given user tags.
+#[allow(clippy::too_many_arguments)]
+pub fn list_jobs(
+    conn: &rusqlite::Connection,
+    search: Option<String>,
+    active_only: Option<bool>,
+    source: Option<String>,
+    location_contains: Option<Vec<String>>,
+    page: Option<i64>,
+    page_size: Option<i64>,
+    needs_visa_sponsorship: Option<bool>,
+    min_salary: Option<f64>,
+    tags: Option<Vec<String>>,
+) -> Result<PaginatedJobList, String> {
     let page = page.unwrap_or(1).max(1);
     let page_size = page_size.unwrap_or(50).max(1).min(100); // Limit to 100 per page
     let offset = (page - 1) * page_size;
@@ -1915,22 +3384,79 @@ pub async fn get_job_list(
         params.push(source_filter.clone());
     }
 
-    if let Some(search_term) = &search {
-        where_clauses.push("(title LIKE ? OR company LIKE ? OR location LIKE ? OR raw_description LIKE ?)".to_string());
-        let search_pattern = format!("%{}%", search_term);
-        for _ in 0..4 {
-            params.push(search_pattern.clone());
+    // Searches go through the FTS index and are ranked by relevance
+    let fts_query = search.as_deref().and_then(fts_match_query);
+    for term in search.as_deref().map(symbol_search_terms).unwrap_or_default() {
+        let columns = ["title", "company", "location", "raw_description"];
+        let matches: Vec<String> = columns.iter().map(|c| format!("{} LIKE ? ESCAPE '\\'", c)).collect();
+        where_clauses.push(format!("({})", matches.join(" OR ")));
+        let pattern = format!("%{}%", term.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_"));
+        params.extend(std::iter::repeat_n(pattern, columns.len()));
+    }
+
+    let location_keywords: Vec<&str> = location_contains
+        .iter()
+        .flatten()
+        .map(|k| k.trim())
+        .filter(|k| !k.is_empty())
+        .collect();
+    if !location_keywords.is_empty() {
+        let mut location_clauses = vec!["location LIKE ?"; location_keywords.len()];
+        // Remote jobs are commutable from anywhere
+        location_clauses.push(
+            "(CASE WHEN json_valid(parsed_json) THEN json_extract(parsed_json, '$.remoteFriendly') END) = 1",
+        );
+        where_clauses.push(format!("({})", location_clauses.join(" OR ")));
+        for keyword in location_keywords {
+            params.push(format!("%{}%", keyword));
         }
     }
 
+    if needs_visa_sponsorship.unwrap_or(false) {
+        where_clauses.push("(visa_sponsorship IS NULL OR visa_sponsorship = 1)".to_string());
+    }
+
+    if let Some(min_salary) = min_salary {
+        where_clauses.push("salary_max >= CAST(? AS REAL)".to_string());
+        params.push(min_salary.to_string());
+    }
+
+    let mut required_tags: Vec<String> = Vec::new();
+    for tag in tags.iter().flatten() {
+        let tag = crate::job_tags::normalize_user_tag(tag).map_err(|e| e.to_string_for_tauri())?;
+        if !required_tags.contains(&tag) {
+            required_tags.push(tag);
+        }
+    }
+    if !required_tags.is_empty() {
+        where_clauses.push(format!(
+            "jobs.id IN (SELECT job_id FROM job_tags WHERE tag IN ({}) GROUP BY job_id HAVING COUNT(DISTINCT tag) = {})",
+            vec!["?"; required_tags.len()].join(", "),
+            required_tags.len()
+        ));
+        params.extend(required_tags);
+    }
+
     let where_clause = if where_clauses.is_empty() {
         String::new()
     } else {
         format!("WHERE {}", where_clauses.join(" AND "))
     };
 
+    // The MATCH param comes before the WHERE params in the SQL text
+    let (from_clause, order_by) = match &fts_query {
+        Some(query) => {
+            params.insert(0, query.clone());
+            (
+                "jobs JOIN (SELECT rowid, bm25(jobs_fts) AS score FROM jobs_fts WHERE jobs_fts MATCH ?) AS fts ON fts.rowid = jobs.id",
+                "fts.score ASC, date_added DESC",
+            )
+        }
+        None => ("jobs", "date_added DESC"),
+    };
+
     // Get total count
-    let count_query = format!("SELECT COUNT(*) FROM jobs {}", where_clause);
+    let count_query = format!("SELECT COUNT(*) FROM {} {}", from_clause, where_clause);
     let total: i64 = if params.is_empty() {
         conn.query_row(&count_query, [], |row| row.get(0))
             .map_err(|e| format!("Failed to get total count: {}", e))?
@@ -1942,8 +3468,10 @@ pub async fn get_job_list(
 
     // Get paginated results
     let query = format!(
-        "SELECT id, title, company, location, seniority, domain_tags, date_added FROM jobs {} ORDER BY date_added DESC LIMIT ? OFFSET ?",
-        where_clause
+        "SELECT id, title, company, location, seniority, domain_tags, date_added,
+                (SELECT group_concat(tag) FROM job_tags WHERE job_tags.job_id = jobs.id) AS tags
+         FROM {} {} ORDER BY {} LIMIT ? OFFSET ?",
+        from_clause, where_clause, order_by
     );
 
     let mut stmt = conn
@@ -1966,6 +3494,11 @@ pub async fn get_job_list(
                 seniority: row.get(4)?,
                 domain_tags: row.get(5)?,
                 date_added: row.get(6)?,
+                tags: {
+                    let mut tags = crate::job_tags::split_tags(row.get::<_, Option<String>>(7)?.as_deref());
+                    tags.sort();
+                    tags
+                },
             })
         })
         .map_err(|e| format!("Failed to get jobs: {}", e))?;
@@ -1993,10 +3526,35 @@ pub async fn get_job_list(
 #[tauri::command]
 pub async fn get_job_detail(id: i64) -> Result<Job, String> {
     let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    load_job(&conn, id)
+}
 
+/// The job's posting URL with the configured tracking param, for opening in the browser
+#[tauri::command]
+pub async fn build_apply_url(job_id: i64) -> Result<String, String> {
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    crate::apply_link::build_apply_url(&conn, job_id).map_err(|e| e.to_string_for_tauri())
+}
+
+/// Get the tracking param appended to apply links
+#[tauri::command]
+pub async fn get_apply_link_tracking() -> Result<crate::apply_link::ApplyLinkTracking, String> {
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    crate::apply_link::load_apply_link_tracking(&conn).map_err(|e| e.to_string_for_tauri())
+}
+
+/// Save the apply link tracking param; clear `value` to stop appending it
+#[tauri::command]
+pub async fn save_apply_link_tracking(tracking: crate::apply_link::ApplyLinkTracking) -> Result<(), String> {
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    crate::apply_link::save_apply_link_tracking(&conn, &tracking).map_err(|e| e.to_string_for_tauri())
+}
+
+/// Load a single job by id
+pub fn load_job(conn: &rusqlite::Connection, id: i64) -> Result<Job, String> {
     let mut stmt = conn
         .prepare(
-            "SELECT id, title, company, location, job_source, posting_url, raw_description, parsed_json, seniority, domain_tags, is_active, date_added, last_updated FROM jobs WHERE id = ?"
+            "SELECT id, title, company, location, job_source, posting_url, raw_description, parsed_json, seniority, domain_tags, is_active, date_added, last_updated, salary_text, application_deadline, salary_min, salary_max, salary_currency FROM jobs WHERE id = ?"
         )
         .map_err(|e| format!("Failed to prepare query: {}", e))?;
 
@@ -2016,6 +3574,11 @@ pub async fn get_job_detail(id: i64) -> Result<Job, String> {
                 is_active: row.get::<_, i32>(10)? != 0,
                 date_added: row.get(11)?,
                 last_updated: row.get(12)?,
+                salary_text: row.get(13)?,
+                application_deadline: row.get(14)?,
+                salary_min: row.get(15)?,
+                salary_max: row.get(16)?,
+                salary_currency: row.get(17)?,
             })
         })
         .map_err(|e| format!("Job not found: {}", e))?;
@@ -2059,11 +3622,33 @@ pub struct ParsedJob {
 
     #[serde(default)]
     pub remote_friendly: Option<bool>,
+
+    #[serde(default)]
+    pub application_deadline: Option<String>,
+
+    #[serde(default)]
+    pub benefits: Vec<String>,
+
+    /// Some(false) when the posting explicitly rules out sponsorship
+    #[serde(default)]
+    pub visa_sponsorship: Option<bool>,
+
+    #[serde(default)]
+    pub salary_min: Option<f64>,
+
+    #[serde(default)]
+    pub salary_max: Option<f64>,
+
+    #[serde(default)]
+    pub salary_currency: Option<String>,
 }
 
 #[tauri::command]
 pub async fn parse_job_with_ai(job_id: i64) -> Result<ParsedJob, String> {
-    use crate::ai_cache::{ai_cache_get, ai_cache_put, compute_input_hash, CACHE_TTL_JOB_PARSE_DAYS};
+    use crate::ai_cache::{
+        ai_cache_get, ai_cache_get_similar, ai_cache_put, ai_cache_set_embedding, compute_input_hash,
+        load_semantic_cache_config, CACHE_TTL_JOB_PARSE_DAYS,
+    };
     use crate::ai::resolver::ResolvedProvider;
     use crate::ai::types::{JobParsingInput, JobMeta};
     
@@ -2081,6 +3666,13 @@ pub async fn parse_job_with_ai(job_id: i64) -> Result<ParsedJob, String> {
         return Err("Job description is empty; cannot parse.".to_string());
     }
 
+    // Deadlines are extracted in code so relative phrases resolve against when the job was added
+    let application_deadline = crate::job_deadlines::parse_anchor_date(&job.date_added)
+        .and_then(|anchor| crate::job_deadlines::extract_application_deadline(raw_description, anchor))
+        .map(|date| date.format("%Y-%m-%d").to_string());
+    let benefits = crate::job_benefits::extract_benefits(raw_description);
+    let visa_sponsorship = crate::job_benefits::detect_visa_sponsorship(raw_description);
+
     // Step 3: Build canonical input JSON for caching
     let request_payload = serde_json::json!({
         "jobDescription": raw_description,
@@ -2097,8 +3689,11 @@ pub async fn parse_job_with_ai(job_id: i64) -> Result<ParsedJob, String> {
     if let Some(cached_entry) = ai_cache_get(&conn, "job_parse", &input_hash, &now)
         .map_err(|e| format!("Cache lookup error: {}", e))? {
         // Cache hit - deserialize and return
-        let parsed: ParsedJob = serde_json::from_value(cached_entry.response_payload)
+        let mut parsed: ParsedJob = serde_json::from_value(cached_entry.response_payload)
             .map_err(|e| format!("Failed to deserialize cached response: {}", e))?;
+        parsed.application_deadline = application_deadline;
+        parsed.benefits = benefits;
+        parsed.visa_sponsorship = visa_sponsorship;
         
         // Update job with cached parsed data
         update_job_with_parsed_data(&conn, job_id, &parsed, &now)?;
@@ -2107,8 +3702,52 @@ pub async fn parse_job_with_ai(job_id: i64) -> Result<ParsedJob, String> {
     }
 
     // Step 5: Cache miss - call AI provider using new provider system
-    let provider = ResolvedProvider::resolve()
+    let provider = ResolvedProvider::resolve_for("job_parse")
         .map_err(|e| format!("Failed to resolve provider: {}", e))?;
+
+    // Get model name from settings for cache
+    let model_name = crate::ai::settings::load_ai_settings()
+        .ok()
+        .and_then(|s| s.model_for("job_parse"))
+        .unwrap_or_else(|| "unknown-model".to_string());
+
+    // Step 5b: With the semantic cache on, reuse a parse of a near-identical input
+    // made by the same model and compared in the same embedding space
+    let semantic_cache = load_semantic_cache_config(&conn).unwrap_or_default();
+    let mut embedding = None;
+    let embedder = if semantic_cache.enabled { provider.embedding_provider() } else { None };
+    if let Some((embedder, embedding_model)) = embedder {
+        let canonical_input = serde_json::to_string(&request_payload)
+            .map_err(|e| format!("Failed to serialize cache input: {}", e))?;
+        match embedder.embed(&canonical_input).await {
+            Ok(vector) => {
+                if let Some((cached_entry, similarity)) = ai_cache_get_similar(
+                    &conn,
+                    "job_parse",
+                    &model_name,
+                    &embedding_model,
+                    &vector,
+                    semantic_cache.min_similarity,
+                    &now,
+                )
+                .map_err(|e| format!("Cache lookup error: {}", e))? {
+                    log::info!("[parse_job_with_ai] Semantic cache hit for job {} (similarity {:.3})", job_id, similarity);
+                    let mut parsed: ParsedJob = serde_json::from_value(cached_entry.response_payload)
+                        .map_err(|e| format!("Failed to deserialize cached response: {}", e))?;
+                    parsed.application_deadline = application_deadline;
+                    parsed.benefits = benefits;
+                    parsed.visa_sponsorship = visa_sponsorship;
+
+                    update_job_with_parsed_data(&conn, job_id, &parsed, &now)?;
+
+                    return Ok(parsed);
+                }
+                embedding = Some((vector, embedding_model));
+            }
+            // The semantic cache is best effort; parse as usual without it
+            Err(e) => log::warn!("[parse_job_with_ai] Skipping semantic cache: {}", e),
+        }
+    }
     
     // Build job parsing input
     let parsing_input = JobParsingInput {
@@ -2154,17 +3793,17 @@ pub async fn parse_job_with_ai(job_id: i64) -> Result<ParsedJob, String> {
         domain_tags: parsed_output.domain_tags,
         seniority_score: parsed_output.seniority_score,
         remote_friendly: parsed_output.remote_friendly,
+        application_deadline,
+        benefits,
+        visa_sponsorship,
+        salary_min: parsed_output.salary_min,
+        salary_max: parsed_output.salary_max,
+        salary_currency: parsed_output.salary_currency,
     };
 
     // Step 6: Store in cache
     let response_payload = serde_json::to_value(&parsed)
         .map_err(|e| format!("Failed to serialize parsed job: {}", e))?;
-    
-    // Get model name from settings for cache
-    let model_name = crate::ai::settings::load_ai_settings()
-        .ok()
-        .and_then(|s| s.model_name)
-        .unwrap_or_else(|| "unknown-model".to_string());
 
     ai_cache_put(
         &conn,
@@ -2177,6 +3816,11 @@ pub async fn parse_job_with_ai(job_id: i64) -> Result<ParsedJob, String> {
         &now,
     )
     .map_err(|e| format!("Failed to cache result: {}", e))?;
+    if let Some((embedding, embedding_model)) = embedding {
+        if let Err(e) = ai_cache_set_embedding(&conn, "job_parse", &input_hash, &embedding, &embedding_model) {
+            log::warn!("[parse_job_with_ai] {}", e);
+        }
+    }
 
     // Step 7: Update job with parsed data
     update_job_with_parsed_data(&conn, job_id, &parsed, &now)?;
@@ -2184,8 +3828,81 @@ pub async fn parse_job_with_ai(job_id: i64) -> Result<ParsedJob, String> {
     Ok(parsed)
 }
 
-// Helper function to update job with parsed data
-fn update_job_with_parsed_data(
+/// Jobs that have a description but haven't been parsed yet
+#[tauri::command]
+pub async fn get_jobs_needing_parse() -> Result<Vec<JobSummary>, String> {
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    crate::bulk_parse::get_jobs_needing_parse(&conn).map_err(|e| e.to_string_for_tauri())
+}
+
+/// Parse every job needing it, emitting a `parse-progress` event as each one finishes
+#[tauri::command]
+pub async fn parse_all_pending(app: tauri::AppHandle) -> Result<Vec<crate::bulk_parse::ParseProgress>, String> {
+    use crate::ai::rate_limiter::RateLimiter;
+    use crate::bulk_parse::{
+        parse_jobs_concurrently, PARSE_ALL_CONCURRENCY, PARSE_ALL_REQUESTS_PER_MINUTE, PARSE_PROGRESS_EVENT,
+    };
+    use tauri::Emitter;
+
+    let job_ids: Vec<i64> = {
+        let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+        crate::bulk_parse::get_jobs_needing_parse(&conn)
+            .map_err(|e| e.to_string_for_tauri())?
+            .into_iter()
+            .map(|job| job.id)
+            .collect()
+    };
+
+    let limiter = RateLimiter::new(PARSE_ALL_REQUESTS_PER_MINUTE, 60);
+    let results = parse_jobs_concurrently(
+        job_ids,
+        PARSE_ALL_CONCURRENCY,
+        &limiter,
+        |job_id| async move { parse_job_with_ai(job_id).await.map(|_| ()) },
+        |progress| {
+            if let Err(e) = app.emit(PARSE_PROGRESS_EVENT, progress) {
+                log::warn!("Failed to emit parse progress: {}", e);
+            }
+        },
+    )
+    .await;
+    Ok(results)
+}
+
+/// Tag jobs from their descriptions using the built-in keyword dictionary (no AI call)
+#[tauri::command]
+pub async fn auto_tag_jobs_offline(job_ids: Vec<i64>) -> Result<Vec<crate::job_tags::JobTagUpdate>, String> {
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    let now = chrono::Utc::now().to_rfc3339();
+    crate::job_tags::auto_tag_jobs(&conn, &job_ids, &now).map_err(|e| e.to_string_for_tauri())
+}
+
+/// Add a user tag to a job, returning the job's tags
+#[tauri::command]
+pub async fn add_job_tag(job_id: i64, tag: String) -> Result<Vec<String>, String> {
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    let now = chrono::Utc::now().to_rfc3339();
+    crate::job_tags::add_job_user_tag(&conn, job_id, &tag, &now).map_err(|e| e.to_string_for_tauri())
+}
+
+/// Remove a user tag from a job, returning the job's remaining tags
+#[tauri::command]
+pub async fn remove_job_tag(job_id: i64, tag: String) -> Result<Vec<String>, String> {
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    crate::job_tags::remove_job_user_tag(&conn, job_id, &tag).map_err(|e| e.to_string_for_tauri())
+}
+
+/// Every user tag in use, for the job list filter
+#[tauri::command]
+pub async fn get_all_tags() -> Result<Vec<String>, String> {
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    crate::job_tags::get_all_user_tags(&conn).map_err(|e| e.to_string_for_tauri())
+}
+
+// Helper function to update job with parsed data. Benefits, visa sponsorship and the
+// salary range come only from the description, so a re-parse overwrites them even
+// when nothing is found.
+pub fn update_job_with_parsed_data(
     conn: &rusqlite::Connection,
     job_id: i64,
     parsed: &ParsedJob,
@@ -2195,13 +3912,25 @@ fn update_job_with_parsed_data(
         .map_err(|e| format!("Failed to serialize parsed job: {}", e))?;
 
     let domain_tags_str = parsed.domain_tags.join(", ");
+    let benefits_str = parsed.benefits.join(", ");
+    let (salary_min, salary_max, salary_currency) = crate::compensation::normalize_salary_range(
+        parsed.salary_min,
+        parsed.salary_max,
+        parsed.salary_currency.as_deref(),
+    );
 
     conn.execute(
-        "UPDATE jobs SET parsed_json = ?, seniority = COALESCE(?, seniority), domain_tags = COALESCE(?, domain_tags), last_updated = ? WHERE id = ?",
+        "UPDATE jobs SET parsed_json = ?, seniority = COALESCE(?, seniority), domain_tags = COALESCE(?, domain_tags), application_deadline = COALESCE(?, application_deadline), benefits = ?, visa_sponsorship = ?, salary_min = ?, salary_max = ?, salary_currency = ?, last_updated = ? WHERE id = ?",
         rusqlite::params![
             parsed_json,
             parsed.seniority,
             if domain_tags_str.is_empty() { None } else { Some(domain_tags_str) },
+            parsed.application_deadline,
+            if benefits_str.is_empty() { None } else { Some(benefits_str) },
+            parsed.visa_sponsorship,
+            salary_min,
+            salary_max,
+            salary_currency,
             now,
             job_id
         ],
@@ -2213,6 +3942,14 @@ fn update_job_with_parsed_data(
 
 
 // Application types
+/// A newly created application, plus a warning when the job is a poor match
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreatedApplication {
+    #[serde(flatten)]
+    pub application: Application,
+    pub low_match_warning: Option<crate::job_match::JobMatch>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Application {
     pub id: Option<i64>,
@@ -2247,6 +3984,10 @@ pub struct ApplicationSummary {
     pub date_saved: String,
     pub date_applied: Option<String>,
     pub last_activity_date: Option<String>,
+    #[serde(default)]
+    pub event_count: i64,
+    #[serde(default)]
+    pub latest_event_type: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -2290,6 +4031,12 @@ pub struct UpdateApplicationInput {
     pub contact_linkedin: Option<String>,
     pub location_override: Option<String>,
     pub offer_compensation: Option<String>,
+    /// The `updated_at` the client last read; the update is rejected if the row changed since
+    #[serde(default)]
+    pub expected_updated_at: Option<String>,
+    /// Permit a status change the normal pipeline doesn't allow, to correct a mistake
+    #[serde(default)]
+    pub allow_override: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -2299,10 +4046,13 @@ pub struct AddEventInput {
     pub event_date: Option<String>,
     pub title: Option<String>,
     pub details: Option<String>,
+    /// Client-generated id; repeated submits with the same id return the existing event
+    #[serde(default)]
+    pub client_event_id: Option<String>,
 }
 
 #[tauri::command]
-pub async fn create_application(input: CreateApplicationInput) -> Result<Application, String> {
+pub async fn create_application(input: CreateApplicationInput) -> Result<CreatedApplication, String> {
     let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
     let now = Utc::now().to_rfc3339();
     let status = input.status.unwrap_or_else(|| "Saved".to_string());
@@ -2342,18 +4092,40 @@ pub async fn create_application(input: CreateApplicationInput) -> Result<Applica
     )
     .map_err(|e| format!("Failed to create event: {}", e))?;
 
-    get_application_detail(application_id).await.map(|d| d.application)
+    // The warning is only a nudge, so a failure to compute it never blocks the create
+    let low_match_warning = crate::job_match::low_match_warning(&conn, input.job_id).unwrap_or(None);
+
+    let application = get_application_detail(application_id).await?.application;
+    Ok(CreatedApplication {
+        application,
+        low_match_warning,
+    })
 }
 
 #[tauri::command]
 pub async fn update_application(id: i64, input: UpdateApplicationInput) -> Result<Application, String> {
     let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
-    let now = Utc::now().to_rfc3339();
+    update_application_with_conn(&conn, id, input, Utc::now())
+}
 
-    // Get current application to check status change
-    let current_app = get_application_detail(id).await
-        .map_err(|_| "Application not found".to_string())?;
-    let old_status = current_app.application.status.clone();
+/// Apply `input` to an application, recording a StatusChanged event when the status moves.
+///
+/// With `expected_updated_at` set, the write only goes through if the stored
+/// `updated_at` still matches; otherwise nothing is changed and a conflict is returned.
+pub fn update_application_with_conn(
+    conn: &rusqlite::Connection,
+    id: i64,
+    input: UpdateApplicationInput,
+    now: chrono::DateTime<Utc>,
+) -> Result<Application, String> {
+    let current_app = load_application(conn, id)?;
+    let old_status = current_app.status.clone();
+    let now_str = now.to_rfc3339();
+
+    if let Some(status) = &input.status {
+        crate::application_status::validate_status_transition(&old_status, status, input.allow_override)
+            .map_err(|e| e.to_string_for_tauri())?;
+    }
 
     // Build update query
     let mut updates = Vec::new();
@@ -2409,102 +4181,180 @@ pub async fn update_application(id: i64, input: UpdateApplicationInput) -> Resul
     }
 
     if updates.is_empty() {
-        return Ok(current_app.application);
+        return Ok(current_app);
     }
 
     // Handle status change
     let new_status = input.status.as_ref().unwrap_or(&old_status);
-    if new_status != &old_status {
+    let status_changed = new_status != &old_status;
+    if status_changed {
         updates.push("last_activity_date = ?");
-        params.push(now.clone());
+        params.push(now_str.clone());
 
         // If status becomes Applied and date_applied is empty, set it
-        if new_status == "Applied" && input.date_applied.is_none() && current_app.application.date_applied.is_none() {
+        if new_status == "Applied" && input.date_applied.is_none() && current_app.date_applied.is_none() {
             updates.push("date_applied = ?");
-            params.push(now.clone());
+            params.push(now_str.clone());
         }
+    }
 
-        // Create StatusChanged event
-        conn.execute(
+    updates.push("updated_at = ?");
+    params.push(now_str.clone());
+
+    let tx = conn
+        .unchecked_transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    // The version check is part of the UPDATE so nothing can slip in between
+    let query = format!(
+        "UPDATE applications SET {} WHERE id = ? AND updated_at IS COALESCE(?, updated_at)",
+        updates.join(", ")
+    );
+    let mut all_params: Vec<&dyn rusqlite::ToSql> = params.iter().map(|p| p as &dyn rusqlite::ToSql).collect();
+    all_params.push(&id);
+    all_params.push(&input.expected_updated_at);
+    let changed = tx
+        .execute(&query, rusqlite::params_from_iter(all_params))
+        .map_err(|e| format!("Failed to update application: {}", e))?;
+    if changed == 0 {
+        return Err(modified_since_loaded_error(&tx, "applications", id));
+    }
+
+    if status_changed {
+        tx.execute(
             "INSERT INTO application_events (application_id, event_type, event_date, from_status, to_status, created_at) VALUES (?, ?, ?, ?, ?, ?)",
             rusqlite::params![
                 id,
                 "StatusChanged",
-                now,
+                now_str,
                 old_status,
                 new_status,
-                now
+                now_str
             ],
         )
         .map_err(|e| format!("Failed to create status change event: {}", e))?;
     }
 
-    updates.push("updated_at = ?");
-    params.push(now.clone());
+    tx.commit().map_err(|e| format!("Failed to commit application update: {}", e))?;
 
-    // Build query and execute - avoid holding references across await
-    let query = format!("UPDATE applications SET {} WHERE id = ?", updates.join(", "));
-    
-    // Use rusqlite::params! macro which is Send-safe
-    let param_count = params.len();
-    match param_count {
-        1 => {
-            conn.execute(&query, rusqlite::params![params[0], id])
-                .map_err(|e| format!("Failed to update application: {}", e))?;
-        }
-        2 => {
-            conn.execute(&query, rusqlite::params![params[0], params[1], id])
-                .map_err(|e| format!("Failed to update application: {}", e))?;
-        }
-        3 => {
-            conn.execute(&query, rusqlite::params![params[0], params[1], params[2], id])
-                .map_err(|e| format!("Failed to update application: {}", e))?;
-        }
-        4 => {
-            conn.execute(&query, rusqlite::params![params[0], params[1], params[2], params[3], id])
-                .map_err(|e| format!("Failed to update application: {}", e))?;
+    if status_changed {
+        // The status change is already saved, so a failed reminder shouldn't undo it
+        if let Err(e) = crate::reminders::create_status_follow_up_reminder(conn, id, &old_status, new_status, now.naive_utc()) {
+            log::warn!("Failed to create follow-up reminder for application {}: {}", id, e);
         }
-        5 => {
-            conn.execute(&query, rusqlite::params![params[0], params[1], params[2], params[3], params[4], id])
-                .map_err(|e| format!("Failed to update application: {}", e))?;
-        }
-        6 => {
-            conn.execute(&query, rusqlite::params![params[0], params[1], params[2], params[3], params[4], params[5], id])
-                .map_err(|e| format!("Failed to update application: {}", e))?;
-        }
-        7 => {
-            conn.execute(&query, rusqlite::params![params[0], params[1], params[2], params[3], params[4], params[5], params[6], id])
-                .map_err(|e| format!("Failed to update application: {}", e))?;
-        }
-        8 => {
-            conn.execute(&query, rusqlite::params![params[0], params[1], params[2], params[3], params[4], params[5], params[6], params[7], id])
-                .map_err(|e| format!("Failed to update application: {}", e))?;
+    }
+
+    load_application(conn, id)
+}
+
+/// Outcome of `bulk_update_application_status`
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct BulkStatusUpdateResult {
+    /// Applications moved to the new status
+    pub updated: Vec<i64>,
+    /// Applications already in the new status, left untouched
+    pub unchanged: Vec<i64>,
+    /// Applications whose current status can't move to the new one, left untouched
+    #[serde(default)]
+    pub not_allowed: Vec<i64>,
+    /// IDs with no matching application
+    pub not_found: Vec<i64>,
+}
+
+/// Set the same status on several applications at once, e.g. after a wave of rejections
+#[tauri::command]
+pub async fn bulk_update_application_status(
+    application_ids: Vec<i64>,
+    status: String,
+) -> Result<BulkStatusUpdateResult, String> {
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    let now = Utc::now().to_rfc3339();
+    bulk_update_application_status_with_conn(&conn, &application_ids, &status, &now)
+}
+
+/// Runs in one transaction, recording a StatusChanged event per application moved.
+/// Missing IDs and disallowed transitions are reported rather than failing the batch.
+pub fn bulk_update_application_status_with_conn(
+    conn: &rusqlite::Connection,
+    application_ids: &[i64],
+    status: &str,
+    now: &str,
+) -> Result<BulkStatusUpdateResult, String> {
+    let status = status.trim();
+    if status.is_empty() {
+        return Err("Status is required".to_string());
+    }
+    if crate::application_status::ApplicationStatus::parse(status).is_none() {
+        return Err(format!("Unknown status '{}'", status));
+    }
+
+    let tx = conn
+        .unchecked_transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    let mut result = BulkStatusUpdateResult::default();
+    let mut transitions: Vec<(i64, String)> = Vec::new();
+
+    for &id in application_ids {
+        if result.updated.contains(&id)
+            || result.unchanged.contains(&id)
+            || result.not_found.contains(&id)
+            || result.not_allowed.contains(&id)
+        {
+            continue;
         }
-        _ => {
-            // Fallback for many params - use a helper to build params array
-            // We'll manually handle up to 15 params, which should be more than enough
-            if param_count <= 15 {
-                let mut all_params = params.clone();
-                all_params.push(id.to_string());
-                // Use a macro-like approach with match for safety
-                let result = match all_params.len() {
-                    9 => conn.execute(&query, rusqlite::params![all_params[0], all_params[1], all_params[2], all_params[3], all_params[4], all_params[5], all_params[6], all_params[7], all_params[8]]),
-                    10 => conn.execute(&query, rusqlite::params![all_params[0], all_params[1], all_params[2], all_params[3], all_params[4], all_params[5], all_params[6], all_params[7], all_params[8], all_params[9]]),
-                    11 => conn.execute(&query, rusqlite::params![all_params[0], all_params[1], all_params[2], all_params[3], all_params[4], all_params[5], all_params[6], all_params[7], all_params[8], all_params[9], all_params[10]]),
-                    12 => conn.execute(&query, rusqlite::params![all_params[0], all_params[1], all_params[2], all_params[3], all_params[4], all_params[5], all_params[6], all_params[7], all_params[8], all_params[9], all_params[10], all_params[11]]),
-                    13 => conn.execute(&query, rusqlite::params![all_params[0], all_params[1], all_params[2], all_params[3], all_params[4], all_params[5], all_params[6], all_params[7], all_params[8], all_params[9], all_params[10], all_params[11], all_params[12]]),
-                    14 => conn.execute(&query, rusqlite::params![all_params[0], all_params[1], all_params[2], all_params[3], all_params[4], all_params[5], all_params[6], all_params[7], all_params[8], all_params[9], all_params[10], all_params[11], all_params[12], all_params[13]]),
-                    15 => conn.execute(&query, rusqlite::params![all_params[0], all_params[1], all_params[2], all_params[3], all_params[4], all_params[5], all_params[6], all_params[7], all_params[8], all_params[9], all_params[10], all_params[11], all_params[12], all_params[13], all_params[14]]),
-                    _ => return Err("Too many parameters for update query".to_string()),
-                };
-                result.map_err(|e| format!("Failed to update application: {}", e))?;
-            } else {
-                return Err("Too many parameters for update query".to_string());
+        let current = tx.query_row(
+            "SELECT status, date_applied FROM applications WHERE id = ?",
+            [id],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, Option<String>>(1)?)),
+        );
+        let (old_status, date_applied) = match current {
+            Ok(row) => row,
+            Err(rusqlite::Error::QueryReturnedNoRows) => {
+                result.not_found.push(id);
+                continue;
             }
+            Err(e) => return Err(format!("Failed to load application {}: {}", id, e)),
+        };
+        if old_status == status {
+            result.unchanged.push(id);
+            continue;
         }
+        if crate::application_status::validate_status_transition(&old_status, status, false).is_err() {
+            result.not_allowed.push(id);
+            continue;
+        }
+
+        // Same bookkeeping as a single status change in update_application
+        let date_applied = match date_applied {
+            None if status == "Applied" => Some(now.to_string()),
+            other => other,
+        };
+        tx.execute(
+            "UPDATE applications SET status = ?, date_applied = ?, last_activity_date = ?, updated_at = ? WHERE id = ?",
+            rusqlite::params![status, date_applied, now, now, id],
+        )
+        .map_err(|e| format!("Failed to update application {}: {}", id, e))?;
+        tx.execute(
+            "INSERT INTO application_events (application_id, event_type, event_date, from_status, to_status, created_at) VALUES (?, ?, ?, ?, ?, ?)",
+            rusqlite::params![id, "StatusChanged", now, old_status, status, now],
+        )
+        .map_err(|e| format!("Failed to create status change event: {}", e))?;
+        result.updated.push(id);
+        transitions.push((id, old_status));
     }
 
-    get_application_detail(id).await.map(|d| d.application)
+    tx.commit().map_err(|e| format!("Failed to commit status updates: {}", e))?;
+
+    // Follow-up reminders as for a single update; the status changes are already saved
+    let now_utc = chrono::DateTime::parse_from_rfc3339(now)
+        .map(|d| d.naive_utc())
+        .unwrap_or_else(|_| Utc::now().naive_utc());
+    for (id, old_status) in transitions {
+        if let Err(e) = crate::reminders::create_status_follow_up_reminder(conn, id, &old_status, status, now_utc) {
+            log::warn!("Failed to create follow-up reminder for application {}: {}", id, e);
+        }
+    }
+    Ok(result)
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -2523,36 +4373,38 @@ pub async fn get_applications(
     active_only: Option<bool>,
     page: Option<i64>,
     page_size: Option<i64>,
+    order_by: Option<String>,
 ) -> Result<PaginatedApplicationList, String> {
     let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    list_applications(&conn, status, job_id, active_only, page, page_size, order_by)
+}
 
+/// Paginated application summaries, with per-application event rollups.
+///
+/// `order_by` = "board" sorts by status in pipeline order, then the Kanban position
+/// within each status; unset or "date_saved" sorts newest saved first.
+pub fn list_applications(
+    conn: &rusqlite::Connection,
+    status: Option<String>,
+    job_id: Option<i64>,
+    active_only: Option<bool>,
+    page: Option<i64>,
+    page_size: Option<i64>,
+    order_by: Option<String>,
+) -> Result<PaginatedApplicationList, String> {
+    let order_by = match order_by.as_deref().map(str::trim) {
+        None | Some("") | Some("date_saved") => "a.date_saved DESC".to_string(),
+        Some("board") => format!(
+            "{}, a.board_order ASC, a.date_saved DESC",
+            crate::application_status::ApplicationStatus::pipeline_rank_sql("a.status")
+        ),
+        Some(other) => return Err(format!("Unknown order_by '{}': expected board or date_saved", other)),
+    };
     let page = page.unwrap_or(1).max(1);
     let page_size = page_size.unwrap_or(50).max(1).min(100); // Limit to 100 per page
     let offset = (page - 1) * page_size;
 
-    // Build WHERE clause
-    let mut where_clauses = Vec::new();
-    let mut params: Vec<String> = Vec::new();
-
-    if active_only.unwrap_or(true) {
-        where_clauses.push("a.archived = 0".to_string());
-    }
-
-    if let Some(status_filter) = &status {
-        where_clauses.push("a.status = ?".to_string());
-        params.push(status_filter.clone());
-    }
-
-    if let Some(job_id_filter) = job_id {
-        where_clauses.push("a.job_id = ?".to_string());
-        params.push(job_id_filter.to_string());
-    }
-
-    let where_clause = if where_clauses.is_empty() {
-        String::new()
-    } else {
-        format!("WHERE {}", where_clauses.join(" AND "))
-    };
+    let (where_clause, params) = application_filters(status.as_deref(), job_id, active_only);
 
     // Get total count
     let count_query = format!("SELECT COUNT(*) FROM applications a LEFT JOIN jobs j ON a.job_id = j.id {}", where_clause);
@@ -2565,10 +4417,21 @@ pub async fn get_applications(
             .map_err(|e| format!("Failed to get total count: {}", e))?
     };
 
-    // Get paginated results
+    // Get paginated results; event count and latest event type come from one windowed
+    // pass over application_events rather than a query per application
     let query = format!(
-        "SELECT a.id, a.job_id, j.title, j.company, a.status, a.priority, a.date_saved, a.date_applied, a.last_activity_date FROM applications a LEFT JOIN jobs j ON a.job_id = j.id {} ORDER BY a.date_saved DESC LIMIT ? OFFSET ?",
-        where_clause
+        "SELECT a.id, a.job_id, j.title, j.company, a.status, a.priority, a.date_saved, a.date_applied, a.last_activity_date,
+                COALESCE(ev.event_count, 0), ev.event_type
+         FROM applications a
+         LEFT JOIN jobs j ON a.job_id = j.id
+         LEFT JOIN (
+             SELECT application_id, event_type,
+                    COUNT(*) OVER (PARTITION BY application_id) AS event_count,
+                    ROW_NUMBER() OVER (PARTITION BY application_id ORDER BY event_date DESC, id DESC) AS event_rank
+             FROM application_events
+         ) ev ON ev.application_id = a.id AND ev.event_rank = 1
+         {} ORDER BY {} LIMIT ? OFFSET ?",
+        where_clause, order_by
     );
 
     let mut stmt = conn
@@ -2593,6 +4456,8 @@ pub async fn get_applications(
                 date_saved: row.get(6)?,
                 date_applied: row.get(7)?,
                 last_activity_date: row.get(8)?,
+                event_count: row.get(9)?,
+                latest_event_type: row.get(10)?,
             })
         })
         .map_err(|e| format!("Failed to get applications: {}", e))?;
@@ -2617,18 +4482,214 @@ pub async fn get_applications(
     })
 }
 
+/// WHERE clause (over `applications a`) and params shared by the application list and export
+fn application_filters(
+    status: Option<&str>,
+    job_id: Option<i64>,
+    active_only: Option<bool>,
+) -> (String, Vec<String>) {
+    let mut where_clauses = Vec::new();
+    let mut params: Vec<String> = Vec::new();
+
+    if active_only.unwrap_or(true) {
+        where_clauses.push("a.archived = 0".to_string());
+    }
+
+    if let Some(status_filter) = status {
+        where_clauses.push("a.status = ?".to_string());
+        params.push(status_filter.to_string());
+    }
+
+    if let Some(job_id_filter) = job_id {
+        where_clauses.push("a.job_id = ?".to_string());
+        params.push(job_id_filter.to_string());
+    }
+
+    let where_clause = if where_clauses.is_empty() {
+        String::new()
+    } else {
+        format!("WHERE {}", where_clauses.join(" AND "))
+    };
+    (where_clause, params)
+}
+
+/// Export every application matching the `get_applications` filters as CSV, ignoring pagination
 #[tauri::command]
-pub async fn get_application_detail(id: i64) -> Result<ApplicationDetail, String> {
+pub async fn export_applications_csv(
+    status: Option<String>,
+    job_id: Option<i64>,
+    active_only: Option<bool>,
+) -> Result<String, String> {
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    export_applications_csv_with_conn(&conn, status, job_id, active_only)
+}
+
+pub fn export_applications_csv_with_conn(
+    conn: &rusqlite::Connection,
+    status: Option<String>,
+    job_id: Option<i64>,
+    active_only: Option<bool>,
+) -> Result<String, String> {
+    const COLUMNS: [&str; 10] = [
+        "company",
+        "title",
+        "status",
+        "priority",
+        "channel",
+        "date_saved",
+        "date_applied",
+        "last_activity_date",
+        "next_action_date",
+        "notes_summary",
+    ];
+
+    let (where_clause, params) = application_filters(status.as_deref(), job_id, active_only);
+    let query = format!(
+        "SELECT j.company, j.title, a.status, a.priority, a.channel, a.date_saved, a.date_applied,
+                a.last_activity_date, a.next_action_date, a.notes_summary
+         FROM applications a
+         LEFT JOIN jobs j ON a.job_id = j.id
+         {} ORDER BY a.date_saved DESC, a.id DESC",
+        where_clause
+    );
+    let mut stmt = conn
+        .prepare(&query)
+        .map_err(|e| format!("Failed to prepare query: {}", e))?;
+    let mut rows = stmt
+        .query(rusqlite::params_from_iter(params.iter()))
+        .map_err(|e| format!("Failed to export applications: {}", e))?;
+
+    let mut csv = COLUMNS.join(",");
+    csv.push('\n');
+    while let Some(row) = rows.next().map_err(|e| format!("Failed to export applications: {}", e))? {
+        let mut fields = Vec::with_capacity(COLUMNS.len());
+        for i in 0..COLUMNS.len() {
+            let value: Option<String> = row.get(i).map_err(|e| format!("Failed to read application: {}", e))?;
+            fields.push(crate::ai::usage_log::csv_field(value.as_deref().unwrap_or("")));
+        }
+        csv.push_str(&fields.join(","));
+        csv.push('\n');
+    }
+    Ok(csv)
+}
+
+/// Save the Kanban order of one status column: `application_ids[0]` goes first.
+/// Every ID must be an application currently in `status`.
+#[tauri::command]
+pub async fn reorder_applications(status: String, application_ids: Vec<i64>) -> Result<(), String> {
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    reorder_applications_with_conn(&conn, &status, &application_ids)
+}
+
+/// Board position isn't an edit to the application, so `updated_at` is left alone;
+/// otherwise dragging a card would make an open edit form look stale
+pub fn reorder_applications_with_conn(
+    conn: &rusqlite::Connection,
+    status: &str,
+    application_ids: &[i64],
+) -> Result<(), String> {
+    let tx = conn
+        .unchecked_transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    for (position, id) in application_ids.iter().enumerate() {
+        let updated = tx
+            .execute(
+                "UPDATE applications SET board_order = ? WHERE id = ? AND status = ?",
+                rusqlite::params![position as i64, id, status],
+            )
+            .map_err(|e| format!("Failed to reorder application {}: {}", id, e))?;
+        if updated == 0 {
+            // Dropping the transaction rolls back the positions already written
+            return Err(format!("Application {} not found in the {} column", id, status));
+        }
+    }
+    tx.commit().map_err(|e| format!("Failed to save board order: {}", e))?;
+    Ok(())
+}
+
+/// An application whose next action date has passed
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OverdueAction {
+    #[serde(flatten)]
+    pub application: ApplicationSummary,
+    pub next_action_date: String,
+    pub next_action_note: Option<String>,
+    pub days_overdue: i64,
+}
+
+/// Unarchived applications whose next action date is before today, most overdue first
+#[tauri::command]
+pub async fn get_overdue_actions() -> Result<Vec<OverdueAction>, String> {
     let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    get_overdue_actions_with_conn(&conn, chrono::Local::now().date_naive())
+}
+
+/// `today` is the user's local date; an action due today isn't overdue yet
+pub fn get_overdue_actions_with_conn(
+    conn: &rusqlite::Connection,
+    today: chrono::NaiveDate,
+) -> Result<Vec<OverdueAction>, String> {
+    let today = today.format("%Y-%m-%d").to_string();
+    let mut stmt = conn
+        .prepare(
+            "SELECT a.id, a.job_id, j.title, j.company, a.status, a.priority, a.date_saved, a.date_applied, a.last_activity_date,
+                    COALESCE(ev.event_count, 0), ev.event_type,
+                    a.next_action_date, a.next_action_note,
+                    CAST(julianday(?1) - julianday(substr(a.next_action_date, 1, 10)) AS INTEGER) AS days_overdue
+             FROM applications a
+             LEFT JOIN jobs j ON a.job_id = j.id
+             LEFT JOIN (
+                 SELECT application_id, event_type,
+                        COUNT(*) OVER (PARTITION BY application_id) AS event_count,
+                        ROW_NUMBER() OVER (PARTITION BY application_id ORDER BY event_date DESC, id DESC) AS event_rank
+                 FROM application_events
+             ) ev ON ev.application_id = a.id AND ev.event_rank = 1
+             WHERE a.archived = 0
+               AND a.next_action_date IS NOT NULL
+               AND substr(a.next_action_date, 1, 10) < ?1
+             ORDER BY days_overdue DESC, a.id ASC",
+        )
+        .map_err(|e| format!("Failed to prepare query: {}", e))?;
+
+    let rows = stmt
+        .query_map([&today], |row| {
+            Ok(OverdueAction {
+                application: ApplicationSummary {
+                    id: row.get(0)?,
+                    job_id: row.get(1)?,
+                    job_title: row.get(2)?,
+                    company: row.get(3)?,
+                    status: row.get(4)?,
+                    priority: row.get(5)?,
+                    date_saved: row.get(6)?,
+                    date_applied: row.get(7)?,
+                    last_activity_date: row.get(8)?,
+                    event_count: row.get(9)?,
+                    latest_event_type: row.get(10)?,
+                },
+                next_action_date: row.get(11)?,
+                next_action_note: row.get(12)?,
+                days_overdue: row.get(13)?,
+            })
+        })
+        .map_err(|e| format!("Failed to get overdue actions: {}", e))?;
+
+    let mut actions = Vec::new();
+    for row_result in rows {
+        actions.push(row_result.map_err(|e| format!("Error: {}", e))?);
+    }
+    Ok(actions)
+}
 
-    // Get application
+/// Load a single application row
+pub fn load_application(conn: &rusqlite::Connection, id: i64) -> Result<Application, String> {
     let mut stmt = conn
         .prepare(
             "SELECT id, job_id, status, channel, priority, date_saved, date_applied, last_activity_date, next_action_date, next_action_note, notes_summary, contact_name, contact_email, contact_linkedin, location_override, offer_compensation, archived, created_at, updated_at FROM applications WHERE id = ?"
         )
         .map_err(|e| format!("Failed to prepare query: {}", e))?;
 
-    let application = stmt
+    stmt
         .query_row([id], |row| {
             Ok(Application {
                 id: Some(row.get(0)?),
@@ -2652,7 +4713,13 @@ pub async fn get_application_detail(id: i64) -> Result<ApplicationDetail, String
                 updated_at: row.get(18)?,
             })
         })
-        .map_err(|e| format!("Application not found: {}", e))?;
+        .map_err(|e| format!("Application not found: {}", e))
+}
+
+#[tauri::command]
+pub async fn get_application_detail(id: i64) -> Result<ApplicationDetail, String> {
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    let application = load_application(&conn, id)?;
 
     // Get events
     let mut stmt = conn
@@ -2688,17 +4755,48 @@ pub async fn get_application_detail(id: i64) -> Result<ApplicationDetail, String
 #[tauri::command]
 pub async fn add_application_event(input: AddEventInput) -> Result<ApplicationEvent, String> {
     let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    insert_application_event(&conn, &input)
+}
+
+/// Remove duplicate timeline events for one application, or for all when None
+#[tauri::command]
+pub async fn dedupe_application_events(application_id: Option<i64>) -> Result<u64, String> {
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    crate::event_dedupe::dedupe_application_events(&conn, application_id).map_err(|e| e.to_string_for_tauri())
+}
+
+/// Insert an application event, returning the existing event if `client_event_id` was already used
+pub fn insert_application_event(
+    conn: &rusqlite::Connection,
+    input: &AddEventInput,
+) -> Result<ApplicationEvent, String> {
     let now = Utc::now().to_rfc3339();
-    let event_date = input.event_date.unwrap_or_else(|| now.clone());
+    let event_date = input.event_date.clone().unwrap_or_else(|| now.clone());
+
+    // A repeat with the same client id is a double-submit; don't insert again
+    if let Some(client_event_id) = &input.client_event_id {
+        let existing = conn.query_row(
+            "SELECT id FROM application_events WHERE application_id = ? AND client_event_id = ?",
+            rusqlite::params![input.application_id, client_event_id],
+            |row| row.get::<_, i64>(0),
+        );
+
+        match existing {
+            Ok(event_id) => return get_application_event(conn, event_id),
+            Err(rusqlite::Error::QueryReturnedNoRows) => {}
+            Err(e) => return Err(format!("Failed to check existing event: {}", e)),
+        }
+    }
 
     conn.execute(
-        "INSERT INTO application_events (application_id, event_type, event_date, title, details, created_at) VALUES (?, ?, ?, ?, ?, ?)",
+        "INSERT INTO application_events (application_id, event_type, event_date, title, details, client_event_id, created_at) VALUES (?, ?, ?, ?, ?, ?, ?)",
         rusqlite::params![
             input.application_id,
             input.event_type,
             event_date,
             input.title,
             input.details,
+            input.client_event_id,
             now
         ],
     )
@@ -2713,7 +4811,10 @@ pub async fn add_application_event(input: AddEventInput) -> Result<ApplicationEv
     )
     .map_err(|e| format!("Failed to update last activity: {}", e))?;
 
-    // Get the created event
+    get_application_event(conn, event_id)
+}
+
+fn get_application_event(conn: &rusqlite::Connection, event_id: i64) -> Result<ApplicationEvent, String> {
     let mut stmt = conn
         .prepare("SELECT id, application_id, event_type, event_date, from_status, to_status, title, details, created_at FROM application_events WHERE id = ?")
         .map_err(|e| format!("Failed to prepare query: {}", e))?;
@@ -2751,6 +4852,39 @@ pub async fn archive_application(id: i64) -> Result<Application, String> {
     get_application_detail(id).await.map(|d| d.application)
 }
 
+/// Restore an archived application, recording an Unarchived event
+#[tauri::command]
+pub async fn unarchive_application(id: i64) -> Result<Application, String> {
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    let now = Utc::now().to_rfc3339();
+    unarchive_application_with_conn(&conn, id, &now)?;
+
+    get_application_detail(id).await.map(|d| d.application)
+}
+
+pub fn unarchive_application_with_conn(conn: &rusqlite::Connection, id: i64, now: &str) -> Result<(), String> {
+    let tx = conn
+        .unchecked_transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    let updated = tx
+        .execute(
+            "UPDATE applications SET archived = 0, updated_at = ? WHERE id = ?",
+            rusqlite::params![now, id],
+        )
+        .map_err(|e| format!("Failed to unarchive application: {}", e))?;
+    if updated == 0 {
+        return Err(format!("Application {} not found", id));
+    }
+
+    tx.execute(
+        "INSERT INTO application_events (application_id, event_type, event_date, created_at) VALUES (?, ?, ?, ?)",
+        rusqlite::params![id, "Unarchived", now, now],
+    )
+    .map_err(|e| format!("Failed to create event: {}", e))?;
+    tx.commit().map_err(|e| format!("Failed to unarchive application: {}", e))?;
+    Ok(())
+}
+
 // Resume & Cover Letter types
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ResumeSection {
@@ -2765,6 +4899,53 @@ pub struct ResumeSectionItem {
     pub bullets: Vec<String>,
 }
 
+/// Contact details shown under the resume headline
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ResumeContact {
+    pub location: Option<String>,
+    pub email: Option<String>,
+    pub phone: Option<String>,
+    pub website: Option<String>,
+    pub github: Option<String>,
+    pub linkedin: Option<String>,
+}
+
+impl ResumeContact {
+    pub fn from_profile(profile: &UserProfile) -> Self {
+        Self {
+            location: profile.location.clone(),
+            email: profile.email.clone(),
+            phone: profile.phone.clone(),
+            website: profile.website.clone(),
+            github: profile.github.clone(),
+            linkedin: profile.linkedin.clone(),
+        }
+    }
+
+    /// Present fields in a fixed order, separated by bullets; None when all are empty
+    pub fn header_line(&self) -> Option<String> {
+        let fields: Vec<&str> = [
+            &self.location,
+            &self.email,
+            &self.phone,
+            &self.website,
+            &self.github,
+            &self.linkedin,
+        ]
+        .into_iter()
+        .filter_map(|field| field.as_deref().map(str::trim))
+        .filter(|field| !field.is_empty())
+        .collect();
+
+        if fields.is_empty() {
+            None
+        } else {
+            Some(fields.join(" • "))
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct GeneratedResume {
@@ -2772,6 +4953,9 @@ pub struct GeneratedResume {
     pub headline: Option<String>,
     pub sections: Vec<ResumeSection>,
     pub highlights: Vec<String>,
+    /// Set when the resume was generated with `include_contact_header`
+    #[serde(default)]
+    pub contact: Option<ResumeContact>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -2784,24 +4968,53 @@ pub struct GeneratedLetter {
     pub signature: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct GenerationOptions {
     pub tone: Option<String>,
     pub length: Option<String>,
     pub focus: Option<String>,
     pub audience: Option<String>, // For cover letters
+    /// Overrides the saved resume date format for this generation
+    #[serde(default)]
+    pub date_format: Option<ResumeDateFormat>,
+    /// Render a contact line (location, email, phone, links) under the resume headline
+    #[serde(default)]
+    pub include_contact_header: Option<bool>,
+    /// Resume layout from `list_resume_templates`; "chronological" when unset
+    #[serde(default)]
+    pub template: Option<String>,
+    /// Add a Certifications section from the profile (off by default)
+    #[serde(default)]
+    pub include_certifications: Option<bool>,
+    /// Add a Portfolio section from the profile (off by default)
+    #[serde(default)]
+    pub include_portfolio: Option<bool>,
+}
+
+impl GenerationOptions {
+    pub fn includes_certifications(options: Option<&GenerationOptions>) -> bool {
+        options.and_then(|opt| opt.include_certifications).unwrap_or(false)
+    }
+
+    pub fn includes_portfolio(options: Option<&GenerationOptions>) -> bool {
+        options.and_then(|opt| opt.include_portfolio).unwrap_or(false)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ResumeGenerationResult {
     pub resume: GeneratedResume,
     pub content: String,
+    #[serde(default)]
+    pub warnings: Vec<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct LetterGenerationResult {
     pub letter: GeneratedLetter,
     pub content: String,
+    #[serde(default)]
+    pub warnings: Vec<String>,
 }
 
 #[tauri::command]
@@ -2809,21 +5022,30 @@ pub async fn generate_resume_for_job(
     job_id: i64,
     _application_id: Option<i64>,
     options: Option<GenerationOptions>,
-) -> Result<ResumeGenerationResult, String> {
+) -> Result<ResumeGenerationResult, UserFacingError> {
+    let ctx = GenerationContext::app()?;
+    generate_resume_with_context(&ctx, job_id, options).await
+}
+
+/// Generate (or load from cache) a resume for a job using `ctx`'s database and providers
+pub async fn generate_resume_with_context(
+    ctx: &GenerationContext,
+    job_id: i64,
+    options: Option<GenerationOptions>,
+) -> Result<ResumeGenerationResult, UserFacingError> {
     use crate::ai_cache::{ai_cache_get, ai_cache_put, compute_input_hash, CACHE_TTL_RESUME_DAYS};
     use crate::resume_generator::*;
     
-    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
     let now = Utc::now().to_rfc3339();
 
     // Load user profile
-    let profile_data = get_user_profile_data().await?;
+    let profile_data = load_user_profile_data(&ctx.conn())?;
     if profile_data.profile.is_none() {
-        return Err("User profile not found. Please set up your profile first.".to_string());
+        return Err(UserFacingError::from_message("User profile not found. Please set up your profile first."));
     }
 
     // Load job
-    let job = get_job_detail(job_id).await?;
+    let job = load_job(&ctx.conn(), job_id)?;
     let job_description = job.raw_description.as_deref().unwrap_or("");
 
     // Parse job if not already parsed
@@ -2833,34 +5055,35 @@ pub async fn generate_resume_for_job(
         None
     };
 
-    // Build canonical request payload for final resume cache
-    let request_payload = serde_json::json!({
-        "userProfile": profile_data.profile,
-        "experience": profile_data.experience,
-        "skills": profile_data.skills,
-        "education": profile_data.education,
-        "job": {
-            "title": job.title,
-            "company": job.company,
-            "rawDescription": job.raw_description,
-        },
-        "options": options
-    });
+    let date_format = options
+        .as_ref()
+        .and_then(|opt| opt.date_format)
+        .unwrap_or_else(|| load_resume_date_format_with_conn(&ctx.conn()));
+
+    let template = resume_template_from_options(options.as_ref())?;
+    let output_language = crate::language::load_output_language_with_conn(&ctx.conn());
+
+    let request_payload =
+        resume_request_payload(&profile_data, &job, options.as_ref(), date_format, template, output_language);
 
     // Check final resume cache
     let input_hash = compute_input_hash(&request_payload)
         .map_err(|e| format!("Failed to compute hash: {}", e))?;
 
-    if let Some(cached_entry) = ai_cache_get(&conn, "resume_generation", &input_hash, &now)
+    let cached = ai_cache_get(&ctx.conn(), "resume_generation", &input_hash, &now);
+    if let Some(cached_entry) = cached
         .map_err(|e| format!("Cache lookup error: {}", e))? {
         let resume: GeneratedResume = serde_json::from_value(cached_entry.response_payload)
             .map_err(|e| format!("Failed to deserialize cached response: {}", e))?;
         
         let content = render_resume_to_text(&resume);
+        let mut warnings = keyword_stuffing_warnings(&resume);
+        warnings.extend(language_mismatch_warning(job_description, output_language));
 
         return Ok(ResumeGenerationResult {
             resume,
             content,
+            warnings,
         });
     }
 
@@ -2868,35 +5091,47 @@ pub async fn generate_resume_for_job(
     // NEW PIPELINE: Small, focused AI calls + code-based preprocessing
     // ============================================================================
 
+    // Warn (without blocking) when the generation inputs are large enough to be costly
+    let mut warnings = Vec::new();
+    let prompt_estimate_text = request_payload.to_string();
+    if let Some(warning) = crate::ai::token_budget::prompt_token_warning(
+        &prompt_estimate_text,
+        crate::ai::token_budget::load_prompt_token_warn_threshold_with_conn(&ctx.conn()),
+    ) {
+        warnings.push(warning);
+    }
+
     // Step 1: Summarize job description (small AI call, cached)
-    let jd_summary = summarize_job_description(job_description, parsed_job.as_ref()).await?;
+    let jd_summary = summarize_job_description(ctx, job_description, parsed_job.as_ref()).await?;
 
     // Step 2: Preprocess and select relevant roles/bullets (code-based, no AI)
-    let top_roles = select_top_roles(&profile_data.experience, &jd_summary, 3);
+    let top_roles = select_top_roles(&profile_data.experience, &jd_summary, RESUME_TOP_ROLES);
     
     // Step 3: Select top bullets for each role and rewrite them (small AI calls per role)
     let mut experience_sections = Vec::new();
     for mapped_role in &top_roles {
         // Select top bullets for this role
-        let selected_bullets = select_top_bullets_for_role(&mapped_role.experience, &jd_summary, 5);
+        let selected_bullets = select_top_bullets_for_role(&mapped_role.experience, &jd_summary, template.bullets_per_role());
         
         // Rewrite bullets (small AI call per role)
         let rewritten_bullets = rewrite_bullets_for_role(
+            ctx,
             &mapped_role.experience.title,
             &mapped_role.experience.company,
             &selected_bullets,
             &jd_summary,
+            output_language,
         ).await?;
         
         // Build subheading with dates and location
         let mut subheading = String::new();
         if let Some(start) = &mapped_role.experience.start_date {
-            subheading.push_str(&crate::commands::format_date(start));
+            subheading.push_str(&date_format.format(start));
         }
         if mapped_role.experience.is_current {
             subheading.push_str(" – Present");
         } else if let Some(end) = &mapped_role.experience.end_date {
-            subheading.push_str(&format!(" – {}", crate::commands::format_date(end)));
+            subheading.push_str(&format!(" – {}", date_format.format(end)));
         }
         if let Some(loc) = &mapped_role.experience.location {
             subheading.push_str(&format!(" | {}", loc));
@@ -2915,12 +5150,12 @@ pub async fn generate_resume_for_job(
     }
 
     // Step 4: Generate professional summary (optional small AI call, cached)
-    let summary = generate_professional_summary(&profile_data, &jd_summary).await?;
+    let summary = generate_professional_summary(ctx, &profile_data, &jd_summary, output_language).await?;
 
     // Step 5: Select top skills (code-based, no AI)
     let top_skills = select_top_skills(&profile_data.skills, &jd_summary, 10);
 
-    // Step 6: Assemble final resume in code (no AI)
+    // Step 6: Assemble final resume in code (no AI); the template picks and orders sections at the end
     let mut sections = Vec::new();
     
     // Experience section
@@ -2962,7 +5197,17 @@ pub async fn generate_resume_for_job(
             items: edu_items,
         });
     }
+
+    // Certifications and portfolio (raw from user, no AI), only when asked for
+    if GenerationOptions::includes_certifications(options.as_ref()) {
+        sections.extend(certifications_section(&profile_data.certifications, date_format));
+    }
+    if GenerationOptions::includes_portfolio(options.as_ref()) {
+        sections.extend(portfolio_section(&profile_data.portfolio));
+    }
     
+    let sections = template.arrange(sections);
+
     // Build headline
     let headline = if let Some(profile) = &profile_data.profile {
         if let Some(title) = &profile.current_role_title {
@@ -2973,10 +5218,21 @@ pub async fn generate_resume_for_job(
     } else {
         "Professional Resume".to_string()
     };
-    
-    let resume = GeneratedResume {
+    
+    let include_contact_header = options
+        .as_ref()
+        .and_then(|opt| opt.include_contact_header)
+        .unwrap_or(false);
+    let contact = if include_contact_header {
+        profile_data.profile.as_ref().map(ResumeContact::from_profile)
+    } else {
+        None
+    };
+
+    let mut resume = GeneratedResume {
         summary: Some(summary),
         headline: Some(headline),
+        contact,
         sections,
         highlights: vec![
             format!("Tailored for {} role", jd_summary.role_title.as_deref().unwrap_or("this position")),
@@ -2984,17 +5240,19 @@ pub async fn generate_resume_for_job(
         ],
     };
 
+    // Guard against fabricated competencies: only skills from the profile may appear
+    warnings.extend(remove_unlisted_skills(&mut resume, &profile_data.skills));
+    warnings.extend(keyword_stuffing_warnings(&resume));
+    warnings.extend(language_mismatch_warning(job_description, output_language));
+
     // Store in cache
     let response_payload = serde_json::to_value(&resume)
         .map_err(|e| format!("Failed to serialize resume: {}", e))?;
     
-    let model_name = crate::ai::settings::load_ai_settings()
-        .ok()
-        .and_then(|s| s.model_name)
-        .unwrap_or_else(|| "unknown-model".to_string());
+    let model_name = ctx.model_for("resume_generation");
 
     ai_cache_put(
-        &conn,
+        &ctx.conn(),
         "resume_generation",
         &input_hash,
         &model_name,
@@ -3011,31 +5269,159 @@ pub async fn generate_resume_for_job(
     Ok(ResumeGenerationResult {
         resume,
         content,
+        warnings,
     })
 }
 
-#[tauri::command]
-pub async fn generate_cover_letter_for_job(
-    job_id: i64,
-    _application_id: Option<i64>,
-    options: Option<GenerationOptions>,
-) -> Result<LetterGenerationResult, String> {
-    use crate::ai_cache::{ai_cache_get, ai_cache_put, compute_input_hash, CACHE_TTL_COVER_LETTER_DAYS};
-    
-    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
-    let now = Utc::now().to_rfc3339();
+/// Certifications as "Name – Issuer" with the issue date underneath; `None` when there are none
+pub fn certifications_section(certifications: &[Certification], date_format: ResumeDateFormat) -> Option<ResumeSection> {
+    if certifications.is_empty() {
+        return None;
+    }
+    let items = certifications
+        .iter()
+        .map(|cert| {
+            let mut heading = cert.name.clone();
+            if let Some(issuer) = cert.issuing_organization.as_deref().filter(|s| !s.trim().is_empty()) {
+                heading.push_str(&format!(" – {}", issuer));
+            }
+            ResumeSectionItem {
+                heading,
+                subheading: cert
+                    .issue_date
+                    .as_deref()
+                    .filter(|s| !s.trim().is_empty())
+                    .map(|date| date_format.format(date)),
+                bullets: Vec::new(),
+            }
+        })
+        .collect();
+    Some(ResumeSection {
+        title: "Certifications".to_string(),
+        items,
+    })
+}
 
-    // Load user profile
-    let profile_data = get_user_profile_data().await?;
-    if profile_data.profile.is_none() {
-        return Err("User profile not found. Please set up your profile first.".to_string());
+/// Portfolio items as "Title – Role" with the URL underneath; `None` when there are none
+pub fn portfolio_section(portfolio: &[PortfolioItem]) -> Option<ResumeSection> {
+    if portfolio.is_empty() {
+        return None;
+    }
+    let items = portfolio
+        .iter()
+        .map(|item| {
+            let mut heading = item.title.clone();
+            if let Some(role) = item.role.as_deref().filter(|s| !s.trim().is_empty()) {
+                heading.push_str(&format!(" – {}", role));
+            }
+            ResumeSectionItem {
+                heading,
+                subheading: item.url.clone().filter(|s| !s.trim().is_empty()),
+                bullets: Vec::new(),
+            }
+        })
+        .collect();
+    Some(ResumeSection {
+        title: "Portfolio".to_string(),
+        items,
+    })
+}
+
+/// Drop any skill in the resume's Skills section that isn't in the user's profile
+/// (case-insensitive). Returns a warning for each skill removed.
+pub fn remove_unlisted_skills(resume: &mut GeneratedResume, profile_skills: &[Skill]) -> Vec<String> {
+    let known: std::collections::HashSet<String> = profile_skills
+        .iter()
+        .map(|s| s.name.trim().to_lowercase())
+        .collect();
+    let mut warnings = Vec::new();
+
+    for section in resume.sections.iter_mut().filter(|s| s.title == "Skills") {
+        for item in section.items.iter_mut() {
+            for bullet in item.bullets.iter_mut() {
+                let kept: Vec<&str> = bullet
+                    .split(',')
+                    .map(|s| s.trim())
+                    .filter(|s| !s.is_empty())
+                    .filter(|skill| {
+                        let listed = known.contains(&skill.to_lowercase());
+                        if !listed {
+                            warnings.push(format!("Removed skill not found in profile: {}", skill));
+                        }
+                        listed
+                    })
+                    .collect();
+                *bullet = kept.join(", ");
+            }
+            item.bullets.retain(|b| !b.is_empty());
+        }
+        section.items.retain(|item| !item.bullets.is_empty());
     }
+    resume.sections.retain(|s| s.title != "Skills" || !s.items.is_empty());
 
-    // Load job
-    let job = get_job_detail(job_id).await?;
+    if !warnings.is_empty() {
+        log::warn!("Resume skills validation: {}", warnings.join("; "));
+    }
 
-    // Build canonical request payload
-    let request_payload = serde_json::json!({
+    warnings
+}
+
+/// Warnings for buzzwords repeated too often across the resume's bullets
+fn keyword_stuffing_warnings(resume: &GeneratedResume) -> Vec<String> {
+    let warnings: Vec<String> = crate::resume_checks::detect_keyword_stuffing(resume)
+        .iter()
+        .map(|w| w.message())
+        .collect();
+
+    if !warnings.is_empty() {
+        log::warn!("Resume keyword density check: {}", warnings.join("; "));
+    }
+
+    warnings
+}
+
+/// Canonical request payload for the final resume cache.
+///
+/// The effective date format and output language are included so switching either
+/// doesn't return a resume rendered with the old one. Certifications and portfolio
+/// are only part of the key when their sections are included.
+pub fn resume_request_payload(
+    profile_data: &UserProfileData,
+    job: &Job,
+    options: Option<&GenerationOptions>,
+    date_format: ResumeDateFormat,
+    template: ResumeTemplate,
+    output_language: Lang,
+) -> serde_json::Value {
+    serde_json::json!({
+        "userProfile": profile_data.profile,
+        "experience": profile_data.experience,
+        "skills": profile_data.skills,
+        "education": profile_data.education,
+        "certifications": GenerationOptions::includes_certifications(options).then_some(&profile_data.certifications),
+        "portfolio": GenerationOptions::includes_portfolio(options).then_some(&profile_data.portfolio),
+        "job": {
+            "title": job.title,
+            "company": job.company,
+            "rawDescription": job.raw_description,
+        },
+        "options": options,
+        "dateFormat": date_format,
+        "template": template.as_str(),
+        "outputLanguage": output_language.code(),
+    })
+}
+
+/// Canonical request payload for the cover letter cache. The options are part of it,
+/// so each variant of `generate_cover_letter_variants` is cached on its own, and so
+/// is the output language.
+pub fn cover_letter_request_payload(
+    profile_data: &UserProfileData,
+    job: &Job,
+    options: Option<&GenerationOptions>,
+    output_language: Lang,
+) -> serde_json::Value {
+    serde_json::json!({
         "userProfile": profile_data.profile,
         "experience": profile_data.experience,
         "skills": profile_data.skills,
@@ -3045,29 +5431,136 @@ pub async fn generate_cover_letter_for_job(
             "rawDescription": job.raw_description,
             "parsedJson": job.parsed_json
         },
-        "options": options
-    });
+        "options": options,
+        "outputLanguage": output_language.code(),
+    })
+}
+
+#[tauri::command]
+pub async fn generate_cover_letter_for_job(
+    job_id: i64,
+    _application_id: Option<i64>,
+    options: Option<GenerationOptions>,
+) -> Result<LetterGenerationResult, UserFacingError> {
+    let ctx = GenerationContext::app()?;
+    generate_cover_letter_with_context(&ctx, job_id, options).await
+}
+
+/// Generate (or load from cache) a cover letter for a job using `ctx`'s database and providers
+pub async fn generate_cover_letter_with_context(
+    ctx: &GenerationContext,
+    job_id: i64,
+    options: Option<GenerationOptions>,
+) -> Result<LetterGenerationResult, UserFacingError> {
+    // Load user profile
+    let profile_data = load_user_profile_data(&ctx.conn())?;
+    if profile_data.profile.is_none() {
+        return Err(UserFacingError::from_message("User profile not found. Please set up your profile first."));
+    }
+
+    // Load job
+    let job = load_job(&ctx.conn(), job_id)?;
+
+    cover_letter_for_options(ctx, &profile_data, &job, options).await
+}
+
+/// Most variants generated by one `generate_cover_letter_variants` call
+const MAX_COVER_LETTER_VARIANTS: usize = 4;
+
+/// One entry of `generate_cover_letter_variants`: the options it was generated with
+/// and either the letter or why it failed
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CoverLetterVariant {
+    pub options: GenerationOptions,
+    pub result: Option<LetterGenerationResult>,
+    pub error: Option<UserFacingError>,
+}
+
+/// Generate a cover letter for each set of options concurrently, e.g. one formal and
+/// one enthusiastic. Each variant is cached by its own options, and a failed variant
+/// is reported in its entry without affecting the others.
+#[tauri::command]
+pub async fn generate_cover_letter_variants(
+    job_id: i64,
+    variants: Vec<GenerationOptions>,
+) -> Result<Vec<CoverLetterVariant>, UserFacingError> {
+    if variants.is_empty() {
+        return Err(UserFacingError::from_message("Choose at least one variant to generate."));
+    }
+    if variants.len() > MAX_COVER_LETTER_VARIANTS {
+        return Err(UserFacingError::from_message(&format!(
+            "At most {} cover letter variants can be generated at once.",
+            MAX_COVER_LETTER_VARIANTS
+        )));
+    }
+
+    let ctx = GenerationContext::app()?;
+    let profile_data = load_user_profile_data(&ctx.conn())?;
+    if profile_data.profile.is_none() {
+        return Err(UserFacingError::from_message("User profile not found. Please set up your profile first."));
+    }
+    let job = load_job(&ctx.conn(), job_id)?;
+
+    // Provider calls go through the shared request limiter, so this never exceeds
+    // the configured concurrency
+    let results = futures::future::join_all(
+        variants
+            .iter()
+            .map(|options| cover_letter_for_options(&ctx, &profile_data, &job, Some(options.clone()))),
+    )
+    .await;
+
+    Ok(variants
+        .into_iter()
+        .zip(results)
+        .map(|(options, result)| match result {
+            Ok(result) => CoverLetterVariant { options, result: Some(result), error: None },
+            Err(error) => {
+                log::warn!("[generate_cover_letter_variants] Variant failed: {}", error.message);
+                CoverLetterVariant { options, result: None, error: Some(error) }
+            }
+        })
+        .collect())
+}
+
+/// Generate (or load from cache) one cover letter for `job` with `options`
+async fn cover_letter_for_options(
+    ctx: &GenerationContext,
+    profile_data: &UserProfileData,
+    job: &Job,
+    options: Option<GenerationOptions>,
+) -> Result<LetterGenerationResult, UserFacingError> {
+    use crate::ai_cache::{ai_cache_get, ai_cache_put, compute_input_hash, CACHE_TTL_COVER_LETTER_DAYS};
+
+    let now = Utc::now().to_rfc3339();
+
+    let output_language = crate::language::load_output_language_with_conn(&ctx.conn());
+    let request_payload = cover_letter_request_payload(profile_data, job, options.as_ref(), output_language);
 
     // Check cache
     let input_hash = compute_input_hash(&request_payload)
         .map_err(|e| format!("Failed to compute hash: {}", e))?;
 
-    if let Some(cached_entry) = ai_cache_get(&conn, "cover_letter_generation", &input_hash, &now)
+    let cached = ai_cache_get(&ctx.conn(), "cover_letter_generation", &input_hash, &now);
+    if let Some(cached_entry) = cached
         .map_err(|e| format!("Cache lookup error: {}", e))? {
         let letter: GeneratedLetter = serde_json::from_value(cached_entry.response_payload)
             .map_err(|e| format!("Failed to deserialize cached response: {}", e))?;
         
         let content = render_letter_to_text(&letter);
+        let warnings = language_mismatch_warning(job.raw_description.as_deref().unwrap_or(""), output_language)
+            .into_iter()
+            .collect();
 
         return Ok(LetterGenerationResult {
             letter,
             content,
+            warnings,
         });
     }
 
     // Cache miss - generate letter using AI provider
-    let provider = ResolvedProvider::resolve()
-        .map_err(|e| format!("Failed to resolve provider: {}", e))?;
+    let provider = ctx.provider_for("cover_letter_generation")?;
     
     // Build profile data JSON for AI provider
     let profile_json = serde_json::json!({
@@ -3096,12 +5589,24 @@ pub async fn generate_cover_letter_for_job(
         job_description,
         company_name: job.company.clone(),
         options: letter_options,
+        output_language: Some(output_language),
     };
     
-    let cover_letter = provider.as_provider()
+    // Warn (without blocking) when the prompt is large enough to be costly
+    let mut warnings = Vec::new();
+    let prompt_estimate_text = format!("{}\n{}", letter_input.profile_data, letter_input.job_description);
+    if let Some(warning) = crate::ai::token_budget::prompt_token_warning(
+        &prompt_estimate_text,
+        crate::ai::token_budget::load_prompt_token_warn_threshold_with_conn(&ctx.conn()),
+    ) {
+        warnings.push(warning);
+    }
+    warnings.extend(language_mismatch_warning(&letter_input.job_description, output_language));
+    
+    let cover_letter = provider
         .generate_cover_letter(letter_input)
         .await
-        .map_err(|e| format!("AI generation failed: {}", e))?;
+        .map_err(|e| to_user_facing_error(&e))?;
 
     // Convert CoverLetter to GeneratedLetter (they have the same structure)
     let letter = GeneratedLetter {
@@ -3117,13 +5622,10 @@ pub async fn generate_cover_letter_for_job(
         .map_err(|e| format!("Failed to serialize letter: {}", e))?;
     
     // Get model name from settings for cache
-    let model_name = crate::ai::settings::load_ai_settings()
-        .ok()
-        .and_then(|s| s.model_name)
-        .unwrap_or_else(|| "unknown-model".to_string());
+    let model_name = ctx.model_for("cover_letter_generation");
 
     ai_cache_put(
-        &conn,
+        &ctx.conn(),
         "cover_letter_generation",
         &input_hash,
         &model_name,
@@ -3140,6 +5642,216 @@ pub async fn generate_cover_letter_for_job(
     Ok(LetterGenerationResult {
         letter,
         content,
+        warnings,
+    })
+}
+
+/// Re-target a saved cover letter at another job, keeping the writer's voice
+#[tauri::command]
+pub async fn adapt_cover_letter(
+    source_artifact_id: i64,
+    target_job_id: i64,
+) -> Result<LetterGenerationResult, UserFacingError> {
+    use crate::cover_letter_adapt::{
+        cache_letter_adaptation, prepare_letter_adaptation, request_letter_adaptation, AdaptLetterRequest,
+    };
+
+    let now = Utc::now().to_rfc3339();
+    let output_language = crate::language::load_output_language();
+    let (request, job_description) = {
+        let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+        let request = prepare_letter_adaptation(&conn, source_artifact_id, target_job_id, output_language, &now)?;
+        (request, load_job(&conn, target_job_id)?.raw_description.unwrap_or_default())
+    };
+    let warnings: Vec<String> = language_mismatch_warning(&job_description, output_language).into_iter().collect();
+
+    let letter = match request {
+        AdaptLetterRequest::Cached(letter) => letter,
+        AdaptLetterRequest::Needed { input_hash, request_payload, prompt } => {
+            let provider = ResolvedProvider::resolve_for("cover_letter_adaptation")
+                .map_err(|e| format!("Failed to resolve provider: {}", e))?;
+            let letter = request_letter_adaptation(provider.as_provider().as_ref(), &prompt).await?;
+            let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+            cache_letter_adaptation(&conn, &input_hash, &request_payload, &letter, &now)?;
+            letter
+        }
+    };
+
+    let content = render_letter_to_text(&letter);
+    Ok(LetterGenerationResult {
+        letter,
+        content,
+        warnings,
+    })
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ApplicationDocuments {
+    pub resume: Option<ResumeGenerationResult>,
+    pub cover_letter: Option<LetterGenerationResult>,
+    pub warnings: Vec<String>,
+}
+
+/// Regenerate the resume and cover letter for a job in one call, sharing options
+#[tauri::command]
+pub async fn generate_application_documents(
+    job_id: i64,
+    _application_id: Option<i64>,
+    options: Option<GenerationOptions>,
+) -> Result<ApplicationDocuments, String> {
+    let ctx = GenerationContext::app()?;
+    generate_application_documents_with_context(&ctx, job_id, options).await
+}
+
+pub async fn generate_application_documents_with_context(
+    ctx: &GenerationContext,
+    job_id: i64,
+    options: Option<GenerationOptions>,
+) -> Result<ApplicationDocuments, String> {
+    // Both generators check their own caches, so unchanged inputs are cheap to re-run
+    let resume = generate_resume_with_context(ctx, job_id, options.clone())
+        .await
+        .map_err(|e| e.to_string());
+    let cover_letter = generate_cover_letter_with_context(ctx, job_id, options)
+        .await
+        .map_err(|e| e.to_string());
+
+    combine_application_documents(resume, cover_letter)
+}
+
+/// Queue a resume or cover letter generation to retry later, e.g. while offline
+#[tauri::command]
+pub async fn queue_generation(
+    kind: String,
+    job_id: i64,
+    application_id: Option<i64>,
+    options: Option<GenerationOptions>,
+) -> Result<i64, String> {
+    let options_json = options
+        .map(|opt| serde_json::to_string(&opt))
+        .transpose()
+        .map_err(|e| format!("Failed to serialize options: {}", e))?;
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    crate::generation_queue::enqueue_generation(&conn, &kind, job_id, application_id, options_json.as_deref(), Utc::now())
+        .map_err(|e| e.to_string_for_tauri())
+}
+
+/// Queued, failed and dead generations with their attempt counts and last errors
+#[tauri::command]
+pub async fn get_pending_generations() -> Result<Vec<crate::generation_queue::PendingGeneration>, String> {
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    crate::generation_queue::get_pending_generations(&conn).map_err(|e| e.to_string_for_tauri())
+}
+
+/// Retry one queued generation now, even if it is dead or still backing off
+#[tauri::command]
+pub async fn retry_pending_generation(id: i64) -> Result<crate::generation_queue::PendingGeneration, String> {
+    let item = {
+        let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+        crate::generation_queue::reset_generation_for_retry(&conn, id, Utc::now())
+            .map_err(|e| e.to_string_for_tauri())?
+    };
+    run_pending_generation(&item).await
+}
+
+/// Run every queued generation whose backoff has elapsed; dead items are skipped
+#[tauri::command]
+pub async fn process_pending_generations() -> Result<Vec<crate::generation_queue::PendingGeneration>, String> {
+    let due = {
+        let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+        crate::generation_queue::get_due_generations(&conn, Utc::now()).map_err(|e| e.to_string_for_tauri())?
+    };
+
+    let mut processed = Vec::with_capacity(due.len());
+    for item in &due {
+        processed.push(run_pending_generation(item).await?);
+    }
+    Ok(processed)
+}
+
+#[tauri::command]
+pub async fn get_generation_max_attempts() -> Result<u32, String> {
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    crate::generation_queue::load_generation_max_attempts(&conn).map_err(|e| e.to_string_for_tauri())
+}
+
+#[tauri::command]
+pub async fn save_generation_max_attempts(max_attempts: u32) -> Result<(), String> {
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    crate::generation_queue::save_generation_max_attempts(&conn, max_attempts).map_err(|e| e.to_string_for_tauri())
+}
+
+/// Run a queued generation and record the outcome. Results land in the AI cache, so the
+/// next regular generation for the job returns them instantly.
+async fn run_pending_generation(
+    item: &crate::generation_queue::PendingGeneration,
+) -> Result<crate::generation_queue::PendingGeneration, String> {
+    use crate::generation_queue::{load_generation_max_attempts, record_generation_failure, record_generation_success};
+
+    let outcome = match item
+        .options_json
+        .as_deref()
+        .map(serde_json::from_str::<GenerationOptions>)
+        .transpose()
+    {
+        Ok(options) => match item.kind.as_str() {
+            "resume" => generate_resume_for_job(item.job_id, item.application_id, options)
+                .await
+                .map(|_| ())
+                .map_err(|e| e.to_string()),
+            "cover_letter" => generate_cover_letter_for_job(item.job_id, item.application_id, options)
+                .await
+                .map(|_| ())
+                .map_err(|e| e.to_string()),
+            other => Err(format!("Unknown generation kind '{}'", other)),
+        },
+        Err(e) => Err(format!("Invalid queued options: {}", e)),
+    };
+
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    let now = Utc::now();
+    let updated = match outcome {
+        Ok(()) => record_generation_success(&conn, item.id, now),
+        Err(e) => {
+            let max_attempts = load_generation_max_attempts(&conn).map_err(|e| e.to_string_for_tauri())?;
+            record_generation_failure(&conn, item.id, &e, max_attempts, now)
+        }
+    };
+    updated.map_err(|e| e.to_string_for_tauri())
+}
+
+/// Combine resume and cover letter results, keeping whichever succeeded.
+/// Only fails when neither document could be generated.
+pub fn combine_application_documents(
+    resume: Result<ResumeGenerationResult, String>,
+    cover_letter: Result<LetterGenerationResult, String>,
+) -> Result<ApplicationDocuments, String> {
+    let mut warnings = Vec::new();
+
+    let resume = match resume {
+        Ok(result) => Some(result),
+        Err(e) => {
+            warnings.push(format!("Resume generation failed: {}", e));
+            None
+        }
+    };
+
+    let cover_letter = match cover_letter {
+        Ok(result) => Some(result),
+        Err(e) => {
+            warnings.push(format!("Cover letter generation failed: {}", e));
+            None
+        }
+    };
+
+    if resume.is_none() && cover_letter.is_none() {
+        return Err(warnings.join("; "));
+    }
+
+    Ok(ApplicationDocuments {
+        resume,
+        cover_letter,
+        warnings,
     })
 }
 
@@ -3178,7 +5890,10 @@ fn create_artifact(
     )
     .map_err(|e| format!("Failed to create artifact: {}", e))?;
 
-    Ok(conn.last_insert_rowid())
+    let artifact_id = conn.last_insert_rowid();
+    record_artifact_version(conn, artifact_id, Some(content), now)?;
+
+    Ok(artifact_id)
 }
 
 // Placeholder AI generation functions
@@ -3186,12 +5901,13 @@ fn create_artifact(
 async fn generate_resume_with_ai(
     profile_data: &UserProfileData,
     _job: &Job,
-    _options: Option<&GenerationOptions>,
+    options: Option<&GenerationOptions>,
 ) -> Result<GeneratedResume, String> {
     // TODO: Replace with actual AI provider call
     // For now, create a basic resume structure from profile data
     
     let profile = profile_data.profile.as_ref().unwrap();
+    let date_format = options.and_then(|opt| opt.date_format).unwrap_or_default();
     let mut sections = Vec::new();
 
     // Experience section
@@ -3213,12 +5929,12 @@ async fn generate_resume_with_ai(
             
             let mut subheading = String::new();
             if let Some(start) = &exp.start_date {
-                subheading.push_str(&format_date(start));
+                subheading.push_str(&date_format.format(start));
             }
             if exp.is_current {
                 subheading.push_str(" – Present");
             } else if let Some(end) = &exp.end_date {
-                subheading.push_str(&format!(" – {}", format_date(end)));
+                subheading.push_str(&format!(" – {}", date_format.format(end)));
             }
             if let Some(loc) = &exp.location {
                 subheading.push_str(&format!(" | {}", loc));
@@ -3278,11 +5994,14 @@ async fn generate_resume_with_ai(
         });
     }
 
+    let include_contact_header = options.and_then(|opt| opt.include_contact_header).unwrap_or(false);
+
     Ok(GeneratedResume {
         summary: profile.summary.clone(),
         headline: profile.headline.clone(),
         sections,
         highlights: vec!["Tailored for this specific role".to_string()],
+        contact: include_contact_header.then(|| ResumeContact::from_profile(profile)),
     })
 }
 
@@ -3322,40 +6041,185 @@ async fn generate_cover_letter_with_ai(
         company
     ));
 
-    let closing = "Thank you for your time and consideration.".to_string();
-    let signature = format!("Sincerely,\n{}", name);
+    let closing = "Thank you for your time and consideration.".to_string();
+    let signature = format!("Sincerely,\n{}", name);
+
+    Ok(GeneratedLetter {
+        subject: Some(subject),
+        greeting: Some(greeting),
+        body_paragraphs: paragraphs,
+        closing: Some(closing),
+        signature: Some(signature),
+    })
+}
+
+const RESUME_DATE_FORMAT_SETTING: &str = "resume_date_format";
+
+/// Named resume layouts: which sections the assembly step includes, in what order
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ResumeTemplate {
+    /// Experience, Skills, Education, then certifications and portfolio when included
+    #[default]
+    #[serde(rename = "chronological")]
+    Chronological,
+    /// Skills ahead of Experience, for career changers
+    #[serde(rename = "skills-first")]
+    SkillsFirst,
+    /// Experience, Skills, certifications and portfolio only, with fewer bullets per role
+    #[serde(rename = "compact")]
+    Compact,
+}
+
+impl ResumeTemplate {
+    pub const ALL: [ResumeTemplate; 3] = [Self::Chronological, Self::SkillsFirst, Self::Compact];
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|template| template.as_str() == value.trim().to_lowercase())
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ResumeTemplate::Chronological => "chronological",
+            ResumeTemplate::SkillsFirst => "skills-first",
+            ResumeTemplate::Compact => "compact",
+        }
+    }
+
+    /// Section titles included, in order
+    pub fn section_order(&self) -> &'static [&'static str] {
+        match self {
+            ResumeTemplate::Chronological => &["Experience", "Skills", "Education", "Certifications", "Portfolio"],
+            ResumeTemplate::SkillsFirst => &["Skills", "Experience", "Education", "Certifications", "Portfolio"],
+            ResumeTemplate::Compact => &["Experience", "Skills", "Certifications", "Portfolio"],
+        }
+    }
+
+    pub fn bullets_per_role(&self) -> usize {
+        match self {
+            ResumeTemplate::Compact => 3,
+            _ => 5,
+        }
+    }
+
+    /// Keep the sections this template includes, in its order
+    pub fn arrange(&self, sections: Vec<ResumeSection>) -> Vec<ResumeSection> {
+        let mut sections: Vec<Option<ResumeSection>> = sections.into_iter().map(Some).collect();
+        self.section_order()
+            .iter()
+            .filter_map(|title| {
+                sections
+                    .iter_mut()
+                    .find(|section| section.as_ref().is_some_and(|s| s.title == *title))
+                    .and_then(Option::take)
+            })
+            .collect()
+    }
+}
+
+/// Template for generation options, rejecting names that aren't in `ResumeTemplate::ALL`
+pub fn resume_template_from_options(options: Option<&GenerationOptions>) -> Result<ResumeTemplate, String> {
+    match options.and_then(|opt| opt.template.as_deref()) {
+        None => Ok(ResumeTemplate::default()),
+        Some(name) => ResumeTemplate::parse(name).ok_or_else(|| {
+            let known: Vec<&str> = ResumeTemplate::ALL.iter().map(|t| t.as_str()).collect();
+            format!("Unknown resume template '{}'. Expected one of: {}", name, known.join(", "))
+        }),
+    }
+}
+
+/// Names of the available resume templates
+#[tauri::command]
+pub fn list_resume_templates() -> Vec<String> {
+    ResumeTemplate::ALL.iter().map(|t| t.as_str().to_string()).collect()
+}
+
+/// How experience dates are written in generated resumes
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ResumeDateFormat {
+    /// "Jan 2024"
+    #[default]
+    #[serde(rename = "mon_yyyy")]
+    MonthNameYear,
+    /// "01/2024"
+    #[serde(rename = "mm_yyyy")]
+    NumericMonthYear,
+    /// "2024"
+    #[serde(rename = "yyyy")]
+    YearOnly,
+}
+
+impl ResumeDateFormat {
+    pub fn parse(value: &str) -> Option<Self> {
+        serde_json::from_value(serde_json::Value::String(value.trim().to_lowercase())).ok()
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ResumeDateFormat::MonthNameYear => "mon_yyyy",
+            ResumeDateFormat::NumericMonthYear => "mm_yyyy",
+            ResumeDateFormat::YearOnly => "yyyy",
+        }
+    }
+
+    /// Format a `YYYY-MM` date; anything else is returned unchanged
+    pub fn format(&self, date_str: &str) -> String {
+        if date_str.len() >= 7 {
+            let parts: Vec<&str> = date_str.split('-').collect();
+            if parts.len() >= 2 {
+                return match self {
+                    ResumeDateFormat::MonthNameYear => {
+                        let month = match parts[1] {
+                            "01" => "Jan", "02" => "Feb", "03" => "Mar", "04" => "Apr",
+                            "05" => "May", "06" => "Jun", "07" => "Jul", "08" => "Aug",
+                            "09" => "Sep", "10" => "Oct", "11" => "Nov", "12" => "Dec",
+                            _ => parts[1],
+                        };
+                        format!("{} {}", month, parts[0])
+                    }
+                    ResumeDateFormat::NumericMonthYear => format!("{}/{}", parts[1], parts[0]),
+                    ResumeDateFormat::YearOnly => parts[0].to_string(),
+                };
+            }
+        }
+        date_str.to_string()
+    }
+}
+
+pub fn format_date(date_str: &str) -> String {
+    ResumeDateFormat::default().format(date_str)
+}
+
+/// Saved resume date format, defaulting to "Mon YYYY"
+pub fn load_resume_date_format() -> ResumeDateFormat {
+    get_connection()
+        .map(|conn| load_resume_date_format_with_conn(&conn))
+        .unwrap_or_default()
+}
 
-    Ok(GeneratedLetter {
-        subject: Some(subject),
-        greeting: Some(greeting),
-        body_paragraphs: paragraphs,
-        closing: Some(closing),
-        signature: Some(signature),
-    })
+pub fn load_resume_date_format_with_conn(conn: &rusqlite::Connection) -> ResumeDateFormat {
+    crate::app_settings::get_setting(conn, RESUME_DATE_FORMAT_SETTING)
+        .ok()
+        .flatten()
+        .and_then(|value| ResumeDateFormat::parse(&value))
+        .unwrap_or_default()
 }
 
-pub fn format_date(date_str: &str) -> String {
-    if date_str.len() >= 7 {
-        // Format: YYYY-MM
-        let parts: Vec<&str> = date_str.split('-').collect();
-        if parts.len() >= 2 {
-            let month = match parts[1] {
-                "01" => "Jan", "02" => "Feb", "03" => "Mar", "04" => "Apr",
-                "05" => "May", "06" => "Jun", "07" => "Jul", "08" => "Aug",
-                "09" => "Sep", "10" => "Oct", "11" => "Nov", "12" => "Dec",
-                _ => parts[1],
-            };
-            return format!("{} {}", month, parts[0]);
-        }
-    }
-    date_str.to_string()
+/// Headline followed by the contact line, when present; shared by the text and PDF renderers
+pub fn resume_header_lines(resume: &GeneratedResume) -> Vec<String> {
+    resume
+        .headline
+        .iter()
+        .cloned()
+        .chain(resume.contact.as_ref().and_then(ResumeContact::header_line))
+        .collect()
 }
 
 pub fn render_resume_to_text(resume: &GeneratedResume) -> String {
     let mut output = String::new();
     
-    if let Some(headline) = &resume.headline {
-        output.push_str(headline);
+    let header_lines = resume_header_lines(resume);
+    if !header_lines.is_empty() {
+        output.push_str(&header_lines.join("\n"));
         output.push_str("\n\n");
     }
     
@@ -3428,6 +6292,78 @@ mod tests {
         assert_eq!(format_date("invalid"), "invalid"); // Invalid format
     }
 
+    #[test]
+    fn test_resume_date_formats() {
+        assert_eq!(ResumeDateFormat::MonthNameYear.format("2024-03"), "Mar 2024");
+        assert_eq!(ResumeDateFormat::NumericMonthYear.format("2024-03"), "03/2024");
+        assert_eq!(ResumeDateFormat::YearOnly.format("2024-03"), "2024");
+        assert_eq!(ResumeDateFormat::YearOnly.format("2024"), "2024");
+
+        assert_eq!(ResumeDateFormat::parse("MM_YYYY"), Some(ResumeDateFormat::NumericMonthYear));
+        assert_eq!(ResumeDateFormat::parse("dd/mm"), None);
+        assert_eq!(ResumeDateFormat::default(), ResumeDateFormat::MonthNameYear);
+    }
+
+    #[test]
+    fn test_resume_templates_arrange_sections() {
+        let section = |title: &str| ResumeSection { title: title.to_string(), items: vec![] };
+        let sections = || vec![section("Experience"), section("Skills"), section("Education")];
+        let titles = |sections: Vec<ResumeSection>| sections.into_iter().map(|s| s.title).collect::<Vec<_>>();
+
+        assert_eq!(titles(ResumeTemplate::Chronological.arrange(sections())), vec!["Experience", "Skills", "Education"]);
+        assert_eq!(titles(ResumeTemplate::SkillsFirst.arrange(sections())), vec!["Skills", "Experience", "Education"]);
+        assert_eq!(titles(ResumeTemplate::Compact.arrange(sections())), vec!["Experience", "Skills"]);
+
+        // Opt-in sections survive every template
+        for template in ResumeTemplate::ALL {
+            let arranged = template.arrange(vec![section("Portfolio"), section("Certifications"), section("Experience")]);
+            assert_eq!(titles(arranged), vec!["Experience", "Certifications", "Portfolio"], "{:?}", template);
+        }
+
+        assert_eq!(ResumeTemplate::parse("Skills-First"), Some(ResumeTemplate::SkillsFirst));
+        let options: GenerationOptions = serde_json::from_value(serde_json::json!({"template": "fancy"})).unwrap();
+        assert!(resume_template_from_options(Some(&options)).unwrap_err().contains("fancy"));
+        assert_eq!(resume_template_from_options(None).unwrap(), ResumeTemplate::Chronological);
+    }
+
+    #[test]
+    fn test_certification_and_portfolio_sections() {
+        let certifications = vec![Certification {
+            id: None,
+            name: "AWS Solutions Architect".to_string(),
+            issuing_organization: Some("Amazon".to_string()),
+            issue_date: Some("2023-06-01".to_string()),
+            expiration_date: None,
+            credential_id: None,
+            credential_url: None,
+        }];
+        let portfolio = vec![PortfolioItem {
+            id: None,
+            title: "Trail Finder".to_string(),
+            url: Some("https://example.com/trails".to_string()),
+            description: None,
+            role: Some("Lead developer".to_string()),
+            tech_stack: None,
+            highlighted: true,
+        }];
+        let resume = GeneratedResume {
+            summary: None,
+            headline: None,
+            contact: None,
+            sections: vec![
+                certifications_section(&certifications, ResumeDateFormat::MonthNameYear).unwrap(),
+                portfolio_section(&portfolio).unwrap(),
+            ],
+            highlights: vec![],
+        };
+
+        let text = render_resume_to_text(&resume);
+        assert!(text.contains("## Certifications\n\n### AWS Solutions Architect – Amazon\nJun 2023\n"));
+        assert!(text.contains("## Portfolio\n\n### Trail Finder – Lead developer\nhttps://example.com/trails\n"));
+        assert!(certifications_section(&[], ResumeDateFormat::default()).is_none());
+        assert!(portfolio_section(&[]).is_none());
+    }
+
     #[test]
     fn test_render_resume_to_text() {
         let resume = GeneratedResume {
@@ -3459,6 +6395,7 @@ mod tests {
                 },
             ],
             highlights: vec![],
+            contact: None,
         };
 
         let text = render_resume_to_text(&resume);
@@ -3484,12 +6421,45 @@ mod tests {
             headline: None,
             sections: vec![],
             highlights: vec![],
+            contact: None,
         };
 
         let text = render_resume_to_text(&resume);
         assert_eq!(text, "");
     }
 
+    #[test]
+    fn test_resume_contact_header() {
+        let contact = ResumeContact {
+            location: Some("Berlin".to_string()),
+            email: Some("sam@example.com".to_string()),
+            phone: None,
+            website: Some("  ".to_string()),
+            github: Some("github.com/sam".to_string()),
+            linkedin: Some("linkedin.com/in/sam".to_string()),
+        };
+        assert_eq!(
+            contact.header_line().as_deref(),
+            Some("Berlin • sam@example.com • github.com/sam • linkedin.com/in/sam")
+        );
+        assert_eq!(ResumeContact::default().header_line(), None);
+
+        let resume = GeneratedResume {
+            summary: Some("Summary".to_string()),
+            headline: Some("Sam Lee – Engineer".to_string()),
+            sections: vec![],
+            highlights: vec![],
+            contact: Some(contact),
+        };
+        assert!(render_resume_to_text(&resume).starts_with(
+            "Sam Lee – Engineer\nBerlin • sam@example.com • github.com/sam • linkedin.com/in/sam\n\nSummary"
+        ));
+
+        // Without contact details the header is unchanged
+        let plain = GeneratedResume { contact: None, ..resume };
+        assert!(render_resume_to_text(&plain).starts_with("Sam Lee – Engineer\n\nSummary"));
+    }
+
     #[test]
     fn test_render_letter_to_text() {
         let letter = GeneratedLetter {
@@ -3597,12 +6567,13 @@ mod tests {
 // ============================================================================
 
 use crate::ai::resolver::ResolvedProvider;
+use crate::generation_context::GenerationContext;
 use crate::ai::types::*;
 use crate::ai::settings::AiSettings;
 
 #[tauri::command]
 pub async fn ai_resume_suggestions(input: ResumeInput) -> Result<ResumeSuggestions, String> {
-    let provider = ResolvedProvider::resolve()
+    let provider = ResolvedProvider::resolve_for("resume_generation")
         .map_err(|e| format!("Failed to resolve provider: {}", e))?;
     
     let result = provider.as_provider()
@@ -3615,7 +6586,7 @@ pub async fn ai_resume_suggestions(input: ResumeInput) -> Result<ResumeSuggestio
 
 #[tauri::command]
 pub async fn ai_cover_letter(input: CoverLetterInput) -> Result<CoverLetter, String> {
-    let provider = ResolvedProvider::resolve()
+    let provider = ResolvedProvider::resolve_for("cover_letter_generation")
         .map_err(|e| format!("Failed to resolve provider: {}", e))?;
     
     let result = provider.as_provider()
@@ -3628,7 +6599,7 @@ pub async fn ai_cover_letter(input: CoverLetterInput) -> Result<CoverLetter, Str
 
 #[tauri::command]
 pub async fn ai_skill_suggestions(input: SkillSuggestionsInput) -> Result<SkillSuggestions, String> {
-    let provider = ResolvedProvider::resolve()
+    let provider = ResolvedProvider::resolve_for("skill_suggestions")
         .map_err(|e| format!("Failed to resolve provider: {}", e))?;
     
     let result = provider.as_provider()
@@ -3651,6 +6622,73 @@ pub async fn save_ai_settings(settings: AiSettings) -> Result<(), String> {
         .map_err(|e| format!("Failed to save settings: {}", e))
 }
 
+/// Get the estimated prompt size (tokens) above which generation results include a cost warning
+#[tauri::command]
+pub async fn get_prompt_token_warn_threshold() -> Result<usize, String> {
+    Ok(crate::ai::token_budget::load_prompt_token_warn_threshold())
+}
+
+/// Set the prompt size warning threshold
+#[tauri::command]
+pub async fn save_prompt_token_warn_threshold(threshold: usize) -> Result<(), String> {
+    crate::ai::token_budget::save_prompt_token_warn_threshold(threshold)
+}
+
+/// Whether emails, phone numbers and addresses are redacted from cloud prompts
+#[tauri::command]
+pub async fn get_scrub_pii_before_cloud() -> Result<bool, String> {
+    Ok(crate::ai::pii_scrubber::load_scrub_pii_before_cloud())
+}
+
+/// Enable or disable PII redaction for cloud prompts
+#[tauri::command]
+pub async fn save_scrub_pii_before_cloud(enabled: bool) -> Result<(), String> {
+    crate::ai::pii_scrubber::save_scrub_pii_before_cloud(enabled)
+}
+
+/// Match score below which creating an application shows a warning (None = off)
+#[tauri::command]
+pub async fn get_low_match_warn_threshold() -> Result<Option<f64>, String> {
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    crate::job_match::load_low_match_warn_threshold(&conn).map_err(|e| e.to_string_for_tauri())
+}
+
+/// Set or clear the low match score warning threshold
+#[tauri::command]
+pub async fn save_low_match_warn_threshold(threshold: Option<f64>) -> Result<(), String> {
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    crate::job_match::save_low_match_warn_threshold(&conn, threshold).map_err(|e| e.to_string_for_tauri())
+}
+
+/// Get the language generated documents are written in
+#[tauri::command]
+pub async fn get_output_language() -> Result<crate::language::Lang, String> {
+    Ok(crate::language::load_output_language())
+}
+
+/// Set the output language (ISO code such as "de", or a name such as "German")
+#[tauri::command]
+pub async fn save_output_language(language: String) -> Result<crate::language::Lang, String> {
+    crate::language::save_output_language(&language)
+}
+
+/// Get the date format used in generated resumes
+#[tauri::command]
+pub async fn get_resume_date_format() -> Result<ResumeDateFormat, String> {
+    Ok(load_resume_date_format())
+}
+
+/// Save the resume date format ("mon_yyyy", "mm_yyyy" or "yyyy")
+#[tauri::command]
+pub async fn save_resume_date_format(format: String) -> Result<ResumeDateFormat, String> {
+    let date_format = ResumeDateFormat::parse(&format)
+        .ok_or_else(|| format!("Unsupported date format '{}'. Supported: mon_yyyy, mm_yyyy, yyyy", format))?;
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    crate::app_settings::set_setting(&conn, RESUME_DATE_FORMAT_SETTING, date_format.as_str())
+        .map_err(|e| e.to_string_for_tauri())?;
+    Ok(date_format)
+}
+
 /// Rotate the AI API key with validation
 #[tauri::command]
 pub async fn rotate_api_key(
@@ -3673,21 +6711,28 @@ pub async fn check_api_key_rotation_needed(max_age_days: Option<u32>) -> Result<
 }
 
 #[tauri::command]
-pub async fn test_ai_connection() -> Result<String, String> {
-    let provider = ResolvedProvider::resolve()
-        .map_err(|e| format!("Failed to resolve provider: {}", e))?;
-    
-    // Test with a simple skill suggestions request
-    let test_input = SkillSuggestionsInput {
-        current_skills: vec!["Rust".to_string(), "TypeScript".to_string()],
-        job_description: "Looking for a software engineer with Python and React experience.".to_string(),
-        experience: None,
+pub async fn test_ai_connection(
+    timeout_secs: Option<u64>,
+) -> Result<crate::ai::connection_test::ConnectionTestResult, String> {
+    use crate::ai::connection_test::{
+        run_connection_test, ConnectionErrorKind, ConnectionTestResult, DEFAULT_CONNECTION_TEST_TIMEOUT_SECS,
     };
-    
-    match provider.as_provider().generate_skill_suggestions(test_input).await {
-        Ok(_) => Ok("Connection successful!".to_string()),
-        Err(e) => Err(format!("Connection test failed: {}", e)),
-    }
+
+    let model = crate::ai::settings::load_ai_settings().ok().and_then(|s| s.model_name);
+    let provider = match ResolvedProvider::resolve() {
+        Ok(provider) => provider,
+        Err(e) => {
+            return Ok(ConnectionTestResult::failure(
+                ConnectionErrorKind::NotConfigured,
+                &format!("Failed to resolve provider: {}", e),
+                None,
+                model,
+            ))
+        }
+    };
+
+    let timeout = std::time::Duration::from_secs(timeout_secs.unwrap_or(DEFAULT_CONNECTION_TEST_TIMEOUT_SECS).max(1));
+    Ok(run_connection_test(provider.as_provider().as_ref(), model, timeout).await)
 }
 
 #[tauri::command]
@@ -3702,6 +6747,16 @@ pub async fn check_local_provider_availability() -> Result<bool, String> {
     if settings.mode != crate::ai::settings::AiMode::Local {
         return Ok(false);
     }
+
+    // Ollama serves its own models; check the server has the configured one
+    if settings.local_backend == crate::ai::settings::LocalBackend::Ollama {
+        let model = match settings.ollama_model.filter(|model| !model.trim().is_empty()) {
+            Some(model) => model.trim().to_string(),
+            None => return Ok(false),
+        };
+        let provider = crate::ai::ollama_provider::OllamaProvider::new(settings.ollama_base_url, model);
+        return Ok(provider.is_available().await);
+    }
     
     // Check if model path is configured
     let model_path = match settings.local_model_path {
@@ -3812,7 +6867,11 @@ pub fn get_artifacts_for_job(job_id: i64) -> Result<Vec<Artifact>, String> {
 pub fn get_artifact(id: i64) -> Result<Artifact, String> {
     let conn = get_connection()
         .map_err(|e| format!("Failed to get database connection: {}", e))?;
-    
+    load_artifact(&conn, id)
+}
+
+/// Load a single artifact by id
+pub fn load_artifact(conn: &rusqlite::Connection, id: i64) -> Result<Artifact, String> {
     let artifact = conn.query_row(
         "SELECT id, application_id, job_id, type, title, content, format, ai_payload, ai_model, source, version, created_at, updated_at
          FROM artifacts
@@ -3841,20 +6900,284 @@ pub fn get_artifact(id: i64) -> Result<Artifact, String> {
     Ok(artifact)
 }
 
+/// Most results returned by `search_artifacts`
+const ARTIFACT_SEARCH_LIMIT: usize = 50;
+/// Characters of context shown on each side of a search hit
+const ARTIFACT_SNIPPET_CONTEXT: usize = 60;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ArtifactMatch {
+    pub artifact_id: i64,
+    pub r#type: String,
+    pub title: String,
+    pub job_id: Option<i64>,
+    pub application_id: Option<i64>,
+    /// Content around the first hit (or the start of the content for title-only hits)
+    pub snippet: String,
+    /// Number of times the query occurs in the content
+    pub match_count: usize,
+    pub title_match: bool,
+    pub updated_at: String,
+    /// Title of the job the artifact was written for, when known
+    #[serde(default)]
+    pub source_job_title: Option<String>,
+    /// Skill/tag overlap with the target job (0–1), for similarity suggestions
+    #[serde(default)]
+    pub similarity: Option<f64>,
+}
+
+/// Find artifacts whose title or content contains `query` (case-insensitive)
 #[tauri::command]
-pub fn update_artifact(id: i64, content: String) -> Result<Artifact, String> {
+pub fn search_artifacts(query: String, artifact_type: Option<String>) -> Result<Vec<ArtifactMatch>, String> {
     let conn = get_connection()
         .map_err(|e| format!("Failed to get database connection: {}", e))?;
-    
+    search_artifacts_with_conn(&conn, &query, artifact_type.as_deref())
+}
+
+pub fn search_artifacts_with_conn(
+    conn: &rusqlite::Connection,
+    query: &str,
+    artifact_type: Option<&str>,
+) -> Result<Vec<ArtifactMatch>, String> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // Text is matched in Rust rather than SQL, whose lower() only folds ASCII letters
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, application_id, job_id, type, title, content, updated_at
+             FROM artifacts
+             WHERE ?1 IS NULL OR type = ?1",
+        )
+        .map_err(|e| format!("Failed to prepare query: {}", e))?;
+
+    let rows = stmt
+        .query_map(rusqlite::params![artifact_type], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, Option<i64>>(1)?,
+                row.get::<_, Option<i64>>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, String>(4)?,
+                row.get::<_, Option<String>>(5)?,
+                row.get::<_, String>(6)?,
+            ))
+        })
+        .map_err(|e| format!("Failed to search artifacts: {}", e))?;
+
+    let needle: Vec<char> = fold_case(query);
+    let mut matches = Vec::new();
+    for row in rows {
+        let (artifact_id, application_id, job_id, artifact_type, title, content, updated_at) =
+            row.map_err(|e| format!("Failed to read artifact: {}", e))?;
+        let content = content.unwrap_or_default();
+        let content_chars: Vec<char> = content.chars().collect();
+        let hits = find_case_insensitive(&content_chars, &needle);
+        let title_match = !find_case_insensitive(&title.chars().collect::<Vec<_>>(), &needle).is_empty();
+        if hits.is_empty() && !title_match {
+            continue;
+        }
+
+        matches.push(ArtifactMatch {
+            artifact_id,
+            r#type: artifact_type,
+            title_match,
+            title,
+            job_id,
+            application_id,
+            snippet: snippet_around(&content_chars, hits.first().copied(), needle.len()),
+            match_count: hits.len(),
+            updated_at,
+            source_job_title: None,
+            similarity: None,
+        });
+    }
+
+    // Title hits first, then the most occurrences, then the most recently edited
+    matches.sort_by(|a, b| {
+        b.title_match
+            .cmp(&a.title_match)
+            .then_with(|| b.match_count.cmp(&a.match_count))
+            .then_with(|| b.updated_at.cmp(&a.updated_at))
+    });
+    matches.truncate(ARTIFACT_SEARCH_LIMIT);
+
+    Ok(matches)
+}
+
+/// Lowercase char by char so positions line up with the original text
+fn fold_case(text: &str) -> Vec<char> {
+    text.chars().map(|c| c.to_lowercase().next().unwrap_or(c)).collect()
+}
+
+/// Start positions (in chars) of non-overlapping occurrences of `needle`
+fn find_case_insensitive(haystack: &[char], needle: &[char]) -> Vec<usize> {
+    let mut positions = Vec::new();
+    if needle.is_empty() || haystack.len() < needle.len() {
+        return positions;
+    }
+    let folded: Vec<char> = haystack.iter().map(|c| c.to_lowercase().next().unwrap_or(*c)).collect();
+    let mut i = 0;
+    while i + needle.len() <= folded.len() {
+        if folded[i..i + needle.len()] == *needle {
+            positions.push(i);
+            i += needle.len();
+        } else {
+            i += 1;
+        }
+    }
+    positions
+}
+
+pub(crate) fn snippet_around(content: &[char], hit: Option<usize>, hit_len: usize) -> String {
+    let (start, end) = match hit {
+        Some(pos) => (
+            pos.saturating_sub(ARTIFACT_SNIPPET_CONTEXT),
+            (pos + hit_len + ARTIFACT_SNIPPET_CONTEXT).min(content.len()),
+        ),
+        None => (0, (2 * ARTIFACT_SNIPPET_CONTEXT).min(content.len())),
+    };
+    let body: String = content[start..end].iter().collect();
+    let body = body.split_whitespace().collect::<Vec<_>>().join(" ");
+    format!(
+        "{}{}{}",
+        if start > 0 { "…" } else { "" },
+        body,
+        if end < content.len() { "…" } else { "" }
+    )
+}
+
+#[tauri::command]
+pub fn update_artifact(
+    id: i64,
+    content: String,
+    expected_updated_at: Option<String>,
+) -> Result<Artifact, String> {
+    let conn = get_connection()
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+    save_artifact_content(&conn, id, &content, expected_updated_at.as_deref())?;
+    get_artifact(id)
+}
+
+/// Save new artifact content as a user edit, recording it in the version history
+pub fn save_artifact_content(
+    conn: &rusqlite::Connection,
+    id: i64,
+    content: &str,
+    expected_updated_at: Option<&str>,
+) -> Result<(), String> {
     let now = chrono::Utc::now().to_rfc3339();
+    let tx = conn
+        .unchecked_transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    
+    // Artifacts saved before versioning have no history; keep their current content as the baseline
+    let has_history: bool = tx
+        .query_row(
+            "SELECT COUNT(*) FROM artifact_versions WHERE artifact_id = ?",
+            [id],
+            |row| Ok(row.get::<_, i64>(0)? > 0),
+        )
+        .map_err(|e| format!("Failed to check artifact history: {}", e))?;
+    if !has_history {
+        let existing: Option<String> = tx
+            .query_row("SELECT content FROM artifacts WHERE id = ?", [id], |row| row.get(0))
+            .map_err(|e| format!("Failed to get artifact: {}", e))?;
+        record_artifact_version(&tx, id, existing.as_deref(), &now)?;
+    }
     
+    let changed = tx
+        .execute(
+            "UPDATE artifacts SET content = ?, source = 'user_edited', updated_at = ? WHERE id = ? AND updated_at IS COALESCE(?, updated_at)",
+            rusqlite::params![content, now, id, expected_updated_at],
+        )
+        .map_err(|e| format!("Failed to update artifact: {}", e))?;
+    if changed == 0 {
+        // The baseline version above is rolled back with the transaction
+        return Err(modified_since_loaded_error(&tx, "artifacts", id));
+    }
+    
+    record_artifact_version(&tx, id, Some(content), &now)?;
+    
+    tx.commit().map_err(|e| format!("Failed to commit artifact: {}", e))
+}
+
+/// Store a content snapshot as the artifact's next version and return the version number
+pub fn record_artifact_version(
+    conn: &rusqlite::Connection,
+    artifact_id: i64,
+    content: Option<&str>,
+    now: &str,
+) -> Result<i64, String> {
+    let version: i64 = conn
+        .query_row(
+            "SELECT COALESCE(MAX(version), 0) + 1 FROM artifact_versions WHERE artifact_id = ?",
+            [artifact_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Failed to get next artifact version: {}", e))?;
+
     conn.execute(
-        "UPDATE artifacts SET content = ?, updated_at = ? WHERE id = ?",
-        rusqlite::params![content, now, id],
+        "INSERT INTO artifact_versions (artifact_id, version, content, created_at) VALUES (?, ?, ?, ?)",
+        rusqlite::params![artifact_id, version, content, now],
     )
-    .map_err(|e| format!("Failed to update artifact: {}", e))?;
+    .map_err(|e| format!("Failed to save artifact version: {}", e))?;
+
+    conn.execute(
+        "UPDATE artifacts SET version = ? WHERE id = ?",
+        rusqlite::params![version, artifact_id],
+    )
+    .map_err(|e| format!("Failed to update artifact version: {}", e))?;
+
+    Ok(version)
+}
+
+/// Unified diff of the content of two versions of an artifact
+#[tauri::command]
+pub fn diff_artifact_versions(artifact_id: i64, version_a: i64, version_b: i64) -> Result<String, String> {
+    let conn = get_connection()
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
     
-    get_artifact(id)
+    artifact_version_diff(&conn, artifact_id, version_a, version_b)
+}
+
+pub fn artifact_version_diff(
+    conn: &rusqlite::Connection,
+    artifact_id: i64,
+    version_a: i64,
+    version_b: i64,
+) -> Result<String, String> {
+    let load_version = |version: i64| -> Result<String, String> {
+        let result = conn.query_row(
+            "SELECT content FROM artifact_versions WHERE artifact_id = ? AND version = ?",
+            rusqlite::params![artifact_id, version],
+            |row| row.get::<_, Option<String>>(0),
+        );
+        match result {
+            Ok(content) => Ok(content.unwrap_or_default()),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Err(format!(
+                "Version {} not found for artifact {}",
+                version, artifact_id
+            )),
+            Err(e) => Err(format!("Failed to get artifact version: {}", e)),
+        }
+    };
+
+    let content_a = load_version(version_a)?;
+    let content_b = load_version(version_b)?;
+
+    if version_a == version_b {
+        return Ok(String::new());
+    }
+
+    Ok(crate::text_diff::unified_diff(
+        &content_a,
+        &content_b,
+        &format!("version {}", version_a),
+        &format!("version {}", version_b),
+    ))
 }
 
 #[tauri::command]
@@ -3902,6 +7225,78 @@ pub async fn save_resume(
     get_artifact(artifact_id)
 }
 
+/// Write a saved resume artifact to `path` as a PDF, returning the path
+#[tauri::command]
+pub async fn export_resume_pdf(artifact_id: i64, path: String) -> Result<String, String> {
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    let artifact = load_artifact(&conn, artifact_id)?;
+    let resume = crate::resume_export::resume_from_artifact(&artifact)?;
+    std::fs::write(&path, crate::resume_export::render_resume_to_pdf(&resume))
+        .map_err(|e| format!("Failed to write PDF: {}", e))?;
+    Ok(path)
+}
+
+/// Write a saved resume artifact to `path` as a Word document, returning the path
+#[tauri::command]
+pub async fn export_resume_docx(artifact_id: i64, path: String) -> Result<String, String> {
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    let artifact = load_artifact(&conn, artifact_id)?;
+    let resume = crate::resume_export::resume_from_artifact(&artifact)?;
+    let profile = load_user_profile_data(&conn)?.profile;
+    let bytes = crate::resume_export::render_resume_to_docx(&resume, profile.as_ref())?;
+    std::fs::write(&path, bytes).map_err(|e| format!("Failed to write DOCX: {}", e))?;
+    Ok(path)
+}
+
+/// Write a saved cover letter artifact to `path` as a Word document, returning the path
+#[tauri::command]
+pub async fn export_cover_letter_docx(artifact_id: i64, path: String) -> Result<String, String> {
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    let artifact = load_artifact(&conn, artifact_id)?;
+    let letter = crate::resume_export::letter_from_artifact(&artifact)?;
+    let profile = load_user_profile_data(&conn)?.profile;
+    let bytes = crate::resume_export::render_letter_to_docx(&letter, profile.as_ref())?;
+    std::fs::write(&path, bytes).map_err(|e| format!("Failed to write DOCX: {}", e))?;
+    Ok(path)
+}
+
+/// Which of a job's required and nice-to-have skills appear in a saved resume
+#[tauri::command]
+pub async fn analyze_resume_keyword_coverage(
+    artifact_id: i64,
+    job_id: i64,
+) -> Result<crate::resume_checks::KeywordCoverage, String> {
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    resume_keyword_coverage(&conn, artifact_id, job_id)
+}
+
+/// Coverage of the job's parsed skills in the artifact's text. Edited content is
+/// what gets checked; the stored payload is only rendered when there is no content.
+pub fn resume_keyword_coverage(
+    conn: &rusqlite::Connection,
+    artifact_id: i64,
+    job_id: i64,
+) -> Result<crate::resume_checks::KeywordCoverage, String> {
+    let artifact = load_artifact(conn, artifact_id)?;
+    let resume_text = match artifact.content.clone().filter(|c| !c.trim().is_empty()) {
+        Some(content) => content,
+        None => render_resume_to_text(&crate::resume_export::resume_from_artifact(&artifact)?),
+    };
+
+    let job = load_job(conn, job_id)?;
+    let parsed: ParsedJob = job
+        .parsed_json
+        .as_deref()
+        .and_then(|json| serde_json::from_str(json).ok())
+        .ok_or_else(|| "This job hasn't been parsed yet. Parse it first to check keyword coverage.".to_string())?;
+
+    Ok(crate::resume_checks::keyword_coverage(
+        &resume_text,
+        &parsed.required_skills,
+        &parsed.nice_to_have_skills,
+    ))
+}
+
 #[tauri::command]
 pub async fn save_cover_letter(
     job_id: i64,
@@ -3969,7 +7364,7 @@ pub async fn generate_profile_summary() -> Result<String, String> {
     }
     
     // Cache miss - call AI provider
-    let provider = ResolvedProvider::resolve()
+    let provider = ResolvedProvider::resolve_for("profile_summary")
         .map_err(|e| format!("Failed to resolve provider: {}", e))?;
     
     // Build prompt for summary generation
@@ -4065,7 +7460,7 @@ Return only the summary text, no markdown formatting or additional commentary."#
     // Get model name from settings for cache
     let model_name = crate::ai::settings::load_ai_settings()
         .ok()
-        .and_then(|s| s.model_name)
+        .and_then(|s| s.model_for("profile_summary"))
         .unwrap_or_else(|| "unknown-model".to_string());
     
     ai_cache_put(
@@ -4105,6 +7500,12 @@ pub async fn export_all_data() -> Result<String, String> {
     crate::data_export::export_to_json()
 }
 
+/// Validate an export JSON before importing (schema version, counts, id collisions)
+#[tauri::command]
+pub async fn validate_import_json(json: String) -> Result<crate::data_import::ImportValidation, String> {
+    crate::data_import::validate_import_json(&json)
+}
+
 /// Delete a job and all related data
 #[tauri::command]
 pub async fn delete_job(job_id: i64) -> Result<(), String> {
@@ -4123,6 +7524,12 @@ pub async fn delete_artifact(artifact_id: i64) -> Result<(), String> {
     crate::data_deletion::delete_artifact(artifact_id)
 }
 
+/// Delete empty artifacts left behind by aborted generations
+#[tauri::command]
+pub async fn prune_empty_artifacts() -> Result<u64, String> {
+    crate::data_deletion::prune_empty_artifacts()
+}
+
 /// Delete a profile section or specific item
 #[tauri::command]
 pub async fn delete_profile_section(section: String, item_id: Option<i64>) -> Result<(), String> {
@@ -4212,7 +7619,7 @@ pub async fn extract_profile_from_resume(resume_text: String) -> Result<crate::p
     }
     
     // Cache miss - call AI provider
-    let provider = ResolvedProvider::resolve()
+    let provider = ResolvedProvider::resolve_for("profile_extract")
         .map_err(|e| format!("Failed to resolve provider: {}", e))?;
     
     // Process resume in chunks if it's too long
@@ -4304,7 +7711,7 @@ Return JSON only."#,
     // Get model name from settings for cache
     let model_name = crate::ai::settings::load_ai_settings()
         .ok()
-        .and_then(|s| s.model_name)
+        .and_then(|s| s.model_for("profile_extract"))
         .unwrap_or_else(|| "unknown-model".to_string());
     
     ai_cache_put(
@@ -4388,6 +7795,17 @@ fn merge_extracted_profiles(results: Vec<crate::profile_import::ExtractedProfile
                 if profile.location.is_some() && merged.location.is_none() {
                     merged.location = profile.location.clone();
                 }
+                for (merged_field, field) in [
+                    (&mut merged.email, &profile.email),
+                    (&mut merged.phone, &profile.phone),
+                    (&mut merged.website, &profile.website),
+                    (&mut merged.github, &profile.github),
+                    (&mut merged.linkedin, &profile.linkedin),
+                ] {
+                    if field.is_some() && merged_field.is_none() {
+                        *merged_field = field.clone();
+                    }
+                }
                 // Merge summary if both exist (combine them)
                 if let Some(ref p_summary) = profile.summary {
                     if let Some(ref m_summary) = merged.summary {
@@ -4626,6 +8044,20 @@ pub async fn get_cache_stats() -> Result<crate::ai_cache::CacheStats, String> {
         .map_err(|e| format!("Failed to get cache stats: {}", e))
 }
 
+/// Get a per-purpose cache report (size, expired entries, age range)
+#[tauri::command]
+pub async fn get_cache_report() -> Result<crate::ai_cache::CacheReport, String> {
+    use crate::ai_cache::ai_cache_get_report;
+    use crate::db::get_connection;
+    use chrono::Utc;
+
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    let now = Utc::now().to_rfc3339();
+
+    ai_cache_get_report(&conn, &now)
+        .map_err(|e| format!("Failed to get cache report: {}", e))
+}
+
 /// Clear cache by purpose
 #[tauri::command]
 pub async fn clear_cache_by_purpose(purpose: String) -> Result<u64, String> {
@@ -4638,6 +8070,19 @@ pub async fn clear_cache_by_purpose(purpose: String) -> Result<u64, String> {
         .map_err(|e| format!("Failed to clear cache: {}", e))
 }
 
+/// Expire a purpose's cache entries within a few minutes instead of clearing them immediately
+#[tauri::command]
+pub async fn expire_cache_purpose_soon(purpose: String, within_minutes: i64) -> Result<u64, String> {
+    use crate::ai_cache::ai_cache_expire_purpose_soon;
+    use crate::db::get_connection;
+
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    let now = chrono::Utc::now().to_rfc3339();
+
+    ai_cache_expire_purpose_soon(&conn, &purpose, within_minutes, &now)
+        .map_err(|e| format!("Failed to expire cache: {}", e))
+}
+
 /// Clear all cache entries
 #[tauri::command]
 pub async fn clear_all_cache() -> Result<u64, String> {
@@ -4689,6 +8134,27 @@ pub async fn evict_cache_by_count(max_entries: u64) -> Result<u64, String> {
         .map_err(|e| format!("Failed to evict cache: {}", e))
 }
 
+/// Cache TTL in days per purpose, after applying the ones configured in AI settings
+#[tauri::command]
+pub async fn get_cache_ttls() -> Result<std::collections::HashMap<String, u32>, String> {
+    let settings = crate::ai::settings::load_ai_settings()?;
+    Ok(crate::ai_cache::effective_cache_ttls(&settings.cache_ttls))
+}
+
+/// Get the semantic (embedding similarity) cache settings
+#[tauri::command]
+pub async fn get_semantic_cache_config() -> Result<crate::ai_cache::SemanticCacheConfig, String> {
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    crate::ai_cache::load_semantic_cache_config(&conn).map_err(|e| e.to_string_for_tauri())
+}
+
+/// Save the semantic cache settings
+#[tauri::command]
+pub async fn save_semantic_cache_config(config: crate::ai_cache::SemanticCacheConfig) -> Result<(), String> {
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    crate::ai_cache::save_semantic_cache_config(&conn, &config).map_err(|e| e.to_string_for_tauri())
+}
+
 // ============================================================================
 // Model Download Commands
 // ============================================================================
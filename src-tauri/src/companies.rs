@@ -1,7 +1,9 @@
 //! Companies module for managing company information and linking jobs/applications
 
+use crate::company_taxonomy::{normalize_company_size, normalize_field, normalize_industry, CompanySize, Industry};
 use crate::db::get_connection;
 use crate::errors::CareerBenchError;
+use rusqlite::Connection;
 use serde::{Deserialize, Serialize};
 use scraper::{Html, Selector};
 
@@ -13,6 +15,12 @@ pub struct Company {
     pub website: Option<String>,
     pub industry: Option<String>,
     pub company_size: Option<String>,
+    /// Industry as originally entered, before normalization
+    #[serde(default)]
+    pub industry_raw: Option<String>,
+    /// Company size as originally entered, before normalization
+    #[serde(default)]
+    pub company_size_raw: Option<String>,
     pub location: Option<String>,
     pub description: Option<String>,
     pub mission: Option<String>,
@@ -31,6 +39,12 @@ pub struct CompanyWithStats {
     pub website: Option<String>,
     pub industry: Option<String>,
     pub company_size: Option<String>,
+    /// Industry as originally entered, before normalization
+    #[serde(default)]
+    pub industry_raw: Option<String>,
+    /// Company size as originally entered, before normalization
+    #[serde(default)]
+    pub company_size_raw: Option<String>,
     pub location: Option<String>,
     pub description: Option<String>,
     pub mission: Option<String>,
@@ -57,16 +71,21 @@ pub fn create_company(
     notes: Option<String>,
 ) -> Result<i64, CareerBenchError> {
     let conn = get_connection()?;
+    let (industry, industry_raw) = normalize_field(industry.as_deref(), normalize_industry, Industry::as_str);
+    let (company_size, company_size_raw) =
+        normalize_field(company_size.as_deref(), normalize_company_size, CompanySize::as_str);
 
     conn.execute(
         "INSERT INTO companies 
-         (name, website, industry, company_size, location, description, mission, vision, \"values\", notes, created_at, updated_at)
-         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, datetime('now'), datetime('now'))",
+         (name, website, industry, company_size, industry_raw, company_size_raw, location, description, mission, vision, \"values\", notes, created_at, updated_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, datetime('now'), datetime('now'))",
         rusqlite::params![
             name,
             website,
             industry,
             company_size,
+            industry_raw,
+            company_size_raw,
             location,
             description,
             mission,
@@ -85,7 +104,7 @@ pub fn get_companies(
 ) -> Result<Vec<Company>, CareerBenchError> {
     let conn = get_connection()?;
 
-    let mut query = "SELECT id, name, website, industry, company_size, location, description, mission, vision, \"values\", notes, created_at, updated_at
+    let mut query = "SELECT id, name, website, industry, company_size, location, description, mission, vision, \"values\", notes, created_at, updated_at, industry_raw, company_size_raw
                      FROM companies".to_string();
     let mut params = Vec::new();
 
@@ -115,6 +134,8 @@ pub fn get_companies(
             notes: row.get(10)?,
             created_at: row.get(11)?,
             updated_at: row.get(12)?,
+            industry_raw: row.get(13)?,
+            company_size_raw: row.get(14)?,
         })
     })?;
 
@@ -126,30 +147,73 @@ pub fn get_companies(
     Ok(companies)
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PaginatedCompanyList {
+    pub companies: Vec<CompanyWithStats>,
+    pub total: i64,
+    pub page: i64,
+    pub page_size: i64,
+    pub total_pages: i64,
+}
+
 /// Get companies with statistics (job count, application count)
 pub fn get_companies_with_stats(
     search_query: Option<&str>,
-) -> Result<Vec<CompanyWithStats>, CareerBenchError> {
+    page: Option<i64>,
+    page_size: Option<i64>,
+) -> Result<PaginatedCompanyList, CareerBenchError> {
     let conn = get_connection()?;
+    list_companies_with_stats(&conn, search_query, page, page_size)
+}
 
-    let mut query = "SELECT 
-        c.id, c.name, c.website, c.industry, c.company_size, c.location, c.description, c.mission, c.vision, c.\"values\", c.notes, 
-        c.created_at, c.updated_at,
-        COALESCE((SELECT COUNT(*) FROM jobs WHERE company_id = c.id), 0) as job_count,
-        COALESCE((SELECT COUNT(*) FROM applications WHERE company_id = c.id), 0) as application_count
-        FROM companies c
-    ".to_string();
+/// Paginated companies with job and application counts.
+///
+/// Counts come from per-table grouped subqueries joined once, rather than two
+/// correlated subqueries per company row.
+pub fn list_companies_with_stats(
+    conn: &Connection,
+    search_query: Option<&str>,
+    page: Option<i64>,
+    page_size: Option<i64>,
+) -> Result<PaginatedCompanyList, CareerBenchError> {
+    let page = page.unwrap_or(1).max(1);
+    let page_size = page_size.unwrap_or(50).clamp(1, 100); // Limit to 100 per page
+    let offset = (page - 1) * page_size;
+
+    let mut where_clause = String::new();
     let mut params = Vec::new();
 
     if let Some(search) = search_query {
-        query.push_str(" WHERE (c.name LIKE ? OR c.industry LIKE ? OR c.location LIKE ?)");
+        where_clause.push_str(" WHERE (c.name LIKE ? OR c.industry LIKE ? OR c.location LIKE ?)");
         let search_pattern = format!("%{}%", search);
         params.push(search_pattern.clone());
         params.push(search_pattern.clone());
         params.push(search_pattern);
     }
 
-    query.push_str(" ORDER BY c.name ASC");
+    let total: i64 = conn.query_row(
+        &format!("SELECT COUNT(*) FROM companies c{}", where_clause),
+        rusqlite::params_from_iter(params.iter()),
+        |row| row.get(0),
+    )?;
+
+    let query = format!(
+        "SELECT 
+        c.id, c.name, c.website, c.industry, c.company_size, c.location, c.description, c.mission, c.vision, c.\"values\", c.notes, 
+        c.created_at, c.updated_at, c.industry_raw, c.company_size_raw,
+        COALESCE(j.job_count, 0) as job_count,
+        COALESCE(a.application_count, 0) as application_count
+        FROM companies c
+        LEFT JOIN (SELECT company_id, COUNT(*) AS job_count FROM jobs WHERE company_id IS NOT NULL GROUP BY company_id) j
+            ON j.company_id = c.id
+        LEFT JOIN (SELECT company_id, COUNT(*) AS application_count FROM applications WHERE company_id IS NOT NULL GROUP BY company_id) a
+            ON a.company_id = c.id{}
+        ORDER BY c.name ASC
+        LIMIT ? OFFSET ?",
+        where_clause
+    );
+    params.push(page_size.to_string());
+    params.push(offset.to_string());
 
     let mut stmt = conn.prepare(&query)?;
     let rows = stmt.query_map(rusqlite::params_from_iter(params.iter()), |row| {
@@ -167,8 +231,10 @@ pub fn get_companies_with_stats(
             notes: row.get(10)?,
             created_at: row.get(11)?,
             updated_at: row.get(12)?,
-            job_count: row.get(13)?,
-            application_count: row.get(14)?,
+            industry_raw: row.get(13)?,
+            company_size_raw: row.get(14)?,
+            job_count: row.get(15)?,
+            application_count: row.get(16)?,
         })
     })?;
 
@@ -177,7 +243,19 @@ pub fn get_companies_with_stats(
         companies.push(row_result?);
     }
 
-    Ok(companies)
+    let total_pages = if total > 0 {
+        ((total as f64 / page_size as f64).ceil() as i64).max(1)
+    } else {
+        0
+    };
+
+    Ok(PaginatedCompanyList {
+        companies,
+        total,
+        page,
+        page_size,
+        total_pages,
+    })
 }
 
 /// Get a single company by ID
@@ -185,7 +263,7 @@ pub fn get_company(company_id: i64) -> Result<Company, CareerBenchError> {
     let conn = get_connection()?;
 
     let company = conn.query_row(
-        "SELECT id, name, website, industry, company_size, location, description, mission, vision, \"values\", notes, created_at, updated_at
+        "SELECT id, name, website, industry, company_size, location, description, mission, vision, \"values\", notes, created_at, updated_at, industry_raw, company_size_raw
          FROM companies WHERE id = ?",
         [company_id],
         |row| {
@@ -203,6 +281,8 @@ pub fn get_company(company_id: i64) -> Result<Company, CareerBenchError> {
                 notes: row.get(10)?,
                 created_at: row.get(11)?,
                 updated_at: row.get(12)?,
+                industry_raw: row.get(13)?,
+                company_size_raw: row.get(14)?,
             })
         },
     )?;
@@ -238,12 +318,18 @@ pub fn update_company(
         params.push(Box::new(w));
     }
     if let Some(i) = industry {
+        let (canonical, raw) = normalize_field(Some(&i), normalize_industry, Industry::as_str);
         updates.push("industry = ?");
-        params.push(Box::new(i));
+        params.push(Box::new(canonical));
+        updates.push("industry_raw = ?");
+        params.push(Box::new(raw));
     }
     if let Some(cs) = company_size {
+        let (canonical, raw) = normalize_field(Some(&cs), normalize_company_size, CompanySize::as_str);
         updates.push("company_size = ?");
-        params.push(Box::new(cs));
+        params.push(Box::new(canonical));
+        updates.push("company_size_raw = ?");
+        params.push(Box::new(raw));
     }
     if let Some(l) = location {
         updates.push("location = ?");
@@ -828,7 +914,7 @@ fn extract_description(document: &Html) -> Option<String> {
 pub async fn extract_company_info_with_ai(scraped: &ScrapedCompanyInfo) -> Result<Company, CareerBenchError> {
     use crate::ai::resolver::ResolvedProvider;
     
-    let provider = ResolvedProvider::resolve()
+    let provider = ResolvedProvider::resolve_for("company_extract")
         .map_err(|e| CareerBenchError::AiProvider(crate::ai::errors::AiProviderError::Unknown(
             format!("Failed to resolve provider: {}", e)
         )))?;
@@ -921,6 +1007,8 @@ If information is not found in the text, use empty string "". Return ONLY the JS
                 website: scraped.website.clone(),
                 industry: scraped.industry.clone(),
                 company_size: scraped.company_size.clone(),
+                industry_raw: None,
+                company_size_raw: None,
                 location: scraped.location.clone(),
                 description: scraped.description.clone(),
                 mission: None,
@@ -959,6 +1047,8 @@ If information is not found in the text, use empty string "". Return ONLY the JS
             website: scraped.website.clone(),
             industry: scraped.industry.clone(),
             company_size: scraped.company_size.clone(),
+            industry_raw: None,
+            company_size_raw: None,
             location: scraped.location.clone(),
             description: scraped.description.clone(),
             mission: None,
@@ -1046,6 +1136,8 @@ If information is not found in the text, use empty string "". Return ONLY the JS
             .or_else(|| scraped.website.clone()),
         industry: final_industry,
         company_size: final_company_size,
+        industry_raw: None,
+        company_size_raw: None,
         location: final_location,
         description: final_description,
         mission: final_mission,
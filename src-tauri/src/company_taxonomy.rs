@@ -0,0 +1,229 @@
+//! Canonical company size buckets and industries
+//!
+//! Company size and industry come from free-text input and scraping ("51-200",
+//! "50-200 employees", "Small"), so they are mapped to a fixed set of values that
+//! analytics can group by. The original text is kept alongside the canonical value.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CompanySize {
+    /// Up to 10 employees
+    Startup,
+    /// 11–200 employees
+    Small,
+    /// 201–1,000 employees
+    Medium,
+    /// 1,001–10,000 employees
+    Large,
+    /// More than 10,000 employees
+    Enterprise,
+    Other,
+}
+
+impl CompanySize {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CompanySize::Startup => "Startup",
+            CompanySize::Small => "Small",
+            CompanySize::Medium => "Medium",
+            CompanySize::Large => "Large",
+            CompanySize::Enterprise => "Enterprise",
+            CompanySize::Other => "Other",
+        }
+    }
+
+    fn from_headcount(headcount: u64) -> Self {
+        match headcount {
+            0..=10 => CompanySize::Startup,
+            11..=200 => CompanySize::Small,
+            201..=1_000 => CompanySize::Medium,
+            1_001..=10_000 => CompanySize::Large,
+            _ => CompanySize::Enterprise,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Industry {
+    Technology,
+    Finance,
+    Healthcare,
+    Education,
+    Retail,
+    Media,
+    Consulting,
+    Government,
+    Nonprofit,
+    Energy,
+    Manufacturing,
+    Transportation,
+    RealEstate,
+    Telecommunications,
+    Other,
+}
+
+impl Industry {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Industry::Technology => "Technology",
+            Industry::Finance => "Finance",
+            Industry::Healthcare => "Healthcare",
+            Industry::Education => "Education",
+            Industry::Retail => "Retail & E-commerce",
+            Industry::Media => "Media & Entertainment",
+            Industry::Consulting => "Consulting",
+            Industry::Government => "Government",
+            Industry::Nonprofit => "Nonprofit",
+            Industry::Energy => "Energy",
+            Industry::Manufacturing => "Manufacturing",
+            Industry::Transportation => "Transportation & Logistics",
+            Industry::RealEstate => "Real Estate",
+            Industry::Telecommunications => "Telecommunications",
+            Industry::Other => "Other",
+        }
+    }
+}
+
+/// Keywords per industry, matched at word starts ("financ" matches "financial").
+/// A trailing space makes a keyword match the whole word only. Checked in order, so
+/// specific industries come before the broad Technology bucket ("fintech" is Finance).
+const INDUSTRY_KEYWORDS: &[(Industry, &[&str])] = &[
+    (Industry::Finance, &["financ", "fintech", "bank", "insurance", "insurtech", "investment", "payments", "accounting", "crypto"]),
+    (Industry::Healthcare, &["health", "medical", "pharma", "biotech", "hospital", "life science", "medtech", "clinical"]),
+    (Industry::Education, &["education", "edtech", "university", "school", "e learning", "elearning", "academ"]),
+    (Industry::Retail, &["retail", "e commerce", "ecommerce", "consumer goods", "fashion", "marketplace"]),
+    (Industry::Media, &["media", "entertainment", "gaming", "games ", "publishing", "advertising", "marketing", "music", "film"]),
+    (Industry::Consulting, &["consult", "professional services", "agency", "agencies", "staffing", "legal"]),
+    (Industry::Government, &["government", "public sector", "defense", "defence", "military"]),
+    (Industry::Nonprofit, &["non profit", "nonprofit", "ngo ", "charity", "foundation"]),
+    (Industry::Energy, &["energy", "oil ", "utilities", "renewable", "climate", "solar", "cleantech"]),
+    (Industry::Manufacturing, &["manufactur", "industrial", "automotive", "aerospace", "hardware", "semiconductor"]),
+    (Industry::Transportation, &["logistics", "transport", "shipping", "mobility", "airline", "aviation", "supply chain"]),
+    (Industry::RealEstate, &["real estate", "property", "proptech", "construction"]),
+    (Industry::Telecommunications, &["telecom", "wireless", "networking"]),
+    (Industry::Technology, &["software", "tech", "saas", "internet", "information technology", "it services", "cloud", "ai ", "artificial intelligence", "machine learning", "cyber", "computer", "data ", "developer tools"]),
+];
+
+/// Map free-text company size to a bucket; text with no recognizable size becomes `Other`
+pub fn normalize_company_size(raw: &str) -> CompanySize {
+    let text = raw.trim().to_lowercase();
+    if text.is_empty() {
+        return CompanySize::Other;
+    }
+
+    // "Fortune 500" is a ranking, not a headcount
+    if text.contains("fortune") {
+        return CompanySize::Enterprise;
+    }
+
+    // Headcounts: "51-200", "1,001 to 5,000 employees", "10k+", "500"
+    let number_re = Regex::new(r"(\d[\d,.]*)\s*(k)?").unwrap();
+    let numbers: Vec<u64> = number_re
+        .captures_iter(&text)
+        .filter_map(|caps| {
+            let value: f64 = caps[1].replace(',', "").parse().ok()?;
+            let multiplier = if caps.get(2).is_some() { 1_000.0 } else { 1.0 };
+            Some((value * multiplier) as u64)
+        })
+        .collect();
+    if let Some(&last) = numbers.last() {
+        // Ranges are bucketed by their upper bound; "10,000+" means more than 10,000
+        let headcount = if numbers.len() == 1 && text.contains('+') { last + 1 } else { last };
+        return CompanySize::from_headcount(headcount);
+    }
+
+    let words: Vec<&str> = text.split(|c: char| !c.is_alphanumeric()).filter(|w| !w.is_empty()).collect();
+    let has = |candidates: &[&str]| words.iter().any(|word| candidates.contains(word));
+    if has(&["startup", "seed", "early"]) || text.contains("start-up") {
+        CompanySize::Startup
+    } else if has(&["enterprise", "multinational", "corporation", "global"]) {
+        CompanySize::Enterprise
+    } else if has(&["medium", "mid", "midsize", "midsized"]) || text.contains("mid-size") {
+        CompanySize::Medium
+    } else if has(&["small", "smb", "boutique"]) {
+        CompanySize::Small
+    } else if has(&["large", "big"]) {
+        CompanySize::Large
+    } else {
+        CompanySize::Other
+    }
+}
+
+/// Map free-text industry to the curated list; unrecognized industries become `Other`
+pub fn normalize_industry(raw: &str) -> Industry {
+    // Pad with spaces so keywords can be matched at word boundaries
+    let normalized: String = raw
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect();
+    let padded = format!(" {} ", normalized.split_whitespace().collect::<Vec<_>>().join(" "));
+
+    INDUSTRY_KEYWORDS
+        .iter()
+        .find(|(_, keywords)| keywords.iter().any(|keyword| padded.contains(&format!(" {}", keyword))))
+        .map(|(industry, _)| *industry)
+        .unwrap_or(Industry::Other)
+}
+
+/// Canonical and trimmed raw value for a free-text field; blank input clears both
+pub fn normalize_field<T>(
+    raw: Option<&str>,
+    normalize: impl Fn(&str) -> T,
+    as_str: impl Fn(&T) -> &'static str,
+) -> (Option<String>, Option<String>) {
+    match raw.map(str::trim).filter(|value| !value.is_empty()) {
+        Some(value) => (Some(as_str(&normalize(value)).to_string()), Some(value.to_string())),
+        None => (None, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_size_ranges_map_to_buckets() {
+        assert_eq!(normalize_company_size("51-200"), CompanySize::Small);
+        assert_eq!(normalize_company_size("50-200 employees"), CompanySize::Small);
+        assert_eq!(normalize_company_size("Small"), CompanySize::Small);
+        assert_eq!(normalize_company_size("1-10"), CompanySize::Startup);
+        assert_eq!(normalize_company_size("Early-stage startup"), CompanySize::Startup);
+        assert_eq!(normalize_company_size("201-500"), CompanySize::Medium);
+        assert_eq!(normalize_company_size("Mid-size"), CompanySize::Medium);
+        assert_eq!(normalize_company_size("1,001-5,000 employees"), CompanySize::Large);
+        assert_eq!(normalize_company_size("10,000+"), CompanySize::Enterprise);
+        assert_eq!(normalize_company_size("10k+"), CompanySize::Enterprise);
+        assert_eq!(normalize_company_size("Fortune 500"), CompanySize::Enterprise);
+    }
+
+    #[test]
+    fn test_unknown_size_is_other() {
+        assert_eq!(normalize_company_size("It depends"), CompanySize::Other);
+        assert_eq!(normalize_company_size("   "), CompanySize::Other);
+    }
+
+    #[test]
+    fn test_industry_mapping() {
+        assert_eq!(normalize_industry("Computer Software"), Industry::Technology);
+        assert_eq!(normalize_industry("SaaS"), Industry::Technology);
+        assert_eq!(normalize_industry("FinTech"), Industry::Finance);
+        assert_eq!(normalize_industry("Financial Services"), Industry::Finance);
+        assert_eq!(normalize_industry("Hospital & Health Care"), Industry::Healthcare);
+        assert_eq!(normalize_industry("E-commerce"), Industry::Retail);
+        assert_eq!(normalize_industry("Airlines/Aviation"), Industry::Transportation);
+        assert_eq!(normalize_industry("Basket weaving"), Industry::Other);
+    }
+
+    #[test]
+    fn test_normalize_field_keeps_raw_value() {
+        assert_eq!(
+            normalize_field(Some(" 51-200 "), normalize_company_size, CompanySize::as_str),
+            (Some("Small".to_string()), Some("51-200".to_string()))
+        );
+        assert_eq!(normalize_field(Some(""), normalize_industry, Industry::as_str), (None, None));
+        assert_eq!(normalize_field(None, normalize_industry, Industry::as_str), (None, None));
+    }
+}
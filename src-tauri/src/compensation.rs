@@ -0,0 +1,358 @@
+//! Compensation parsing and currency normalization for offer analytics
+
+use crate::app_settings::{get_json_setting, set_json_setting};
+use crate::db::get_connection;
+use crate::errors::CareerBenchError;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// Settings key for the static currency rate table
+pub const CURRENCY_RATES_SETTING: &str = "currency_rates";
+/// Settings key for the user's minimum acceptable salary
+pub const SALARY_EXPECTATION_SETTING: &str = "salary_expectation";
+
+static CURRENCY_CODE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)\b(USD|EUR|GBP|CAD|AUD|CHF|JPY|INR|SEK|NOK|DKK|NZD|SGD)\b").unwrap());
+static AMOUNT_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)(\d[\d,]*(?:\.\d+)?)\s*([km])?\b").unwrap());
+static RANGE_SEPARATOR_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)^\s*(?:[A-Z]{3})?\s*[$£€¥₹]?\s*(?:-|–|to)\s*(?:[A-Z]{3})?\s*[$£€¥₹]?\s*$").unwrap()
+});
+
+/// Static currency rate table.
+///
+/// Each rate is the value of one unit of the currency in `reference_currency`.
+/// Rates are user-maintained and not fetched live, so conversions are estimates.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CurrencyRates {
+    pub reference_currency: String,
+    pub rates: HashMap<String, f64>,
+}
+
+impl Default for CurrencyRates {
+    fn default() -> Self {
+        let rates = [
+            ("USD", 1.0),
+            ("EUR", 1.08),
+            ("GBP", 1.27),
+            ("CAD", 0.73),
+            ("AUD", 0.66),
+        ]
+        .iter()
+        .map(|(code, rate)| (code.to_string(), *rate))
+        .collect();
+
+        Self {
+            reference_currency: "USD".to_string(),
+            rates,
+        }
+    }
+}
+
+impl CurrencyRates {
+    /// Conversion factor from one currency to another, if both are known
+    pub fn factor(&self, from: &str, to: &str) -> Option<f64> {
+        if from == to {
+            return Some(1.0);
+        }
+        let from_rate = self.rates.get(from)?;
+        let to_rate = self.rates.get(to)?;
+        if *to_rate <= 0.0 {
+            return None;
+        }
+        Some(from_rate / to_rate)
+    }
+}
+
+/// A compensation string parsed and, when possible, converted to a base currency
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct NormalizedComp {
+    pub original_text: String,
+    pub original_amount: Option<f64>,
+    pub original_currency: Option<String>,
+    pub base_currency: String,
+    pub normalized_amount: Option<f64>,
+    /// True when `normalized_amount` is expressed in the base currency
+    pub converted: bool,
+    /// True when a static exchange rate was applied
+    pub is_estimate: bool,
+}
+
+/// The lowest salary the user would accept, used to judge salary fit
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SalaryExpectation {
+    pub minimum: Option<f64>,
+    /// Currency of `minimum`; the rate table's reference currency when unset
+    pub currency: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OfferComparison {
+    pub application_id: i64,
+    pub job_title: Option<String>,
+    pub company: Option<String>,
+    pub compensation: NormalizedComp,
+}
+
+/// Detect the currency of a compensation string from its symbol or ISO code
+fn detect_currency(text: &str) -> Option<String> {
+    if let Some(m) = CURRENCY_CODE_RE.find(text) {
+        return Some(m.as_str().to_uppercase());
+    }
+
+    if text.contains('£') {
+        Some("GBP".to_string())
+    } else if text.contains('€') {
+        Some("EUR".to_string())
+    } else if text.contains('¥') {
+        Some("JPY".to_string())
+    } else if text.contains('₹') {
+        Some("INR".to_string())
+    } else if text.contains('$') {
+        Some("USD".to_string())
+    } else {
+        None
+    }
+}
+
+/// Parse the amount from a compensation string.
+///
+/// Handles thousands separators and `k`/`m` suffixes. Ranges ("90k - 110k",
+/// "$90k to $110k") are reduced to their midpoint; other figures such as a
+/// "+ 10% bonus" are ignored.
+fn parse_amount(text: &str) -> Option<f64> {
+
+    // (amount, start, end) for every figure that isn't a percentage
+    let amounts: Vec<(f64, usize, usize)> = AMOUNT_RE
+        .captures_iter(text)
+        .filter_map(|caps| {
+            let whole = caps.get(0)?;
+            if text[whole.end()..].trim_start().starts_with('%') {
+                return None;
+            }
+            let number: f64 = caps.get(1)?.as_str().replace(',', "").parse().ok()?;
+            let multiplier = match caps.get(2).map(|m| m.as_str().to_lowercase()) {
+                Some(ref s) if s == "k" => 1_000.0,
+                Some(ref s) if s == "m" => 1_000_000.0,
+                _ => 1.0,
+            };
+            Some((number * multiplier, whole.start(), whole.end()))
+        })
+        .take(2)
+        .collect();
+
+    match amounts.as_slice() {
+        [] => None,
+        [(low, _, low_end), (high, high_start, _)] if RANGE_SEPARATOR_RE.is_match(&text[*low_end..*high_start]) => {
+            Some((low + high) / 2.0)
+        }
+        [(first, _, _), ..] => Some(*first),
+    }
+}
+
+/// Tidy a parsed salary range: a single figure becomes both ends, reversed ends are
+/// swapped, and non-positive amounts are dropped. The currency is upper-cased and
+/// kept only alongside an amount.
+pub fn normalize_salary_range(
+    min: Option<f64>,
+    max: Option<f64>,
+    currency: Option<&str>,
+) -> (Option<f64>, Option<f64>, Option<String>) {
+    let positive = |amount: Option<f64>| amount.filter(|a| a.is_finite() && *a > 0.0);
+    let (min, max) = match (positive(min), positive(max)) {
+        (Some(low), Some(high)) if low > high => (Some(high), Some(low)),
+        (Some(low), None) => (Some(low), Some(low)),
+        (None, Some(high)) => (Some(high), Some(high)),
+        range => range,
+    };
+    let currency = currency
+        .map(|c| c.trim().to_uppercase())
+        .filter(|c| !c.is_empty() && min.is_some());
+    (min, max, currency)
+}
+
+/// Parse a compensation string and convert it to `base_currency` using the rate table.
+///
+/// Amounts with no detectable currency are assumed to already be in the base currency.
+/// Currencies missing from the rate table are left as-is and marked unconverted.
+pub fn parse_and_normalize_comp(text: &str, base_currency: &str, rates: &CurrencyRates) -> NormalizedComp {
+    let base_currency = base_currency.to_uppercase();
+    let original_amount = parse_amount(text);
+    let original_currency = detect_currency(text);
+    let source_currency = original_currency.clone().unwrap_or_else(|| base_currency.clone());
+
+    let (normalized_amount, converted, is_estimate) = match original_amount {
+        Some(amount) => match rates.factor(&source_currency, &base_currency) {
+            Some(factor) => (Some(amount * factor), true, source_currency != base_currency),
+            None => (None, false, false),
+        },
+        None => (None, false, false),
+    };
+
+    NormalizedComp {
+        original_text: text.to_string(),
+        original_amount,
+        original_currency,
+        base_currency,
+        normalized_amount,
+        converted,
+        is_estimate,
+    }
+}
+
+/// Load the configured currency rate table
+pub fn get_currency_rates() -> Result<CurrencyRates, CareerBenchError> {
+    let conn = get_connection()?;
+    get_json_setting(&conn, CURRENCY_RATES_SETTING)
+}
+
+/// Save the currency rate table
+pub fn save_currency_rates(rates: &CurrencyRates) -> Result<(), CareerBenchError> {
+    let conn = get_connection()?;
+    set_json_setting(&conn, CURRENCY_RATES_SETTING, rates)
+}
+
+/// Load the configured salary expectation
+pub fn get_salary_expectation() -> Result<SalaryExpectation, CareerBenchError> {
+    let conn = get_connection()?;
+    get_json_setting(&conn, SALARY_EXPECTATION_SETTING)
+}
+
+/// Save the salary expectation
+pub fn save_salary_expectation(expectation: &SalaryExpectation) -> Result<(), CareerBenchError> {
+    let conn = get_connection()?;
+    set_json_setting(&conn, SALARY_EXPECTATION_SETTING, expectation)
+}
+
+/// Compare offers side by side with compensation normalized to one currency
+pub fn get_offer_comparison(base_currency: Option<&str>) -> Result<Vec<OfferComparison>, CareerBenchError> {
+    let conn = get_connection()?;
+    let rates: CurrencyRates = get_json_setting(&conn, CURRENCY_RATES_SETTING)?;
+    let base_currency = base_currency
+        .map(|c| c.to_string())
+        .unwrap_or_else(|| rates.reference_currency.clone());
+
+    let mut stmt = conn.prepare(
+        "SELECT a.id, j.title, j.company, a.offer_compensation
+         FROM applications a
+         LEFT JOIN jobs j ON a.job_id = j.id
+         WHERE a.offer_compensation IS NOT NULL AND TRIM(a.offer_compensation) != ''
+         ORDER BY a.updated_at DESC",
+    )?;
+
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, i64>(0)?,
+            row.get::<_, Option<String>>(1)?,
+            row.get::<_, Option<String>>(2)?,
+            row.get::<_, String>(3)?,
+        ))
+    })?;
+
+    let mut offers = Vec::new();
+    for row in rows {
+        let (application_id, job_title, company, comp_text) = row?;
+        offers.push(OfferComparison {
+            application_id,
+            job_title,
+            company,
+            compensation: parse_and_normalize_comp(&comp_text, &base_currency, &rates),
+        });
+    }
+
+    // Highest normalized offers first; unconverted offers go last
+    offers.sort_by(|a, b| {
+        let a_amount = a.compensation.normalized_amount.unwrap_or(f64::MIN);
+        let b_amount = b.compensation.normalized_amount.unwrap_or(f64::MIN);
+        b_amount.partial_cmp(&a_amount).unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    Ok(offers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_single_salary_figure_sets_both_ends() {
+        assert_eq!(
+            normalize_salary_range(Some(95000.0), None, Some("usd")),
+            (Some(95000.0), Some(95000.0), Some("USD".to_string()))
+        );
+        assert_eq!(
+            normalize_salary_range(None, Some(80000.0), None),
+            (Some(80000.0), Some(80000.0), None)
+        );
+    }
+
+    #[test]
+    fn test_salary_range_swaps_and_drops_empty() {
+        assert_eq!(
+            normalize_salary_range(Some(140000.0), Some(110000.0), Some("EUR")),
+            (Some(110000.0), Some(140000.0), Some("EUR".to_string()))
+        );
+        assert_eq!(normalize_salary_range(None, Some(0.0), Some("USD")), (None, None, None));
+    }
+
+    #[test]
+    fn test_gbp_converted_to_usd() {
+        let rates = CurrencyRates::default();
+        let comp = parse_and_normalize_comp("£85,000 base", "USD", &rates);
+
+        assert_eq!(comp.original_currency, Some("GBP".to_string()));
+        assert_eq!(comp.original_amount, Some(85_000.0));
+        assert!(comp.converted);
+        assert!(comp.is_estimate);
+        let amount = comp.normalized_amount.unwrap();
+        assert!((amount - 85_000.0 * 1.27).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_eur_range_converted_to_usd() {
+        let rates = CurrencyRates::default();
+        let comp = parse_and_normalize_comp("EUR 90k - 110k", "USD", &rates);
+
+        assert_eq!(comp.original_currency, Some("EUR".to_string()));
+        assert_eq!(comp.original_amount, Some(100_000.0));
+        assert!(comp.converted);
+        assert!(comp.is_estimate);
+        let amount = comp.normalized_amount.unwrap();
+        assert!((amount - 108_000.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_only_joined_figures_are_a_range() {
+        assert_eq!(parse_amount("$150k + 10% bonus"), Some(150_000.0));
+        assert_eq!(parse_amount("$90,000 to $110,000"), Some(100_000.0));
+        assert_eq!(parse_amount("90k–110k"), Some(100_000.0));
+        assert_eq!(parse_amount("$120k base, 401k match"), Some(120_000.0));
+    }
+
+    #[test]
+    fn test_base_currency_is_not_an_estimate() {
+        let rates = CurrencyRates::default();
+        let comp = parse_and_normalize_comp("$150,000", "USD", &rates);
+
+        assert_eq!(comp.normalized_amount, Some(150_000.0));
+        assert!(comp.converted);
+        assert!(!comp.is_estimate);
+    }
+
+    #[test]
+    fn test_unknown_currency_left_unconverted() {
+        let rates = CurrencyRates::default();
+        let comp = parse_and_normalize_comp("CHF 120,000", "USD", &rates);
+
+        assert_eq!(comp.original_currency, Some("CHF".to_string()));
+        assert_eq!(comp.original_amount, Some(120_000.0));
+        assert_eq!(comp.normalized_amount, None);
+        assert!(!comp.converted);
+        assert!(!comp.is_estimate);
+    }
+}
@@ -0,0 +1,184 @@
+//! Re-target an existing cover letter at a different job
+//!
+//! The source letter's voice and structure are kept; the provider swaps in the target
+//! company, role and requirements. Results are cached on the source content, the
+//! target job and the output language, so changing any of them produces a fresh
+//! adaptation.
+
+use crate::ai::provider::AiProvider;
+use crate::ai_cache::{ai_cache_get, ai_cache_put, compute_input_hash, CACHE_TTL_COVER_LETTER_DAYS};
+use crate::commands::{load_artifact, load_job, GeneratedLetter, Job, ParsedJob};
+use crate::language::{output_language_instruction, Lang};
+use rusqlite::Connection;
+use serde_json::json;
+
+const ADAPT_LETTER_PURPOSE: &str = "cover_letter_adaptation";
+
+const ADAPT_LETTER_SYSTEM_PROMPT: &str = "You are a cover letter writing assistant. \
+Always return valid JSON matching the specified schema.";
+
+/// What's needed to adapt a letter: either a cached adaptation, or the prompt to send
+#[derive(Debug)]
+pub enum AdaptLetterRequest {
+    Cached(GeneratedLetter),
+    Needed {
+        input_hash: String,
+        request_payload: serde_json::Value,
+        prompt: String,
+    },
+}
+
+fn target_job_brief(job: &Job) -> String {
+    let parsed = job
+        .parsed_json
+        .as_deref()
+        .and_then(|json| serde_json::from_str::<ParsedJob>(json).ok());
+
+    let mut brief = String::new();
+    let title = job
+        .title
+        .clone()
+        .or_else(|| parsed.as_ref().and_then(|p| p.title_suggestion.clone()));
+    let company = job
+        .company
+        .clone()
+        .or_else(|| parsed.as_ref().and_then(|p| p.company_suggestion.clone()));
+    if let Some(title) = title {
+        brief.push_str(&format!("Role: {}\n", title));
+    }
+    if let Some(company) = company {
+        brief.push_str(&format!("Company: {}\n", company));
+    }
+
+    match parsed {
+        Some(parsed) => {
+            if let Some(summary) = &parsed.summary {
+                brief.push_str(&format!("Summary: {}\n", summary));
+            }
+            if !parsed.responsibilities.is_empty() {
+                brief.push_str(&format!("Responsibilities: {}\n", parsed.responsibilities.join("; ")));
+            }
+            if !parsed.required_skills.is_empty() {
+                brief.push_str(&format!("Required skills: {}\n", parsed.required_skills.join(", ")));
+            }
+            if !parsed.nice_to_have_skills.is_empty() {
+                brief.push_str(&format!("Nice to have: {}\n", parsed.nice_to_have_skills.join(", ")));
+            }
+        }
+        None => {
+            if let Some(description) = job.raw_description.as_deref().filter(|d| !d.trim().is_empty()) {
+                brief.push_str(&format!("Job description:\n{}\n", description.trim()));
+            }
+        }
+    }
+    brief
+}
+
+fn adapt_prompt(source_letter: &str, job_brief: &str, output_language: Lang) -> String {
+    format!(
+        r#"Adapt the cover letter below to the target job. Keep the writer's voice, tone and structure and every true fact about them.
+Replace anything specific to the original company or role (names, products, requirements) with details from the target job, and point the strongest examples at what the target job asks for. Do not invent experience.{}
+
+Target job:
+{}
+Original cover letter:
+{}
+
+Return JSON only: {{"subject": "...", "greeting": "...", "bodyParagraphs": ["..."], "closing": "...", "signature": "..."}}"#,
+        output_language_instruction(Some(output_language)),
+        job_brief,
+        source_letter
+    )
+}
+
+/// Validate the source letter and target job, and return a cached adaptation or the
+/// prompt to generate one
+pub fn prepare_letter_adaptation(
+    conn: &Connection,
+    source_artifact_id: i64,
+    target_job_id: i64,
+    output_language: Lang,
+    now: &str,
+) -> Result<AdaptLetterRequest, String> {
+    let artifact = load_artifact(conn, source_artifact_id)?;
+    if artifact.r#type != "CoverLetter" {
+        return Err(format!(
+            "Artifact {} is a {}, not a cover letter",
+            source_artifact_id, artifact.r#type
+        ));
+    }
+    let source_content = artifact.content.as_deref().unwrap_or("").trim().to_string();
+    if source_content.is_empty() {
+        return Err("The source cover letter is empty".to_string());
+    }
+
+    let job = load_job(conn, target_job_id)?;
+    let job_brief = target_job_brief(&job);
+
+    let request_payload = json!({
+        "sourceContent": source_content,
+        "targetJob": {
+            "title": job.title,
+            "company": job.company,
+            "rawDescription": job.raw_description,
+            "parsedJson": job.parsed_json
+        },
+        "outputLanguage": output_language.code()
+    });
+    let input_hash = compute_input_hash(&request_payload)?;
+    if let Some(entry) = ai_cache_get(conn, ADAPT_LETTER_PURPOSE, &input_hash, now)? {
+        if let Ok(letter) = serde_json::from_value::<GeneratedLetter>(entry.response_payload) {
+            return Ok(AdaptLetterRequest::Cached(letter));
+        }
+    }
+
+    Ok(AdaptLetterRequest::Needed {
+        input_hash,
+        request_payload,
+        prompt: adapt_prompt(&source_content, &job_brief, output_language),
+    })
+}
+
+/// Ask the provider for the adapted letter
+pub async fn request_letter_adaptation(provider: &dyn AiProvider, prompt: &str) -> Result<GeneratedLetter, String> {
+    let response = provider
+        .call_llm(Some(ADAPT_LETTER_SYSTEM_PROMPT), prompt)
+        .await
+        .map_err(|e| format!("AI adaptation failed: {}", e))?;
+
+    let json_str = response
+        .find('{')
+        .and_then(|start| response.rfind('}').map(|end| &response[start..=end]))
+        .unwrap_or(response.as_str());
+    let letter: GeneratedLetter = serde_json::from_str(json_str)
+        .map_err(|e| format!("Failed to parse AI response: {}", e))?;
+
+    if letter.body_paragraphs.iter().all(|p| p.trim().is_empty()) {
+        return Err("The AI provider returned an empty letter; please try again.".to_string());
+    }
+    Ok(letter)
+}
+
+pub fn cache_letter_adaptation(
+    conn: &Connection,
+    input_hash: &str,
+    request_payload: &serde_json::Value,
+    letter: &GeneratedLetter,
+    now: &str,
+) -> Result<(), String> {
+    let response_payload = serde_json::to_value(letter).map_err(|e| format!("Failed to serialize letter: {}", e))?;
+    let model_name = crate::ai::settings::load_ai_settings()
+        .ok()
+        .and_then(|s| s.model_for(ADAPT_LETTER_PURPOSE))
+        .unwrap_or_else(|| "unknown-model".to_string());
+    ai_cache_put(
+        conn,
+        ADAPT_LETTER_PURPOSE,
+        input_hash,
+        &model_name,
+        request_payload,
+        &response_payload,
+        Some(CACHE_TTL_COVER_LETTER_DAYS),
+        now,
+    )
+}
@@ -0,0 +1,101 @@
+//! User-defined dashboard funnel stages
+//!
+//! The default funnel is Applied → Interviewing → Offer. Users who track finer stages
+//! ("Phone Screen", "Onsite") can replace it with an ordered list of names. An
+//! application reaches a stage when its status has that name or one of its timeline
+//! events has it as the event type or the status moved to. Each stage counts the
+//! applications that reached it or any later stage, so the funnel only narrows.
+
+use crate::app_settings::{get_json_setting, set_json_setting};
+use crate::commands::FunnelStep;
+use crate::errors::{CareerBenchError, ValidationError};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+
+/// Settings key for the custom funnel
+pub const DASHBOARD_FUNNEL_SETTING: &str = "dashboard_funnel";
+const MAX_FUNNEL_STAGES: usize = 10;
+
+/// Ordered funnel stage names; empty means the built-in three stages
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DashboardFunnelConfig {
+    #[serde(default)]
+    pub stages: Vec<String>,
+}
+
+pub fn load_dashboard_funnel(conn: &Connection) -> Result<DashboardFunnelConfig, CareerBenchError> {
+    get_json_setting(conn, DASHBOARD_FUNNEL_SETTING)
+}
+
+/// Save the funnel, trimming names and dropping blanks and repeats. Saving an empty
+/// list restores the built-in funnel.
+pub fn save_dashboard_funnel(conn: &Connection, config: &DashboardFunnelConfig) -> Result<(), CareerBenchError> {
+    let mut stages: Vec<String> = Vec::new();
+    for stage in &config.stages {
+        let stage = stage.trim();
+        if !stage.is_empty() && !stages.iter().any(|s| s.eq_ignore_ascii_case(stage)) {
+            stages.push(stage.to_string());
+        }
+    }
+    if stages.len() > MAX_FUNNEL_STAGES {
+        return Err(ValidationError::OutOfRange(format!(
+            "A funnel can have at most {} stages",
+            MAX_FUNNEL_STAGES
+        ))
+        .into());
+    }
+    set_json_setting(conn, DASHBOARD_FUNNEL_SETTING, &DashboardFunnelConfig { stages })
+}
+
+/// Counts for each custom stage over applications whose `date_saved` falls in
+/// `start_date..=end_date`. `day` is the SQL function giving a timestamp's calendar day.
+pub fn count_custom_funnel(
+    conn: &Connection,
+    stages: &[String],
+    day: &str,
+    start_date: &str,
+    end_date: &str,
+) -> Result<Vec<FunnelStep>, CareerBenchError> {
+    if stages.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // ?1..?n are the stage names, the date range follows
+    let reached = |param: usize| {
+        format!(
+            "(a.status = ?{0} OR EXISTS (SELECT 1 FROM application_events e
+                 WHERE e.application_id = a.id AND (e.event_type = ?{0} OR e.to_status = ?{0})))",
+            param
+        )
+    };
+    let columns: Vec<String> = (1..=stages.len())
+        .map(|stage| {
+            let reached_any_later: Vec<String> = (stage..=stages.len()).map(reached).collect();
+            format!("COUNT(CASE WHEN {} THEN 1 END)", reached_any_later.join(" OR "))
+        })
+        .collect();
+    let sql = format!(
+        "SELECT {0} FROM applications a WHERE {1}(a.date_saved) >= ?{2} AND {1}(a.date_saved) <= ?{3}",
+        columns.join(", "),
+        day,
+        stages.len() + 1,
+        stages.len() + 2
+    );
+
+    let mut params: Vec<&str> = stages.iter().map(String::as_str).collect();
+    params.push(start_date);
+    params.push(end_date);
+    let counts = conn.query_row(&sql, rusqlite::params_from_iter(params), |row| {
+        (0..stages.len()).map(|i| row.get::<_, i64>(i)).collect::<Result<Vec<_>, _>>()
+    })?;
+
+    Ok(stages
+        .iter()
+        .zip(counts)
+        .map(|(label, count)| FunnelStep {
+            label: label.clone(),
+            count,
+        })
+        .collect())
+}
@@ -21,6 +21,18 @@ pub fn delete_job(job_id: i64) -> Result<(), String> {
     let conn = get_connection()
         .map_err(|e| format!("Failed to connect to database: {}", e))?;
     
+    // Delete attachments and version history for artifacts linked to this job
+    conn.execute(
+        "DELETE FROM artifact_attachments WHERE artifact_id IN (SELECT id FROM artifacts WHERE job_id = ?)",
+        [job_id],
+    )
+    .map_err(|e| format!("Failed to delete artifact attachments for job {}: {}", job_id, e))?;
+    conn.execute(
+        "DELETE FROM artifact_versions WHERE artifact_id IN (SELECT id FROM artifacts WHERE job_id = ?)",
+        [job_id],
+    )
+    .map_err(|e| format!("Failed to delete artifact versions for job {}: {}", job_id, e))?;
+    
     // Delete artifacts linked to this job
     conn.execute(
         "DELETE FROM artifacts WHERE job_id = ?",
@@ -42,6 +54,13 @@ pub fn delete_job(job_id: i64) -> Result<(), String> {
     )
     .map_err(|e| format!("Failed to delete applications for job {}: {}", job_id, e))?;
     
+    // Delete user tags on this job
+    conn.execute(
+        "DELETE FROM job_tags WHERE job_id = ?",
+        [job_id],
+    )
+    .map_err(|e| format!("Failed to delete tags for job {}: {}", job_id, e))?;
+    
     // Delete the job itself
     conn.execute(
         "DELETE FROM jobs WHERE id = ?",
@@ -69,6 +88,18 @@ pub fn delete_application(application_id: i64) -> Result<(), String> {
     let conn = get_connection()
         .map_err(|e| format!("Failed to connect to database: {}", e))?;
     
+    // Delete attachments and version history for artifacts linked to this application
+    conn.execute(
+        "DELETE FROM artifact_attachments WHERE artifact_id IN (SELECT id FROM artifacts WHERE application_id = ?)",
+        [application_id],
+    )
+    .map_err(|e| format!("Failed to delete artifact attachments for application {}: {}", application_id, e))?;
+    conn.execute(
+        "DELETE FROM artifact_versions WHERE artifact_id IN (SELECT id FROM artifacts WHERE application_id = ?)",
+        [application_id],
+    )
+    .map_err(|e| format!("Failed to delete artifact versions for application {}: {}", application_id, e))?;
+    
     // Delete artifacts linked to this application
     conn.execute(
         "DELETE FROM artifacts WHERE application_id = ?",
@@ -105,6 +136,18 @@ pub fn delete_artifact(artifact_id: i64) -> Result<(), String> {
     let conn = get_connection()
         .map_err(|e| format!("Failed to connect to database: {}", e))?;
     
+    conn.execute(
+        "DELETE FROM artifact_attachments WHERE artifact_id = ?",
+        [artifact_id],
+    )
+    .map_err(|e| format!("Failed to delete attachments for artifact {}: {}", artifact_id, e))?;
+    
+    conn.execute(
+        "DELETE FROM artifact_versions WHERE artifact_id = ?",
+        [artifact_id],
+    )
+    .map_err(|e| format!("Failed to delete versions for artifact {}: {}", artifact_id, e))?;
+    
     conn.execute(
         "DELETE FROM artifacts WHERE id = ?",
         [artifact_id],
@@ -115,6 +158,46 @@ pub fn delete_artifact(artifact_id: i64) -> Result<(), String> {
     Ok(())
 }
 
+/// Delete artifacts left empty by aborted generations
+/// 
+/// Only removes artifacts whose content is null or whitespace, that have no
+/// attachments and that the user has never edited.
+/// 
+/// # Returns
+/// Number of artifacts removed
+pub fn prune_empty_artifacts() -> Result<u64, String> {
+    let conn = get_connection()
+        .map_err(|e| format!("Failed to connect to database: {}", e))?;
+    
+    prune_empty_artifacts_with_conn(&conn)
+}
+
+pub fn prune_empty_artifacts_with_conn(conn: &rusqlite::Connection) -> Result<u64, String> {
+    let prunable = "SELECT id FROM artifacts
+         WHERE (content IS NULL OR TRIM(content, ' ' || char(9) || char(10) || char(13)) = '')
+           AND COALESCE(source, '') != 'user_edited'
+           AND NOT EXISTS (SELECT 1 FROM artifact_attachments WHERE artifact_id = artifacts.id)";
+    
+    let tx = conn
+        .unchecked_transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    
+    tx.execute(
+        &format!("DELETE FROM artifact_versions WHERE artifact_id IN ({})", prunable),
+        [],
+    )
+    .map_err(|e| format!("Failed to delete versions for empty artifacts: {}", e))?;
+    
+    let removed = tx
+        .execute(&format!("DELETE FROM artifacts WHERE id IN ({})", prunable), [])
+        .map_err(|e| format!("Failed to prune empty artifacts: {}", e))?;
+    
+    tx.commit().map_err(|e| format!("Failed to commit artifact pruning: {}", e))?;
+    
+    log::info!("Pruned {} empty artifacts", removed);
+    Ok(removed as u64)
+}
+
 /// Delete profile section data
 /// 
 /// # Arguments
@@ -237,7 +320,11 @@ pub fn delete_all_user_data(include_ai_cache: bool) -> Result<(), String> {
     
     // Delete in order to respect foreign key constraints
     
-    // 1. Delete artifacts (no dependencies)
+    // 1. Delete artifacts with their attachments and version history (no dependencies)
+    conn.execute("DELETE FROM artifact_attachments", [])
+        .map_err(|e| format!("Failed to delete artifact attachments: {}", e))?;
+    conn.execute("DELETE FROM artifact_versions", [])
+        .map_err(|e| format!("Failed to delete artifact versions: {}", e))?;
     conn.execute("DELETE FROM artifacts", [])
         .map_err(|e| format!("Failed to delete artifacts: {}", e))?;
     log::info!("Deleted all artifacts");
@@ -252,7 +339,9 @@ pub fn delete_all_user_data(include_ai_cache: bool) -> Result<(), String> {
         .map_err(|e| format!("Failed to delete applications: {}", e))?;
     log::info!("Deleted all applications");
     
-    // 4. Delete jobs
+    // 4. Delete jobs and their tags
+    conn.execute("DELETE FROM job_tags", [])
+        .map_err(|e| format!("Failed to delete job tags: {}", e))?;
     conn.execute("DELETE FROM jobs", [])
         .map_err(|e| format!("Failed to delete jobs: {}", e))?;
     log::info!("Deleted all jobs");
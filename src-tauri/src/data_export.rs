@@ -7,6 +7,13 @@ use crate::db::get_connection;
 use serde::{Deserialize, Serialize};
 use chrono::Utc;
 
+/// Version of the export file format. Bump when the structure changes incompatibly.
+pub const EXPORT_SCHEMA_VERSION: u32 = 1;
+
+fn default_schema_version() -> u32 {
+    1
+}
+
 /// Complete data export structure
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DataExport {
@@ -29,6 +36,9 @@ pub struct ExportMetadata {
     pub exported_at: String,
     /// Application version (if available)
     pub version: String,
+    /// Export file format version (exports predating this field are version 1)
+    #[serde(default = "default_schema_version")]
+    pub schema_version: u32,
     /// Total records exported
     pub record_counts: RecordCounts,
 }
@@ -142,6 +152,7 @@ pub fn export_all_data() -> Result<DataExport, String> {
     let metadata = ExportMetadata {
         exported_at: Utc::now().to_rfc3339(),
         version: env!("CARGO_PKG_VERSION").to_string(),
+        schema_version: EXPORT_SCHEMA_VERSION,
         record_counts: RecordCounts {
             jobs: jobs.len(),
             applications: applications.len(),
@@ -163,7 +174,7 @@ fn export_profile_data(conn: &rusqlite::Connection) -> Result<Option<ProfileExpo
     // Get profile
     let profile_result: Result<crate::commands::UserProfile, _> = conn
         .query_row(
-            "SELECT id, full_name, headline, location, summary, current_role_title, current_company, seniority, open_to_roles, created_at, updated_at FROM user_profile WHERE id = 1",
+            "SELECT id, full_name, headline, location, summary, current_role_title, current_company, seniority, open_to_roles, created_at, updated_at, email, phone, website, github, linkedin FROM user_profile WHERE id = 1",
             [],
             |row| {
                 Ok(crate::commands::UserProfile {
@@ -178,6 +189,11 @@ fn export_profile_data(conn: &rusqlite::Connection) -> Result<Option<ProfileExpo
                     open_to_roles: row.get(8)?,
                     created_at: row.get(9)?,
                     updated_at: row.get(10)?,
+                    email: row.get(11)?,
+                    phone: row.get(12)?,
+                    website: row.get(13)?,
+                    github: row.get(14)?,
+                    linkedin: row.get(15)?,
                 })
             },
         );
@@ -313,6 +329,11 @@ fn export_profile_data(conn: &rusqlite::Connection) -> Result<Option<ProfileExpo
                 open_to_roles: None,
                 created_at: None,
                 updated_at: None,
+                email: None,
+                phone: None,
+                website: None,
+                github: None,
+                linkedin: None,
             }),
             experience,
             skills,
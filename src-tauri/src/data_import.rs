@@ -0,0 +1,146 @@
+//! Data import validation
+//!
+//! Checks a JSON export before anything is written to the database, so the UI
+//! can show what an import would do and let the user confirm.
+
+use crate::data_export::{DataExport, EXPORT_SCHEMA_VERSION};
+use crate::db::get_connection;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+
+/// Entity counts found in an import file
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct ImportCounts {
+    pub jobs: usize,
+    pub applications: usize,
+    pub events: usize,
+    pub artifacts: usize,
+    pub has_profile: bool,
+}
+
+/// An id in the import file that already exists in the database
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct IdCollision {
+    pub entity: String,
+    pub id: i64,
+}
+
+/// Result of validating an import file without importing it
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImportValidation {
+    pub schema_version: Option<u32>,
+    pub supported_schema_version: u32,
+    pub compatible: bool,
+    pub counts: ImportCounts,
+    pub collisions: Vec<IdCollision>,
+    /// True when the import would replace the existing profile
+    pub overwrites_profile: bool,
+    pub errors: Vec<String>,
+    /// True when the file is compatible, well-formed, and has no id collisions
+    pub can_import: bool,
+}
+
+/// Validate an import JSON against the current database
+pub fn validate_import_json(json: &str) -> Result<ImportValidation, String> {
+    let conn = get_connection()
+        .map_err(|e| format!("Failed to connect to database: {}", e))?;
+    validate_import_json_with_conn(&conn, json)
+}
+
+pub fn validate_import_json_with_conn(conn: &Connection, json: &str) -> Result<ImportValidation, String> {
+    let mut report = ImportValidation {
+        schema_version: None,
+        supported_schema_version: EXPORT_SCHEMA_VERSION,
+        compatible: false,
+        counts: ImportCounts::default(),
+        collisions: Vec::new(),
+        overwrites_profile: false,
+        errors: Vec::new(),
+        can_import: false,
+    };
+
+    let value: serde_json::Value = match serde_json::from_str(json) {
+        Ok(value) => value,
+        Err(e) => {
+            report.errors.push(format!("Invalid JSON: {}", e));
+            return Ok(report);
+        }
+    };
+
+    // Check the version before parsing the full structure; newer files may not parse
+    let schema_version = match value.pointer("/metadata/schema_version") {
+        Some(v) => v.as_u64().map(|v| v as u32),
+        None => Some(1),
+    };
+    report.schema_version = schema_version;
+
+    match schema_version {
+        Some(version) if version <= EXPORT_SCHEMA_VERSION => report.compatible = true,
+        Some(version) => {
+            report.errors.push(format!(
+                "Export schema version {} is newer than supported version {}. Update CareerBench to import this file.",
+                version, EXPORT_SCHEMA_VERSION
+            ));
+            return Ok(report);
+        }
+        None => {
+            report.errors.push("Export schema version is not a number".to_string());
+            return Ok(report);
+        }
+    }
+
+    let export: DataExport = match serde_json::from_value(value) {
+        Ok(export) => export,
+        Err(e) => {
+            report.compatible = false;
+            report.errors.push(format!("Export file structure is invalid: {}", e));
+            return Ok(report);
+        }
+    };
+
+    report.counts = ImportCounts {
+        jobs: export.jobs.len(),
+        applications: export.applications.len(),
+        events: export.applications.iter().map(|a| a.events.len()).sum(),
+        artifacts: export.artifacts.len(),
+        has_profile: export.profile.is_some(),
+    };
+
+    let exists = |table: &str, id: i64| -> Result<bool, String> {
+        conn.query_row(
+            &format!("SELECT COUNT(*) FROM {} WHERE id = ?", table),
+            [id],
+            |row| Ok(row.get::<_, i64>(0)? > 0),
+        )
+        .map_err(|e| format!("Failed to check existing {}: {}", table, e))
+    };
+
+    for job in &export.jobs {
+        if exists("jobs", job.id)? {
+            report.collisions.push(IdCollision { entity: "job".to_string(), id: job.id });
+        }
+    }
+    for application in &export.applications {
+        if exists("applications", application.id)? {
+            report.collisions.push(IdCollision { entity: "application".to_string(), id: application.id });
+        }
+        for event in &application.events {
+            if exists("application_events", event.id)? {
+                report.collisions.push(IdCollision { entity: "application_event".to_string(), id: event.id });
+            }
+        }
+    }
+    for artifact in &export.artifacts {
+        if exists("artifacts", artifact.id)? {
+            report.collisions.push(IdCollision { entity: "artifact".to_string(), id: artifact.id });
+        }
+    }
+
+    if export.profile.is_some() {
+        report.overwrites_profile = exists("user_profile", 1)?;
+    }
+
+    report.can_import = report.compatible && report.errors.is_empty() && report.collisions.is_empty();
+
+    Ok(report)
+}
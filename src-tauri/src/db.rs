@@ -34,7 +34,11 @@ pub fn get_connection() -> Result<Connection> {
 
 pub fn init_database() -> Result<()> {
     let conn = get_connection()?;
-    
+    init_database_with_conn(&conn)
+}
+
+/// Create the migrations table and run any migrations not applied to `conn` yet
+pub fn init_database_with_conn(conn: &Connection) -> Result<()> {
     // Create migrations table
     conn.execute(
         "CREATE TABLE IF NOT EXISTS migrations (
@@ -46,7 +50,7 @@ pub fn init_database() -> Result<()> {
     )?;
 
     // Run migrations
-    run_migrations(&conn)?;
+    run_migrations(conn)?;
     
     Ok(())
 }
@@ -206,6 +210,300 @@ fn run_migrations(conn: &Connection) -> Result<()> {
         )?;
     }
 
+    // Run migration 012 - Client event ids for idempotent event inserts
+    let migration_name = "012_application_event_client_ids";
+    let mut stmt = conn.prepare("SELECT COUNT(*) FROM migrations WHERE name = ?")?;
+    let count: i64 = stmt.query_row([migration_name], |row| row.get(0))?;
+    
+    if count == 0 {
+        println!("Running migration: {}", migration_name);
+        migration_012_application_event_client_ids(conn)?;
+        conn.execute(
+            "INSERT INTO migrations (name, applied_at) VALUES (?, datetime('now'))",
+            [migration_name],
+        )?;
+    }
+
+    // Run migration 013 - General key/value app settings
+    let migration_name = "013_app_settings";
+    let mut stmt = conn.prepare("SELECT COUNT(*) FROM migrations WHERE name = ?")?;
+    let count: i64 = stmt.query_row([migration_name], |row| row.get(0))?;
+    
+    if count == 0 {
+        println!("Running migration: {}", migration_name);
+        migration_013_app_settings(conn)?;
+        conn.execute(
+            "INSERT INTO migrations (name, applied_at) VALUES (?, datetime('now'))",
+            [migration_name],
+        )?;
+    }
+
+    // Run migration 014 - Artifact content history
+    let migration_name = "014_artifact_versions";
+    let mut stmt = conn.prepare("SELECT COUNT(*) FROM migrations WHERE name = ?")?;
+    let count: i64 = stmt.query_row([migration_name], |row| row.get(0))?;
+    
+    if count == 0 {
+        println!("Running migration: {}", migration_name);
+        migration_014_artifact_versions(conn)?;
+        conn.execute(
+            "INSERT INTO migrations (name, applied_at) VALUES (?, datetime('now'))",
+            [migration_name],
+        )?;
+    }
+
+    // Run migration 015 - Captured salary text on jobs
+    let migration_name = "015_job_salary_text";
+    let mut stmt = conn.prepare("SELECT COUNT(*) FROM migrations WHERE name = ?")?;
+    let count: i64 = stmt.query_row([migration_name], |row| row.get(0))?;
+    
+    if count == 0 {
+        println!("Running migration: {}", migration_name);
+        migration_015_job_salary_text(conn)?;
+        conn.execute(
+            "INSERT INTO migrations (name, applied_at) VALUES (?, datetime('now'))",
+            [migration_name],
+        )?;
+    }
+
+    // Run migration 016 - Add artifact attachments
+    let migration_name = "016_artifact_attachments";
+    let mut stmt = conn.prepare("SELECT COUNT(*) FROM migrations WHERE name = ?")?;
+    let count: i64 = stmt.query_row([migration_name], |row| row.get(0))?;
+    
+    if count == 0 {
+        println!("Running migration: {}", migration_name);
+        migration_016_artifact_attachments(conn)?;
+        conn.execute(
+            "INSERT INTO migrations (name, applied_at) VALUES (?, datetime('now'))",
+            [migration_name],
+        )?;
+    }
+
+    // Run migration 017 - Add job application deadlines
+    let migration_name = "017_job_application_deadline";
+    let mut stmt = conn.prepare("SELECT COUNT(*) FROM migrations WHERE name = ?")?;
+    let count: i64 = stmt.query_row([migration_name], |row| row.get(0))?;
+    
+    if count == 0 {
+        println!("Running migration: {}", migration_name);
+        migration_017_job_application_deadline(conn)?;
+        conn.execute(
+            "INSERT INTO migrations (name, applied_at) VALUES (?, datetime('now'))",
+            [migration_name],
+        )?;
+    }
+
+    // Run migration 018 - Keep original company size/industry text next to the canonical values
+    let migration_name = "018_company_raw_fields";
+    let mut stmt = conn.prepare("SELECT COUNT(*) FROM migrations WHERE name = ?")?;
+    let count: i64 = stmt.query_row([migration_name], |row| row.get(0))?;
+    
+    if count == 0 {
+        println!("Running migration: {}", migration_name);
+        migration_018_company_raw_fields(conn)?;
+        conn.execute(
+            "INSERT INTO migrations (name, applied_at) VALUES (?, datetime('now'))",
+            [migration_name],
+        )?;
+    }
+
+    // Run migration 019 - AI call log for usage and cost auditing
+    let migration_name = "019_ai_call_log";
+    let mut stmt = conn.prepare("SELECT COUNT(*) FROM migrations WHERE name = ?")?;
+    let count: i64 = stmt.query_row([migration_name], |row| row.get(0))?;
+    
+    if count == 0 {
+        println!("Running migration: {}", migration_name);
+        migration_019_ai_call_log(conn)?;
+        conn.execute(
+            "INSERT INTO migrations (name, applied_at) VALUES (?, datetime('now'))",
+            [migration_name],
+        )?;
+    }
+
+    // Run migration 020 - Benefits and visa sponsorship on jobs
+    let migration_name = "020_job_benefits";
+    let mut stmt = conn.prepare("SELECT COUNT(*) FROM migrations WHERE name = ?")?;
+    let count: i64 = stmt.query_row([migration_name], |row| row.get(0))?;
+    
+    if count == 0 {
+        println!("Running migration: {}", migration_name);
+        migration_020_job_benefits(conn)?;
+        conn.execute(
+            "INSERT INTO migrations (name, applied_at) VALUES (?, datetime('now'))",
+            [migration_name],
+        )?;
+    }
+
+    // Run migration 021 - Interview debriefs
+    let migration_name = "021_interview_debriefs";
+    let mut stmt = conn.prepare("SELECT COUNT(*) FROM migrations WHERE name = ?")?;
+    let count: i64 = stmt.query_row([migration_name], |row| row.get(0))?;
+    
+    if count == 0 {
+        println!("Running migration: {}", migration_name);
+        migration_021_interview_debriefs(conn)?;
+        conn.execute(
+            "INSERT INTO migrations (name, applied_at) VALUES (?, datetime('now'))",
+            [migration_name],
+        )?;
+    }
+
+    // Run migration 022 - Profile contact fields
+    let migration_name = "022_profile_contact_fields";
+    let mut stmt = conn.prepare("SELECT COUNT(*) FROM migrations WHERE name = ?")?;
+    let count: i64 = stmt.query_row([migration_name], |row| row.get(0))?;
+    
+    if count == 0 {
+        println!("Running migration: {}", migration_name);
+        migration_022_profile_contact_fields(conn)?;
+        conn.execute(
+            "INSERT INTO migrations (name, applied_at) VALUES (?, datetime('now'))",
+            [migration_name],
+        )?;
+    }
+
+    // Run migration 023 - Pending generation queue
+    let migration_name = "023_pending_generations";
+    let mut stmt = conn.prepare("SELECT COUNT(*) FROM migrations WHERE name = ?")?;
+    let count: i64 = stmt.query_row([migration_name], |row| row.get(0))?;
+    
+    if count == 0 {
+        println!("Running migration: {}", migration_name);
+        migration_023_pending_generations(conn)?;
+        conn.execute(
+            "INSERT INTO migrations (name, applied_at) VALUES (?, datetime('now'))",
+            [migration_name],
+        )?;
+    }
+
+    // Run migration 024 - Full-text search index over jobs
+    let migration_name = "024_jobs_fts";
+    let mut stmt = conn.prepare("SELECT COUNT(*) FROM migrations WHERE name = ?")?;
+    let count: i64 = stmt.query_row([migration_name], |row| row.get(0))?;
+    
+    if count == 0 {
+        println!("Running migration: {}", migration_name);
+        migration_024_jobs_fts(conn)?;
+        conn.execute(
+            "INSERT INTO migrations (name, applied_at) VALUES (?, datetime('now'))",
+            [migration_name],
+        )?;
+    }
+
+    // Run migration 025 - Structured salary range on jobs
+    let migration_name = "025_job_salary_range";
+    let mut stmt = conn.prepare("SELECT COUNT(*) FROM migrations WHERE name = ?")?;
+    let count: i64 = stmt.query_row([migration_name], |row| row.get(0))?;
+    
+    if count == 0 {
+        println!("Running migration: {}", migration_name);
+        migration_025_job_salary_range(conn)?;
+        conn.execute(
+            "INSERT INTO migrations (name, applied_at) VALUES (?, datetime('now'))",
+            [migration_name],
+        )?;
+    }
+
+    // Run migration 026 - User tags on jobs
+    let migration_name = "026_job_tags";
+    let mut stmt = conn.prepare("SELECT COUNT(*) FROM migrations WHERE name = ?")?;
+    let count: i64 = stmt.query_row([migration_name], |row| row.get(0))?;
+    
+    if count == 0 {
+        println!("Running migration: {}", migration_name);
+        migration_026_job_tags(conn)?;
+        conn.execute(
+            "INSERT INTO migrations (name, applied_at) VALUES (?, datetime('now'))",
+            [migration_name],
+        )?;
+    }
+
+    // Run migration 027 - Card order within Kanban columns
+    let migration_name = "027_application_board_order";
+    let mut stmt = conn.prepare("SELECT COUNT(*) FROM migrations WHERE name = ?")?;
+    let count: i64 = stmt.query_row([migration_name], |row| row.get(0))?;
+    
+    if count == 0 {
+        println!("Running migration: {}", migration_name);
+        migration_027_application_board_order(conn)?;
+        conn.execute(
+            "INSERT INTO migrations (name, applied_at) VALUES (?, datetime('now'))",
+            [migration_name],
+        )?;
+    }
+
+    // Run migration 028 - Add embeddings to the AI cache
+    let migration_name = "028_ai_cache_embedding";
+    let mut stmt = conn.prepare("SELECT COUNT(*) FROM migrations WHERE name = ?")?;
+    let count: i64 = stmt.query_row([migration_name], |row| row.get(0))?;
+    
+    if count == 0 {
+        println!("Running migration: {}", migration_name);
+        migration_028_ai_cache_embedding(conn)?;
+        conn.execute(
+            "INSERT INTO migrations (name, applied_at) VALUES (?, datetime('now'))",
+            [migration_name],
+        )?;
+    }
+
+    // Run migration 029 - Add recurrence rules to application events
+    let migration_name = "029_event_recurrence";
+    let mut stmt = conn.prepare("SELECT COUNT(*) FROM migrations WHERE name = ?")?;
+    let count: i64 = stmt.query_row([migration_name], |row| row.get(0))?;
+    
+    if count == 0 {
+        println!("Running migration: {}", migration_name);
+        migration_029_event_recurrence(conn)?;
+        conn.execute(
+            "INSERT INTO migrations (name, applied_at) VALUES (?, datetime('now'))",
+            [migration_name],
+        )?;
+    }
+
+    // Run migration 030 - Reminder recurrence
+    let migration_name = "030_reminder_recurrence";
+    let mut stmt = conn.prepare("SELECT COUNT(*) FROM migrations WHERE name = ?")?;
+    let count: i64 = stmt.query_row([migration_name], |row| row.get(0))?;
+    
+    if count == 0 {
+        println!("Running migration: {}", migration_name);
+        migration_030_reminder_recurrence(conn)?;
+        conn.execute(
+            "INSERT INTO migrations (name, applied_at) VALUES (?, datetime('now'))",
+            [migration_name],
+        )?;
+    }
+
+    // Run migration 031 - Record which model computed each cache embedding
+    let migration_name = "031_ai_cache_embedding_model";
+    let mut stmt = conn.prepare("SELECT COUNT(*) FROM migrations WHERE name = ?")?;
+    let count: i64 = stmt.query_row([migration_name], |row| row.get(0))?;
+    
+    if count == 0 {
+        println!("Running migration: {}", migration_name);
+        migration_031_ai_cache_embedding_model(conn)?;
+        conn.execute(
+            "INSERT INTO migrations (name, applied_at) VALUES (?, datetime('now'))",
+            [migration_name],
+        )?;
+    }
+
+    // Run migration 032 - IMAP UID cursor for email sync
+    let migration_name = "032_email_sync_cursor";
+    let mut stmt = conn.prepare("SELECT COUNT(*) FROM migrations WHERE name = ?")?;
+    let count: i64 = stmt.query_row([migration_name], |row| row.get(0))?;
+    
+    if count == 0 {
+        println!("Running migration: {}", migration_name);
+        migration_032_email_sync_cursor(conn)?;
+        conn.execute(
+            "INSERT INTO migrations (name, applied_at) VALUES (?, datetime('now'))",
+            [migration_name],
+        )?;
+    }
+
     Ok(())
 }
 
@@ -1022,3 +1320,374 @@ pub fn migration_011_companies_mission_vision_values(conn: &Connection) -> Resul
     Ok(())
 }
 
+pub fn migration_012_application_event_client_ids(conn: &Connection) -> Result<()> {
+    // Client-supplied id so repeated submits of the same event can be detected
+    conn.execute(
+        "ALTER TABLE application_events ADD COLUMN client_event_id TEXT",
+        [],
+    ).ok(); // Ignore error if column already exists
+
+    // Unique per application; NULLs are allowed to repeat for events without a client id
+    conn.execute(
+        "CREATE UNIQUE INDEX IF NOT EXISTS idx_application_events_client_event_id 
+         ON application_events (application_id, client_event_id)",
+        [],
+    )?;
+
+    Ok(())
+}
+
+pub fn migration_013_app_settings(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS app_settings (
+            key TEXT PRIMARY KEY,
+            value TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        )",
+        [],
+    )?;
+
+    Ok(())
+}
+
+pub fn migration_014_artifact_versions(conn: &Connection) -> Result<()> {
+    // Snapshot of artifact content for each saved version
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS artifact_versions (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            artifact_id INTEGER NOT NULL,
+            version INTEGER NOT NULL,
+            content TEXT,
+            created_at TEXT NOT NULL,
+            UNIQUE (artifact_id, version),
+            FOREIGN KEY (artifact_id) REFERENCES artifacts(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
+    Ok(())
+}
+
+pub fn migration_015_job_salary_text(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "ALTER TABLE jobs ADD COLUMN salary_text TEXT",
+        [],
+    ).ok(); // Ignore error if column already exists
+
+    Ok(())
+}
+
+pub fn migration_016_artifact_attachments(conn: &Connection) -> Result<()> {
+    // Files attached to an artifact (e.g. an uploaded PDF of the final resume)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS artifact_attachments (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            artifact_id INTEGER NOT NULL,
+            file_name TEXT NOT NULL,
+            file_path TEXT NOT NULL,
+            mime_type TEXT,
+            created_at TEXT NOT NULL,
+            FOREIGN KEY (artifact_id) REFERENCES artifacts(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_artifact_attachments_artifact_id
+         ON artifact_attachments (artifact_id)",
+        [],
+    )?;
+
+    Ok(())
+}
+
+pub fn migration_017_job_application_deadline(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "ALTER TABLE jobs ADD COLUMN application_deadline TEXT",
+        [],
+    ).ok(); // Ignore error if column already exists
+
+    Ok(())
+}
+
+pub fn migration_018_company_raw_fields(conn: &Connection) -> Result<()> {
+    use crate::company_taxonomy::{normalize_company_size, normalize_field, normalize_industry, CompanySize, Industry};
+
+    conn.execute(
+        "ALTER TABLE companies ADD COLUMN company_size_raw TEXT",
+        [],
+    ).ok(); // Ignore error if column already exists
+
+    conn.execute(
+        "ALTER TABLE companies ADD COLUMN industry_raw TEXT",
+        [],
+    ).ok(); // Ignore error if column already exists
+
+    // Normalize existing companies, keeping what was typed in the raw columns
+    let mut stmt = conn.prepare(
+        "SELECT id, company_size, industry FROM companies WHERE company_size_raw IS NULL AND industry_raw IS NULL",
+    )?;
+    let existing: Vec<(i64, Option<String>, Option<String>)> = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+        .collect::<Result<_>>()?;
+    drop(stmt);
+
+    for (id, company_size, industry) in existing {
+        let (size, size_raw) = normalize_field(company_size.as_deref(), normalize_company_size, CompanySize::as_str);
+        let (industry, industry_raw) = normalize_field(industry.as_deref(), normalize_industry, Industry::as_str);
+        conn.execute(
+            "UPDATE companies SET company_size = ?, company_size_raw = ?, industry = ?, industry_raw = ? WHERE id = ?",
+            rusqlite::params![size, size_raw, industry, industry_raw, id],
+        )?;
+    }
+
+    Ok(())
+}
+
+pub fn migration_019_ai_call_log(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS ai_call_log (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            created_at TEXT NOT NULL,
+            purpose TEXT NOT NULL,
+            provider TEXT NOT NULL,
+            model TEXT,
+            prompt_tokens INTEGER NOT NULL DEFAULT 0,
+            completion_tokens INTEGER NOT NULL DEFAULT 0,
+            estimated_cost REAL NOT NULL DEFAULT 0,
+            latency_ms INTEGER NOT NULL DEFAULT 0,
+            cache_hit INTEGER NOT NULL DEFAULT 0
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_ai_call_log_created_at ON ai_call_log(created_at)",
+        [],
+    )?;
+
+    Ok(())
+}
+
+pub fn migration_020_job_benefits(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "ALTER TABLE jobs ADD COLUMN benefits TEXT",
+        [],
+    ).ok(); // Ignore error if column already exists
+
+    conn.execute(
+        "ALTER TABLE jobs ADD COLUMN visa_sponsorship INTEGER",
+        [],
+    ).ok(); // Ignore error if column already exists
+
+    Ok(())
+}
+
+pub fn migration_021_interview_debriefs(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS interview_debriefs (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            application_id INTEGER NOT NULL,
+            round TEXT NOT NULL,
+            went_well TEXT NOT NULL,
+            to_improve TEXT NOT NULL,
+            rating INTEGER NOT NULL CHECK (rating BETWEEN 1 AND 5),
+            created_at TEXT NOT NULL,
+            FOREIGN KEY (application_id) REFERENCES applications(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_interview_debriefs_application_id
+         ON interview_debriefs (application_id, created_at DESC)",
+        [],
+    )?;
+
+    Ok(())
+}
+
+pub fn migration_022_profile_contact_fields(conn: &Connection) -> Result<()> {
+    for column in ["email", "phone", "website", "github", "linkedin"] {
+        conn.execute(
+            &format!("ALTER TABLE user_profile ADD COLUMN {} TEXT", column),
+            [],
+        ).ok(); // Ignore error if column already exists
+    }
+
+    Ok(())
+}
+
+pub fn migration_023_pending_generations(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS pending_generations (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            kind TEXT NOT NULL,
+            job_id INTEGER NOT NULL,
+            application_id INTEGER,
+            options_json TEXT,
+            status TEXT NOT NULL DEFAULT 'queued',
+            attempts INTEGER NOT NULL DEFAULT 0,
+            last_error TEXT,
+            next_attempt_at TEXT,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            FOREIGN KEY (job_id) REFERENCES jobs(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_pending_generations_status
+         ON pending_generations (status, next_attempt_at)",
+        [],
+    )?;
+
+    Ok(())
+}
+
+pub fn migration_024_jobs_fts(conn: &Connection) -> Result<()> {
+    // External-content FTS index over the searchable job text; rowid is jobs.id
+    conn.execute(
+        "CREATE VIRTUAL TABLE IF NOT EXISTS jobs_fts USING fts5(
+            title, company, location, raw_description,
+            content='jobs', content_rowid='id'
+        )",
+        [],
+    )?;
+
+    // Keep the index in step with the jobs table
+    conn.execute(
+        "CREATE TRIGGER IF NOT EXISTS jobs_fts_after_insert AFTER INSERT ON jobs BEGIN
+            INSERT INTO jobs_fts(rowid, title, company, location, raw_description)
+            VALUES (new.id, new.title, new.company, new.location, new.raw_description);
+        END",
+        [],
+    )?;
+    conn.execute(
+        "CREATE TRIGGER IF NOT EXISTS jobs_fts_after_delete AFTER DELETE ON jobs BEGIN
+            INSERT INTO jobs_fts(jobs_fts, rowid, title, company, location, raw_description)
+            VALUES ('delete', old.id, old.title, old.company, old.location, old.raw_description);
+        END",
+        [],
+    )?;
+    conn.execute(
+        "CREATE TRIGGER IF NOT EXISTS jobs_fts_after_update AFTER UPDATE OF title, company, location, raw_description ON jobs BEGIN
+            INSERT INTO jobs_fts(jobs_fts, rowid, title, company, location, raw_description)
+            VALUES ('delete', old.id, old.title, old.company, old.location, old.raw_description);
+            INSERT INTO jobs_fts(rowid, title, company, location, raw_description)
+            VALUES (new.id, new.title, new.company, new.location, new.raw_description);
+        END",
+        [],
+    )?;
+
+    // Backfill from the rows that already exist
+    conn.execute("INSERT INTO jobs_fts(jobs_fts) VALUES ('rebuild')", [])?;
+
+    Ok(())
+}
+
+pub fn migration_025_job_salary_range(conn: &Connection) -> Result<()> {
+    // Filled in from the AI parse; salary_text keeps whatever the posting said
+    for column in ["salary_min REAL", "salary_max REAL", "salary_currency TEXT"] {
+        conn.execute(&format!("ALTER TABLE jobs ADD COLUMN {}", column), []).ok(); // Ignore error if column already exists
+    }
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_jobs_salary_max ON jobs(salary_max)",
+        [],
+    )?;
+
+    Ok(())
+}
+
+pub fn migration_026_job_tags(conn: &Connection) -> Result<()> {
+    // Free-form labels such as "dream" or "remote-only", stored lowercased
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS job_tags (
+            job_id INTEGER NOT NULL,
+            tag TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            PRIMARY KEY (job_id, tag),
+            FOREIGN KEY (job_id) REFERENCES jobs(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_job_tags_tag ON job_tags(tag)",
+        [],
+    )?;
+
+    Ok(())
+}
+
+pub fn migration_027_application_board_order(conn: &Connection) -> Result<()> {
+    // Position of the card within its status column; 0 until the user reorders
+    conn.execute(
+        "ALTER TABLE applications ADD COLUMN board_order INTEGER NOT NULL DEFAULT 0",
+        [],
+    ).ok(); // Ignore error if column already exists
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_applications_status_board_order ON applications(status, board_order)",
+        [],
+    )?;
+
+    Ok(())
+}
+
+pub fn migration_028_ai_cache_embedding(conn: &Connection) -> Result<()> {
+    // JSON array of floats, set when the semantic cache is on; NULL otherwise
+    conn.execute(
+        "ALTER TABLE ai_cache ADD COLUMN embedding TEXT",
+        [],
+    ).ok(); // Ignore error if column already exists
+
+    Ok(())
+}
+
+pub fn migration_029_event_recurrence(conn: &Connection) -> Result<()> {
+    // RRULE value (e.g. FREQ=WEEKLY;COUNT=4); NULL for one-off events
+    conn.execute(
+        "ALTER TABLE application_events ADD COLUMN recurrence TEXT",
+        [],
+    ).ok(); // Ignore error if column already exists
+
+    Ok(())
+}
+
+pub fn migration_030_reminder_recurrence(conn: &Connection) -> Result<()> {
+    // RRULE for repeating reminders; NULL for one-shot reminders
+    conn.execute(
+        "ALTER TABLE reminders ADD COLUMN recurrence TEXT",
+        [],
+    ).ok(); // Ignore error if column already exists
+
+    Ok(())
+}
+
+pub fn migration_031_ai_cache_embedding_model(conn: &Connection) -> Result<()> {
+    // Vectors from different embedding models can't be compared, so each is tagged
+    conn.execute(
+        "ALTER TABLE ai_cache ADD COLUMN embedding_model TEXT",
+        [],
+    ).ok(); // Ignore error if column already exists
+
+    // Embeddings stored before this have no known model and would never match
+    conn.execute("UPDATE ai_cache SET embedding = NULL WHERE embedding_model IS NULL", [])?;
+
+    Ok(())
+}
+
+pub fn migration_032_email_sync_cursor(conn: &Connection) -> Result<()> {
+    // INBOX UIDVALIDITY and the highest UID fetched, so a sync continues by UID
+    // rather than by the sender-supplied Date header
+    conn.execute(
+        "ALTER TABLE email_accounts ADD COLUMN imap_uid_validity INTEGER",
+        [],
+    ).ok(); // Ignore error if column already exists
+    conn.execute(
+        "ALTER TABLE email_accounts ADD COLUMN imap_last_uid INTEGER",
+        [],
+    ).ok(); // Ignore error if column already exists
+
+    Ok(())
+}
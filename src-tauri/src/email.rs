@@ -1,7 +1,8 @@
 //! Email integration module for connecting to email providers and tracking application-related emails
 
 use crate::db::get_connection;
-use crate::errors::CareerBenchError;
+use crate::errors::{CareerBenchError, DatabaseError};
+use rusqlite::{Connection, OptionalExtension};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -57,8 +58,24 @@ pub struct EmailMessage {
     pub created_at: String,
 }
 
+fn account_from_row(row: &rusqlite::Row) -> rusqlite::Result<EmailAccount> {
+    Ok(EmailAccount {
+        id: row.get(0)?,
+        email_address: row.get(1)?,
+        provider: row.get(2)?,
+        imap_server: row.get(3)?,
+        imap_port: row.get(4)?,
+        smtp_server: row.get(5)?,
+        smtp_port: row.get(6)?,
+        use_ssl: row.get::<_, i64>(7)? != 0,
+        is_active: row.get::<_, i64>(8)? != 0,
+        last_sync_at: row.get(9)?,
+        created_at: row.get(10)?,
+        updated_at: row.get(11)?,
+    })
+}
+
 /// Get IMAP server settings for common email providers
-#[allow(dead_code)]
 pub fn get_provider_settings(provider: &str, email: &str) -> (String, u16, bool) {
     match provider.to_lowercase().as_str() {
         "gmail" => ("imap.gmail.com".to_string(), 993, true),
@@ -133,22 +150,7 @@ pub fn get_email_accounts() -> Result<Vec<EmailAccount>, CareerBenchError> {
          ORDER BY created_at DESC"
     )?;
 
-    let rows = stmt.query_map([], |row| {
-        Ok(EmailAccount {
-            id: row.get(0)?,
-            email_address: row.get(1)?,
-            provider: row.get(2)?,
-            imap_server: row.get(3)?,
-            imap_port: row.get(4)?,
-            smtp_server: row.get(5)?,
-            smtp_port: row.get(6)?,
-            use_ssl: row.get::<_, i64>(7)? != 0,
-            is_active: row.get::<_, i64>(8)? != 0,
-            last_sync_at: row.get(9)?,
-            created_at: row.get(10)?,
-            updated_at: row.get(11)?,
-        })
-    })?;
+    let rows = stmt.query_map([], account_from_row)?;
 
     let mut accounts = Vec::new();
     for row_result in rows {
@@ -158,10 +160,25 @@ pub fn get_email_accounts() -> Result<Vec<EmailAccount>, CareerBenchError> {
     Ok(accounts)
 }
 
-/// Delete an email account
+/// Get one email account
+pub fn get_email_account(conn: &Connection, account_id: i64) -> Result<EmailAccount, CareerBenchError> {
+    conn.query_row(
+        "SELECT id, email_address, provider, imap_server, imap_port, smtp_server, smtp_port, 
+         use_ssl, is_active, last_sync_at, created_at, updated_at
+         FROM email_accounts
+         WHERE id = ?",
+        [account_id],
+        account_from_row,
+    )
+    .optional()?
+    .ok_or_else(|| DatabaseError::NotFound(format!("Email account {} not found", account_id)).into())
+}
+
+/// Delete an email account and its saved password
 pub fn delete_email_account(account_id: i64) -> Result<(), CareerBenchError> {
     let conn = get_connection()?;
     conn.execute("DELETE FROM email_accounts WHERE id = ?", [account_id])?;
+    crate::email_sync::remove_account_password(account_id);
     Ok(())
 }
 
@@ -237,6 +254,14 @@ pub fn link_thread_to_application(
     application_id: i64,
 ) -> Result<(), CareerBenchError> {
     let conn = get_connection()?;
+    link_thread_to_application_with_conn(&conn, thread_id, application_id)
+}
+
+pub fn link_thread_to_application_with_conn(
+    conn: &Connection,
+    thread_id: i64,
+    application_id: i64,
+) -> Result<(), CareerBenchError> {
     conn.execute(
         "UPDATE email_threads SET application_id = ?, updated_at = datetime('now') WHERE id = ?",
         [application_id, thread_id],
@@ -0,0 +1,349 @@
+//! Matching synced email threads to applications
+//!
+//! A thread is scored against each open application from its senders and subject:
+//!
+//! | Signal                                          | Points |
+//! |-------------------------------------------------|--------|
+//! | A sender is the application's contact email     | 100    |
+//! | A sender's domain is the job's company          | 60     |
+//! | The subject contains the job title              | 40     |
+//! | The subject contains the company name           | 30     |
+//!
+//! A thread is linked when its best application reaches `LINK_THRESHOLD` and no other
+//! application scores as high. Mail from webmail and applicant-tracking domains says
+//! nothing about the employer, so those domains never count.
+
+use crate::errors::CareerBenchError;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+
+/// Score an application needs before a thread is linked to it automatically
+pub const LINK_THRESHOLD: u32 = 60;
+
+const CONTACT_EMAIL_SCORE: u32 = 100;
+const SENDER_DOMAIN_SCORE: u32 = 60;
+const SUBJECT_TITLE_SCORE: u32 = 40;
+const SUBJECT_COMPANY_SCORE: u32 = 30;
+
+/// Legal suffixes dropped from company names before comparing
+const COMPANY_SUFFIXES: &[&str] = &[
+    "inc", "llc", "ltd", "limited", "corp", "corporation", "co", "company", "gmbh", "plc", "ag", "sa", "bv",
+];
+
+/// Extra words companies add to their mail domains (acmehq.com, acme-careers.com)
+const DOMAIN_SUFFIXES: &[&str] = &["hq", "jobs", "careers", "group", "labs", "app", "mail"];
+
+/// Domains whose senders could be writing on behalf of anyone
+const SHARED_DOMAINS: &[&str] = &[
+    "gmail.com", "googlemail.com", "outlook.com", "hotmail.com", "live.com", "yahoo.com", "icloud.com",
+    "me.com", "aol.com", "proton.me", "protonmail.com", "greenhouse.io", "greenhouse-mail.io", "lever.co",
+    "hire.lever.co", "myworkday.com", "myworkdayjobs.com", "ashbyhq.com", "workablemail.com",
+    "smartrecruiters.com", "icims.com", "linkedin.com", "indeed.com", "jobvite.com", "bamboohr.com",
+];
+
+/// An application a thread could belong to
+#[derive(Debug, Clone)]
+pub struct LinkCandidate {
+    pub application_id: i64,
+    pub company: Option<String>,
+    pub job_title: Option<String>,
+    pub contact_email: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchScore {
+    pub score: u32,
+    pub reasons: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThreadLink {
+    pub thread_id: i64,
+    pub application_id: i64,
+    pub score: u32,
+    pub reasons: Vec<String>,
+}
+
+/// A thread that couldn't be linked confidently, with the closest application if any
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnlinkedThread {
+    pub thread_id: i64,
+    pub subject: Option<String>,
+    pub suggested_application_id: Option<i64>,
+    pub score: u32,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutoLinkResult {
+    pub linked: Vec<ThreadLink>,
+    pub unlinked: Vec<UnlinkedThread>,
+}
+
+/// Lowercase alphanumeric words
+fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Company name words without legal suffixes ("Acme Corp." -> ["acme"])
+fn company_words(company: &str) -> Vec<String> {
+    let mut words = words(company);
+    while words.len() > 1 && words.last().is_some_and(|w| COMPANY_SUFFIXES.contains(&w.as_str())) {
+        words.pop();
+    }
+    words
+}
+
+/// True when `needle` appears in `haystack` as a run of whole words
+fn contains_phrase(haystack: &[String], needle: &[String]) -> bool {
+    !needle.is_empty() && haystack.windows(needle.len()).any(|window| window == needle)
+}
+
+/// The address part of a From header ("Jane <jane@acme.com>" -> "jane@acme.com")
+pub fn sender_address(from: &str) -> Option<String> {
+    let address = match (from.rfind('<'), from.rfind('>')) {
+        (Some(start), Some(end)) if start < end => &from[start + 1..end],
+        _ => from,
+    };
+    let address = address.trim().to_lowercase();
+    address.contains('@').then_some(address)
+}
+
+/// Whether mail from `domain` is likely sent by `company` itself
+pub fn domain_matches_company(domain: &str, company: &str) -> bool {
+    let domain = domain.trim().to_lowercase();
+    if SHARED_DOMAINS.iter().any(|shared| domain == *shared || domain.ends_with(&format!(".{}", shared))) {
+        return false;
+    }
+    let key = company_words(company).concat();
+    if key.len() < 3 {
+        return false;
+    }
+
+    let labels: Vec<&str> = domain.split('.').collect();
+    // The last label is the TLD and never names the company
+    labels[..labels.len().saturating_sub(1)].iter().any(|label| {
+        let label: String = label.chars().filter(|c| c.is_alphanumeric()).collect();
+        label == key
+            || label
+                .strip_prefix(key.as_str())
+                .is_some_and(|rest| COMPANY_SUFFIXES.contains(&rest) || DOMAIN_SUFFIXES.contains(&rest))
+    })
+}
+
+/// Score how likely a thread with these senders and subject belongs to `candidate`
+pub fn score_thread(subject: Option<&str>, senders: &[String], candidate: &LinkCandidate) -> MatchScore {
+    let mut score = 0;
+    let mut reasons = Vec::new();
+    let addresses: Vec<String> = senders.iter().filter_map(|from| sender_address(from)).collect();
+
+    if let Some(contact) = candidate.contact_email.as_deref().and_then(sender_address) {
+        if addresses.contains(&contact) {
+            score += CONTACT_EMAIL_SCORE;
+            reasons.push(format!("Sent by the application contact ({})", contact));
+        }
+    }
+
+    if let Some(company) = candidate.company.as_deref() {
+        if let Some(domain) = addresses
+            .iter()
+            .filter_map(|address| address.rsplit('@').next())
+            .find(|domain| domain_matches_company(domain, company))
+        {
+            score += SENDER_DOMAIN_SCORE;
+            reasons.push(format!("Sender domain {} matches {}", domain, company));
+        }
+    }
+
+    let subject_words = words(subject.unwrap_or_default());
+    if let Some(title) = candidate.job_title.as_deref() {
+        // Drop qualifiers like "(Remote)" or " - London" that subjects usually leave out
+        let core = title.split(['(', ',', '|', '–']).next().unwrap_or(title);
+        let core = core.split(" - ").next().unwrap_or(core);
+        if contains_phrase(&subject_words, &words(core)) {
+            score += SUBJECT_TITLE_SCORE;
+            reasons.push(format!("Subject mentions {}", core.trim()));
+        }
+    }
+    if let Some(company) = candidate.company.as_deref() {
+        if contains_phrase(&subject_words, &company_words(company)) {
+            score += SUBJECT_COMPANY_SCORE;
+            reasons.push(format!("Subject mentions {}", company));
+        }
+    }
+
+    MatchScore { score, reasons }
+}
+
+/// The application a thread should be linked to: the best-scoring candidate, if it
+/// reaches the threshold and beats every other candidate
+pub fn pick_application(
+    subject: Option<&str>,
+    senders: &[String],
+    candidates: &[LinkCandidate],
+) -> Result<(i64, MatchScore), Option<(i64, MatchScore)>> {
+    let mut scored: Vec<(i64, MatchScore)> = candidates
+        .iter()
+        .map(|candidate| (candidate.application_id, score_thread(subject, senders, candidate)))
+        .filter(|(_, score)| score.score > 0)
+        .collect();
+    scored.sort_by(|a, b| b.1.score.cmp(&a.1.score));
+
+    let runner_up = scored.get(1).map(|(_, score)| score.score).unwrap_or(0);
+    match scored.into_iter().next() {
+        Some(best) if best.1.score >= LINK_THRESHOLD && best.1.score > runner_up => Ok(best),
+        best => Err(best),
+    }
+}
+
+fn link_candidates(conn: &Connection) -> Result<Vec<LinkCandidate>, CareerBenchError> {
+    let mut stmt = conn.prepare(
+        "SELECT a.id, j.company, j.title, a.contact_email
+         FROM applications a
+         LEFT JOIN jobs j ON j.id = a.job_id
+         WHERE COALESCE(a.archived, 0) = 0",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok(LinkCandidate {
+            application_id: row.get(0)?,
+            company: row.get(1)?,
+            job_title: row.get(2)?,
+            contact_email: row.get(3)?,
+        })
+    })?;
+    Ok(rows.collect::<Result<Vec<_>, _>>()?)
+}
+
+/// Try to link every unlinked, unarchived thread to an application. Threads that
+/// match some application, but not confidently enough to link, are returned for
+/// manual review; threads matching nothing (newsletters, personal mail) are left out.
+pub fn auto_link_threads(conn: &Connection) -> Result<AutoLinkResult, CareerBenchError> {
+    let candidates = link_candidates(conn)?;
+    let own_addresses: Vec<String> = {
+        let mut stmt = conn.prepare("SELECT email_address FROM email_accounts")?;
+        let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+        rows.filter_map(|row| row.ok()).map(|address| address.to_lowercase()).collect()
+    };
+    let threads: Vec<(i64, Option<String>)> = {
+        let mut stmt = conn.prepare(
+            "SELECT id, subject FROM email_threads
+             WHERE application_id IS NULL AND COALESCE(is_archived, 0) = 0
+             ORDER BY id",
+        )?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.collect::<Result<Vec<_>, _>>()?
+    };
+
+    let mut sender_stmt = conn.prepare("SELECT DISTINCT from_address FROM email_messages WHERE thread_id = ? AND from_address IS NOT NULL")?;
+    let mut result = AutoLinkResult::default();
+    for (thread_id, subject) in threads {
+        // The user's own replies would otherwise match every application
+        let senders: Vec<String> = sender_stmt
+            .query_map([thread_id], |row| row.get::<_, String>(0))?
+            .filter_map(|row| row.ok())
+            .filter(|from| !sender_address(from).is_some_and(|address| own_addresses.contains(&address)))
+            .collect();
+
+        match pick_application(subject.as_deref(), &senders, &candidates) {
+            Ok((application_id, score)) => {
+                crate::email::link_thread_to_application_with_conn(conn, thread_id, application_id)?;
+                result.linked.push(ThreadLink {
+                    thread_id,
+                    application_id,
+                    score: score.score,
+                    reasons: score.reasons,
+                });
+            }
+            Err(Some((suggested_application_id, score))) => result.unlinked.push(UnlinkedThread {
+                thread_id,
+                subject,
+                suggested_application_id: Some(suggested_application_id),
+                score: score.score,
+            }),
+            Err(None) => {}
+        }
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(id: i64, company: &str, title: &str, contact: Option<&str>) -> LinkCandidate {
+        LinkCandidate {
+            application_id: id,
+            company: Some(company.to_string()),
+            job_title: Some(title.to_string()),
+            contact_email: contact.map(str::to_string),
+        }
+    }
+
+    fn senders(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_sender_domain_matches_company() {
+        assert!(domain_matches_company("acme.com", "Acme Corp"));
+        assert!(domain_matches_company("mail.acme-corp.co.uk", "Acme Corp."));
+        assert!(domain_matches_company("acmehq.io", "Acme, Inc."));
+        assert!(!domain_matches_company("acmetrics.com", "Acme"));
+        assert!(!domain_matches_company("us.greenhouse-mail.io", "Greenhouse"));
+        assert!(!domain_matches_company("gmail.com", "Gmail"));
+        assert!(!domain_matches_company("io.com", "IO"));
+    }
+
+    #[test]
+    fn test_scores_combine_signals() {
+        let acme = candidate(1, "Acme", "Platform Engineer (Remote)", Some("Jane <JANE@acme.com>"));
+
+        let contact = score_thread(Some("Quick question"), &senders(&["jane@acme.com"]), &acme);
+        assert_eq!(contact.score, CONTACT_EMAIL_SCORE + SENDER_DOMAIN_SCORE);
+        assert_eq!(contact.reasons.len(), 2);
+
+        let ats = score_thread(
+            Some("Your application for Platform Engineer at Acme"),
+            &senders(&["Acme Hiring <no-reply@greenhouse.io>"]),
+            &acme,
+        );
+        assert_eq!(ats.score, SUBJECT_TITLE_SCORE + SUBJECT_COMPANY_SCORE);
+
+        let unrelated = score_thread(Some("Platform engineering newsletter"), &senders(&["news@example.org"]), &acme);
+        assert_eq!(unrelated.score, 0);
+    }
+
+    #[test]
+    fn test_pick_requires_threshold_and_a_clear_winner() {
+        let candidates = vec![
+            candidate(1, "Acme", "Platform Engineer", None),
+            candidate(2, "Acme", "Data Engineer", None),
+            candidate(3, "Globex", "Platform Engineer", None),
+        ];
+
+        // Both Acme applications match the domain equally, so neither is picked
+        let tie = pick_application(Some("Hello"), &senders(&["recruiting@acme.com"]), &candidates);
+        assert!(matches!(tie, Err(Some((1, _)))));
+
+        let (id, score) = pick_application(
+            Some("Data Engineer interview"),
+            &senders(&["recruiting@acme.com"]),
+            &candidates,
+        )
+        .unwrap();
+        assert_eq!(id, 2);
+        assert_eq!(score.score, SENDER_DOMAIN_SCORE + SUBJECT_TITLE_SCORE);
+
+        // A title alone isn't enough
+        let weak = pick_application(Some("Platform Engineer role"), &senders(&["someone@gmail.com"]), &candidates[2..]);
+        assert!(matches!(weak, Err(Some((3, MatchScore { score: SUBJECT_TITLE_SCORE, .. })))));
+        assert!(matches!(pick_application(None, &[], &candidates), Err(None)));
+    }
+}
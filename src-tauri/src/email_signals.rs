@@ -0,0 +1,304 @@
+//! Status signals in application emails
+//!
+//! Scans the messages linked to an application for phrases that mean an offer,
+//! a rejection or an interview invitation, and suggests the matching status. The
+//! phrases are case-insensitive regular expressions kept in settings, so they can
+//! be tuned without a release. Suggestions are never applied automatically.
+
+use crate::app_settings::{get_json_setting, set_json_setting};
+use crate::application_status::{validate_status_transition, ApplicationStatus};
+use crate::errors::{CareerBenchError, DatabaseError, ValidationError};
+use regex::{Regex, RegexBuilder};
+use rusqlite::{Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+/// Settings key for the signal phrases
+pub const EMAIL_SIGNAL_PATTERNS_SETTING: &str = "email_signal_patterns";
+
+/// Characters of context kept on each side of a matched phrase
+const SNIPPET_CONTEXT_CHARS: usize = 60;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum EmailSignal {
+    Offer,
+    Rejection,
+    InterviewInvitation,
+}
+
+impl EmailSignal {
+    /// The status an application would move to on this signal
+    pub fn proposed_status(&self) -> ApplicationStatus {
+        match self {
+            EmailSignal::Offer => ApplicationStatus::Offer,
+            EmailSignal::Rejection => ApplicationStatus::Rejected,
+            EmailSignal::InterviewInvitation => ApplicationStatus::Interviewing,
+        }
+    }
+}
+
+/// Phrases for each signal. Offers are checked first, then rejections, since
+/// rejection emails often thank the candidate for interviewing.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EmailSignalPatterns {
+    pub offer: Vec<String>,
+    pub rejection: Vec<String>,
+    pub interview_invitation: Vec<String>,
+}
+
+impl Default for EmailSignalPatterns {
+    fn default() -> Self {
+        let patterns = |list: &[&str]| list.iter().map(|p| p.to_string()).collect();
+        Self {
+            offer: patterns(&[
+                r"\b(pleased|delighted|happy|excited|thrilled) to (extend|offer)\b",
+                r"\boffer of employment\b",
+                r"\b(formal|written|verbal|job) offer\b",
+                r"\boffer letter\b",
+            ]),
+            rejection: patterns(&[
+                r"\b(decided|chosen|elected) to (move|go|proceed) forward with (other|another) candidates?\b",
+                r"\b(will not|won't|not) be (moving|proceeding|going) forward\b",
+                r"\bnot (to )?(move|moving|proceed|proceeding) forward with your (application|candidacy)\b",
+                r"\b(pursue|pursuing) other candidates\b",
+                r"\b(position|role) has been filled\b",
+                r"\bno longer (being )?considered\b",
+                r"\bregret to (inform|let you know)\b",
+            ]),
+            interview_invitation: patterns(&[
+                r"\b(invite|inviting|invitation) (you )?(to|for) (an? )?(phone |video |technical |onsite |on-site |virtual |final |first |second )?(interview|screen|chat|call|conversation)\b",
+                r"\bschedule (an? |your |the )?(phone |video |technical |onsite |virtual |final |next )?(interview|screen|call|chat)\b",
+                r"\b(like|love) to (set up|arrange|schedule|book) (an? |some )?(time|call|chat|interview)\b",
+                r"\bnext (round|stage|step) (of|in) (the|our) (interview|hiring) process\b",
+                r"\bcalendly\.com/",
+            ]),
+        }
+    }
+}
+
+impl EmailSignalPatterns {
+    /// Compile the phrases, naming the first one that isn't a valid regex
+    pub fn compile(&self) -> Result<SignalMatcher, CareerBenchError> {
+        let mut rules = Vec::new();
+        for (signal, patterns) in [
+            (EmailSignal::Offer, &self.offer),
+            (EmailSignal::Rejection, &self.rejection),
+            (EmailSignal::InterviewInvitation, &self.interview_invitation),
+        ] {
+            for pattern in patterns.iter().filter(|p| !p.trim().is_empty()) {
+                let regex = RegexBuilder::new(pattern)
+                    .case_insensitive(true)
+                    .build()
+                    .map_err(|e| ValidationError::InvalidFormat(format!("Invalid pattern '{}': {}", pattern, e)))?;
+                rules.push((signal, regex));
+            }
+        }
+        Ok(SignalMatcher { rules })
+    }
+}
+
+/// A signal found in a message
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignalMatch {
+    pub signal: EmailSignal,
+    /// The matched phrase with some surrounding text
+    pub snippet: String,
+}
+
+pub struct SignalMatcher {
+    rules: Vec<(EmailSignal, Regex)>,
+}
+
+impl SignalMatcher {
+    /// The first signal found in the subject or body
+    pub fn detect(&self, subject: Option<&str>, body: &str) -> Option<SignalMatch> {
+        let text = collapse_whitespace(&format!("{}\n{}", subject.unwrap_or_default(), body));
+        self.rules.iter().find_map(|(signal, regex)| {
+            regex.find(&text).map(|found| SignalMatch {
+                signal: *signal,
+                snippet: snippet(&text, found.start(), found.end()),
+            })
+        })
+    }
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// The text of an HTML body, good enough for phrase matching
+fn html_to_text(html: &str) -> String {
+    let tags = Regex::new(r"(?s)<(script|style)\b.*?</(script|style)>|<[^>]+>").unwrap();
+    tags.replace_all(html, " ")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+        .replace("&#39;", "'")
+        .replace("&rsquo;", "'")
+        .replace("&quot;", "\"")
+}
+
+/// `text[start..end]` with up to `SNIPPET_CONTEXT_CHARS` characters either side,
+/// cut at word boundaries
+fn snippet(text: &str, start: usize, end: usize) -> String {
+    let before: Vec<(usize, char)> = text[..start].char_indices().collect();
+    let mut from = before.len().checked_sub(SNIPPET_CONTEXT_CHARS).map(|i| before[i].0).unwrap_or(0);
+    if from > 0 {
+        from = text[from..start].find(' ').map(|i| from + i + 1).unwrap_or(from);
+    }
+    let mut to = text[end..].char_indices().nth(SNIPPET_CONTEXT_CHARS).map(|(i, _)| end + i).unwrap_or(text.len());
+    if to < text.len() {
+        to = text[end..to].rfind(' ').map(|i| end + i).unwrap_or(to);
+    }
+
+    let mut snippet = text[from..to].trim().to_string();
+    if from > 0 {
+        snippet.insert_str(0, "…");
+    }
+    if to < text.len() {
+        snippet.push('…');
+    }
+    snippet
+}
+
+/// A suggested status change backed by one email
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EmailStatusSuggestion {
+    pub message_id: i64,
+    pub thread_id: i64,
+    pub subject: Option<String>,
+    pub received_date: String,
+    pub signal: EmailSignal,
+    pub snippet: String,
+    pub proposed_status: String,
+    /// False when the move from the current status would need an override
+    pub transition_allowed: bool,
+}
+
+/// Load the signal phrases, or the defaults when none are saved
+pub fn get_email_signal_patterns(conn: &Connection) -> Result<EmailSignalPatterns, CareerBenchError> {
+    get_json_setting(conn, EMAIL_SIGNAL_PATTERNS_SETTING)
+}
+
+/// Save the signal phrases after checking they compile
+pub fn save_email_signal_patterns(conn: &Connection, patterns: &EmailSignalPatterns) -> Result<(), CareerBenchError> {
+    patterns.compile()?;
+    set_json_setting(conn, EMAIL_SIGNAL_PATTERNS_SETTING, patterns)
+}
+
+/// Status changes suggested by the emails linked to an application, newest first.
+/// Messages that only confirm the current status are left out.
+pub fn suggest_status_from_emails(
+    conn: &Connection,
+    application_id: i64,
+) -> Result<Vec<EmailStatusSuggestion>, CareerBenchError> {
+    let current_status: String = conn
+        .query_row("SELECT status FROM applications WHERE id = ?", [application_id], |row| row.get(0))
+        .optional()?
+        .ok_or_else(|| DatabaseError::NotFound(format!("Application {} not found", application_id)))?;
+    let matcher = get_email_signal_patterns(conn)?.compile()?;
+
+    let mut stmt = conn.prepare(
+        "SELECT m.id, m.thread_id, m.subject, m.body_text, m.body_html, m.received_date
+         FROM email_messages m
+         JOIN email_threads t ON t.id = m.thread_id
+         WHERE t.application_id = ?
+         ORDER BY m.received_date DESC, m.id DESC",
+    )?;
+    let rows = stmt.query_map([application_id], |row| {
+        Ok((
+            row.get::<_, i64>(0)?,
+            row.get::<_, i64>(1)?,
+            row.get::<_, Option<String>>(2)?,
+            row.get::<_, Option<String>>(3)?,
+            row.get::<_, Option<String>>(4)?,
+            row.get::<_, String>(5)?,
+        ))
+    })?;
+
+    let mut suggestions = Vec::new();
+    for row in rows {
+        let (message_id, thread_id, subject, body_text, body_html, received_date) = row?;
+        let body = body_text.or_else(|| body_html.as_deref().map(html_to_text)).unwrap_or_default();
+        let Some(found) = matcher.detect(subject.as_deref(), &body) else { continue };
+
+        let proposed_status = found.signal.proposed_status().as_str();
+        if proposed_status == current_status {
+            continue;
+        }
+        suggestions.push(EmailStatusSuggestion {
+            message_id,
+            thread_id,
+            subject,
+            received_date,
+            signal: found.signal,
+            snippet: found.snippet,
+            proposed_status: proposed_status.to_string(),
+            transition_allowed: validate_status_transition(&current_status, proposed_status, false).is_ok(),
+        });
+    }
+
+    Ok(suggestions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detect(subject: &str, body: &str) -> Option<SignalMatch> {
+        EmailSignalPatterns::default().compile().unwrap().detect(Some(subject), body)
+    }
+
+    #[test]
+    fn test_default_patterns_detect_each_signal() {
+        let offer = detect("Congratulations!", "We are delighted to extend an offer for the Platform Engineer role.").unwrap();
+        assert_eq!(offer.signal, EmailSignal::Offer);
+
+        let rejection = detect(
+            "Your application",
+            "Thank you for interviewing with us. Unfortunately, we have decided to move forward with other candidates.",
+        )
+        .unwrap();
+        assert_eq!(rejection.signal, EmailSignal::Rejection);
+        assert!(rejection.snippet.contains("decided to move forward with other candidates"));
+
+        let invite = detect("Next steps", "We'd love to schedule a technical interview next week.").unwrap();
+        assert_eq!(invite.signal, EmailSignal::InterviewInvitation);
+        assert_eq!(invite.signal.proposed_status(), ApplicationStatus::Interviewing);
+
+        assert_eq!(detect("Weekly newsletter", "Ten tips for your next offer negotiation"), None);
+    }
+
+    #[test]
+    fn test_snippet_keeps_context_at_word_boundaries() {
+        let filler = "lorem ipsum dolor sit amet ".repeat(6);
+        let body = format!("{}we regret to inform you that {}", filler, filler);
+        let found = detect("", &body).unwrap();
+        assert!(found.snippet.starts_with('…') && found.snippet.ends_with('…'));
+        assert!(found.snippet.contains("regret to inform"));
+        assert!(found.snippet.chars().count() < body.chars().count());
+        assert!(!found.snippet.contains("  "));
+    }
+
+    #[test]
+    fn test_custom_patterns_and_invalid_regex() {
+        let patterns = EmailSignalPatterns {
+            offer: vec![],
+            rejection: vec![r"\bnicht weiter\b".to_string()],
+            interview_invitation: vec![r"\bVorstellungsgespräch\b".to_string()],
+        };
+        let matcher = patterns.compile().unwrap();
+        assert_eq!(matcher.detect(None, "Einladung zum vorstellungsgespräch").unwrap().signal, EmailSignal::InterviewInvitation);
+        assert_eq!(matcher.detect(None, "pleased to offer you the role"), None);
+
+        let broken = EmailSignalPatterns { offer: vec!["(unclosed".to_string()], ..patterns };
+        assert!(broken.compile().is_err());
+    }
+
+    #[test]
+    fn test_html_bodies_are_matched_as_text() {
+        let body = html_to_text("<style>p{}</style><p>We&nbsp;regret to <b>inform</b> you</p>");
+        assert_eq!(detect("", &body).unwrap().signal, EmailSignal::Rejection);
+    }
+}
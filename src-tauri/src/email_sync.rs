@@ -0,0 +1,513 @@
+//! IMAP sync for email accounts
+//!
+//! Logs in with the account's address and app password (kept in secure storage),
+//! fetches INBOX messages newer than the last sync and files them into
+//! `email_threads` and `email_messages`. Syncs continue from the highest UID fetched
+//! so far; the first sync, or one after the server renumbers the mailbox, goes by
+//! date instead. Messages are deduped by Message-ID, so fetching the same day twice
+//! is harmless. Only password login is supported for now; OAuth will need its own
+//! authenticator.
+
+use crate::db::get_connection;
+use crate::email::{get_email_account, get_provider_settings, EmailAccount};
+use crate::email_matching::{auto_link_threads, ThreadLink, UnlinkedThread};
+use crate::errors::{CareerBenchError, ConfigurationError, EmailError, ValidationError};
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use futures::TryStreamExt;
+use mailparse::{DispositionType, MailHeaderMap, ParsedMail};
+use rusqlite::{Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::TcpStream;
+use tokio_native_tls::{native_tls, TlsConnector, TlsStream};
+
+/// How far back the first sync of an account reaches
+const INITIAL_SYNC_DAYS: i64 = 30;
+
+/// Most messages fetched in one sync; a larger backlog is worked through oldest first
+/// over several syncs
+pub const MAX_MESSAGES_PER_SYNC: usize = 500;
+
+/// A message fetched from the server, ready to store
+#[derive(Debug, Clone, PartialEq)]
+pub struct FetchedEmail {
+    pub message_id: String,
+    /// Message-ID of the first message in the conversation, used as the thread key
+    pub thread_key: String,
+    pub from_address: Option<String>,
+    pub to_address: Option<String>,
+    pub subject: Option<String>,
+    pub body_text: Option<String>,
+    pub body_html: Option<String>,
+    /// When the message was sent (UTC)
+    pub received_date: NaiveDateTime,
+}
+
+/// Where an account's last sync stopped: the INBOX's UIDVALIDITY and the highest UID
+/// fetched. UIDs only grow while UIDVALIDITY stays the same.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SyncCursor {
+    pub uid_validity: u32,
+    pub last_uid: u32,
+}
+
+/// Messages fetched in one sync and where the next one should continue
+#[derive(Debug, Clone, Default)]
+pub struct FetchOutcome {
+    pub emails: Vec<FetchedEmail>,
+    /// True when more messages are waiting on the server
+    pub truncated: bool,
+    pub cursor: Option<SyncCursor>,
+    /// Latest server arrival time (INTERNALDATE) among the fetched messages, UTC
+    pub newest_arrival: Option<NaiveDateTime>,
+}
+
+/// Outcome of one account sync
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmailSyncResult {
+    pub new_messages: usize,
+    /// Threads linked to an application automatically
+    pub linked: Vec<ThreadLink>,
+    /// Threads that partly match an application, waiting to be linked by hand
+    pub unlinked: Vec<UnlinkedThread>,
+}
+
+fn password_key(account_id: i64) -> String {
+    format!("email_account_{}_password", account_id)
+}
+
+/// Save the app password for an account; an empty password removes it
+pub fn save_account_password(account_id: i64, password: &str) -> Result<(), CareerBenchError> {
+    crate::secure_storage::store_secret(&password_key(account_id), password)
+        .map_err(|e| ConfigurationError::Other(format!("Failed to store email password: {}", e)).into())
+}
+
+/// Forget the app password for an account
+pub fn remove_account_password(account_id: i64) {
+    let _ = crate::secure_storage::remove_secret(&password_key(account_id));
+}
+
+fn imap_error(error: async_imap::error::Error) -> CareerBenchError {
+    EmailError::Protocol(error.to_string()).into()
+}
+
+fn first_message_id(value: &str) -> Option<String> {
+    value.split_whitespace().next().map(str::to_string)
+}
+
+/// First text/plain and text/html parts of a message, skipping attachments
+fn collect_bodies(part: &ParsedMail, text: &mut Option<String>, html: &mut Option<String>) {
+    if !part.subparts.is_empty() {
+        for subpart in &part.subparts {
+            collect_bodies(subpart, text, html);
+        }
+        return;
+    }
+    if part.get_content_disposition().disposition == DispositionType::Attachment {
+        return;
+    }
+    let slot = match part.ctype.mimetype.as_str() {
+        "text/plain" => text,
+        "text/html" => html,
+        _ => return,
+    };
+    if slot.is_none() {
+        *slot = part.get_body().ok().filter(|body| !body.trim().is_empty());
+    }
+}
+
+/// Parse a raw RFC 822 message. `internal_date` (the server's arrival time) is used
+/// when the Date header is missing or unreadable. Messages without a Message-ID get
+/// one derived from their content so they still dedupe.
+pub fn parse_fetched_email(
+    raw: &[u8],
+    internal_date: Option<DateTime<FixedOffset>>,
+) -> Result<FetchedEmail, CareerBenchError> {
+    let parsed = mailparse::parse_mail(raw)
+        .map_err(|e| EmailError::Protocol(format!("Unreadable message: {}", e)))?;
+    let header = |name: &str| {
+        parsed
+            .headers
+            .get_first_value(name)
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    };
+
+    let message_id = header("Message-ID").unwrap_or_else(|| {
+        let mut hasher = Sha256::new();
+        hasher.update(raw);
+        format!("<{:x}@careerbench.local>", hasher.finalize())
+    });
+    let thread_key = header("References")
+        .as_deref()
+        .and_then(first_message_id)
+        .or_else(|| header("In-Reply-To").as_deref().and_then(first_message_id))
+        .unwrap_or_else(|| message_id.clone());
+    let received_date = header("Date")
+        .and_then(|date| mailparse::dateparse(&date).ok())
+        .and_then(|timestamp| Utc.timestamp_opt(timestamp, 0).single())
+        .map(|date| date.naive_utc())
+        .or_else(|| internal_date.map(|date| date.naive_utc()))
+        .unwrap_or_else(|| Utc::now().naive_utc());
+
+    let mut body_text = None;
+    let mut body_html = None;
+    collect_bodies(&parsed, &mut body_text, &mut body_html);
+
+    Ok(FetchedEmail {
+        message_id,
+        thread_key,
+        from_address: header("From"),
+        to_address: header("To"),
+        subject: header("Subject"),
+        body_text,
+        body_html,
+        received_date,
+    })
+}
+
+fn merge_participants(existing: Option<&str>, from_address: Option<&str>) -> Option<String> {
+    let mut participants: Vec<String> = existing
+        .unwrap_or_default()
+        .split(", ")
+        .filter(|p| !p.is_empty())
+        .map(str::to_string)
+        .collect();
+    if let Some(from) = from_address {
+        if !participants.iter().any(|p| p.eq_ignore_ascii_case(from)) {
+            participants.push(from.to_string());
+        }
+    }
+    (!participants.is_empty()).then(|| participants.join(", "))
+}
+
+/// Store fetched messages, adding them to their thread (created if needed).
+/// Messages whose Message-ID is already stored are skipped. Returns the number of new messages.
+pub fn store_fetched_emails(
+    conn: &Connection,
+    account_id: i64,
+    emails: &[FetchedEmail],
+) -> Result<usize, CareerBenchError> {
+    let tx = conn.unchecked_transaction()?;
+    let mut new_messages = 0;
+
+    for email in emails {
+        let exists: bool = tx.query_row(
+            "SELECT EXISTS(SELECT 1 FROM email_messages WHERE message_id = ?)",
+            [&email.message_id],
+            |row| row.get(0),
+        )?;
+        if exists {
+            continue;
+        }
+
+        let received_date = email.received_date.format("%Y-%m-%d %H:%M:%S").to_string();
+        let thread = tx
+            .query_row(
+                "SELECT id, participants FROM email_threads WHERE thread_id = ?",
+                [&email.thread_key],
+                |row| Ok((row.get::<_, i64>(0)?, row.get::<_, Option<String>>(1)?)),
+            )
+            .optional()?;
+        let thread_id = match thread {
+            Some((thread_id, participants)) => {
+                tx.execute(
+                    "UPDATE email_threads
+                     SET participants = ?, message_count = message_count + 1,
+                         last_message_date = MAX(COALESCE(last_message_date, ''), ?),
+                         updated_at = datetime('now')
+                     WHERE id = ?",
+                    rusqlite::params![
+                        merge_participants(participants.as_deref(), email.from_address.as_deref()),
+                        received_date,
+                        thread_id
+                    ],
+                )?;
+                thread_id
+            }
+            None => {
+                tx.execute(
+                    "INSERT INTO email_threads (thread_id, subject, participants, last_message_date, message_count, created_at, updated_at)
+                     VALUES (?, ?, ?, ?, 1, datetime('now'), datetime('now'))",
+                    rusqlite::params![
+                        email.thread_key,
+                        email.subject,
+                        merge_participants(None, email.from_address.as_deref()),
+                        received_date
+                    ],
+                )?;
+                tx.last_insert_rowid()
+            }
+        };
+
+        tx.execute(
+            "INSERT INTO email_messages
+             (thread_id, email_account_id, message_id, from_address, to_address, subject, body_text, body_html, received_date, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, datetime('now'))",
+            rusqlite::params![
+                thread_id,
+                account_id,
+                email.message_id,
+                email.from_address,
+                email.to_address,
+                email.subject,
+                email.body_text,
+                email.body_html,
+                received_date
+            ],
+        )?;
+        new_messages += 1;
+    }
+
+    tx.commit()?;
+    Ok(new_messages)
+}
+
+/// First day to ask the server for. IMAP SINCE only has day precision and uses the
+/// server's time zone, so the day before the last sync is included and dedupe drops
+/// anything already stored.
+pub fn sync_since(last_sync_at: Option<&str>, now: DateTime<Utc>) -> NaiveDate {
+    let last_sync = last_sync_at.and_then(|value| {
+        DateTime::parse_from_rfc3339(value)
+            .map(|date| date.naive_utc())
+            .or_else(|_| NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S"))
+            .ok()
+    });
+    match last_sync {
+        Some(last_sync) => last_sync.date() - Duration::days(1),
+        None => (now - Duration::days(INITIAL_SYNC_DAYS)).date_naive(),
+    }
+}
+
+async fn tls_connect(server: &str, stream: TcpStream) -> Result<TlsStream<TcpStream>, CareerBenchError> {
+    let connector = native_tls::TlsConnector::new()
+        .map_err(|e| EmailError::ConnectionFailed(format!("TLS setup failed: {}", e)))?;
+    TlsConnector::from(connector)
+        .connect(server, stream)
+        .await
+        .map_err(|e| EmailError::ConnectionFailed(format!("TLS handshake with {} failed: {}", server, e)).into())
+}
+
+async fn read_greeting<T>(client: &mut async_imap::Client<T>) -> Result<(), CareerBenchError>
+where
+    T: AsyncRead + AsyncWrite + Unpin + std::fmt::Debug + Send,
+{
+    match client.read_response().await {
+        Some(Ok(_)) => Ok(()),
+        Some(Err(e)) => Err(EmailError::ConnectionFailed(e.to_string()).into()),
+        None => Err(EmailError::ConnectionFailed("Server closed the connection before greeting".to_string()).into()),
+    }
+}
+
+/// Connect over TLS (port 993) or, when the account doesn't use SSL, upgrade a plain
+/// connection with STARTTLS. The password is never sent unencrypted.
+async fn connect(account: &EmailAccount) -> Result<async_imap::Client<TlsStream<TcpStream>>, CareerBenchError> {
+    let (default_server, default_port, _) = get_provider_settings(&account.provider, &account.email_address);
+    let server = account
+        .imap_server
+        .clone()
+        .filter(|server| !server.trim().is_empty())
+        .unwrap_or(default_server);
+    let port = match account.imap_port {
+        Some(port) => u16::try_from(port)
+            .map_err(|_| ValidationError::OutOfRange(format!("IMAP port {} is not valid", port)))?,
+        None if account.use_ssl => default_port,
+        None => 143,
+    };
+
+    let stream = tokio::time::timeout(std::time::Duration::from_secs(20), TcpStream::connect((server.as_str(), port)))
+        .await
+        .map_err(|_| EmailError::ConnectionFailed(format!("Timed out connecting to {}:{}", server, port)))?
+        .map_err(|e| EmailError::ConnectionFailed(format!("Could not reach {}:{}: {}", server, port, e)))?;
+
+    if account.use_ssl {
+        let mut client = async_imap::Client::new(tls_connect(&server, stream).await?);
+        read_greeting(&mut client).await?;
+        return Ok(client);
+    }
+
+    let mut plain = async_imap::Client::new(stream);
+    read_greeting(&mut plain).await?;
+    plain
+        .run_command_and_check_ok("STARTTLS", None)
+        .await
+        .map_err(|e| EmailError::ConnectionFailed(format!("{} does not support STARTTLS: {}", server, e)))?;
+    // No greeting follows STARTTLS
+    Ok(async_imap::Client::new(tls_connect(&server, plain.into_inner()).await?))
+}
+
+/// Load the UID cursor saved by the account's last sync
+pub fn get_sync_cursor(conn: &Connection, account_id: i64) -> Result<Option<SyncCursor>, CareerBenchError> {
+    let (uid_validity, last_uid): (Option<u32>, Option<u32>) = conn.query_row(
+        "SELECT imap_uid_validity, imap_last_uid FROM email_accounts WHERE id = ?",
+        [account_id],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+    Ok(uid_validity.zip(last_uid).map(|(uid_validity, last_uid)| SyncCursor { uid_validity, last_uid }))
+}
+
+/// The cursor to resume from, if it still applies to a mailbox with `uid_validity`
+fn resume_from(cursor: Option<SyncCursor>, uid_validity: Option<u32>) -> Option<SyncCursor> {
+    cursor.filter(|cursor| Some(cursor.uid_validity) == uid_validity)
+}
+
+/// The UID SEARCH for one sync: every UID after the cursor while the mailbox's
+/// UIDVALIDITY is unchanged, otherwise messages received on or after `since`
+pub fn sync_search_query(cursor: Option<SyncCursor>, uid_validity: Option<u32>, since: NaiveDate) -> String {
+    match resume_from(cursor, uid_validity) {
+        Some(cursor) => format!("UID {}:*", cursor.last_uid.saturating_add(1)),
+        None => format!("SINCE {}", since.format("%d-%b-%Y")),
+    }
+}
+
+/// The UIDs to fetch in one sync: those after `after`, oldest `MAX_MESSAGES_PER_SYNC`
+/// first, so no message is skipped when more arrived than one sync takes. (`n:*` still
+/// matches the newest message when nothing is newer than `n`, hence the filter.) The
+/// flag is true when some were left for the next sync.
+pub fn uids_for_sync(mut uids: Vec<u32>, after: Option<u32>) -> (Vec<u32>, bool) {
+    uids.retain(|uid| after.is_none_or(|after| *uid > after));
+    uids.sort_unstable();
+    let truncated = uids.len() > MAX_MESSAGES_PER_SYNC;
+    uids.truncate(MAX_MESSAGES_PER_SYNC);
+    (uids, truncated)
+}
+
+/// The cursor to save after fetching `fetched_uids` from a mailbox with `uid_validity`.
+/// None when the server doesn't report UIDVALIDITY, or nothing has been fetched yet.
+pub fn next_sync_cursor(previous: Option<SyncCursor>, uid_validity: Option<u32>, fetched_uids: &[u32]) -> Option<SyncCursor> {
+    let uid_validity = uid_validity?;
+    let previous_uid = resume_from(previous, Some(uid_validity)).map(|cursor| cursor.last_uid);
+    let last_uid = fetched_uids.iter().copied().max().max(previous_uid)?;
+    Some(SyncCursor { uid_validity, last_uid })
+}
+
+/// The `last_sync_at` to record after a sync. When messages were left on the server it
+/// is the newest server arrival time among those fetched, so a date-based sync continues
+/// from there; otherwise, and never later than, `now`.
+pub fn next_sync_at(newest_arrival: Option<NaiveDateTime>, truncated: bool, now: DateTime<Utc>) -> String {
+    match newest_arrival {
+        Some(newest) if truncated => Utc.from_utc_datetime(&newest).min(now).to_rfc3339(),
+        _ => now.to_rfc3339(),
+    }
+}
+
+/// Fetch new INBOX messages, at most `MAX_MESSAGES_PER_SYNC` of them: those after
+/// `cursor` when it still applies, otherwise those received on or after `since`
+pub async fn fetch_new_emails(
+    account: &EmailAccount,
+    password: &str,
+    cursor: Option<SyncCursor>,
+    since: NaiveDate,
+) -> Result<FetchOutcome, CareerBenchError> {
+    let client = connect(account).await?;
+    let mut session = client
+        .login(&account.email_address, password)
+        .await
+        .map_err(|(e, _)| EmailError::AuthenticationFailed(e.to_string()))?;
+    let uid_validity = session.select("INBOX").await.map_err(imap_error)?.uid_validity;
+
+    let (uids, truncated) = uids_for_sync(
+        session
+            .uid_search(sync_search_query(cursor, uid_validity, since))
+            .await
+            .map_err(imap_error)?
+            .into_iter()
+            .collect(),
+        resume_from(cursor, uid_validity).map(|cursor| cursor.last_uid),
+    );
+
+    let mut emails = Vec::new();
+    let mut newest_arrival = None;
+    // Parsed in place so the raw fetches are dropped before logging out
+    if !uids.is_empty() {
+        let uid_set = uids.iter().map(|uid| uid.to_string()).collect::<Vec<_>>().join(",");
+        // BODY.PEEK leaves the messages unread on the server
+        let fetches: Vec<_> = session
+            .uid_fetch(&uid_set, "(INTERNALDATE BODY.PEEK[])")
+            .await
+            .map_err(imap_error)?
+            .try_collect()
+            .await
+            .map_err(imap_error)?;
+        for fetch in &fetches {
+            newest_arrival = newest_arrival.max(fetch.internal_date().map(|date| date.naive_utc()));
+            let Some(raw) = fetch.body() else { continue };
+            match parse_fetched_email(raw, fetch.internal_date()) {
+                Ok(email) => emails.push(email),
+                Err(e) => log::warn!("[EmailSync] Skipping message {:?}: {}", fetch.uid, e),
+            }
+        }
+    }
+
+    if let Err(e) = session.logout().await {
+        log::warn!("[EmailSync] Logout from {} failed: {}", account.email_address, e);
+    }
+    Ok(FetchOutcome {
+        emails,
+        truncated,
+        cursor: next_sync_cursor(cursor, uid_validity, &uids),
+        newest_arrival,
+    })
+}
+
+/// Fetch new mail for an account and store it, record the sync time and link the
+/// new threads to applications where the match is clear
+pub async fn sync_account(account_id: i64) -> Result<EmailSyncResult, CareerBenchError> {
+    let account = {
+        let conn = get_connection()?;
+        get_email_account(&conn, account_id)?
+    };
+    if !account.is_active {
+        return Err(ValidationError::BusinessRule(format!("{} is not active", account.email_address)).into());
+    }
+    let password = crate::secure_storage::get_secret(&password_key(account_id))
+        .map_err(ConfigurationError::Other)?
+        .filter(|password| !password.is_empty())
+        .ok_or_else(|| EmailError::MissingCredentials(account.email_address.clone()))?;
+
+    let cursor = {
+        let conn = get_connection()?;
+        get_sync_cursor(&conn, account_id)?
+    };
+    let now = Utc::now();
+    let since = sync_since(account.last_sync_at.as_deref(), now);
+    let outcome = fetch_new_emails(&account, &password, cursor, since).await?;
+    let emails = &outcome.emails;
+    if outcome.truncated {
+        log::info!(
+            "[EmailSync] {}: more than {} messages waiting, the rest follow on the next sync",
+            account.email_address,
+            MAX_MESSAGES_PER_SYNC
+        );
+    }
+
+    let conn = get_connection()?;
+    let new_messages = store_fetched_emails(&conn, account_id, emails)?;
+    conn.execute(
+        "UPDATE email_accounts
+         SET last_sync_at = ?, imap_uid_validity = ?, imap_last_uid = ?, updated_at = datetime('now')
+         WHERE id = ?",
+        rusqlite::params![
+            next_sync_at(outcome.newest_arrival, outcome.truncated, now),
+            outcome.cursor.map(|cursor| cursor.uid_validity),
+            outcome.cursor.map(|cursor| cursor.last_uid),
+            account_id
+        ],
+    )?;
+    let links = auto_link_threads(&conn)?;
+    log::info!(
+        "[EmailSync] {}: {} fetched, {} new, {} threads linked",
+        account.email_address,
+        emails.len(),
+        new_messages,
+        links.linked.len()
+    );
+
+    Ok(EmailSyncResult {
+        new_messages,
+        linked: links.linked,
+        unlinked: links.unlinked,
+    })
+}
@@ -57,6 +57,7 @@ impl ErrorMetrics {
             CareerBenchError::Validation(_) => "Validation",
             CareerBenchError::Configuration(_) => "Configuration",
             CareerBenchError::FileSystem(_) => "FileSystem",
+            CareerBenchError::Email(_) => "Email",
             CareerBenchError::Application(_) => "Application",
         };
         
@@ -80,6 +81,7 @@ impl ErrorMetrics {
             CareerBenchError::Validation(_) => true,
             CareerBenchError::Configuration(_) => false,
             CareerBenchError::FileSystem(_) => false,
+            CareerBenchError::Email(e) => matches!(e, crate::errors::EmailError::ConnectionFailed(_)),
             CareerBenchError::Application(_) => true,
         };
         
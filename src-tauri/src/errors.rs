@@ -22,6 +22,8 @@ pub enum CareerBenchError {
     Configuration(ConfigurationError),
     /// File system errors
     FileSystem(FileSystemError),
+    /// Email (IMAP) errors
+    Email(EmailError),
     /// General application errors
     Application(String),
 }
@@ -41,6 +43,8 @@ pub enum DatabaseError {
     NotFound(String),
     /// Invalid data format
     InvalidData(String),
+    /// Record was modified after the client last read it
+    Conflict(String),
 }
 
 /// AI provider errors (re-exported from ai::errors)
@@ -76,6 +80,19 @@ pub enum ConfigurationError {
     Other(String),
 }
 
+/// Email (IMAP) errors
+#[derive(Debug, Clone)]
+pub enum EmailError {
+    /// Mail server could not be reached or the TLS handshake failed
+    ConnectionFailed(String),
+    /// Server rejected the login
+    AuthenticationFailed(String),
+    /// No password saved for the account
+    MissingCredentials(String),
+    /// Unexpected response from the server
+    Protocol(String),
+}
+
 /// File system errors
 #[derive(Debug, Clone)]
 pub enum FileSystemError {
@@ -97,6 +114,7 @@ impl fmt::Display for CareerBenchError {
             CareerBenchError::Validation(e) => write!(f, "Validation error: {}", e),
             CareerBenchError::Configuration(e) => write!(f, "Configuration error: {}", e),
             CareerBenchError::FileSystem(e) => write!(f, "File system error: {}", e),
+            CareerBenchError::Email(e) => write!(f, "Email error: {}", e),
             CareerBenchError::Application(msg) => write!(f, "Application error: {}", msg),
         }
     }
@@ -111,6 +129,7 @@ impl fmt::Display for DatabaseError {
             DatabaseError::ConstraintViolation(msg) => write!(f, "Constraint violation: {}", msg),
             DatabaseError::NotFound(msg) => write!(f, "Record not found: {}", msg),
             DatabaseError::InvalidData(msg) => write!(f, "Invalid data: {}", msg),
+            DatabaseError::Conflict(msg) => write!(f, "Conflict: {}", msg),
         }
     }
 }
@@ -150,6 +169,17 @@ impl fmt::Display for FileSystemError {
     }
 }
 
+impl fmt::Display for EmailError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EmailError::ConnectionFailed(msg) => write!(f, "Mail server connection failed: {}", msg),
+            EmailError::AuthenticationFailed(msg) => write!(f, "Mail server login failed: {}", msg),
+            EmailError::MissingCredentials(account) => write!(f, "No password saved for {}", account),
+            EmailError::Protocol(msg) => write!(f, "IMAP error: {}", msg),
+        }
+    }
+}
+
 impl std::error::Error for CareerBenchError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
@@ -163,6 +193,7 @@ impl std::error::Error for DatabaseError {}
 impl std::error::Error for ValidationError {}
 impl std::error::Error for ConfigurationError {}
 impl std::error::Error for FileSystemError {}
+impl std::error::Error for EmailError {}
 
 // Convenience conversions
 
@@ -196,6 +227,12 @@ impl From<FileSystemError> for CareerBenchError {
     }
 }
 
+impl From<EmailError> for CareerBenchError {
+    fn from(err: EmailError) -> Self {
+        CareerBenchError::Email(err)
+    }
+}
+
 impl From<rusqlite::Error> for DatabaseError {
     fn from(err: rusqlite::Error) -> Self {
         match err {
@@ -267,6 +304,9 @@ pub fn to_user_message(error: &CareerBenchError) -> String {
             DatabaseError::InvalidData(msg) => {
                 format!("Invalid data: {}", msg)
             }
+            DatabaseError::Conflict(msg) => {
+                format!("Conflict: {}. Reload to get the latest version before saving again.", msg)
+            }
         },
         CareerBenchError::AiProvider(e) => {
             // Use the existing error message utility
@@ -320,6 +360,20 @@ pub fn to_user_message(error: &CareerBenchError) -> String {
                 format!("File system error: {}", msg)
             }
         },
+        CareerBenchError::Email(e) => match e {
+            EmailError::ConnectionFailed(msg) => {
+                format!("Couldn't reach the mail server: {}", msg)
+            }
+            EmailError::AuthenticationFailed(_) => {
+                "The mail server rejected the login. Check the address and app password.".to_string()
+            }
+            EmailError::MissingCredentials(account) => {
+                format!("No password saved for {}. Add an app password in Settings.", account)
+            }
+            EmailError::Protocol(msg) => {
+                format!("Email sync failed: {}", msg)
+            }
+        },
         CareerBenchError::Application(msg) => msg.clone(),
     }
 }
@@ -364,6 +418,7 @@ pub fn get_short_error_message(error: &CareerBenchError) -> String {
             DatabaseError::ConstraintViolation(msg) => msg.clone(),
             DatabaseError::NotFound(msg) => msg.clone(),
             DatabaseError::InvalidData(msg) => format!("Invalid data: {}", msg),
+            DatabaseError::Conflict(msg) => format!("Conflict: {}", msg),
         },
         CareerBenchError::AiProvider(e) => {
             crate::ai::error_messages::get_short_error_message(e)
@@ -388,6 +443,12 @@ pub fn get_short_error_message(error: &CareerBenchError) -> String {
             FileSystemError::DiskFull(_) => "Disk full".to_string(),
             FileSystemError::IoError(msg) => msg.clone(),
         },
+        CareerBenchError::Email(e) => match e {
+            EmailError::ConnectionFailed(_) => "Mail server unreachable".to_string(),
+            EmailError::AuthenticationFailed(_) => "Mail server login failed".to_string(),
+            EmailError::MissingCredentials(_) => "Email password not set".to_string(),
+            EmailError::Protocol(msg) => msg.clone(),
+        },
         CareerBenchError::Application(msg) => msg.clone(),
     }
 }
@@ -0,0 +1,58 @@
+//! Removal of duplicate application timeline events
+//!
+//! Double-saves and old migrations left some applications with the same event recorded
+//! more than once. Events are duplicates when application, type, date and from/to status
+//! all match; the earliest-inserted copy is kept.
+
+use crate::errors::CareerBenchError;
+use rusqlite::Connection;
+
+/// Ids of the events that aren't the first of their duplicate group
+const DUPLICATE_EVENTS_SQL: &str = "SELECT e.id, e.application_id FROM application_events e
+     WHERE e.id NOT IN (
+         SELECT MIN(id) FROM application_events
+         GROUP BY application_id, event_type, event_date, from_status, to_status
+     )";
+
+/// Delete duplicate events for one application, or all of them when `application_id`
+/// is None, and recompute `last_activity_date` for the applications that changed.
+///
+/// Returns the number of events removed.
+pub fn dedupe_application_events(conn: &Connection, application_id: Option<i64>) -> Result<u64, CareerBenchError> {
+    let duplicates: Vec<(i64, i64)> = {
+        let (sql, params) = match application_id {
+            Some(id) => (format!("{} AND e.application_id = ?", DUPLICATE_EVENTS_SQL), vec![id]),
+            None => (DUPLICATE_EVENTS_SQL.to_string(), Vec::new()),
+        };
+        let mut stmt = conn.prepare(&sql)?;
+        let rows = stmt.query_map(rusqlite::params_from_iter(params.iter()), |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.collect::<Result<_, _>>()?
+    };
+    if duplicates.is_empty() {
+        return Ok(0);
+    }
+
+    let mut affected_applications: Vec<i64> = duplicates.iter().map(|(_, application_id)| *application_id).collect();
+    affected_applications.sort_unstable();
+    affected_applications.dedup();
+
+    let tx = conn.unchecked_transaction()?;
+    let mut removed = 0u64;
+    for (event_id, _) in &duplicates {
+        removed += tx.execute("DELETE FROM application_events WHERE id = ?", [event_id])? as u64;
+    }
+    for application_id in &affected_applications {
+        tx.execute(
+            "UPDATE applications
+             SET last_activity_date = COALESCE(
+                 (SELECT MAX(event_date) FROM application_events WHERE application_id = ?1),
+                 last_activity_date
+             )
+             WHERE id = ?1",
+            [application_id],
+        )?;
+    }
+    tx.commit()?;
+
+    Ok(removed)
+}
@@ -0,0 +1,63 @@
+//! What a resume or cover letter generation runs against
+//!
+//! Commands use the app database and the provider configured for each AI purpose.
+//! Tests build a context from their own database and a mock provider, so the real
+//! generation path (caches included) runs without touching the app's data.
+
+use crate::ai::provider::AiProvider;
+use crate::ai::resolver::ResolvedProvider;
+use crate::db::get_connection;
+use rusqlite::Connection;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+pub struct GenerationContext {
+    conn: Mutex<Connection>,
+    /// Provider and model name used for every purpose, instead of the configured ones
+    provider: Option<(Arc<dyn AiProvider>, String)>,
+}
+
+impl GenerationContext {
+    /// The app database and configured providers
+    pub fn app() -> Result<Self, String> {
+        let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+            provider: None,
+        })
+    }
+
+    /// `conn` and `provider` for everything; `model_name` is recorded with cached results
+    #[allow(dead_code)]
+    pub fn with_provider(conn: Connection, provider: Arc<dyn AiProvider>, model_name: &str) -> Self {
+        Self {
+            conn: Mutex::new(conn),
+            provider: Some((provider, model_name.to_string())),
+        }
+    }
+
+    /// The database connection. Don't hold it across an `.await`.
+    pub fn conn(&self) -> MutexGuard<'_, Connection> {
+        self.conn.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// The provider to call for `purpose`
+    pub fn provider_for(&self, purpose: &str) -> Result<Arc<dyn AiProvider>, String> {
+        match &self.provider {
+            Some((provider, _)) => Ok(provider.clone()),
+            None => ResolvedProvider::resolve_for(purpose)
+                .map(|resolved| resolved.as_provider())
+                .map_err(|e| format!("Failed to resolve provider: {}", e)),
+        }
+    }
+
+    /// The model name to record with a cached result for `purpose`
+    pub fn model_for(&self, purpose: &str) -> String {
+        match &self.provider {
+            Some((_, model_name)) => model_name.clone(),
+            None => crate::ai::settings::load_ai_settings()
+                .ok()
+                .and_then(|s| s.model_for(purpose))
+                .unwrap_or_else(|| "unknown-model".to_string()),
+        }
+    }
+}
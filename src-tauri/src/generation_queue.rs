@@ -0,0 +1,220 @@
+//! Queue of resume and cover letter generations to retry when the AI provider is unreachable
+//!
+//! Failed items are retried automatically with exponential backoff. After the configured
+//! number of attempts an item is marked `dead`: automatic processing skips it, but it can
+//! still be retried by hand.
+
+use crate::app_settings::{get_setting, set_setting};
+use crate::errors::{CareerBenchError, DatabaseError, ValidationError};
+use chrono::{DateTime, Duration, Utc};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+
+pub const GENERATION_MAX_ATTEMPTS_SETTING: &str = "generation_max_attempts";
+pub const DEFAULT_GENERATION_MAX_ATTEMPTS: u32 = 5;
+/// Delay before the first automatic retry; doubles with each further failure
+const BASE_RETRY_DELAY_MINUTES: i64 = 1;
+/// Backoff never waits longer than this
+const MAX_RETRY_DELAY_MINUTES: i64 = 6 * 60;
+
+pub const GENERATION_KINDS: &[&str] = &["resume", "cover_letter"];
+
+pub const STATUS_QUEUED: &str = "queued";
+pub const STATUS_FAILED: &str = "failed";
+pub const STATUS_DEAD: &str = "dead";
+pub const STATUS_COMPLETED: &str = "completed";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingGeneration {
+    pub id: i64,
+    /// "resume" or "cover_letter"
+    pub kind: String,
+    pub job_id: i64,
+    pub application_id: Option<i64>,
+    /// Serialized `GenerationOptions`, if any
+    pub options_json: Option<String>,
+    /// queued, failed, dead or completed
+    pub status: String,
+    pub attempts: u32,
+    pub last_error: Option<String>,
+    /// When automatic processing may try again; None for dead or completed items
+    pub next_attempt_at: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+const SELECT_COLUMNS: &str = "id, kind, job_id, application_id, options_json, status, attempts, last_error, next_attempt_at, created_at, updated_at";
+
+fn map_pending(row: &rusqlite::Row) -> rusqlite::Result<PendingGeneration> {
+    Ok(PendingGeneration {
+        id: row.get(0)?,
+        kind: row.get(1)?,
+        job_id: row.get(2)?,
+        application_id: row.get(3)?,
+        options_json: row.get(4)?,
+        status: row.get(5)?,
+        attempts: row.get(6)?,
+        last_error: row.get(7)?,
+        next_attempt_at: row.get(8)?,
+        created_at: row.get(9)?,
+        updated_at: row.get(10)?,
+    })
+}
+
+pub fn load_generation_max_attempts(conn: &Connection) -> Result<u32, CareerBenchError> {
+    Ok(get_setting(conn, GENERATION_MAX_ATTEMPTS_SETTING)?
+        .and_then(|value| value.parse().ok())
+        .filter(|attempts| *attempts > 0)
+        .unwrap_or(DEFAULT_GENERATION_MAX_ATTEMPTS))
+}
+
+pub fn save_generation_max_attempts(conn: &Connection, max_attempts: u32) -> Result<(), CareerBenchError> {
+    if max_attempts == 0 {
+        return Err(ValidationError::OutOfRange("Max attempts must be at least 1".to_string()).into());
+    }
+    set_setting(conn, GENERATION_MAX_ATTEMPTS_SETTING, &max_attempts.to_string())
+}
+
+/// Delay before the next automatic retry after `attempts` failures
+pub fn retry_delay(attempts: u32) -> Duration {
+    let exponent = attempts.saturating_sub(1).min(16);
+    let minutes = (BASE_RETRY_DELAY_MINUTES << exponent).min(MAX_RETRY_DELAY_MINUTES);
+    Duration::minutes(minutes)
+}
+
+/// Queue a generation for (re)trying later; returns the queue item id
+pub fn enqueue_generation(
+    conn: &Connection,
+    kind: &str,
+    job_id: i64,
+    application_id: Option<i64>,
+    options_json: Option<&str>,
+    now: DateTime<Utc>,
+) -> Result<i64, CareerBenchError> {
+    if !GENERATION_KINDS.contains(&kind) {
+        return Err(ValidationError::InvalidFormat(format!(
+            "Unknown generation kind '{}'. Expected one of: {}",
+            kind,
+            GENERATION_KINDS.join(", ")
+        ))
+        .into());
+    }
+
+    let now = now.to_rfc3339();
+    conn.execute(
+        "INSERT INTO pending_generations (kind, job_id, application_id, options_json, status, attempts, next_attempt_at, created_at, updated_at)
+         VALUES (?, ?, ?, ?, ?, 0, ?, ?, ?)",
+        rusqlite::params![kind, job_id, application_id, options_json, STATUS_QUEUED, now, now, now],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+pub fn get_pending_generation(conn: &Connection, id: i64) -> Result<PendingGeneration, CareerBenchError> {
+    match conn.query_row(
+        &format!("SELECT {} FROM pending_generations WHERE id = ?", SELECT_COLUMNS),
+        [id],
+        map_pending,
+    ) {
+        Ok(item) => Ok(item),
+        Err(rusqlite::Error::QueryReturnedNoRows) => {
+            Err(DatabaseError::NotFound(format!("Pending generation {} not found", id)).into())
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Queued, failed and dead items, oldest first
+pub fn get_pending_generations(conn: &Connection) -> Result<Vec<PendingGeneration>, CareerBenchError> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM pending_generations WHERE status != ? ORDER BY created_at ASC, id ASC",
+        SELECT_COLUMNS
+    ))?;
+    let rows = stmt.query_map([STATUS_COMPLETED], map_pending)?;
+
+    let mut items = Vec::new();
+    for row_result in rows {
+        items.push(row_result?);
+    }
+    Ok(items)
+}
+
+/// Items automatic processing should try now: queued or failed, past their backoff, never dead
+pub fn get_due_generations(conn: &Connection, now: DateTime<Utc>) -> Result<Vec<PendingGeneration>, CareerBenchError> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM pending_generations
+         WHERE status IN (?, ?) AND (next_attempt_at IS NULL OR next_attempt_at <= ?)
+         ORDER BY created_at ASC, id ASC",
+        SELECT_COLUMNS
+    ))?;
+    let rows = stmt.query_map(
+        rusqlite::params![STATUS_QUEUED, STATUS_FAILED, now.to_rfc3339()],
+        map_pending,
+    )?;
+
+    let mut items = Vec::new();
+    for row_result in rows {
+        items.push(row_result?);
+    }
+    Ok(items)
+}
+
+/// Record a failed attempt: schedules the next retry, or marks the item dead once it
+/// has used up `max_attempts`
+pub fn record_generation_failure(
+    conn: &Connection,
+    id: i64,
+    error: &str,
+    max_attempts: u32,
+    now: DateTime<Utc>,
+) -> Result<PendingGeneration, CareerBenchError> {
+    let item = get_pending_generation(conn, id)?;
+    let attempts = item.attempts + 1;
+    let (status, next_attempt_at) = if attempts >= max_attempts {
+        (STATUS_DEAD, None)
+    } else {
+        (STATUS_FAILED, Some((now + retry_delay(attempts)).to_rfc3339()))
+    };
+
+    conn.execute(
+        "UPDATE pending_generations SET status = ?, attempts = ?, last_error = ?, next_attempt_at = ?, updated_at = ? WHERE id = ?",
+        rusqlite::params![status, attempts, error, next_attempt_at, now.to_rfc3339(), id],
+    )?;
+    get_pending_generation(conn, id)
+}
+
+pub fn record_generation_success(conn: &Connection, id: i64, now: DateTime<Utc>) -> Result<PendingGeneration, CareerBenchError> {
+    conn.execute(
+        "UPDATE pending_generations SET status = ?, attempts = attempts + 1, last_error = NULL, next_attempt_at = NULL, updated_at = ? WHERE id = ?",
+        rusqlite::params![STATUS_COMPLETED, now.to_rfc3339(), id],
+    )?;
+    get_pending_generation(conn, id)
+}
+
+/// Make an item (including a dead one) due immediately, keeping its attempt history
+pub fn reset_generation_for_retry(conn: &Connection, id: i64, now: DateTime<Utc>) -> Result<PendingGeneration, CareerBenchError> {
+    let item = get_pending_generation(conn, id)?;
+    if item.status == STATUS_COMPLETED {
+        return Err(ValidationError::BusinessRule(format!("Generation {} already completed", id)).into());
+    }
+
+    let now = now.to_rfc3339();
+    conn.execute(
+        "UPDATE pending_generations SET status = ?, next_attempt_at = ?, updated_at = ? WHERE id = ?",
+        rusqlite::params![STATUS_QUEUED, now, now, id],
+    )?;
+    get_pending_generation(conn, id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_delay_backs_off_and_caps() {
+        assert_eq!(retry_delay(1), Duration::minutes(1));
+        assert_eq!(retry_delay(2), Duration::minutes(2));
+        assert_eq!(retry_delay(4), Duration::minutes(8));
+        assert_eq!(retry_delay(30), Duration::minutes(MAX_RETRY_DELAY_MINUTES));
+    }
+}
@@ -0,0 +1,309 @@
+//! iCalendar (RFC 5545) output for exporting events to other calendars
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, Offset, TimeZone, Utc};
+use chrono_tz::{OffsetComponents, OffsetName, Tz};
+use crate::errors::{CareerBenchError, ValidationError};
+
+const PRODID_LINE: &str = "PRODID:-//CareerBench//Interview Calendar//EN";
+/// Content lines longer than this many octets are folded
+const MAX_LINE_OCTETS: usize = 75;
+
+/// When an event starts or ends
+#[derive(Debug, Clone, PartialEq)]
+pub enum IcsTime {
+    /// An instant, written in UTC with a trailing Z
+    Utc(DateTime<Utc>),
+    /// Wall-clock time in whatever zone the importing calendar uses
+    Floating(NaiveDateTime),
+    /// An all-day event's day
+    Date(NaiveDate),
+    /// Wall-clock time in an IANA timezone, written with a TZID and a VTIMEZONE block
+    Zoned(NaiveDateTime, Tz),
+}
+
+impl IcsTime {
+    /// Parse a stored date or timestamp: RFC 3339 becomes UTC, a date-time without
+    /// an offset stays floating, and a bare date is an all-day event
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+            return Some(IcsTime::Utc(dt.with_timezone(&Utc)));
+        }
+        for format in ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%dT%H:%M", "%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M"] {
+            if let Ok(dt) = NaiveDateTime::parse_from_str(value, format) {
+                return Some(IcsTime::Floating(dt));
+            }
+        }
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").ok().map(IcsTime::Date)
+    }
+
+    /// The default end: an hour after a timed start, or the next day for an all-day event
+    pub fn default_end(&self) -> Self {
+        match self {
+            IcsTime::Utc(dt) => IcsTime::Utc(*dt + Duration::hours(1)),
+            IcsTime::Floating(dt) => IcsTime::Floating(*dt + Duration::hours(1)),
+            IcsTime::Date(day) => IcsTime::Date(*day + Duration::days(1)),
+            IcsTime::Zoned(dt, tz) => IcsTime::Zoned(*dt + Duration::hours(1), *tz),
+        }
+    }
+
+    fn zone(&self) -> Option<(Tz, i32)> {
+        match self {
+            IcsTime::Zoned(dt, tz) => Some((*tz, dt.year())),
+            _ => None,
+        }
+    }
+
+    /// The property with its parameters and value, e.g. `DTSTART;VALUE=DATE:20250304`
+    fn property(&self, name: &str) -> String {
+        match self {
+            IcsTime::Utc(dt) => format!("{}:{}", name, dt.format("%Y%m%dT%H%M%SZ")),
+            IcsTime::Floating(dt) => format!("{}:{}", name, dt.format("%Y%m%dT%H%M%S")),
+            IcsTime::Date(day) => format!("{};VALUE=DATE:{}", name, day.format("%Y%m%d")),
+            IcsTime::Zoned(dt, tz) => format!("{};TZID={}:{}", name, tz.name(), dt.format("%Y%m%dT%H%M%S")),
+        }
+    }
+}
+
+/// Parse an IANA timezone name such as "Europe/London"
+pub fn parse_timezone(name: &str) -> Result<Tz, CareerBenchError> {
+    name.trim().parse::<Tz>().map_err(|_| {
+        ValidationError::InvalidFormat(format!("Unknown timezone '{}'. Use an IANA name such as America/New_York", name)).into()
+    })
+}
+
+/// UTC offset in ICS form, e.g. `-0500` or `+0530`
+fn format_offset(seconds: i32) -> String {
+    let sign = if seconds < 0 { '-' } else { '+' };
+    let seconds = seconds.abs();
+    let (hours, minutes, rest) = (seconds / 3600, seconds % 3600 / 60, seconds % 60);
+    if rest == 0 {
+        format!("{}{:02}{:02}", sign, hours, minutes)
+    } else {
+        format!("{}{:02}{:02}{:02}", sign, hours, minutes, rest)
+    }
+}
+
+/// Offset in effect at `instant`: total seconds from UTC, whether it is daylight time, and its abbreviation
+fn offset_at(tz: Tz, instant: NaiveDateTime) -> (i32, bool, String) {
+    let offset = tz.offset_from_utc_datetime(&instant);
+    (
+        offset.fix().local_minus_utc(),
+        !offset.dst_offset().is_zero(),
+        offset.abbreviation().to_string(),
+    )
+}
+
+/// A VTIMEZONE for `tz` covering `first_year..=last_year`: the observance in effect on
+/// 1 January of the first year, then one observance per transition found. Transitions
+/// are found hour by hour and then narrowed to the minute.
+fn vtimezone_lines(tz: Tz, first_year: i32, last_year: i32) -> Vec<String> {
+    let year_start = |year: i32| NaiveDate::from_ymd_opt(year, 1, 1).and_then(|d| d.and_hms_opt(0, 0, 0));
+    let (Some(start), Some(end)) = (year_start(first_year), year_start(last_year + 1)) else {
+        return Vec::new();
+    };
+
+    let observance = |lines: &mut Vec<String>, onset_utc: NaiveDateTime, from: i32| {
+        let (to, is_daylight, abbreviation) = offset_at(tz, onset_utc);
+        let kind = if is_daylight { "DAYLIGHT" } else { "STANDARD" };
+        lines.push(format!("BEGIN:{}", kind));
+        lines.push(format!("DTSTART:{}", (onset_utc + Duration::seconds(from as i64)).format("%Y%m%dT%H%M%S")));
+        lines.push(format!("TZOFFSETFROM:{}", format_offset(from)));
+        lines.push(format!("TZOFFSETTO:{}", format_offset(to)));
+        lines.push(format!("TZNAME:{}", abbreviation));
+        lines.push(format!("END:{}", kind));
+    };
+
+    let mut lines = vec!["BEGIN:VTIMEZONE".to_string(), format!("TZID:{}", tz.name())];
+    let initial = offset_at(tz, start).0;
+    observance(&mut lines, start - Duration::seconds(initial as i64), initial);
+
+    let mut previous = offset_at(tz, start);
+    let mut hour = start;
+    while hour < end {
+        let next_hour = hour + Duration::hours(1);
+        let current = offset_at(tz, next_hour);
+        if current != previous {
+            let onset = (1..=60)
+                .map(|minute| hour + Duration::minutes(minute))
+                .find(|instant| offset_at(tz, *instant) != previous)
+                .unwrap_or(next_hour);
+            observance(&mut lines, onset, previous.0);
+            previous = current;
+        }
+        hour = next_hour;
+    }
+
+    lines.push("END:VTIMEZONE".to_string());
+    lines
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct IcsEvent {
+    /// Stable across exports, so re-importing updates the event instead of duplicating it
+    pub uid: String,
+    pub start: IcsTime,
+    pub end: IcsTime,
+    pub summary: String,
+    pub location: Option<String>,
+    pub description: Option<String>,
+    /// A validated RRULE value, without the `RRULE:` prefix
+    pub recurrence: Option<String>,
+    /// Add a display alarm this many minutes before the start
+    pub alarm_minutes_before: Option<u32>,
+}
+
+/// Escape a TEXT value: backslashes, semicolons, commas and newlines
+pub fn escape_text(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Fold a content line at 75 octets, continuing on lines that start with a space
+fn push_line(output: &mut String, line: &str) {
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > MAX_LINE_OCTETS {
+            output.push_str("\r\n ");
+            // The leading space counts towards the continuation line's length
+            width = 1;
+        }
+        output.push(c);
+        width += c.len_utf8();
+    }
+    output.push_str("\r\n");
+}
+
+/// A VCALENDAR holding `events`, stamped with `now`
+pub fn render_calendar(events: &[IcsEvent], now: DateTime<Utc>) -> String {
+    let mut output = String::new();
+    let stamp = now.format("%Y%m%dT%H%M%SZ").to_string();
+    for line in ["BEGIN:VCALENDAR", "VERSION:2.0", PRODID_LINE, "CALSCALE:GREGORIAN"] {
+        push_line(&mut output, line);
+    }
+
+    // One VTIMEZONE per zone used, spanning the years its events start in plus the
+    // next one, so a recurring series crossing New Year still resolves
+    let mut zones: Vec<(Tz, i32, i32)> = Vec::new();
+    for (tz, year) in events.iter().flat_map(|e| [e.start.zone(), e.end.zone()]).flatten() {
+        match zones.iter_mut().find(|(zone, _, _)| *zone == tz) {
+            Some((_, first, last)) => {
+                *first = (*first).min(year);
+                *last = (*last).max(year + 1);
+            }
+            None => zones.push((tz, year, year + 1)),
+        }
+    }
+    for (tz, first_year, last_year) in zones {
+        for line in vtimezone_lines(tz, first_year, last_year) {
+            push_line(&mut output, &line);
+        }
+    }
+
+    for event in events {
+        push_line(&mut output, "BEGIN:VEVENT");
+        push_line(&mut output, &format!("UID:{}", event.uid));
+        push_line(&mut output, &format!("DTSTAMP:{}", stamp));
+        push_line(&mut output, &event.start.property("DTSTART"));
+        push_line(&mut output, &event.end.property("DTEND"));
+        push_line(&mut output, &format!("SUMMARY:{}", escape_text(&event.summary)));
+        if let Some(location) = &event.location {
+            push_line(&mut output, &format!("LOCATION:{}", escape_text(location)));
+        }
+        if let Some(description) = &event.description {
+            push_line(&mut output, &format!("DESCRIPTION:{}", escape_text(description)));
+        }
+        if let Some(rule) = &event.recurrence {
+            push_line(&mut output, &format!("RRULE:{}", rule));
+        }
+        push_line(&mut output, "STATUS:CONFIRMED");
+        if let Some(minutes) = event.alarm_minutes_before {
+            push_line(&mut output, "BEGIN:VALARM");
+            push_line(&mut output, "ACTION:DISPLAY");
+            push_line(&mut output, &format!("DESCRIPTION:{}", escape_text(&event.summary)));
+            push_line(&mut output, &format!("TRIGGER:-PT{}M", minutes));
+            push_line(&mut output, "END:VALARM");
+        }
+        push_line(&mut output, "END:VEVENT");
+    }
+
+    push_line(&mut output, "END:VCALENDAR");
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_stored_times() {
+        let utc = IcsTime::parse("2025-03-04T10:30:00-05:00").unwrap();
+        assert_eq!(utc.property("DTSTART"), "DTSTART:20250304T153000Z");
+        assert_eq!(IcsTime::parse("2025-03-04T10:30").unwrap().property("DTSTART"), "DTSTART:20250304T103000");
+        let day = IcsTime::parse("2025-03-04").unwrap();
+        assert_eq!(day.default_end().property("DTEND"), "DTEND;VALUE=DATE:20250305");
+        assert_eq!(IcsTime::parse("next week"), None);
+    }
+
+    #[test]
+    fn test_zoned_times_get_a_vtimezone() {
+        let tz = parse_timezone("America/New_York").unwrap();
+        assert!(parse_timezone("Mars/Olympus").is_err());
+
+        let start = IcsTime::Zoned(NaiveDate::from_ymd_opt(2025, 3, 4).unwrap().and_hms_opt(10, 0, 0).unwrap(), tz);
+        assert_eq!(start.property("DTSTART"), "DTSTART;TZID=America/New_York:20250304T100000");
+
+        let lines = vtimezone_lines(tz, 2025, 2025).join("\n");
+        assert_eq!(lines.matches("BEGIN:DAYLIGHT").count(), 1);
+        assert_eq!(lines.matches("BEGIN:STANDARD").count(), 2);
+        assert!(lines.contains("BEGIN:STANDARD\nDTSTART:20250101T000000\nTZOFFSETFROM:-0500\nTZOFFSETTO:-0500\nTZNAME:EST"));
+        assert!(lines.contains("BEGIN:DAYLIGHT\nDTSTART:20250309T020000\nTZOFFSETFROM:-0500\nTZOFFSETTO:-0400\nTZNAME:EDT"));
+        assert!(lines.contains("BEGIN:STANDARD\nDTSTART:20251102T020000\nTZOFFSETFROM:-0400\nTZOFFSETTO:-0500"));
+
+        // Zones without daylight saving have just the one observance
+        let tokyo = vtimezone_lines(parse_timezone("Asia/Tokyo").unwrap(), 2025, 2025).join("\n");
+        assert!(tokyo.contains("TZOFFSETFROM:+0900\nTZOFFSETTO:+0900"));
+        assert_eq!(tokyo.matches("DTSTART").count(), 1);
+    }
+
+    #[test]
+    fn test_text_escaping_and_folding() {
+        assert_eq!(escape_text("Onsite; bring laptop, ID\nRoom 4\\B"), "Onsite\\; bring laptop\\, ID\\nRoom 4\\\\B");
+
+        let mut output = String::new();
+        push_line(&mut output, &format!("DESCRIPTION:{}", "x".repeat(100)));
+        let lines: Vec<&str> = output.trim_end().split("\r\n").collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].len(), 75);
+        assert!(lines[1].starts_with(' '));
+    }
+
+    #[test]
+    fn test_render_calendar_with_alarm() {
+        let now = Utc.with_ymd_and_hms(2025, 3, 1, 12, 0, 0).unwrap();
+        let start = IcsTime::parse("2025-03-04T15:00:00Z").unwrap();
+        let event = IcsEvent {
+            uid: "careerbench-interview-1-2".to_string(),
+            end: start.default_end(),
+            start,
+            summary: "Interview at Acme".to_string(),
+            location: None,
+            description: None,
+            recurrence: Some("FREQ=WEEKLY;COUNT=4".to_string()),
+            alarm_minutes_before: Some(15),
+        };
+        let ics = render_calendar(&[event.clone(), IcsEvent { uid: "other".to_string(), alarm_minutes_before: None, ..event }], now);
+
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 2);
+        assert_eq!(ics.matches("BEGIN:VALARM").count(), 1);
+        assert!(ics.contains("DTSTAMP:20250301T120000Z\r\nDTSTART:20250304T150000Z\r\nDTEND:20250304T160000Z\r\n"));
+        assert!(ics.contains("RRULE:FREQ=WEEKLY;COUNT=4\r\n"));
+        assert!(ics.contains("TRIGGER:-PT15M\r\n"));
+    }
+}
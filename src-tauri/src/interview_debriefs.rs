@@ -0,0 +1,118 @@
+//! Structured self-debriefs captured after interview rounds
+
+use crate::errors::{CareerBenchError, DatabaseError, ValidationError};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+
+pub const MIN_DEBRIEF_RATING: i32 = 1;
+pub const MAX_DEBRIEF_RATING: i32 = 5;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct InterviewDebrief {
+    pub id: i64,
+    pub application_id: i64,
+    pub round: String,
+    pub went_well: String,
+    pub to_improve: String,
+    /// Self-rating, 1 (went badly) to 5 (went great)
+    pub rating: i32,
+    pub created_at: String,
+}
+
+fn map_debrief(row: &rusqlite::Row) -> rusqlite::Result<InterviewDebrief> {
+    Ok(InterviewDebrief {
+        id: row.get(0)?,
+        application_id: row.get(1)?,
+        round: row.get(2)?,
+        went_well: row.get(3)?,
+        to_improve: row.get(4)?,
+        rating: row.get(5)?,
+        created_at: row.get(6)?,
+    })
+}
+
+/// Save a debrief for an interview round; returns the new debrief id
+pub fn save_interview_debrief(
+    conn: &Connection,
+    application_id: i64,
+    round: &str,
+    went_well: &str,
+    to_improve: &str,
+    rating: i32,
+    now: &str,
+) -> Result<i64, CareerBenchError> {
+    if !(MIN_DEBRIEF_RATING..=MAX_DEBRIEF_RATING).contains(&rating) {
+        return Err(ValidationError::OutOfRange(format!(
+            "Rating must be between {} and {}, got {}",
+            MIN_DEBRIEF_RATING, MAX_DEBRIEF_RATING, rating
+        ))
+        .into());
+    }
+    if round.trim().is_empty() {
+        return Err(ValidationError::MissingField("round".to_string()).into());
+    }
+
+    let exists: bool = conn.query_row(
+        "SELECT COUNT(*) FROM applications WHERE id = ?",
+        [application_id],
+        |row| Ok(row.get::<_, i64>(0)? > 0),
+    )?;
+    if !exists {
+        return Err(DatabaseError::NotFound(format!("Application {} not found", application_id)).into());
+    }
+
+    conn.execute(
+        "INSERT INTO interview_debriefs (application_id, round, went_well, to_improve, rating, created_at)
+         VALUES (?, ?, ?, ?, ?, ?)",
+        rusqlite::params![application_id, round.trim(), went_well, to_improve, rating, now],
+    )?;
+
+    Ok(conn.last_insert_rowid())
+}
+
+/// Debriefs for an application, most recent first
+pub fn get_debriefs_for_application(
+    conn: &Connection,
+    application_id: i64,
+) -> Result<Vec<InterviewDebrief>, CareerBenchError> {
+    let mut stmt = conn.prepare(
+        "SELECT id, application_id, round, went_well, to_improve, rating, created_at
+         FROM interview_debriefs
+         WHERE application_id = ?
+         ORDER BY created_at DESC, id DESC",
+    )?;
+    let rows = stmt.query_map([application_id], map_debrief)?;
+
+    let mut debriefs = Vec::new();
+    for row_result in rows {
+        debriefs.push(row_result?);
+    }
+    Ok(debriefs)
+}
+
+/// Debriefs saved at or after `since` (RFC3339), oldest first
+pub fn get_debriefs_since(conn: &Connection, since: &str) -> Result<Vec<InterviewDebrief>, CareerBenchError> {
+    let mut stmt = conn.prepare(
+        "SELECT id, application_id, round, went_well, to_improve, rating, created_at
+         FROM interview_debriefs
+         WHERE created_at >= ?
+         ORDER BY created_at ASC, id ASC",
+    )?;
+    let rows = stmt.query_map([since], map_debrief)?;
+
+    let mut debriefs = Vec::new();
+    for row_result in rows {
+        debriefs.push(row_result?);
+    }
+    Ok(debriefs)
+}
+
+/// Mean self-rating, or `None` when there are no debriefs
+pub fn average_rating(debriefs: &[InterviewDebrief]) -> Option<f64> {
+    if debriefs.is_empty() {
+        return None;
+    }
+    let total: i32 = debriefs.iter().map(|debrief| debrief.rating).sum();
+    Some(total as f64 / debriefs.len() as f64)
+}
@@ -0,0 +1,92 @@
+//! Benefits and visa sponsorship extraction from job descriptions
+//!
+//! Like deadlines, these are extracted in code rather than by the AI parser so the
+//! results are consistent and work without a provider.
+
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// Canonical benefit → phrases that indicate it (matched case-insensitively)
+const BENEFIT_PATTERNS: &[(&str, &str)] = &[
+    ("Equity", r"\b(equity|stock options?|rsus?|esop|ownership stake)\b"),
+    ("401(k)", r"(\b401\s?\(?k\)?|\bretirement (plan|savings|contributions?)|\bpension\b)"),
+    ("Health insurance", r"\b(health|medical|dental|vision)\s+(insurance|coverage|benefits|plans?)\b"),
+    ("Paid time off", r"\b(pto|paid time off|unlimited (vacation|pto)|paid (vacation|holidays)|\d+\s+days? (of )?(vacation|holiday))\b"),
+    ("Parental leave", r"\b(parental|maternity|paternity|family) leave\b"),
+    ("Bonus", r"\b(annual|performance|signing|sign-on) bonus\b"),
+    ("Remote stipend", r"\b(home office|remote work|wfh|equipment) (stipend|allowance|budget)\b"),
+    ("Learning budget", r"\b(learning|education|training|conference|professional development) (budget|stipend|allowance)\b"),
+];
+
+/// Phrases saying the employer won't sponsor; checked before the positive phrases
+const NO_SPONSORSHIP_PATTERN: &str = r"(?i)\b(no|not offering|without|unable to (provide|offer)|cannot (provide|offer)|can't (provide|offer)|do(es)? not (provide|offer))\s+(visa\s+|h-?1b\s+|work\s+visa\s+)?sponsorship\b|\b(unable to|cannot|can't|will not|won't|do(es)? not|are not able to|not able to)\s+sponsor\b|\bsponsorship (is )?not (available|offered|provided)\b";
+
+const SPONSORSHIP_PATTERN: &str = r"(?i)\b(visa sponsorship (is )?available|will sponsor|(happy|able|willing) to sponsor|we sponsor|sponsorship (is )?(available|offered|provided)|(h-?1b|work visa|visa) sponsorship (is )?(offered|provided|available)|sponsor (your |a )?(h-?1b|work visa|visa))\b";
+
+static BENEFIT_RES: LazyLock<Vec<(&str, Regex)>> = LazyLock::new(|| {
+    BENEFIT_PATTERNS
+        .iter()
+        .map(|(name, pattern)| (*name, Regex::new(&format!("(?i){}", pattern)).unwrap()))
+        .collect()
+});
+static NO_SPONSORSHIP_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(NO_SPONSORSHIP_PATTERN).unwrap());
+static SPONSORSHIP_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(SPONSORSHIP_PATTERN).unwrap());
+
+/// Canonical benefits mentioned in the description, in a fixed order.
+/// "Visa sponsorship" is included only when sponsorship is offered.
+pub fn extract_benefits(description: &str) -> Vec<String> {
+    let mut benefits: Vec<String> = BENEFIT_RES
+        .iter()
+        .filter(|(_, re)| re.is_match(description))
+        .map(|(name, _)| name.to_string())
+        .collect();
+    if detect_visa_sponsorship(description) == Some(true) {
+        benefits.push("Visa sponsorship".to_string());
+    }
+    benefits
+}
+
+/// Whether the description says visa sponsorship is offered.
+///
+/// Some(false) for explicit refusals ("no sponsorship", "unable to sponsor"),
+/// Some(true) for offers ("will sponsor H-1B"), None when it isn't mentioned.
+pub fn detect_visa_sponsorship(description: &str) -> Option<bool> {
+    if NO_SPONSORSHIP_RE.is_match(description) {
+        return Some(false);
+    }
+    if SPONSORSHIP_RE.is_match(description) {
+        return Some(true);
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_benefits_list() {
+        let description = "We offer competitive equity, a 401(k) match, medical and dental insurance \
+            (full health coverage), unlimited PTO and 16 weeks of parental leave. We will sponsor H-1B visas.";
+        assert_eq!(
+            extract_benefits(description),
+            vec!["Equity", "401(k)", "Health insurance", "Paid time off", "Parental leave", "Visa sponsorship"]
+        );
+    }
+
+    #[test]
+    fn test_visa_sponsorship_phrasings() {
+        assert_eq!(detect_visa_sponsorship("We will sponsor H-1B visas for the right candidate."), Some(true));
+        assert_eq!(detect_visa_sponsorship("Visa sponsorship available."), Some(true));
+        assert_eq!(detect_visa_sponsorship("Please note: no sponsorship is available for this role."), Some(false));
+        assert_eq!(detect_visa_sponsorship("We are unable to sponsor work visas."), Some(false));
+        assert_eq!(detect_visa_sponsorship("Sponsorship is not available."), Some(false));
+        assert_eq!(detect_visa_sponsorship("A great team in Berlin."), None);
+    }
+
+    #[test]
+    fn test_refused_sponsorship_is_not_a_benefit() {
+        let benefits = extract_benefits("Equity included. We cannot offer visa sponsorship.");
+        assert_eq!(benefits, vec!["Equity"]);
+    }
+}
@@ -0,0 +1,223 @@
+//! Application deadline extraction from job descriptions
+
+use chrono::{Datelike, Days, Months, NaiveDate};
+use regex::Regex;
+
+/// How far past a deadline phrase we look for the date itself
+const DEADLINE_WINDOW_CHARS: usize = 80;
+
+/// Extract an application deadline from a job description.
+///
+/// Looks for deadline phrases ("apply by", "applications close", ...) followed by
+/// either an absolute date or a relative one ("in 2 weeks"). Relative dates and
+/// dates without a year are resolved against `anchor`, normally the job's `date_added`.
+pub fn extract_application_deadline(description: &str, anchor: NaiveDate) -> Option<NaiveDate> {
+    let keyword_re = Regex::new(
+        r"(?i)\b(application deadline|deadline|apply by|apply before|applications? (?:close|closes|closing|are due|due)|closing date|accepting applications (?:until|through)|open until)\b",
+    )
+    .unwrap();
+
+    for keyword in keyword_re.find_iter(description) {
+        let window: String = description[keyword.end()..]
+            .chars()
+            .take(DEADLINE_WINDOW_CHARS)
+            .collect();
+
+        // Whichever date expression comes first after the phrase wins
+        let candidates = [
+            parse_relative_date(&window, anchor),
+            parse_absolute_date(&window, anchor),
+        ];
+        if let Some((_, date)) = candidates.into_iter().flatten().min_by_key(|(pos, _)| *pos) {
+            return Some(date);
+        }
+    }
+
+    None
+}
+
+/// Parse the anchor date from a stored timestamp (RFC3339 or `YYYY-MM-DD...`)
+pub fn parse_anchor_date(timestamp: &str) -> Option<NaiveDate> {
+    timestamp
+        .get(..10)
+        .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+}
+
+fn number_word(word: &str) -> Option<u32> {
+    match word.to_lowercase().as_str() {
+        "a" | "an" | "one" => Some(1),
+        "two" => Some(2),
+        "three" => Some(3),
+        "four" => Some(4),
+        "five" => Some(5),
+        "six" => Some(6),
+        "seven" => Some(7),
+        "eight" => Some(8),
+        "nine" => Some(9),
+        "ten" => Some(10),
+        other => other.parse().ok(),
+    }
+}
+
+/// "in 2 weeks", "within ten days", "in a month"
+fn parse_relative_date(text: &str, anchor: NaiveDate) -> Option<(usize, NaiveDate)> {
+    let relative_re = Regex::new(
+        r"(?i)\b(?:in|within)\s+(\d{1,3}|an?|one|two|three|four|five|six|seven|eight|nine|ten)\s+(day|week|month)s?\b",
+    )
+    .unwrap();
+
+    let caps = relative_re.captures(text)?;
+    let amount = number_word(&caps[1])?;
+    let date = match caps[2].to_lowercase().as_str() {
+        "day" => anchor.checked_add_days(Days::new(amount as u64)),
+        "week" => anchor.checked_add_days(Days::new(amount as u64 * 7)),
+        _ => anchor.checked_add_months(Months::new(amount)),
+    }?;
+
+    Some((caps.get(0)?.start(), date))
+}
+
+fn month_number(name: &str) -> Option<u32> {
+    let prefix: String = name.to_lowercase().chars().take(3).collect();
+    let month = match prefix.as_str() {
+        "jan" => 1,
+        "feb" => 2,
+        "mar" => 3,
+        "apr" => 4,
+        "may" => 5,
+        "jun" => 6,
+        "jul" => 7,
+        "aug" => 8,
+        "sep" => 9,
+        "oct" => 10,
+        "nov" => 11,
+        "dec" => 12,
+        _ => return None,
+    };
+    Some(month)
+}
+
+/// Build a date, choosing the next occurrence on or after `anchor` when the year is missing
+fn resolve_date(year: Option<i32>, month: u32, day: u32, anchor: NaiveDate) -> Option<NaiveDate> {
+    match year {
+        Some(year) => NaiveDate::from_ymd_opt(year, month, day),
+        None => {
+            let this_year = NaiveDate::from_ymd_opt(anchor.year(), month, day)?;
+            if this_year >= anchor {
+                Some(this_year)
+            } else {
+                NaiveDate::from_ymd_opt(anchor.year() + 1, month, day)
+            }
+        }
+    }
+}
+
+/// "2025-03-15", "March 15, 2025", "15 March 2025", "Mar 15", "03/15/2025" (US order)
+fn parse_absolute_date(text: &str, anchor: NaiveDate) -> Option<(usize, NaiveDate)> {
+    const MONTH: &str = r"(jan(?:uary)?|feb(?:ruary)?|mar(?:ch)?|apr(?:il)?|may|june?|july?|aug(?:ust)?|sep(?:t(?:ember)?)?|oct(?:ober)?|nov(?:ember)?|dec(?:ember)?)";
+
+    let iso_re = Regex::new(r"\b(\d{4})-(\d{1,2})-(\d{1,2})\b").unwrap();
+    let month_first_re = Regex::new(&format!(
+        r"(?i)\b{}\.?\s+(\d{{1,2}})(?:st|nd|rd|th)?\b(?:,?\s+(\d{{4}}))?",
+        MONTH
+    ))
+    .unwrap();
+    let day_first_re = Regex::new(&format!(
+        r"(?i)\b(\d{{1,2}})(?:st|nd|rd|th)?\s+(?:of\s+)?{}\.?(?:,?\s+(\d{{4}}))?",
+        MONTH
+    ))
+    .unwrap();
+    let numeric_re = Regex::new(r"\b(\d{1,2})/(\d{1,2})/(\d{4})\b").unwrap();
+
+    let mut found: Vec<(usize, NaiveDate)> = Vec::new();
+
+    if let Some(caps) = iso_re.captures(text) {
+        let date = NaiveDate::from_ymd_opt(caps[1].parse().ok()?, caps[2].parse().ok()?, caps[3].parse().ok()?);
+        if let (Some(date), Some(m)) = (date, caps.get(0)) {
+            found.push((m.start(), date));
+        }
+    }
+
+    if let Some(caps) = month_first_re.captures(text) {
+        let month = month_number(&caps[1]);
+        let day = caps[2].parse().ok();
+        let year = caps.get(3).and_then(|y| y.as_str().parse().ok());
+        if let (Some(month), Some(day), Some(m)) = (month, day, caps.get(0)) {
+            if let Some(date) = resolve_date(year, month, day, anchor) {
+                found.push((m.start(), date));
+            }
+        }
+    }
+
+    if let Some(caps) = day_first_re.captures(text) {
+        let day = caps[1].parse().ok();
+        let month = month_number(&caps[2]);
+        let year = caps.get(3).and_then(|y| y.as_str().parse().ok());
+        if let (Some(month), Some(day), Some(m)) = (month, day, caps.get(0)) {
+            if let Some(date) = resolve_date(year, month, day, anchor) {
+                found.push((m.start(), date));
+            }
+        }
+    }
+
+    if let Some(caps) = numeric_re.captures(text) {
+        let date = NaiveDate::from_ymd_opt(caps[3].parse().ok()?, caps[1].parse().ok()?, caps[2].parse().ok()?);
+        if let (Some(date), Some(m)) = (date, caps.get(0)) {
+            found.push((m.start(), date));
+        }
+    }
+
+    found.into_iter().min_by_key(|(pos, _)| *pos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn anchor() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 3, 1).unwrap()
+    }
+
+    #[test]
+    fn test_absolute_deadline() {
+        let description = "Great team. Application deadline: March 31, 2025. We look forward to hearing from you.";
+        assert_eq!(
+            extract_application_deadline(description, anchor()),
+            NaiveDate::from_ymd_opt(2025, 3, 31)
+        );
+
+        let iso = "Please apply by 2025-04-15 via our careers page.";
+        assert_eq!(extract_application_deadline(iso, anchor()), NaiveDate::from_ymd_opt(2025, 4, 15));
+    }
+
+    #[test]
+    fn test_relative_deadline_resolves_against_anchor() {
+        let description = "Applications close in 2 weeks, so don't wait.";
+        assert_eq!(
+            extract_application_deadline(description, anchor()),
+            NaiveDate::from_ymd_opt(2025, 3, 15)
+        );
+    }
+
+    #[test]
+    fn test_missing_year_uses_next_occurrence() {
+        let description = "Deadline: 15th January";
+        assert_eq!(
+            extract_application_deadline(description, anchor()),
+            NaiveDate::from_ymd_opt(2026, 1, 15)
+        );
+    }
+
+    #[test]
+    fn test_no_deadline() {
+        let description = "We are hiring a senior engineer to join us in 2025. You may apply anytime.";
+        assert_eq!(extract_application_deadline(description, anchor()), None);
+    }
+
+    #[test]
+    fn test_parse_anchor_date() {
+        assert_eq!(parse_anchor_date("2025-03-01T10:00:00+00:00"), Some(anchor()));
+        assert_eq!(parse_anchor_date("2025-03-01 10:00:00"), Some(anchor()));
+        assert_eq!(parse_anchor_date("bad"), None);
+    }
+}
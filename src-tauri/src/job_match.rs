@@ -0,0 +1,472 @@
+//! Job match scoring against the user's profile skills
+
+use crate::app_settings::{get_setting, set_setting};
+use crate::commands::{snippet_around, ArtifactMatch, JobSummary, ParsedJob};
+use crate::db::get_connection;
+use crate::errors::{CareerBenchError, DatabaseError};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use crate::job_tags::split_tags;
+
+/// Settings key for the optional match score below which new applications get a warning
+pub const LOW_MATCH_WARN_THRESHOLD_SETTING: &str = "low_match_warn_threshold";
+
+/// Added to the similarity of related jobs with the same seniority
+const SAME_SENIORITY_BONUS: f64 = 0.1;
+
+/// Required skills count twice as much as nice-to-have skills
+const REQUIRED_SKILL_WEIGHT: f64 = 2.0;
+const NICE_TO_HAVE_SKILL_WEIGHT: f64 = 1.0;
+/// Skills the job only mentions in its description or responsibilities
+const MENTIONED_SKILL_WEIGHT: f64 = 0.5;
+
+/// How well a parsed job matches the profile's skills
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct JobMatch {
+    /// 0–100 weighted share of the job's skills found in the profile
+    pub score: f64,
+    pub matching_skills: Vec<String>,
+    pub missing_skills: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RankedJob {
+    pub job_id: i64,
+    pub title: Option<String>,
+    pub company: Option<String>,
+    /// None when the job hasn't been parsed yet
+    pub score: Option<f64>,
+    pub matching_skills: Vec<String>,
+    pub missing_skills: Vec<String>,
+    pub needs_parsing: bool,
+}
+
+/// Normalize a skill name for comparison ("Node.js" and "nodejs" compare equal)
+pub fn normalize_skill(skill: &str) -> String {
+    skill
+        .trim()
+        .to_lowercase()
+        .chars()
+        .filter(|c| c.is_alphanumeric() || *c == '+' || *c == '#')
+        .collect()
+}
+
+/// Score a parsed job against the given profile skill names
+pub fn calculate_job_match(parsed_job: &ParsedJob, profile_skills: &[String]) -> JobMatch {
+    let known: HashSet<String> = profile_skills.iter().map(|s| normalize_skill(s)).collect();
+
+    let mut matching_skills = Vec::new();
+    let mut missing_skills = Vec::new();
+    let mut earned = 0.0;
+    let mut possible = 0.0;
+    let mut seen = HashSet::new();
+
+    let weighted = parsed_job
+        .required_skills
+        .iter()
+        .map(|s| (s, REQUIRED_SKILL_WEIGHT))
+        .chain(parsed_job.nice_to_have_skills.iter().map(|s| (s, NICE_TO_HAVE_SKILL_WEIGHT)));
+
+    for (skill, weight) in weighted {
+        let normalized = normalize_skill(skill);
+        if normalized.is_empty() || !seen.insert(normalized.clone()) {
+            continue;
+        }
+        possible += weight;
+        if known.contains(&normalized) {
+            earned += weight;
+            matching_skills.push(skill.clone());
+        } else {
+            missing_skills.push(skill.clone());
+        }
+    }
+
+    let score = if possible > 0.0 {
+        (earned / possible * 100.0).round()
+    } else {
+        0.0
+    };
+
+    JobMatch {
+        score,
+        matching_skills,
+        missing_skills,
+    }
+}
+
+/// Load the profile's skill names
+pub fn load_profile_skill_names(conn: &Connection) -> Result<Vec<String>, CareerBenchError> {
+    let mut stmt = conn.prepare("SELECT name FROM skills")?;
+    let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+
+    let mut skills = Vec::new();
+    for row_result in rows {
+        skills.push(row_result?);
+    }
+    Ok(skills)
+}
+
+/// Rank active jobs by match score; unparsed jobs are listed last
+pub fn rank_saved_jobs(limit: Option<i64>) -> Result<Vec<RankedJob>, CareerBenchError> {
+    let conn = get_connection()?;
+    rank_jobs(&conn, limit)
+}
+
+pub fn rank_jobs(conn: &Connection, limit: Option<i64>) -> Result<Vec<RankedJob>, CareerBenchError> {
+    let profile_skills = load_profile_skill_names(conn)?;
+
+    let mut stmt = conn.prepare(
+        "SELECT id, title, company, parsed_json FROM jobs WHERE is_active = 1 ORDER BY date_added DESC",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, i64>(0)?,
+            row.get::<_, Option<String>>(1)?,
+            row.get::<_, Option<String>>(2)?,
+            row.get::<_, Option<String>>(3)?,
+        ))
+    })?;
+
+    let mut ranked = Vec::new();
+    for row_result in rows {
+        let (job_id, title, company, parsed_json) = row_result?;
+        let parsed = parsed_json.and_then(|json| serde_json::from_str::<ParsedJob>(&json).ok());
+
+        ranked.push(match parsed {
+            Some(parsed_job) => {
+                let job_match = calculate_job_match(&parsed_job, &profile_skills);
+                RankedJob {
+                    job_id,
+                    title,
+                    company,
+                    score: Some(job_match.score),
+                    matching_skills: job_match.matching_skills,
+                    missing_skills: job_match.missing_skills,
+                    needs_parsing: false,
+                }
+            }
+            None => RankedJob {
+                job_id,
+                title,
+                company,
+                score: None,
+                matching_skills: Vec::new(),
+                missing_skills: Vec::new(),
+                needs_parsing: true,
+            },
+        });
+    }
+
+    // Highest score first; jobs needing parsing sort after all scored jobs
+    ranked.sort_by(|a, b| match (a.score, b.score) {
+        (Some(a_score), Some(b_score)) => b_score.partial_cmp(&a_score).unwrap_or(std::cmp::Ordering::Equal),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => std::cmp::Ordering::Equal,
+    });
+
+    if let Some(limit) = limit {
+        ranked.truncate(limit.max(0) as usize);
+    }
+
+    Ok(ranked)
+}
+
+/// Load the low-match warning threshold (0–100); None when not configured
+pub fn load_low_match_warn_threshold(conn: &Connection) -> Result<Option<f64>, CareerBenchError> {
+    Ok(get_setting(conn, LOW_MATCH_WARN_THRESHOLD_SETTING)?.and_then(|value| value.trim().parse().ok()))
+}
+
+/// Save the low-match warning threshold; None turns the warning off
+pub fn save_low_match_warn_threshold(conn: &Connection, threshold: Option<f64>) -> Result<(), CareerBenchError> {
+    let value = threshold
+        .map(|t| t.clamp(0.0, 100.0).to_string())
+        .unwrap_or_default();
+    set_setting(conn, LOW_MATCH_WARN_THRESHOLD_SETTING, &value)
+}
+
+/// The job's match when it is parsed and scores below the configured threshold.
+///
+/// Returns None when no threshold is set, the job isn't parsed yet, or the match is good enough.
+pub fn low_match_warning(conn: &Connection, job_id: i64) -> Result<Option<JobMatch>, CareerBenchError> {
+    let threshold = match load_low_match_warn_threshold(conn)? {
+        Some(threshold) => threshold,
+        None => return Ok(None),
+    };
+
+    let parsed_json = match conn.query_row("SELECT parsed_json FROM jobs WHERE id = ?", [job_id], |row| {
+        row.get::<_, Option<String>>(0)
+    }) {
+        Ok(parsed_json) => parsed_json,
+        Err(rusqlite::Error::QueryReturnedNoRows) => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    let parsed_job = match parsed_json.and_then(|json| serde_json::from_str::<ParsedJob>(&json).ok()) {
+        Some(parsed_job) => parsed_job,
+        None => return Ok(None),
+    };
+
+    let job_match = calculate_job_match(&parsed_job, &load_profile_skill_names(conn)?);
+    Ok((job_match.score < threshold).then_some(job_match))
+}
+
+/// Normalized skills (required and nice-to-have) plus domain tags describing a job
+fn job_feature_set(parsed_json: Option<&str>, domain_tags: Option<&str>) -> HashSet<String> {
+    let mut features: HashSet<String> = split_tags(domain_tags)
+        .iter()
+        .map(|tag| format!("tag:{}", normalize_skill(tag)))
+        .collect();
+    if let Some(parsed) = parsed_json.and_then(|json| serde_json::from_str::<ParsedJob>(json).ok()) {
+        for skill in parsed.required_skills.iter().chain(parsed.nice_to_have_skills.iter()) {
+            let normalized = normalize_skill(skill);
+            if !normalized.is_empty() {
+                features.insert(normalized);
+            }
+        }
+        for tag in &parsed.domain_tags {
+            features.insert(format!("tag:{}", normalize_skill(tag)));
+        }
+    }
+    features
+}
+
+/// |A ∩ B| / |A ∪ B|; 0 when both are empty
+pub fn jaccard_similarity(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+/// Other active jobs most similar to `job_id`, by Jaccard similarity of skills and
+/// domain tags with a small bonus for matching seniority. Jobs with nothing in
+/// common are left out.
+pub fn find_related_jobs(conn: &Connection, job_id: i64, limit: i64) -> Result<Vec<JobSummary>, CareerBenchError> {
+    let source = conn.query_row(
+        "SELECT parsed_json, domain_tags, seniority FROM jobs WHERE id = ?",
+        [job_id],
+        |row| {
+            Ok((
+                row.get::<_, Option<String>>(0)?,
+                row.get::<_, Option<String>>(1)?,
+                row.get::<_, Option<String>>(2)?,
+            ))
+        },
+    );
+    let (source_parsed, source_tags, source_seniority) = match source {
+        Ok(source) => source,
+        Err(rusqlite::Error::QueryReturnedNoRows) => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let source_features = job_feature_set(source_parsed.as_deref(), source_tags.as_deref());
+    let source_seniority = source_seniority.map(|s| s.trim().to_lowercase()).filter(|s| !s.is_empty());
+
+    let mut stmt = conn.prepare(
+        "SELECT id, title, company, location, seniority, domain_tags, date_added, parsed_json
+         FROM jobs WHERE is_active = 1 AND id != ?",
+    )?;
+    let rows = stmt.query_map([job_id], |row| {
+        Ok((
+            JobSummary {
+                id: row.get(0)?,
+                title: row.get(1)?,
+                company: row.get(2)?,
+                location: row.get(3)?,
+                seniority: row.get(4)?,
+                domain_tags: row.get(5)?,
+                date_added: row.get(6)?,
+                tags: Vec::new(),
+            },
+            row.get::<_, Option<String>>(7)?,
+        ))
+    })?;
+
+    let mut scored: Vec<(f64, JobSummary)> = Vec::new();
+    for row_result in rows {
+        let (summary, parsed_json) = row_result?;
+        let features = job_feature_set(parsed_json.as_deref(), summary.domain_tags.as_deref());
+        let similarity = jaccard_similarity(&source_features, &features);
+        if similarity == 0.0 {
+            continue;
+        }
+        let same_seniority = match (&source_seniority, &summary.seniority) {
+            (Some(source), Some(other)) => *source == other.trim().to_lowercase(),
+            _ => false,
+        };
+        let score = similarity + if same_seniority { SAME_SENIORITY_BONUS } else { 0.0 };
+        scored.push((score, summary));
+    }
+
+    scored.sort_by(|a, b| {
+        b.0.partial_cmp(&a.0)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| b.1.date_added.cmp(&a.1.date_added))
+    });
+    Ok(scored
+        .into_iter()
+        .take(limit.max(0) as usize)
+        .map(|(_, summary)| summary)
+        .collect())
+}
+
+/// Past cover letters worth reusing for `job_id`, ranked by the skill and tag overlap
+/// of the job each letter was written for.
+///
+/// Letters already attached to this job or its applications are left out; letters
+/// with no overlap (or no known source job) still appear, ranked last.
+pub fn find_similar_cover_letters(conn: &Connection, job_id: i64, limit: i64) -> Result<Vec<ArtifactMatch>, CareerBenchError> {
+    let target = conn.query_row(
+        "SELECT parsed_json, domain_tags FROM jobs WHERE id = ?",
+        [job_id],
+        |row| Ok((row.get::<_, Option<String>>(0)?, row.get::<_, Option<String>>(1)?)),
+    );
+    let (target_parsed, target_tags) = match target {
+        Ok(target) => target,
+        Err(rusqlite::Error::QueryReturnedNoRows) => {
+            return Err(DatabaseError::NotFound(format!("Job {} not found", job_id)).into())
+        }
+        Err(e) => return Err(e.into()),
+    };
+    let target_features = job_feature_set(target_parsed.as_deref(), target_tags.as_deref());
+
+    let mut stmt = conn.prepare(
+        "SELECT a.id, a.type, a.title, a.job_id, a.application_id, a.content, a.updated_at,
+                j.title, j.parsed_json, j.domain_tags
+         FROM artifacts a
+         LEFT JOIN applications app ON app.id = a.application_id
+         LEFT JOIN jobs j ON j.id = COALESCE(a.job_id, app.job_id)
+         WHERE a.type = 'CoverLetter'
+           AND COALESCE(a.job_id, app.job_id, -1) != ?1
+           AND (a.application_id IS NULL OR a.application_id NOT IN (SELECT id FROM applications WHERE job_id = ?1))",
+    )?;
+    let rows = stmt.query_map([job_id], |row| {
+        Ok((
+            row.get::<_, i64>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, String>(2)?,
+            row.get::<_, Option<i64>>(3)?,
+            row.get::<_, Option<i64>>(4)?,
+            row.get::<_, Option<String>>(5)?,
+            row.get::<_, String>(6)?,
+            row.get::<_, Option<String>>(7)?,
+            row.get::<_, Option<String>>(8)?,
+            row.get::<_, Option<String>>(9)?,
+        ))
+    })?;
+
+    let mut candidates = Vec::new();
+    for row_result in rows {
+        let (artifact_id, artifact_type, title, artifact_job_id, application_id, content, updated_at, job_title, parsed_json, domain_tags) =
+            row_result?;
+        let features = job_feature_set(parsed_json.as_deref(), domain_tags.as_deref());
+        let content_chars: Vec<char> = content.unwrap_or_default().chars().collect();
+        candidates.push(ArtifactMatch {
+            artifact_id,
+            r#type: artifact_type,
+            title,
+            job_id: artifact_job_id,
+            application_id,
+            snippet: snippet_around(&content_chars, None, 0),
+            match_count: target_features.intersection(&features).count(),
+            title_match: false,
+            updated_at,
+            source_job_title: job_title,
+            similarity: Some(jaccard_similarity(&target_features, &features)),
+        });
+    }
+
+    candidates.sort_by(|a, b| {
+        b.similarity
+            .unwrap_or(0.0)
+            .partial_cmp(&a.similarity.unwrap_or(0.0))
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| b.updated_at.cmp(&a.updated_at))
+    });
+    candidates.truncate(limit.max(0) as usize);
+    Ok(candidates)
+}
+
+/// How much the job cares about one profile skill: required, nice-to-have, only
+/// mentioned in the description text, or not at all
+fn skill_match_weight(skill: &str, parsed_job: &ParsedJob, description_text: &str) -> f64 {
+    let normalized = normalize_skill(skill);
+    if normalized.is_empty() {
+        return 0.0;
+    }
+    if parsed_job.required_skills.iter().any(|s| normalize_skill(s) == normalized) {
+        return REQUIRED_SKILL_WEIGHT;
+    }
+    if parsed_job.nice_to_have_skills.iter().any(|s| normalize_skill(s) == normalized) {
+        return NICE_TO_HAVE_SKILL_WEIGHT;
+    }
+
+    // Alphanumeric boundaries rather than \b so skills like "C++" still match
+    let pattern = format!(r"(?i)(?:^|[^A-Za-z0-9]){}(?:$|[^A-Za-z0-9])", regex::escape(skill.trim()));
+    match regex::Regex::new(&pattern) {
+        Ok(re) if re.is_match(description_text) => MENTIONED_SKILL_WEIGHT,
+        _ => 0.0,
+    }
+}
+
+/// Profile skills least relevant to a parsed job, least relevant first, so a tailored
+/// resume can leave them out.
+///
+/// Required and nice-to-have skills are never suggested. The rest are ordered by match
+/// weight, then self-rating (unrated first), with Core-priority skills last. Unparsed or
+/// missing jobs get no suggestions.
+pub fn suggest_skills_to_hide(conn: &Connection, job_id: i64) -> Result<Vec<String>, CareerBenchError> {
+    let job = conn.query_row(
+        "SELECT parsed_json, raw_description FROM jobs WHERE id = ?",
+        [job_id],
+        |row| Ok((row.get::<_, Option<String>>(0)?, row.get::<_, Option<String>>(1)?)),
+    );
+    let (parsed_json, raw_description) = match job {
+        Ok(job) => job,
+        Err(rusqlite::Error::QueryReturnedNoRows) => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let parsed_job = match parsed_json.and_then(|json| serde_json::from_str::<ParsedJob>(&json).ok()) {
+        Some(parsed_job) => parsed_job,
+        None => return Ok(Vec::new()),
+    };
+
+    let mut description_text = raw_description.unwrap_or_default();
+    for text in parsed_job.summary.iter().chain(parsed_job.responsibilities.iter()) {
+        description_text.push('\n');
+        description_text.push_str(text);
+    }
+
+    let mut stmt = conn.prepare("SELECT name, self_rating, priority FROM skills")?;
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, Option<i32>>(1)?,
+            row.get::<_, Option<String>>(2)?,
+        ))
+    })?;
+
+    let mut candidates: Vec<(f64, i32, bool, String)> = Vec::new();
+    for row_result in rows {
+        let (name, self_rating, priority) = row_result?;
+        let weight = skill_match_weight(&name, &parsed_job, &description_text);
+        if weight >= NICE_TO_HAVE_SKILL_WEIGHT {
+            continue;
+        }
+        let is_core = priority.as_deref() == Some("Core");
+        candidates.push((weight, self_rating.unwrap_or(0), is_core, name));
+    }
+
+    candidates.sort_by(|a, b| {
+        a.0.partial_cmp(&b.0)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.2.cmp(&b.2))
+            .then_with(|| a.1.cmp(&b.1))
+            .then_with(|| a.3.cmp(&b.3))
+    });
+    Ok(candidates.into_iter().map(|(_, _, _, name)| name).collect())
+}
+
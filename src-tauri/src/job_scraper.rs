@@ -14,10 +14,22 @@ pub struct ScrapedJobData {
     pub source: String, // e.g., "LinkedIn", "Indeed", "Generic"
 }
 
+/// Whether the URL's host is `domain` or one of its subdomains
+fn host_is(url: &str, domain: &str) -> bool {
+    reqwest::Url::parse(url)
+        .ok()
+        .and_then(|u| u.host_str().map(|h| h.to_lowercase()))
+        .is_some_and(|host| host == domain || host.ends_with(&format!(".{}", domain)))
+}
+
 /// Detect the job board type from URL
 pub fn detect_job_board(url: &str) -> &str {
     let url_lower = url.to_lowercase();
-    if url_lower.contains("linkedin.com") {
+    if host_is(url, "greenhouse.io") {
+        "Greenhouse"
+    } else if host_is(url, "lever.co") {
+        "Lever"
+    } else if url_lower.contains("linkedin.com") {
         "LinkedIn"
     } else if url_lower.contains("indeed.com") {
         "Indeed"
@@ -66,11 +78,18 @@ pub async fn scrape_job_url(url: &str) -> Result<ScrapedJobData, CareerBenchErro
             format!("Failed to read response: {}", e)
         )))?;
 
-    let document = Html::parse_document(&html);
+    parse_job_html(&html, url)
+}
+
+/// Extract job data from a fetched page, using the scraper for the URL's job board
+pub fn parse_job_html(html: &str, url: &str) -> Result<ScrapedJobData, CareerBenchError> {
+    let document = Html::parse_document(html);
     let source = detect_job_board(url);
 
     // Try job board-specific scrapers first
     let result = match source {
+        "Greenhouse" => scrape_greenhouse(&document, url),
+        "Lever" => scrape_lever(&document, url),
         "LinkedIn" => scrape_linkedin(&document, url),
         "Indeed" => scrape_indeed(&document, url),
         "Glassdoor" => scrape_glassdoor(&document, url),
@@ -279,6 +298,216 @@ fn scrape_glassdoor(document: &Html, _url: &str) -> Result<ScrapedJobData, Caree
     Ok(data)
 }
 
+/// Scrape a Greenhouse job board posting (boards.greenhouse.io or job-boards.greenhouse.io)
+fn scrape_greenhouse(document: &Html, url: &str) -> Result<ScrapedJobData, CareerBenchError> {
+    let posting = extract_job_posting_json_ld(document);
+    let mut data = ScrapedJobData {
+        title: posting.title,
+        company: posting.company,
+        location: posting.location,
+        description: posting.description,
+        source: "Greenhouse".to_string(),
+    };
+
+    // Classic boards use app-title/company-name/location/content; the newer
+    // job-boards pages use job__title/job__location/job__description
+    if data.title.is_none() {
+        data.title = select_text(document, &[".job__title h1", "h1.app-title", "h1.section-header", "h1"]);
+    }
+    if data.company.is_none() {
+        data.company = select_text(document, &["span.company-name"])
+            .map(|company| company.trim_start_matches("at ").trim().to_string())
+            .filter(|company| !company.is_empty());
+    }
+    if data.location.is_none() {
+        data.location = select_text(document, &[".job__location", "div.location"]);
+    }
+    if data.description.is_empty() {
+        data.description = select_text(document, &[".job__description", "div#content"]).unwrap_or_default();
+    }
+
+    // Board URLs are /{company}/jobs/{id}
+    if data.company.is_none() {
+        data.company = company_from_path(url);
+    }
+    if data.description.is_empty() {
+        data.description = extract_from_meta_tags(document);
+    }
+
+    Ok(data)
+}
+
+/// Scrape a Lever job posting (jobs.lever.co)
+fn scrape_lever(document: &Html, url: &str) -> Result<ScrapedJobData, CareerBenchError> {
+    let posting = extract_job_posting_json_ld(document);
+    let mut data = ScrapedJobData {
+        title: posting.title,
+        company: posting.company,
+        location: posting.location,
+        description: posting.description,
+        source: "Lever".to_string(),
+    };
+
+    if data.title.is_none() {
+        data.title = select_text(document, &[".posting-headline h2", "h2"]);
+    }
+    if data.location.is_none() {
+        data.location = select_text(document, &[".posting-categories .location", ".posting-category.location"]);
+    }
+    if data.description.is_empty() {
+        // The description is split into an intro, one section per list
+        // (responsibilities, requirements...) and a closing; skip the header and apply button
+        if let Ok(selector) = Selector::parse(".section-wrapper.page-full-width .section:not(.posting-header):not(.last-section-apply)") {
+            data.description = document
+                .select(&selector)
+                .map(|section| block_text(&section))
+                .filter(|text| !text.is_empty())
+                .collect::<Vec<_>>()
+                .join("\n\n");
+        }
+    }
+
+    // Posting URLs are /{company}/{posting id}; the logo alt text has the real name
+    if data.company.is_none() {
+        data.company = Selector::parse(".main-header-logo img")
+            .ok()
+            .and_then(|selector| document.select(&selector).next())
+            .and_then(|img| img.value().attr("alt"))
+            .map(|alt| alt.trim().trim_end_matches(" logo").trim().to_string())
+            .filter(|alt| !alt.is_empty())
+            .or_else(|| company_from_path(url));
+    }
+    if data.description.is_empty() {
+        data.description = extract_from_meta_tags(document);
+    }
+
+    Ok(data)
+}
+
+/// Text of an element with one line per text node, so lists and paragraphs stay apart
+fn block_text(element: &scraper::ElementRef) -> String {
+    element
+        .text()
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Text of the first non-empty match among `selectors`, tried in order
+fn select_text(document: &Html, selectors: &[&str]) -> Option<String> {
+    selectors.iter().find_map(|selector_str| {
+        let selector = Selector::parse(selector_str).ok()?;
+        document
+            .select(&selector)
+            .map(|element| block_text(&element))
+            .find(|text| !text.is_empty())
+    })
+}
+
+/// Plain text from an HTML snippet, which ATS JSON sometimes entity-escapes twice.
+/// Only snippets with escaped tags (e.g. `&lt;p&gt;`) are parsed a second time, so
+/// plain text such as "<5 years of Java" survives.
+fn html_fragment_to_text(html: &str) -> String {
+    let escaped_tag_re = regex::Regex::new(r"(?i)&lt;/?[a-z][a-z0-9]*(\s[^&]*)?/?&gt;").unwrap();
+    let text = block_text(&Html::parse_fragment(html).root_element());
+    if escaped_tag_re.is_match(html) {
+        block_text(&Html::parse_fragment(&text).root_element())
+    } else {
+        text
+    }
+}
+
+/// Company from the first path segment of an ATS URL, e.g. "acme-corp" -> "Acme Corp"
+fn company_from_path(url: &str) -> Option<String> {
+    let url = reqwest::Url::parse(url).ok()?;
+    let slug = url.path_segments()?.next()?;
+    let name = slug
+        .split(['-', '_'])
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    (!name.is_empty()).then_some(name)
+}
+
+/// Fields of a schema.org JobPosting embedded as JSON-LD
+#[derive(Debug, Default)]
+struct JobPostingLd {
+    title: Option<String>,
+    company: Option<String>,
+    location: Option<String>,
+    description: String,
+}
+
+/// Read the JobPosting JSON-LD block, with the description converted to text. The
+/// posting may be top level, in an array, or nested under another type's `jobPosting`.
+fn extract_job_posting_json_ld(document: &Html) -> JobPostingLd {
+    let selector = Selector::parse("script[type='application/ld+json']").unwrap();
+    let posting = document
+        .select(&selector)
+        .filter_map(|element| serde_json::from_str::<serde_json::Value>(&element.text().collect::<String>()).ok())
+        .flat_map(|json| match json {
+            serde_json::Value::Array(items) => items,
+            other => vec![other],
+        })
+        .find_map(|json| {
+            if json.get("@type").and_then(|t| t.as_str()) == Some("JobPosting") {
+                Some(json)
+            } else {
+                json.get("jobPosting").cloned()
+            }
+        });
+    let Some(posting) = posting else {
+        return JobPostingLd::default();
+    };
+
+    let text = |value: Option<&serde_json::Value>| {
+        value
+            .and_then(|v| v.as_str())
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+    };
+    let locations: Vec<serde_json::Value> = match posting.get("jobLocation") {
+        Some(serde_json::Value::Array(items)) => items.clone(),
+        Some(item) => vec![item.clone()],
+        None => Vec::new(),
+    };
+    let location = locations
+        .iter()
+        .filter_map(|place| {
+            let address = place.get("address")?;
+            let parts: Vec<String> = ["addressLocality", "addressRegion", "addressCountry"]
+                .iter()
+                .filter_map(|key| text(address.get(*key)))
+                .collect();
+            (!parts.is_empty()).then(|| parts.join(", "))
+        })
+        .collect::<Vec<_>>();
+    let remote = text(posting.get("jobLocationType")).as_deref() == Some("TELECOMMUTE");
+
+    JobPostingLd {
+        title: text(posting.get("title")),
+        company: text(posting.get("hiringOrganization").and_then(|org| org.get("name"))),
+        location: if !location.is_empty() {
+            Some(location.join("; "))
+        } else if remote {
+            Some("Remote".to_string())
+        } else {
+            None
+        },
+        description: text(posting.get("description"))
+            .map(|html| html_fragment_to_text(&html))
+            .unwrap_or_default(),
+    }
+}
+
 /// Generic scraper for unknown job boards
 fn scrape_generic(document: &Html, _url: &str) -> Result<ScrapedJobData, CareerBenchError> {
     let mut data = ScrapedJobData {
@@ -337,14 +566,15 @@ fn scrape_generic(document: &Html, _url: &str) -> Result<ScrapedJobData, CareerB
 
     // Extract from JSON-LD structured data if available
     if data.description.is_empty() || data.title.is_none() {
-        if let Ok((title, desc)) = extract_from_json_ld(document) {
-            if data.title.is_none() {
-                data.title = title;
-            }
-            if data.description.is_empty() {
-                data.description = desc;
-            }
+        let posting = extract_job_posting_json_ld(document);
+        if data.title.is_none() {
+            data.title = posting.title;
+        }
+        if data.description.is_empty() {
+            data.description = posting.description;
         }
+        data.company = posting.company;
+        data.location = posting.location;
     }
 
     Ok(data)
@@ -373,31 +603,86 @@ fn extract_from_meta_tags(document: &Html) -> String {
     String::new()
 }
 
-/// Extract job data from JSON-LD structured data
-fn extract_from_json_ld(document: &Html) -> Result<(Option<String>, String), CareerBenchError> {
-    let selector = Selector::parse("script[type='application/ld+json']").unwrap();
-    let mut title = None;
-    let mut description = String::new();
-
-    for element in document.select(&selector) {
-        if let Some(text) = element.text().next() {
-            if let Ok(json) = serde_json::from_str::<serde_json::Value>(text) {
-                // Try to extract from JobPosting schema
-                if let Some(job_posting) = json.get("@type")
-                    .and_then(|t| if t == "JobPosting" { Some(&json) } else { None })
-                    .or_else(|| json.get("@type").and_then(|_| json.get("jobPosting")))
-                {
-                    if let Some(t) = job_posting.get("title").and_then(|v| v.as_str()) {
-                        title = Some(t.to_string());
-                    }
-                    if let Some(d) = job_posting.get("description").and_then(|v| v.as_str()) {
-                        description = d.to_string();
-                    }
-                }
-            }
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_job_board_by_host() {
+        assert_eq!(detect_job_board("https://boards.greenhouse.io/acme/jobs/123"), "Greenhouse");
+        assert_eq!(detect_job_board("https://job-boards.greenhouse.io/globex/jobs/456"), "Greenhouse");
+        assert_eq!(detect_job_board("https://jobs.lever.co/initech/8c1f"), "Lever");
+        // Only the host counts, not paths or lookalike domains
+        assert_eq!(detect_job_board("https://example.com/jobs?via=jobs.lever.co"), "Generic");
+        assert_eq!(detect_job_board("https://greenhouse.io.example.com/jobs/1"), "Generic");
     }
 
-    Ok((title, description))
-}
+    #[test]
+    fn test_parse_greenhouse_classic_board() {
+        let html = include_str!("../tests/fixtures/greenhouse_classic.html");
+        let data = parse_job_html(html, "https://boards.greenhouse.io/acmerobotics/jobs/123").unwrap();
+        assert_eq!(data.source, "Greenhouse");
+        assert_eq!(data.title.as_deref(), Some("Senior Backend Engineer"));
+        assert_eq!(data.company.as_deref(), Some("Acme Robotics"));
+        assert_eq!(data.location.as_deref(), Some("Berlin, Germany"));
+        assert!(data.description.starts_with("Acme Robotics builds warehouse automation."));
+        assert!(data.description.contains("Own the fleet telemetry pipeline"));
+        assert!(!data.description.contains("First Name"));
+    }
 
+    #[test]
+    fn test_parse_greenhouse_json_ld() {
+        let html = include_str!("../tests/fixtures/greenhouse_job_boards.html");
+        let data = parse_job_html(html, "https://job-boards.greenhouse.io/globex/jobs/456").unwrap();
+        assert_eq!(data.title.as_deref(), Some("Staff Data Engineer"));
+        assert_eq!(data.company.as_deref(), Some("Globex"));
+        assert_eq!(data.location.as_deref(), Some("Toronto, ON, CA"));
+        assert_eq!(
+            data.description,
+            "Globex is hiring a Staff Data Engineer.\nOwn our lakehouse\nMentor engineers"
+        );
+    }
+
+    #[test]
+    fn test_parse_lever_posting() {
+        let html = include_str!("../tests/fixtures/lever_posting.html");
+        let data = parse_job_html(html, "https://jobs.lever.co/initech/8c1f").unwrap();
+        assert_eq!(data.source, "Lever");
+        assert_eq!(data.title.as_deref(), Some("Product Designer"));
+        assert_eq!(data.company.as_deref(), Some("Initech"));
+        assert_eq!(data.location.as_deref(), Some("Austin, TX"));
+        assert_eq!(
+            data.description,
+            "Initech designs the tools that run offices.\n\nWhat you'll do\nShape our design system\nRun user research\n\nWe offer flexible hours."
+        );
+    }
+
+    #[test]
+    fn test_unknown_hosts_use_generic_extraction() {
+        let html = include_str!("../tests/fixtures/lever_posting.html");
+        let data = parse_job_html(html, "https://careers.initech.example/designer").unwrap();
+        assert_eq!(data.source, "Generic");
+        assert_eq!(data.company, None);
+        assert_eq!(company_from_path("https://boards.greenhouse.io/acme-robotics/jobs/1").as_deref(), Some("Acme Robotics"));
+    }
+
+    #[test]
+    fn test_generic_falls_back_to_nested_json_ld() {
+        let html = r#"<html><head><script type="application/ld+json">
+            {"@type": "WebPage", "jobPosting": {"@type": "JobPosting", "title": "Data Analyst",
+             "hiringOrganization": {"name": "Umbrella"},
+             "description": "&lt;p&gt;Build dashboards&lt;/p&gt;&lt;p&gt;Needs &amp;lt;3 years SQL&lt;/p&gt;"}}
+            </script></head><body></body></html>"#;
+        let data = parse_job_html(html, "https://careers.umbrella.example/analyst").unwrap();
+        assert_eq!(data.source, "Generic");
+        assert_eq!(data.title.as_deref(), Some("Data Analyst"));
+        assert_eq!(data.company.as_deref(), Some("Umbrella"));
+        assert_eq!(data.description, "Build dashboards\nNeeds <3 years SQL");
+    }
+
+    #[test]
+    fn test_plain_text_with_angle_brackets_is_not_reparsed() {
+        assert_eq!(html_fragment_to_text("<p>Requires <5 years or >2 teams</p>"), "Requires <5 years or >2 teams");
+        assert_eq!(html_fragment_to_text("&lt;p&gt;Own &lt;b&gt;billing&lt;/b&gt;&lt;/p&gt;"), "Own\nbilling");
+    }
+}
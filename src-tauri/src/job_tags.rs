@@ -0,0 +1,220 @@
+//! Deterministic job tagging from description text, and user tags
+//!
+//! Gives jobs basic domain tags without an AI provider, using a curated
+//! keyword dictionary. Derived tags are merged into `jobs.domain_tags` alongside
+//! any tags the AI parser (or the user) already set.
+//!
+//! User tags ("dream", "remote-only") are separate: they live in the `job_tags`
+//! table, are only ever set by hand, and are what the job list filters on.
+
+use crate::errors::{CareerBenchError, DatabaseError, ValidationError};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+
+/// Tag → keywords that imply it. Keywords are matched as whole words in the
+/// lowercased text, with punctuation other than `+` and `#` treated as spaces
+/// ("node.js" becomes "node js").
+const TAG_KEYWORDS: &[(&str, &[&str])] = &[
+    ("Frontend", &["react", "vue", "angular", "svelte", "next js", "frontend", "front end", "css", "html", "tailwind", "redux"]),
+    ("Backend", &["backend", "back end", "node js", "django", "flask", "fastapi", "rails", "spring boot", "graphql", "rest api", "microservices", "postgres", "postgresql", "mysql", "golang", "rust"]),
+    ("Data", &["postgres", "postgresql", "mysql", "sql", "snowflake", "bigquery", "redshift", "spark", "airflow", "dbt", "kafka", "etl", "data pipeline", "data warehouse"]),
+    ("DevOps", &["kubernetes", "k8s", "docker", "terraform", "ansible", "helm", "ci cd", "jenkins", "sre", "devops", "observability", "prometheus"]),
+    ("Cloud", &["aws", "azure", "gcp", "google cloud", "lambda", "serverless", "cloudformation"]),
+    ("Mobile", &["ios", "android", "swift", "kotlin", "react native", "flutter", "mobile"]),
+    ("Machine Learning", &["machine learning", "ml", "deep learning", "pytorch", "tensorflow", "llm", "nlp", "computer vision", "data science"]),
+    ("Security", &["security", "appsec", "penetration testing", "iam", "soc 2", "siem", "vulnerability"]),
+    ("Embedded", &["embedded", "firmware", "rtos", "fpga", "microcontroller"]),
+];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct JobTagUpdate {
+    pub job_id: i64,
+    /// Tags that were not on the job before
+    pub added_tags: Vec<String>,
+    /// Full tag list after merging
+    pub tags: Vec<String>,
+}
+
+/// Lowercase and replace punctuation with spaces, padded so keywords can be
+/// matched on word boundaries with `contains(" keyword ")`
+fn normalize_for_matching(text: &str) -> String {
+    let cleaned: String = text
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '+' || c == '#' { c } else { ' ' })
+        .collect();
+    format!(" {} ", cleaned.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+/// Domain tags implied by the text, in dictionary order
+pub fn derive_tags_from_text(description: &str) -> Vec<String> {
+    let padded = normalize_for_matching(description);
+    TAG_KEYWORDS
+        .iter()
+        .filter(|(_, keywords)| keywords.iter().any(|keyword| padded.contains(&format!(" {} ", keyword))))
+        .map(|(tag, _)| tag.to_string())
+        .collect()
+}
+
+/// Split a stored `domain_tags` value ("Fintech, Backend") into tags
+pub fn split_tags(tags: Option<&str>) -> Vec<String> {
+    tags.unwrap_or("")
+        .split(',')
+        .map(|tag| tag.trim())
+        .filter(|tag| !tag.is_empty())
+        .map(|tag| tag.to_string())
+        .collect()
+}
+
+/// Append derived tags missing from the existing list (compared case-insensitively),
+/// keeping existing tags and their order untouched
+pub fn merge_tags(existing: &[String], derived: &[String]) -> Vec<String> {
+    let mut merged = existing.to_vec();
+    for tag in derived {
+        if !merged.iter().any(|current| current.eq_ignore_ascii_case(tag)) {
+            merged.push(tag.clone());
+        }
+    }
+    merged
+}
+
+/// Derive tags for each job from its title and description and merge them into
+/// `domain_tags`. Jobs that don't exist are skipped.
+pub fn auto_tag_jobs(conn: &Connection, job_ids: &[i64], now: &str) -> Result<Vec<JobTagUpdate>, CareerBenchError> {
+    let mut updates = Vec::new();
+
+    for &job_id in job_ids {
+        let row = conn.query_row(
+            "SELECT title, raw_description, domain_tags FROM jobs WHERE id = ?",
+            [job_id],
+            |row| {
+                Ok((
+                    row.get::<_, Option<String>>(0)?,
+                    row.get::<_, Option<String>>(1)?,
+                    row.get::<_, Option<String>>(2)?,
+                ))
+            },
+        );
+        let (title, description, domain_tags) = match row {
+            Ok(row) => row,
+            Err(rusqlite::Error::QueryReturnedNoRows) => continue,
+            Err(e) => return Err(e.into()),
+        };
+
+        let text = format!("{}\n{}", title.unwrap_or_default(), description.unwrap_or_default());
+        let existing = split_tags(domain_tags.as_deref());
+        let tags = merge_tags(&existing, &derive_tags_from_text(&text));
+        let added_tags: Vec<String> = tags[existing.len()..].to_vec();
+
+        if !added_tags.is_empty() {
+            conn.execute(
+                "UPDATE jobs SET domain_tags = ?, last_updated = ? WHERE id = ?",
+                rusqlite::params![tags.join(", "), now, job_id],
+            )?;
+        }
+
+        updates.push(JobTagUpdate {
+            job_id,
+            added_tags,
+            tags,
+        });
+    }
+
+    Ok(updates)
+}
+
+const MAX_USER_TAG_LENGTH: usize = 40;
+
+/// Canonical form of a user tag: trimmed, lowercased, inner whitespace collapsed.
+/// Commas are rejected because tag lists are comma-joined in list queries.
+pub fn normalize_user_tag(tag: &str) -> Result<String, CareerBenchError> {
+    let tag = tag.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase();
+    if tag.is_empty() {
+        return Err(ValidationError::MissingField("tag".to_string()).into());
+    }
+    if tag.contains(',') {
+        return Err(ValidationError::InvalidFormat("Tags can't contain commas".to_string()).into());
+    }
+    if tag.chars().count() > MAX_USER_TAG_LENGTH {
+        return Err(ValidationError::OutOfRange(format!(
+            "Tags can be at most {} characters",
+            MAX_USER_TAG_LENGTH
+        ))
+        .into());
+    }
+    Ok(tag)
+}
+
+/// User tags on a job, alphabetically
+pub fn get_job_user_tags(conn: &Connection, job_id: i64) -> Result<Vec<String>, CareerBenchError> {
+    let mut stmt = conn.prepare("SELECT tag FROM job_tags WHERE job_id = ? ORDER BY tag ASC")?;
+    let rows = stmt.query_map([job_id], |row| row.get(0))?;
+    Ok(rows.collect::<Result<_, _>>()?)
+}
+
+/// Tag a job; tagging it again with the same tag is a no-op. Returns the job's tags.
+pub fn add_job_user_tag(conn: &Connection, job_id: i64, tag: &str, now: &str) -> Result<Vec<String>, CareerBenchError> {
+    let tag = normalize_user_tag(tag)?;
+    let exists: i64 = conn.query_row("SELECT COUNT(*) FROM jobs WHERE id = ?", [job_id], |row| row.get(0))?;
+    if exists == 0 {
+        return Err(DatabaseError::NotFound(format!("Job {} not found", job_id)).into());
+    }
+    conn.execute(
+        "INSERT OR IGNORE INTO job_tags (job_id, tag, created_at) VALUES (?, ?, ?)",
+        rusqlite::params![job_id, tag, now],
+    )?;
+    get_job_user_tags(conn, job_id)
+}
+
+/// Remove a tag from a job if it has it. Returns the job's remaining tags.
+pub fn remove_job_user_tag(conn: &Connection, job_id: i64, tag: &str) -> Result<Vec<String>, CareerBenchError> {
+    let tag = normalize_user_tag(tag)?;
+    conn.execute(
+        "DELETE FROM job_tags WHERE job_id = ? AND tag = ?",
+        rusqlite::params![job_id, tag],
+    )?;
+    get_job_user_tags(conn, job_id)
+}
+
+/// Every user tag in use, alphabetically
+pub fn get_all_user_tags(conn: &Connection) -> Result<Vec<String>, CareerBenchError> {
+    let mut stmt = conn.prepare("SELECT DISTINCT tag FROM job_tags ORDER BY tag ASC")?;
+    let rows = stmt.query_map([], |row| row.get(0))?;
+    Ok(rows.collect::<Result<_, _>>()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_react_and_postgres_tags() {
+        let tags = derive_tags_from_text("We build our UI in React and store everything in Postgres.");
+        assert_eq!(tags, vec!["Frontend", "Backend", "Data"]);
+    }
+
+    #[test]
+    fn test_keywords_match_whole_words() {
+        // "html" inside "xhtmlish" and "ml" inside "html" must not match
+        assert!(derive_tags_from_text("xhtmlish reactor").is_empty());
+        assert_eq!(derive_tags_from_text("Experience with Node.js"), vec!["Backend"]);
+        assert_eq!(derive_tags_from_text("Kubernetes, Terraform"), vec!["DevOps"]);
+    }
+
+    #[test]
+    fn test_merge_keeps_existing_tags() {
+        let existing = split_tags(Some("Fintech, frontend"));
+        let merged = merge_tags(&existing, &["Frontend".to_string(), "Data".to_string()]);
+        assert_eq!(merged, vec!["Fintech", "frontend", "Data"]);
+    }
+
+    #[test]
+    fn test_user_tags_are_normalized() {
+        assert_eq!(normalize_user_tag("  Remote   Only ").unwrap(), "remote only");
+        assert_eq!(normalize_user_tag("Dream").unwrap(), "dream");
+        assert!(normalize_user_tag("   ").is_err());
+        assert!(normalize_user_tag("a,b").is_err());
+        assert!(normalize_user_tag(&"x".repeat(41)).is_err());
+    }
+}
@@ -0,0 +1,120 @@
+//! Plain-English TL;DR of a job posting, separate from the structured parse
+//!
+//! The TL;DR is cached per description under `job_tldr`. When a structured JD summary
+//! is already cached for the job, its role, seniority and must-have skills are passed
+//! along as context so the TL;DR agrees with the rest of the app.
+
+use crate::ai::provider::AiProvider;
+use crate::ai_cache::{ai_cache_get, ai_cache_put, compute_input_hash, CACHE_TTL_JOB_PARSE_DAYS};
+use crate::commands::{load_job, ParsedJob};
+use crate::resume_generator::{get_cached_jd_summary, JobDescriptionSummary};
+use rusqlite::Connection;
+use serde_json::json;
+
+const JOB_TLDR_PURPOSE: &str = "job_tldr";
+
+const JOB_TLDR_SYSTEM_PROMPT: &str = "You summarize job postings for busy job seekers. \
+Reply with a 3-4 sentence plain-English TL;DR: what the role is, what they most need, \
+and anything notable (seniority, location, compensation). No bullet points, no preamble.";
+
+/// What's needed to produce a TL;DR: either a cached one, or the prompt to send
+#[derive(Debug)]
+pub enum JobTldrRequest {
+    Cached(String),
+    Needed {
+        input_hash: String,
+        request_payload: serde_json::Value,
+        prompt: String,
+    },
+}
+
+fn tldr_prompt(description: &str, jd_summary: Option<&JobDescriptionSummary>) -> String {
+    let mut prompt = String::new();
+    if let Some(summary) = jd_summary {
+        if let Some(role) = &summary.role_title {
+            prompt.push_str(&format!("Role: {}\n", role));
+        }
+        if let Some(seniority) = &summary.seniority {
+            prompt.push_str(&format!("Seniority: {}\n", seniority));
+        }
+        if !summary.must_have_skills.is_empty() {
+            prompt.push_str(&format!("Must-have skills: {}\n", summary.must_have_skills.join(", ")));
+        }
+        prompt.push('\n');
+    }
+    prompt.push_str(&format!("Job posting:\n{}", description));
+    prompt
+}
+
+/// Load the job and return its cached TL;DR, or the prompt to generate one.
+///
+/// Fails with guidance when the job has no description to summarize.
+pub fn prepare_job_tldr(conn: &Connection, job_id: i64, now: &str) -> Result<JobTldrRequest, String> {
+    let job = load_job(conn, job_id)?;
+    let description = job.raw_description.as_deref().unwrap_or("").trim();
+    if description.is_empty() {
+        return Err(
+            "This job has no description to summarize. Paste the posting text into the job first, then try again."
+                .to_string(),
+        );
+    }
+
+    let request_payload = json!({ "jobDescription": description });
+    let input_hash = compute_input_hash(&request_payload)?;
+    if let Some(entry) = ai_cache_get(conn, JOB_TLDR_PURPOSE, &input_hash, now)? {
+        if let Some(tldr) = entry.response_payload.as_str() {
+            return Ok(JobTldrRequest::Cached(tldr.to_string()));
+        }
+    }
+
+    // Reuse the structured summary when it's already cached; never pay for it here
+    let parsed_job = job
+        .parsed_json
+        .as_deref()
+        .and_then(|json| serde_json::from_str::<ParsedJob>(json).ok());
+    let jd_summary = get_cached_jd_summary(conn, job.raw_description.as_deref().unwrap_or(""), parsed_job.as_ref(), now)
+        .ok()
+        .flatten();
+
+    Ok(JobTldrRequest::Needed {
+        input_hash,
+        request_payload,
+        prompt: tldr_prompt(description, jd_summary.as_ref()),
+    })
+}
+
+/// Ask the provider for the TL;DR
+pub async fn request_job_tldr(provider: &dyn AiProvider, prompt: &str) -> Result<String, String> {
+    let tldr = provider
+        .call_llm(Some(JOB_TLDR_SYSTEM_PROMPT), prompt)
+        .await
+        .map_err(|e| format!("AI summarization failed: {}", e))?;
+    let tldr = tldr.trim();
+    if tldr.is_empty() {
+        return Err("The AI provider returned an empty summary".to_string());
+    }
+    Ok(tldr.to_string())
+}
+
+pub fn cache_job_tldr(
+    conn: &Connection,
+    input_hash: &str,
+    request_payload: &serde_json::Value,
+    tldr: &str,
+    now: &str,
+) -> Result<(), String> {
+    let model_name = crate::ai::settings::load_ai_settings()
+        .ok()
+        .and_then(|s| s.model_for(JOB_TLDR_PURPOSE))
+        .unwrap_or_else(|| "unknown-model".to_string());
+    ai_cache_put(
+        conn,
+        JOB_TLDR_PURPOSE,
+        input_hash,
+        &model_name,
+        request_payload,
+        &json!(tldr),
+        Some(CACHE_TTL_JOB_PARSE_DAYS),
+        now,
+    )
+}
@@ -0,0 +1,224 @@
+//! Posting URL normalization and merging of jobs that turn out to be the same posting
+//!
+//! Older jobs were saved with whatever URL the browser had, tracking params included,
+//! so the same posting can appear more than once. `normalize_job_urls` rewrites every
+//! posting URL into one canonical form and merges jobs that collapse together.
+
+use crate::errors::{CareerBenchError, DatabaseError, ValidationError};
+use reqwest::Url;
+use rusqlite::Connection;
+use std::collections::HashMap;
+
+/// Query params that only track where a click came from
+fn is_tracking_param(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    name.starts_with("utm_") || name == "ref" || name.starts_with("ref_") || name == "source"
+}
+
+/// Canonical form of a posting URL: lowercase host, no tracking params, no trailing
+/// slash. Returns `None` for anything that isn't an http(s) URL.
+pub fn normalize_job_url(url: &str) -> Option<String> {
+    let mut parsed = Url::parse(url.trim()).ok()?;
+    if !matches!(parsed.scheme(), "http" | "https") || parsed.host_str().is_none() {
+        return None;
+    }
+
+    let has_tracking = parsed.query_pairs().any(|(name, _)| is_tracking_param(&name));
+    if has_tracking {
+        let kept: Vec<(String, String)> = parsed
+            .query_pairs()
+            .filter(|(name, _)| !is_tracking_param(name))
+            .map(|(name, value)| (name.into_owned(), value.into_owned()))
+            .collect();
+        if kept.is_empty() {
+            parsed.set_query(None);
+        } else {
+            parsed.query_pairs_mut().clear().extend_pairs(kept);
+        }
+    }
+    if parsed.query() == Some("") {
+        parsed.set_query(None);
+    }
+
+    let path = parsed.path().trim_end_matches('/').to_string();
+    parsed.set_path(&path);
+
+    let mut normalized = parsed.to_string();
+    // An empty path serializes as "/"; drop it so bare hosts match the rest
+    if parsed.path() == "/" && parsed.query().is_none() && parsed.fragment().is_none() {
+        normalized.truncate(normalized.trim_end_matches('/').len());
+    }
+    Some(normalized)
+}
+
+/// Form of a posting URL for spotting likely duplicates: the normalized URL without
+/// its fragment. Non-tracking query params are kept, since ATS links such as
+/// `job_app?for=acme&token=123` or `?gh_jid=7` identify the posting by them.
+pub fn duplicate_check_url(url: &str) -> Option<String> {
+    let mut parsed = Url::parse(&normalize_job_url(url)?).ok()?;
+    parsed.set_fragment(None);
+    let mut stripped = parsed.to_string();
+    stripped.truncate(stripped.trim_end_matches('/').len());
+    Some(stripped)
+}
+
+fn table_exists(conn: &Connection, table: &str) -> Result<bool, CareerBenchError> {
+    let count: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name = ?",
+        [table],
+        |row| row.get(0),
+    )?;
+    Ok(count > 0)
+}
+
+/// Tables and columns that point at a job, moved to the kept job on merge
+const JOB_REFERENCES: &[(&str, &str)] = &[
+    ("applications", "job_id"),
+    ("artifacts", "job_id"),
+    ("learning_plans", "target_job_id"),
+    ("recruiter_interactions", "linked_job_id"),
+    ("pending_generations", "job_id"),
+];
+
+/// Fold `duplicate_id` into `keep_id`.
+///
+/// Everything linked to the duplicate moves to the kept job, fields the kept job is
+/// missing are copied over, and the duplicate is deleted.
+pub fn merge_jobs(conn: &Connection, keep_id: i64, duplicate_id: i64) -> Result<(), CareerBenchError> {
+    if keep_id == duplicate_id {
+        return Err(ValidationError::BusinessRule("Cannot merge a job into itself".to_string()).into());
+    }
+    for id in [keep_id, duplicate_id] {
+        let exists: i64 = conn.query_row("SELECT COUNT(*) FROM jobs WHERE id = ?", [id], |row| row.get(0))?;
+        if exists == 0 {
+            return Err(DatabaseError::NotFound(format!("Job {} not found", id)).into());
+        }
+    }
+
+    let tx = conn.unchecked_transaction()?;
+
+    let fill_columns = [
+        "title",
+        "company",
+        "location",
+        "job_source",
+        "raw_description",
+        "parsed_json",
+        "seniority",
+        "domain_tags",
+        "salary_text",
+        "application_deadline",
+    ];
+    let mut assignments: Vec<String> = fill_columns
+        .iter()
+        .map(|column| {
+            format!(
+                "{0} = COALESCE(NULLIF({0}, ''), (SELECT {0} FROM jobs WHERE id = ?2))",
+                column
+            )
+        })
+        .collect();
+    assignments.push("company_id = COALESCE(company_id, (SELECT company_id FROM jobs WHERE id = ?2))".to_string());
+    assignments.push("is_active = MAX(is_active, (SELECT is_active FROM jobs WHERE id = ?2))".to_string());
+    tx.execute(
+        &format!("UPDATE jobs SET {} WHERE id = ?1", assignments.join(", ")),
+        rusqlite::params![keep_id, duplicate_id],
+    )?;
+
+    for (table, column) in JOB_REFERENCES {
+        if table_exists(&tx, table)? {
+            tx.execute(
+                &format!("UPDATE {} SET {} = ? WHERE {} = ?", table, column, column),
+                rusqlite::params![keep_id, duplicate_id],
+            )?;
+        }
+    }
+
+    // A tag on both jobs would collide on the (job_id, tag) key, so keep one copy
+    if table_exists(&tx, "job_tags")? {
+        tx.execute(
+            "UPDATE OR IGNORE job_tags SET job_id = ? WHERE job_id = ?",
+            rusqlite::params![keep_id, duplicate_id],
+        )?;
+        tx.execute("DELETE FROM job_tags WHERE job_id = ?", [duplicate_id])?;
+    }
+
+    tx.execute("DELETE FROM jobs WHERE id = ?", [duplicate_id])?;
+    tx.commit()?;
+    Ok(())
+}
+
+/// Rewrite every posting URL into its normalized form, merging jobs whose URLs
+/// collapse together into the oldest of them.
+///
+/// Returns how many jobs were rewritten or merged away; running it again returns 0.
+pub fn normalize_job_urls(conn: &Connection) -> Result<u64, CareerBenchError> {
+    let jobs: Vec<(i64, String)> = {
+        let mut stmt = conn.prepare(
+            "SELECT id, posting_url FROM jobs WHERE posting_url IS NOT NULL AND TRIM(posting_url) != '' ORDER BY id ASC",
+        )?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.collect::<Result<_, _>>()?
+    };
+
+    let mut changed = 0u64;
+    let mut kept_by_url: HashMap<String, i64> = HashMap::new();
+    for (id, posting_url) in jobs {
+        // Unparseable URLs are left alone but still dedupe against exact matches
+        let normalized = normalize_job_url(&posting_url).unwrap_or_else(|| posting_url.trim().to_string());
+
+        if let Some(keep_id) = kept_by_url.get(&normalized) {
+            merge_jobs(conn, *keep_id, id)?;
+            changed += 1;
+            continue;
+        }
+
+        if normalized != posting_url {
+            conn.execute("UPDATE jobs SET posting_url = ? WHERE id = ?", rusqlite::params![normalized, id])?;
+            changed += 1;
+        }
+        kept_by_url.insert(normalized, id);
+    }
+
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_job_url() {
+        assert_eq!(
+            normalize_job_url("https://Jobs.Example.COM/roles/42/?utm_source=linkedin&ref=feed&gh_jid=7").as_deref(),
+            Some("https://jobs.example.com/roles/42?gh_jid=7")
+        );
+        assert_eq!(
+            normalize_job_url("https://jobs.example.com/roles/42?utm_medium=email").as_deref(),
+            Some("https://jobs.example.com/roles/42")
+        );
+        assert_eq!(normalize_job_url("https://example.com/").as_deref(), Some("https://example.com"));
+        assert_eq!(
+            normalize_job_url("https://jobs.example.com/roles/42?gh_jid=7").as_deref(),
+            Some("https://jobs.example.com/roles/42?gh_jid=7")
+        );
+        assert_eq!(normalize_job_url("not a url"), None);
+        assert_eq!(normalize_job_url("mailto:jobs@example.com"), None);
+    }
+
+    #[test]
+    fn test_duplicate_check_url_keeps_posting_params() {
+        assert_eq!(
+            duplicate_check_url("https://jobs.example.com/roles/42/?source=linkedin#apply"),
+            duplicate_check_url("https://jobs.example.com/roles/42?utm_campaign=spring")
+        );
+        assert_ne!(
+            duplicate_check_url("https://boards.greenhouse.io/embed/job_app?for=acme&token=101"),
+            duplicate_check_url("https://boards.greenhouse.io/embed/job_app?for=acme&token=202")
+        );
+        assert_ne!(
+            duplicate_check_url("https://acme.com/careers?gh_jid=101"),
+            duplicate_check_url("https://acme.com/careers?gh_jid=202")
+        );
+    }
+}
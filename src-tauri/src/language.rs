@@ -0,0 +1,199 @@
+//! Lightweight language detection for job descriptions and generated documents
+//!
+//! Uses stopword frequencies rather than a full language model: common function
+//! words are distinctive enough to tell the supported languages apart in a few
+//! sentences of text.
+
+use crate::app_settings::{get_setting, set_setting};
+use crate::db::get_connection;
+use serde::{Deserialize, Serialize};
+
+/// Settings key for the language generated documents are written in
+pub const OUTPUT_LANGUAGE_SETTING: &str = "output_language";
+
+/// Minimum stopword hits before we trust a detection
+const MIN_STOPWORD_HITS: usize = 5;
+/// The winning language must have at least this many times the runner-up's hits
+const MIN_LEAD_RATIO: f64 = 1.5;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Lang {
+    English,
+    German,
+    French,
+    Spanish,
+    Dutch,
+}
+
+impl Lang {
+    pub const ALL: [Lang; 5] = [Lang::English, Lang::German, Lang::French, Lang::Spanish, Lang::Dutch];
+
+    /// ISO 639-1 code
+    pub fn code(&self) -> &'static str {
+        match self {
+            Lang::English => "en",
+            Lang::German => "de",
+            Lang::French => "fr",
+            Lang::Spanish => "es",
+            Lang::Dutch => "nl",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Lang::English => "English",
+            Lang::German => "German",
+            Lang::French => "French",
+            Lang::Spanish => "Spanish",
+            Lang::Dutch => "Dutch",
+        }
+    }
+
+    /// Parse an ISO code or English language name ("de", "German")
+    pub fn parse(value: &str) -> Option<Lang> {
+        let value = value.trim().to_lowercase();
+        Lang::ALL
+            .into_iter()
+            .find(|lang| lang.code() == value || lang.name().to_lowercase() == value)
+    }
+
+    fn stopwords(&self) -> &'static [&'static str] {
+        match self {
+            Lang::English => &[
+                "the", "and", "of", "to", "in", "is", "you", "with", "for", "our", "we", "will", "are",
+                "on", "as", "be", "this", "that", "your", "have",
+            ],
+            Lang::German => &[
+                "der", "die", "das", "und", "ist", "mit", "für", "wir", "sie", "ein", "eine", "zu",
+                "von", "den", "im", "auf", "bei", "unser", "unsere", "nicht", "sich", "oder",
+            ],
+            Lang::French => &[
+                "le", "la", "les", "et", "des", "est", "un", "une", "pour", "nous", "vous", "avec",
+                "dans", "du", "sur", "au", "notre", "votre", "sont", "qui",
+            ],
+            Lang::Spanish => &[
+                "el", "la", "los", "las", "y", "es", "un", "una", "para", "con", "nuestro",
+                "nuestra", "del", "por", "que", "en", "somos", "buscamos", "tu", "su",
+            ],
+            Lang::Dutch => &[
+                "de", "het", "een", "en", "van", "is", "wij", "we", "jij", "je", "met", "voor",
+                "onze", "ons", "bij", "op", "zijn", "niet", "naar", "ook",
+            ],
+        }
+    }
+}
+
+/// Detect the dominant language of `text`, or None when it is too short or ambiguous
+pub fn detect_language(text: &str) -> Option<Lang> {
+    let words: Vec<String> = text
+        .split(|c: char| !c.is_alphabetic())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+        .collect();
+
+    let mut scores: Vec<(Lang, usize)> = Lang::ALL
+        .into_iter()
+        .map(|lang| {
+            let stopwords = lang.stopwords();
+            let hits = words.iter().filter(|word| stopwords.contains(&word.as_str())).count();
+            (lang, hits)
+        })
+        .collect();
+    scores.sort_by(|a, b| b.1.cmp(&a.1));
+
+    let (best, best_hits) = scores[0];
+    let runner_up_hits = scores[1].1;
+    if best_hits < MIN_STOPWORD_HITS || (best_hits as f64) < runner_up_hits as f64 * MIN_LEAD_RATIO {
+        return None;
+    }
+    Some(best)
+}
+
+/// Warn when the job description is clearly in a different language than the output language
+pub fn language_mismatch_warning(job_description: &str, output_language: Lang) -> Option<String> {
+    match detect_language(job_description) {
+        Some(job_language) if job_language != output_language => Some(format!(
+            "This job description appears to be in {} but documents are generated in {}. Change the output language in settings if you want to apply in {}.",
+            job_language.name(),
+            output_language.name(),
+            job_language.name()
+        )),
+        _ => None,
+    }
+}
+
+/// Prompt suffix asking the model to write in `language`; empty when no language is set
+pub fn output_language_instruction(language: Option<Lang>) -> String {
+    match language {
+        Some(lang) => format!(" Write all text in {}.", lang.name()),
+        None => String::new(),
+    }
+}
+
+/// Load the configured output language, defaulting to English
+pub fn load_output_language() -> Lang {
+    get_connection()
+        .map(|conn| load_output_language_with_conn(&conn))
+        .unwrap_or(Lang::English)
+}
+
+pub fn load_output_language_with_conn(conn: &rusqlite::Connection) -> Lang {
+    get_setting(conn, OUTPUT_LANGUAGE_SETTING)
+        .ok()
+        .flatten()
+        .and_then(|value| Lang::parse(&value))
+        .unwrap_or(Lang::English)
+}
+
+/// Save the output language (ISO code or name)
+pub fn save_output_language(language: &str) -> Result<Lang, String> {
+    let lang = Lang::parse(language).ok_or_else(|| {
+        let supported: Vec<&str> = Lang::ALL.iter().map(|lang| lang.code()).collect();
+        format!("Unsupported language '{}'. Supported: {}", language, supported.join(", "))
+    })?;
+    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    set_setting(&conn, OUTPUT_LANGUAGE_SETTING, lang.code()).map_err(|e| e.to_string_for_tauri())?;
+    Ok(lang)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ENGLISH_JD: &str = "We are looking for a backend engineer to join our platform team. You will design and build the services that power our payments product, and you will work closely with the product team on the roadmap.";
+    const GERMAN_JD: &str = "Wir suchen eine engagierte Softwareentwicklerin oder einen Softwareentwickler für unser Team in Berlin. Du arbeitest mit der Produktabteilung an der Weiterentwicklung unserer Plattform und bist für die Qualität des Codes verantwortlich. Sie haben Erfahrung mit Rust und sind ein Teamplayer.";
+
+    #[test]
+    fn test_detects_english_and_german() {
+        assert_eq!(detect_language(ENGLISH_JD), Some(Lang::English));
+        assert_eq!(detect_language(GERMAN_JD), Some(Lang::German));
+    }
+
+    #[test]
+    fn test_short_text_is_undetected() {
+        assert_eq!(detect_language("Senior Rust Engineer"), None);
+        assert_eq!(detect_language(""), None);
+    }
+
+    #[test]
+    fn test_mismatch_warning() {
+        let warning = language_mismatch_warning(GERMAN_JD, Lang::English).expect("mismatch warning");
+        assert!(warning.contains("German"));
+        assert!(language_mismatch_warning(GERMAN_JD, Lang::German).is_none());
+        assert!(language_mismatch_warning(ENGLISH_JD, Lang::English).is_none());
+    }
+
+    #[test]
+    fn test_output_language_instruction() {
+        assert_eq!(output_language_instruction(Some(Lang::German)), " Write all text in German.");
+        assert_eq!(output_language_instruction(None), "");
+    }
+
+    #[test]
+    fn test_parse_lang() {
+        assert_eq!(Lang::parse("de"), Some(Lang::German));
+        assert_eq!(Lang::parse(" English "), Some(Lang::English));
+        assert_eq!(Lang::parse("klingon"), None);
+    }
+}
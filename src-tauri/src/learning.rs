@@ -485,7 +485,7 @@ pub async fn generate_learning_content(
 ) -> Result<(), CareerBenchError> {
     use crate::ai::resolver::ResolvedProvider;
 
-    let provider = ResolvedProvider::resolve()
+    let provider = ResolvedProvider::resolve_for("learning_content")
         .map_err(|e| CareerBenchError::Configuration(crate::errors::ConfigurationError::Other(
             format!("Failed to resolve AI provider: {}", e)
         )))?;
@@ -11,16 +11,49 @@ pub mod logging;
 pub mod encryption;
 pub mod secure_storage;
 pub mod data_export;
+pub mod data_import;
+pub mod job_match;
 pub mod data_deletion;
 pub mod local_storage;
 pub mod profile_import;
 pub mod job_scraper;
 pub mod calendar;
+pub mod ics;
 pub mod reminders;
+pub mod recurrence;
 pub mod portfolio_export;
 pub mod analytics;
 pub mod email;
+pub mod email_matching;
+pub mod email_signals;
+pub mod email_sync;
 pub mod learning;
 pub mod recruiter_crm;
 pub mod companies;
+pub mod app_settings;
+pub mod compensation;
+pub mod text_diff;
+pub mod pdf_writer;
+pub mod resume_export;
+pub mod job_deadlines;
+pub mod job_benefits;
+pub mod resume_checks;
+pub mod language;
+pub mod application_priority;
+pub mod company_taxonomy;
+pub mod job_tags;
+pub mod weekly_digest;
+pub mod support_bundle;
+pub mod interview_debriefs;
+pub mod generation_queue;
+pub mod job_tldr;
+pub mod apply_link;
+pub mod job_urls;
+pub mod cover_letter_adapt;
+pub mod bulk_parse;
+pub mod event_dedupe;
+pub mod next_action;
+pub mod dashboard_funnel;
+pub mod application_status;
+pub mod generation_context;
 
@@ -121,6 +121,9 @@ pub fn log_careerbench_error(context: &str, error: &crate::errors::CareerBenchEr
         crate::errors::CareerBenchError::FileSystem(fs_err) => {
             log::error!("[{}] File system error: {:?}", context, fs_err);
         }
+        crate::errors::CareerBenchError::Email(email_err) => {
+            log::error!("[{}] Email error: {:?}", context, email_err);
+        }
         crate::errors::CareerBenchError::Application(msg) => {
             log::error!("[{}] Application error: {}", context, msg);
         }
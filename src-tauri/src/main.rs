@@ -13,18 +13,51 @@ mod error_logging;
 mod encryption;
 mod secure_storage;
 mod data_export;
+mod data_import;
+mod job_match;
 mod data_deletion;
 mod local_storage;
 mod profile_import;
 mod job_scraper;
 mod calendar;
+mod ics;
 mod reminders;
+mod recurrence;
 mod portfolio_export;
 mod analytics;
 mod email;
+mod email_matching;
+mod email_signals;
+mod email_sync;
 mod learning;
 mod recruiter_crm;
 mod companies;
+mod app_settings;
+mod compensation;
+mod text_diff;
+mod pdf_writer;
+mod resume_export;
+mod job_deadlines;
+mod job_benefits;
+mod resume_checks;
+mod language;
+mod application_priority;
+mod company_taxonomy;
+mod job_tags;
+mod weekly_digest;
+mod support_bundle;
+mod interview_debriefs;
+mod generation_queue;
+mod job_tldr;
+mod apply_link;
+mod job_urls;
+mod cover_letter_adapt;
+mod bulk_parse;
+mod event_dedupe;
+mod next_action;
+mod dashboard_funnel;
+mod application_status;
+mod generation_context;
 
 use db::init_database;
 
@@ -51,26 +84,75 @@ async fn main() {
         .plugin(tauri_plugin_notification::init())
         .invoke_handler(tauri::generate_handler![
             commands::get_dashboard_data,
+            commands::get_dashboard_funnel,
+            commands::save_dashboard_funnel,
             commands::get_user_profile_data,
             commands::save_user_profile_data,
+            commands::apply_profile_patch,
+            commands::convert_to_star,
+            commands::split_experience,
+            commands::preview_role_selection,
+            commands::tighten_content,
             commands::create_job,
+            commands::import_job_payload,
             commands::update_job,
+            commands::archive_stale_jobs,
+            commands::normalize_job_urls,
             commands::get_job_list,
             commands::get_job_detail,
+            commands::build_apply_url,
+            commands::get_apply_link_tracking,
+            commands::save_apply_link_tracking,
+            commands::get_related_jobs,
+            commands::find_similar_cover_letters,
+            commands::suggest_skills_to_hide,
+            commands::summarize_job_tldr,
             commands::parse_job_with_ai,
+            commands::get_jobs_needing_parse,
+            commands::parse_all_pending,
+            commands::auto_tag_jobs_offline,
+            commands::add_job_tag,
+            commands::remove_job_tag,
+            commands::get_all_tags,
             commands::create_application,
             commands::update_application,
+            commands::bulk_update_application_status,
             commands::get_applications,
+            commands::export_applications_csv,
+            commands::reorder_applications,
+            commands::get_overdue_actions,
             commands::get_application_detail,
             commands::add_application_event,
+            commands::dedupe_application_events,
             commands::archive_application,
+            commands::unarchive_application,
             commands::generate_resume_for_job,
             commands::generate_cover_letter_for_job,
+            commands::generate_cover_letter_variants,
+            commands::adapt_cover_letter,
+            commands::generate_application_documents,
+            commands::queue_generation,
+            commands::get_pending_generations,
+            commands::retry_pending_generation,
+            commands::process_pending_generations,
+            commands::get_generation_max_attempts,
+            commands::save_generation_max_attempts,
             commands::ai_resume_suggestions,
             commands::ai_cover_letter,
             commands::ai_skill_suggestions,
             commands::get_ai_settings,
             commands::save_ai_settings,
+            commands::get_prompt_token_warn_threshold,
+            commands::save_prompt_token_warn_threshold,
+            commands::get_scrub_pii_before_cloud,
+            commands::save_scrub_pii_before_cloud,
+            commands::get_low_match_warn_threshold,
+            commands::save_low_match_warn_threshold,
+            commands::get_output_language,
+            commands::save_output_language,
+            commands::get_resume_date_format,
+            commands::save_resume_date_format,
+            commands::list_resume_templates,
             commands::rotate_api_key,
             commands::get_api_key_metadata,
             commands::check_api_key_rotation_needed,
@@ -79,17 +161,25 @@ async fn main() {
             commands::get_artifacts_for_application,
             commands::get_artifacts_for_job,
             commands::get_artifact,
+            commands::search_artifacts,
             commands::update_artifact,
             commands::update_artifact_title,
+            commands::diff_artifact_versions,
             commands::save_resume,
+            commands::export_resume_pdf,
+            commands::export_resume_docx,
+            commands::export_cover_letter_docx,
+            commands::analyze_resume_keyword_coverage,
             commands::save_cover_letter,
             commands::generate_profile_summary,
             commands::extract_skills_from_experience,
             commands::rewrite_portfolio_description,
             commands::export_all_data,
+            commands::validate_import_json,
             commands::delete_job,
             commands::delete_application,
             commands::delete_artifact,
+            commands::prune_empty_artifacts,
             commands::delete_profile_section,
             commands::delete_all_user_data,
             commands::get_deletion_summary,
@@ -100,31 +190,65 @@ async fn main() {
             commands::extract_profile_from_resume,
             commands::scrape_job_url,
             commands::get_cache_stats,
+            commands::get_cache_report,
             commands::clear_cache_by_purpose,
+            commands::expire_cache_purpose_soon,
             commands::clear_all_cache,
             commands::cleanup_expired_cache,
             commands::evict_cache_by_size,
             commands::evict_cache_by_count,
+            commands::get_cache_ttls,
+            commands::get_semantic_cache_config,
+            commands::save_semantic_cache_config,
             commands::export_dashboard_data,
+            commands::export_dashboard_json,
+            commands::export_ai_usage_csv,
+            commands::get_ai_usage_summary,
             commands::get_calendar_events,
+            commands::export_calendar_ics,
             commands::get_events_for_date,
+            commands::get_upcoming_actions,
             commands::sync_interview_to_calendar,
             commands::create_reminder,
             commands::get_reminders,
             commands::get_due_reminders,
             commands::get_reminders_for_application,
             commands::mark_reminder_sent,
+            commands::snooze_reminder,
             commands::delete_reminder,
+            commands::get_quiet_hours,
+            commands::save_quiet_hours,
+            commands::get_follow_up_rules,
+            commands::save_follow_up_rules,
             commands::export_portfolio_html,
             commands::export_portfolio_markdown,
             commands::export_portfolio_text,
+            commands::export_portfolio_pdf,
             commands::get_portfolio_for_application,
             commands::link_portfolio_to_application,
+            commands::bulk_link_portfolio,
             commands::get_applications_for_portfolio,
             commands::get_conversion_rates,
             commands::get_time_in_stage,
             commands::get_channel_effectiveness,
             commands::get_analytics_insights,
+            commands::get_time_to_offer_stats,
+            commands::estimate_offer_probability,
+            commands::calculate_response_times,
+            commands::get_application_age_distribution,
+            commands::generate_weekly_digest,
+            commands::get_next_best_action,
+            commands::generate_support_bundle,
+            commands::rank_saved_jobs,
+            commands::get_offer_comparison,
+            commands::get_currency_rates,
+            commands::save_currency_rates,
+            commands::get_salary_expectation,
+            commands::save_salary_expectation,
+            commands::suggest_application_priority,
+            commands::accept_priority_suggestion,
+            commands::save_interview_debrief,
+            commands::get_debriefs_for_application,
             commands::save_email_account,
             commands::get_email_accounts,
             commands::delete_email_account,
@@ -133,6 +257,10 @@ async fn main() {
             commands::get_email_messages_for_thread,
             commands::test_email_connection,
             commands::sync_email_account,
+            commands::auto_link_email_threads,
+            commands::suggest_status_from_emails,
+            commands::get_email_signal_patterns,
+            commands::save_email_signal_patterns,
             commands::analyze_skill_gaps,
             commands::create_learning_plan,
             commands::get_learning_plans,
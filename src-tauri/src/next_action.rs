@@ -0,0 +1,296 @@
+//! A single "what should I do next" recommendation across the whole app
+//!
+//! Each candidate gets a fixed base weight for its kind plus an urgency bonus, and the
+//! highest score wins. Ties go to the kind listed first below, then to the earliest
+//! date, so the same data always gives the same answer.
+//!
+//! | Kind                      | Score                                         |
+//! |---------------------------|-----------------------------------------------|
+//! | Overdue reminder          | 90 + days overdue (max +9)                    |
+//! | Application deadline      | 70 + 2 per day closer than a week (max +16)   |
+//! | Due follow-up             | 60 + days overdue (max +9)                    |
+//! | Stale high-priority app   | 50 + weeks past the stale threshold (max +9)  |
+//! | Learning goal shortfall   | 20 + tenths of the plan left to do (max +10)  |
+//! | All caught up             | 0                                             |
+
+use crate::errors::CareerBenchError;
+use crate::reminders::get_due_reminders_with_conn;
+use crate::weekly_digest::{find_stale_applications, get_goal_progress, STALE_AFTER_DAYS};
+use chrono::{NaiveDate, NaiveDateTime};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+
+const OVERDUE_REMINDER_WEIGHT: i64 = 90;
+const DEADLINE_WEIGHT: i64 = 70;
+const FOLLOW_UP_WEIGHT: i64 = 60;
+const STALE_APPLICATION_WEIGHT: i64 = 50;
+const GOAL_SHORTFALL_WEIGHT: i64 = 20;
+
+/// Deadlines this many days out (or missed within this many days) are considered
+const DEADLINE_WINDOW_DAYS: i64 = 7;
+/// Urgency bonuses are capped so one kind can't overtake the next weight band
+const MAX_URGENCY_BONUS: i64 = 9;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum NextActionKind {
+    OverdueReminder,
+    ApplicationDeadline,
+    FollowUp,
+    StaleApplication,
+    GoalShortfall,
+    AllCaughtUp,
+}
+
+/// What the UI should open when the user acts on the recommendation
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum NextActionTarget {
+    Application,
+    Job,
+    LearningPlan,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NextAction {
+    pub kind: NextActionKind,
+    pub title: String,
+    /// Why this action was picked, in plain language
+    pub reason: String,
+    pub target: Option<NextActionTarget>,
+    pub target_id: Option<i64>,
+    pub score: i64,
+}
+
+impl NextAction {
+    fn caught_up() -> Self {
+        Self {
+            kind: NextActionKind::AllCaughtUp,
+            title: "You're all caught up".to_string(),
+            reason: "Nothing is overdue or due soon. A good moment to find a new role to apply for.".to_string(),
+            target: None,
+            target_id: None,
+            score: 0,
+        }
+    }
+}
+
+fn parse_date(value: &str) -> Option<NaiveDate> {
+    value.get(..10).and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+}
+
+fn plural_days(days: i64) -> String {
+    if days == 1 {
+        "1 day".to_string()
+    } else {
+        format!("{} days", days)
+    }
+}
+
+fn describe_job(title: Option<&str>, company: Option<&str>) -> String {
+    match (title, company) {
+        (Some(title), Some(company)) => format!("{} at {}", title, company),
+        (Some(title), None) => title.to_string(),
+        (None, Some(company)) => company.to_string(),
+        (None, None) => "Untitled job".to_string(),
+    }
+}
+
+fn overdue_reminders(conn: &Connection, now: NaiveDateTime, today: NaiveDate) -> Result<Vec<NextAction>, CareerBenchError> {
+    let mut actions = Vec::new();
+    for reminder in get_due_reminders_with_conn(conn, now)? {
+        let days_overdue = parse_date(&reminder.reminder_date)
+            .map(|due| (today - due).num_days().max(0))
+            .unwrap_or(0);
+        let message = reminder.message.clone().unwrap_or_else(|| format!("{} reminder", reminder.reminder_type));
+        let reason = if days_overdue == 0 {
+            "This reminder is due now.".to_string()
+        } else {
+            format!("This reminder is {} overdue.", plural_days(days_overdue))
+        };
+        actions.push(NextAction {
+            kind: NextActionKind::OverdueReminder,
+            title: message,
+            reason,
+            target: reminder.application_id.map(|_| NextActionTarget::Application),
+            target_id: reminder.application_id,
+            score: OVERDUE_REMINDER_WEIGHT + days_overdue.min(MAX_URGENCY_BONUS),
+        });
+    }
+    Ok(actions)
+}
+
+/// Deadlines for active jobs that haven't been applied to, from a week ago to a week out
+fn application_deadlines(conn: &Connection, today: NaiveDate) -> Result<Vec<NextAction>, CareerBenchError> {
+    let start = (today - chrono::Duration::days(DEADLINE_WINDOW_DAYS)).format("%Y-%m-%d").to_string();
+    let end = (today + chrono::Duration::days(DEADLINE_WINDOW_DAYS)).format("%Y-%m-%d").to_string();
+    let mut stmt = conn.prepare(
+        "SELECT j.id, j.title, j.company, j.application_deadline
+         FROM jobs j
+         WHERE j.is_active = 1
+           AND j.application_deadline IS NOT NULL
+           AND j.application_deadline >= ? AND j.application_deadline <= ?
+           AND NOT EXISTS (
+               SELECT 1 FROM applications a WHERE a.job_id = j.id AND a.date_applied IS NOT NULL
+           )
+         ORDER BY j.application_deadline ASC, j.id ASC",
+    )?;
+    let rows = stmt.query_map([&start, &end], |row| {
+        Ok((
+            row.get::<_, i64>(0)?,
+            row.get::<_, Option<String>>(1)?,
+            row.get::<_, Option<String>>(2)?,
+            row.get::<_, String>(3)?,
+        ))
+    })?;
+
+    let mut actions = Vec::new();
+    for row_result in rows {
+        let (job_id, title, company, deadline) = row_result?;
+        let deadline = match parse_date(&deadline) {
+            Some(date) => date,
+            None => continue,
+        };
+        let days_left = (deadline - today).num_days();
+        let what = describe_job(title.as_deref(), company.as_deref());
+        let reason = match days_left {
+            d if d < 0 => format!("The application deadline passed {} ago and you haven't applied yet.", plural_days(-d)),
+            0 => "The application deadline is today.".to_string(),
+            d => format!("The application deadline is in {}.", plural_days(d)),
+        };
+        // Missed deadlines count as one day past due: still worth a try before the posting closes
+        let closeness = (DEADLINE_WINDOW_DAYS - days_left).min(DEADLINE_WINDOW_DAYS + 1);
+        actions.push(NextAction {
+            kind: NextActionKind::ApplicationDeadline,
+            title: format!("Apply to {}", what),
+            reason,
+            target: Some(NextActionTarget::Job),
+            target_id: Some(job_id),
+            score: DEADLINE_WEIGHT + 2 * closeness,
+        });
+    }
+    Ok(actions)
+}
+
+/// Applications whose next action date has arrived
+fn due_follow_ups(conn: &Connection, today: NaiveDate) -> Result<Vec<NextAction>, CareerBenchError> {
+    let mut stmt = conn.prepare(
+        "SELECT a.id, a.next_action_date, a.next_action_note, j.title, j.company
+         FROM applications a
+         LEFT JOIN jobs j ON a.job_id = j.id
+         WHERE a.archived = 0
+           AND a.next_action_date IS NOT NULL
+           AND substr(a.next_action_date, 1, 10) <= ?
+           AND a.status NOT IN ('Offer', 'Rejected', 'Ghosted', 'Withdrawn')
+         ORDER BY a.next_action_date ASC, a.id ASC",
+    )?;
+    let rows = stmt.query_map([today.format("%Y-%m-%d").to_string()], |row| {
+        Ok((
+            row.get::<_, i64>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, Option<String>>(2)?,
+            row.get::<_, Option<String>>(3)?,
+            row.get::<_, Option<String>>(4)?,
+        ))
+    })?;
+
+    let mut actions = Vec::new();
+    for row_result in rows {
+        let (application_id, date, note, title, company) = row_result?;
+        let days_overdue = parse_date(&date).map(|due| (today - due).num_days().max(0)).unwrap_or(0);
+        let what = describe_job(title.as_deref(), company.as_deref());
+        let reason = if days_overdue == 0 {
+            format!("Your next step for {} is due today.", what)
+        } else {
+            format!("Your next step for {} was due {} ago.", what, plural_days(days_overdue))
+        };
+        actions.push(NextAction {
+            kind: NextActionKind::FollowUp,
+            title: note
+                .filter(|note| !note.trim().is_empty())
+                .unwrap_or_else(|| format!("Follow up on {}", what)),
+            reason,
+            target: Some(NextActionTarget::Application),
+            target_id: Some(application_id),
+            score: FOLLOW_UP_WEIGHT + days_overdue.min(MAX_URGENCY_BONUS),
+        });
+    }
+    Ok(actions)
+}
+
+fn stale_high_priority_applications(conn: &Connection, today: NaiveDate) -> Result<Vec<NextAction>, CareerBenchError> {
+    let high_priority: Vec<i64> = {
+        let mut stmt = conn.prepare("SELECT id FROM applications WHERE priority = 'High'")?;
+        let rows = stmt.query_map([], |row| row.get(0))?;
+        rows.collect::<Result<_, _>>()?
+    };
+
+    let mut actions = Vec::new();
+    for application in find_stale_applications(conn, today)? {
+        if !high_priority.contains(&application.application_id) {
+            continue;
+        }
+        let weeks_past = (application.days_since_activity - STALE_AFTER_DAYS) / 7;
+        actions.push(NextAction {
+            kind: NextActionKind::StaleApplication,
+            title: format!(
+                "Check in on {}",
+                describe_job(application.job_title.as_deref(), application.company.as_deref())
+            ),
+            reason: format!(
+                "This high-priority application ({}) has had no activity for {}.",
+                application.status,
+                plural_days(application.days_since_activity)
+            ),
+            target: Some(NextActionTarget::Application),
+            target_id: Some(application.application_id),
+            score: STALE_APPLICATION_WEIGHT + weeks_past.clamp(0, MAX_URGENCY_BONUS),
+        });
+    }
+    Ok(actions)
+}
+
+fn goal_shortfalls(conn: &Connection) -> Result<Vec<NextAction>, CareerBenchError> {
+    let mut actions = Vec::new();
+    for goal in get_goal_progress(conn)? {
+        let remaining = goal.total_tasks - goal.completed_tasks;
+        if goal.total_tasks == 0 || remaining <= 0 {
+            continue;
+        }
+        actions.push(NextAction {
+            kind: NextActionKind::GoalShortfall,
+            title: format!("Work on {}", goal.title),
+            reason: format!(
+                "{} of {} tasks left in this learning plan.",
+                remaining, goal.total_tasks
+            ),
+            target: Some(NextActionTarget::LearningPlan),
+            target_id: Some(goal.plan_id),
+            score: GOAL_SHORTFALL_WEIGHT + remaining * 10 / goal.total_tasks,
+        });
+    }
+    Ok(actions)
+}
+
+/// The single highest-priority action, or an "all caught up" action when there is nothing to do.
+///
+/// `now` is UTC (reminder times are stored in UTC); `today` is the user's local date.
+pub fn get_next_best_action(conn: &Connection, now: NaiveDateTime, today: NaiveDate) -> Result<NextAction, CareerBenchError> {
+    let mut candidates = overdue_reminders(conn, now, today)?;
+    candidates.extend(application_deadlines(conn, today)?);
+    candidates.extend(due_follow_ups(conn, today)?);
+    candidates.extend(stale_high_priority_applications(conn, today)?);
+    candidates.extend(goal_shortfalls(conn)?);
+
+    // Keep the first of equal scores so kind order and date order break ties
+    let mut best: Option<NextAction> = None;
+    for candidate in candidates {
+        let better = match &best {
+            Some(current) => candidate.score > current.score,
+            None => true,
+        };
+        if better {
+            best = Some(candidate);
+        }
+    }
+    Ok(best.unwrap_or_else(NextAction::caught_up))
+}
@@ -0,0 +1,369 @@
+//! Minimal dependency-free PDF writer used by the document exporters
+//!
+//! Produces uncompressed PDF 1.4 documents using the standard Helvetica fonts,
+//! with simple word wrapping, automatic page breaks and per-page footnotes.
+//! Text is WinAnsi encoded; characters outside it are approximated in ASCII.
+
+/// US Letter page size in points
+const PAGE_WIDTH: f32 = 612.0;
+const PAGE_HEIGHT: f32 = 792.0;
+const MARGIN: f32 = 54.0;
+
+/// Line height as a multiple of the font size
+const LINE_SPACING: f32 = 1.35;
+const FOOTNOTE_SIZE: f32 = 8.0;
+
+/// Average Helvetica glyph width as a fraction of the font size, used for wrapping
+const AVG_CHAR_WIDTH: f32 = 0.5;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PdfFont {
+    Regular,
+    Bold,
+}
+
+impl PdfFont {
+    fn resource_name(&self) -> &'static str {
+        match self {
+            PdfFont::Regular => "F1",
+            PdfFont::Bold => "F2",
+        }
+    }
+}
+
+#[derive(Default)]
+struct PdfPage {
+    content: Vec<u8>,
+    footnotes: Vec<String>,
+}
+
+/// A document built top to bottom; call `finish` to get the PDF bytes
+pub struct PdfDocument {
+    pages: Vec<PdfPage>,
+    cursor_y: f32,
+}
+
+impl Default for PdfDocument {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PdfDocument {
+    pub fn new() -> Self {
+        Self {
+            pages: vec![PdfPage::default()],
+            cursor_y: PAGE_HEIGHT - MARGIN,
+        }
+    }
+
+    fn current_page(&mut self) -> &mut PdfPage {
+        self.pages.last_mut().expect("document always has a page")
+    }
+
+    /// Lowest y position body text may use on the current page (above its footnotes)
+    fn bottom_limit(&self) -> f32 {
+        let footnotes = self.pages.last().map(|p| p.footnotes.len()).unwrap_or(0);
+        let reserved = if footnotes > 0 {
+            (footnotes as f32 + 1.0) * FOOTNOTE_SIZE * LINE_SPACING
+        } else {
+            0.0
+        };
+        MARGIN + reserved
+    }
+
+    fn new_page(&mut self) {
+        self.pages.push(PdfPage::default());
+        self.cursor_y = PAGE_HEIGHT - MARGIN;
+    }
+
+    /// Break to a new page unless `height` points of body space remain
+    pub fn ensure_space(&mut self, height: f32) {
+        if self.cursor_y - height < self.bottom_limit() {
+            self.new_page();
+        }
+    }
+
+    /// Add vertical space
+    pub fn spacer(&mut self, height: f32) {
+        self.cursor_y -= height;
+    }
+
+    /// Write wrapped text at the current position
+    pub fn text(&mut self, text: &str, font: PdfFont, size: f32) {
+        self.text_indented(text, font, size, 0.0);
+    }
+
+    /// Write wrapped text indented from the left margin
+    pub fn text_indented(&mut self, text: &str, font: PdfFont, size: f32, indent: f32) {
+        let line_height = size * LINE_SPACING;
+        let max_width = PAGE_WIDTH - 2.0 * MARGIN - indent;
+
+        for paragraph in text.lines() {
+            let lines = wrap_text(paragraph, size, max_width);
+            if lines.is_empty() {
+                self.spacer(line_height);
+                continue;
+            }
+            for line in lines {
+                self.ensure_space(line_height);
+                self.cursor_y -= size;
+                let y = self.cursor_y;
+                write_text_op(&mut self.current_page().content, &line, font, size, MARGIN + indent, y);
+                self.cursor_y -= line_height - size;
+            }
+        }
+    }
+
+    /// Write a line followed by a footnote marker, keeping both on the same page
+    pub fn text_with_footnote(&mut self, text: &str, font: PdfFont, size: f32, note: &str) {
+        self.ensure_space(size * LINE_SPACING + FOOTNOTE_SIZE * LINE_SPACING);
+        let marker = self.footnote(note);
+        self.text(&format!("{} [{}]", text, marker), font, size);
+    }
+
+    /// Attach a footnote to the current page and return its marker number (1-based, per page)
+    pub fn footnote(&mut self, text: &str) -> usize {
+        // Make sure the footnote still fits below the body text written so far
+        if self.cursor_y - FOOTNOTE_SIZE * LINE_SPACING < self.bottom_limit() {
+            self.new_page();
+        }
+        let page = self.current_page();
+        page.footnotes.push(text.to_string());
+        page.footnotes.len()
+    }
+
+    /// Render the document to PDF bytes
+    pub fn finish(mut self) -> Vec<u8> {
+        for page in &mut self.pages {
+            render_footnotes(page);
+        }
+
+        let page_count = self.pages.len();
+        // Object layout: 1 catalog, 2 page tree, 3-4 fonts, then (page, content) pairs
+        let page_object_id = |idx: usize| 5 + idx * 2;
+
+        let mut objects: Vec<Vec<u8>> = Vec::new();
+        objects.push(b"<< /Type /Catalog /Pages 2 0 R >>".to_vec());
+
+        let kids: Vec<String> = (0..page_count)
+            .map(|idx| format!("{} 0 R", page_object_id(idx)))
+            .collect();
+        objects.push(
+            format!("<< /Type /Pages /Kids [{}] /Count {} >>", kids.join(" "), page_count).into_bytes(),
+        );
+        objects.push(
+            b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>".to_vec(),
+        );
+        objects.push(
+            b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>"
+                .to_vec(),
+        );
+
+        for (idx, page) in self.pages.iter().enumerate() {
+            objects.push(
+                format!(
+                    "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] \
+                     /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+                    PAGE_WIDTH,
+                    PAGE_HEIGHT,
+                    page_object_id(idx) + 1
+                )
+                .into_bytes(),
+            );
+
+            let mut stream = format!("<< /Length {} >>\nstream\n", page.content.len()).into_bytes();
+            stream.extend_from_slice(&page.content);
+            stream.extend_from_slice(b"\nendstream");
+            objects.push(stream);
+        }
+
+        let mut output = b"%PDF-1.4\n".to_vec();
+        let mut offsets = Vec::with_capacity(objects.len());
+        for (idx, object) in objects.iter().enumerate() {
+            offsets.push(output.len());
+            output.extend_from_slice(format!("{} 0 obj\n", idx + 1).as_bytes());
+            output.extend_from_slice(object);
+            output.extend_from_slice(b"\nendobj\n");
+        }
+
+        let xref_offset = output.len();
+        output.extend_from_slice(format!("xref\n0 {}\n", objects.len() + 1).as_bytes());
+        output.extend_from_slice(b"0000000000 65535 f \n");
+        for offset in offsets {
+            output.extend_from_slice(format!("{:010} 00000 n \n", offset).as_bytes());
+        }
+        output.extend_from_slice(
+            format!(
+                "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+                objects.len() + 1,
+                xref_offset
+            )
+            .as_bytes(),
+        );
+
+        output
+    }
+}
+
+/// Draw a page's footnotes at the bottom margin
+fn render_footnotes(page: &mut PdfPage) {
+    let line_height = FOOTNOTE_SIZE * LINE_SPACING;
+    let mut y = MARGIN + (page.footnotes.len() as f32 - 1.0) * line_height;
+    let notes = std::mem::take(&mut page.footnotes);
+    for (idx, note) in notes.iter().enumerate() {
+        let line = format!("[{}] {}", idx + 1, note);
+        write_text_op(&mut page.content, &line, PdfFont::Regular, FOOTNOTE_SIZE, MARGIN, y);
+        y -= line_height;
+    }
+    page.footnotes = notes;
+}
+
+/// Greedy word wrap using an average glyph width estimate
+fn wrap_text(text: &str, size: f32, max_width: f32) -> Vec<String> {
+    let max_chars = ((max_width / (size * AVG_CHAR_WIDTH)) as usize).max(1);
+    let mut lines = Vec::new();
+    let mut current = String::new();
+
+    for word in text.split_whitespace() {
+        let needed = if current.is_empty() {
+            word.chars().count()
+        } else {
+            current.chars().count() + 1 + word.chars().count()
+        };
+        if needed > max_chars && !current.is_empty() {
+            lines.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+fn write_text_op(content: &mut Vec<u8>, text: &str, font: PdfFont, size: f32, x: f32, y: f32) {
+    content.extend_from_slice(
+        format!("BT /{} {} Tf {:.2} {:.2} Td (", font.resource_name(), size, x, y).as_bytes(),
+    );
+    content.extend_from_slice(&encode_pdf_string(text));
+    content.extend_from_slice(b") Tj ET\n");
+}
+
+/// Base letters of Latin Extended-A (U+0100..=U+017F), for accented letters WinAnsi lacks
+const LATIN_EXTENDED_A_BASE: &[u8; 128] =
+    b"AaAaAaCcCcCcCcDdDdEeEeEeEeEeGgGgGgGgHhHhIiIiIiIiIiIiJjKkkLlLlLlLlLlNnNnNnnNnOoOoOoOoRrRrRrSsSsSsSsTtTtTtUuUuUuUuUuUuWwYyYZzZzZzs";
+
+/// Encode text as a WinAnsi PDF literal string body, escaping delimiters
+fn encode_pdf_string(text: &str) -> Vec<u8> {
+    let mut encoded = Vec::with_capacity(text.len());
+    for c in text.chars() {
+        win_ansi_bytes(c, &mut encoded);
+    }
+
+    let mut bytes = Vec::with_capacity(encoded.len());
+    for byte in encoded {
+        match byte {
+            b'(' | b')' | b'\\' => {
+                bytes.push(b'\\');
+                bytes.push(byte);
+            }
+            0x80..=0xff => bytes.extend_from_slice(format!("\\{:03o}", byte).as_bytes()),
+            _ => bytes.push(byte),
+        }
+    }
+    bytes
+}
+
+/// Append the WinAnsi bytes for `c`. Characters the standard fonts can't show fall
+/// back to their closest ASCII form (accented letters lose the accent), and to '?'
+/// when there is none.
+fn win_ansi_bytes(c: char, out: &mut Vec<u8>) {
+    let byte = match c {
+        '\u{20}'..='\u{7e}' => c as u8,
+        '\u{a0}'..='\u{ff}' => c as u32 as u8,
+        '\u{20ac}' => 0x80,
+        '\u{201a}' => 0x82,
+        '\u{0192}' => 0x83,
+        '\u{201e}' => 0x84,
+        '\u{2026}' => 0x85,
+        '\u{2020}' => 0x86,
+        '\u{2021}' => 0x87,
+        '\u{02c6}' => 0x88,
+        '\u{2030}' => 0x89,
+        '\u{0160}' => 0x8a,
+        '\u{2039}' => 0x8b,
+        '\u{0152}' => 0x8c,
+        '\u{017d}' => 0x8e,
+        '\u{2018}' => 0x91,
+        '\u{2019}' => 0x92,
+        '\u{201c}' => 0x93,
+        '\u{201d}' => 0x94,
+        '\u{2022}' => 0x95,
+        '\u{2013}' => 0x96,
+        '\u{2014}' | '\u{2015}' => 0x97,
+        '\u{02dc}' => 0x98,
+        '\u{2122}' => 0x99,
+        '\u{0161}' => 0x9a,
+        '\u{203a}' => 0x9b,
+        '\u{0153}' => 0x9c,
+        '\u{017e}' => 0x9e,
+        '\u{0178}' => 0x9f,
+        '\u{0100}'..='\u{017f}' => LATIN_EXTENDED_A_BASE[c as usize - 0x100],
+        '\u{2010}'..='\u{2012}' | '\u{2212}' => b'-',
+        '\t' | '\u{2000}'..='\u{200a}' | '\u{202f}' => b' ',
+        '\u{2032}' => b'\'',
+        '\u{2033}' => b'"',
+        '\u{2192}' => return out.extend_from_slice(b"->"),
+        // Zero-width characters have nothing to draw
+        '\u{200b}'..='\u{200d}' | '\u{feff}' => return,
+        _ => b'?',
+    };
+    out.push(byte);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escapes_delimiters_and_non_ascii() {
+        assert_eq!(encode_pdf_string("a (b) \\"), b"a \\(b\\) \\\\".to_vec());
+        assert_eq!(encode_pdf_string("café"), b"caf\\351".to_vec());
+        assert_eq!(encode_pdf_string("日"), b"?".to_vec());
+    }
+
+    #[test]
+    fn test_characters_outside_win_ansi_fall_back() {
+        // In WinAnsi, just outside Latin-1
+        assert_eq!(encode_pdf_string("Šťastný…"), b"\\212tastn\\375\\205".to_vec());
+        // Accented letters WinAnsi lacks keep their base letter
+        assert_eq!(encode_pdf_string("Łódź Kraków"), b"L\\363dz Krak\\363w".to_vec());
+        assert_eq!(encode_pdf_string("Dvořák"), b"Dvor\\341k".to_vec());
+        assert_eq!(encode_pdf_string("a\u{2011}b \u{2192} c\u{200b}"), b"a-b -> c".to_vec());
+    }
+
+    #[test]
+    fn test_wrap_text_respects_width() {
+        let lines = wrap_text(&"word ".repeat(100), 10.0, 100.0);
+        assert!(lines.len() > 1);
+        assert!(lines.iter().all(|line| line.chars().count() <= 20));
+    }
+
+    #[test]
+    fn test_long_document_spans_multiple_pages() {
+        let mut doc = PdfDocument::new();
+        for i in 0..200 {
+            doc.text(&format!("Line {}", i), PdfFont::Regular, 11.0);
+        }
+        let bytes = doc.finish();
+        let pdf = String::from_utf8_lossy(&bytes);
+
+        assert!(pdf.starts_with("%PDF-1.4"));
+        assert!(pdf.trim_end().ends_with("%%EOF"));
+        assert!(pdf.matches("/Type /Page ").count() > 1);
+    }
+}
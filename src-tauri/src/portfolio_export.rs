@@ -1,7 +1,9 @@
 //! Portfolio export and generation functionality
 
 use crate::db::get_connection;
-use crate::errors::CareerBenchError;
+use crate::errors::{CareerBenchError, DatabaseError};
+use crate::pdf_writer::{PdfDocument, PdfFont};
+use rusqlite::Connection;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -275,6 +277,58 @@ fn html_escape(text: &str) -> String {
         .replace('\'', "&#39;")
 }
 
+/// Export portfolio as PDF, with each item's URL listed as a footnote
+pub fn export_portfolio_pdf(
+    portfolio_items: &[PortfolioItem],
+    include_highlighted_only: bool,
+) -> Vec<u8> {
+    let mut items = if include_highlighted_only {
+        portfolio_items.iter().filter(|item| item.highlighted).collect::<Vec<_>>()
+    } else {
+        portfolio_items.iter().collect::<Vec<_>>()
+    };
+    // Highlighted items first, otherwise keep the given order
+    items.sort_by_key(|item| !item.highlighted);
+
+    let mut doc = PdfDocument::new();
+    doc.text("Portfolio", PdfFont::Bold, 20.0);
+    doc.spacer(12.0);
+
+    for item in items {
+        doc.ensure_space(60.0);
+        match &item.url {
+            Some(url) if !url.trim().is_empty() => {
+                doc.text_with_footnote(&item.title, PdfFont::Bold, 14.0, url.trim())
+            }
+            _ => doc.text(&item.title, PdfFont::Bold, 14.0),
+        }
+
+        if let Some(role) = &item.role {
+            doc.text(&format!("Role: {}", role), PdfFont::Regular, 10.0);
+        }
+
+        if let Some(tech_stack) = &item.tech_stack {
+            let tech_list: Vec<&str> = tech_stack
+                .split(',')
+                .map(|s| s.trim())
+                .filter(|s| !s.is_empty())
+                .collect();
+            if !tech_list.is_empty() {
+                doc.text(&format!("Tech Stack: {}", tech_list.join(", ")), PdfFont::Regular, 10.0);
+            }
+        }
+
+        if let Some(description) = &item.description {
+            doc.spacer(4.0);
+            doc.text(description, PdfFont::Regular, 11.0);
+        }
+
+        doc.spacer(16.0);
+    }
+
+    doc.finish()
+}
+
 /// Get portfolio items linked to an application
 pub fn get_portfolio_for_application(
     application_id: i64,
@@ -334,6 +388,46 @@ pub fn link_portfolio_to_application(
     Ok(())
 }
 
+/// Link one portfolio item to many applications in a single transaction.
+///
+/// Existing links are left alone, so re-running is harmless; returns how many
+/// links were newly created.
+pub fn bulk_link_portfolio_with_conn(
+    conn: &Connection,
+    portfolio_item_id: i64,
+    application_ids: &[i64],
+) -> Result<u64, CareerBenchError> {
+    let exists: bool = conn.query_row(
+        "SELECT COUNT(*) FROM portfolio_items WHERE id = ?",
+        [portfolio_item_id],
+        |row| Ok(row.get::<_, i64>(0)? > 0),
+    )?;
+    if !exists {
+        return Err(DatabaseError::NotFound(format!("Portfolio item {} not found", portfolio_item_id)).into());
+    }
+
+    let tx = conn.unchecked_transaction()?;
+    let mut linked = 0u64;
+    for application_id in application_ids {
+        linked += tx.execute(
+            "INSERT OR IGNORE INTO application_portfolio_links (application_id, portfolio_item_id, created_at)
+             VALUES (?, ?, datetime('now'))",
+            [*application_id, portfolio_item_id],
+        )? as u64;
+    }
+    tx.commit()?;
+
+    Ok(linked)
+}
+
+pub fn bulk_link_portfolio(
+    portfolio_item_id: i64,
+    application_ids: &[i64],
+) -> Result<u64, CareerBenchError> {
+    let conn = get_connection()?;
+    bulk_link_portfolio_with_conn(&conn, portfolio_item_id, application_ids)
+}
+
 /// Get applications linked to a portfolio item
 pub fn get_applications_for_portfolio(
     portfolio_item_id: i64,
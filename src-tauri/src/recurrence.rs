@@ -0,0 +1,372 @@
+//! Recurrence rules (RFC 5545 RRULE) for calendar events
+//!
+//! Only the parts of RRULE that a recurring check-in needs are supported: `FREQ`
+//! (DAILY, WEEKLY, MONTHLY, YEARLY), `INTERVAL`, `COUNT`, `UNTIL` and, for weekly
+//! rules, `BYDAY` with plain weekday codes. Anything else is rejected rather than
+//! emitted into an ICS file that calendars would read differently from us.
+
+use crate::errors::{CareerBenchError, ValidationError};
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+
+/// Upper bound on the occurrences walked while expanding one rule
+const MAX_OCCURRENCES: usize = 1000;
+
+/// Upper bound on the periods stepped through while expanding one rule. Steps that
+/// land on a missing day (the 31st, 29 February) yield nothing, so this, not
+/// MAX_OCCURRENCES, is what guarantees the walk ends.
+const MAX_STEPS: i64 = 2 * MAX_OCCURRENCES as i64;
+
+/// Largest INTERVAL accepted; anything longer would step past the calendar's range
+const MAX_INTERVAL: u32 = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+impl Frequency {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Frequency::Daily => "DAILY",
+            Frequency::Weekly => "WEEKLY",
+            Frequency::Monthly => "MONTHLY",
+            Frequency::Yearly => "YEARLY",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecurrenceRule {
+    pub frequency: Frequency,
+    pub interval: u32,
+    pub count: Option<u32>,
+    /// Last day an occurrence may fall on (inclusive)
+    pub until: Option<NaiveDate>,
+    /// Weekdays for a weekly rule; empty means the start date's weekday
+    pub by_day: Vec<Weekday>,
+}
+
+fn invalid(message: String) -> CareerBenchError {
+    ValidationError::InvalidFormat(message).into()
+}
+
+fn weekday_code(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "MO",
+        Weekday::Tue => "TU",
+        Weekday::Wed => "WE",
+        Weekday::Thu => "TH",
+        Weekday::Fri => "FR",
+        Weekday::Sat => "SA",
+        Weekday::Sun => "SU",
+    }
+}
+
+fn parse_weekday(code: &str) -> Option<Weekday> {
+    match code {
+        "MO" => Some(Weekday::Mon),
+        "TU" => Some(Weekday::Tue),
+        "WE" => Some(Weekday::Wed),
+        "TH" => Some(Weekday::Thu),
+        "FR" => Some(Weekday::Fri),
+        "SA" => Some(Weekday::Sat),
+        "SU" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+/// `UNTIL` is a date (`20250301`) or a date-time (`20250301T170000Z`); only the day matters here
+fn parse_until(value: &str) -> Option<NaiveDate> {
+    let date = value.get(..8)?;
+    let rest = &value[8..];
+    if !(rest.is_empty() || (rest.starts_with('T') && rest.len() >= 7)) {
+        return None;
+    }
+    NaiveDate::parse_from_str(date, "%Y%m%d").ok()
+}
+
+fn parse_positive(name: &str, value: &str) -> Result<u32, CareerBenchError> {
+    value
+        .parse::<u32>()
+        .ok()
+        .filter(|n| *n > 0)
+        .ok_or_else(|| invalid(format!("{} must be a positive number, got '{}'", name, value)))
+}
+
+impl RecurrenceRule {
+    /// Parse an RRULE value such as `FREQ=WEEKLY;COUNT=4`. A leading `RRULE:` is allowed.
+    pub fn parse(rule: &str) -> Result<Self, CareerBenchError> {
+        let rule = rule.trim();
+        let rule = rule.strip_prefix("RRULE:").unwrap_or(rule);
+        if rule.is_empty() {
+            return Err(invalid("Recurrence rule is empty".to_string()));
+        }
+
+        let mut frequency = None;
+        let mut interval = None;
+        let mut count = None;
+        let mut until = None;
+        let mut by_day = Vec::new();
+        let mut seen: Vec<String> = Vec::new();
+
+        for part in rule.split(';').filter(|p| !p.is_empty()) {
+            let (name, value) = part
+                .split_once('=')
+                .ok_or_else(|| invalid(format!("Expected NAME=VALUE in recurrence rule, got '{}'", part)))?;
+            let name = name.trim().to_uppercase();
+            let value = value.trim().to_uppercase();
+            if seen.contains(&name) {
+                return Err(invalid(format!("{} appears more than once in the recurrence rule", name)));
+            }
+            seen.push(name.clone());
+
+            match name.as_str() {
+                "FREQ" => {
+                    frequency = Some(match value.as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        "YEARLY" => Frequency::Yearly,
+                        _ => {
+                            return Err(invalid(format!(
+                                "Unsupported FREQ '{}'. Expected DAILY, WEEKLY, MONTHLY or YEARLY",
+                                value
+                            )))
+                        }
+                    })
+                }
+                "INTERVAL" => {
+                    let n = parse_positive("INTERVAL", &value)?;
+                    if n > MAX_INTERVAL {
+                        return Err(invalid(format!("INTERVAL can be at most {}, got {}", MAX_INTERVAL, n)));
+                    }
+                    interval = Some(n)
+                }
+                "COUNT" => count = Some(parse_positive("COUNT", &value)?),
+                "UNTIL" => {
+                    until = Some(parse_until(&value).ok_or_else(|| {
+                        invalid(format!("UNTIL must look like 20250301 or 20250301T170000Z, got '{}'", value))
+                    })?)
+                }
+                "BYDAY" => {
+                    for code in value.split(',') {
+                        let day = parse_weekday(code.trim())
+                            .ok_or_else(|| invalid(format!("Unsupported BYDAY value '{}'", code)))?;
+                        if !by_day.contains(&day) {
+                            by_day.push(day);
+                        }
+                    }
+                }
+                _ => return Err(invalid(format!("Unsupported recurrence rule part '{}'", name))),
+            }
+        }
+
+        let frequency = frequency.ok_or_else(|| invalid("Recurrence rule needs a FREQ".to_string()))?;
+        if count.is_some() && until.is_some() {
+            return Err(invalid("A recurrence rule can't have both COUNT and UNTIL".to_string()));
+        }
+        if !by_day.is_empty() && frequency != Frequency::Weekly {
+            return Err(invalid("BYDAY is only supported with FREQ=WEEKLY".to_string()));
+        }
+        by_day.sort_by_key(|day| day.num_days_from_monday());
+
+        Ok(Self {
+            frequency,
+            interval: interval.unwrap_or(1),
+            count,
+            until,
+            by_day,
+        })
+    }
+
+    /// The rule in canonical RRULE form, as written to ICS files
+    pub fn to_rrule(&self) -> String {
+        let mut parts = vec![format!("FREQ={}", self.frequency.as_str())];
+        if self.interval != 1 {
+            parts.push(format!("INTERVAL={}", self.interval));
+        }
+        if let Some(count) = self.count {
+            parts.push(format!("COUNT={}", count));
+        }
+        if let Some(until) = self.until {
+            // Events are written with UTC start times, so UNTIL has to be a UTC date-time too
+            parts.push(format!("UNTIL={}T235959Z", until.format("%Y%m%d")));
+        }
+        if !self.by_day.is_empty() {
+            let days: Vec<&str> = self.by_day.iter().map(|d| weekday_code(*d)).collect();
+            parts.push(format!("BYDAY={}", days.join(",")));
+        }
+        parts.join(";")
+    }
+
+    /// Candidate dates in order, before COUNT and UNTIL are applied. Months or years
+    /// without the start's day (the 31st, 29 February) are skipped, as RFC 5545 does.
+    /// The walk stops after MAX_STEPS periods or at the end of the calendar's range.
+    fn candidates(&self, start: NaiveDate) -> Box<dyn Iterator<Item = NaiveDate> + '_> {
+        let interval = self.interval as i64;
+        let steps = 0..MAX_STEPS;
+        match self.frequency {
+            Frequency::Daily => Box::new(steps.map_while(move |k| start.checked_add_signed(Duration::days(k * interval)))),
+            Frequency::Weekly if self.by_day.is_empty() => {
+                Box::new(steps.map_while(move |k| start.checked_add_signed(Duration::weeks(k * interval))))
+            }
+            Frequency::Weekly => {
+                let week_start = start - Duration::days(start.weekday().num_days_from_monday() as i64);
+                Box::new(
+                    steps
+                        .map_while(move |k| week_start.checked_add_signed(Duration::weeks(k * interval)))
+                        .flat_map(move |monday| {
+                            self.by_day
+                                .iter()
+                                .filter_map(move |day| monday.checked_add_signed(Duration::days(day.num_days_from_monday() as i64)))
+                        })
+                        .filter(move |date| *date >= start),
+                )
+            }
+            Frequency::Monthly => Box::new(steps.filter_map(move |k| {
+                let months = start.month0() as i64 + k * interval;
+                let year = i32::try_from(start.year() as i64 + months.div_euclid(12)).ok()?;
+                let month = months.rem_euclid(12) as u32 + 1;
+                NaiveDate::from_ymd_opt(year, month, start.day())
+            })),
+            Frequency::Yearly => Box::new(steps.filter_map(move |k| {
+                let year = i32::try_from(start.year() as i64 + k * interval).ok()?;
+                NaiveDate::from_ymd_opt(year, start.month(), start.day())
+            })),
+        }
+    }
+
+    /// Occurrences of an event first held on `start` that fall in `range_start..=range_end`
+    pub fn occurrences_between(&self, start: NaiveDate, range_start: NaiveDate, range_end: NaiveDate) -> Vec<NaiveDate> {
+        let count = self.count.map(|c| c as usize).unwrap_or(MAX_OCCURRENCES).min(MAX_OCCURRENCES);
+        self.candidates(start)
+            .take(count)
+            .take_while(|date| *date <= range_end && !matches!(self.until, Some(until) if *date > until))
+            .filter(|date| *date >= range_start)
+            .collect()
+    }
+
+    /// The occurrence following `current` and the rule left for the rest of the
+    /// series (COUNT reduced by one), or None when `current` was the last one
+    pub fn next_occurrence(&self, current: NaiveDate) -> Option<(NaiveDate, RecurrenceRule)> {
+        if self.count == Some(1) {
+            return None;
+        }
+        let next = self.candidates(current).take(MAX_OCCURRENCES).find(|date| *date > current)?;
+        if matches!(self.until, Some(until) if next > until) {
+            return None;
+        }
+        let rest = RecurrenceRule {
+            count: self.count.map(|c| c - 1),
+            ..self.clone()
+        };
+        Some((next, rest))
+    }
+}
+
+/// Check an RRULE and return it in canonical form
+pub fn validate_rrule(rule: &str) -> Result<String, CareerBenchError> {
+    RecurrenceRule::parse(rule).map(|rule| rule.to_rrule())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_canonical_form() {
+        let rule = RecurrenceRule::parse("RRULE:freq=weekly;byday=fr,mo;COUNT=4").unwrap();
+        assert_eq!(rule.frequency, Frequency::Weekly);
+        assert_eq!(rule.by_day, vec![Weekday::Mon, Weekday::Fri]);
+        assert_eq!(rule.to_rrule(), "FREQ=WEEKLY;COUNT=4;BYDAY=MO,FR");
+        assert_eq!(validate_rrule("FREQ=MONTHLY;UNTIL=20251231T120000Z").unwrap(), "FREQ=MONTHLY;UNTIL=20251231T235959Z");
+    }
+
+    #[test]
+    fn test_malformed_rules_are_rejected() {
+        for rule in [
+            "",
+            "COUNT=4",
+            "FREQ=HOURLY",
+            "FREQ=WEEKLY;COUNT=0",
+            "FREQ=WEEKLY;COUNT=2;UNTIL=20250101",
+            "FREQ=WEEKLY;BYDAY=XX",
+            "FREQ=DAILY;BYDAY=MO",
+            "FREQ=WEEKLY;FREQ=DAILY",
+            "FREQ=WEEKLY;BYMONTHDAY=3",
+            "FREQ=WEEKLY;UNTIL=2025-01-01",
+            "FREQ=MONTHLY;INTERVAL=300000",
+            "FREQ=DAILY;INTERVAL=4000000000",
+            "FREQ",
+        ] {
+            assert!(RecurrenceRule::parse(rule).is_err(), "{} should be rejected", rule);
+        }
+    }
+
+    #[test]
+    fn test_weekly_with_count() {
+        let rule = RecurrenceRule::parse("FREQ=WEEKLY;COUNT=4").unwrap();
+        let all = rule.occurrences_between(date("2025-03-03"), date("2025-01-01"), date("2025-12-31"));
+        assert_eq!(all, vec![date("2025-03-03"), date("2025-03-10"), date("2025-03-17"), date("2025-03-24")]);
+
+        // COUNT includes occurrences before the range
+        let later = rule.occurrences_between(date("2025-03-03"), date("2025-03-15"), date("2025-12-31"));
+        assert_eq!(later, vec![date("2025-03-17"), date("2025-03-24")]);
+    }
+
+    #[test]
+    fn test_byday_interval_and_until() {
+        // Every other week on Tuesday and Thursday, starting on a Thursday
+        let rule = RecurrenceRule::parse("FREQ=WEEKLY;INTERVAL=2;BYDAY=TU,TH;UNTIL=20250320").unwrap();
+        let dates = rule.occurrences_between(date("2025-03-06"), date("2025-03-01"), date("2025-04-30"));
+        assert_eq!(dates, vec![date("2025-03-06"), date("2025-03-18"), date("2025-03-20")]);
+    }
+
+    #[test]
+    fn test_monthly_skips_short_months() {
+        let rule = RecurrenceRule::parse("FREQ=MONTHLY;COUNT=3").unwrap();
+        let dates = rule.occurrences_between(date("2025-01-31"), date("2025-01-01"), date("2025-12-31"));
+        assert_eq!(dates, vec![date("2025-01-31"), date("2025-03-31"), date("2025-05-31")]);
+    }
+
+    #[test]
+    fn test_next_occurrence_steps_through_series() {
+        let rule = RecurrenceRule::parse("FREQ=WEEKLY;INTERVAL=2;BYDAY=TU,TH;COUNT=3").unwrap();
+        let (second, rest) = rule.next_occurrence(date("2025-03-06")).unwrap();
+        assert_eq!(second, date("2025-03-18"));
+        assert_eq!(rest.count, Some(2));
+        let (third, rest) = rest.next_occurrence(second).unwrap();
+        assert_eq!(third, date("2025-03-20"));
+        assert_eq!(rest.to_rrule(), "FREQ=WEEKLY;INTERVAL=2;COUNT=1;BYDAY=TU,TH");
+        assert!(rest.next_occurrence(third).is_none());
+
+        let until = RecurrenceRule::parse("FREQ=DAILY;UNTIL=20250302").unwrap();
+        assert_eq!(until.next_occurrence(date("2025-03-01")).map(|(d, _)| d), Some(date("2025-03-02")));
+        assert!(until.next_occurrence(date("2025-03-02")).is_none());
+    }
+
+    #[test]
+    fn test_long_intervals_stop_at_the_end_of_the_calendar() {
+        // Only a few hundred of these fit before chrono's last year, and the walk must still end
+        let yearly = RecurrenceRule::parse("FREQ=YEARLY;INTERVAL=1000").unwrap();
+        let dates = yearly.occurrences_between(date("2025-02-28"), date("2025-01-01"), NaiveDate::MAX);
+        assert_eq!(dates.first(), Some(&date("2025-02-28")));
+        assert!(dates.len() < MAX_OCCURRENCES);
+        let leap_day = RecurrenceRule::parse("FREQ=MONTHLY;INTERVAL=1000").unwrap();
+        assert!(leap_day.next_occurrence(date("2024-02-29")).is_some());
+
+        // Stepping past the last representable day ends the series instead of panicking
+        let daily = RecurrenceRule::parse("FREQ=DAILY;INTERVAL=1000").unwrap();
+        assert!(daily.next_occurrence(NaiveDate::MAX - Duration::days(10)).is_none());
+        let near_end = NaiveDate::MAX - Duration::days(10);
+        let weekly = RecurrenceRule::parse(&format!("FREQ=WEEKLY;INTERVAL=1000;BYDAY={}", weekday_code(near_end.weekday()))).unwrap();
+        assert!(weekly.next_occurrence(near_end).is_none());
+    }
+}
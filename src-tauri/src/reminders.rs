@@ -1,10 +1,25 @@
 //! Reminder notification system for interviews and events
 
+use crate::app_settings::{get_json_setting, set_json_setting};
 use crate::db::get_connection;
-use crate::errors::CareerBenchError;
-use chrono::Utc;
+use crate::errors::{CareerBenchError, ValidationError};
+use crate::recurrence::RecurrenceRule;
+use chrono::{Duration, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use rusqlite::{Connection, OptionalExtension};
 use serde::{Deserialize, Serialize};
 
+/// Settings key for reminder quiet hours
+pub const QUIET_HOURS_SETTING: &str = "quiet_hours";
+
+/// Settings key for the reminders created automatically on status changes
+pub const FOLLOW_UP_RULES_SETTING: &str = "follow_up_rules";
+
+/// Longest delay a follow-up rule can have
+pub const MAX_FOLLOW_UP_DAYS: u32 = 365;
+
+/// Reminder type prefix for reminders created by a follow-up rule
+const STATUS_FOLLOW_UP_TYPE: &str = "status_follow_up";
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Reminder {
@@ -17,6 +32,279 @@ pub struct Reminder {
     pub is_sent: bool,
     pub sent_at: Option<String>,
     pub created_at: String,
+    /// RRULE for a repeating reminder; each occurrence is its own row
+    pub recurrence: Option<String>,
+}
+
+fn reminder_from_row(row: &rusqlite::Row) -> rusqlite::Result<Reminder> {
+    Ok(Reminder {
+        id: row.get(0)?,
+        application_id: row.get(1)?,
+        event_id: row.get(2)?,
+        reminder_type: row.get(3)?,
+        reminder_date: row.get(4)?,
+        message: row.get(5)?,
+        is_sent: row.get::<_, i64>(6)? != 0,
+        sent_at: row.get(7)?,
+        created_at: row.get(8)?,
+        recurrence: row.get(9)?,
+    })
+}
+
+/// Quiet hours window in local time ("HH:MM"). Windows may cross midnight (e.g. 22:00–07:00).
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct QuietHours {
+    pub quiet_hours_start: Option<String>,
+    pub quiet_hours_end: Option<String>,
+}
+
+impl QuietHours {
+    /// Parsed (start, end) window, or None when quiet hours are disabled
+    fn window(&self) -> Option<(NaiveTime, NaiveTime)> {
+        let start = NaiveTime::parse_from_str(self.quiet_hours_start.as_deref()?, "%H:%M").ok()?;
+        let end = NaiveTime::parse_from_str(self.quiet_hours_end.as_deref()?, "%H:%M").ok()?;
+        if start == end {
+            return None;
+        }
+        Some((start, end))
+    }
+
+    /// Check whether a local time falls inside the quiet window
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        match self.window() {
+            Some((start, end)) if start < end => time >= start && time < end,
+            Some((start, end)) => time >= start || time < end,
+            None => false,
+        }
+    }
+
+    /// Earliest local time at or after `local` that is outside quiet hours
+    pub fn next_allowed_time(&self, local: NaiveDateTime) -> NaiveDateTime {
+        let (_, end) = match self.window() {
+            Some(window) if self.contains(local.time()) => window,
+            _ => return local,
+        };
+
+        // Inside the window: it ends either later today or (after midnight) tomorrow
+        let end_today = local.date().and_time(end);
+        if local.time() < end {
+            end_today
+        } else {
+            end_today + Duration::days(1)
+        }
+    }
+}
+
+/// A reminder to create when an application moves to `to_status`.
+///
+/// `message` may use `{company}`, `{title}` and `{status}`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FollowUpRule {
+    /// Only fire when moving from this status; None matches any previous status
+    pub from_status: Option<String>,
+    pub to_status: String,
+    pub days_after: u32,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FollowUpRules {
+    pub rules: Vec<FollowUpRule>,
+}
+
+impl Default for FollowUpRules {
+    fn default() -> Self {
+        let rule = |to_status: &str, days_after, message: &str| FollowUpRule {
+            from_status: None,
+            to_status: to_status.to_string(),
+            days_after,
+            message: message.to_string(),
+        };
+        Self {
+            rules: vec![
+                rule("Applied", 7, "Follow up on your {title} application at {company}"),
+                rule("Interviewing", 3, "Check in with {company} about next steps for {title}"),
+                rule("Offer", 2, "Respond to the {title} offer from {company}"),
+            ],
+        }
+    }
+}
+
+impl FollowUpRules {
+    /// The first rule matching a transition
+    pub fn rule_for(&self, from_status: &str, to_status: &str) -> Option<&FollowUpRule> {
+        self.rules.iter().find(|rule| {
+            rule.to_status == to_status && !matches!(rule.from_status.as_deref(), Some(from) if from != from_status)
+        })
+    }
+}
+
+/// Parse a stored reminder date (UTC), accepting both SQLite datetime and RFC3339 formats
+fn parse_reminder_date(reminder_date: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(reminder_date, "%Y-%m-%d %H:%M:%S")
+        .or_else(|_| NaiveDateTime::parse_from_str(reminder_date, "%Y-%m-%dT%H:%M:%S"))
+        .ok()
+        .or_else(|| chrono::DateTime::parse_from_rfc3339(reminder_date).ok().map(|d| d.naive_utc()))
+}
+
+/// Check whether a reminder is due, deferring due times that fall in quiet hours.
+///
+/// Both times are UTC; `tz` is the user's local time zone used to interpret quiet hours.
+pub fn is_due_with_quiet_hours<Tz: TimeZone>(
+    reminder_date_utc: NaiveDateTime,
+    now_utc: NaiveDateTime,
+    tz: &Tz,
+    quiet_hours: &QuietHours,
+) -> bool {
+    if reminder_date_utc > now_utc {
+        return false;
+    }
+
+    let due_local = tz.from_utc_datetime(&reminder_date_utc).naive_local();
+    let now_local = tz.from_utc_datetime(&now_utc).naive_local();
+    let deliver_at = quiet_hours.next_allowed_time(due_local.max(now_local));
+
+    deliver_at <= now_local
+}
+
+/// Load the configured quiet hours
+pub fn get_quiet_hours() -> Result<QuietHours, CareerBenchError> {
+    let conn = get_connection()?;
+    get_json_setting(&conn, QUIET_HOURS_SETTING)
+}
+
+/// Save quiet hours; both times must be "HH:MM" or both empty to disable
+pub fn save_quiet_hours(quiet_hours: &QuietHours) -> Result<(), CareerBenchError> {
+    validate_quiet_hours(quiet_hours)?;
+    let conn = get_connection()?;
+    set_json_setting(&conn, QUIET_HOURS_SETTING, quiet_hours)
+}
+
+fn validate_quiet_hours(quiet_hours: &QuietHours) -> Result<(), CareerBenchError> {
+    let set = |time: &Option<String>| time.as_deref().map(str::trim).filter(|t| !t.is_empty()).is_some();
+    if set(&quiet_hours.quiet_hours_start) != set(&quiet_hours.quiet_hours_end) {
+        return Err(ValidationError::InvalidFormat(
+            "Quiet hours need both a start and an end time, or neither".to_string(),
+        )
+        .into());
+    }
+    for time in [&quiet_hours.quiet_hours_start, &quiet_hours.quiet_hours_end].into_iter().flatten() {
+        if !time.trim().is_empty() && NaiveTime::parse_from_str(time, "%H:%M").is_err() {
+            return Err(ValidationError::InvalidFormat(format!(
+                "Quiet hours time '{}' must be in HH:MM format",
+                time
+            ))
+            .into());
+        }
+    }
+    Ok(())
+}
+
+/// Parse a reminder date supplied by the caller, rejecting anything we couldn't schedule
+fn require_reminder_date(reminder_date: &str) -> Result<NaiveDateTime, CareerBenchError> {
+    parse_reminder_date(reminder_date).ok_or_else(|| {
+        ValidationError::InvalidFormat(format!(
+            "Reminder date '{}' must be YYYY-MM-DD HH:MM:SS or RFC3339",
+            reminder_date
+        ))
+        .into()
+    })
+}
+
+/// Load the follow-up rules, or the defaults when none are saved
+pub fn get_follow_up_rules() -> Result<FollowUpRules, CareerBenchError> {
+    let conn = get_connection()?;
+    get_json_setting(&conn, FOLLOW_UP_RULES_SETTING)
+}
+
+/// Save follow-up rules; statuses must be known, delays at most `MAX_FOLLOW_UP_DAYS`
+/// and messages non-empty
+pub fn save_follow_up_rules(rules: &FollowUpRules) -> Result<(), CareerBenchError> {
+    validate_follow_up_rules(rules)?;
+    let conn = get_connection()?;
+    set_json_setting(&conn, FOLLOW_UP_RULES_SETTING, rules)
+}
+
+fn validate_follow_up_rules(rules: &FollowUpRules) -> Result<(), CareerBenchError> {
+    for rule in &rules.rules {
+        for status in std::iter::once(&rule.to_status).chain(rule.from_status.as_ref()) {
+            if crate::application_status::ApplicationStatus::parse(status).is_none() {
+                return Err(ValidationError::InvalidFormat(format!("Unknown application status '{}'", status)).into());
+            }
+        }
+        if rule.message.trim().is_empty() {
+            return Err(ValidationError::InvalidFormat(format!(
+                "The follow-up rule for {} needs a message",
+                rule.to_status
+            ))
+            .into());
+        }
+        if rule.days_after > MAX_FOLLOW_UP_DAYS {
+            return Err(ValidationError::InvalidFormat(format!(
+                "The follow-up rule for {} can wait at most {} days",
+                rule.to_status, MAX_FOLLOW_UP_DAYS
+            ))
+            .into());
+        }
+    }
+    Ok(())
+}
+
+/// Create the follow-up reminder configured for an application's status change, unless
+/// the application already has one for this transition. Returns the new reminder's id.
+pub fn create_status_follow_up_reminder(
+    conn: &Connection,
+    application_id: i64,
+    from_status: &str,
+    to_status: &str,
+    now_utc: NaiveDateTime,
+) -> Result<Option<i64>, CareerBenchError> {
+    let rules: FollowUpRules = get_json_setting(conn, FOLLOW_UP_RULES_SETTING)?;
+    let rule = match rules.rule_for(from_status, to_status) {
+        Some(rule) => rule,
+        None => return Ok(None),
+    };
+
+    let reminder_type = format!("{}:{}->{}", STATUS_FOLLOW_UP_TYPE, from_status, to_status);
+    let existing: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM reminders WHERE application_id = ? AND reminder_type = ?",
+        rusqlite::params![application_id, reminder_type],
+        |row| row.get(0),
+    )?;
+    if existing > 0 {
+        return Ok(None);
+    }
+
+    let (title, company) = conn
+        .query_row(
+            "SELECT j.title, j.company FROM applications a LEFT JOIN jobs j ON j.id = a.job_id WHERE a.id = ?",
+            [application_id],
+            |row| Ok((row.get::<_, Option<String>>(0)?, row.get::<_, Option<String>>(1)?)),
+        )
+        .optional()?
+        .unwrap_or_default();
+    let message = rule
+        .message
+        .replace("{company}", company.as_deref().unwrap_or("the company"))
+        .replace("{title}", title.as_deref().unwrap_or("the role"))
+        .replace("{status}", to_status);
+    // Rules saved before the delay was capped could still overflow the date
+    let reminder_date = Duration::try_days(rule.days_after as i64)
+        .and_then(|delay| now_utc.checked_add_signed(delay))
+        .ok_or_else(|| {
+            ValidationError::InvalidFormat(format!(
+                "The follow-up rule for {} has an out-of-range delay of {} days",
+                rule.to_status, rule.days_after
+            ))
+        })?
+        .format("%Y-%m-%d %H:%M:%S")
+        .to_string();
+
+    create_reminder_with_conn(conn, Some(application_id), None, &reminder_type, &reminder_date, Some(&message), None)
+        .map(Some)
 }
 
 /// Create a reminder for an interview or event
@@ -26,13 +314,35 @@ pub fn create_reminder(
     reminder_type: &str,
     reminder_date: &str,
     message: Option<&str>,
+    recurrence: Option<&str>,
 ) -> Result<i64, CareerBenchError> {
     let conn = get_connection()?;
+    create_reminder_with_conn(&conn, application_id, event_id, reminder_type, reminder_date, message, recurrence)
+}
+
+/// Create a reminder; a recurring one stores its rule in canonical form and needs a
+/// date we can step forward from
+pub fn create_reminder_with_conn(
+    conn: &Connection,
+    application_id: Option<i64>,
+    event_id: Option<i64>,
+    reminder_type: &str,
+    reminder_date: &str,
+    message: Option<&str>,
+    recurrence: Option<&str>,
+) -> Result<i64, CareerBenchError> {
+    let recurrence = match recurrence.map(str::trim).filter(|r| !r.is_empty()) {
+        Some(rule) => {
+            require_reminder_date(reminder_date)?;
+            Some(crate::recurrence::validate_rrule(rule)?)
+        }
+        None => None,
+    };
 
     conn.execute(
-        "INSERT INTO reminders (application_id, event_id, reminder_type, reminder_date, message, created_at)
-         VALUES (?1, ?2, ?3, ?4, ?5, datetime('now'))",
-        rusqlite::params![application_id, event_id, reminder_type, reminder_date, message],
+        "INSERT INTO reminders (application_id, event_id, reminder_type, reminder_date, message, recurrence, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, datetime('now'))",
+        rusqlite::params![application_id, event_id, reminder_type, reminder_date, message, recurrence],
     )?;
 
     Ok(conn.last_insert_rowid())
@@ -47,31 +357,19 @@ pub fn get_reminders(
     let conn = get_connection()?;
 
     let query = if include_sent {
-        "SELECT id, application_id, event_id, reminder_type, reminder_date, message, is_sent, sent_at, created_at
+        "SELECT id, application_id, event_id, reminder_type, reminder_date, message, is_sent, sent_at, created_at, recurrence
          FROM reminders
          WHERE reminder_date >= ? AND reminder_date <= ?
          ORDER BY reminder_date ASC"
     } else {
-        "SELECT id, application_id, event_id, reminder_type, reminder_date, message, is_sent, sent_at, created_at
+        "SELECT id, application_id, event_id, reminder_type, reminder_date, message, is_sent, sent_at, created_at, recurrence
          FROM reminders
          WHERE reminder_date >= ? AND reminder_date <= ? AND is_sent = 0
          ORDER BY reminder_date ASC"
     };
 
     let mut stmt = conn.prepare(query)?;
-    let rows = stmt.query_map([start_date, end_date], |row| {
-        Ok(Reminder {
-            id: row.get(0)?,
-            application_id: row.get(1)?,
-            event_id: row.get(2)?,
-            reminder_type: row.get(3)?,
-            reminder_date: row.get(4)?,
-            message: row.get(5)?,
-            is_sent: row.get::<_, i64>(6)? != 0,
-            sent_at: row.get(7)?,
-            created_at: row.get(8)?,
-        })
-    })?;
+    let rows = stmt.query_map([start_date, end_date], reminder_from_row)?;
 
     let mut reminders = Vec::new();
     for row_result in rows {
@@ -81,35 +379,37 @@ pub fn get_reminders(
     Ok(reminders)
 }
 
-/// Get reminders that are due (reminder_date <= now and not sent)
+/// Get reminders that are due (reminder_date <= now and not sent).
+/// Reminders due during quiet hours are held back until the window ends.
 pub fn get_due_reminders() -> Result<Vec<Reminder>, CareerBenchError> {
-    let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
     let conn = get_connection()?;
+    get_due_reminders_with_conn(&conn, Utc::now().naive_utc())
+}
+
+/// Unsent reminders due at `now_utc`, excluding any deferred by quiet hours
+pub fn get_due_reminders_with_conn(conn: &Connection, now_utc: NaiveDateTime) -> Result<Vec<Reminder>, CareerBenchError> {
+    let now = now_utc.format("%Y-%m-%d %H:%M:%S").to_string();
+    let quiet_hours: QuietHours = get_json_setting(conn, QUIET_HOURS_SETTING)?;
 
     let mut stmt = conn.prepare(
-        "SELECT id, application_id, event_id, reminder_type, reminder_date, message, is_sent, sent_at, created_at
+        "SELECT id, application_id, event_id, reminder_type, reminder_date, message, is_sent, sent_at, created_at, recurrence
          FROM reminders
          WHERE reminder_date <= ? AND is_sent = 0
          ORDER BY reminder_date ASC"
     )?;
 
-    let rows = stmt.query_map([&now], |row| {
-        Ok(Reminder {
-            id: row.get(0)?,
-            application_id: row.get(1)?,
-            event_id: row.get(2)?,
-            reminder_type: row.get(3)?,
-            reminder_date: row.get(4)?,
-            message: row.get(5)?,
-            is_sent: row.get::<_, i64>(6)? != 0,
-            sent_at: row.get(7)?,
-            created_at: row.get(8)?,
-        })
-    })?;
+    let rows = stmt.query_map([&now], reminder_from_row)?;
 
     let mut reminders = Vec::new();
     for row_result in rows {
-        reminders.push(row_result?);
+        let reminder = row_result?;
+        let is_due = match parse_reminder_date(&reminder.reminder_date) {
+            Some(due) => is_due_with_quiet_hours(due, now_utc, &chrono::Local, &quiet_hours),
+            None => true, // Don't hide reminders we can't interpret
+        };
+        if is_due {
+            reminders.push(reminder);
+        }
     }
 
     Ok(reminders)
@@ -118,10 +418,132 @@ pub fn get_due_reminders() -> Result<Vec<Reminder>, CareerBenchError> {
 /// Mark a reminder as sent
 pub fn mark_reminder_sent(reminder_id: i64) -> Result<(), CareerBenchError> {
     let conn = get_connection()?;
+    mark_reminder_sent_with_conn(&conn, reminder_id, Utc::now().naive_utc())?;
+    Ok(())
+}
+
+/// Mark a reminder as sent at `now_utc`. A recurring reminder gets a new row for its
+/// next occurrence after `now_utc` (occurrences missed in between are skipped, so only
+/// one is ever due); its id is returned, or None when nothing follows.
+pub fn mark_reminder_sent_with_conn(
+    conn: &Connection,
+    reminder_id: i64,
+    now_utc: NaiveDateTime,
+) -> Result<Option<i64>, CareerBenchError> {
+    let now = now_utc.format("%Y-%m-%d %H:%M:%S").to_string();
+    let reminder = conn
+        .query_row(
+            "SELECT id, application_id, event_id, reminder_type, reminder_date, message, is_sent, sent_at, created_at, recurrence
+             FROM reminders
+             WHERE id = ?",
+            [reminder_id],
+            reminder_from_row,
+        )
+        .optional()?;
+    let reminder = match reminder {
+        Some(reminder) if !reminder.is_sent => reminder,
+        _ => return Ok(None),
+    };
+    let next = next_occurrence_after(&reminder, now_utc)?;
+
+    // Marking sent and scheduling the next occurrence happen together or not at all
+    let tx = conn.unchecked_transaction()?;
+    let updated = tx.execute(
+        "UPDATE reminders SET is_sent = 1, sent_at = ? WHERE id = ? AND is_sent = 0",
+        rusqlite::params![now, reminder_id],
+    )?;
+    // Sending twice must not schedule the next occurrence twice
+    if updated == 0 {
+        return Ok(None);
+    }
+
+    let next_id = match next {
+        Some((due, rule)) => {
+            tx.execute(
+                "INSERT INTO reminders (application_id, event_id, reminder_type, reminder_date, message, recurrence, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                rusqlite::params![
+                    reminder.application_id,
+                    reminder.event_id,
+                    reminder.reminder_type,
+                    due.format("%Y-%m-%d %H:%M:%S").to_string(),
+                    reminder.message,
+                    rule.to_rrule(),
+                    now,
+                ],
+            )?;
+            Some(tx.last_insert_rowid())
+        }
+        None => None,
+    };
+    tx.commit()?;
+
+    Ok(next_id)
+}
+
+/// First occurrence of a recurring reminder after `now_utc`, with the rule that
+/// continues from it; None for one-off reminders and finished series
+fn next_occurrence_after(
+    reminder: &Reminder,
+    now_utc: NaiveDateTime,
+) -> Result<Option<(NaiveDateTime, RecurrenceRule)>, CareerBenchError> {
+    let (mut rule, mut due) = match (reminder.recurrence.as_deref(), parse_reminder_date(&reminder.reminder_date)) {
+        (Some(rule), Some(due)) => (RecurrenceRule::parse(rule)?, due),
+        _ => return Ok(None),
+    };
+
+    loop {
+        let (date, rest) = match rule.next_occurrence(due.date()) {
+            Some(next) => next,
+            None => return Ok(None),
+        };
+        due = date.and_time(due.time());
+        rule = rest;
+        if due > now_utc {
+            return Ok(Some((due, rule)));
+        }
+    }
+}
+
+/// Push an unsent reminder to a later date
+pub fn snooze_reminder(reminder_id: i64, new_date: &str) -> Result<(), CareerBenchError> {
+    let conn = get_connection()?;
+    snooze_reminder_with_conn(&conn, reminder_id, new_date)
+}
+
+/// Move an unsent reminder to `new_date`, which must be later than its current date.
+/// The row is updated in place; a recurring series continues from the new date.
+pub fn snooze_reminder_with_conn(conn: &Connection, reminder_id: i64, new_date: &str) -> Result<(), CareerBenchError> {
+    let snoozed_until = require_reminder_date(new_date)?;
+    let current = conn
+        .query_row(
+            "SELECT reminder_date, is_sent FROM reminders WHERE id = ?",
+            [reminder_id],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)? != 0)),
+        )
+        .optional()?;
+    let (reminder_date, is_sent) = current.ok_or_else(|| {
+        CareerBenchError::from(ValidationError::InvalidFormat(format!("Reminder {} not found", reminder_id)))
+    })?;
+
+    if is_sent {
+        return Err(ValidationError::InvalidFormat(format!(
+            "Reminder {} was already sent and can't be snoozed",
+            reminder_id
+        ))
+        .into());
+    }
+    if matches!(parse_reminder_date(&reminder_date), Some(due) if snoozed_until <= due) {
+        return Err(ValidationError::InvalidFormat(format!(
+            "Snooze date must be after the reminder's current date ({})",
+            reminder_date
+        ))
+        .into());
+    }
 
     conn.execute(
-        "UPDATE reminders SET is_sent = 1, sent_at = datetime('now') WHERE id = ?",
-        [reminder_id],
+        "UPDATE reminders SET reminder_date = ? WHERE id = ?",
+        rusqlite::params![snoozed_until.format("%Y-%m-%d %H:%M:%S").to_string(), reminder_id],
     )?;
 
     Ok(())
@@ -141,25 +563,13 @@ pub fn get_reminders_for_application(application_id: i64) -> Result<Vec<Reminder
     let conn = get_connection()?;
 
     let mut stmt = conn.prepare(
-        "SELECT id, application_id, event_id, reminder_type, reminder_date, message, is_sent, sent_at, created_at
+        "SELECT id, application_id, event_id, reminder_type, reminder_date, message, is_sent, sent_at, created_at, recurrence
          FROM reminders
          WHERE application_id = ?
          ORDER BY reminder_date ASC"
     )?;
 
-    let rows = stmt.query_map([application_id], |row| {
-        Ok(Reminder {
-            id: row.get(0)?,
-            application_id: row.get(1)?,
-            event_id: row.get(2)?,
-            reminder_type: row.get(3)?,
-            reminder_date: row.get(4)?,
-            message: row.get(5)?,
-            is_sent: row.get::<_, i64>(6)? != 0,
-            sent_at: row.get(7)?,
-            created_at: row.get(8)?,
-        })
-    })?;
+    let rows = stmt.query_map([application_id], reminder_from_row)?;
 
     let mut reminders = Vec::new();
     for row_result in rows {
@@ -168,3 +578,82 @@ pub fn get_reminders_for_application(application_id: i64) -> Result<Vec<Reminder
 
     Ok(reminders)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::FixedOffset;
+
+    fn dt(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    fn overnight() -> QuietHours {
+        QuietHours {
+            quiet_hours_start: Some("22:00".to_string()),
+            quiet_hours_end: Some("07:00".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_follow_up_rules_limit_the_delay() {
+        let mut rules = FollowUpRules::default();
+        assert!(validate_follow_up_rules(&rules).is_ok());
+        rules.rules[0].days_after = MAX_FOLLOW_UP_DAYS + 1;
+        assert!(validate_follow_up_rules(&rules).unwrap_err().to_string().contains("at most 365 days"));
+    }
+
+    #[test]
+    fn test_quiet_hours_are_both_or_neither() {
+        let hours = |start: Option<&str>, end: Option<&str>| QuietHours {
+            quiet_hours_start: start.map(str::to_string),
+            quiet_hours_end: end.map(str::to_string),
+        };
+        assert!(validate_quiet_hours(&overnight()).is_ok());
+        assert!(validate_quiet_hours(&hours(None, None)).is_ok());
+        assert!(validate_quiet_hours(&hours(Some(""), None)).is_ok());
+        assert!(validate_quiet_hours(&hours(Some("22:00"), None)).unwrap_err().to_string().contains("both"));
+        assert!(validate_quiet_hours(&hours(Some(""), Some("07:00"))).is_err());
+        assert!(validate_quiet_hours(&hours(Some("10pm"), Some("07:00"))).unwrap_err().to_string().contains("HH:MM"));
+    }
+
+    #[test]
+    fn test_reminder_in_quiet_hours_deferred_until_window_ends() {
+        let utc = FixedOffset::east_opt(0).unwrap();
+        let due = dt("2024-03-10 03:00:00");
+
+        assert!(!is_due_with_quiet_hours(due, dt("2024-03-10 03:30:00"), &utc, &overnight()));
+        assert!(!is_due_with_quiet_hours(due, dt("2024-03-10 06:59:00"), &utc, &overnight()));
+        assert!(is_due_with_quiet_hours(due, dt("2024-03-10 07:00:00"), &utc, &overnight()));
+    }
+
+    #[test]
+    fn test_quiet_hours_crossing_midnight_before_midnight() {
+        let utc = FixedOffset::east_opt(0).unwrap();
+        let due = dt("2024-03-10 23:15:00");
+
+        assert!(!is_due_with_quiet_hours(due, dt("2024-03-11 01:00:00"), &utc, &overnight()));
+        assert!(is_due_with_quiet_hours(due, dt("2024-03-11 07:00:00"), &utc, &overnight()));
+        assert_eq!(overnight().next_allowed_time(due), dt("2024-03-11 07:00:00"));
+    }
+
+    #[test]
+    fn test_quiet_hours_use_local_time() {
+        // 03:00 UTC is 22:00 in UTC-5, inside the overnight window
+        let est = FixedOffset::west_opt(5 * 3600).unwrap();
+        let due = dt("2024-03-10 03:00:00");
+
+        assert!(!is_due_with_quiet_hours(due, dt("2024-03-10 03:05:00"), &est, &overnight()));
+        assert!(is_due_with_quiet_hours(due, dt("2024-03-10 12:00:00"), &est, &overnight()));
+    }
+
+    #[test]
+    fn test_reminder_outside_quiet_hours_is_due() {
+        let utc = FixedOffset::east_opt(0).unwrap();
+        let due = dt("2024-03-10 12:00:00");
+
+        assert!(is_due_with_quiet_hours(due, dt("2024-03-10 12:00:00"), &utc, &overnight()));
+        assert!(!is_due_with_quiet_hours(due, dt("2024-03-10 11:59:00"), &utc, &overnight()));
+        assert!(is_due_with_quiet_hours(due, dt("2024-03-10 12:00:00"), &utc, &QuietHours::default()));
+    }
+}
@@ -0,0 +1,254 @@
+//! Deterministic quality checks for generated resumes
+
+use crate::commands::GeneratedResume;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A term is flagged when it makes up more than this share of all bullet words...
+const STUFFING_DENSITY_THRESHOLD: f64 = 0.03;
+/// ...and appears at least this many times (so short resumes aren't flagged for a word used twice)
+const STUFFING_MIN_COUNT: usize = 4;
+
+/// Words too common to count as keywords
+const STOPWORDS: &[&str] = &[
+    "the", "and", "for", "with", "from", "into", "onto", "that", "this", "these", "those", "our",
+    "their", "its", "are", "was", "were", "been", "being", "has", "have", "had", "not", "but",
+    "all", "any", "via", "per", "over", "across", "using", "used", "use", "including", "within",
+    "while", "through", "each", "more", "than", "also", "who", "which", "where", "when",
+];
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct StuffingWarning {
+    pub term: String,
+    pub count: usize,
+    /// Share of all bullet words, 0.0–1.0
+    pub density: f64,
+}
+
+impl StuffingWarning {
+    pub fn message(&self) -> String {
+        format!(
+            "\"{}\" appears {} times in the resume bullets; consider varying the wording",
+            self.term, self.count
+        )
+    }
+}
+
+fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !(c.is_alphanumeric() || c == '+' || c == '#'))
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+}
+
+/// Flag terms repeated with unusually high density across the resume's bullets.
+///
+/// The Skills section is skipped because it is a list of terms by design.
+pub fn detect_keyword_stuffing(resume: &GeneratedResume) -> Vec<StuffingWarning> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    let mut total_words = 0usize;
+
+    let bullets = resume
+        .sections
+        .iter()
+        .filter(|section| section.title != "Skills")
+        .flat_map(|section| section.items.iter())
+        .flat_map(|item| item.bullets.iter());
+
+    for bullet in bullets {
+        for word in tokenize(bullet) {
+            total_words += 1;
+            if word.chars().count() < 3 || STOPWORDS.contains(&word.as_str()) {
+                continue;
+            }
+            *counts.entry(word).or_insert(0) += 1;
+        }
+    }
+
+    if total_words == 0 {
+        return Vec::new();
+    }
+
+    let mut warnings: Vec<StuffingWarning> = counts
+        .into_iter()
+        .filter(|(_, count)| *count >= STUFFING_MIN_COUNT)
+        .map(|(term, count)| StuffingWarning {
+            term,
+            count,
+            density: count as f64 / total_words as f64,
+        })
+        .filter(|warning| warning.density > STUFFING_DENSITY_THRESHOLD)
+        .collect();
+
+    warnings.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.term.cmp(&b.term)));
+    warnings
+}
+
+/// Which of a job's skills appear in a resume's text
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct KeywordCoverage {
+    /// Required skills first, then nice-to-haves, as written in the job
+    pub matched: Vec<String>,
+    pub missing: Vec<String>,
+    pub missing_required: Vec<String>,
+    /// Share of the job's skills found in the resume, 0–100 (100 when the job lists none)
+    pub coverage_percent: f64,
+}
+
+/// Fold simple English plurals so "APIs" matches "API" and "databases" matches "database".
+/// Terms with symbols or digits ("c++", "k8s") are left alone.
+fn stem(word: &str) -> String {
+    if !word.chars().all(|c| c.is_alphabetic()) || word.chars().count() <= 3 {
+        return word.to_string();
+    }
+    if let Some(base) = word.strip_suffix("ies") {
+        return format!("{}y", base);
+    }
+    for suffix in ["ches", "shes", "sses", "xes"] {
+        if word.ends_with(suffix) {
+            return word[..word.len() - 2].to_string();
+        }
+    }
+    if word.ends_with('s') && !word.ends_with("ss") {
+        return word[..word.len() - 1].to_string();
+    }
+    word.to_string()
+}
+
+fn stemmed_tokens(text: &str) -> Vec<String> {
+    tokenize(text).map(|word| stem(&word)).collect()
+}
+
+/// Compare `resume_text` against the job's required and nice-to-have skills.
+///
+/// Matching is case-insensitive on whole words with plurals folded; a multi-word
+/// skill ("machine learning") matches when its words appear together in order.
+/// Skills listed twice (in either list) are counted once.
+pub fn keyword_coverage(resume_text: &str, required: &[String], nice_to_have: &[String]) -> KeywordCoverage {
+    let resume_tokens = stemmed_tokens(resume_text);
+    let mut seen: Vec<Vec<String>> = Vec::new();
+    let mut coverage = KeywordCoverage {
+        matched: Vec::new(),
+        missing: Vec::new(),
+        missing_required: Vec::new(),
+        coverage_percent: 100.0,
+    };
+
+    let keywords = required
+        .iter()
+        .map(|skill| (skill, true))
+        .chain(nice_to_have.iter().map(|skill| (skill, false)));
+    for (skill, is_required) in keywords {
+        let tokens = stemmed_tokens(skill);
+        if tokens.is_empty() || seen.contains(&tokens) {
+            continue;
+        }
+        let found = resume_tokens.windows(tokens.len()).any(|window| window == tokens.as_slice());
+        let keyword = skill.trim().to_string();
+        if found {
+            coverage.matched.push(keyword);
+        } else {
+            if is_required {
+                coverage.missing_required.push(keyword.clone());
+            }
+            coverage.missing.push(keyword);
+        }
+        seen.push(tokens);
+    }
+
+    let total = coverage.matched.len() + coverage.missing.len();
+    if total > 0 {
+        coverage.coverage_percent = (coverage.matched.len() as f64 * 1000.0 / total as f64).round() / 10.0;
+    }
+    coverage
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{ResumeSection, ResumeSectionItem};
+
+    fn resume_with_bullets(bullets: Vec<String>) -> GeneratedResume {
+        GeneratedResume {
+            summary: None,
+            headline: None,
+            sections: vec![ResumeSection {
+                title: "Experience".to_string(),
+                items: vec![ResumeSectionItem {
+                    heading: "Engineer — Acme".to_string(),
+                    subheading: None,
+                    bullets,
+                }],
+            }],
+            highlights: Vec::new(),
+            contact: None,
+        }
+    }
+
+    #[test]
+    fn test_repeated_buzzword_is_flagged() {
+        let bullets = (0..10)
+            .map(|i| format!("Drove synergy across project {} to deliver results", i))
+            .collect();
+        let warnings = detect_keyword_stuffing(&resume_with_bullets(bullets));
+
+        let synergy = warnings.iter().find(|w| w.term == "synergy").expect("synergy flagged");
+        assert_eq!(synergy.count, 10);
+        assert!(synergy.density > STUFFING_DENSITY_THRESHOLD);
+    }
+
+    #[test]
+    fn test_balanced_resume_has_no_warnings() {
+        let bullets = vec![
+            "Built a Rust ingestion service handling 2M events per day".to_string(),
+            "Reduced p99 latency by 40% by redesigning the caching layer".to_string(),
+            "Mentored four engineers and introduced structured code reviews".to_string(),
+            "Migrated billing from cron jobs to an event-driven pipeline".to_string(),
+            "Partnered with product to ship a self-serve onboarding flow".to_string(),
+        ];
+        assert!(detect_keyword_stuffing(&resume_with_bullets(bullets)).is_empty());
+    }
+
+    #[test]
+    fn test_skills_section_is_ignored() {
+        let mut resume = resume_with_bullets(vec!["Shipped features".to_string()]);
+        resume.sections.push(ResumeSection {
+            title: "Skills".to_string(),
+            items: vec![ResumeSectionItem {
+                heading: "Technical".to_string(),
+                subheading: None,
+                bullets: vec!["Rust, Rust, Rust, Rust, Rust".to_string()],
+            }],
+        });
+        assert!(detect_keyword_stuffing(&resume).is_empty());
+    }
+
+    fn skills(names: &[&str]) -> Vec<String> {
+        names.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_keyword_coverage_matches_case_and_plurals() {
+        let text = "## Experience\n- Designed REST API endpoints in rust backed by PostgreSQL databases\n- Built Machine Learning pipelines with C++";
+        let coverage = keyword_coverage(
+            text,
+            &skills(&["Rust", "REST APIs", "database", "Kubernetes", "rust"]),
+            &skills(&["machine learning", "C++", "Go"]),
+        );
+
+        assert_eq!(coverage.matched, skills(&["Rust", "REST APIs", "database", "machine learning", "C++"]));
+        assert_eq!(coverage.missing, skills(&["Kubernetes", "Go"]));
+        assert_eq!(coverage.missing_required, skills(&["Kubernetes"]));
+        assert_eq!(coverage.coverage_percent, 71.4);
+    }
+
+    #[test]
+    fn test_keyword_coverage_needs_whole_words() {
+        let coverage = keyword_coverage("Led a javascript migration", &skills(&["Java"]), &[]);
+        assert_eq!(coverage.missing, skills(&["Java"]));
+        assert_eq!(coverage.coverage_percent, 0.0);
+
+        assert_eq!(keyword_coverage("Anything", &[], &[]).coverage_percent, 100.0);
+    }
+}
@@ -0,0 +1,360 @@
+//! Resume and cover letter exports for attaching to applications
+//!
+//! Exports are rebuilt from the structured `GeneratedResume`/`GeneratedLetter` saved in
+//! an artifact's `ai_payload`, not from the flattened text, so headings and bullets keep
+//! their layout.
+
+use crate::commands::{
+    resume_header_lines, Artifact, GeneratedLetter, GeneratedResume, ResumeContact, UserProfile,
+};
+use crate::pdf_writer::{PdfDocument, PdfFont};
+use docx_rs::{
+    AbstractNumbering, Docx, IndentLevel, Level, LevelJc, LevelText, NumberFormat, Numbering,
+    NumberingId, Paragraph, Run, SpecialIndentType, Start, Style, StyleType,
+};
+
+const HEADLINE_SIZE: f32 = 18.0;
+const CONTACT_SIZE: f32 = 10.0;
+const SECTION_TITLE_SIZE: f32 = 13.0;
+const HEADING_SIZE: f32 = 11.0;
+const BODY_SIZE: f32 = 10.0;
+const BULLET_INDENT: f32 = 14.0;
+
+/// Numbering id for bullet lists; docx-rs always writes its default decimal list as 1
+const BULLET_NUMBERING_ID: usize = 2;
+
+/// Deserialize the structured resume saved with a resume artifact
+pub fn resume_from_artifact(artifact: &Artifact) -> Result<GeneratedResume, String> {
+    payload_from_artifact(artifact, "Resume", "resume")
+}
+
+/// Deserialize the structured letter saved with a cover letter artifact
+pub fn letter_from_artifact(artifact: &Artifact) -> Result<GeneratedLetter, String> {
+    payload_from_artifact(artifact, "CoverLetter", "cover letter")
+}
+
+fn payload_from_artifact<T: serde::de::DeserializeOwned>(
+    artifact: &Artifact,
+    artifact_type: &str,
+    label: &str,
+) -> Result<T, String> {
+    if artifact.r#type != artifact_type {
+        return Err(format!("Artifact {} is a {}, not a {}", artifact.id, artifact.r#type, label));
+    }
+    let payload = artifact
+        .ai_payload
+        .as_deref()
+        .filter(|payload| !payload.trim().is_empty())
+        .ok_or_else(|| format!("Artifact {} has no structured {} to export", artifact.id, label))?;
+    serde_json::from_str(payload).map_err(|e| format!("Failed to read the saved {}: {}", label, e))
+}
+
+/// Lay out a resume as a PDF: headline and contact line, summary, then each section.
+/// Uses the standard Helvetica fonts, which every PDF viewer provides, so the file
+/// renders the same everywhere. Item headings are kept on the same page as their
+/// first line; long bullet lists continue on the next page.
+pub fn render_resume_to_pdf(resume: &GeneratedResume) -> Vec<u8> {
+    let mut doc = PdfDocument::new();
+
+    let mut header_lines = resume_header_lines(resume).into_iter();
+    if let Some(headline) = header_lines.next() {
+        let (font, size) = if resume.headline.is_some() {
+            (PdfFont::Bold, HEADLINE_SIZE)
+        } else {
+            (PdfFont::Regular, CONTACT_SIZE)
+        };
+        doc.text(&headline, font, size);
+    }
+    for line in header_lines {
+        doc.text(&line, PdfFont::Regular, CONTACT_SIZE);
+    }
+
+    if let Some(summary) = resume.summary.as_deref().filter(|s| !s.trim().is_empty()) {
+        doc.spacer(8.0);
+        doc.text(summary, PdfFont::Regular, BODY_SIZE + 1.0);
+    }
+
+    for section in &resume.sections {
+        doc.spacer(12.0);
+        // Title plus the first item's heading
+        doc.ensure_space(SECTION_TITLE_SIZE * 2.0 + HEADING_SIZE * 1.5);
+        doc.text(&section.title, PdfFont::Bold, SECTION_TITLE_SIZE);
+        doc.spacer(2.0);
+
+        for item in &section.items {
+            doc.ensure_space(HEADING_SIZE * 1.5 + BODY_SIZE * 1.5);
+            doc.text(&item.heading, PdfFont::Bold, HEADING_SIZE);
+            if let Some(subheading) = item.subheading.as_deref().filter(|s| !s.trim().is_empty()) {
+                doc.text(subheading, PdfFont::Regular, BODY_SIZE);
+            }
+            for bullet in &item.bullets {
+                doc.text_indented(&format!("\u{2022} {}", bullet), PdfFont::Regular, BODY_SIZE, BULLET_INDENT);
+            }
+            doc.spacer(4.0);
+        }
+    }
+
+    doc.finish()
+}
+
+/// Named paragraph styles and the bullet list definition, so the output can be
+/// restyled in Word
+fn docx_with_styles() -> Docx {
+    Docx::new()
+        .add_style(Style::new("Title", StyleType::Paragraph).name("Title").bold().size(36))
+        .add_style(Style::new("Subtitle", StyleType::Paragraph).name("Subtitle").size(22))
+        .add_style(Style::new("Heading1", StyleType::Paragraph).name("Heading 1").bold().size(26))
+        .add_style(Style::new("Heading2", StyleType::Paragraph).name("Heading 2").bold().size(22))
+        .add_style(Style::new("ListBullet", StyleType::Paragraph).name("List Bullet").size(20))
+        .add_abstract_numbering(
+            AbstractNumbering::new(BULLET_NUMBERING_ID).add_level(
+                Level::new(
+                    0,
+                    Start::new(1),
+                    NumberFormat::new("bullet"),
+                    LevelText::new("\u{2022}"),
+                    LevelJc::new("left"),
+                )
+                .indent(Some(720), Some(SpecialIndentType::Hanging(360)), None, None),
+            ),
+        )
+        .add_numbering(Numbering::new(BULLET_NUMBERING_ID, BULLET_NUMBERING_ID))
+}
+
+fn styled(text: &str, style: &str) -> Paragraph {
+    Paragraph::new().add_run(Run::new().add_text(text)).style(style)
+}
+
+fn bullet(text: &str) -> Paragraph {
+    styled(text, "ListBullet").numbering(NumberingId::new(BULLET_NUMBERING_ID), IndentLevel::new(0))
+}
+
+/// Name and contact line from the profile, as the first paragraphs of the document
+fn add_profile_header(mut docx: Docx, profile: &UserProfile) -> Docx {
+    if !profile.full_name.trim().is_empty() {
+        docx = docx.add_paragraph(styled(&profile.full_name, "Title"));
+    }
+    if let Some(contact) = ResumeContact::from_profile(profile).header_line() {
+        docx = docx.add_paragraph(styled(&contact, "Subtitle"));
+    }
+    docx
+}
+
+fn pack_docx(docx: Docx) -> Result<Vec<u8>, String> {
+    let mut buffer = std::io::Cursor::new(Vec::new());
+    docx.build()
+        .pack(&mut buffer)
+        .map_err(|e| format!("Failed to write DOCX: {}", e))?;
+    Ok(buffer.into_inner())
+}
+
+/// Build a Word document from a resume: section titles use Heading 1, items Heading 2
+/// and bullets a real bulleted list, in the resume's section order. With a profile
+/// the header shows its name and contact details; without one it falls back to the
+/// resume's own headline and contact line.
+pub fn render_resume_to_docx(resume: &GeneratedResume, profile: Option<&UserProfile>) -> Result<Vec<u8>, String> {
+    let mut docx = docx_with_styles();
+    match profile {
+        Some(profile) => {
+            docx = add_profile_header(docx, profile);
+            if let Some(headline) = &resume.headline {
+                docx = docx.add_paragraph(styled(headline, "Subtitle"));
+            }
+        }
+        None => {
+            for (index, line) in resume_header_lines(resume).iter().enumerate() {
+                let style = if index == 0 && resume.headline.is_some() { "Title" } else { "Subtitle" };
+                docx = docx.add_paragraph(styled(line, style));
+            }
+        }
+    }
+
+    if let Some(summary) = resume.summary.as_deref().filter(|s| !s.trim().is_empty()) {
+        docx = docx.add_paragraph(styled(summary, "Normal"));
+    }
+
+    for section in &resume.sections {
+        docx = docx.add_paragraph(styled(&section.title, "Heading1"));
+        for item in &section.items {
+            docx = docx.add_paragraph(styled(&item.heading, "Heading2"));
+            if let Some(subheading) = item.subheading.as_deref().filter(|s| !s.trim().is_empty()) {
+                docx = docx.add_paragraph(
+                    Paragraph::new().add_run(Run::new().add_text(subheading).italic()).style("Normal"),
+                );
+            }
+            for text in &item.bullets {
+                docx = docx.add_paragraph(bullet(text));
+            }
+        }
+    }
+
+    pack_docx(docx)
+}
+
+/// Build a Word document from a cover letter, one Normal paragraph per part, under the
+/// profile's name and contact details when a profile is given
+pub fn render_letter_to_docx(letter: &GeneratedLetter, profile: Option<&UserProfile>) -> Result<Vec<u8>, String> {
+    let mut docx = docx_with_styles();
+    if let Some(profile) = profile {
+        docx = add_profile_header(docx, profile);
+    }
+    if let Some(subject) = &letter.subject {
+        docx = docx.add_paragraph(
+            Paragraph::new().add_run(Run::new().add_text(subject.as_str()).bold()).style("Normal"),
+        );
+    }
+    let parts = letter
+        .greeting
+        .iter()
+        .chain(letter.body_paragraphs.iter())
+        .chain(letter.closing.iter())
+        .chain(letter.signature.iter());
+    for part in parts {
+        docx = docx.add_paragraph(styled(part, "Normal"));
+    }
+    pack_docx(docx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{ResumeSection, ResumeSectionItem};
+
+    fn resume(bullets: usize) -> GeneratedResume {
+        GeneratedResume {
+            summary: Some("Backend engineer".to_string()),
+            headline: Some("Sam Lee".to_string()),
+            sections: vec![ResumeSection {
+                title: "Experience".to_string(),
+                items: vec![ResumeSectionItem {
+                    heading: "Engineer at Acme".to_string(),
+                    subheading: Some("2020 - 2024".to_string()),
+                    bullets: (0..bullets).map(|i| format!("Shipped feature {}", i)).collect(),
+                }],
+            }],
+            highlights: vec![],
+            contact: None,
+        }
+    }
+
+    fn artifact(r#type: &str, ai_payload: Option<String>) -> Artifact {
+        Artifact {
+            id: 7,
+            application_id: None,
+            job_id: Some(1),
+            r#type: r#type.to_string(),
+            title: "Resume".to_string(),
+            content: None,
+            format: None,
+            ai_payload,
+            ai_model: None,
+            source: None,
+            version: None,
+            created_at: "2025-01-01T00:00:00Z".to_string(),
+            updated_at: "2025-01-01T00:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_resume_pdf_contains_sections() {
+        let bytes = render_resume_to_pdf(&resume(3));
+        let pdf = String::from_utf8_lossy(&bytes);
+        assert!(pdf.starts_with("%PDF-1.4"));
+        assert!(pdf.contains("(Sam Lee)"));
+        assert!(pdf.contains("(Experience)"));
+        assert!(pdf.contains("Shipped feature 2"));
+        assert_eq!(pdf.matches("/Type /Page ").count(), 1);
+    }
+
+    #[test]
+    fn test_long_experience_breaks_pages() {
+        let bytes = render_resume_to_pdf(&resume(120));
+        let pdf = String::from_utf8_lossy(&bytes);
+        assert!(pdf.matches("/Type /Page ").count() > 1);
+        assert!(pdf.contains("Shipped feature 119"));
+    }
+
+    fn profile() -> UserProfile {
+        serde_json::from_value(serde_json::json!({
+            "id": 1,
+            "full_name": "Sam Lee",
+            "headline": null,
+            "location": "Berlin",
+            "summary": null,
+            "current_role_title": null,
+            "current_company": null,
+            "seniority": null,
+            "open_to_roles": null,
+            "created_at": null,
+            "updated_at": null,
+            "email": "sam@example.com"
+        }))
+        .unwrap()
+    }
+
+    /// (style, text) of each paragraph in a generated document
+    fn paragraphs(bytes: &[u8]) -> Vec<(String, String)> {
+        let docx = docx_rs::read_docx(bytes).unwrap();
+        docx.document
+            .children
+            .iter()
+            .filter_map(|child| match child {
+                docx_rs::DocumentChild::Paragraph(p) => Some((
+                    p.property.style.as_ref().map(|s| s.val.clone()).unwrap_or_default(),
+                    p.raw_text(),
+                )),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_resume_docx_uses_word_styles() {
+        let mut resume = resume(2);
+        resume.headline = Some("Backend Engineer".to_string());
+        resume.sections.push(ResumeSection {
+            title: "Skills".to_string(),
+            items: vec![],
+        });
+        let bytes = render_resume_to_docx(&resume, Some(&profile())).unwrap();
+        let paragraphs = paragraphs(&bytes);
+
+        assert_eq!(paragraphs[0], ("Title".to_string(), "Sam Lee".to_string()));
+        assert_eq!(paragraphs[1], ("Subtitle".to_string(), "Berlin • sam@example.com".to_string()));
+        assert_eq!(paragraphs[2].1, "Backend Engineer");
+        let headings: Vec<&str> = paragraphs
+            .iter()
+            .filter(|(style, _)| style == "Heading1")
+            .map(|(_, text)| text.as_str())
+            .collect();
+        assert_eq!(headings, vec!["Experience", "Skills"]);
+        assert_eq!(paragraphs.iter().filter(|(style, _)| style == "ListBullet").count(), 2);
+    }
+
+    #[test]
+    fn test_letter_docx_paragraphs() {
+        let letter = GeneratedLetter {
+            subject: Some("Application".to_string()),
+            greeting: Some("Dear team,".to_string()),
+            body_paragraphs: vec!["First.".to_string(), "Second.".to_string()],
+            closing: Some("Best,".to_string()),
+            signature: Some("Sam".to_string()),
+        };
+        let texts: Vec<String> = paragraphs(&render_letter_to_docx(&letter, None).unwrap())
+            .into_iter()
+            .map(|(_, text)| text)
+            .collect();
+        assert_eq!(texts, vec!["Application", "Dear team,", "First.", "Second.", "Best,", "Sam"]);
+    }
+
+    #[test]
+    fn test_resume_from_artifact() {
+        let payload = serde_json::to_string(&resume(1)).unwrap();
+        let loaded = resume_from_artifact(&artifact("Resume", Some(payload.clone()))).unwrap();
+        assert_eq!(loaded.headline.as_deref(), Some("Sam Lee"));
+
+        assert!(resume_from_artifact(&artifact("CoverLetter", Some(payload))).is_err());
+        assert!(resume_from_artifact(&artifact("Resume", None)).unwrap_err().contains("no structured resume"));
+        assert!(letter_from_artifact(&artifact("Resume", None)).unwrap_err().contains("not a cover letter"));
+    }
+}
@@ -4,6 +4,7 @@
 
 use serde::{Deserialize, Serialize};
 use crate::commands::{UserProfileData, Experience, Skill, ParsedJob};
+use crate::language::Lang;
 
 /// Job Description Summary - extracted from JD via small AI call
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -226,6 +227,43 @@ pub fn calculate_bullet_relevance(
     score.min(1.0)
 }
 
+/// How many roles the resume pipeline tailors for a job
+pub const RESUME_TOP_ROLES: usize = 3;
+
+/// A role's relevance to a job and whether the pipeline would include it
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RoleRelevance {
+    pub experience_id: Option<i64>,
+    pub company: String,
+    pub title: String,
+    pub score: f64,
+    pub matched_skills: Vec<String>,
+    pub matched_responsibilities: Vec<String>,
+    pub included: bool,
+}
+
+/// Score every role the way `select_top_roles` does, most relevant first,
+/// marking the top `top_n` as included
+pub fn preview_top_roles(
+    experiences: &[Experience],
+    jd_summary: &JobDescriptionSummary,
+    top_n: usize,
+) -> Vec<RoleRelevance> {
+    select_top_roles(experiences, jd_summary, experiences.len())
+        .into_iter()
+        .enumerate()
+        .map(|(rank, mapped)| RoleRelevance {
+            experience_id: mapped.experience.id,
+            company: mapped.experience.company,
+            title: mapped.experience.title,
+            score: mapped.relevance_score.score,
+            matched_skills: mapped.relevance_score.matched_skills,
+            matched_responsibilities: mapped.relevance_score.matched_responsibilities,
+            included: rank < top_n,
+        })
+        .collect()
+}
+
 /// Select top N roles by relevance
 pub fn select_top_roles(
     experiences: &[Experience],
@@ -387,41 +425,96 @@ pub fn select_top_skills(
 // AI Helper Functions for Small, Focused Calls
 // ============================================================================
 
-use crate::ai::resolver::ResolvedProvider;
+use crate::generation_context::GenerationContext;
 
 /// Step 1: Summarize job description (small AI call ~300-500 tokens input, ~150-250 tokens output)
 pub async fn summarize_job_description(
+    ctx: &GenerationContext,
     job_description: &str,
     parsed_job: Option<&ParsedJob>,
 ) -> Result<JobDescriptionSummary, String> {
-    use crate::ai_cache::{ai_cache_get, ai_cache_put, compute_input_hash, CACHE_TTL_JOB_PARSE_DAYS};
-    use crate::db::get_connection;
     use chrono::Utc;
     
-    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
     let now = Utc::now().to_rfc3339();
     
+    if let Some(summary) = get_cached_jd_summary(&ctx.conn(), job_description, parsed_job, &now)? {
+        return Ok(summary);
+    }
+    
+    // Cache miss - call AI provider
+    let provider = ctx.provider_for("jd_summary")?;
+    let summary = request_jd_summary(provider.as_ref(), job_description).await?;
+    
+    cache_jd_summary(&ctx.conn(), &ctx.model_for("jd_summary"), job_description, parsed_job, &summary, &now)?;
+    
+    Ok(summary)
+}
+
+fn jd_summary_request(
+    job_description: &str,
+    parsed_job: Option<&ParsedJob>,
+) -> Result<(serde_json::Value, String), String> {
     // Build canonical request payload
     let request_payload = serde_json::json!({
         "jobDescription": job_description,
         "parsedJob": parsed_job,
     });
-    
-    // Check cache
-    let input_hash = compute_input_hash(&request_payload)
+    let input_hash = crate::ai_cache::compute_input_hash(&request_payload)
         .map_err(|e| format!("Failed to compute hash: {}", e))?;
+    Ok((request_payload, input_hash))
+}
+
+/// Look up a cached JD summary
+pub fn get_cached_jd_summary(
+    conn: &rusqlite::Connection,
+    job_description: &str,
+    parsed_job: Option<&ParsedJob>,
+    now: &str,
+) -> Result<Option<JobDescriptionSummary>, String> {
+    let (_, input_hash) = jd_summary_request(job_description, parsed_job)?;
     
-    if let Some(cached_entry) = ai_cache_get(&conn, "jd_summary", &input_hash, &now)
+    match crate::ai_cache::ai_cache_get(conn, "jd_summary", &input_hash, now)
         .map_err(|e| format!("Cache lookup error: {}", e))? {
-        let summary: JobDescriptionSummary = serde_json::from_value(cached_entry.response_payload)
-            .map_err(|e| format!("Failed to deserialize cached summary: {}", e))?;
-        return Ok(summary);
+        Some(cached_entry) => serde_json::from_value(cached_entry.response_payload)
+            .map(Some)
+            .map_err(|e| format!("Failed to deserialize cached summary: {}", e)),
+        None => Ok(None),
     }
+}
+
+/// Store a JD summary in the cache
+pub fn cache_jd_summary(
+    conn: &rusqlite::Connection,
+    model_name: &str,
+    job_description: &str,
+    parsed_job: Option<&ParsedJob>,
+    summary: &JobDescriptionSummary,
+    now: &str,
+) -> Result<(), String> {
+    use crate::ai_cache::{ai_cache_put, CACHE_TTL_JOB_PARSE_DAYS};
     
-    // Cache miss - call AI provider
-    let provider = ResolvedProvider::resolve()
-        .map_err(|e| format!("Failed to resolve provider: {}", e))?;
+    let (request_payload, input_hash) = jd_summary_request(job_description, parsed_job)?;
+    let response_payload = serde_json::to_value(summary)
+        .map_err(|e| format!("Failed to serialize summary: {}", e))?;
     
+    ai_cache_put(
+        conn,
+        "jd_summary",
+        &input_hash,
+        model_name,
+        &request_payload,
+        &response_payload,
+        Some(CACHE_TTL_JOB_PARSE_DAYS),
+        now,
+    )
+    .map_err(|e| format!("Failed to cache summary: {}", e))
+}
+
+/// Ask the provider to summarize a job description
+pub async fn request_jd_summary(
+    provider: &dyn crate::ai::provider::AiProvider,
+    job_description: &str,
+) -> Result<JobDescriptionSummary, String> {
     // Build prompt for JD summary (small, focused)
     // Note: Currently using parse_job as a base - can be enhanced with dedicated JD summary call
     // let _system_prompt = "You are a job description analyzer...";
@@ -433,13 +526,13 @@ pub async fn summarize_job_description(
         job_meta: None,
     };
     
-    let parsed = provider.as_provider()
+    let parsed = provider
         .parse_job(parsing_input)
         .await
         .map_err(|e| format!("AI parsing failed: {}", e))?;
     
     // Convert ParsedJobOutput to JobDescriptionSummary
-    let summary = JobDescriptionSummary {
+    Ok(JobDescriptionSummary {
         role_title: parsed.title_suggestion,
         seniority: parsed.seniority,
         must_have_skills: parsed.required_skills,
@@ -447,44 +540,22 @@ pub async fn summarize_job_description(
         top_responsibilities: parsed.responsibilities,
         tools_tech: parsed.domain_tags, // Using domain_tags as tools/tech
         tone: None, // Can be enhanced later
-    };
-    
-    // Store in cache
-    let response_payload = serde_json::to_value(&summary)
-        .map_err(|e| format!("Failed to serialize summary: {}", e))?;
-    
-    let model_name = crate::ai::settings::load_ai_settings()
-        .ok()
-        .and_then(|s| s.model_name)
-        .unwrap_or_else(|| "unknown-model".to_string());
-    
-    ai_cache_put(
-        &conn,
-        "jd_summary",
-        &input_hash,
-        &model_name,
-        &request_payload,
-        &response_payload,
-        Some(CACHE_TTL_JOB_PARSE_DAYS),
-        &now,
-    )
-    .map_err(|e| format!("Failed to cache summary: {}", e))?;
-    
-    Ok(summary)
+    })
 }
 
 /// Step 2: Rewrite bullets for a role (small AI call ~300-600 tokens input, ~100-200 tokens output)
 pub async fn rewrite_bullets_for_role(
+    ctx: &GenerationContext,
     role_title: &str,
     company: &str,
     bullets: &[MappedBullet],
     jd_summary: &JobDescriptionSummary,
+    output_language: Lang,
 ) -> Result<Vec<RewrittenBullet>, String> {
     use crate::ai_cache::{ai_cache_get, ai_cache_put, compute_input_hash, CACHE_TTL_RESUME_DAYS};
-    use crate::db::get_connection;
     use chrono::Utc;
     
-    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    let conn = ctx.conn();
     let now = Utc::now().to_rfc3339();
     
     // Build canonical request payload
@@ -496,6 +567,7 @@ pub async fn rewrite_bullets_for_role(
             "text": b.original_text
         })).collect::<Vec<_>>(),
         "jdSummary": jd_summary,
+        "outputLanguage": output_language.code(),
     });
     
     // Check cache
@@ -526,10 +598,7 @@ pub async fn rewrite_bullets_for_role(
     let response_payload = serde_json::to_value(&rewritten)
         .map_err(|e| format!("Failed to serialize rewritten bullets: {}", e))?;
     
-    let model_name = crate::ai::settings::load_ai_settings()
-        .ok()
-        .and_then(|s| s.model_name)
-        .unwrap_or_else(|| "unknown-model".to_string());
+    let model_name = ctx.model_for("bullet_rewrite");
     
     ai_cache_put(
         &conn,
@@ -548,20 +617,22 @@ pub async fn rewrite_bullets_for_role(
 
 /// Step 3: Generate professional summary (optional small AI call)
 pub async fn generate_professional_summary(
+    ctx: &GenerationContext,
     profile_data: &UserProfileData,
     jd_summary: &JobDescriptionSummary,
+    output_language: Lang,
 ) -> Result<String, String> {
     use crate::ai_cache::{ai_cache_get, ai_cache_put, compute_input_hash, CACHE_TTL_RESUME_DAYS};
-    use crate::db::get_connection;
     use chrono::Utc;
     
-    let conn = get_connection().map_err(|e| format!("DB error: {}", e))?;
+    let conn = ctx.conn();
     let now = Utc::now().to_rfc3339();
     
     // Build canonical request payload
     let request_payload = serde_json::json!({
         "profile": profile_data.profile,
         "jdSummary": jd_summary,
+        "outputLanguage": output_language.code(),
     });
     
     // Check cache
@@ -606,10 +677,7 @@ pub async fn generate_professional_summary(
     let response_payload = serde_json::to_value(&summary)
         .map_err(|e| format!("Failed to serialize summary: {}", e))?;
     
-    let model_name = crate::ai::settings::load_ai_settings()
-        .ok()
-        .and_then(|s| s.model_name)
-        .unwrap_or_else(|| "unknown-model".to_string());
+    let model_name = ctx.model_for("professional_summary");
     
     ai_cache_put(
         &conn,
@@ -626,3 +694,270 @@ pub async fn generate_professional_summary(
     Ok(summary)
 }
 
+
+/// Split an experience's achievements text into bullets, dropping list markers
+pub fn split_achievement_bullets(achievements: &str) -> Vec<String> {
+    achievements
+        .lines()
+        .map(|line| {
+            line.trim()
+                .trim_start_matches(|c: char| c == '-' || c == '*' || c == '•' || c == '–')
+                .trim()
+                .to_string()
+        })
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
+#[derive(Debug, Deserialize)]
+struct StarRewriteResponse {
+    bullets: Vec<String>,
+}
+
+fn star_rewrite_request(bullets: &[String]) -> Result<(serde_json::Value, String), String> {
+    let request_payload = serde_json::json!({
+        "achievements": bullets,
+    });
+    let input_hash = crate::ai_cache::compute_input_hash(&request_payload)
+        .map_err(|e| format!("Failed to compute hash: {}", e))?;
+    Ok((request_payload, input_hash))
+}
+
+/// Look up a cached STAR rewrite for these achievement bullets
+pub fn get_cached_star_rewrite(
+    conn: &rusqlite::Connection,
+    bullets: &[String],
+    now: &str,
+) -> Result<Option<Vec<String>>, String> {
+    let (_, input_hash) = star_rewrite_request(bullets)?;
+
+    match crate::ai_cache::ai_cache_get(conn, "star_rewrite", &input_hash, now)
+        .map_err(|e| format!("Cache lookup error: {}", e))? {
+        Some(cached_entry) => serde_json::from_value(cached_entry.response_payload)
+            .map(Some)
+            .map_err(|e| format!("Failed to deserialize cached bullets: {}", e)),
+        None => Ok(None),
+    }
+}
+
+/// Store a STAR rewrite, keyed on the original achievement bullets
+pub fn cache_star_rewrite(
+    conn: &rusqlite::Connection,
+    bullets: &[String],
+    rewritten: &[String],
+    now: &str,
+) -> Result<(), String> {
+    use crate::ai_cache::{ai_cache_put, CACHE_TTL_RESUME_DAYS};
+
+    let (request_payload, input_hash) = star_rewrite_request(bullets)?;
+    let response_payload = serde_json::to_value(rewritten)
+        .map_err(|e| format!("Failed to serialize rewritten bullets: {}", e))?;
+
+    let model_name = crate::ai::settings::load_ai_settings()
+        .ok()
+        .and_then(|s| s.model_for("star_rewrite"))
+        .unwrap_or_else(|| "unknown-model".to_string());
+
+    ai_cache_put(
+        conn,
+        "star_rewrite",
+        &input_hash,
+        &model_name,
+        &request_payload,
+        &response_payload,
+        Some(CACHE_TTL_RESUME_DAYS),
+        now,
+    )
+    .map_err(|e| format!("Failed to cache rewritten bullets: {}", e))
+}
+
+/// Ask the provider to rewrite achievement bullets in STAR (Situation-Task-Action-Result)
+/// phrasing. One call covers all bullets, answered in the same order.
+pub async fn request_star_rewrite(
+    provider: &dyn crate::ai::provider::AiProvider,
+    bullets: &[String],
+) -> Result<Vec<String>, String> {
+    let bullet_list = bullets
+        .iter()
+        .map(|b| format!("- {}", b))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let prompt = format!(
+        r#"Rewrite each achievement below in STAR format (Situation, Task, Action, Result) as a single resume bullet.
+Keep every fact from the original and do not invent numbers. Return exactly one bullet per achievement, in the same order.
+
+Achievements:
+{}
+
+Return JSON only: {{"bullets": ["..."]}}"#,
+        bullet_list
+    );
+    let system_prompt = Some("You are a resume writing assistant. Always return valid JSON matching the specified schema.");
+
+    let response = provider
+        .call_llm(system_prompt, &prompt)
+        .await
+        .map_err(|e| format!("AI rewrite failed: {}", e))?;
+
+    let json_str = response
+        .find('{')
+        .and_then(|start| response.rfind('}').map(|end| &response[start..=end]))
+        .unwrap_or(response.as_str());
+    let parsed: StarRewriteResponse = serde_json::from_str(json_str)
+        .map_err(|e| format!("Failed to parse AI response: {}", e))?;
+
+    if parsed.bullets.len() != bullets.len() {
+        return Err(format!(
+            "AI returned {} bullets for {} achievements; please try again.",
+            parsed.bullets.len(),
+            bullets.len()
+        ));
+    }
+
+    Ok(parsed.bullets.into_iter().map(|b| b.trim().to_string()).collect())
+}
+
+/// Result of tightening text to a word limit
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TightenedContent {
+    pub content: String,
+    pub word_count: usize,
+    pub max_words: usize,
+    /// Set when the provider could not get under the limit and this is a best effort
+    pub warning: Option<String>,
+}
+
+/// Count whitespace-separated words
+pub fn count_words(text: &str) -> usize {
+    text.split_whitespace().count()
+}
+
+fn tighten_request(content: &str, max_words: usize) -> Result<(serde_json::Value, String), String> {
+    let request_payload = serde_json::json!({
+        "content": content,
+        "maxWords": max_words,
+    });
+    let input_hash = crate::ai_cache::compute_input_hash(&request_payload)
+        .map_err(|e| format!("Failed to compute hash: {}", e))?;
+    Ok((request_payload, input_hash))
+}
+
+/// Look up a cached tightened version of this content for the given limit
+pub fn get_cached_tightened_content(
+    conn: &rusqlite::Connection,
+    content: &str,
+    max_words: usize,
+    now: &str,
+) -> Result<Option<TightenedContent>, String> {
+    let (_, input_hash) = tighten_request(content, max_words)?;
+
+    match crate::ai_cache::ai_cache_get(conn, "tighten_content", &input_hash, now)
+        .map_err(|e| format!("Cache lookup error: {}", e))? {
+        Some(cached_entry) => serde_json::from_value(cached_entry.response_payload)
+            .map(Some)
+            .map_err(|e| format!("Failed to deserialize cached content: {}", e)),
+        None => Ok(None),
+    }
+}
+
+/// Store a tightened version, keyed on the original content and word limit
+pub fn cache_tightened_content(
+    conn: &rusqlite::Connection,
+    content: &str,
+    max_words: usize,
+    tightened: &TightenedContent,
+    now: &str,
+) -> Result<(), String> {
+    use crate::ai_cache::{ai_cache_put, CACHE_TTL_COVER_LETTER_DAYS};
+
+    let (request_payload, input_hash) = tighten_request(content, max_words)?;
+    let response_payload = serde_json::to_value(tightened)
+        .map_err(|e| format!("Failed to serialize tightened content: {}", e))?;
+
+    let model_name = crate::ai::settings::load_ai_settings()
+        .ok()
+        .and_then(|s| s.model_for("tighten_content"))
+        .unwrap_or_else(|| "unknown-model".to_string());
+
+    ai_cache_put(
+        conn,
+        "tighten_content",
+        &input_hash,
+        &model_name,
+        &request_payload,
+        &response_payload,
+        Some(CACHE_TTL_COVER_LETTER_DAYS),
+        now,
+    )
+    .map_err(|e| format!("Failed to cache tightened content: {}", e))
+}
+
+fn tighten_prompt(content: &str, word_limit: usize, strict: bool) -> String {
+    let strictness = if strict {
+        "Your previous attempt was too long. Be much more aggressive: drop secondary points and merge sentences. "
+    } else {
+        ""
+    };
+    format!(
+        r#"Tighten the text below to at most {} words while preserving its meaning, tone and key facts.
+{}Do not add new information. Return only the rewritten text, with no preamble or commentary.
+
+Text:
+{}"#,
+        word_limit, strictness, content
+    )
+}
+
+/// Ask the provider to cut content to under `max_words`.
+///
+/// If the first answer is still over the limit, retries once with a stricter instruction
+/// and a lower target, then returns the shorter attempt with a warning if neither fits.
+pub async fn request_tightened_content(
+    provider: &dyn crate::ai::provider::AiProvider,
+    content: &str,
+    max_words: usize,
+) -> Result<TightenedContent, String> {
+    let system_prompt = Some("You are an editor who shortens professional writing without losing meaning.");
+
+    let first = provider
+        .call_llm(system_prompt, &tighten_prompt(content, max_words, false))
+        .await
+        .map_err(|e| format!("AI tightening failed: {}", e))?
+        .trim()
+        .to_string();
+    if count_words(&first) <= max_words {
+        return Ok(TightenedContent {
+            word_count: count_words(&first),
+            content: first,
+            max_words,
+            warning: None,
+        });
+    }
+
+    // Models tend to overshoot, so aim below the limit on the retry
+    let strict_limit = (max_words * 85 / 100).max(1);
+    let second = provider
+        .call_llm(system_prompt, &tighten_prompt(content, strict_limit, true))
+        .await
+        .map_err(|e| format!("AI tightening failed: {}", e))?
+        .trim()
+        .to_string();
+
+    let best = if count_words(&second) <= count_words(&first) { second } else { first };
+    let word_count = count_words(&best);
+    let warning = if word_count > max_words {
+        Some(format!(
+            "Could only shorten this to {} words (limit {}); trim the rest by hand.",
+            word_count, max_words
+        ))
+    } else {
+        None
+    };
+
+    Ok(TightenedContent {
+        content: best,
+        word_count,
+        max_words,
+        warning,
+    })
+}
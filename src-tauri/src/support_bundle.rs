@@ -0,0 +1,343 @@
+//! Support bundle: a zip of redacted logs and diagnostics users can attach to bug reports
+//!
+//! The database itself is never included. Logs are run through `redact_support_text`,
+//! which strips API keys and credentials, emails, phone numbers, addresses and any
+//! names known from the database; everything else in the bundle is counts and status.
+
+use crate::ai::pii_scrubber::PiiScrubber;
+use crate::ai::settings::AiSettings;
+use crate::errors::{CareerBenchError, FileSystemError};
+use chrono::{Datelike, NaiveDateTime, Timelike};
+use regex::{Regex, RegexBuilder};
+use rusqlite::Connection;
+use serde_json::{json, Map, Value};
+use std::io::{Cursor, Write};
+use std::path::Path;
+use std::sync::LazyLock;
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+/// Log files in the app data directory that go into the bundle
+pub const SUPPORT_LOG_FILES: &[&str] = &["careerbench.log", "crash.log"];
+/// Only the end of each log is included
+const LOG_TAIL_LINES: usize = 2000;
+/// Name parts shorter than this aren't redacted on their own ("Al", "Li" match too much)
+const MIN_NAME_PART_CHARS: usize = 3;
+
+/// Tables whose row counts are reported; missing tables are skipped
+const COUNTED_TABLES: &[&str] = &[
+    "experience",
+    "skills",
+    "education",
+    "jobs",
+    "applications",
+    "application_events",
+    "artifacts",
+    "reminders",
+    "recruiter_contacts",
+    "companies",
+    "learning_plans",
+    "email_accounts",
+    "email_messages",
+    "ai_cache",
+    "ai_call_log",
+];
+
+/// Secret patterns and what they're replaced with
+static SECRET_PATTERNS: LazyLock<Vec<(Regex, &'static str)>> = LazyLock::new(|| {
+    [
+        // OpenAI / Anthropic style keys
+        (r"\bsk-[A-Za-z0-9_-]{8,}", "[REDACTED_KEY]"),
+        // Google API keys
+        (r"\bAIza[0-9A-Za-z_-]{20,}", "[REDACTED_KEY]"),
+        (r"(?i)\b(bearer)\s+[A-Za-z0-9._~+/=-]+", "$1 [REDACTED]"),
+        (
+            r#"(?i)\b(api[_-]?key|access[_-]?token|token|secret|password)(["']?\s*[:=]\s*["']?)[^\s"',}]+"#,
+            "$1$2[REDACTED]",
+        ),
+        // Long opaque strings, e.g. encrypted keys copied into a log line
+        (r"\b[A-Za-z0-9+/_-]{40,}={0,2}", "[REDACTED_TOKEN]"),
+    ]
+    .into_iter()
+    .map(|(pattern, replacement)| (Regex::new(pattern).unwrap(), replacement))
+    .collect()
+});
+
+/// Redact API keys, bearer tokens and credential assignments from text
+pub fn redact_secrets(text: &str) -> String {
+    SECRET_PATTERNS.iter().fold(text.to_string(), |text, (re, replacement)| {
+        re.replace_all(&text, *replacement).into_owned()
+    })
+}
+
+fn table_exists(conn: &Connection, table: &str) -> bool {
+    conn.query_row(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name = ?",
+        [table],
+        |row| Ok(row.get::<_, i64>(0)? > 0),
+    )
+    .unwrap_or(false)
+}
+
+fn collect_column(conn: &Connection, sql: &str) -> Vec<String> {
+    let mut stmt = match conn.prepare(sql) {
+        Ok(stmt) => stmt,
+        Err(_) => return Vec::new(),
+    };
+    stmt.query_map([], |row| row.get::<_, Option<String>>(0))
+        .map(|rows| rows.flatten().flatten().collect())
+        .unwrap_or_default()
+}
+
+/// Names, emails and phone numbers stored in the database, so logs can be scrubbed of
+/// values the generic patterns might miss
+pub fn load_known_identifiers(conn: &Connection) -> Vec<String> {
+    let mut identifiers = Vec::new();
+
+    if table_exists(conn, "user_profile") {
+        for name in collect_column(conn, "SELECT full_name FROM user_profile") {
+            identifiers.extend(
+                name.split_whitespace()
+                    .filter(|part| part.chars().count() >= MIN_NAME_PART_CHARS)
+                    .map(|part| part.to_string()),
+            );
+            identifiers.push(name);
+        }
+        identifiers.extend(collect_column(conn, "SELECT email FROM user_profile"));
+        identifiers.extend(collect_column(conn, "SELECT phone FROM user_profile"));
+    }
+    if table_exists(conn, "recruiter_contacts") {
+        identifiers.extend(collect_column(conn, "SELECT name FROM recruiter_contacts"));
+        identifiers.extend(collect_column(conn, "SELECT email FROM recruiter_contacts"));
+        identifiers.extend(collect_column(conn, "SELECT phone FROM recruiter_contacts"));
+    }
+    if table_exists(conn, "email_accounts") {
+        identifiers.extend(collect_column(conn, "SELECT email_address FROM email_accounts"));
+    }
+
+    identifiers.retain(|value| !value.trim().is_empty());
+    // Longest first so a full name is replaced before its parts
+    identifiers.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+    identifiers.dedup();
+    identifiers
+}
+
+/// Strip secrets, PII and known identifiers from text destined for the bundle
+pub fn redact_support_text(text: &str, identifiers: &[String]) -> String {
+    let text = redact_secrets(text);
+    let mut text = PiiScrubber::new().scrub(&text);
+
+    for identifier in identifiers {
+        let pattern = format!(r"\b{}\b", regex::escape(identifier.trim()));
+        if let Ok(re) = RegexBuilder::new(&pattern).case_insensitive(true).build() {
+            text = re.replace_all(&text, "[REDACTED_NAME]").into_owned();
+        }
+    }
+    text
+}
+
+/// Read the last lines of each support log; `None` when a file doesn't exist
+pub fn read_log_tails(log_dir: &Path) -> Vec<(String, Option<String>)> {
+    SUPPORT_LOG_FILES
+        .iter()
+        .map(|name| {
+            let contents = std::fs::read_to_string(log_dir.join(name)).ok().map(|text| {
+                let lines: Vec<&str> = text.lines().collect();
+                let start = lines.len().saturating_sub(LOG_TAIL_LINES);
+                lines[start..].join("\n")
+            });
+            (name.to_string(), contents)
+        })
+        .collect()
+}
+
+fn migration_status(conn: &Connection) -> Result<Value, CareerBenchError> {
+    if !table_exists(conn, "migrations") {
+        return Ok(json!({ "applied": [], "count": 0 }));
+    }
+
+    let mut stmt = conn.prepare("SELECT name, applied_at FROM migrations ORDER BY id")?;
+    let applied: Vec<Value> = stmt
+        .query_map([], |row| {
+            Ok(json!({
+                "name": row.get::<_, String>(0)?,
+                "appliedAt": row.get::<_, String>(1)?,
+            }))
+        })?
+        .collect::<Result<_, _>>()?;
+
+    Ok(json!({
+        "count": applied.len(),
+        "latest": applied.last().and_then(|m| m.get("name")).cloned(),
+        "applied": applied,
+    }))
+}
+
+/// Row counts only; no names, descriptions or other content
+fn anonymized_counts(conn: &Connection) -> Result<Value, CareerBenchError> {
+    let mut counts = Map::new();
+    for table in COUNTED_TABLES {
+        if !table_exists(conn, table) {
+            continue;
+        }
+        let count: i64 = conn.query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| row.get(0))?;
+        counts.insert(table.to_string(), json!(count));
+    }
+    Ok(Value::Object(counts))
+}
+
+fn health_report(
+    conn: &Connection,
+    logs: &[(String, Option<String>)],
+    ai_settings: Option<&AiSettings>,
+    generated_at: NaiveDateTime,
+) -> Value {
+    let integrity = conn
+        .query_row("PRAGMA quick_check", [], |row| row.get::<_, String>(0))
+        .unwrap_or_else(|e| format!("failed: {}", e));
+    let page_count: i64 = conn.query_row("PRAGMA page_count", [], |row| row.get(0)).unwrap_or(0);
+    let page_size: i64 = conn.query_row("PRAGMA page_size", [], |row| row.get(0)).unwrap_or(0);
+    let migrations_applied: i64 = if table_exists(conn, "migrations") {
+        conn.query_row("SELECT COUNT(*) FROM migrations", [], |row| row.get(0)).unwrap_or(0)
+    } else {
+        0
+    };
+
+    // Only whether things are configured; never the key or local paths
+    let ai = ai_settings.map(|settings| {
+        json!({
+            "mode": settings.mode,
+            "cloudProvider": settings.cloud_provider,
+            "modelName": settings.model_name,
+            "apiKeyConfigured": matches!(settings.api_key.as_deref(), Some(key) if !key.is_empty()),
+            "localModelConfigured": settings.local_model_path.is_some(),
+        })
+    });
+
+    let (total_errors, errors_by_type) = crate::error_logging::get_error_stats()
+        .map(|(total, by_type, _)| (total, by_type))
+        .unwrap_or_default();
+
+    let log_status: Vec<Value> = logs
+        .iter()
+        .map(|(name, contents)| {
+            json!({
+                "name": name,
+                "present": contents.is_some(),
+                "lines": contents.as_deref().map(|text| text.lines().count()).unwrap_or(0),
+            })
+        })
+        .collect();
+
+    json!({
+        "generatedAt": generated_at.format("%Y-%m-%dT%H:%M:%S").to_string(),
+        "appVersion": env!("CARGO_PKG_VERSION"),
+        "os": std::env::consts::OS,
+        "arch": std::env::consts::ARCH,
+        "database": {
+            "integrityCheck": integrity,
+            "sqliteVersion": rusqlite::version(),
+            "sizeBytes": page_count * page_size,
+            "migrationsApplied": migrations_applied,
+        },
+        "ai": ai,
+        "errors": {
+            "total": total_errors,
+            "byType": errors_by_type,
+        },
+        "logs": log_status,
+    })
+}
+
+/// Assemble the support bundle zip.
+///
+/// `logs` are `(file name, contents)` pairs as returned by `read_log_tails`; missing
+/// logs are listed in the health report but not added to the archive.
+pub fn build_support_bundle(
+    conn: &Connection,
+    logs: &[(String, Option<String>)],
+    ai_settings: Option<&AiSettings>,
+    generated_at: NaiveDateTime,
+) -> Result<Vec<u8>, CareerBenchError> {
+    let identifiers = load_known_identifiers(conn);
+    let to_json = |value: &Value| serde_json::to_string_pretty(value).unwrap_or_default();
+
+    // ZIP timestamps can't go before 1980; fall back to the format's default there
+    let modified = zip::DateTime::from_date_and_time(
+        u16::try_from(generated_at.year()).unwrap_or(0),
+        generated_at.month() as u8,
+        generated_at.day() as u8,
+        generated_at.hour() as u8,
+        generated_at.minute() as u8,
+        generated_at.second() as u8,
+    )
+    .unwrap_or_default();
+    let options = FileOptions::default()
+        .compression_method(CompressionMethod::Deflated)
+        .last_modified_time(modified);
+
+    let mut archive = ZipWriter::new(Cursor::new(Vec::new()));
+    let mut add_text = |name: &str, contents: &str| -> Result<(), CareerBenchError> {
+        archive.start_file(name, options).map_err(zip_error)?;
+        archive.write_all(contents.as_bytes())?;
+        Ok(())
+    };
+
+    add_text(
+        "README.txt",
+        "CareerBench support bundle\n\n\
+         logs/            Recent logs with API keys, emails, phones, addresses and names redacted\n\
+         health.json      App version, database integrity and AI configuration status\n\
+         migrations.json  Applied database migrations\n\
+         counts.json      Number of rows per table (no content)\n\n\
+         The database itself is not included.\n",
+    )?;
+
+    for (name, contents) in logs {
+        if let Some(contents) = contents {
+            add_text(&format!("logs/{}", name), &redact_support_text(contents, &identifiers))?;
+        }
+    }
+
+    add_text("health.json", &to_json(&health_report(conn, logs, ai_settings, generated_at)))?;
+    add_text("migrations.json", &to_json(&migration_status(conn)?))?;
+    add_text("counts.json", &to_json(&anonymized_counts(conn)?))?;
+
+    let bundle = archive.finish().map_err(zip_error)?;
+    Ok(bundle.into_inner())
+}
+
+fn zip_error(e: zip::result::ZipError) -> CareerBenchError {
+    CareerBenchError::FileSystem(FileSystemError::IoError(format!("Failed to write support bundle: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_secrets() {
+        let text = "Using key sk-proj-abcdefghijklmnop1234 with Authorization: Bearer eyJhbGciOi.abc.def";
+        let redacted = redact_secrets(text);
+        assert!(!redacted.contains("sk-proj"));
+        assert!(!redacted.contains("eyJhbGciOi"));
+        assert!(redacted.contains("[REDACTED_KEY]"));
+        assert!(redacted.contains("Bearer [REDACTED]"));
+
+        let assignment = redact_secrets(r#"{"api_key": "hunter2", "model": "gpt-4o-mini"} password=letmein"#);
+        assert!(!assignment.contains("hunter2"));
+        assert!(!assignment.contains("letmein"));
+        assert!(assignment.contains("gpt-4o-mini"));
+    }
+
+    #[test]
+    fn test_redact_support_text_with_identifiers() {
+        let identifiers = vec!["Jordan Rivera".to_string(), "Jordan".to_string(), "Rivera".to_string()];
+        let text = "Profile saved for Jordan Rivera (jordan@example.com); rivera retried";
+        let redacted = redact_support_text(text, &identifiers);
+        assert!(!redacted.contains("Jordan"));
+        assert!(!redacted.to_lowercase().contains("rivera"));
+        assert!(!redacted.contains("jordan@example.com"));
+        assert!(redacted.contains("Profile saved for"));
+    }
+}
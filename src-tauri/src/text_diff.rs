@@ -0,0 +1,132 @@
+//! Line-based unified diff for comparing artifact versions
+
+/// Lines of context shown around each change
+const CONTEXT_LINES: usize = 3;
+
+#[derive(Debug, PartialEq)]
+enum DiffOp<'a> {
+    Equal(&'a str),
+    Delete(&'a str),
+    Insert(&'a str),
+}
+
+/// Compute a line diff using the longest common subsequence
+fn diff_lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<DiffOp<'a>> {
+    let (n, m) = (old.len(), new.len());
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if old[i] == new[j] {
+            ops.push(DiffOp::Equal(old[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            ops.push(DiffOp::Delete(old[i]));
+            i += 1;
+        } else {
+            ops.push(DiffOp::Insert(new[j]));
+            j += 1;
+        }
+    }
+    ops.extend(old[i..].iter().map(|line| DiffOp::Delete(*line)));
+    ops.extend(new[j..].iter().map(|line| DiffOp::Insert(*line)));
+    ops
+}
+
+/// Produce a unified diff between two texts. Returns an empty string when they are equal.
+pub fn unified_diff(old: &str, new: &str, old_label: &str, new_label: &str) -> String {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let ops = diff_lines(&old_lines, &new_lines);
+
+    // Group changes (with surrounding context) into hunks of op indices
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for (idx, op) in ops.iter().enumerate() {
+        if matches!(op, DiffOp::Equal(_)) {
+            continue;
+        }
+        let start = idx.saturating_sub(CONTEXT_LINES);
+        let end = (idx + 1 + CONTEXT_LINES).min(ops.len());
+        match hunks.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    if hunks.is_empty() {
+        return String::new();
+    }
+
+    // Line positions in each text before each op
+    let mut old_pos = vec![0usize; ops.len() + 1];
+    let mut new_pos = vec![0usize; ops.len() + 1];
+    for (idx, op) in ops.iter().enumerate() {
+        let (old_step, new_step) = match op {
+            DiffOp::Equal(_) => (1, 1),
+            DiffOp::Delete(_) => (1, 0),
+            DiffOp::Insert(_) => (0, 1),
+        };
+        old_pos[idx + 1] = old_pos[idx] + old_step;
+        new_pos[idx + 1] = new_pos[idx] + new_step;
+    }
+
+    let mut output = format!("--- {}\n+++ {}\n", old_label, new_label);
+    for (start, end) in hunks {
+        let old_len = old_pos[end] - old_pos[start];
+        let new_len = new_pos[end] - new_pos[start];
+        let old_start = if old_len == 0 { old_pos[start] } else { old_pos[start] + 1 };
+        let new_start = if new_len == 0 { new_pos[start] } else { new_pos[start] + 1 };
+        output.push_str(&format!("@@ -{},{} +{},{} @@\n", old_start, old_len, new_start, new_len));
+
+        for op in &ops[start..end] {
+            match op {
+                DiffOp::Equal(line) => output.push_str(&format!(" {}\n", line)),
+                DiffOp::Delete(line) => output.push_str(&format!("-{}\n", line)),
+                DiffOp::Insert(line) => output.push_str(&format!("+{}\n", line)),
+            }
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_equal_texts_produce_empty_diff() {
+        assert_eq!(unified_diff("a\nb\n", "a\nb\n", "v1", "v2"), "");
+    }
+
+    #[test]
+    fn test_single_line_change() {
+        let diff = unified_diff("one\ntwo\nthree", "one\n2\nthree", "v1", "v2");
+        assert_eq!(
+            diff,
+            "--- v1\n+++ v2\n@@ -1,3 +1,3 @@\n one\n-two\n+2\n three\n"
+        );
+    }
+
+    #[test]
+    fn test_distant_changes_split_into_hunks() {
+        let old: Vec<String> = (1..=20).map(|i| format!("line {}", i)).collect();
+        let mut new = old.clone();
+        new[1] = "changed 2".to_string();
+        new[18] = "changed 19".to_string();
+
+        let diff = unified_diff(&old.join("\n"), &new.join("\n"), "v1", "v2");
+        assert_eq!(diff.matches("@@ -").count(), 2);
+    }
+}
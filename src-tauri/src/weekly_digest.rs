@@ -0,0 +1,268 @@
+//! Weekly digest combining upcoming actions, stale applications, follow-ups, goals and
+//! interview self-ratings
+//!
+//! The provider composes a short prioritized summary from the gathered data and the
+//! result is cached for the day. When no provider is available (or it fails), a plain
+//! digest is assembled in code so the user always gets something.
+
+use crate::ai::provider::AiProvider;
+use crate::calendar::{get_upcoming_actions_with_conn, UpcomingAction};
+use crate::errors::CareerBenchError;
+use crate::interview_debriefs::{average_rating, get_debriefs_since, InterviewDebrief};
+use crate::reminders::{get_due_reminders_with_conn, Reminder};
+use chrono::{NaiveDate, NaiveDateTime};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+/// Days ahead included in "upcoming actions"
+const DIGEST_DAYS_AHEAD: i64 = 7;
+/// Active applications without activity for this many days are stale
+pub const STALE_AFTER_DAYS: i64 = 14;
+
+const WEEKLY_DIGEST_PURPOSE: &str = "weekly_digest";
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StaleApplication {
+    pub application_id: i64,
+    pub job_title: Option<String>,
+    pub company: Option<String>,
+    pub status: String,
+    pub days_since_activity: i64,
+}
+
+/// Task completion for an active learning plan
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GoalProgress {
+    pub plan_id: i64,
+    pub title: String,
+    pub completed_tasks: i64,
+    pub total_tasks: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WeeklyDigestData {
+    pub upcoming_actions: Vec<UpcomingAction>,
+    pub stale_applications: Vec<StaleApplication>,
+    pub due_follow_ups: Vec<Reminder>,
+    pub goals: Vec<GoalProgress>,
+    /// Interview debriefs saved in the past week
+    pub recent_debriefs: Vec<InterviewDebrief>,
+}
+
+/// Active, non-terminal applications with no activity for `STALE_AFTER_DAYS`, oldest first
+pub fn find_stale_applications(conn: &Connection, today: NaiveDate) -> Result<Vec<StaleApplication>, CareerBenchError> {
+    let mut stmt = conn.prepare(
+        "SELECT a.id, j.title, j.company, a.status, COALESCE(a.last_activity_date, a.date_applied, a.date_saved)
+         FROM applications a
+         LEFT JOIN jobs j ON a.job_id = j.id
+         WHERE a.archived = 0 AND a.status NOT IN ('Offer', 'Rejected', 'Ghosted', 'Withdrawn')",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, i64>(0)?,
+            row.get::<_, Option<String>>(1)?,
+            row.get::<_, Option<String>>(2)?,
+            row.get::<_, String>(3)?,
+            row.get::<_, Option<String>>(4)?,
+        ))
+    })?;
+
+    let mut stale = Vec::new();
+    for row_result in rows {
+        let (application_id, job_title, company, status, last_activity) = row_result?;
+        let last_activity = match last_activity
+            .as_deref()
+            .and_then(|date| date.get(..10))
+            .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+        {
+            Some(date) => date,
+            None => continue,
+        };
+        let days_since_activity = (today - last_activity).num_days();
+        if days_since_activity >= STALE_AFTER_DAYS {
+            stale.push(StaleApplication {
+                application_id,
+                job_title,
+                company,
+                status,
+                days_since_activity,
+            });
+        }
+    }
+
+    stale.sort_by(|a, b| b.days_since_activity.cmp(&a.days_since_activity));
+    Ok(stale)
+}
+
+/// Task completion for each active learning plan
+pub fn get_goal_progress(conn: &Connection) -> Result<Vec<GoalProgress>, CareerBenchError> {
+    let mut stmt = conn.prepare(
+        "SELECT p.id, p.title,
+                COALESCE(SUM(CASE WHEN t.completed = 1 THEN 1 ELSE 0 END), 0),
+                COUNT(t.id)
+         FROM learning_plans p
+         LEFT JOIN learning_tracks tr ON tr.learning_plan_id = p.id
+         LEFT JOIN learning_tasks t ON t.learning_track_id = tr.id
+         WHERE p.status = 'active'
+         GROUP BY p.id
+         ORDER BY p.created_at ASC",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok(GoalProgress {
+            plan_id: row.get(0)?,
+            title: row.get(1)?,
+            completed_tasks: row.get(2)?,
+            total_tasks: row.get(3)?,
+        })
+    })?;
+
+    let mut goals = Vec::new();
+    for row_result in rows {
+        goals.push(row_result?);
+    }
+    Ok(goals)
+}
+
+pub fn gather_weekly_digest_data(conn: &Connection, now: NaiveDateTime, today: NaiveDate) -> Result<WeeklyDigestData, CareerBenchError> {
+    let debriefs_since = (now - chrono::Duration::days(DIGEST_DAYS_AHEAD)).and_utc().to_rfc3339();
+    Ok(WeeklyDigestData {
+        upcoming_actions: get_upcoming_actions_with_conn(conn, today, DIGEST_DAYS_AHEAD)?,
+        stale_applications: find_stale_applications(conn, today)?,
+        due_follow_ups: get_due_reminders_with_conn(conn, now)?,
+        goals: get_goal_progress(conn)?,
+        recent_debriefs: get_debriefs_since(conn, &debriefs_since)?,
+    })
+}
+
+fn describe_job(title: Option<&str>, company: Option<&str>) -> String {
+    match (title, company) {
+        (Some(title), Some(company)) => format!("{} at {}", title, company),
+        (Some(title), None) => title.to_string(),
+        (None, Some(company)) => company.to_string(),
+        (None, None) => "Untitled job".to_string(),
+    }
+}
+
+/// Digest assembled without AI: actions first, then follow-ups, stale applications and goals
+pub fn build_plain_digest(data: &WeeklyDigestData, today: NaiveDate) -> String {
+    let mut digest = format!("Weekly digest for the week of {}\n", today.format("%B %-d, %Y"));
+
+    digest.push_str("\nThis week's actions\n");
+    if data.upcoming_actions.is_empty() {
+        digest.push_str("- Nothing scheduled. A good week to line up new applications.\n");
+    }
+    for action in &data.upcoming_actions {
+        let what = describe_job(action.job_title.as_deref(), action.company.as_deref());
+        let note = action.note.as_deref().unwrap_or("Next action");
+        digest.push_str(&format!("- {}: {} ({})\n", action.date.get(..10).unwrap_or(&action.date), note, what));
+    }
+
+    if !data.due_follow_ups.is_empty() {
+        digest.push_str("\nFollow-ups due\n");
+        for reminder in &data.due_follow_ups {
+            let message = reminder.message.as_deref().unwrap_or(&reminder.reminder_type);
+            digest.push_str(&format!("- {}\n", message));
+        }
+    }
+
+    if !data.stale_applications.is_empty() {
+        digest.push_str("\nApplications that have gone quiet\n");
+        for application in &data.stale_applications {
+            digest.push_str(&format!(
+                "- {} ({}), no activity for {} days\n",
+                describe_job(application.job_title.as_deref(), application.company.as_deref()),
+                application.status,
+                application.days_since_activity
+            ));
+        }
+    }
+
+    if !data.goals.is_empty() {
+        digest.push_str("\nGoal progress\n");
+        for goal in &data.goals {
+            digest.push_str(&format!(
+                "- {}: {}/{} tasks done\n",
+                goal.title, goal.completed_tasks, goal.total_tasks
+            ));
+        }
+    }
+
+    if let Some(average) = average_rating(&data.recent_debriefs) {
+        digest.push_str(&format!("\nInterviews this week (average self-rating {:.1}/5)\n", average));
+        for debrief in &data.recent_debriefs {
+            digest.push_str(&format!("- {}: {}/5", debrief.round, debrief.rating));
+            if !debrief.to_improve.trim().is_empty() {
+                digest.push_str(&format!(", work on: {}", debrief.to_improve.trim()));
+            }
+            digest.push('\n');
+        }
+    }
+
+    digest
+}
+
+fn weekly_digest_prompt(data: &WeeklyDigestData, today: NaiveDate) -> String {
+    format!(
+        "Today is {}. Write a short, encouraging Monday-morning digest for a job seeker. \
+         Prioritize what to do this week: upcoming actions and due follow-ups first, then \
+         applications that have gone quiet, then goal progress and what to practice based \
+         on this week's interview self-ratings. Use plain text with short \
+         bullet points, under 200 words.\n\nData (JSON):\n{}",
+        today.format("%Y-%m-%d"),
+        serde_json::to_string_pretty(data).unwrap_or_default()
+    )
+}
+
+fn digest_cache_hash(today: NaiveDate) -> Result<String, String> {
+    crate::ai_cache::compute_input_hash(&json!({ "date": today.format("%Y-%m-%d").to_string() }))
+}
+
+/// Today's cached AI digest, if one was generated
+pub fn get_cached_weekly_digest(conn: &Connection, today: NaiveDate, now: &str) -> Result<Option<String>, String> {
+    let input_hash = digest_cache_hash(today)?;
+    match crate::ai_cache::ai_cache_get(conn, WEEKLY_DIGEST_PURPOSE, &input_hash, now)? {
+        Some(entry) => Ok(entry.response_payload.as_str().map(|digest| digest.to_string())),
+        None => Ok(None),
+    }
+}
+
+/// Cache an AI digest for the rest of the day
+pub fn cache_weekly_digest(conn: &Connection, today: NaiveDate, digest: &str, now: &str) -> Result<(), String> {
+    let input_hash = digest_cache_hash(today)?;
+    let model_name = crate::ai::settings::load_ai_settings()
+        .ok()
+        .and_then(|s| s.model_for(WEEKLY_DIGEST_PURPOSE))
+        .unwrap_or_else(|| "unknown-model".to_string());
+    crate::ai_cache::ai_cache_put(
+        conn,
+        WEEKLY_DIGEST_PURPOSE,
+        &input_hash,
+        &model_name,
+        &json!({ "date": today.format("%Y-%m-%d").to_string() }),
+        &json!(digest),
+        Some(crate::ai_cache::CACHE_TTL_WEEKLY_DIGEST_DAYS),
+        now,
+    )
+}
+
+/// Compose the digest with the provider, falling back to the plain digest when there is
+/// no provider or the call fails. Returns the digest and whether the AI wrote it.
+pub async fn compose_weekly_digest(
+    provider: Option<&dyn AiProvider>,
+    data: &WeeklyDigestData,
+    today: NaiveDate,
+) -> (String, bool) {
+    if let Some(provider) = provider {
+        let prompt = weekly_digest_prompt(data, today);
+        match provider.call_llm(None, &prompt).await {
+            Ok(digest) if !digest.trim().is_empty() => return (digest.trim().to_string(), true),
+            Ok(_) => log::warn!("[WeeklyDigest] Provider returned an empty digest, using fallback"),
+            Err(e) => log::warn!("[WeeklyDigest] Provider failed, using fallback: {}", e),
+        }
+    }
+    (build_plain_digest(data, today), false)
+}
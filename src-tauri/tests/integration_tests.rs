@@ -3,43 +3,16 @@
 
 use careerbench::commands::*;
 use careerbench::db;
+use careerbench::language::Lang;
 use rusqlite::Connection;
 use serde_json::json;
 use std::fs;
 use std::path::PathBuf;
 
-// Helper to create a test database
+// Helper to create a test database with every migration applied
 fn setup_test_db() -> Connection {
     let conn = Connection::open_in_memory().unwrap();
-    
-    // Create migrations table
-    conn.execute(
-        "CREATE TABLE IF NOT EXISTS migrations (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            name TEXT NOT NULL UNIQUE,
-            applied_at TEXT NOT NULL
-        )",
-        [],
-    ).unwrap();
-    
-    // Run migrations
-    db::migration_001_initial_schema(&conn).unwrap();
-    
-    // Create ai_cache table for tests
-    conn.execute(
-        "CREATE TABLE IF NOT EXISTS ai_cache (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            purpose TEXT NOT NULL,
-            input_hash TEXT NOT NULL,
-            model_name TEXT NOT NULL,
-            request_payload TEXT NOT NULL,
-            response_payload TEXT NOT NULL,
-            created_at TEXT NOT NULL,
-            expires_at TEXT
-        )",
-        [],
-    ).unwrap();
-    
+    db::init_database_with_conn(&conn).unwrap();
     conn
 }
 
@@ -55,6 +28,56 @@ fn load_fixture(path: &str) -> serde_json::Value {
     serde_json::from_str(&content).unwrap()
 }
 
+// Helper to insert an active job at Acme, added on 2025-01-01
+fn seed_job_at(conn: &Connection, title: &str, location: Option<&str>, parsed_json: Option<&str>) -> i64 {
+    conn.execute(
+        "INSERT INTO jobs (title, company, location, parsed_json, is_active, date_added, last_updated) VALUES (?, 'Acme', ?, ?, 1, '2025-01-01', '2025-01-01')",
+        rusqlite::params![title, location, parsed_json],
+    ).unwrap();
+    conn.last_insert_rowid()
+}
+
+fn seed_job_with_parsed(conn: &Connection, title: &str, parsed: Option<serde_json::Value>) -> i64 {
+    seed_job_at(conn, title, None, parsed.map(|p| p.to_string()).as_deref())
+}
+
+/// Insert an application for `job_id` in `status`, saved (and last touched) on `date_saved`
+fn seed_application_at(conn: &Connection, job_id: i64, status: &str, date_saved: &str) -> i64 {
+    conn.execute(
+        "INSERT INTO applications (job_id, status, date_saved, created_at, updated_at) VALUES (?, ?, ?, ?, ?)",
+        rusqlite::params![job_id, status, date_saved, date_saved, date_saved],
+    ).unwrap();
+    conn.last_insert_rowid()
+}
+
+// Helper to create a job + application for event tests
+fn setup_application_for_events(conn: &Connection) -> i64 {
+    let job_id = seed_job_at(conn, "Software Engineer", None, None);
+    seed_application_at(conn, job_id, "Applied", &chrono::Utc::now().to_rfc3339())
+}
+
+// Helper to seed an application that applied on `applied` and received an offer on `offer`
+fn seed_offer_pipeline(conn: &Connection, seniority: Option<&str>, applied: Option<&str>, offer: &str) {
+    let job_id = seed_job_at(conn, "Engineer", None, None);
+    conn.execute("UPDATE jobs SET seniority = ? WHERE id = ?", rusqlite::params![seniority, job_id]).unwrap();
+    let application_id = seed_application_at(conn, job_id, "Offer", offer);
+    conn.execute("UPDATE applications SET date_applied = ? WHERE id = ?", rusqlite::params![applied, application_id]).unwrap();
+    conn.execute(
+        "INSERT INTO application_events (application_id, event_type, event_date, created_at) VALUES (?, 'OfferReceived', ?, ?)",
+        rusqlite::params![application_id, offer, offer],
+    ).unwrap();
+}
+
+fn seed_application_for_priority(conn: &Connection, required_skills: &[&str], deadline: &str, salary: Option<&str>) -> i64 {
+    let parsed_json = json!({ "requiredSkills": required_skills }).to_string();
+    let job_id = seed_job_at(conn, "Engineer", None, Some(&parsed_json));
+    conn.execute(
+        "UPDATE jobs SET application_deadline = ?, salary_text = ? WHERE id = ?",
+        rusqlite::params![deadline, salary, job_id],
+    ).unwrap();
+    seed_application_at(conn, job_id, "Saved", "2025-03-01")
+}
+
 mod tests {
     use super::*;
 
@@ -604,6 +627,9 @@ mod tests {
             status_breakdown,
             activity_last_30_days,
             funnel,
+            date_range: None,
+            comparison: None,
+            deltas: Vec::new(),
         })
     }
 
@@ -891,183 +917,6 @@ mod tests {
         assert!(result.unwrap_err().contains("Job not found"));
     }
 
-    // Helper function to test update_application logic with a test database
-    fn test_update_application_with_db(
-        conn: &Connection,
-        id: i64,
-        input: UpdateApplicationInput,
-    ) -> Result<Application, String> {
-        use chrono::Utc;
-        let now = Utc::now().to_rfc3339();
-
-        // Get current application
-        let mut stmt = conn
-            .prepare(
-                "SELECT id, job_id, status, channel, priority, date_saved, date_applied, last_activity_date, next_action_date, next_action_note, notes_summary, contact_name, contact_email, contact_linkedin, location_override, offer_compensation, archived, created_at, updated_at FROM applications WHERE id = ?"
-            )
-            .map_err(|e| format!("Failed to prepare query: {}", e))?;
-
-        let current_app = stmt
-            .query_row([id], |row| {
-                Ok(Application {
-                    id: Some(row.get(0)?),
-                    job_id: row.get(1)?,
-                    status: row.get(2)?,
-                    channel: row.get(3)?,
-                    priority: row.get(4)?,
-                    date_saved: row.get(5)?,
-                    date_applied: row.get(6)?,
-                    last_activity_date: row.get(7)?,
-                    next_action_date: row.get(8)?,
-                    next_action_note: row.get(9)?,
-                    notes_summary: row.get(10)?,
-                    contact_name: row.get(11)?,
-                    contact_email: row.get(12)?,
-                    contact_linkedin: row.get(13)?,
-                    location_override: row.get(14)?,
-                    offer_compensation: row.get(15)?,
-                    archived: row.get::<_, i32>(16)? != 0,
-                    created_at: row.get(17)?,
-                    updated_at: row.get(18)?,
-                })
-            })
-            .map_err(|_| "Application not found".to_string())?;
-
-        let old_status = current_app.status.clone();
-
-        // Build update query
-        let mut updates = Vec::new();
-        let mut params: Vec<String> = Vec::new();
-
-        if let Some(status) = &input.status {
-            updates.push("status = ?");
-            params.push(status.clone());
-        }
-        if let Some(channel) = &input.channel {
-            updates.push("channel = ?");
-            params.push(channel.clone());
-        }
-        if let Some(priority) = &input.priority {
-            updates.push("priority = ?");
-            params.push(priority.clone());
-        }
-        if let Some(date_applied) = &input.date_applied {
-            updates.push("date_applied = ?");
-            params.push(date_applied.clone());
-        }
-        if let Some(next_action_date) = &input.next_action_date {
-            updates.push("next_action_date = ?");
-            params.push(next_action_date.clone());
-        }
-        if let Some(next_action_note) = &input.next_action_note {
-            updates.push("next_action_note = ?");
-            params.push(next_action_note.clone());
-        }
-        if let Some(notes_summary) = &input.notes_summary {
-            updates.push("notes_summary = ?");
-            params.push(notes_summary.clone());
-        }
-        if let Some(contact_name) = &input.contact_name {
-            updates.push("contact_name = ?");
-            params.push(contact_name.clone());
-        }
-        if let Some(contact_email) = &input.contact_email {
-            updates.push("contact_email = ?");
-            params.push(contact_email.clone());
-        }
-        if let Some(contact_linkedin) = &input.contact_linkedin {
-            updates.push("contact_linkedin = ?");
-            params.push(contact_linkedin.clone());
-        }
-        if let Some(location_override) = &input.location_override {
-            updates.push("location_override = ?");
-            params.push(location_override.clone());
-        }
-        if let Some(offer_compensation) = &input.offer_compensation {
-            updates.push("offer_compensation = ?");
-            params.push(offer_compensation.clone());
-        }
-
-        if updates.is_empty() {
-            return Ok(current_app);
-        }
-
-        // Handle status change
-        let new_status = input.status.as_ref().unwrap_or(&old_status);
-        if new_status != &old_status {
-            updates.push("last_activity_date = ?");
-            params.push(now.clone());
-
-            // If status becomes Applied and date_applied is empty, set it
-            if new_status == "Applied" && input.date_applied.is_none() && current_app.date_applied.is_none() {
-                updates.push("date_applied = ?");
-                params.push(now.clone());
-            }
-
-            // Create StatusChanged event
-            conn.execute(
-                "INSERT INTO application_events (application_id, event_type, event_date, from_status, to_status, created_at) VALUES (?, ?, ?, ?, ?, ?)",
-                rusqlite::params![
-                    id,
-                    "StatusChanged",
-                    now,
-                    old_status,
-                    new_status,
-                    now
-                ],
-            )
-            .map_err(|e| format!("Failed to create status change event: {}", e))?;
-        }
-
-        updates.push("updated_at = ?");
-        params.push(now.clone());
-
-        // Build query and execute
-        let query = format!("UPDATE applications SET {} WHERE id = ?", updates.join(", "));
-        
-        // Simple execution for test - in real code this uses match for many params
-        let mut all_params: Vec<&dyn rusqlite::ToSql> = params.iter().map(|p| p as &dyn rusqlite::ToSql).collect();
-        all_params.push(&id);
-        
-        conn.execute(&query, rusqlite::params_from_iter(all_params.iter().cloned()))
-            .map_err(|e| format!("Failed to update application: {}", e))?;
-
-        // Get updated application
-        let mut stmt = conn
-            .prepare(
-                "SELECT id, job_id, status, channel, priority, date_saved, date_applied, last_activity_date, next_action_date, next_action_note, notes_summary, contact_name, contact_email, contact_linkedin, location_override, offer_compensation, archived, created_at, updated_at FROM applications WHERE id = ?"
-            )
-            .map_err(|e| format!("Failed to prepare query: {}", e))?;
-
-        let application = stmt
-            .query_row([id], |row| {
-                Ok(Application {
-                    id: Some(row.get(0)?),
-                    job_id: row.get(1)?,
-                    status: row.get(2)?,
-                    channel: row.get(3)?,
-                    priority: row.get(4)?,
-                    date_saved: row.get(5)?,
-                    date_applied: row.get(6)?,
-                    last_activity_date: row.get(7)?,
-                    next_action_date: row.get(8)?,
-                    next_action_note: row.get(9)?,
-                    notes_summary: row.get(10)?,
-                    contact_name: row.get(11)?,
-                    contact_email: row.get(12)?,
-                    contact_linkedin: row.get(13)?,
-                    location_override: row.get(14)?,
-                    offer_compensation: row.get(15)?,
-                    archived: row.get::<_, i32>(16)? != 0,
-                    created_at: row.get(17)?,
-                    updated_at: row.get(18)?,
-                })
-            })
-            .map_err(|e| format!("Failed to get application: {}", e))?;
-
-        Ok(application)
-    }
-
     #[test]
     fn test_update_application_status() {
         let conn = setup_test_db();
@@ -1090,15 +939,11 @@ mod tests {
         ).unwrap();
         let job_id = conn.last_insert_rowid();
 
-        conn.execute(
-            "INSERT INTO applications (job_id, status, date_saved, created_at, updated_at) VALUES (?, ?, ?, ?, ?)",
-            rusqlite::params![job_id, "Saved", now, now, now],
-        ).unwrap();
-        let app_id = conn.last_insert_rowid();
+        let app_id = seed_application_at(&conn, job_id, "Saved", &now);
 
         // Update status
         let input = UpdateApplicationInput {
-            status: Some("Interviewing".to_string()),
+            status: Some("Applied".to_string()),
             channel: None,
             priority: None,
             date_applied: None,
@@ -1110,14 +955,17 @@ mod tests {
             contact_linkedin: None,
             location_override: None,
             offer_compensation: None,
+            expected_updated_at: None,
+            allow_override: false,
         };
 
-        let result = test_update_application_with_db(&conn, app_id, input);
+        let result = update_application_with_conn(&conn, app_id, input, chrono::Utc::now());
         assert!(result.is_ok());
         
         let app = result.unwrap();
-        assert_eq!(app.status, "Interviewing");
+        assert_eq!(app.status, "Applied");
         assert!(app.last_activity_date.is_some()); // Should be set on status change
+        assert!(app.date_applied.is_some()); // Set when moving to Applied
 
         // Verify StatusChanged event was created
         let event_count: i64 = conn
@@ -1152,11 +1000,7 @@ mod tests {
         ).unwrap();
         let job_id = conn.last_insert_rowid();
 
-        conn.execute(
-            "INSERT INTO applications (job_id, status, date_saved, created_at, updated_at) VALUES (?, ?, ?, ?, ?)",
-            rusqlite::params![job_id, "Saved", now, now, now],
-        ).unwrap();
-        let app_id = conn.last_insert_rowid();
+        let app_id = seed_application_at(&conn, job_id, "Saved", &now);
 
         // Update multiple fields
         let input = UpdateApplicationInput {
@@ -1172,9 +1016,11 @@ mod tests {
             contact_linkedin: Some("linkedin.com/in/johndoe".to_string()),
             location_override: None,
             offer_compensation: None,
+            expected_updated_at: None,
+            allow_override: false,
         };
 
-        let result = test_update_application_with_db(&conn, app_id, input);
+        let result = update_application_with_conn(&conn, app_id, input, chrono::Utc::now());
         assert!(result.is_ok());
         
         let app = result.unwrap();
@@ -1207,9 +1053,11 @@ mod tests {
             contact_linkedin: None,
             location_override: None,
             offer_compensation: None,
+            expected_updated_at: None,
+            allow_override: false,
         };
 
-        let result = test_update_application_with_db(&conn, 999, input);
+        let result = update_application_with_conn(&conn, 999, input, chrono::Utc::now());
         assert!(result.is_err());
         assert!(result.unwrap_err().contains("Application not found"));
     }
@@ -1258,6 +1106,9 @@ mod tests {
             remote_friendly: Some(true),
             summary: Some("Great opportunity".to_string()),
             seniority_score: Some(0.5),
+            salary_min: None,
+            salary_max: None,
+            salary_currency: None,
         };
         
         let key = MockProvider::job_key(job_description);
@@ -1302,6 +1153,12 @@ mod tests {
             domain_tags: parsed_output_result.domain_tags,
             seniority_score: parsed_output_result.seniority_score,
             remote_friendly: parsed_output_result.remote_friendly,
+            application_deadline: None,
+            benefits: Vec::new(),
+            visa_sponsorship: None,
+            salary_min: None,
+            salary_max: None,
+            salary_currency: None,
         };
 
         // Verify parsed data
@@ -1401,6 +1258,12 @@ mod tests {
             domain_tags: vec![],
             seniority_score: None,
             remote_friendly: None,
+            application_deadline: None,
+            benefits: Vec::new(),
+            visa_sponsorship: None,
+            salary_min: None,
+            salary_max: None,
+            salary_currency: None,
         };
         
         let response_payload = serde_json::to_value(&cached_parsed).unwrap();
@@ -1718,6 +1581,7 @@ mod tests {
                 },
             ],
             highlights: vec!["Tailored for Senior Software Engineer role".to_string()],
+            contact: None,
         };
         
         let resume_response = serde_json::to_value(&cached_resume).unwrap();
@@ -1881,5 +1745,3290 @@ mod tests {
         let cached_entry = ai_cache_get(&conn, "cover_letter_generation", &letter_hash, &now).unwrap();
         assert!(cached_entry.is_none()); // Should be cache miss
     }
-}
 
+    fn event_input(application_id: i64, client_event_id: Option<&str>) -> AddEventInput {
+        AddEventInput {
+            application_id,
+            event_type: "FollowUpSent".to_string(),
+            event_date: None,
+            title: Some("Follow up".to_string()),
+            details: None,
+            client_event_id: client_event_id.map(|s| s.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_add_event_same_client_id_is_idempotent() {
+        let conn = setup_test_db();
+        let application_id = setup_application_for_events(&conn);
+
+        let first = insert_application_event(&conn, &event_input(application_id, Some("click-1"))).unwrap();
+        let second = insert_application_event(&conn, &event_input(application_id, Some("click-1"))).unwrap();
+        assert_eq!(first.id, second.id);
+
+        let count: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM application_events WHERE application_id = ?",
+                [application_id],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_add_event_distinct_client_ids_insert_twice() {
+        let conn = setup_test_db();
+        let application_id = setup_application_for_events(&conn);
+
+        let first = insert_application_event(&conn, &event_input(application_id, Some("click-1"))).unwrap();
+        let second = insert_application_event(&conn, &event_input(application_id, Some("click-2"))).unwrap();
+        assert_ne!(first.id, second.id);
+
+        let count: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM application_events WHERE application_id = ?",
+                [application_id],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(count, 2);
+    }
+
+    // A generation context over a test database with a profile and one job, backed by the mock provider
+    fn application_documents_context() -> (careerbench::generation_context::GenerationContext, std::sync::Arc<careerbench::ai::mock_provider::MockProvider>, i64) {
+        use careerbench::ai::mock_provider::MockProvider;
+        use careerbench::generation_context::GenerationContext;
+
+        let conn = setup_test_db();
+        setup_user_profile_for_resume(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO jobs (title, company, raw_description, is_active, date_added, last_updated)
+             VALUES ('Senior Rust Engineer', 'Tech Corp', 'Senior Rust engineer building distributed systems', 1, '2025-01-01', '2025-01-01')",
+            [],
+        ).unwrap();
+        let job_id = conn.last_insert_rowid();
+        let provider = std::sync::Arc::new(MockProvider::new());
+        (GenerationContext::with_provider(conn, provider.clone(), "mock-model"), provider, job_id)
+    }
+
+    fn enthusiastic_options() -> GenerationOptions {
+        GenerationOptions {
+            tone: Some("Enthusiastic".to_string()),
+            length: None,
+            focus: None,
+            audience: None,
+            date_format: None,
+            include_contact_header: None,
+            template: None,
+            include_certifications: None,
+            include_portfolio: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_generate_application_documents_both_produced() {
+        use careerbench::ai::errors::AiProviderError;
+
+        let (ctx, provider, job_id) = application_documents_context();
+        let documents = generate_application_documents_with_context(&ctx, job_id, Some(enthusiastic_options())).await.unwrap();
+        let resume = documents.resume.unwrap();
+        let letter = documents.cover_letter.unwrap();
+        assert!(documents.warnings.is_empty());
+        assert!(resume.content.contains("John Doe"));
+        assert!(letter.content.contains("Dear Hiring Manager"));
+
+        // Both documents got the same options
+        let letter_requests = provider.received_requests();
+        assert_eq!(letter_requests.len(), 1);
+        assert!(letter_requests[0].contains("Enthusiastic"));
+        let resume_request: String = ctx
+            .conn()
+            .query_row("SELECT request_payload FROM ai_cache WHERE purpose = 'resume_generation'", [], |row| row.get(0))
+            .unwrap();
+        assert!(resume_request.contains("Enthusiastic"));
+
+        // A second run is served from the caches without calling the provider
+        provider.fail_with(AiProviderError::NetworkError("offline".to_string()));
+        let again = generate_application_documents_with_context(&ctx, job_id, Some(enthusiastic_options())).await.unwrap();
+        assert_eq!(again.resume.unwrap().content, resume.content);
+        assert_eq!(again.cover_letter.unwrap().content, letter.content);
+    }
+
+    #[tokio::test]
+    async fn test_generate_application_documents_partial_failure() {
+        use careerbench::ai::errors::AiProviderError;
+
+        let (ctx, provider, job_id) = application_documents_context();
+        // The resume's job summary call fails; the cover letter call after it succeeds
+        provider.fail_next(AiProviderError::InvalidApiKey, 1);
+        let documents = generate_application_documents_with_context(&ctx, job_id, None).await.unwrap();
+        assert!(documents.resume.is_none());
+        assert!(documents.cover_letter.is_some());
+        assert_eq!(documents.warnings.len(), 1);
+        assert!(documents.warnings[0].starts_with("Resume generation failed"));
+
+        // Nothing cached for these options, so with the provider down both fail
+        provider.fail_with(AiProviderError::InvalidApiKey);
+        let both_failed = generate_application_documents_with_context(&ctx, job_id, Some(enthusiastic_options())).await;
+        let error = both_failed.unwrap_err();
+        assert!(error.contains("Resume generation failed") && error.contains("Cover letter generation failed"));
+    }
+
+    #[test]
+    fn test_diff_artifact_versions_marks_changed_line() {
+        let conn = setup_test_db();
+        let now = chrono::Utc::now().to_rfc3339();
+
+        conn.execute(
+            "INSERT INTO artifacts (type, title, content, created_at, updated_at) VALUES (?, ?, ?, ?, ?)",
+            rusqlite::params!["Resume", "Resume v1", "Summary\nBuilt APIs in Go\nSkills", now, now],
+        ).unwrap();
+        let artifact_id = conn.last_insert_rowid();
+
+        let v1 = record_artifact_version(&conn, artifact_id, Some("Summary\nBuilt APIs in Go\nSkills"), &now).unwrap();
+        let v2 = record_artifact_version(&conn, artifact_id, Some("Summary\nBuilt APIs in Rust\nSkills"), &now).unwrap();
+        assert_eq!((v1, v2), (1, 2));
+
+        let diff = artifact_version_diff(&conn, artifact_id, v1, v2).unwrap();
+        let removed: Vec<&str> = diff.lines().filter(|l| l.starts_with('-') && !l.starts_with("---")).collect();
+        let added: Vec<&str> = diff.lines().filter(|l| l.starts_with('+') && !l.starts_with("+++")).collect();
+        assert_eq!(removed, vec!["-Built APIs in Go"]);
+        assert_eq!(added, vec!["+Built APIs in Rust"]);
+
+        // Same version diffs to nothing; unknown versions are reported
+        assert_eq!(artifact_version_diff(&conn, artifact_id, v2, v2).unwrap(), "");
+        let missing = artifact_version_diff(&conn, artifact_id, v1, 7).unwrap_err();
+        assert!(missing.contains("Version 7 not found"));
+    }
+
+    #[test]
+    fn test_remove_unlisted_skills_drops_injected_skill() {
+        let skill = |name: &str| Skill {
+            id: None,
+            name: name.to_string(),
+            category: None,
+            self_rating: None,
+            priority: None,
+            years_experience: None,
+            notes: None,
+        };
+        let profile_skills = vec![skill("Rust"), skill("TypeScript")];
+
+        let mut resume: GeneratedResume = serde_json::from_value(json!({
+            "sections": [
+                {
+                    "title": "Skills",
+                    "items": [
+                        { "heading": "Key Skills", "subheading": null, "bullets": ["rust, Kubernetes, TypeScript"] }
+                    ]
+                }
+            ],
+            "highlights": []
+        })).unwrap();
+
+        let warnings = remove_unlisted_skills(&mut resume, &profile_skills);
+        assert_eq!(resume.sections[0].items[0].bullets, vec!["rust, TypeScript".to_string()]);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("Kubernetes"));
+    }
+
+    fn import_payload(url: Option<&str>) -> JobImportPayload {
+        JobImportPayload {
+            title: Some("Staff Engineer".to_string()),
+            company: Some("Acme".to_string()),
+            location: Some("Remote".to_string()),
+            url: url.map(|u| u.to_string()),
+            description: Some("Build the platform".to_string()),
+            source: Some("LinkedIn".to_string()),
+            salary: Some("$180k - $210k".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_import_job_payload_full() {
+        let conn = setup_test_db();
+
+        let job = import_job_from_payload(&conn, &import_payload(Some("https://jobs.example.com/123"))).unwrap();
+        assert_eq!(job.title, Some("Staff Engineer".to_string()));
+        assert_eq!(job.job_source, Some("LinkedIn".to_string()));
+        assert_eq!(job.posting_url, Some("https://jobs.example.com/123".to_string()));
+        assert_eq!(job.salary_text, Some("$180k - $210k".to_string()));
+    }
+
+    #[test]
+    fn test_import_job_payload_minimal() {
+        let conn = setup_test_db();
+
+        let minimal: JobImportPayload = serde_json::from_value(json!({ "description": "Rust role" })).unwrap();
+        let job = import_job_from_payload(&conn, &minimal).unwrap();
+        assert_eq!(job.raw_description, Some("Rust role".to_string()));
+        assert_eq!(job.job_source, Some("browser_extension".to_string()));
+
+        let empty: JobImportPayload = serde_json::from_value(json!({ "title": "  ", "url": "https://x.test" })).unwrap();
+        assert!(import_job_from_payload(&conn, &empty).is_err());
+    }
+
+    #[test]
+    fn test_import_job_payload_dedupes_by_url() {
+        let conn = setup_test_db();
+
+        let first = import_job_from_payload(&conn, &import_payload(Some("https://jobs.example.com/123"))).unwrap();
+        let second = import_job_from_payload(&conn, &import_payload(Some("https://jobs.example.com/123/"))).unwrap();
+        assert_eq!(first.id, second.id);
+
+        let count: i64 = conn.query_row("SELECT COUNT(*) FROM jobs", [], |row| row.get(0)).unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_time_to_offer_stats() {
+        use careerbench::analytics::calculate_time_to_offer_stats;
+
+        let conn = setup_test_db();
+        seed_offer_pipeline(&conn, Some("Senior"), Some("2024-01-01"), "2024-01-11");
+        seed_offer_pipeline(&conn, Some("Senior"), Some("2024-02-01"), "2024-03-02");
+        seed_offer_pipeline(&conn, Some("Mid"), Some("2024-01-01"), "2024-01-21");
+        // Missing date_applied is excluded
+        seed_offer_pipeline(&conn, Some("Mid"), None, "2024-01-21");
+
+        let stats = calculate_time_to_offer_stats(&conn).unwrap();
+        assert_eq!(stats.sample_size, 3);
+        assert_eq!(stats.min_days, Some(10.0));
+        assert_eq!(stats.median_days, Some(20.0));
+        assert_eq!(stats.max_days, Some(30.0));
+
+        assert_eq!(stats.by_seniority.len(), 2);
+        let senior = stats.by_seniority.iter().find(|s| s.seniority == "Senior").unwrap();
+        assert_eq!(senior.sample_size, 2);
+        assert_eq!(senior.median_days, 20.0);
+        let mid = stats.by_seniority.iter().find(|s| s.seniority == "Mid").unwrap();
+        assert_eq!(mid.sample_size, 1);
+        assert_eq!(mid.median_days, 20.0);
+    }
+
+    fn import_export_json(schema_version: u32, job_id: i64) -> String {
+        json!({
+            "metadata": {
+                "exported_at": "2024-01-01T00:00:00Z",
+                "version": "0.1.0",
+                "schema_version": schema_version,
+                "record_counts": { "jobs": 1, "applications": 1, "artifacts": 0 }
+            },
+            "profile": null,
+            "jobs": [{
+                "id": job_id,
+                "title": "Engineer",
+                "company": "Acme",
+                "location": null,
+                "job_source": null,
+                "posting_url": null,
+                "raw_description": null,
+                "parsed_json": null,
+                "is_active": true,
+                "date_added": "2024-01-01T00:00:00Z",
+                "last_updated": "2024-01-01T00:00:00Z"
+            }],
+            "applications": [{
+                "id": 10,
+                "job_id": job_id,
+                "status": "Applied",
+                "channel": null,
+                "priority": null,
+                "date_applied": null,
+                "next_action_date": null,
+                "next_action_note": null,
+                "notes_summary": null,
+                "contact_name": null,
+                "contact_email": null,
+                "contact_linkedin": null,
+                "location_override": null,
+                "offer_compensation": null,
+                "archived": false,
+                "date_saved": "2024-01-01T00:00:00Z",
+                "created_at": "2024-01-01T00:00:00Z",
+                "updated_at": "2024-01-01T00:00:00Z",
+                "events": [{
+                    "id": 100,
+                    "event_type": "ApplicationCreated",
+                    "event_date": "2024-01-01T00:00:00Z",
+                    "from_status": null,
+                    "to_status": null,
+                    "title": null,
+                    "details": null,
+                    "created_at": "2024-01-01T00:00:00Z"
+                }]
+            }],
+            "artifacts": []
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn test_validate_import_json_valid() {
+        use careerbench::data_import::validate_import_json_with_conn;
+
+        let conn = setup_test_db();
+        let report = validate_import_json_with_conn(&conn, &import_export_json(1, 1)).unwrap();
+        assert!(report.compatible);
+        assert!(report.can_import);
+        assert_eq!(report.counts.jobs, 1);
+        assert_eq!(report.counts.applications, 1);
+        assert_eq!(report.counts.events, 1);
+        assert!(report.collisions.is_empty());
+    }
+
+    #[test]
+    fn test_validate_import_json_newer_schema_incompatible() {
+        use careerbench::data_import::validate_import_json_with_conn;
+
+        let conn = setup_test_db();
+        let report = validate_import_json_with_conn(&conn, &import_export_json(99, 1)).unwrap();
+        assert_eq!(report.schema_version, Some(99));
+        assert!(!report.compatible);
+        assert!(!report.can_import);
+        assert!(report.errors[0].contains("newer"));
+    }
+
+    #[test]
+    fn test_validate_import_json_flags_id_collisions() {
+        use careerbench::data_import::validate_import_json_with_conn;
+
+        let conn = setup_test_db();
+        let now = chrono::Utc::now().to_rfc3339();
+        conn.execute(
+            "INSERT INTO jobs (id, title, is_active, date_added, last_updated) VALUES (7, 'Existing', 1, ?, ?)",
+            rusqlite::params![now, now],
+        ).unwrap();
+
+        let report = validate_import_json_with_conn(&conn, &import_export_json(1, 7)).unwrap();
+        assert!(report.compatible);
+        assert!(!report.can_import);
+        assert_eq!(report.collisions.len(), 1);
+        assert_eq!(report.collisions[0].entity, "job");
+        assert_eq!(report.collisions[0].id, 7);
+
+        // Importing changes nothing
+        let count: i64 = conn.query_row("SELECT COUNT(*) FROM jobs", [], |row| row.get(0)).unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_rank_saved_jobs_orders_by_score_and_puts_unparsed_last() {
+        use careerbench::job_match::rank_jobs;
+
+        let conn = setup_test_db();
+        let now = chrono::Utc::now().to_rfc3339();
+        conn.execute(
+            "INSERT INTO user_profile (id, full_name, created_at, updated_at) VALUES (1, 'Jane', ?, ?)",
+            rusqlite::params![now, now],
+        ).unwrap();
+        for skill in ["Rust", "TypeScript", "PostgreSQL"] {
+            conn.execute("INSERT INTO skills (user_profile_id, name) VALUES (1, ?)", [skill]).unwrap();
+        }
+
+        let unparsed = seed_job_with_parsed(&conn, "Unparsed", None);
+        let weak = seed_job_with_parsed(&conn, "Weak", Some(json!({ "requiredSkills": ["Java", "Spring", "Rust"] })));
+        let strong = seed_job_with_parsed(&conn, "Strong", Some(json!({
+            "requiredSkills": ["Rust", "PostgreSQL"],
+            "niceToHaveSkills": ["Kubernetes"]
+        })));
+
+        let ranked = rank_jobs(&conn, None).unwrap();
+        let order: Vec<i64> = ranked.iter().map(|r| r.job_id).collect();
+        assert_eq!(order, vec![strong, weak, unparsed]);
+
+        assert_eq!(ranked[0].score, Some(80.0));
+        assert_eq!(ranked[0].missing_skills, vec!["Kubernetes".to_string()]);
+        assert!(ranked[2].needs_parsing);
+        assert_eq!(ranked[2].score, None);
+
+        assert_eq!(rank_jobs(&conn, Some(1)).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_export_portfolio_pdf_puts_highlighted_items_first() {
+        use careerbench::portfolio_export::{export_portfolio_pdf, PortfolioItem};
+
+        let items = vec![
+            PortfolioItem {
+                id: Some(1),
+                title: "Regular Project".to_string(),
+                url: Some("https://example.com/regular".to_string()),
+                description: Some("A side project.".to_string()),
+                role: None,
+                tech_stack: Some("Rust, SQLite".to_string()),
+                highlighted: false,
+            },
+            PortfolioItem {
+                id: Some(2),
+                title: "Featured Project".to_string(),
+                url: Some("https://example.com/featured".to_string()),
+                description: Some("The flagship project (with parentheses).".to_string()),
+                role: Some("Lead Engineer".to_string()),
+                tech_stack: Some("TypeScript, React".to_string()),
+                highlighted: true,
+            },
+        ];
+
+        let bytes = export_portfolio_pdf(&items, false);
+        let pdf = String::from_utf8_lossy(&bytes);
+
+        assert!(pdf.starts_with("%PDF-"));
+        assert!(pdf.trim_end().ends_with("%%EOF"));
+        assert!(pdf.contains("xref"));
+
+        let featured = pdf.find("(Featured Project [1])").expect("highlighted item rendered");
+        let regular = pdf.find("(Regular Project [2])").expect("regular item rendered");
+        assert!(featured < regular);
+        assert!(pdf.contains("[1] https://example.com/featured"));
+        assert!(pdf.contains("\\(with parentheses\\)"));
+
+        let highlighted_only = export_portfolio_pdf(&items, true);
+        assert!(!String::from_utf8_lossy(&highlighted_only).contains("Regular Project"));
+    }
+
+    #[test]
+    fn test_prune_empty_artifacts_keeps_content_and_attachments() {
+        use careerbench::data_deletion::prune_empty_artifacts_with_conn;
+
+        let conn = setup_test_db();
+        let now = chrono::Utc::now().to_rfc3339();
+
+        let insert_artifact = |content: Option<&str>, source: &str| {
+            conn.execute(
+                "INSERT INTO artifacts (type, title, content, source, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?)",
+                rusqlite::params!["Resume", "Resume", content, source, now, now],
+            ).unwrap();
+            conn.last_insert_rowid()
+        };
+
+        let empty = insert_artifact(Some("  \n\t "), "ai_generated");
+        let non_empty = insert_artifact(Some("Summary"), "ai_generated");
+        let with_attachment = insert_artifact(None, "ai_generated");
+        let user_edited = insert_artifact(Some(""), "user_edited");
+        conn.execute(
+            "INSERT INTO artifact_attachments (artifact_id, file_name, file_path, created_at) VALUES (?, ?, ?, ?)",
+            rusqlite::params![with_attachment, "resume.pdf", "/tmp/resume.pdf", now],
+        ).unwrap();
+
+        assert_eq!(prune_empty_artifacts_with_conn(&conn).unwrap(), 1);
+
+        let remaining: Vec<i64> = conn
+            .prepare("SELECT id FROM artifacts ORDER BY id")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .map(|r| r.unwrap())
+            .collect();
+        assert!(!remaining.contains(&empty));
+        assert_eq!(remaining, vec![non_empty, with_attachment, user_edited]);
+
+        // Nothing left to prune on a second pass
+        assert_eq!(prune_empty_artifacts_with_conn(&conn).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_upcoming_actions_include_application_deadlines() {
+        use careerbench::calendar::get_upcoming_actions_with_conn;
+
+        let conn = setup_test_db();
+        let today = chrono::NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+
+        let insert_job = |title: &str, deadline: &str| {
+            conn.execute(
+                "INSERT INTO jobs (title, company, is_active, date_added, last_updated, application_deadline) VALUES (?, 'Acme', 1, '2025-02-20', '2025-02-20', ?)",
+                rusqlite::params![title, deadline],
+            ).unwrap();
+            conn.last_insert_rowid()
+        };
+        let soon = insert_job("Soon", "2025-03-05");
+        insert_job("Later", "2025-06-01");
+        let applied = insert_job("Applied", "2025-03-03");
+        conn.execute(
+            "INSERT INTO applications (job_id, status, date_saved, date_applied, next_action_date, next_action_note, archived, created_at, updated_at)
+             VALUES (?, 'Applied', '2025-02-21', '2025-02-22', '2025-03-04', 'Follow up', 0, '2025-02-21', '2025-02-21')",
+            [applied],
+        ).unwrap();
+
+        let actions = get_upcoming_actions_with_conn(&conn, today, 14).unwrap();
+        let summary: Vec<(&str, &str)> = actions
+            .iter()
+            .map(|a| (a.action_type.as_str(), a.date.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![("NextAction", "2025-03-04"), ("ApplicationDeadline", "2025-03-05")]
+        );
+        assert_eq!(actions[1].job_id, Some(soon));
+    }
+
+    fn seed_profile_for_patch(conn: &Connection) {
+        let seed: ProfilePatch = serde_json::from_value(json!({
+            "profile": { "full_name": "Alex Doe", "headline": "Engineer", "summary": "Old summary" },
+            "experience": [{
+                "id": null, "company": "Acme", "title": "Engineer", "location": null,
+                "start_date": "2020-01", "end_date": null, "is_current": true,
+                "description": "Built things", "achievements": null, "tech_stack": "Rust"
+            }],
+            "skills": [
+                { "id": null, "name": "Rust", "category": null, "self_rating": null, "priority": null, "years_experience": null, "notes": null }
+            ]
+        })).unwrap();
+        patch_user_profile_data(conn, &seed).unwrap();
+    }
+
+    #[test]
+    fn test_profile_patch_summary_leaves_experience_intact() {
+        let conn = setup_test_db();
+        seed_profile_for_patch(&conn);
+        conn.execute(
+            "INSERT INTO ai_cache (purpose, input_hash, model_name, request_payload, response_payload, created_at) VALUES ('resume_generation', 'h', 'm', '{}', '{}', '2025-01-01')",
+            [],
+        ).unwrap();
+
+        let patch: ProfilePatch = serde_json::from_value(json!({
+            "profile": { "summary": "New summary", "headline": null }
+        })).unwrap();
+        let data = patch_user_profile_data(&conn, &patch).unwrap();
+
+        let profile = data.profile.unwrap();
+        assert_eq!(profile.full_name, "Alex Doe");
+        assert_eq!(profile.summary, Some("New summary".to_string()));
+        assert_eq!(profile.headline, None);
+        assert_eq!(data.experience.len(), 1);
+        assert_eq!(data.experience[0].company, "Acme");
+        assert_eq!(data.skills.len(), 1);
+
+        let cached: i64 = conn.query_row("SELECT COUNT(*) FROM ai_cache", [], |row| row.get(0)).unwrap();
+        assert_eq!(cached, 0);
+    }
+
+    #[test]
+    fn test_profile_patch_skills_replaces_only_skills() {
+        let conn = setup_test_db();
+        seed_profile_for_patch(&conn);
+
+        let patch: ProfilePatch = serde_json::from_value(json!({
+            "skills": [
+                { "id": null, "name": "Go", "category": null, "self_rating": null, "priority": null, "years_experience": null, "notes": null },
+                { "id": null, "name": "SQL", "category": null, "self_rating": null, "priority": null, "years_experience": null, "notes": null }
+            ]
+        })).unwrap();
+        let data = patch_user_profile_data(&conn, &patch).unwrap();
+
+        let skill_names: Vec<&str> = data.skills.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(skill_names, vec!["Go", "SQL"]);
+        assert_eq!(data.experience.len(), 1);
+        assert_eq!(data.profile.unwrap().summary, Some("Old summary".to_string()));
+
+        // Clearing the required name is rejected without touching stored data
+        let invalid: ProfilePatch = serde_json::from_value(json!({ "profile": { "full_name": null } })).unwrap();
+        assert!(patch_user_profile_data(&conn, &invalid).is_err());
+        assert_eq!(load_user_profile_data(&conn).unwrap().profile.unwrap().full_name, "Alex Doe");
+    }
+
+    #[test]
+    fn test_application_list_includes_event_rollup() {
+        let conn = setup_test_db();
+        let with_events = setup_application_for_events(&conn);
+        let without_events = setup_application_for_events(&conn);
+
+        for (event_type, event_date) in [
+            ("Applied", "2025-01-01T09:00:00Z"),
+            ("InterviewScheduled", "2025-01-10T09:00:00Z"),
+            ("FollowUpSent", "2025-01-05T09:00:00Z"),
+        ] {
+            conn.execute(
+                "INSERT INTO application_events (application_id, event_type, event_date, created_at) VALUES (?, ?, ?, ?)",
+                rusqlite::params![with_events, event_type, event_date, event_date],
+            ).unwrap();
+        }
+
+        let list = list_applications(&conn, None, None, None, None, None, None).unwrap();
+        assert_eq!(list.total, 2);
+
+        let summary = list.applications.iter().find(|a| a.id == with_events).unwrap();
+        assert_eq!(summary.event_count, 3);
+        assert_eq!(summary.latest_event_type, Some("InterviewScheduled".to_string()));
+
+        let empty = list.applications.iter().find(|a| a.id == without_events).unwrap();
+        assert_eq!(empty.event_count, 0);
+        assert_eq!(empty.latest_event_type, None);
+    }
+
+    #[tokio::test]
+    async fn test_star_rewrite_rewrites_each_bullet_and_caches() {
+        use careerbench::ai::mock_provider::MockProvider;
+        use careerbench::resume_generator::{
+            cache_star_rewrite, get_cached_star_rewrite, request_star_rewrite, split_achievement_bullets,
+        };
+
+        let conn = setup_test_db();
+        let mock_provider = MockProvider::new();
+        let now = chrono::Utc::now().to_rfc3339();
+
+        let bullets = split_achievement_bullets("- Improved deploy times\n• Led the billing migration\n\n* Mentored two engineers");
+        assert_eq!(bullets.len(), 3);
+        assert!(split_achievement_bullets("  \n ").is_empty());
+
+        assert_eq!(get_cached_star_rewrite(&conn, &bullets, &now).unwrap(), None);
+        let rewritten = request_star_rewrite(&mock_provider, &bullets).await.unwrap();
+        assert_eq!(
+            rewritten,
+            vec![
+                "STAR: Improved deploy times",
+                "STAR: Led the billing migration",
+                "STAR: Mentored two engineers",
+            ]
+        );
+        assert_eq!(mock_provider.llm_call_count(), 1);
+
+        cache_star_rewrite(&conn, &bullets, &rewritten, &now).unwrap();
+        assert_eq!(get_cached_star_rewrite(&conn, &bullets, &now).unwrap(), Some(rewritten));
+
+        // Edited achievements miss the cache
+        let edited = vec!["Improved deploy times by 50%".to_string()];
+        assert_eq!(get_cached_star_rewrite(&conn, &edited, &now).unwrap(), None);
+    }
+
+    #[test]
+    fn test_job_list_location_filter_matches_any_keyword() {
+        let conn = setup_test_db();
+        let london = seed_job_at(&conn, "London role", Some("London, UK"), None);
+        let hybrid = seed_job_at(&conn, "Hybrid role", Some("Manchester (hybrid)"), None);
+        seed_job_at(&conn, "Berlin role", Some("Berlin, Germany"), None);
+        seed_job_at(&conn, "No location", None, None);
+
+        let keywords = Some(vec!["london".to_string(), "Hybrid".to_string(), " ".to_string()]);
+        let list = list_jobs(&conn, None, None, None, keywords, None, None, None, None, None).unwrap();
+
+        let mut ids: Vec<i64> = list.jobs.iter().map(|j| j.id).collect();
+        ids.sort();
+        assert_eq!(ids, vec![london, hybrid]);
+        assert_eq!(list.total, 2);
+
+        // No keywords means no location filtering
+        let all = list_jobs(&conn, None, None, None, Some(vec![]), None, None, None, None, None).unwrap();
+        assert_eq!(all.total, 4);
+    }
+
+    #[test]
+    fn test_job_list_location_filter_always_includes_remote_jobs() {
+        let conn = setup_test_db();
+        let remote = seed_job_at(&conn, "Remote role", Some("Anywhere"), Some(r#"{"remoteFriendly": true}"#));
+        seed_job_at(&conn, "Onsite role", Some("Paris"), Some(r#"{"remoteFriendly": false}"#));
+        seed_job_at(&conn, "Bad parse", Some("Paris"), Some("not json"));
+
+        let list = list_jobs(&conn, None, None, None, Some(vec!["London".to_string()]), None, None, None, None, None).unwrap();
+        let ids: Vec<i64> = list.jobs.iter().map(|j| j.id).collect();
+        assert_eq!(ids, vec![remote]);
+    }
+
+    #[test]
+    fn test_stale_artifact_save_is_rejected() {
+        let conn = setup_test_db();
+        let loaded_at = "2025-01-01T00:00:00+00:00";
+        conn.execute(
+            "INSERT INTO artifacts (type, title, content, created_at, updated_at) VALUES ('Resume', 'Resume', 'Original', ?, ?)",
+            rusqlite::params![loaded_at, loaded_at],
+        ).unwrap();
+        let artifact_id = conn.last_insert_rowid();
+
+        // First editor saves against the version they loaded
+        save_artifact_content(&conn, artifact_id, "Edited in window A", Some(loaded_at)).unwrap();
+
+        // Second editor still holds the original timestamp
+        let err = save_artifact_content(&conn, artifact_id, "Edited in window B", Some(loaded_at)).unwrap_err();
+        assert!(err.starts_with("Conflict:"), "unexpected error: {}", err);
+
+        let content: String = conn
+            .query_row("SELECT content FROM artifacts WHERE id = ?", [artifact_id], |row| row.get(0))
+            .unwrap();
+        assert_eq!(content, "Edited in window A");
+
+        // Reloading gives a fresh timestamp that is accepted
+        let fresh: String = conn
+            .query_row("SELECT updated_at FROM artifacts WHERE id = ?", [artifact_id], |row| row.get(0))
+            .unwrap();
+        assert!(save_artifact_content(&conn, artifact_id, "Edited in window B", Some(&fresh)).is_ok());
+    }
+
+    #[test]
+    fn test_stale_profile_save_is_rejected() {
+        let conn = setup_test_db();
+        seed_profile_for_patch(&conn);
+        let mut stale = load_user_profile_data(&conn).unwrap();
+
+        // Another window saves the profile after this one loaded it
+        conn.execute("UPDATE user_profile SET summary = 'Other window', updated_at = '2099-01-01T00:00:00+00:00' WHERE id = 1", []).unwrap();
+
+        stale.profile.as_mut().unwrap().summary = Some("Stale edit".to_string());
+        let err = store_user_profile_data(&conn, &stale).unwrap_err();
+        assert!(err.starts_with("Conflict:"), "unexpected error: {}", err);
+        let data = load_user_profile_data(&conn).unwrap();
+        assert_eq!(data.profile.as_ref().unwrap().summary.as_deref(), Some("Other window"));
+        assert_eq!(data.experience.len(), 1);
+
+        let mut fresh = data;
+        fresh.profile.as_mut().unwrap().summary = Some("Fresh edit".to_string());
+        store_user_profile_data(&conn, &fresh).unwrap();
+        let saved = load_user_profile_data(&conn).unwrap().profile.unwrap();
+        assert_eq!(saved.summary.as_deref(), Some("Fresh edit"));
+    }
+
+    #[test]
+    fn test_stale_application_update_is_rejected() {
+        let conn = setup_test_db();
+        let app_id = setup_application_for_events(&conn);
+        let stored: String = conn
+            .query_row("SELECT updated_at FROM applications WHERE id = ?", [app_id], |row| row.get(0))
+            .unwrap();
+
+        let input = |expected: &str| UpdateApplicationInput {
+            status: None,
+            channel: None,
+            priority: Some("High".to_string()),
+            date_applied: None,
+            next_action_date: None,
+            next_action_note: None,
+            notes_summary: None,
+            contact_name: None,
+            contact_email: None,
+            contact_linkedin: None,
+            location_override: None,
+            offer_compensation: None,
+            expected_updated_at: Some(expected.to_string()),
+            allow_override: false,
+        };
+
+        let mut stale = input("2000-01-01T00:00:00+00:00");
+        stale.status = Some("Applied".to_string());
+        let err = update_application_with_conn(&conn, app_id, stale, chrono::Utc::now()).unwrap_err();
+        assert!(err.starts_with("Conflict:"), "unexpected error: {}", err);
+        // Nothing from the rejected save is kept, including its status event
+        let (priority, events): (Option<String>, i64) = conn
+            .query_row(
+                "SELECT priority, (SELECT COUNT(*) FROM application_events WHERE application_id = applications.id AND event_type = 'StatusChanged') FROM applications WHERE id = ?",
+                [app_id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_ne!(priority, Some("High".to_string()));
+        assert_eq!(events, 0);
+
+        let app = update_application_with_conn(&conn, app_id, input(&stored), chrono::Utc::now()).unwrap();
+        assert_eq!(app.priority, Some("High".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_tighten_content_retries_when_over_limit_and_caches() {
+        use careerbench::ai::mock_provider::MockProvider;
+        use careerbench::resume_generator::{
+            cache_tightened_content, count_words, get_cached_tightened_content, request_tightened_content,
+        };
+
+        let conn = setup_test_db();
+        let now = chrono::Utc::now().to_rfc3339();
+        let letter = "I am writing to express my strong interest in the role. ".repeat(10);
+
+        // First answer is still too long, the stricter retry fits
+        let mock_provider = MockProvider::new();
+        mock_provider.register_llm_responses(vec![
+            "word ".repeat(30),
+            "I am excited to apply for this role.".to_string(),
+        ]);
+        let tightened = request_tightened_content(&mock_provider, &letter, 20).await.unwrap();
+        assert_eq!(mock_provider.llm_call_count(), 2);
+        assert_eq!(tightened.content, "I am excited to apply for this role.");
+        assert_eq!(tightened.word_count, 8);
+        assert!(tightened.warning.is_none());
+
+        assert_eq!(get_cached_tightened_content(&conn, &letter, 20, &now).unwrap(), None);
+        cache_tightened_content(&conn, &letter, 20, &tightened, &now).unwrap();
+        assert_eq!(get_cached_tightened_content(&conn, &letter, 20, &now).unwrap(), Some(tightened));
+        // A different limit is a different request
+        assert_eq!(get_cached_tightened_content(&conn, &letter, 50, &now).unwrap(), None);
+
+        // Both attempts over the limit: the shorter one comes back with a warning
+        let stubborn = MockProvider::new();
+        stubborn.register_llm_responses(vec!["word ".repeat(30), "word ".repeat(25)]);
+        let best_effort = request_tightened_content(&stubborn, &letter, 20).await.unwrap();
+        assert_eq!(stubborn.llm_call_count(), 2);
+        assert_eq!(count_words(&best_effort.content), 25);
+        assert!(best_effort.warning.is_some());
+    }
+
+    fn seed_artifact(conn: &Connection, artifact_type: &str, title: &str, content: &str, job_id: Option<i64>) -> i64 {
+        conn.execute(
+            "INSERT INTO artifacts (job_id, type, title, content, created_at, updated_at) VALUES (?, ?, ?, ?, '2025-01-01', '2025-01-01')",
+            rusqlite::params![job_id, artifact_type, title, content],
+        ).unwrap();
+        conn.last_insert_rowid()
+    }
+
+    #[test]
+    fn test_search_artifacts_finds_phrase_with_snippet() {
+        let conn = setup_test_db();
+        let job_id = seed_job_at(&conn, "Backend role", None, None);
+        let filler = "Thank you for considering my application. ".repeat(5);
+        let letter = seed_artifact(
+            &conn,
+            "CoverLetter",
+            "Letter for Acme",
+            &format!("{}I rebuilt the Payments Pipeline in six weeks. {}", filler, filler),
+            Some(job_id),
+        );
+        seed_artifact(&conn, "Resume", "Resume for Acme", "Built APIs in Rust", Some(job_id));
+
+        let results = search_artifacts_with_conn(&conn, "payments pipeline", None).unwrap();
+        assert_eq!(results.len(), 1);
+        let hit = &results[0];
+        assert_eq!(hit.artifact_id, letter);
+        assert_eq!(hit.job_id, Some(job_id));
+        assert_eq!(hit.r#type, "CoverLetter");
+        assert_eq!(hit.match_count, 1);
+        assert!(hit.snippet.contains("Payments Pipeline"));
+        assert!(hit.snippet.starts_with('…') && hit.snippet.ends_with('…'));
+
+        assert!(search_artifacts_with_conn(&conn, "   ", None).unwrap().is_empty());
+        assert!(search_artifacts_with_conn(&conn, "100%", None).unwrap().is_empty());
+
+        // Case folding covers non-ASCII letters too
+        let accented = seed_artifact(&conn, "CoverLetter", "Lettre", "Je rejoindrai l'équipe Ünternehmen", None);
+        let results = search_artifacts_with_conn(&conn, "ÉQUIPE ü", None).unwrap();
+        assert_eq!(results.iter().map(|r| r.artifact_id).collect::<Vec<_>>(), vec![accented]);
+    }
+
+    #[test]
+    fn test_search_artifacts_type_filter_and_ranking() {
+        let conn = setup_test_db();
+        let resume = seed_artifact(&conn, "Resume", "Resume", "Led Kubernetes migration", None);
+        let letter = seed_artifact(&conn, "CoverLetter", "Letter", "Kubernetes, Kubernetes and more Kubernetes", None);
+        let titled = seed_artifact(&conn, "CoverLetter", "Kubernetes letter", "Nothing relevant here", None);
+
+        let all: Vec<i64> = search_artifacts_with_conn(&conn, "kubernetes", None)
+            .unwrap()
+            .iter()
+            .map(|m| m.artifact_id)
+            .collect();
+        assert_eq!(all, vec![titled, letter, resume]);
+
+        let resumes = search_artifacts_with_conn(&conn, "kubernetes", Some("Resume")).unwrap();
+        assert_eq!(resumes.len(), 1);
+        assert_eq!(resumes[0].artifact_id, resume);
+    }
+
+    fn experience_for_preview(id: i64, title: &str, tech_stack: &str, description: &str) -> Experience {
+        Experience {
+            id: Some(id),
+            company: "Acme".to_string(),
+            title: title.to_string(),
+            location: None,
+            start_date: None,
+            end_date: None,
+            is_current: false,
+            description: Some(description.to_string()),
+            achievements: None,
+            tech_stack: Some(tech_stack.to_string()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_role_selection_preview_ranks_most_relevant_role_first() {
+        use careerbench::ai::mock_provider::MockProvider;
+        use careerbench::ai::types::ParsedJobOutput;
+        use careerbench::resume_generator::{
+            cache_jd_summary, get_cached_jd_summary, preview_top_roles, request_jd_summary,
+        };
+
+        let conn = setup_test_db();
+        let now = chrono::Utc::now().to_rfc3339();
+        let job_description = "Backend Engineer working on Rust services with PostgreSQL and Kubernetes.";
+
+        let mock_provider = MockProvider::new();
+        mock_provider.register_parse_job(
+            &MockProvider::job_key(job_description),
+            ParsedJobOutput {
+                title_suggestion: Some("Backend Engineer".to_string()),
+                company_suggestion: None,
+                location: None,
+                seniority: None,
+                required_skills: vec!["Rust".to_string(), "PostgreSQL".to_string()],
+                nice_to_have_skills: vec!["Kubernetes".to_string()],
+                responsibilities: vec![],
+                domain_tags: vec![],
+                remote_friendly: None,
+                summary: None,
+                seniority_score: None,
+                salary_min: None,
+                salary_max: None,
+                salary_currency: None,
+            },
+        );
+
+        assert!(get_cached_jd_summary(&conn, job_description, None, &now).unwrap().is_none());
+        let summary = request_jd_summary(&mock_provider, job_description).await.unwrap();
+        cache_jd_summary(&conn, "mock-model", job_description, None, &summary, &now).unwrap();
+        assert!(get_cached_jd_summary(&conn, job_description, None, &now).unwrap().is_some());
+
+        let experiences = vec![
+            experience_for_preview(1, "Frontend Developer", "React, TypeScript", "Built dashboards"),
+            experience_for_preview(2, "Senior Backend Engineer", "Rust, PostgreSQL, Kubernetes", "Ran the billing services"),
+            experience_for_preview(3, "Data Analyst", "Python", "Wrote reports"),
+            experience_for_preview(4, "Support Engineer", "Linux", "Handled escalations"),
+        ];
+        let preview = preview_top_roles(&experiences, &summary, 3);
+
+        assert_eq!(preview.len(), 4);
+        assert_eq!(preview[0].experience_id, Some(2));
+        assert!(preview[0].included);
+        assert!(preview[0].score > preview[1].score);
+        assert!(preview[0].matched_skills.contains(&"Rust".to_string()));
+        assert_eq!(preview.iter().filter(|role| role.included).count(), 3);
+        assert!(!preview[3].included);
+    }
+
+    #[test]
+    fn test_priority_suggestion_from_match_and_deadline() {
+        use careerbench::application_priority::{accept_priority_suggestion, suggest_application_priority};
+
+        let conn = setup_test_db();
+        seed_profile_for_patch(&conn); // profile skills: Rust
+        let today = chrono::NaiveDate::from_ymd_opt(2025, 3, 10).unwrap();
+
+        let strong = seed_application_for_priority(&conn, &["Rust"], "2025-03-13", Some("$150k"));
+        let weak = seed_application_for_priority(&conn, &["Java", "Spring"], "2025-06-30", None);
+
+        let high = suggest_application_priority(&conn, strong, today).unwrap();
+        assert_eq!(high.priority, "High");
+        assert_eq!(high.match_score, Some(100.0));
+        assert_eq!(high.days_until_deadline, Some(3));
+        assert!(high.rationale.contains("deadline in 3 days"));
+
+        let low = suggest_application_priority(&conn, weak, today).unwrap();
+        assert_eq!(low.priority, "Low");
+        assert_eq!(low.match_score, Some(0.0));
+
+        // Salary fit only counts once an expectation is configured
+        assert_eq!(high.salary_meets_expectation, None);
+        careerbench::app_settings::set_json_setting(
+            &conn,
+            careerbench::compensation::SALARY_EXPECTATION_SETTING,
+            &json!({ "minimum": 200000.0, "currency": "USD" }),
+        ).unwrap();
+        let below = suggest_application_priority(&conn, strong, today).unwrap();
+        assert_eq!(below.salary_meets_expectation, Some(false));
+        assert_eq!(below.priority, "Medium");
+
+        accept_priority_suggestion(&conn, strong, &high.priority).unwrap();
+        let priority: Option<String> = conn
+            .query_row("SELECT priority FROM applications WHERE id = ?", [strong], |row| row.get(0))
+            .unwrap();
+        assert_eq!(priority.as_deref(), Some("High"));
+
+        assert!(suggest_application_priority(&conn, 9999, today).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_cloud_prompt_is_scrubbed_but_rendered_letter_keeps_email() {
+        use careerbench::ai::mock_provider::MockProvider;
+        use careerbench::ai::pii_scrubber::ScrubbingProvider;
+        use careerbench::ai::provider::AiProvider;
+        use careerbench::ai::types::{CoverLetter, CoverLetterInput};
+        use std::sync::Arc;
+
+        let mock_provider = Arc::new(MockProvider::new());
+        let job_description = "Staff platform engineer for our payments team";
+        // The cloud model only ever sees the placeholder, so that's what it echoes back
+        mock_provider.register_cover_letter(
+            &MockProvider::job_key(job_description),
+            CoverLetter {
+                subject: Some("Application for Staff Platform Engineer".to_string()),
+                greeting: Some("Dear Hiring Manager,".to_string()),
+                body_paragraphs: vec!["I would love to join the payments team.".to_string()],
+                closing: Some("Best regards,".to_string()),
+                signature: Some("Jane Doe\n[EMAIL_1]\n[PHONE_1]".to_string()),
+            },
+        );
+
+        let provider = ScrubbingProvider::new(mock_provider.clone());
+        let cover_letter = provider
+            .generate_cover_letter(CoverLetterInput {
+                profile_data: json!({
+                    "profile": {
+                        "full_name": "Jane Doe",
+                        "email": "jane.doe@example.com",
+                        "phone": "+1 415 555 0123"
+                    }
+                }),
+                job_description: job_description.to_string(),
+                company_name: Some("Acme".to_string()),
+                options: None,
+                output_language: None,
+            })
+            .await
+            .unwrap();
+
+        let sent = mock_provider.received_requests();
+        assert_eq!(sent.len(), 1);
+        assert!(!sent[0].contains("jane.doe@example.com"));
+        assert!(!sent[0].contains("555 0123"));
+        assert!(sent[0].contains("[EMAIL_1]"));
+
+        let letter = GeneratedLetter {
+            subject: cover_letter.subject,
+            greeting: cover_letter.greeting,
+            body_paragraphs: cover_letter.body_paragraphs,
+            closing: cover_letter.closing,
+            signature: cover_letter.signature,
+        };
+        let rendered = render_letter_to_text(&letter);
+        assert!(rendered.contains("jane.doe@example.com"));
+        assert!(rendered.contains("+1 415 555 0123"));
+        assert!(!rendered.contains("[EMAIL_1]"));
+    }
+
+    #[tokio::test]
+    async fn test_call_llm_prompt_is_scrubbed_and_response_restored() {
+        use careerbench::ai::mock_provider::MockProvider;
+        use careerbench::ai::pii_scrubber::ScrubbingProvider;
+        use careerbench::ai::provider::AiProvider;
+        use std::sync::Arc;
+
+        let mock_provider = Arc::new(MockProvider::new());
+        mock_provider.register_llm_responses(vec!["Reach me at [EMAIL_1].".to_string()]);
+
+        let provider = ScrubbingProvider::new(mock_provider.clone());
+        let response = provider
+            .call_llm(None, "Write a sign-off for jane.doe@example.com")
+            .await
+            .unwrap();
+
+        assert_eq!(mock_provider.received_requests(), vec!["Write a sign-off for [EMAIL_1]".to_string()]);
+        assert_eq!(response, "Reach me at jane.doe@example.com.");
+    }
+
+    #[test]
+    fn test_application_age_distribution_buckets() {
+        use careerbench::analytics::calculate_application_age_distribution;
+        use chrono::NaiveDate;
+
+        let conn = setup_test_db();
+        conn.execute(
+            "INSERT INTO jobs (title, company, is_active, date_added, last_updated) VALUES ('Engineer', 'Acme', 1, '2024-01-01', '2024-01-01')",
+            [],
+        ).unwrap();
+        let job_id = conn.last_insert_rowid();
+
+        let seed = |status: &str, date_saved: &str, date_applied: Option<&str>, archived: i64| {
+            conn.execute(
+                "INSERT INTO applications (job_id, status, date_saved, date_applied, archived, created_at, updated_at)
+                 VALUES (?, ?, ?, ?, ?, ?, ?)",
+                rusqlite::params![job_id, status, date_saved, date_applied, archived, date_saved, date_saved],
+            ).unwrap();
+        };
+
+        // today = 2024-03-31
+        seed("Applied", "2024-03-20", Some("2024-03-28"), 0); // 3 days
+        seed("Saved", "2024-03-24", None, 0); // 7 days, uses date_saved
+        seed("Interviewing", "2024-03-01", Some("2024-03-20"), 0); // 11 days
+        seed("Applied", "2024-03-01", Some("2024-03-10T09:00:00Z"), 0); // 21 days
+        seed("Offer", "2024-01-01", Some("2024-02-01"), 0); // 59 days
+        seed("Applied", "2024-01-01", Some("2024-01-15"), 0); // 76 days
+        // Terminal and archived applications are excluded
+        seed("Rejected", "2024-03-01", Some("2024-03-02"), 0);
+        seed("Ghosted", "2024-01-01", Some("2024-01-02"), 0);
+        seed("Applied", "2024-03-25", Some("2024-03-25"), 1);
+
+        let today = NaiveDate::from_ymd_opt(2024, 3, 31).unwrap();
+        let buckets = calculate_application_age_distribution(&conn, today).unwrap();
+
+        let counts: Vec<(&str, i64)> = buckets.iter().map(|b| (b.label.as_str(), b.count)).collect();
+        assert_eq!(counts, vec![("0-7", 2), ("8-14", 1), ("15-30", 1), ("30+", 2)]);
+        assert_eq!(buckets[3].max_days, None);
+    }
+
+    #[test]
+    fn test_auto_tag_jobs_offline_merges_with_existing_tags() {
+        use careerbench::job_tags::auto_tag_jobs;
+
+        let conn = setup_test_db();
+        conn.execute(
+            "INSERT INTO jobs (title, company, raw_description, domain_tags, is_active, date_added, last_updated)
+             VALUES ('Full-stack Engineer', 'Acme', 'You will build React screens backed by Postgres.', 'Fintech', 1, '2024-01-01', '2024-01-01')",
+            [],
+        ).unwrap();
+        let job_id = conn.last_insert_rowid();
+
+        let updates = auto_tag_jobs(&conn, &[job_id, 9999], "2024-02-01T00:00:00Z").unwrap();
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].added_tags, vec!["Frontend", "Backend", "Data"]);
+
+        let stored: String = conn
+            .query_row("SELECT domain_tags FROM jobs WHERE id = ?", [job_id], |row| row.get(0))
+            .unwrap();
+        assert_eq!(stored, "Fintech, Frontend, Backend, Data");
+
+        // Re-running adds nothing
+        let updates = auto_tag_jobs(&conn, &[job_id], "2024-02-02T00:00:00Z").unwrap();
+        assert!(updates[0].added_tags.is_empty());
+    }
+
+    #[test]
+    fn test_low_match_warning_on_application_creation() {
+        use careerbench::job_match::{low_match_warning, save_low_match_warn_threshold};
+
+        let conn = setup_test_db();
+        seed_profile_for_patch(&conn); // profile skills: Rust
+
+        let seed_job = |required_skills: &[&str]| {
+            let parsed_json = json!({ "requiredSkills": required_skills }).to_string();
+            conn.execute(
+                "INSERT INTO jobs (title, company, parsed_json, is_active, date_added, last_updated) VALUES ('Engineer', 'Acme', ?, 1, '2025-03-01', '2025-03-01')",
+                [parsed_json],
+            ).unwrap();
+            conn.last_insert_rowid()
+        };
+        let poor_fit = seed_job(&["Java", "Spring", "Kotlin"]);
+        let good_fit = seed_job(&["Rust"]);
+
+        // No threshold configured: never warns
+        assert!(low_match_warning(&conn, poor_fit).unwrap().is_none());
+
+        save_low_match_warn_threshold(&conn, Some(50.0)).unwrap();
+        let warning = low_match_warning(&conn, poor_fit).unwrap().expect("poor fit warns");
+        assert_eq!(warning.score, 0.0);
+        assert_eq!(warning.missing_skills, vec!["Java", "Spring", "Kotlin"]);
+        assert!(low_match_warning(&conn, good_fit).unwrap().is_none());
+
+        // Clearing the threshold turns the warning off again
+        save_low_match_warn_threshold(&conn, None).unwrap();
+        assert!(low_match_warning(&conn, poor_fit).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_export_ai_usage_csv() {
+        use careerbench::ai::usage_log::{export_ai_usage_csv_with_conn, record_ai_call, AiCallRecord, AI_USAGE_CSV_HEADER};
+
+        let conn = setup_test_db();
+
+        let call = |created_at: &str, purpose: &str| AiCallRecord {
+            created_at: created_at.to_string(),
+            purpose: purpose.to_string(),
+            provider: "openai".to_string(),
+            model: Some("gpt-4o-mini".to_string()),
+            prompt_tokens: 1200,
+            completion_tokens: 350,
+            estimated_cost: 0.00039,
+            latency_ms: 842,
+            cache_hit: false,
+        };
+        record_ai_call(&conn, &call("2024-05-02T09:30:00+00:00", "cover_letter, tailored")).unwrap();
+        // Outside the range
+        record_ai_call(&conn, &call("2024-06-01T09:30:00+00:00", "job_parse")).unwrap();
+
+        let csv = export_ai_usage_csv_with_conn(&conn, "2024-05-01", "2024-05-31").unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], AI_USAGE_CSV_HEADER);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            lines[1],
+            "2024-05-02T09:30:00+00:00,\"cover_letter, tailored\",gpt-4o-mini,openai,1200,350,0.000390,842,false"
+        );
+    }
+
+    #[test]
+    fn test_ai_usage_summary_per_day_and_purpose() {
+        use careerbench::ai::usage_log::{get_ai_usage_summary_with_conn, record_ai_call, record_cache_hit, AiCallRecord};
+
+        let conn = setup_test_db();
+
+        let call = |created_at: &str, purpose: &str, provider: &str, cost: f64| AiCallRecord {
+            created_at: created_at.to_string(),
+            purpose: purpose.to_string(),
+            provider: provider.to_string(),
+            model: Some("gpt-4o-mini".to_string()),
+            prompt_tokens: 1000,
+            completion_tokens: 200,
+            estimated_cost: cost,
+            latency_ms: 500,
+            cache_hit: false,
+        };
+        record_ai_call(&conn, &call("2024-05-02T09:00:00+00:00", "cover_letter", "openai", 0.02)).unwrap();
+        record_ai_call(&conn, &call("2024-05-02T10:00:00+00:00", "job_parse", "openai", 0.01)).unwrap();
+        record_ai_call(&conn, &call("2024-05-03T08:00:00+00:00", "job_parse", "local", 0.0)).unwrap();
+        record_cache_hit(&conn, "job_parse", Some("gpt-4o-mini"), "2024-05-03T09:00:00+00:00").unwrap();
+        record_ai_call(&conn, &call("2024-06-01T08:00:00+00:00", "job_parse", "openai", 0.5)).unwrap();
+
+        let summary = get_ai_usage_summary_with_conn(&conn, "2024-05-01", "2024-05-31").unwrap();
+        assert_eq!(summary.calls, 3);
+        assert_eq!(summary.cache_hits, 1);
+        assert_eq!(summary.prompt_tokens, 3000);
+        assert!((summary.estimated_cost - 0.03).abs() < 1e-9);
+
+        let days: Vec<(&str, i64)> = summary.by_day.iter().map(|b| (b.key.as_str(), b.calls)).collect();
+        assert_eq!(days, vec![("2024-05-02", 2), ("2024-05-03", 1)]);
+        let purposes: Vec<(&str, i64, i64)> = summary.by_purpose.iter().map(|b| (b.key.as_str(), b.calls, b.cache_hits)).collect();
+        assert_eq!(purposes, vec![("cover_letter", 1, 0), ("job_parse", 2, 1)]);
+    }
+
+    fn seed_experience_for_split(conn: &Connection, start_date: &str, end_date: Option<&str>, is_current: bool) -> i64 {
+        let now = "2024-01-01T00:00:00Z";
+        conn.execute(
+            "INSERT INTO user_profile (id, full_name, created_at, updated_at) VALUES (1, 'Jane', ?, ?)",
+            rusqlite::params![now, now],
+        ).unwrap();
+        conn.execute(
+            "INSERT INTO experience (user_profile_id, company, title, location, start_date, end_date, is_current, achievements, created_at, updated_at)
+             VALUES (1, 'Acme', 'Software Engineer', 'Berlin', ?, ?, ?, 'Shipped billing v2', ?, ?)",
+            rusqlite::params![start_date, end_date, is_current as i32, now, now],
+        ).unwrap();
+        conn.last_insert_rowid()
+    }
+
+    #[test]
+    fn test_split_experience_models_promotion() {
+        let conn = setup_test_db();
+        let id = seed_experience_for_split(&conn, "2019-03", None, true);
+
+        let (original_id, new_id) =
+            split_experience_entry(&conn, id, "2021-07", "Senior Software Engineer", "2024-02-01T00:00:00Z").unwrap();
+        assert_eq!(original_id, id);
+        assert_ne!(new_id, id);
+
+        let load = |id: i64| {
+            conn.query_row(
+                "SELECT company, title, location, start_date, end_date, is_current, achievements FROM experience WHERE id = ?",
+                [id],
+                |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, Option<String>>(2)?,
+                        row.get::<_, Option<String>>(3)?,
+                        row.get::<_, Option<String>>(4)?,
+                        row.get::<_, i32>(5)?,
+                        row.get::<_, Option<String>>(6)?,
+                    ))
+                },
+            ).unwrap()
+        };
+
+        let original = load(original_id);
+        assert_eq!(original.1, "Software Engineer");
+        assert_eq!(original.3.as_deref(), Some("2019-03"));
+        assert_eq!(original.4.as_deref(), Some("2021-07"));
+        assert_eq!(original.5, 0);
+        assert_eq!(original.6.as_deref(), Some("Shipped billing v2"));
+
+        let promoted = load(new_id);
+        assert_eq!(promoted.0, "Acme");
+        assert_eq!(promoted.1, "Senior Software Engineer");
+        assert_eq!(promoted.2.as_deref(), Some("Berlin"));
+        assert_eq!(promoted.3.as_deref(), Some("2021-07"));
+        assert_eq!(promoted.4, None);
+        assert_eq!(promoted.5, 1);
+        assert_eq!(promoted.6, None);
+    }
+
+    #[test]
+    fn test_split_experience_rejects_out_of_range_date() {
+        let conn = setup_test_db();
+        let id = seed_experience_for_split(&conn, "2019-03", Some("2022-12"), false);
+        let now = "2024-02-01T00:00:00Z";
+
+        assert!(split_experience_entry(&conn, id, "2018-11", "Senior Engineer", now).is_err());
+        assert!(split_experience_entry(&conn, id, "2023-01", "Senior Engineer", now).is_err());
+        assert!(split_experience_entry(&conn, id, "2019-03", "Senior Engineer", now).is_err());
+        assert!(split_experience_entry(&conn, id, "soon", "Senior Engineer", now).is_err());
+
+        let count: i64 = conn.query_row("SELECT COUNT(*) FROM experience", [], |row| row.get(0)).unwrap();
+        assert_eq!(count, 1);
+
+        // A date inside the last month of the role is still in range
+        assert!(split_experience_entry(&conn, id, "2022-12-15", "Senior Engineer", now).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_connection_test_reports_invalid_api_key() {
+        use careerbench::ai::connection_test::{run_connection_test, ConnectionErrorKind};
+        use careerbench::ai::errors::AiProviderError;
+        use careerbench::ai::mock_provider::MockProvider;
+        use std::time::Duration;
+
+        let mock_provider = MockProvider::new();
+        mock_provider.fail_with(AiProviderError::InvalidApiKey);
+
+        let result = run_connection_test(&mock_provider, Some("gpt-4o-mini".to_string()), Duration::from_secs(5)).await;
+        assert!(!result.ok);
+        assert_eq!(result.error_kind, Some(ConnectionErrorKind::InvalidApiKey));
+        assert_eq!(result.to_string(), "Connection test failed: Invalid API key");
+    }
+
+    #[tokio::test]
+    async fn test_connection_test_success_has_latency() {
+        use careerbench::ai::connection_test::run_connection_test;
+        use careerbench::ai::mock_provider::MockProvider;
+        use std::time::Duration;
+
+        let mock_provider = MockProvider::new();
+        let result = run_connection_test(&mock_provider, Some("gpt-4o-mini".to_string()), Duration::from_secs(5)).await;
+        assert!(result.ok);
+        assert_eq!(result.error_kind, None);
+        assert!(result.latency_ms.is_some());
+        assert_eq!(result.model.as_deref(), Some("gpt-4o-mini"));
+        assert_eq!(result.to_string(), "Connection successful!");
+    }
+
+    #[tokio::test]
+    async fn test_weekly_digest_fallback_without_provider() {
+        use careerbench::ai::errors::AiProviderError;
+        use careerbench::ai::mock_provider::MockProvider;
+        use careerbench::weekly_digest::{compose_weekly_digest, gather_weekly_digest_data};
+        use chrono::NaiveDate;
+
+        let conn = setup_test_db();
+
+        conn.execute(
+            "INSERT INTO jobs (title, company, is_active, date_added, last_updated) VALUES ('Platform Engineer', 'Acme', 1, '2024-01-01', '2024-01-01')",
+            [],
+        ).unwrap();
+        let job_id = conn.last_insert_rowid();
+        conn.execute(
+            "INSERT INTO applications (job_id, status, date_saved, date_applied, last_activity_date, next_action_date, next_action_note, created_at, updated_at)
+             VALUES (?, 'Applied', '2024-02-01', '2024-02-01', '2024-02-01', '2024-03-06', 'Prep system design', '2024-02-01', '2024-02-01')",
+            [job_id],
+        ).unwrap();
+
+        let today = NaiveDate::from_ymd_opt(2024, 3, 4).unwrap();
+        let now = today.and_hms_opt(8, 0, 0).unwrap();
+        let data = gather_weekly_digest_data(&conn, now, today).unwrap();
+        assert_eq!(data.upcoming_actions.len(), 1);
+        assert_eq!(data.stale_applications.len(), 1);
+
+        let (digest, from_ai) = compose_weekly_digest(None, &data, today).await;
+        assert!(!from_ai);
+        assert!(digest.contains("Prep system design"));
+        assert!(digest.contains("Platform Engineer at Acme"));
+        assert!(!digest.contains("Interviews this week"));
+
+        // A failing provider also falls back
+        let mock_provider = MockProvider::new();
+        mock_provider.fail_with(AiProviderError::NetworkError("offline".to_string()));
+        let (fallback, from_ai) = compose_weekly_digest(Some(&mock_provider), &data, today).await;
+        assert!(!from_ai);
+        assert_eq!(fallback, digest);
+    }
+
+    #[test]
+    fn test_editing_experience_invalidates_profile_summary_cache() {
+        let conn = setup_test_db();
+        seed_profile_for_patch(&conn);
+        for purpose in ["profile_summary", "elevator_pitch", "job_parse"] {
+            conn.execute(
+                "INSERT INTO ai_cache (purpose, input_hash, model_name, request_payload, response_payload, created_at) VALUES (?, 'h', 'm', '{}', '{}', '2025-01-01')",
+                [purpose],
+            ).unwrap();
+        }
+        let count_purpose = |purpose: &str| -> i64 {
+            conn.query_row("SELECT COUNT(*) FROM ai_cache WHERE purpose = ?", [purpose], |row| row.get(0))
+                .unwrap()
+        };
+
+        let patch: ProfilePatch = serde_json::from_value(json!({
+            "experience": [{
+                "id": null, "company": "New Co", "title": "Staff Engineer", "location": null,
+                "start_date": "2024-06", "end_date": null, "is_current": true,
+                "description": null, "achievements": null, "tech_stack": null
+            }]
+        })).unwrap();
+        patch_user_profile_data(&conn, &patch).unwrap();
+
+        assert_eq!(count_purpose("profile_summary"), 0);
+        assert_eq!(count_purpose("elevator_pitch"), 0);
+        // Unrelated purposes are left alone
+        assert_eq!(count_purpose("job_parse"), 1);
+    }
+
+    #[test]
+    fn test_job_list_visa_filter_excludes_no_sponsorship() {
+        let conn = setup_test_db();
+        let sponsors = seed_job_at(&conn, "Sponsors", None, None);
+        let refuses = seed_job_at(&conn, "No sponsorship", None, None);
+        let unknown = seed_job_at(&conn, "Doesn't say", None, None);
+        conn.execute("UPDATE jobs SET visa_sponsorship = 1 WHERE id = ?", [sponsors]).unwrap();
+        conn.execute("UPDATE jobs SET visa_sponsorship = 0 WHERE id = ?", [refuses]).unwrap();
+
+        let list = list_jobs(&conn, None, None, None, None, None, None, Some(true), None, None).unwrap();
+        let mut ids: Vec<i64> = list.jobs.iter().map(|j| j.id).collect();
+        ids.sort();
+        assert_eq!(ids, vec![sponsors, unknown]);
+        assert_eq!(list.total, 2);
+
+        let all = list_jobs(&conn, None, None, None, None, None, None, None, None, None).unwrap();
+        assert_eq!(all.total, 3);
+    }
+
+    #[test]
+    fn test_reparse_clears_benefits_and_visa_sponsorship() {
+        let conn = setup_test_db();
+        let job_id = seed_job_at(&conn, "Engineer", None, None);
+        let stored = || -> (Option<String>, Option<bool>) {
+            conn.query_row("SELECT benefits, visa_sponsorship FROM jobs WHERE id = ?", [job_id], |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })
+            .unwrap()
+        };
+        let now = "2025-01-01T00:00:00Z";
+
+        let parsed: ParsedJob = serde_json::from_value(json!({})).unwrap();
+        let with_perks = ParsedJob {
+            benefits: vec!["Pension".to_string(), "Remote stipend".to_string()],
+            visa_sponsorship: Some(true),
+            ..parsed.clone()
+        };
+        update_job_with_parsed_data(&conn, job_id, &with_perks, now).unwrap();
+        assert_eq!(stored(), (Some("Pension, Remote stipend".to_string()), Some(true)));
+
+        // The description no longer mentions them, so the re-parse drops them
+        update_job_with_parsed_data(&conn, job_id, &parsed, now).unwrap();
+        assert_eq!(stored(), (None, None));
+    }
+
+    #[test]
+    fn test_related_jobs_rank_by_skill_overlap() {
+        use careerbench::job_match::find_related_jobs;
+
+        let conn = setup_test_db();
+        let seed = |title: &str, skills: &[&str], seniority: &str| {
+            let parsed_json = json!({ "requiredSkills": skills }).to_string();
+            conn.execute(
+                "INSERT INTO jobs (title, company, parsed_json, seniority, is_active, date_added, last_updated) VALUES (?, 'Acme', ?, ?, 1, '2025-01-01', '2025-01-01')",
+                rusqlite::params![title, parsed_json, seniority],
+            ).unwrap();
+            conn.last_insert_rowid()
+        };
+
+        let source = seed("Backend Engineer", &["Rust", "PostgreSQL", "Kafka", "AWS"], "Senior");
+        let close = seed("Platform Engineer", &["Rust", "PostgreSQL", "Kafka", "Docker"], "Senior");
+        let distant = seed("Data Analyst", &["SQL", "AWS", "Tableau", "Excel"], "Senior");
+        seed("Designer", &["Figma"], "Senior");
+
+        let related = find_related_jobs(&conn, source, 10).unwrap();
+        let ids: Vec<i64> = related.iter().map(|j| j.id).collect();
+        assert_eq!(ids, vec![close, distant]);
+        assert!(!ids.contains(&source));
+
+        assert_eq!(find_related_jobs(&conn, source, 1).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_support_bundle_is_redacted() {
+        use careerbench::ai::settings::{AiMode, AiSettings, CloudProvider};
+        use careerbench::support_bundle::build_support_bundle;
+
+        let conn = setup_test_db();
+        conn.execute(
+            "INSERT INTO user_profile (id, full_name, headline, created_at, updated_at) VALUES (1, 'Morgan Castellano', 'Engineer', '2025-03-01', '2025-03-01')",
+            [],
+        )
+        .unwrap();
+        seed_job_at(&conn, "Backend Engineer", Some("Remote"), None);
+
+        let log = "2025-03-01 10:00:00 [INFO] Saved profile for Morgan Castellano <morgan.castellano@example.com>\n\
+                   2025-03-01 10:00:05 [DEBUG] Calling OpenAI with key sk-live-abcdef1234567890XYZ\n\
+                   2025-03-01 10:00:07 [WARN] castellano retried request";
+        let logs = vec![
+            ("careerbench.log".to_string(), Some(log.to_string())),
+            ("crash.log".to_string(), None),
+        ];
+        let settings = AiSettings {
+            mode: AiMode::Cloud,
+            cloud_provider: Some(CloudProvider::OpenAI),
+            api_key: Some("sk-live-abcdef1234567890XYZ".to_string()),
+            model_name: Some("gpt-4o-mini".to_string()),
+            local_model_path: None,
+            ..AiSettings::default()
+        };
+        let generated_at = chrono::NaiveDate::from_ymd_opt(2025, 3, 2).unwrap().and_hms_opt(12, 0, 0).unwrap();
+
+        let bundle = build_support_bundle(&conn, &logs, Some(&settings), generated_at).unwrap();
+        let mut archive = zip::ZipArchive::new(std::io::Cursor::new(bundle)).unwrap();
+        let names: Vec<String> = archive.file_names().map(str::to_string).collect();
+        let mut text = String::new();
+        for name in &names {
+            std::io::Read::read_to_string(&mut archive.by_name(name).unwrap(), &mut text).unwrap();
+        }
+
+        assert!(names.contains(&"logs/careerbench.log".to_string()));
+        assert!(!names.contains(&"logs/crash.log".to_string()));
+        assert!(names.contains(&"health.json".to_string()));
+        assert!(names.contains(&"migrations.json".to_string()));
+        assert!(text.contains("\"apiKeyConfigured\": true"));
+        assert!(text.contains("\"jobs\": 1"));
+        assert!(text.contains("[INFO] Saved profile for"));
+
+        assert!(!text.contains("morgan.castellano@example.com"));
+        assert!(!text.contains("Morgan"));
+        assert!(!text.to_lowercase().contains("castellano"));
+        assert!(!text.contains("sk-live"));
+    }
+
+    #[test]
+    fn test_bulk_link_portfolio_is_idempotent() {
+        use careerbench::portfolio_export::bulk_link_portfolio_with_conn;
+
+        let conn = setup_test_db();
+        conn.execute(
+            "INSERT INTO user_profile (id, full_name, created_at, updated_at) VALUES (1, 'Jane', '2025-01-01', '2025-01-01')",
+            [],
+        ).unwrap();
+        conn.execute(
+            "INSERT INTO portfolio_items (user_profile_id, title) VALUES (1, 'Open-source CLI')",
+            [],
+        ).unwrap();
+        let item_id = conn.last_insert_rowid();
+        let first = seed_application_for_priority(&conn, &["Rust"], "2025-04-01", None);
+        let second = seed_application_for_priority(&conn, &["Rust"], "2025-04-01", None);
+        let third = seed_application_for_priority(&conn, &["Rust"], "2025-04-01", None);
+
+        assert_eq!(bulk_link_portfolio_with_conn(&conn, item_id, &[first, second]).unwrap(), 2);
+        // Re-running links nothing new
+        assert_eq!(bulk_link_portfolio_with_conn(&conn, item_id, &[first, second]).unwrap(), 0);
+        // Only the new application counts
+        assert_eq!(bulk_link_portfolio_with_conn(&conn, item_id, &[first, second, third]).unwrap(), 1);
+
+        let links: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM application_portfolio_links WHERE portfolio_item_id = ?",
+            [item_id],
+            |row| row.get(0),
+        ).unwrap();
+        assert_eq!(links, 3);
+
+        assert!(bulk_link_portfolio_with_conn(&conn, item_id + 100, &[first]).is_err());
+    }
+
+    #[test]
+    fn test_resume_cache_key_includes_date_format() {
+        use careerbench::ai_cache::compute_input_hash;
+
+        let conn = setup_test_db();
+        let job_id = seed_job_at(&conn, "Backend Engineer", Some("Remote"), None);
+        let job = load_job(&conn, job_id).unwrap();
+        let profile_data = UserProfileData {
+            profile: None,
+            experience: Vec::new(),
+            skills: Vec::new(),
+            education: Vec::new(),
+            certifications: Vec::new(),
+            portfolio: Vec::new(),
+        };
+
+        let hashes: Vec<String> = [
+            ResumeDateFormat::MonthNameYear,
+            ResumeDateFormat::NumericMonthYear,
+            ResumeDateFormat::YearOnly,
+        ]
+        .iter()
+        .map(|format| compute_input_hash(&resume_request_payload(&profile_data, &job, None, *format, ResumeTemplate::default(), Lang::English)).unwrap())
+        .collect();
+
+        assert_ne!(hashes[0], hashes[1]);
+        assert_ne!(hashes[1], hashes[2]);
+        assert_ne!(hashes[0], hashes[2]);
+
+        let template_hashes: Vec<String> = ResumeTemplate::ALL
+            .iter()
+            .map(|template| {
+                compute_input_hash(&resume_request_payload(&profile_data, &job, None, ResumeDateFormat::default(), *template, Lang::English)).unwrap()
+            })
+            .collect();
+        assert_ne!(template_hashes[0], template_hashes[1]);
+        assert_ne!(template_hashes[1], template_hashes[2]);
+
+        // Toggling the optional sections changes the key
+        let with_sections = |certifications: bool, portfolio: bool| {
+            let options: GenerationOptions = serde_json::from_value(serde_json::json!({
+                "include_certifications": certifications,
+                "include_portfolio": portfolio,
+            }))
+            .unwrap();
+            compute_input_hash(&resume_request_payload(&profile_data, &job, Some(&options), ResumeDateFormat::default(), ResumeTemplate::default(), Lang::English)).unwrap()
+        };
+        assert_ne!(with_sections(false, false), with_sections(true, false));
+        assert_ne!(with_sections(false, false), with_sections(false, true));
+        assert_ne!(with_sections(true, false), with_sections(false, true));
+        // Documents cached in one language are not served in another
+        let language_hash = |language: Lang| {
+            compute_input_hash(&resume_request_payload(&profile_data, &job, None, ResumeDateFormat::default(), ResumeTemplate::default(), language)).unwrap()
+        };
+        assert_ne!(language_hash(Lang::English), language_hash(Lang::German));
+    }
+
+    #[test]
+    fn test_interview_debriefs() {
+        use careerbench::interview_debriefs::{get_debriefs_for_application, save_interview_debrief};
+        use careerbench::weekly_digest::{build_plain_digest, gather_weekly_digest_data};
+        use chrono::NaiveDate;
+
+        let conn = setup_test_db();
+        let application_id = seed_application_for_priority(&conn, &["Rust"], "2025-04-01", None);
+
+        save_interview_debrief(&conn, application_id, "Phone screen", "Clear project walkthrough", "Ask more questions", 4, "2025-03-03T10:00:00+00:00").unwrap();
+        save_interview_debrief(&conn, application_id, "Onsite", "Solved the design problem", "Pace the coding round", 2, "2025-03-05T16:00:00+00:00").unwrap();
+
+        let debriefs = get_debriefs_for_application(&conn, application_id).unwrap();
+        assert_eq!(debriefs.len(), 2);
+        assert_eq!(debriefs[0].round, "Onsite");
+        assert_eq!(debriefs[0].rating, 2);
+        assert_eq!(debriefs[1].went_well, "Clear project walkthrough");
+
+        // Ratings outside 1-5 are rejected and nothing is stored
+        assert!(save_interview_debrief(&conn, application_id, "Final", "", "", 0, "2025-03-06T10:00:00+00:00").is_err());
+        assert!(save_interview_debrief(&conn, application_id, "Final", "", "", 6, "2025-03-06T10:00:00+00:00").is_err());
+        assert!(save_interview_debrief(&conn, application_id + 100, "Final", "", "", 3, "2025-03-06T10:00:00+00:00").is_err());
+        assert_eq!(get_debriefs_for_application(&conn, application_id).unwrap().len(), 2);
+
+        // The weekly digest picks up the past week's self-ratings
+        let today = NaiveDate::from_ymd_opt(2025, 3, 7).unwrap();
+        let data = gather_weekly_digest_data(&conn, today.and_hms_opt(8, 0, 0).unwrap(), today).unwrap();
+        assert_eq!(data.recent_debriefs.len(), 2);
+        let digest = build_plain_digest(&data, today);
+        assert!(digest.contains("average self-rating 3.0/5"));
+        assert!(digest.contains("Onsite: 2/5, work on: Pace the coding round"));
+    }
+
+    #[test]
+    fn test_suggest_skills_to_hide() {
+        use careerbench::job_match::suggest_skills_to_hide;
+
+        let conn = setup_test_db();
+        let parsed = json!({
+            "requiredSkills": ["Rust", "PostgreSQL"],
+            "niceToHaveSkills": ["Kafka"],
+            "responsibilities": ["Own our Docker-based deploy pipeline"]
+        })
+        .to_string();
+        let job_id = seed_job_at(&conn, "Backend Engineer", Some("Remote"), Some(&parsed));
+        conn.execute(
+            "INSERT INTO user_profile (id, full_name, created_at, updated_at) VALUES (1, 'Jane', '2025-01-01', '2025-01-01')",
+            [],
+        ).unwrap();
+
+        for (name, rating, priority) in [
+            ("Rust", Some(1), None),
+            ("Kafka", None, None),
+            ("Docker", Some(2), None),
+            ("Photoshop", Some(1), None),
+            ("Excel", Some(4), None),
+            ("Leadership", Some(2), Some("Core")),
+        ] {
+            conn.execute(
+                "INSERT INTO skills (user_profile_id, name, self_rating, priority) VALUES (1, ?, ?, ?)",
+                rusqlite::params![name, rating, priority],
+            ).unwrap();
+        }
+
+        let suggestions = suggest_skills_to_hide(&conn, job_id).unwrap();
+        // Unrelated and low-rated first; Core skills and description mentions last
+        assert_eq!(suggestions, vec!["Photoshop", "Excel", "Leadership", "Docker"]);
+        // Required and nice-to-have skills are never suggested, even when rated low
+        assert!(!suggestions.contains(&"Rust".to_string()));
+        assert!(!suggestions.contains(&"Kafka".to_string()));
+
+        let unparsed = seed_job_at(&conn, "Unparsed", None, None);
+        assert!(suggest_skills_to_hide(&conn, unparsed).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_pending_generation_backoff_and_dead_items() {
+        use careerbench::generation_queue::*;
+        use chrono::{Duration, TimeZone, Utc};
+
+        let conn = setup_test_db();
+        save_generation_max_attempts(&conn, 3).unwrap();
+        let max_attempts = load_generation_max_attempts(&conn).unwrap();
+        assert_eq!(max_attempts, 3);
+
+        let job_id = seed_job_at(&conn, "Backend Engineer", None, None);
+        let now = Utc.with_ymd_and_hms(2025, 3, 1, 9, 0, 0).unwrap();
+        let id = enqueue_generation(&conn, "resume", job_id, None, None, now).unwrap();
+        assert!(enqueue_generation(&conn, "poem", job_id, None, None, now).is_err());
+        assert_eq!(get_due_generations(&conn, now).unwrap().len(), 1);
+
+        // A failure increments attempts and backs off
+        let item = record_generation_failure(&conn, id, "Network error: offline", max_attempts, now).unwrap();
+        assert_eq!(item.attempts, 1);
+        assert_eq!(item.status, STATUS_FAILED);
+        assert_eq!(item.last_error.as_deref(), Some("Network error: offline"));
+        assert!(get_due_generations(&conn, now).unwrap().is_empty());
+        assert_eq!(get_due_generations(&conn, now + Duration::minutes(1)).unwrap().len(), 1);
+
+        // After the max attempts the item is dead and skipped by automatic processing
+        record_generation_failure(&conn, id, "offline", max_attempts, now).unwrap();
+        let item = record_generation_failure(&conn, id, "still offline", max_attempts, now).unwrap();
+        assert_eq!(item.attempts, 3);
+        assert_eq!(item.status, STATUS_DEAD);
+        assert!(item.next_attempt_at.is_none());
+        assert!(get_due_generations(&conn, now + Duration::days(30)).unwrap().is_empty());
+
+        // Dead items are still listed and can be retried by hand
+        let pending = get_pending_generations(&conn).unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].status, STATUS_DEAD);
+
+        let later = now + Duration::hours(2);
+        let item = reset_generation_for_retry(&conn, id, later).unwrap();
+        assert_eq!(item.status, STATUS_QUEUED);
+        assert_eq!(item.attempts, 3);
+        assert_eq!(get_due_generations(&conn, later).unwrap().len(), 1);
+
+        let item = record_generation_success(&conn, id, later).unwrap();
+        assert_eq!(item.status, STATUS_COMPLETED);
+        assert!(get_pending_generations(&conn).unwrap().is_empty());
+        assert!(reset_generation_for_retry(&conn, id, later).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_job_tldr_is_cached_and_needs_a_description() {
+        use careerbench::ai::mock_provider::MockProvider;
+        use careerbench::job_tldr::{cache_job_tldr, prepare_job_tldr, request_job_tldr, JobTldrRequest};
+
+        let conn = setup_test_db();
+        let now = "2025-03-01T10:00:00+00:00";
+        conn.execute(
+            "INSERT INTO jobs (title, company, raw_description, is_active, date_added, last_updated) VALUES ('Platform Engineer', 'Acme', ?, 1, '2025-03-01', '2025-03-01')",
+            ["We need a platform engineer to run our Kubernetes clusters. ".repeat(40)],
+        ).unwrap();
+        let job_id = conn.last_insert_rowid();
+
+        let mock_provider = MockProvider::new();
+        mock_provider.register_llm_responses(vec![
+            "  Acme wants a platform engineer to own its Kubernetes clusters.  ".to_string(),
+            "A different summary".to_string(),
+        ]);
+
+        // Same steps as the summarize_job_tldr command
+        let mut results = Vec::new();
+        for _ in 0..2 {
+            let tldr = match prepare_job_tldr(&conn, job_id, now).unwrap() {
+                JobTldrRequest::Cached(tldr) => tldr,
+                JobTldrRequest::Needed { input_hash, request_payload, prompt } => {
+                    assert!(prompt.contains("Kubernetes clusters"));
+                    let tldr = request_job_tldr(&mock_provider, &prompt).await.unwrap();
+                    cache_job_tldr(&conn, &input_hash, &request_payload, &tldr, now).unwrap();
+                    tldr
+                }
+            };
+            results.push(tldr);
+        }
+
+        assert_eq!(mock_provider.llm_call_count(), 1);
+        assert_eq!(results[0], "Acme wants a platform engineer to own its Kubernetes clusters.");
+        assert_eq!(results[1], results[0]);
+
+        let empty_job = seed_job_at(&conn, "No description", None, None);
+        let error = prepare_job_tldr(&conn, empty_job, now).unwrap_err();
+        assert!(error.contains("no description"));
+        assert_eq!(mock_provider.llm_call_count(), 1);
+    }
+
+    #[test]
+    fn test_build_apply_url_appends_tracking_without_changing_job() {
+        use careerbench::apply_link::{build_apply_url, save_apply_link_tracking, ApplyLinkTracking};
+
+        let conn = setup_test_db();
+        conn.execute(
+            "INSERT INTO jobs (title, company, posting_url, is_active, date_added, last_updated) VALUES ('Engineer', 'Acme', 'https://jobs.example.com/roles/42', 1, '2025-01-01', '2025-01-01')",
+            [],
+        ).unwrap();
+        let job_id = conn.last_insert_rowid();
+
+        // No value configured: the posting URL is returned as-is
+        assert_eq!(build_apply_url(&conn, job_id).unwrap(), "https://jobs.example.com/roles/42");
+
+        save_apply_link_tracking(&conn, &ApplyLinkTracking {
+            param_name: "utm_source".to_string(),
+            value: Some(" careerbench ".to_string()),
+        }).unwrap();
+        assert_eq!(
+            build_apply_url(&conn, job_id).unwrap(),
+            "https://jobs.example.com/roles/42?utm_source=careerbench"
+        );
+
+        let stored: String = conn
+            .query_row("SELECT posting_url FROM jobs WHERE id = ?", [job_id], |row| row.get(0))
+            .unwrap();
+        assert_eq!(stored, "https://jobs.example.com/roles/42");
+
+        let no_url = seed_job_at(&conn, "No link", None, None);
+        assert!(build_apply_url(&conn, no_url).is_err());
+
+        conn.execute(
+            "INSERT INTO jobs (title, company, posting_url, is_active, date_added, last_updated) VALUES ('Bad link', 'Acme', 'not a url', 1, '2025-01-01', '2025-01-01')",
+            [],
+        ).unwrap();
+        assert!(build_apply_url(&conn, conn.last_insert_rowid()).is_err());
+        assert!(build_apply_url(&conn, 9999).is_err());
+    }
+
+    #[test]
+    fn test_companies_with_stats_join_counts_and_pagination() {
+        use careerbench::companies::list_companies_with_stats;
+
+        let conn = setup_test_db();
+
+        let mut company_ids = Vec::new();
+        for (index, name) in ["Acme", "Beta Labs", "Cobalt", "Delta Systems", "Echo"].iter().enumerate() {
+            conn.execute(
+                "INSERT INTO companies (name, industry, created_at, updated_at) VALUES (?, 'Software', '2025-01-01', '2025-01-01')",
+                [name],
+            ).unwrap();
+            let company_id = conn.last_insert_rowid();
+            company_ids.push(company_id);
+
+            // Company i gets i jobs, and an application for each job but the first
+            for job_index in 0..index {
+                conn.execute(
+                    "INSERT INTO jobs (title, company, company_id, is_active, date_added, last_updated) VALUES ('Role', ?, ?, 1, '2025-01-01', '2025-01-01')",
+                    rusqlite::params![name, company_id],
+                ).unwrap();
+                if job_index > 0 {
+                    let job_id = conn.last_insert_rowid();
+                    conn.execute(
+                        "INSERT INTO applications (job_id, company_id, status, date_saved, created_at, updated_at) VALUES (?, ?, 'Applied', '2025-01-01', '2025-01-01', '2025-01-01')",
+                        rusqlite::params![job_id, company_id],
+                    ).unwrap();
+                }
+            }
+        }
+        // Unlinked rows don't count towards any company
+        seed_job_at(&conn, "Unlinked", None, None);
+
+        let all = list_companies_with_stats(&conn, None, Some(1), Some(100)).unwrap();
+        assert_eq!(all.total, 5);
+        for company in &all.companies {
+            let (expected_jobs, expected_applications): (i64, i64) = conn.query_row(
+                "SELECT
+                    COALESCE((SELECT COUNT(*) FROM jobs WHERE company_id = c.id), 0),
+                    COALESCE((SELECT COUNT(*) FROM applications WHERE company_id = c.id), 0)
+                 FROM companies c WHERE c.id = ?",
+                [company.id.unwrap()],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            ).unwrap();
+            assert_eq!(company.job_count, expected_jobs, "{}", company.name);
+            assert_eq!(company.application_count, expected_applications, "{}", company.name);
+        }
+        let echo = all.companies.iter().find(|c| c.name == "Echo").unwrap();
+        assert_eq!((echo.job_count, echo.application_count), (4, 3));
+
+        let page_two = list_companies_with_stats(&conn, None, Some(2), Some(2)).unwrap();
+        assert_eq!(page_two.total, 5);
+        assert_eq!(page_two.total_pages, 3);
+        let names: Vec<&str> = page_two.companies.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["Cobalt", "Delta Systems"]);
+
+        let searched = list_companies_with_stats(&conn, Some("Labs"), None, None).unwrap();
+        assert_eq!(searched.total, 1);
+        assert_eq!(searched.companies[0].name, "Beta Labs");
+        assert_eq!(searched.companies[0].job_count, 1);
+    }
+
+    #[test]
+    fn test_normalize_job_urls_merges_tracking_duplicates() {
+        use careerbench::job_urls::normalize_job_urls;
+
+        let conn = setup_test_db();
+
+        let insert_job = |title: &str, url: &str, description: Option<&str>| {
+            conn.execute(
+                "INSERT INTO jobs (title, company, posting_url, raw_description, is_active, date_added, last_updated) VALUES (?, 'Acme', ?, ?, 1, '2025-01-01', '2025-01-01')",
+                rusqlite::params![title, url, description],
+            ).unwrap();
+            conn.last_insert_rowid()
+        };
+        let original = insert_job("Engineer", "https://Jobs.Acme.com/roles/42?utm_source=linkedin", None);
+        let duplicate = insert_job("Engineer", "https://jobs.acme.com/roles/42?ref=newsletter", Some("Full description"));
+        let clean = insert_job("Designer", "https://jobs.acme.com/roles/43", None);
+
+        seed_application_at(&conn, duplicate, "Applied", "2025-01-02");
+
+        // One rewrite of the original, one merge of the duplicate
+        assert_eq!(normalize_job_urls(&conn).unwrap(), 2);
+
+        let url_of = |id: i64| -> String {
+            conn.query_row("SELECT posting_url FROM jobs WHERE id = ?", [id], |row| row.get(0)).unwrap()
+        };
+        assert_eq!(url_of(original), "https://jobs.acme.com/roles/42");
+        assert_eq!(url_of(clean), "https://jobs.acme.com/roles/43");
+
+        let remaining: i64 = conn.query_row("SELECT COUNT(*) FROM jobs WHERE id = ?", [duplicate], |row| row.get(0)).unwrap();
+        assert_eq!(remaining, 0);
+        let application_job: i64 = conn.query_row("SELECT job_id FROM applications", [], |row| row.get(0)).unwrap();
+        assert_eq!(application_job, original);
+        let description: Option<String> = conn
+            .query_row("SELECT raw_description FROM jobs WHERE id = ?", [original], |row| row.get(0))
+            .unwrap();
+        assert_eq!(description.as_deref(), Some("Full description"));
+
+        // Idempotent
+        assert_eq!(normalize_job_urls(&conn).unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_adapt_cover_letter_targets_new_company_and_is_cached() {
+        use careerbench::ai::mock_provider::MockProvider;
+        use careerbench::cover_letter_adapt::{
+            cache_letter_adaptation, prepare_letter_adaptation, request_letter_adaptation, AdaptLetterRequest,
+        };
+
+        let conn = setup_test_db();
+        let now = "2025-03-01T10:00:00+00:00";
+        conn.execute(
+            "INSERT INTO artifacts (type, title, content, created_at, updated_at) VALUES ('CoverLetter', 'Letter for Acme', ?, ?, ?)",
+            rusqlite::params!["Dear Acme team,\n\nI would love to bring my Rust experience to Acme's payments platform.", now, now],
+        ).unwrap();
+        let letter_id = conn.last_insert_rowid();
+        conn.execute(
+            "INSERT INTO artifacts (type, title, content, created_at, updated_at) VALUES ('Resume', 'Resume', 'Resume text', ?, ?)",
+            [now, now],
+        ).unwrap();
+        let resume_id = conn.last_insert_rowid();
+
+        let parsed = r#"{"titleSuggestion":"Backend Engineer","companySuggestion":"Globex","requiredSkills":["Rust","Kafka"]}"#;
+        conn.execute(
+            "INSERT INTO jobs (title, company, parsed_json, is_active, date_added, last_updated) VALUES ('Backend Engineer', 'Globex', ?, 1, '2025-03-01', '2025-03-01')",
+            [parsed],
+        ).unwrap();
+        let target_job = conn.last_insert_rowid();
+
+        let mock_provider = MockProvider::new();
+        mock_provider.register_llm_responses(vec![serde_json::json!({
+            "greeting": "Dear Globex team,",
+            "bodyParagraphs": ["I would love to bring my Rust and Kafka experience to Globex's backend team."],
+            "closing": "Best regards,"
+        })
+        .to_string()]);
+
+        let mut contents = Vec::new();
+        for _ in 0..2 {
+            let letter = match prepare_letter_adaptation(&conn, letter_id, target_job, Lang::English, now).unwrap() {
+                AdaptLetterRequest::Cached(letter) => letter,
+                AdaptLetterRequest::Needed { input_hash, request_payload, prompt } => {
+                    assert!(prompt.contains("Company: Globex"));
+                    assert!(prompt.contains("payments platform"));
+                    let letter = request_letter_adaptation(&mock_provider, &prompt).await.unwrap();
+                    cache_letter_adaptation(&conn, &input_hash, &request_payload, &letter, now).unwrap();
+                    letter
+                }
+            };
+            contents.push(letter.body_paragraphs.join("\n"));
+        }
+
+        assert_eq!(mock_provider.llm_call_count(), 1);
+        assert!(contents[0].contains("Globex"));
+        assert!(!contents[0].contains("Acme"));
+        assert_eq!(contents[1], contents[0]);
+
+        let error = prepare_letter_adaptation(&conn, resume_id, target_job, Lang::English, now).unwrap_err();
+        assert!(error.contains("not a cover letter"));
+    }
+
+    #[test]
+    fn test_get_jobs_needing_parse_only_returns_unparsed_with_description() {
+        use careerbench::bulk_parse::get_jobs_needing_parse;
+
+        let conn = setup_test_db();
+        let insert_job = |title: &str, description: Option<&str>, parsed_json: Option<&str>| {
+            conn.execute(
+                "INSERT INTO jobs (title, company, raw_description, parsed_json, is_active, date_added, last_updated) VALUES (?, 'Acme', ?, ?, 1, '2025-01-01', '2025-01-01')",
+                rusqlite::params![title, description, parsed_json],
+            ).unwrap();
+            conn.last_insert_rowid()
+        };
+        let needs_parse = insert_job("Needs parse", Some("Build APIs in Rust"), None);
+        insert_job("Already parsed", Some("Build APIs in Go"), Some(r#"{"requiredSkills":["Go"]}"#));
+        insert_job("No description", None, None);
+        insert_job("Blank description", Some("   "), None);
+
+        let jobs = get_jobs_needing_parse(&conn).unwrap();
+        let ids: Vec<i64> = jobs.iter().map(|job| job.id).collect();
+        assert_eq!(ids, vec![needs_parse]);
+    }
+
+    #[tokio::test]
+    async fn test_parse_jobs_concurrently_emits_one_event_per_job() {
+        use careerbench::ai::mock_provider::MockProvider;
+        use careerbench::ai::provider::AiProvider;
+        use careerbench::ai::rate_limiter::RateLimiter;
+        use careerbench::ai::types::JobParsingInput;
+        use careerbench::bulk_parse::parse_jobs_concurrently;
+        use std::sync::Arc;
+
+        let provider = Arc::new(MockProvider::new());
+        let limiter = RateLimiter::new(100, 60);
+        let mut events = Vec::new();
+
+        let results = parse_jobs_concurrently(
+            vec![1, 2, 3, 4],
+            2,
+            &limiter,
+            |job_id| {
+                let provider = Arc::clone(&provider);
+                async move {
+                    if job_id == 3 {
+                        return Err("Job description is empty; cannot parse.".to_string());
+                    }
+                    provider
+                        .parse_job(JobParsingInput {
+                            job_description: format!("Job {}", job_id),
+                            job_meta: None,
+                        })
+                        .await
+                        .map(|_| ())
+                        .map_err(|e| e.to_string())
+                }
+            },
+            |progress| events.push(progress.clone()),
+        )
+        .await;
+
+        assert_eq!(events.len(), 4);
+        assert_eq!(results.len(), 4);
+        let mut ids: Vec<i64> = events.iter().map(|event| event.job_id).collect();
+        ids.sort();
+        assert_eq!(ids, vec![1, 2, 3, 4]);
+        assert_eq!(events.last().unwrap().completed, 4);
+        assert!(events.iter().all(|event| event.total == 4));
+
+        let failed: Vec<_> = events.iter().filter(|event| !event.ok).collect();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].job_id, 3);
+        assert!(failed[0].error.as_deref().unwrap().contains("empty"));
+    }
+
+    #[test]
+    fn test_dedupe_application_events_keeps_earliest_and_last_activity() {
+        use careerbench::event_dedupe::dedupe_application_events;
+
+        let conn = setup_test_db();
+        let job_id = seed_job_at(&conn, "Engineer", None, None);
+        let insert_application = || {
+            conn.execute(
+                "INSERT INTO applications (job_id, status, date_saved, last_activity_date, created_at, updated_at) VALUES (?, 'Interviewing', '2025-01-01', '2025-03-05', '2025-01-01', '2025-01-01')",
+                [job_id],
+            ).unwrap();
+            conn.last_insert_rowid()
+        };
+        let insert_event = |application_id: i64, event_type: &str, event_date: &str, from: Option<&str>, to: Option<&str>| {
+            conn.execute(
+                "INSERT INTO application_events (application_id, event_type, event_date, from_status, to_status, created_at) VALUES (?, ?, ?, ?, ?, '2025-01-01')",
+                rusqlite::params![application_id, event_type, event_date, from, to],
+            ).unwrap();
+            conn.last_insert_rowid()
+        };
+
+        let first = insert_application();
+        let kept = insert_event(first, "StatusChanged", "2025-03-01", Some("Applied"), Some("Interviewing"));
+        insert_event(first, "StatusChanged", "2025-03-01", Some("Applied"), Some("Interviewing"));
+        insert_event(first, "StatusChanged", "2025-03-01", Some("Applied"), Some("Interviewing"));
+        // Same day, different transition: not a duplicate
+        insert_event(first, "StatusChanged", "2025-03-01", Some("Interviewing"), Some("Offer"));
+        insert_event(first, "Note", "2025-03-02", None, None);
+        insert_event(first, "Note", "2025-03-02", None, None);
+
+        let second = insert_application();
+        insert_event(second, "Note", "2025-02-01", None, None);
+        insert_event(second, "Note", "2025-02-01", None, None);
+
+        let count_events = |application_id: i64| -> i64 {
+            conn.query_row("SELECT COUNT(*) FROM application_events WHERE application_id = ?", [application_id], |row| row.get(0)).unwrap()
+        };
+        let last_activity = |application_id: i64| -> String {
+            conn.query_row("SELECT last_activity_date FROM applications WHERE id = ?", [application_id], |row| row.get(0)).unwrap()
+        };
+
+        // Scoped to one application
+        assert_eq!(dedupe_application_events(&conn, Some(first)).unwrap(), 3);
+        assert_eq!(count_events(first), 3);
+        assert_eq!(count_events(second), 2);
+        let kept_exists: i64 = conn.query_row("SELECT COUNT(*) FROM application_events WHERE id = ?", [kept], |row| row.get(0)).unwrap();
+        assert_eq!(kept_exists, 1);
+        assert_eq!(last_activity(first), "2025-03-02");
+        assert_eq!(last_activity(second), "2025-03-05");
+
+        // Across all applications, then idempotent
+        assert_eq!(dedupe_application_events(&conn, None).unwrap(), 1);
+        assert_eq!(count_events(second), 1);
+        assert_eq!(last_activity(second), "2025-02-01");
+        assert_eq!(dedupe_application_events(&conn, None).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_find_similar_cover_letters_ranks_by_skill_overlap() {
+        use careerbench::job_match::find_similar_cover_letters;
+
+        let conn = setup_test_db();
+        let target = seed_job_at(&conn, "Backend Engineer", None, Some(r#"{"requiredSkills":["Rust","PostgreSQL","Kafka"]}"#));
+        let similar = seed_job_at(&conn, "Platform Engineer", None, Some(r#"{"requiredSkills":["Rust","Kafka","Docker"]}"#));
+        let unrelated = seed_job_at(&conn, "Illustrator", None, Some(r#"{"requiredSkills":["Photoshop","Figma"]}"#));
+
+        let insert_letter = |title: &str, job_id: i64, application_id: Option<i64>, updated_at: &str| {
+            conn.execute(
+                "INSERT INTO artifacts (type, title, content, job_id, application_id, created_at, updated_at) VALUES ('CoverLetter', ?, 'Dear hiring team, I build reliable systems.', ?, ?, ?, ?)",
+                rusqlite::params![title, job_id, application_id, updated_at, updated_at],
+            ).unwrap();
+            conn.last_insert_rowid()
+        };
+        // The unrelated letter is newer, so only overlap can rank the similar one first
+        let similar_letter = insert_letter("Platform letter", similar, None, "2025-01-01");
+        let unrelated_letter = insert_letter("Illustrator letter", unrelated, None, "2025-02-01");
+
+        let target_application = seed_application_at(&conn, target, "Applied", "2025-01-01");
+        insert_letter("Already used", target, Some(target_application), "2025-03-01");
+
+        conn.execute(
+            "INSERT INTO artifacts (type, title, content, job_id, created_at, updated_at) VALUES ('Resume', 'Resume', 'Rust Kafka', ?, '2025-03-01', '2025-03-01')",
+            [similar],
+        ).unwrap();
+
+        let candidates = find_similar_cover_letters(&conn, target, 10).unwrap();
+        let ids: Vec<i64> = candidates.iter().map(|c| c.artifact_id).collect();
+        assert_eq!(ids, vec![similar_letter, unrelated_letter]);
+        assert_eq!(candidates[0].source_job_title.as_deref(), Some("Platform Engineer"));
+        assert_eq!(candidates[0].match_count, 2);
+        assert!(candidates[0].similarity.unwrap() > candidates[1].similarity.unwrap());
+        assert!(candidates[0].snippet.starts_with("Dear hiring team"));
+
+        assert_eq!(find_similar_cover_letters(&conn, target, 1).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_next_best_action_overdue_deadline_outranks_stale_application() {
+        use careerbench::next_action::{get_next_best_action, NextActionKind, NextActionTarget};
+
+        let conn = setup_test_db();
+        let today = chrono::NaiveDate::from_ymd_opt(2025, 3, 10).unwrap();
+        let now = today.and_hms_opt(9, 0, 0).unwrap();
+
+        conn.execute(
+            "INSERT INTO jobs (title, company, is_active, date_added, last_updated) VALUES ('Backend Engineer', 'Initech', 1, '2025-01-01', '2025-01-01')",
+            [],
+        ).unwrap();
+        let stale_job = conn.last_insert_rowid();
+        conn.execute(
+            "INSERT INTO applications (job_id, status, priority, date_saved, date_applied, last_activity_date, created_at, updated_at)
+             VALUES (?, 'Applied', 'High', '2025-01-05', '2025-01-05', '2025-01-05', '2025-01-05', '2025-01-05')",
+            [stale_job],
+        ).unwrap();
+
+        let stale = get_next_best_action(&conn, now, today).unwrap();
+        assert_eq!(stale.kind, NextActionKind::StaleApplication);
+
+        conn.execute(
+            "INSERT INTO jobs (title, company, application_deadline, is_active, date_added, last_updated) VALUES ('Platform Engineer', 'Acme', '2025-03-09', 1, '2025-03-01', '2025-03-01')",
+            [],
+        ).unwrap();
+        let deadline_job = conn.last_insert_rowid();
+
+        let action = get_next_best_action(&conn, now, today).unwrap();
+        assert_eq!(action.kind, NextActionKind::ApplicationDeadline);
+        assert_eq!(action.target, Some(NextActionTarget::Job));
+        assert_eq!(action.target_id, Some(deadline_job));
+        assert!(action.reason.contains("passed 1 day ago"));
+        assert!(action.score > stale.score);
+    }
+
+    #[test]
+    fn test_next_best_action_when_nothing_to_do() {
+        use careerbench::next_action::{get_next_best_action, NextActionKind};
+
+        let conn = setup_test_db();
+        let today = chrono::NaiveDate::from_ymd_opt(2025, 3, 10).unwrap();
+        let action = get_next_best_action(&conn, today.and_hms_opt(9, 0, 0).unwrap(), today).unwrap();
+        assert_eq!(action.kind, NextActionKind::AllCaughtUp);
+        assert!(action.title.contains("all caught up"));
+        assert_eq!(action.target_id, None);
+    }
+
+    #[test]
+    fn test_dashboard_activity_by_week_and_month() {
+        let conn = setup_test_db();
+        let job_id = seed_job_at(&conn, "Engineer", None, None);
+        for saved in ["2025-01-06T10:00:00+00:00", "2025-01-08T10:00:00+00:00", "2025-02-15T10:00:00+00:00"] {
+            seed_application_at(&conn, job_id, "Applied", saved);
+        }
+
+        let monthly = build_dashboard_data(&conn, "2025-01-01", "2025-03-31", ActivityGranularity::Month, None).unwrap();
+        let months: Vec<(&str, i64)> = monthly.activity_last_30_days.iter().map(|p| (p.date.as_str(), p.applications_created)).collect();
+        assert_eq!(months, vec![("2025-01", 2), ("2025-02", 1), ("2025-03", 0)]);
+        assert_eq!(monthly.date_range.unwrap().granularity, "month");
+
+        // Jan 1-5 is week 00, the first Monday starts week 01
+        let weekly = build_dashboard_data(&conn, "2025-01-01", "2025-01-14", ActivityGranularity::Week, None).unwrap();
+        let weeks: Vec<(&str, i64)> = weekly.activity_last_30_days.iter().map(|p| (p.date.as_str(), p.applications_created)).collect();
+        assert_eq!(weeks, vec![("2025-00", 0), ("2025-01", 2), ("2025-02", 0)]);
+
+        assert_eq!(ActivityGranularity::parse(None).unwrap(), ActivityGranularity::Day);
+        assert!(ActivityGranularity::parse(Some("fortnight")).is_err());
+    }
+
+    #[test]
+    fn test_dashboard_days_follow_the_given_timezone() {
+        let conn = setup_test_db();
+        let job_id = seed_job_at(&conn, "Engineer", None, None);
+        // 11pm on Jan 10 in New York is already Jan 11 in UTC
+        seed_application_at(&conn, job_id, "Applied", "2025-01-11T04:00:00+00:00");
+
+        let applied_on = |data: &DashboardData| -> Vec<String> {
+            data.activity_last_30_days.iter().filter(|p| p.applications_created > 0).map(|p| p.date.clone()).collect()
+        };
+
+        let utc = build_dashboard_data(&conn, "2025-01-09", "2025-01-10", ActivityGranularity::Day, None).unwrap();
+        assert!(applied_on(&utc).is_empty());
+        assert_eq!(utc.kpis.applications_last_30_days, 0);
+
+        let new_york = parse_dashboard_timezone(Some("America/New_York")).unwrap();
+        let local = build_dashboard_data(&conn, "2025-01-09", "2025-01-10", ActivityGranularity::Day, new_york).unwrap();
+        assert_eq!(applied_on(&local), vec!["2025-01-10".to_string()]);
+        assert_eq!(local.kpis.applications_last_30_days, 1);
+
+        assert!(parse_dashboard_timezone(Some("Mars/Olympus_Mons")).is_err());
+        assert_eq!(parse_dashboard_timezone(None).unwrap(), None);
+    }
+
+    #[test]
+    fn test_dashboard_compare_periods() {
+        let conn = setup_test_db();
+        let job_id = seed_job_at(&conn, "Engineer", None, None);
+        for (status, saved) in [("Applied", "2025-01-10"), ("Interviewing", "2025-02-03"), ("Offer", "2025-02-12"), ("Applied", "2025-02-20")] {
+            seed_application_at(&conn, job_id, status, saved);
+        }
+
+        let mut current = build_dashboard_data(&conn, "2025-02-01", "2025-02-28", ActivityGranularity::Day, None).unwrap();
+        let previous = build_dashboard_data(&conn, "2025-01-01", "2025-01-31", ActivityGranularity::Day, None).unwrap();
+        assert_eq!(current.kpis.applications_last_30_days, 3);
+        assert_eq!(previous.kpis.applications_last_30_days, 1);
+
+        let funnel = |data: &DashboardData| data.funnel.iter().map(|s| s.count).collect::<Vec<_>>();
+        assert_eq!(funnel(&current), vec![3, 2, 1]);
+        assert_eq!(funnel(&previous), vec![1, 0, 0]);
+
+        compare_dashboard_data(&mut current, previous);
+        let delta = |metric: &str| current.deltas.iter().find(|d| d.metric == metric).unwrap();
+        assert_eq!(delta("applications").change, 2);
+        assert_eq!(delta("applications").percent_change, Some(200));
+        let interviewing = delta("funnel:Interviewing");
+        assert_eq!((interviewing.current, interviewing.previous, interviewing.change), (2, 0, 2));
+        assert_eq!(interviewing.percent_change, None);
+        assert_eq!(current.deltas.len(), 3 + current.funnel.len());
+
+        let json = serde_json::to_value(&current).unwrap();
+        assert_eq!(json["comparison"]["kpis"]["applications_last_30_days"], 1);
+        assert!(json["comparison"]["comparison"].is_null());
+        assert_eq!(json["comparison"]["deltas"], json!([]));
+    }
+
+    #[test]
+    fn test_dashboard_custom_funnel_stages() {
+        use careerbench::dashboard_funnel::{save_dashboard_funnel, DashboardFunnelConfig};
+
+        let conn = setup_test_db();
+        let job_id = seed_job_at(&conn, "Engineer", None, None);
+        let mut application_ids = Vec::new();
+        for status in ["Applied", "Interviewing", "Interviewing"] {
+            application_ids.push(seed_application_at(&conn, job_id, status, "2025-02-03"));
+        }
+        let add_event = |application_id: i64, event_type: &str| {
+            conn.execute(
+                "INSERT INTO application_events (application_id, event_type, event_date, created_at) VALUES (?, ?, '2025-02-05', '2025-02-05')",
+                rusqlite::params![application_id, event_type],
+            ).unwrap();
+        };
+        add_event(application_ids[1], "Phone Screen");
+        add_event(application_ids[2], "Phone Screen");
+        add_event(application_ids[2], "Onsite");
+
+        // Without a config the built-in funnel is used
+        let data = build_dashboard_data(&conn, "2025-02-01", "2025-02-28", ActivityGranularity::Day, None).unwrap();
+        let labels: Vec<&str> = data.funnel.iter().map(|s| s.label.as_str()).collect();
+        assert_eq!(labels, vec!["Applied", "Interviewing", "Offer"]);
+
+        save_dashboard_funnel(&conn, &DashboardFunnelConfig {
+            stages: vec!["Applied".to_string(), " Phone Screen ".to_string(), "Onsite".to_string(), "Offer".to_string(), "phone screen".to_string()],
+        }).unwrap();
+        let data = build_dashboard_data(&conn, "2025-02-01", "2025-02-28", ActivityGranularity::Day, None).unwrap();
+        let funnel: Vec<(&str, i64)> = data.funnel.iter().map(|s| (s.label.as_str(), s.count)).collect();
+        assert_eq!(funnel, vec![("Applied", 3), ("Phone Screen", 2), ("Onsite", 1), ("Offer", 0)]);
+    }
+
+    #[test]
+    fn test_job_search_uses_fts_ranking() {
+        let conn = setup_test_db();
+        let insert = |title: &str, description: &str, date_added: &str| {
+            conn.execute(
+                "INSERT INTO jobs (title, company, raw_description, is_active, date_added, last_updated) VALUES (?, 'Acme', ?, 1, ?, ?)",
+                rusqlite::params![title, description, date_added, date_added],
+            ).unwrap();
+            conn.last_insert_rowid()
+        };
+        let passing_mention = insert("Frontend Engineer", "Mostly React; some Rust tooling.", "2025-03-02");
+        let rust_role = insert("Rust Engineer", "Rust services, Rust tooling and more Rust.", "2025-03-01");
+        insert("Designer", "Figma and research.", "2025-03-03");
+
+        let list = list_jobs(&conn, Some("rust".to_string()), None, None, None, None, None, None, None, None).unwrap();
+        assert_eq!(list.total, 2);
+        let ids: Vec<i64> = list.jobs.iter().map(|j| j.id).collect();
+        assert_eq!(ids, vec![rust_role, passing_mention]);
+
+        // Prefix matching, and the index follows updates
+        conn.execute("UPDATE jobs SET title = 'Staff Rustacean' WHERE id = ?", [passing_mention]).unwrap();
+        let list = list_jobs(&conn, Some("Rustacean".to_string()), None, None, None, None, None, None, None, None).unwrap();
+        assert_eq!(list.jobs.iter().map(|j| j.id).collect::<Vec<_>>(), vec![passing_mention]);
+
+        assert_eq!(fts_match_query("C++ \"senior\""), Some("\"senior\"*".to_string()));
+        assert_eq!(symbol_search_terms("C++ \"senior\" .NET"), vec!["C++".to_string(), ".NET".to_string()]);
+        assert_eq!(fts_match_query("   "), None);
+    }
+
+    #[test]
+    fn test_job_search_matches_symbol_terms_literally() {
+        let conn = setup_test_db();
+        let insert = |title: &str, description: &str| {
+            conn.execute(
+                "INSERT INTO jobs (title, company, raw_description, is_active, date_added, last_updated) VALUES (?, 'Acme', ?, 1, '2025-03-01', '2025-03-01')",
+                rusqlite::params![title, description],
+            ).unwrap();
+            conn.last_insert_rowid()
+        };
+        let cpp = insert("Senior Engineer", "Modern C++ and some Python.");
+        insert("Senior Engineer", "Embedded C and CUDA.");
+        let dotnet = insert("Backend Developer", "ASP.NET Core services.");
+        insert("Network Engineer", "Cisco networks.");
+
+        let search = |q: &str| {
+            list_jobs(&conn, Some(q.to_string()), None, None, None, None, None, None, None, None)
+                .unwrap()
+                .jobs
+                .iter()
+                .map(|j| j.id)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(search("C++"), vec![cpp]);
+        assert_eq!(search("c++ senior"), vec![cpp]);
+        assert_eq!(search(".net"), vec![dotnet]);
+        assert!(search("C# senior").is_empty());
+    }
+
+    #[test]
+    fn test_create_job_flags_duplicates() {
+        let conn = setup_test_db();
+        let input = |title: &str, company: &str, url: Option<&str>, force: bool| CreateJobInput {
+            title: Some(title.to_string()),
+            company: Some(company.to_string()),
+            location: None,
+            job_source: None,
+            posting_url: url.map(|u| u.to_string()),
+            raw_description: None,
+            force,
+        };
+
+        let first = create_job_with_conn(
+            &conn,
+            &input("Backend Engineer", "Acme", Some("https://jobs.example.com/posting/42/?utm_source=x"), false),
+        ).unwrap();
+        assert_eq!(first.duplicate_of, None);
+        let first_id = first.job.id;
+
+        // Same URL with different tracking params and a different title
+        let by_url = create_job_with_conn(
+            &conn,
+            &input("Platform Engineer", "Other", Some("https://jobs.example.com/posting/42?utm_medium=email&ref=feed"), false),
+        ).unwrap();
+        assert_eq!(by_url.duplicate_of, first_id);
+        assert_eq!(by_url.job.id, first_id);
+
+        // Params that identify the posting are not stripped
+        let other_posting = create_job_with_conn(
+            &conn,
+            &input("Platform Engineer", "Other", Some("https://jobs.example.com/posting/42?gh_jid=7"), false),
+        ).unwrap();
+        assert_eq!(other_posting.duplicate_of, None);
+
+        // No URL, but the title and company match apart from case and spacing
+        let by_title = create_job_with_conn(&conn, &input("backend  engineer", "ACME", None, false)).unwrap();
+        assert_eq!(by_title.duplicate_of, first_id);
+
+        let forced = create_job_with_conn(&conn, &input("Backend Engineer", "Acme", None, true)).unwrap();
+        assert_eq!(forced.duplicate_of, None);
+        assert_ne!(forced.job.id, first_id);
+
+        let different = create_job_with_conn(&conn, &input("Backend Engineer", "Globex", None, false)).unwrap();
+        assert_eq!(different.duplicate_of, None);
+
+        let count: i64 = conn.query_row("SELECT COUNT(*) FROM jobs", [], |row| row.get(0)).unwrap();
+        assert_eq!(count, 4);
+    }
+
+    #[test]
+    fn test_parsed_salary_is_stored_and_filterable() {
+        let conn = setup_test_db();
+        let ranged = seed_job_at(&conn, "Ranged", None, None);
+        let single = seed_job_at(&conn, "Single figure", None, None);
+        let unpaid = seed_job_at(&conn, "No salary", None, None);
+
+        let parsed = |min: Option<f64>, max: Option<f64>, currency: Option<&str>| -> ParsedJob {
+            serde_json::from_value(json!({
+                "salaryMin": min,
+                "salaryMax": max,
+                "salaryCurrency": currency,
+            }))
+            .unwrap()
+        };
+        let now = "2025-01-01T00:00:00Z";
+        update_job_with_parsed_data(&conn, ranged, &parsed(Some(100000.0), Some(130000.0), Some("usd")), now).unwrap();
+        update_job_with_parsed_data(&conn, single, &parsed(Some(90000.0), None, Some("USD")), now).unwrap();
+        update_job_with_parsed_data(&conn, unpaid, &parsed(None, None, None), now).unwrap();
+
+        let job = load_job(&conn, single).unwrap();
+        assert_eq!((job.salary_min, job.salary_max), (Some(90000.0), Some(90000.0)));
+        let job = load_job(&conn, ranged).unwrap();
+        assert_eq!(job.salary_currency.as_deref(), Some("USD"));
+        let job = load_job(&conn, unpaid).unwrap();
+        assert_eq!((job.salary_min, job.salary_max, job.salary_currency), (None, None, None));
+
+        // Re-parsing a description without pay clears the old range
+        update_job_with_parsed_data(&conn, single, &parsed(None, None, None), now).unwrap();
+        let job = load_job(&conn, single).unwrap();
+        assert_eq!((job.salary_min, job.salary_max, job.salary_currency), (None, None, None));
+        update_job_with_parsed_data(&conn, single, &parsed(Some(90000.0), None, Some("USD")), now).unwrap();
+
+        let list = list_jobs(&conn, None, None, None, None, None, None, None, Some(95000.0), None).unwrap();
+        let ids: Vec<i64> = list.jobs.iter().map(|j| j.id).collect();
+        assert_eq!(ids, vec![ranged]);
+
+        let all = list_jobs(&conn, None, None, None, None, None, None, None, None, None).unwrap();
+        assert_eq!(all.total, 3);
+    }
+
+    #[test]
+    fn test_job_list_filters_by_all_user_tags() {
+        use careerbench::job_tags::{add_job_user_tag, get_all_user_tags, remove_job_user_tag};
+
+        let conn = setup_test_db();
+        let now = "2025-01-01T00:00:00Z";
+        let dream = seed_job_at(&conn, "Dream remote job", None, None);
+        let remote = seed_job_at(&conn, "Remote job", None, None);
+        let untagged = seed_job_at(&conn, "Untagged", None, None);
+
+        add_job_user_tag(&conn, dream, "Dream", now).unwrap();
+        add_job_user_tag(&conn, dream, "remote-only", now).unwrap();
+        assert_eq!(add_job_user_tag(&conn, dream, " dream ", now).unwrap(), vec!["dream", "remote-only"]);
+        add_job_user_tag(&conn, remote, "remote-only", now).unwrap();
+        add_job_user_tag(&conn, remote, "startup", now).unwrap();
+        assert!(add_job_user_tag(&conn, 9999, "dream", now).is_err());
+
+        let tagged = |tags: &[&str]| -> Vec<i64> {
+            let tags = Some(tags.iter().map(|t| t.to_string()).collect());
+            let list = list_jobs(&conn, None, None, None, None, None, None, None, None, tags).unwrap();
+            let mut ids: Vec<i64> = list.jobs.iter().map(|j| j.id).collect();
+            ids.sort();
+            ids
+        };
+        assert_eq!(tagged(&["remote-only"]), vec![dream, remote]);
+        assert_eq!(tagged(&["remote-only", "Dream"]), vec![dream]);
+        assert!(tagged(&["dream", "startup"]).is_empty());
+
+        let all = list_jobs(&conn, None, None, None, None, None, None, None, None, None).unwrap();
+        assert_eq!(all.total, 3);
+        let summary = all.jobs.iter().find(|j| j.id == remote).unwrap();
+        assert_eq!(summary.tags, vec!["remote-only", "startup"]);
+        assert!(all.jobs.iter().find(|j| j.id == untagged).unwrap().tags.is_empty());
+
+        assert_eq!(get_all_user_tags(&conn).unwrap(), vec!["dream", "remote-only", "startup"]);
+        assert_eq!(remove_job_user_tag(&conn, remote, "startup").unwrap(), vec!["remote-only"]);
+        assert_eq!(get_all_user_tags(&conn).unwrap(), vec!["dream", "remote-only"]);
+    }
+
+    #[test]
+    fn test_archive_stale_jobs_skips_recent_and_applied() {
+        let conn = setup_test_db();
+        let add_job = |title: &str, added: &str| -> i64 {
+            conn.execute(
+                "INSERT INTO jobs (title, is_active, date_added, last_updated) VALUES (?, 1, ?, ?)",
+                rusqlite::params![title, added, added],
+            ).unwrap();
+            conn.last_insert_rowid()
+        };
+        let stale = add_job("Old, never applied", "2025-01-02T09:00:00Z");
+        let applied = add_job("Old, applied", "2025-01-02T09:00:00Z");
+        let recent = add_job("Recent", "2025-03-20T09:00:00Z");
+        seed_application_at(&conn, applied, "Applied", "2025-01-03");
+        let now = chrono::DateTime::parse_from_rfc3339("2025-04-01T12:00:00Z").unwrap().with_timezone(&chrono::Utc);
+
+        let preview = archive_stale_jobs_with_conn(&conn, 60, true, now).unwrap();
+        assert!(preview.dry_run);
+        assert_eq!(preview.job_ids, vec![stale]);
+        assert_eq!(preview.archived, 0);
+        let active: i64 = conn.query_row("SELECT COUNT(*) FROM jobs WHERE is_active = 1", [], |row| row.get(0)).unwrap();
+        assert_eq!(active, 3);
+
+        let result = archive_stale_jobs_with_conn(&conn, 60, false, now).unwrap();
+        assert_eq!(result.archived, 1);
+        assert_eq!(result.job_ids, vec![stale]);
+        let is_active = |id: i64| -> i64 {
+            conn.query_row("SELECT is_active FROM jobs WHERE id = ?", [id], |row| row.get(0)).unwrap()
+        };
+        assert_eq!((is_active(stale), is_active(applied), is_active(recent)), (0, 1, 1));
+
+        // Already archived jobs aren't counted again
+        assert_eq!(archive_stale_jobs_with_conn(&conn, 60, false, now).unwrap().archived, 0);
+        assert!(archive_stale_jobs_with_conn(&conn, 0, true, now).is_err());
+    }
+
+    #[test]
+    fn test_bulk_status_update_skips_missing_ids() {
+        let conn = setup_test_db();
+        let job_id = seed_job_at(&conn, "Engineer", None, None);
+        let mut ids = Vec::new();
+        for status in ["Applied", "Interviewing", "Rejected", "Withdrawn"] {
+            ids.push(seed_application_at(&conn, job_id, status, "2025-01-02"));
+        }
+
+        let now = "2025-02-01T10:00:00Z";
+        let result = bulk_update_application_status_with_conn(&conn, &[ids[0], 9999, ids[1], ids[2], ids[3], ids[0]], "Rejected", now).unwrap();
+        assert_eq!(result.updated, vec![ids[0], ids[1]]);
+        assert_eq!(result.unchanged, vec![ids[2]]);
+        assert_eq!(result.not_found, vec![9999]);
+        // Withdrawn is final, so it isn't moved to Rejected
+        assert_eq!(result.not_allowed, vec![ids[3]]);
+
+        let rejected: i64 = conn.query_row("SELECT COUNT(*) FROM applications WHERE status = 'Rejected'", [], |row| row.get(0)).unwrap();
+        assert_eq!(rejected, 3);
+        let events: Vec<(i64, String)> = {
+            let mut stmt = conn.prepare(
+                "SELECT application_id, from_status FROM application_events WHERE event_type = 'StatusChanged' AND to_status = 'Rejected' ORDER BY application_id",
+            ).unwrap();
+            stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?))).unwrap().map(|r| r.unwrap()).collect()
+        };
+        assert_eq!(events, vec![(ids[0], "Applied".to_string()), (ids[1], "Interviewing".to_string())]);
+
+        assert!(bulk_update_application_status_with_conn(&conn, &ids, "  ", now).is_err());
+        assert!(bulk_update_application_status_with_conn(&conn, &ids, "Hired", now).is_err());
+    }
+
+    #[test]
+    fn test_unarchive_application_records_event() {
+        let conn = setup_test_db();
+        let job_id = seed_job_at(&conn, "Engineer", None, None);
+        let id = seed_application_at(&conn, job_id, "Applied", "2025-01-02");
+        conn.execute("UPDATE applications SET archived = 1 WHERE id = ?", [id]).unwrap();
+
+        unarchive_application_with_conn(&conn, id, "2025-02-01T10:00:00Z").unwrap();
+        let (archived, updated_at): (i64, String) = conn
+            .query_row("SELECT archived, updated_at FROM applications WHERE id = ?", [id], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap();
+        assert_eq!(archived, 0);
+        assert_eq!(updated_at, "2025-02-01T10:00:00Z");
+        let events: i64 = conn
+            .query_row("SELECT COUNT(*) FROM application_events WHERE application_id = ? AND event_type = 'Unarchived'", [id], |row| row.get(0))
+            .unwrap();
+        assert_eq!(events, 1);
+
+        let err = unarchive_application_with_conn(&conn, 9999, "2025-02-01T10:00:00Z").unwrap_err();
+        assert!(err.contains("not found"));
+    }
+
+    #[test]
+    fn test_overdue_actions_most_overdue_first() {
+        let conn = setup_test_db();
+        let job_id = seed_job_at(&conn, "Engineer", None, None);
+        let add = |next_action_date: Option<&str>, archived: i64| -> i64 {
+            let id = seed_application_at(&conn, job_id, "Applied", "2025-01-02");
+            conn.execute(
+                "UPDATE applications SET next_action_date = ?, next_action_note = 'Follow up', archived = ? WHERE id = ?",
+                rusqlite::params![next_action_date, archived, id],
+            ).unwrap();
+            id
+        };
+        let slightly = add(Some("2025-03-08"), 0);
+        let very = add(Some("2025-02-20T09:00:00Z"), 0);
+        add(Some("2025-03-10"), 0); // due today
+        add(Some("2025-01-01"), 1); // archived
+        add(None, 0);
+
+        let today = chrono::NaiveDate::from_ymd_opt(2025, 3, 10).unwrap();
+        let overdue = get_overdue_actions_with_conn(&conn, today).unwrap();
+        let found: Vec<(i64, i64)> = overdue.iter().map(|a| (a.application.id, a.days_overdue)).collect();
+        assert_eq!(found, vec![(very, 18), (slightly, 2)]);
+        assert_eq!(overdue[0].application.company.as_deref(), Some("Acme"));
+
+        let json = serde_json::to_value(&overdue[0]).unwrap();
+        assert_eq!(json["job_title"], "Engineer");
+        assert_eq!(json["days_overdue"], 18);
+    }
+
+    #[test]
+    fn test_board_order_within_status_columns() {
+        let conn = setup_test_db();
+        let job_id = seed_job_at(&conn, "Engineer", None, None);
+        let mut ids = Vec::new();
+        for (status, saved) in [("Applied", "2025-01-01"), ("Applied", "2025-01-02"), ("Applied", "2025-01-03"), ("Interviewing", "2025-01-04"), ("Saved", "2025-01-05")] {
+            ids.push(seed_application_at(&conn, job_id, status, saved));
+        }
+        let order = |order_by: Option<&str>| -> Vec<i64> {
+            list_applications(&conn, None, None, None, None, None, order_by.map(|o| o.to_string()))
+                .unwrap()
+                .applications
+                .iter()
+                .map(|a| a.id)
+                .collect()
+        };
+
+        // Columns follow the pipeline, not the alphabet
+        reorder_applications_with_conn(&conn, "Applied", &[ids[1], ids[0], ids[2]]).unwrap();
+        assert_eq!(order(Some("board")), vec![ids[4], ids[1], ids[0], ids[2], ids[3]]);
+        assert_eq!(order(None), vec![ids[4], ids[3], ids[2], ids[1], ids[0]]);
+
+        // Moving a card isn't an edit, so an open edit form doesn't go stale
+        let updated_at: String = conn
+            .query_row("SELECT updated_at FROM applications WHERE id = ?", [ids[1]], |row| row.get(0))
+            .unwrap();
+        assert_eq!(updated_at, "2025-01-02");
+
+        // An ID from another column rolls the whole reorder back
+        assert!(reorder_applications_with_conn(&conn, "Applied", &[ids[2], ids[3]]).is_err());
+        assert_eq!(order(Some("board")), vec![ids[4], ids[1], ids[0], ids[2], ids[3]]);
+
+        assert!(list_applications(&conn, None, None, None, None, None, Some("salary".to_string())).is_err());
+    }
+
+    #[test]
+    fn test_export_applications_csv_escapes_fields() {
+        let conn = setup_test_db();
+        conn.execute(
+            "INSERT INTO jobs (title, company, is_active, date_added, last_updated) VALUES ('Engineer, Platform', 'Acme \"Labs\"', 1, '2025-01-01', '2025-01-01')",
+            [],
+        ).unwrap();
+        let job_id = conn.last_insert_rowid();
+        let applied = seed_application_at(&conn, job_id, "Applied", "2025-01-02");
+        conn.execute(
+            "UPDATE applications SET priority = 'High', notes_summary = 'Spoke to recruiter,\nsending portfolio' WHERE id = ?",
+            [applied],
+        ).unwrap();
+        let rejected = seed_application_at(&conn, job_id, "Rejected", "2025-01-03");
+        conn.execute("UPDATE applications SET archived = 1 WHERE id = ?", [rejected]).unwrap();
+
+        let csv = export_applications_csv_with_conn(&conn, None, None, None).unwrap();
+        assert_eq!(
+            csv,
+            "company,title,status,priority,channel,date_saved,date_applied,last_activity_date,next_action_date,notes_summary\n\
+             \"Acme \"\"Labs\"\"\",\"Engineer, Platform\",Applied,High,,2025-01-02,,,,\"Spoke to recruiter,\nsending portfolio\"\n"
+        );
+
+        // Archived rows are included when asked for, and filters match get_applications
+        let all = export_applications_csv_with_conn(&conn, None, None, Some(false)).unwrap();
+        assert_eq!(all.matches("2025-01-0").count(), 2);
+        let rejected = export_applications_csv_with_conn(&conn, Some("Rejected".to_string()), None, Some(false)).unwrap();
+        assert!(rejected.contains(",Rejected,"));
+        assert!(!rejected.contains(",Applied,"));
+    }
+
+    #[test]
+    fn test_resume_keyword_coverage() {
+        let conn = setup_test_db();
+        let parsed = r#"{"requiredSkills": ["Rust", "SQL APIs"], "niceToHaveSkills": ["Kafka"]}"#;
+        let job_id = seed_job_at(&conn, "Backend Engineer", None, Some(parsed));
+        let unparsed_job_id = seed_job_at(&conn, "Unparsed", None, None);
+        conn.execute(
+            "INSERT INTO artifacts (type, title, content, created_at, updated_at) VALUES ('Resume', 'Resume', '- Built rust services behind SQL API gateways', '2025-01-01', '2025-01-01')",
+            [],
+        ).unwrap();
+        let artifact_id = conn.last_insert_rowid();
+
+        let coverage = resume_keyword_coverage(&conn, artifact_id, job_id).unwrap();
+        assert_eq!(coverage.matched, vec!["Rust".to_string(), "SQL APIs".to_string()]);
+        assert_eq!(coverage.missing, vec!["Kafka".to_string()]);
+        assert!(coverage.missing_required.is_empty());
+        assert_eq!(coverage.coverage_percent, 66.7);
+
+        assert!(resume_keyword_coverage(&conn, artifact_id, unparsed_job_id).unwrap_err().contains("parsed"));
+    }
+
+    #[test]
+    fn test_cover_letter_variants_are_cached_by_options() {
+        use careerbench::ai_cache::compute_input_hash;
+
+        let conn = setup_test_db();
+        let job_id = seed_job_at(&conn, "Backend Engineer", None, None);
+        let job = load_job(&conn, job_id).unwrap();
+        let profile_data = UserProfileData {
+            profile: None,
+            experience: Vec::new(),
+            skills: Vec::new(),
+            education: Vec::new(),
+            certifications: Vec::new(),
+            portfolio: Vec::new(),
+        };
+        let hash_for = |tone: &str| {
+            let options: GenerationOptions = serde_json::from_value(serde_json::json!({ "tone": tone })).unwrap();
+            compute_input_hash(&cover_letter_request_payload(&profile_data, &job, Some(&options), Lang::English)).unwrap()
+        };
+
+        assert_eq!(hash_for("formal"), hash_for("formal"));
+        assert_ne!(hash_for("formal"), hash_for("enthusiastic"));
+        assert_ne!(
+            hash_for("formal"),
+            compute_input_hash(&cover_letter_request_payload(&profile_data, &job, None, Lang::English)).unwrap()
+        );
+        assert_ne!(
+            compute_input_hash(&cover_letter_request_payload(&profile_data, &job, None, Lang::English)).unwrap(),
+            compute_input_hash(&cover_letter_request_payload(&profile_data, &job, None, Lang::German)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_recurring_events_expand_in_calendar_range() {
+        use careerbench::calendar::{event_recurrence, get_calendar_events_with_conn, set_event_recurrence};
+
+        let conn = setup_test_db();
+        let job_id = seed_job_at(&conn, "Backend Engineer", None, None);
+        conn.execute(
+            "INSERT INTO applications (job_id, status, date_saved, archived, created_at, updated_at)
+             VALUES (?, 'Interviewing', '2025-02-01', 0, '2025-02-01', '2025-02-01')",
+            [job_id],
+        ).unwrap();
+        let application_id = conn.last_insert_rowid();
+        let insert_event = |date: &str| {
+            conn.execute(
+                "INSERT INTO application_events (application_id, event_type, event_date, title, created_at)
+                 VALUES (?, 'InterviewScheduled', ?, 'Recruiter check-in', '2025-02-01')",
+                rusqlite::params![application_id, date],
+            ).unwrap();
+            conn.last_insert_rowid()
+        };
+        let weekly = insert_event("2025-03-03T15:00:00Z");
+        insert_event("2025-03-12T10:00:00Z");
+        set_event_recurrence(&conn, application_id, weekly, Some("FREQ=WEEKLY;COUNT=4")).unwrap();
+
+        let events = get_calendar_events_with_conn(&conn, "2025-03-08", "2025-03-31").unwrap();
+        let dates: Vec<&str> = events.iter().map(|e| e.event_date.as_str()).collect();
+        assert_eq!(dates, vec!["2025-03-10T15:00:00Z", "2025-03-12T10:00:00Z", "2025-03-17T15:00:00Z", "2025-03-24T15:00:00Z"]);
+        assert!(events.iter().filter(|e| e.id == Some(weekly)).all(|e| e.recurrence.as_deref() == Some("FREQ=WEEKLY;COUNT=4")));
+
+        set_event_recurrence(&conn, application_id, weekly, None).unwrap();
+        assert_eq!(event_recurrence(&conn, weekly).unwrap(), None);
+        assert!(set_event_recurrence(&conn, application_id + 1, weekly, None).is_err());
+    }
+
+    #[test]
+    fn test_export_calendar_ics() {
+        use careerbench::calendar::{export_calendar_ics_with_conn, set_event_recurrence};
+        use chrono::TimeZone;
+
+        let conn = setup_test_db();
+        let job_id = seed_job_at(&conn, "Backend Engineer", None, None);
+        conn.execute(
+            "INSERT INTO applications (job_id, status, date_saved, next_action_date, next_action_note, archived, created_at, updated_at)
+             VALUES (?, 'Interviewing', '2025-02-01', '2025-03-20', 'Send thank-you', 0, '2025-02-01', '2025-02-01')",
+            [job_id],
+        ).unwrap();
+        let application_id = conn.last_insert_rowid();
+        conn.execute(
+            "INSERT INTO application_events (application_id, event_type, event_date, title, created_at)
+             VALUES (?, 'InterviewScheduled', '2025-03-03T15:00:00Z', 'Recruiter check-in', '2025-02-01')",
+            [application_id],
+        ).unwrap();
+        let event_id = conn.last_insert_rowid();
+        set_event_recurrence(&conn, application_id, event_id, Some("FREQ=WEEKLY;COUNT=4")).unwrap();
+
+        let now = chrono::Utc.with_ymd_and_hms(2025, 3, 1, 9, 0, 0).unwrap();
+        let ics = export_calendar_ics_with_conn(&conn, "2025-03-01", "2025-03-31", Some(10), now).unwrap();
+
+        // The weekly series is written once, plus the next action
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 2);
+        assert!(ics.contains(&format!("UID:careerbench-interview-{}-{}\r\n", application_id, event_id)));
+        assert!(ics.contains(&format!("UID:careerbench-next-action-{}\r\n", application_id)));
+        assert!(ics.contains("DTSTART:20250303T150000Z\r\n"));
+        assert!(ics.contains("RRULE:FREQ=WEEKLY;COUNT=4\r\n"));
+        assert!(ics.contains("DTSTART;VALUE=DATE:20250320\r\n"));
+        assert!(ics.contains("SUMMARY:Recruiter check-in – Backend Engineer at Acme\r\n"));
+        assert_eq!(ics.matches("TRIGGER:-PT10M").count(), 2);
+
+        // Re-exporting gives the same UIDs, and 0 turns the alarms off
+        let again = export_calendar_ics_with_conn(&conn, "2025-03-01", "2025-03-31", Some(0), now).unwrap();
+        assert!(again.contains(&format!("UID:careerbench-interview-{}-{}\r\n", application_id, event_id)));
+        assert!(!again.contains("VALARM"));
+    }
+
+    #[test]
+    fn test_ics_content_uses_requested_timezone() {
+        use careerbench::calendar::generate_ics_content;
+
+        let start = chrono::DateTime::parse_from_rfc3339("2025-07-01T14:00:00Z").unwrap();
+        let end = start + chrono::Duration::minutes(45);
+        let tz: chrono_tz::Tz = "Europe/Berlin".parse().unwrap();
+        let ics = generate_ics_content(1, Some(2), "Onsite, round 2", &start, &end, None, None, None, Some(tz)).unwrap();
+
+        assert!(ics.contains("DTSTART;TZID=Europe/Berlin:20250701T160000\r\n"));
+        assert!(ics.contains("DTEND;TZID=Europe/Berlin:20250701T164500\r\n"));
+        assert!(ics.contains("BEGIN:VTIMEZONE\r\nTZID:Europe/Berlin\r\n"));
+        assert!(ics.contains("TZOFFSETTO:+0200\r\nTZNAME:CEST\r\n"));
+        assert!(ics.contains("SUMMARY:Onsite\\, round 2\r\n"));
+        assert!(ics.contains("UID:careerbench-interview-1-2\r\n"));
+
+        // Without a timezone the times stay floating
+        let floating = generate_ics_content(1, Some(2), "Onsite", &start, &end, None, None, None, None).unwrap();
+        assert!(!floating.contains("VTIMEZONE"));
+        assert!(!floating.contains("TZID"));
+        assert!(floating.lines().any(|line| line.starts_with("DTSTART:") && !line.ends_with('Z')));
+    }
+
+    #[test]
+    fn test_recurring_reminder_rolls_forward_and_snoozes() {
+        use careerbench::reminders::{
+            create_reminder_with_conn, get_due_reminders_with_conn, mark_reminder_sent_with_conn,
+            snooze_reminder_with_conn,
+        };
+        use chrono::NaiveDateTime;
+
+        let conn = setup_test_db();
+        let at = |s: &str| NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").unwrap();
+
+        assert!(create_reminder_with_conn(&conn, None, None, "follow_up", "2025-03-03 09:00:00", None, Some("FREQ=HOURLY")).is_err());
+        assert!(create_reminder_with_conn(&conn, None, None, "follow_up", "next monday", None, Some("FREQ=WEEKLY")).is_err());
+
+        let first = create_reminder_with_conn(
+            &conn, None, None, "follow_up", "2025-03-03 09:00:00", Some("Follow up with Acme"), Some("freq=weekly;count=3"),
+        )
+        .unwrap();
+
+        // Sent on time: the next week's occurrence is created
+        let second = mark_reminder_sent_with_conn(&conn, first, at("2025-03-03 09:05:00")).unwrap().unwrap();
+        let due = get_due_reminders_with_conn(&conn, at("2025-03-10 09:30:00")).unwrap();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].id, Some(second));
+        assert_eq!(due[0].reminder_date, "2025-03-10 09:00:00");
+        assert_eq!(due[0].recurrence.as_deref(), Some("FREQ=WEEKLY;COUNT=2"));
+        assert_eq!(due[0].message.as_deref(), Some("Follow up with Acme"));
+
+        // Marking it sent twice doesn't schedule a second copy
+        let third = mark_reminder_sent_with_conn(&conn, second, at("2025-03-10 09:30:00")).unwrap().unwrap();
+        assert_eq!(mark_reminder_sent_with_conn(&conn, second, at("2025-03-10 09:31:00")).unwrap(), None);
+
+        // Snoozing moves the same row forward, and only forward
+        assert!(snooze_reminder_with_conn(&conn, third, "2025-03-16 09:00:00").is_err());
+        snooze_reminder_with_conn(&conn, third, "2025-03-18T14:00:00").unwrap();
+        assert!(get_due_reminders_with_conn(&conn, at("2025-03-17 12:00:00")).unwrap().is_empty());
+        let due = get_due_reminders_with_conn(&conn, at("2025-03-18 14:00:00")).unwrap();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].id, Some(third));
+        assert!(snooze_reminder_with_conn(&conn, second, "2025-04-01 09:00:00").is_err());
+
+        // COUNT=3 is used up after the third occurrence
+        assert_eq!(mark_reminder_sent_with_conn(&conn, third, at("2025-03-18 14:01:00")).unwrap(), None);
+        let total: i64 = conn.query_row("SELECT COUNT(*) FROM reminders", [], |row| row.get(0)).unwrap();
+        assert_eq!(total, 3);
+    }
+
+    #[test]
+    fn test_recurring_reminder_skips_missed_occurrences() {
+        use careerbench::reminders::{create_reminder_with_conn, get_due_reminders_with_conn, mark_reminder_sent_with_conn};
+        use chrono::NaiveDateTime;
+
+        let conn = setup_test_db();
+        let at = |s: &str| NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").unwrap();
+
+        let id = create_reminder_with_conn(&conn, None, None, "follow_up", "2025-03-03 09:00:00", None, Some("FREQ=DAILY")).unwrap();
+        // The app was closed for a few days; only the next upcoming occurrence is created
+        mark_reminder_sent_with_conn(&conn, id, at("2025-03-06 12:00:00")).unwrap().unwrap();
+        let due = get_due_reminders_with_conn(&conn, at("2025-03-07 09:00:00")).unwrap();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].reminder_date, "2025-03-07 09:00:00");
+    }
+
+    #[test]
+    fn test_status_change_creates_one_follow_up_reminder() {
+        use careerbench::app_settings::set_json_setting;
+        use careerbench::reminders::{
+            create_status_follow_up_reminder, FollowUpRule, FollowUpRules, FOLLOW_UP_RULES_SETTING,
+        };
+        use chrono::NaiveDateTime;
+
+        let conn = setup_test_db();
+        let job_id = seed_job_at(&conn, "Platform Engineer", None, None);
+        let app_id = seed_application_at(&conn, job_id, "Interviewing", "2025-03-01");
+        let now = NaiveDateTime::parse_from_str("2025-03-03 10:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+
+        // Default rules: Interviewing follows up three days later
+        let id = create_status_follow_up_reminder(&conn, app_id, "Applied", "Interviewing", now).unwrap().unwrap();
+        let (date, message): (String, String) = conn.query_row(
+            "SELECT reminder_date, message FROM reminders WHERE id = ?",
+            [id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        ).unwrap();
+        assert_eq!(date, "2025-03-06 10:00:00");
+        assert_eq!(message, "Check in with Acme about next steps for Platform Engineer");
+
+        // The same transition again doesn't add a second reminder
+        assert_eq!(create_status_follow_up_reminder(&conn, app_id, "Applied", "Interviewing", now).unwrap(), None);
+        assert_eq!(create_status_follow_up_reminder(&conn, app_id, "Interviewing", "Rejected", now).unwrap(), None);
+
+        // Configured rules replace the defaults and can be limited to one previous status
+        let rules = FollowUpRules {
+            rules: vec![FollowUpRule {
+                from_status: Some("Interviewing".to_string()),
+                to_status: "Ghosted".to_string(),
+                days_after: 10,
+                message: "Last nudge to {company} ({status})".to_string(),
+            }],
+        };
+        set_json_setting(&conn, FOLLOW_UP_RULES_SETTING, &rules).unwrap();
+        assert_eq!(create_status_follow_up_reminder(&conn, app_id, "Applied", "Ghosted", now).unwrap(), None);
+        let id = create_status_follow_up_reminder(&conn, app_id, "Interviewing", "Ghosted", now).unwrap().unwrap();
+        let message: String = conn.query_row("SELECT message FROM reminders WHERE id = ?", [id], |row| row.get(0)).unwrap();
+        assert_eq!(message, "Last nudge to Acme (Ghosted)");
+
+        // A delay past the date range is an error, not a panic
+        let rules = FollowUpRules {
+            rules: vec![FollowUpRule {
+                from_status: None,
+                to_status: "Offer".to_string(),
+                days_after: u32::MAX,
+                message: "Respond to {company}".to_string(),
+            }],
+        };
+        set_json_setting(&conn, FOLLOW_UP_RULES_SETTING, &rules).unwrap();
+        assert!(create_status_follow_up_reminder(&conn, app_id, "Interviewing", "Offer", now).is_err());
+    }
+
+    #[test]
+    fn test_bulk_status_update_creates_follow_up_reminders() {
+        let conn = setup_test_db();
+        let job_id = seed_job_at(&conn, "Platform Engineer", None, None);
+        let mut ids = Vec::new();
+        for _ in 0..2 {
+            ids.push(seed_application_at(&conn, job_id, "Applied", "2025-01-02"));
+        }
+
+        let result = bulk_update_application_status_with_conn(&conn, &ids, "Interviewing", "2025-02-01T10:00:00Z").unwrap();
+        assert_eq!(result.updated, ids);
+
+        // Default rules: Interviewing follows up three days later
+        let reminders: Vec<(i64, String)> = {
+            let mut stmt = conn.prepare("SELECT application_id, reminder_date FROM reminders ORDER BY application_id").unwrap();
+            stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?))).unwrap().map(|r| r.unwrap()).collect()
+        };
+        assert_eq!(
+            reminders,
+            vec![(ids[0], "2025-02-04 10:00:00".to_string()), (ids[1], "2025-02-04 10:00:00".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_fetched_emails_thread_and_dedupe_by_message_id() {
+        use careerbench::email_sync::{get_sync_cursor, parse_fetched_email, store_fetched_emails, sync_since, SyncCursor};
+        use chrono::{NaiveDate, TimeZone, Utc};
+
+        let conn = setup_test_db();
+        conn.execute(
+            "INSERT INTO email_accounts (email_address, provider) VALUES ('me@example.com', 'gmail')",
+            [],
+        ).unwrap();
+        let account_id = conn.last_insert_rowid();
+        assert_eq!(get_sync_cursor(&conn, account_id).unwrap(), None);
+        conn.execute("UPDATE email_accounts SET imap_uid_validity = 7, imap_last_uid = 42 WHERE id = ?", [account_id]).unwrap();
+        assert_eq!(get_sync_cursor(&conn, account_id).unwrap(), Some(SyncCursor { uid_validity: 7, last_uid: 42 }));
+
+        let invite = parse_fetched_email(
+            b"Message-ID: <invite-1@acme.com>\r\n\
+              From: Jane Recruiter <jane@acme.com>\r\n\
+              To: me@example.com\r\n\
+              Subject: Interview invitation\r\n\
+              Date: Mon, 3 Mar 2025 09:30:00 -0500\r\n\
+              Content-Type: multipart/alternative; boundary=\"b\"\r\n\r\n\
+              --b\r\nContent-Type: text/plain\r\n\r\nCan you talk on Friday?\r\n\
+              --b\r\nContent-Type: text/html\r\n\r\n<p>Can you talk on Friday?</p>\r\n\
+              --b--\r\n",
+            None,
+        ).unwrap();
+        assert_eq!(invite.thread_key, "<invite-1@acme.com>");
+        assert_eq!(invite.body_text.as_deref().map(str::trim), Some("Can you talk on Friday?"));
+        assert!(invite.body_html.as_deref().unwrap().contains("<p>"));
+        assert_eq!(invite.received_date.to_string(), "2025-03-03 14:30:00");
+
+        let reply = parse_fetched_email(
+            b"Message-ID: <reply-2@acme.com>\r\n\
+              In-Reply-To: <reply-1@example.com>\r\n\
+              References: <invite-1@acme.com> <reply-1@example.com>\r\n\
+              From: Sam Scheduler <sam@acme.com>\r\n\
+              Subject: Re: Interview invitation\r\n\
+              Date: Tue, 4 Mar 2025 10:00:00 +0000\r\n\r\n\
+              Friday 2pm works.\r\n",
+            None,
+        ).unwrap();
+        assert_eq!(reply.thread_key, "<invite-1@acme.com>");
+
+        assert_eq!(store_fetched_emails(&conn, account_id, &[invite.clone(), reply.clone()]).unwrap(), 2);
+        // A later sync that overlaps the same day stores nothing twice
+        assert_eq!(store_fetched_emails(&conn, account_id, &[reply, invite]).unwrap(), 0);
+
+        let (count, participants, last): (i64, String, String) = conn.query_row(
+            "SELECT message_count, participants, last_message_date FROM email_threads",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        ).unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(participants, "Jane Recruiter <jane@acme.com>, Sam Scheduler <sam@acme.com>");
+        assert_eq!(last, "2025-03-04 10:00:00");
+
+        let now = Utc.with_ymd_and_hms(2025, 3, 31, 12, 0, 0).unwrap();
+        assert_eq!(sync_since(None, now), NaiveDate::from_ymd_opt(2025, 3, 1).unwrap());
+        assert_eq!(sync_since(Some("2025-03-20T08:00:00+00:00"), now), NaiveDate::from_ymd_opt(2025, 3, 19).unwrap());
+    }
+
+    #[test]
+    fn test_sync_continues_from_the_last_fetched_uid() {
+        use careerbench::email_sync::{next_sync_at, next_sync_cursor, sync_search_query, uids_for_sync, SyncCursor, MAX_MESSAGES_PER_SYNC};
+        use chrono::{NaiveDate, TimeZone, Utc};
+
+        // The oldest messages are fetched first and the rest are left for later
+        let waiting: Vec<u32> = (1..=(MAX_MESSAGES_PER_SYNC as u32 + 20)).rev().collect();
+        let (uids, truncated) = uids_for_sync(waiting, None);
+        assert!(truncated);
+        assert_eq!(uids.len(), MAX_MESSAGES_PER_SYNC);
+        assert_eq!((uids[0], uids[MAX_MESSAGES_PER_SYNC - 1]), (1, MAX_MESSAGES_PER_SYNC as u32));
+        assert_eq!(uids_for_sync(vec![3, 1, 2], None), (vec![1, 2, 3], false));
+        // `n:*` matches the newest message even when it is older than n
+        assert_eq!(uids_for_sync(vec![40], Some(40)), (vec![], false));
+
+        // The next sync continues after the last UID, whatever the messages' Date headers say
+        let since = NaiveDate::from_ymd_opt(2025, 3, 4).unwrap();
+        let cursor = next_sync_cursor(None, Some(7), &uids).unwrap();
+        assert_eq!(cursor, SyncCursor { uid_validity: 7, last_uid: MAX_MESSAGES_PER_SYNC as u32 });
+        assert_eq!(sync_search_query(Some(cursor), Some(7), since), format!("UID {}:*", MAX_MESSAGES_PER_SYNC + 1));
+        assert_eq!(next_sync_cursor(Some(cursor), Some(7), &[]), Some(cursor));
+        // A renumbered mailbox starts over by date
+        assert_eq!(sync_search_query(Some(cursor), Some(8), since), "SINCE 04-Mar-2025");
+        assert_eq!(next_sync_cursor(Some(cursor), Some(8), &[]), None);
+        assert_eq!(next_sync_cursor(Some(cursor), None, &[3]), None);
+
+        // last_sync_at follows the server's arrival times and never passes now
+        let now = Utc.with_ymd_and_hms(2025, 3, 31, 12, 0, 0).unwrap();
+        let arrival = NaiveDate::from_ymd_opt(2025, 3, 5).unwrap().and_hms_opt(16, 0, 0);
+        assert_eq!(next_sync_at(arrival, true, now), "2025-03-05T16:00:00+00:00");
+        let future = NaiveDate::from_ymd_opt(2030, 1, 1).unwrap().and_hms_opt(0, 0, 0);
+        assert_eq!(next_sync_at(future, true, now), now.to_rfc3339());
+        assert_eq!(next_sync_at(arrival, false, now), now.to_rfc3339());
+    }
+
+    #[test]
+    fn test_auto_link_threads_by_contact_and_domain() {
+        use careerbench::email_matching::auto_link_threads;
+
+        let conn = setup_test_db();
+        conn.execute("INSERT INTO email_accounts (email_address, provider) VALUES ('me@example.com', 'gmail')", []).unwrap();
+        let account_id = conn.last_insert_rowid();
+        let acme_job = seed_job_at(&conn, "Platform Engineer", None, None);
+        conn.execute(
+            "INSERT INTO jobs (title, company, is_active, date_added, last_updated) VALUES ('Data Engineer', 'Globex Corporation', 1, '2025-01-01', '2025-01-01')",
+            [],
+        ).unwrap();
+        let globex_job = conn.last_insert_rowid();
+        let mut app_ids = Vec::new();
+        for (job_id, contact) in [(acme_job, None), (globex_job, Some("hank@recruiters.example"))] {
+            conn.execute(
+                "INSERT INTO applications (job_id, status, contact_email, date_saved, created_at, updated_at)
+                 VALUES (?, 'Applied', ?, '2025-03-01', '2025-03-01', '2025-03-01')",
+                rusqlite::params![job_id, contact],
+            ).unwrap();
+            app_ids.push(conn.last_insert_rowid());
+        }
+
+        let add_thread = |key: &str, subject: &str, senders: &[&str]| {
+            conn.execute(
+                "INSERT INTO email_threads (thread_id, subject, created_at, updated_at) VALUES (?, ?, datetime('now'), datetime('now'))",
+                rusqlite::params![key, subject],
+            ).unwrap();
+            let thread_id = conn.last_insert_rowid();
+            for (i, from) in senders.iter().enumerate() {
+                conn.execute(
+                    "INSERT INTO email_messages (thread_id, email_account_id, message_id, from_address, subject, received_date)
+                     VALUES (?, ?, ?, ?, ?, '2025-03-03 10:00:00')",
+                    rusqlite::params![thread_id, account_id, format!("{}-{}", key, i), from, subject],
+                ).unwrap();
+            }
+            thread_id
+        };
+        let acme_thread = add_thread("<a@acme.com>", "Next steps", &["Jane <jane@acme.com>", "Me <me@example.com>"]);
+        let globex_thread = add_thread("<g@recruiters.example>", "Quick chat?", &["hank@recruiters.example"]);
+        // The user's own address must not count towards any match, and a thread
+        // matching nothing isn't offered for review
+        add_thread("<n@example.com>", "Newsletter", &["me@example.com", "news@gmail.com"]);
+        let unclear = add_thread("<p@jobs.example>", "Platform Engineer roles this week", &["digest@jobs.example"]);
+
+        let result = auto_link_threads(&conn).unwrap();
+        let linked: Vec<(i64, i64)> = result.linked.iter().map(|l| (l.thread_id, l.application_id)).collect();
+        assert_eq!(linked, vec![(acme_thread, app_ids[0]), (globex_thread, app_ids[1])]);
+        assert_eq!(result.unlinked.len(), 1);
+        assert_eq!(result.unlinked[0].thread_id, unclear);
+        assert_eq!(result.unlinked[0].suggested_application_id, Some(app_ids[0]));
+        assert!(result.unlinked[0].score > 0);
+
+        let linked_app: Option<i64> = conn.query_row(
+            "SELECT application_id FROM email_threads WHERE id = ?",
+            [acme_thread],
+            |row| row.get(0),
+        ).unwrap();
+        assert_eq!(linked_app, Some(app_ids[0]));
+        // Linked threads aren't considered again
+        let again = auto_link_threads(&conn).unwrap();
+        assert!(again.linked.is_empty());
+        assert_eq!(again.unlinked.len(), 1);
+    }
+
+    #[test]
+    fn test_suggest_status_from_linked_emails() {
+        use careerbench::email_signals::{suggest_status_from_emails, EmailSignal};
+
+        let conn = setup_test_db();
+        conn.execute("INSERT INTO email_accounts (email_address, provider) VALUES ('me@example.com', 'gmail')", []).unwrap();
+        let account_id = conn.last_insert_rowid();
+        let job_id = seed_job_at(&conn, "Platform Engineer", None, None);
+        let app_id = seed_application_at(&conn, job_id, "Interviewing", "2025-03-01");
+        conn.execute(
+            "INSERT INTO email_threads (thread_id, subject, application_id, created_at, updated_at)
+             VALUES ('<t@acme.com>', 'Platform Engineer', ?, datetime('now'), datetime('now'))",
+            [app_id],
+        ).unwrap();
+        let thread_id = conn.last_insert_rowid();
+        for (key, body_text, body_html, date) in [
+            ("invite", Some("We'd like to schedule a video interview with you."), None, "2025-03-02 09:00:00"),
+            ("offer", None, Some("<p>We are <b>pleased to extend</b> you an offer.</p>"), "2025-03-10 09:00:00"),
+            ("thanks", Some("Thanks for your time today!"), None, "2025-03-11 09:00:00"),
+        ] {
+            conn.execute(
+                "INSERT INTO email_messages (thread_id, email_account_id, message_id, subject, body_text, body_html, received_date)
+                 VALUES (?, ?, ?, 'Platform Engineer', ?, ?, ?)",
+                rusqlite::params![thread_id, account_id, key, body_text, body_html, date],
+            ).unwrap();
+        }
+
+        // The invitation matches the current status, so only the offer is suggested
+        let suggestions = suggest_status_from_emails(&conn, app_id).unwrap();
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].signal, EmailSignal::Offer);
+        assert_eq!(suggestions[0].proposed_status, "Offer");
+        assert!(suggestions[0].snippet.contains("pleased to extend"));
+        assert!(suggestions[0].transition_allowed);
+
+        // Nothing is applied, and moves out of a closed status are flagged
+        conn.execute("UPDATE applications SET status = 'Rejected' WHERE id = ?", [app_id]).unwrap();
+        let suggestions = suggest_status_from_emails(&conn, app_id).unwrap();
+        assert_eq!(
+            suggestions.iter().map(|s| (s.proposed_status.as_str(), s.transition_allowed)).collect::<Vec<_>>(),
+            vec![("Offer", false), ("Interviewing", false)]
+        );
+        assert!(suggest_status_from_emails(&conn, app_id + 1).is_err());
+    }
+
+    #[test]
+    fn test_estimate_offer_probability_from_history() {
+        use careerbench::analytics::estimate_offer_probability;
+
+        let conn = setup_test_db();
+        let job_id = seed_job_at(&conn, "Platform Engineer", None, None);
+        let add_app = |status: &str, channel: &str, interviewed: bool| {
+            let id = seed_application_at(&conn, job_id, status, "2025-01-01");
+            conn.execute(
+                "UPDATE applications SET channel = ?, date_applied = '2025-01-01' WHERE id = ?",
+                rusqlite::params![channel, id],
+            ).unwrap();
+            if interviewed {
+                conn.execute(
+                    "INSERT INTO application_events (application_id, event_type, to_status, event_date, created_at)
+                     VALUES (?, 'StatusChanged', 'Interviewing', '2025-01-10', '2025-01-10')",
+                    [id],
+                ).unwrap();
+            }
+            id
+        };
+        add_app("Offer", "Referral", false);
+        add_app("Offer", "Referral", false);
+        add_app("Rejected", "Referral", true);
+        for _ in 0..3 {
+            add_app("Rejected", "LinkedIn", false);
+        }
+        // Pending applications say nothing about the outcome yet
+        add_app("Applied", "LinkedIn", false);
+
+        let target = add_app("Applied", "Referral", false);
+        let estimate = estimate_offer_probability(&conn, target).unwrap();
+        // 3 of 6 resolved applications interviewed and 2 of 3 interviews became offers,
+        // each blended with the priors; all 3 resolved referrals interviewed
+        let interview_rate = (3.0 + 5.0 * 0.15) / 11.0;
+        let offer_rate = (2.0 + 5.0 * 0.25) / 8.0;
+        let channel_factor = ((3.0 + 5.0 * interview_rate) / 8.0) / interview_rate;
+        assert!((estimate.probability - interview_rate * channel_factor * offer_rate).abs() < 1e-9);
+        assert_eq!(
+            estimate.factors.iter().map(|f| (f.name.as_str(), f.sample_size)).collect::<Vec<_>>(),
+            vec![("Application to interview rate", 6), ("Channel", 3), ("Interview to offer rate", 3)]
+        );
+        assert!(estimate.low_confidence);
+
+        let interviewing = add_app("Interviewing", "LinkedIn", false);
+        let estimate = estimate_offer_probability(&conn, interviewing).unwrap();
+        assert!((estimate.probability - offer_rate).abs() < 1e-9);
+
+        let rejected = add_app("Rejected", "LinkedIn", false);
+        assert_eq!(estimate_offer_probability(&conn, rejected).unwrap().probability, 0.0);
+        assert!(estimate_offer_probability(&conn, rejected + 1).is_err());
+    }
+
+    #[test]
+    fn test_response_times_by_channel() {
+        use careerbench::analytics::calculate_response_times;
+
+        let conn = setup_test_db();
+        let job_id = seed_job_at(&conn, "Platform Engineer", None, None);
+        let add_app = |status: &str, channel: Option<&str>, response: Option<(&str, &str)>| {
+            let id = seed_application_at(&conn, job_id, status, "2025-01-01");
+            conn.execute(
+                "UPDATE applications SET channel = ?, date_applied = '2025-01-01' WHERE id = ?",
+                rusqlite::params![channel, id],
+            ).unwrap();
+            if let Some((event_type, date)) = response {
+                conn.execute(
+                    "INSERT INTO application_events (application_id, event_type, to_status, event_date, created_at)
+                     VALUES (?, ?, CASE WHEN ? = 'StatusChanged' THEN 'Interviewing' END, ?, ?)",
+                    rusqlite::params![id, event_type, event_type, date, date],
+                ).unwrap();
+            }
+        };
+        add_app("Interviewing", Some("Referral"), Some(("StatusChanged", "2025-01-03")));
+        add_app("Interviewing", Some("Referral"), Some(("InterviewScheduled", "2025-01-05")));
+        add_app("Offer", Some("LinkedIn"), Some(("InterviewCompleted", "2025-01-11")));
+        add_app("Interviewing", Some("LinkedIn"), Some(("StatusChanged", "2025-01-21")));
+        add_app("Applied", Some("LinkedIn"), None);
+        add_app("Applied", None, None);
+        // Rejections without an interview aren't waiting and have no response time
+        add_app("Rejected", Some("LinkedIn"), None);
+
+        let report = calculate_response_times(&conn).unwrap();
+        assert_eq!(report.overall.sample_size, 4);
+        assert_eq!(report.overall.median_days, Some(7.0));
+        assert_eq!(report.overall.p90_days, Some(20.0));
+        assert_eq!(report.overall.still_waiting, 2);
+
+        let channels: Vec<(&str, Option<f64>, i64, i64)> = report
+            .by_channel
+            .iter()
+            .map(|c| (c.channel.as_str(), c.stats.median_days, c.stats.sample_size, c.stats.still_waiting))
+            .collect();
+        assert_eq!(
+            channels,
+            vec![("LinkedIn", Some(15.0), 2, 1), ("Referral", Some(3.0), 2, 0), ("Unknown", None, 0, 1)]
+        );
+    }
+}
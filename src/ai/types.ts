@@ -3,6 +3,7 @@
 
 export type AiMode = "local" | "cloud" | "hybrid";
 export type CloudProvider = "openai" | "anthropic";
+export type LocalBackend = "embedded" | "ollama";
 
 export interface AiSettings {
   mode: AiMode;
@@ -10,6 +11,26 @@ export interface AiSettings {
   apiKey?: string;
   modelName?: string;
   localModelPath?: string;
+  /** Sampling temperature; the provider default is used when unset */
+  temperature?: number | null;
+  /** Best-effort seed for reproducible output (not honored by Anthropic) */
+  seed?: number | null;
+  /** Cap on generated tokens per request; the provider default is used when unset */
+  maxTokens?: number | null;
+  /** AI requests allowed in flight at once (default 2) */
+  maxConcurrentRequests?: number | null;
+  /** Engine behind local mode; defaults to the bundled GGUF runner */
+  localBackend?: LocalBackend;
+  /** Ollama server URL, http://localhost:11434 when unset */
+  ollamaBaseUrl?: string | null;
+  /** Ollama model tag, e.g. "llama3.1" */
+  ollamaModel?: string | null;
+  /** Mode tried when the main one is rate limited or unreachable */
+  fallbackMode?: AiMode | null;
+  /** Model per purpose (e.g. "job_parse"), used instead of modelName */
+  modelOverrides?: Record<string, string>;
+  /** Cache lifetime in days per purpose, replacing the built-in TTL */
+  cacheTtls?: Record<string, number>;
 }
 
 export interface ResumeInput {
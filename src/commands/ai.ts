@@ -2,6 +2,17 @@
  * AI and Resume Generation command types
  */
 
+/**
+ * Error thrown by the generation commands; `recoveryLabel` is the fix button text
+ */
+export interface UserFacingError {
+  message: string;
+  recoveryAction?: "open_settings" | "retry" | "check_network" | null;
+  recoveryLabel?: string | null;
+  retryable: boolean;
+  retryAfterSeconds?: number | null;
+}
+
 export interface ResumeSection {
   title: string;
   items: ResumeSectionItem[];
@@ -18,6 +29,16 @@ export interface GeneratedResume {
   headline?: string;
   sections: ResumeSection[];
   highlights: string[];
+  contact?: ResumeContact;
+}
+
+export interface ResumeContact {
+  location?: string;
+  email?: string;
+  phone?: string;
+  website?: string;
+  github?: string;
+  linkedin?: string;
 }
 
 export interface GeneratedLetter {
@@ -33,6 +54,22 @@ export interface GenerationOptions {
   length?: string;
   focus?: string;
   audience?: string; // For cover letters
+  date_format?: ResumeDateFormat; // Overrides the saved resume date format
+  include_contact_header?: boolean; // Contact line under the resume headline
+  template?: ResumeTemplate; // Section order and density, defaults to chronological
+  include_certifications?: boolean; // Certifications section from the profile
+  include_portfolio?: boolean; // Portfolio section from the profile
+}
+
+export type ResumeDateFormat = "mon_yyyy" | "mm_yyyy" | "yyyy";
+
+export type ResumeTemplate = "chronological" | "skills-first" | "compact";
+
+export interface KeywordCoverage {
+  matched: string[];
+  missing: string[];
+  missingRequired: string[];
+  coveragePercent: number; // 0-100
 }
 
 export interface ResumeGenerationResult {
@@ -45,6 +82,13 @@ export interface LetterGenerationResult {
   content: string;
 }
 
+/** One letter from generate_cover_letter_variants; exactly one of result/error is set */
+export interface CoverLetterVariant {
+  options: GenerationOptions;
+  result: LetterGenerationResult | null;
+  error: UserFacingError | null;
+}
+
 export interface Artifact {
   id: number;
   jobId?: number;
@@ -84,8 +128,14 @@ export interface AiCommands {
     return: void;
   };
   test_ai_connection: {
-    args: [];
-    return: string;
+    args: [timeoutSecs?: number];
+    return: {
+      ok: boolean;
+      errorKind?: string | null;
+      latencyMs?: number | null;
+      model?: string | null;
+      message: string;
+    };
   };
   check_local_provider_availability: {
     args: [];
@@ -111,6 +161,18 @@ export interface AiCommands {
     args: [jobId: number, applicationId?: number | null, options?: GenerationOptions];
     return: ResumeGenerationResult;
   };
+  list_resume_templates: {
+    args: [];
+    return: ResumeTemplate[];
+  };
+  generate_cover_letter_variants: {
+    args: [jobId: number, variants: GenerationOptions[]];
+    return: CoverLetterVariant[];
+  };
+  analyze_resume_keyword_coverage: {
+    args: [artifactId: number, jobId: number];
+    return: KeywordCoverage;
+  };
   generate_cover_letter_for_job: {
     args: [jobId: number, applicationId?: number | null, options?: GenerationOptions];
     return: LetterGenerationResult;
@@ -83,6 +83,8 @@ export interface UpdateApplicationInput {
   contactLinkedin?: string;
   locationOverride?: string;
   offerCompensation?: string;
+  /** Permit a status change the normal pipeline doesn't allow */
+  allow_override?: boolean;
 }
 
 export interface AddEventInput {
@@ -95,19 +97,55 @@ export interface AddEventInput {
   details?: string;
 }
 
+export interface LowMatchWarning {
+  score: number;
+  matchingSkills: string[];
+  missingSkills: string[];
+}
+
+export interface BulkStatusUpdateResult {
+  updated: number[];
+  unchanged: number[];
+  not_found: number[];
+  not_allowed: number[];
+}
+
+/** ApplicationSummary fields plus the missed next action */
+export interface OverdueAction extends ApplicationSummary {
+  next_action_date: string;
+  next_action_note?: string | null;
+  days_overdue: number;
+}
+
 export interface ApplicationCommands {
   create_application: {
     args: [input: CreateApplicationInput];
-    return: Application;
+    return: Application & { low_match_warning?: LowMatchWarning | null };
   };
   update_application: {
     args: [id: number, input: UpdateApplicationInput];
     return: Application;
   };
+  bulk_update_application_status: {
+    args: [applicationIds: number[], status: ApplicationStatus];
+    return: BulkStatusUpdateResult;
+  };
+  get_overdue_actions: {
+    args: [];
+    return: OverdueAction[];
+  };
   get_applications: {
-    args: [options?: { status?: ApplicationStatus | null; jobId?: number | null; activeOnly?: boolean }];
+    args: [options?: { status?: ApplicationStatus | null; jobId?: number | null; activeOnly?: boolean; orderBy?: "board" | "date_saved" | null }];
     return: ApplicationSummary[];
   };
+  export_applications_csv: {
+    args: [status?: ApplicationStatus | null, jobId?: number | null, activeOnly?: boolean | null];
+    return: string;
+  };
+  reorder_applications: {
+    args: [status: ApplicationStatus, applicationIds: number[]];
+    return: void;
+  };
   get_application_detail: {
     args: [id: number];
     return: ApplicationDetail;
@@ -120,4 +158,8 @@ export interface ApplicationCommands {
     args: [id: number];
     return: Application;
   };
+  unarchive_application: {
+    args: [id: number];
+    return: Application;
+  };
 }
\ No newline at end of file
@@ -2,6 +2,16 @@
  * Cache Management command types
  */
 
+/** One purpose from get_cache_report; sizes include request and response payloads */
+export interface PurposeStat {
+  purpose: string;
+  entry_count: number;
+  total_bytes: number;
+  expired_count: number;
+  oldest_entry?: string | null;
+  newest_entry?: string | null;
+}
+
 export interface CacheStats {
   totalEntries: number;
   totalSizeBytes: number;
@@ -9,6 +19,14 @@ export interface CacheStats {
   expiredEntries: number;
   oldestEntry?: string;
   newestEntry?: string;
+  /** Largest purposes first */
+  by_purpose?: PurposeStat[];
+}
+
+export interface SemanticCacheConfig {
+  enabled: boolean;
+  /** Lowest cosine similarity (0-1] that reuses a cached response */
+  minSimilarity: number;
 }
 
 export interface CacheCommands {
@@ -36,4 +54,16 @@ export interface CacheCommands {
     args: [maxEntries: number];
     return: number; // count of evicted entries
   };
+  get_cache_ttls: {
+    args: [];
+    return: Record<string, number>; // days per purpose
+  };
+  get_semantic_cache_config: {
+    args: [];
+    return: SemanticCacheConfig;
+  };
+  save_semantic_cache_config: {
+    args: [config: SemanticCacheConfig];
+    return: void;
+  };
 }
\ No newline at end of file
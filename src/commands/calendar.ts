@@ -13,6 +13,7 @@ export interface CalendarEvent {
   details?: string;
   nextActionDate?: string;
   nextActionNote?: string;
+  recurrence?: string; // RRULE; recurring events are listed once per occurrence
 }
 
 export interface CalendarCommands {
@@ -20,6 +21,10 @@ export interface CalendarCommands {
     args: [startDate: string, endDate: string];
     return: CalendarEvent[];
   };
+  export_calendar_ics: {
+    args: [startDate: string, endDate: string, alarmMinutesBefore?: number | null];
+    return: string; // ICS content; alarms default to 30 minutes before, 0 turns them off
+  };
   get_events_for_date: {
     args: [date: string];
     return: CalendarEvent[];
@@ -32,7 +37,9 @@ export interface CalendarCommands {
       startTime: string,
       endTime: string | null,
       location: string | null,
-      notes: string | null
+      notes: string | null,
+      recurrence?: string | null, // RRULE, e.g. "FREQ=WEEKLY;COUNT=4"; "" clears it
+      timezone?: string | null // IANA name; floating local time when omitted
     ];
     return: string; // ICS file content or sync confirmation
   };
@@ -34,9 +34,44 @@ export interface DashboardData {
   funnel: FunnelStep[];
 }
 
+export type NextActionKind =
+  | 'OverdueReminder'
+  | 'ApplicationDeadline'
+  | 'FollowUp'
+  | 'StaleApplication'
+  | 'GoalShortfall'
+  | 'AllCaughtUp';
+
+export interface NextAction {
+  kind: NextActionKind;
+  title: string;
+  reason: string;
+  /** What to open when acting on it; null when all caught up */
+  target: 'Application' | 'Job' | 'LearningPlan' | null;
+  targetId: number | null;
+  score: number;
+}
+
+/** Ordered custom funnel stages; empty uses Applied → Interviewing → Offer */
+export interface DashboardFunnelConfig {
+  stages: string[];
+}
+
 export interface DashboardCommands {
   get_dashboard_data: {
     args: [];
     return: DashboardData;
   };
+  get_dashboard_funnel: {
+    args: [];
+    return: DashboardFunnelConfig;
+  };
+  save_dashboard_funnel: {
+    args: [config: DashboardFunnelConfig];
+    return: void;
+  };
+  get_next_best_action: {
+    args: [];
+    return: NextAction;
+  };
 }
\ No newline at end of file
@@ -15,13 +15,13 @@ import type { CalendarCommands } from './calendar';
 import type { CacheCommands } from './cache';
 
 // Export all types for external use
-export type { DashboardCommands, DashboardData, DashboardKpis, StatusBucket, DailyActivityPoint, FunnelStep } from './dashboard';
+export type { DashboardCommands, DashboardData, DashboardKpis, StatusBucket, DailyActivityPoint, FunnelStep, NextAction, NextActionKind, DashboardFunnelConfig } from './dashboard';
 export type { ProfileCommands, UserProfile, UserProfileData, Experience, Skill, Education, Certification, PortfolioItem } from './profile';
 export type { JobCommands, Job, JobSummary, CreateJobInput, UpdateJobInput, ParsedJob } from './jobs';
 export type { ApplicationCommands, Application, ApplicationSummary, ApplicationEvent, ApplicationDetail, ApplicationStatus, CreateApplicationInput, UpdateApplicationInput, AddEventInput } from './applications';
-export type { AiCommands, ResumeSection, ResumeSectionItem, GeneratedResume, GeneratedLetter, GenerationOptions, ResumeGenerationResult, LetterGenerationResult, Artifact, SaveResumeInput, SaveCoverLetterInput } from './ai';
+export type { AiCommands, ResumeSection, ResumeSectionItem, GeneratedResume, GeneratedLetter, GenerationOptions, KeywordCoverage, ResumeGenerationResult, LetterGenerationResult, CoverLetterVariant, Artifact, SaveResumeInput, SaveCoverLetterInput } from './ai';
 export type { CalendarCommands, CalendarEvent } from './calendar';
-export type { CacheCommands, CacheStats } from './cache';
+export type { CacheCommands, CacheStats, PurposeStat, SemanticCacheConfig } from './cache';
 
 // Re-export the remaining types that weren't in command modules
 export interface Reminder {
@@ -34,6 +34,20 @@ export interface Reminder {
   isSent: boolean;
   sentAt?: string;
   createdAt: string;
+  recurrence?: string; // RRULE, e.g. "FREQ=WEEKLY"
+}
+
+// Reminder created automatically when an application moves to toStatus.
+// message may use {company}, {title} and {status}.
+export interface FollowUpRule {
+  fromStatus?: string | null;
+  toStatus: string;
+  daysAfter: number;
+  message: string;
+}
+
+export interface FollowUpRules {
+  rules: FollowUpRule[];
 }
 
 export interface EmailAccount {
@@ -64,6 +78,51 @@ export interface EmailThread {
   updatedAt: string;
 }
 
+export interface ThreadLink {
+  threadId: number;
+  applicationId: number;
+  score: number;
+  reasons: string[];
+}
+
+// Thread left for manual review, with the closest application if any
+export interface UnlinkedThread {
+  threadId: number;
+  subject?: string;
+  suggestedApplicationId?: number;
+  score: number;
+}
+
+export interface AutoLinkResult {
+  linked: ThreadLink[];
+  unlinked: UnlinkedThread[];
+}
+
+export interface EmailSyncResult extends AutoLinkResult {
+  newMessages: number;
+}
+
+export type EmailSignal = "offer" | "rejection" | "interviewInvitation";
+
+// Status change suggested by an email; never applied automatically
+export interface EmailStatusSuggestion {
+  messageId: number;
+  threadId: number;
+  subject?: string;
+  receivedDate: string;
+  signal: EmailSignal;
+  snippet: string;
+  proposedStatus: string;
+  transitionAllowed: boolean;
+}
+
+// Case-insensitive regular expressions for each signal
+export interface EmailSignalPatterns {
+  offer: string[];
+  rejection: string[];
+  interviewInvitation: string[];
+}
+
 export interface EmailMessage {
   id?: number;
   threadId: number;
@@ -198,6 +257,14 @@ export interface CompanyWithStats extends Company {
   applicationCount: number;
 }
 
+export interface PaginatedCompanyList {
+  companies: CompanyWithStats[];
+  total: number;
+  page: number;
+  page_size: number;
+  total_pages: number;
+}
+
 // Additional command interfaces for the remaining commands
 export interface ReminderCommands {
   create_reminder: {
@@ -206,7 +273,8 @@ export interface ReminderCommands {
       eventId: number | null,
       reminderType: string,
       reminderDate: string,
-      message: string | null
+      message: string | null,
+      recurrence: string | null
     ];
     return: number; // reminder ID
   };
@@ -226,6 +294,18 @@ export interface ReminderCommands {
     args: [reminderId: number];
     return: void;
   };
+  snooze_reminder: {
+    args: [reminderId: number, newDate: string];
+    return: void;
+  };
+  get_follow_up_rules: {
+    args: [];
+    return: FollowUpRules;
+  };
+  save_follow_up_rules: {
+    args: [rules: FollowUpRules];
+    return: void;
+  };
   delete_reminder: {
     args: [reminderId: number];
     return: void;
@@ -234,7 +314,7 @@ export interface ReminderCommands {
 
 export interface EmailCommands {
   save_email_account: {
-    args: [account: EmailAccount];
+    args: [account: EmailAccount, password: string | null];
     return: number;
   };
   get_email_accounts: {
@@ -263,7 +343,23 @@ export interface EmailCommands {
   };
   sync_email_account: {
     args: [accountId: number];
-    return: string;
+    return: EmailSyncResult;
+  };
+  auto_link_email_threads: {
+    args: [];
+    return: AutoLinkResult;
+  };
+  suggest_status_from_emails: {
+    args: [applicationId: number];
+    return: EmailStatusSuggestion[];
+  };
+  get_email_signal_patterns: {
+    args: [];
+    return: EmailSignalPatterns;
+  };
+  save_email_signal_patterns: {
+    args: [patterns: EmailSignalPatterns];
+    return: void;
   };
 }
 
@@ -16,6 +16,9 @@ export interface Job {
   isActive: boolean;
   dateAdded: string;
   lastUpdated: string;
+  salaryMin?: number | null;
+  salaryMax?: number | null;
+  salaryCurrency?: string | null;
 }
 
 export interface JobSummary {
@@ -26,6 +29,8 @@ export interface JobSummary {
   seniority?: string;
   domainTags?: string;
   dateAdded: string;
+  /** User tags, alphabetically */
+  tags: string[];
 }
 
 export interface CreateJobInput {
@@ -35,6 +40,17 @@ export interface CreateJobInput {
   jobSource?: string;
   postingUrl?: string;
   rawDescription?: string;
+  /** Insert even when the job looks like one already saved */
+  force?: boolean;
+}
+
+/**
+ * Result of create_job. When the job matched a saved one and force was off,
+ * nothing was inserted and duplicate_of is the existing job's id.
+ */
+export interface CreateJobResult {
+  job: Job;
+  duplicate_of: number | null;
 }
 
 export interface UpdateJobInput {
@@ -59,27 +75,92 @@ export interface ParsedJob {
   domainTags: string[];
   seniorityScore?: number | null;
   remoteFriendly?: boolean | null;
+  salaryMin?: number | null;
+  salaryMax?: number | null;
+  salaryCurrency?: string | null;
+}
+
+export interface ApplyLinkTracking {
+  paramName: string;
+  value?: string | null;
+}
+
+/**
+ * Payload of the "parse-progress" event emitted by parse_all_pending
+ */
+export interface ParseProgress {
+  jobId: number;
+  ok: boolean;
+  error?: string | null;
+  completed: number;
+  total: number;
+}
+
+/**
+ * Result of archive_stale_jobs; in a dry run nothing is archived and job_ids lists
+ * what would be
+ */
+export interface StaleJobArchive {
+  archived: number;
+  job_ids: number[];
+  dry_run: boolean;
 }
 
 export interface JobCommands {
   create_job: {
     args: [input: CreateJobInput];
-    return: Job;
+    return: CreateJobResult;
+  };
+  archive_stale_jobs: {
+    args: [olderThanDays?: number | null, dryRun?: boolean | null];
+    return: StaleJobArchive;
   };
   update_job: {
     args: [id: number, input: UpdateJobInput];
     return: Job;
   };
   get_job_list: {
-    args: [options?: { search?: string | null; activeOnly?: boolean; source?: string | null }];
+    args: [options?: { search?: string | null; activeOnly?: boolean; source?: string | null; minSalary?: number | null; tags?: string[] | null }];
     return: JobSummary[];
   };
   get_job_detail: {
     args: [id: number];
     return: Job;
   };
+  add_job_tag: {
+    args: [jobId: number, tag: string];
+    return: string[];
+  };
+  remove_job_tag: {
+    args: [jobId: number, tag: string];
+    return: string[];
+  };
+  get_all_tags: {
+    args: [];
+    return: string[];
+  };
+  build_apply_url: {
+    args: [jobId: number];
+    return: string;
+  };
+  get_apply_link_tracking: {
+    args: [];
+    return: ApplyLinkTracking;
+  };
+  save_apply_link_tracking: {
+    args: [tracking: ApplyLinkTracking];
+    return: void;
+  };
   parse_job_with_ai: {
     args: [jobId: number];
     return: ParsedJob;
   };
+  get_jobs_needing_parse: {
+    args: [];
+    return: JobSummary[];
+  };
+  parse_all_pending: {
+    args: [];
+    return: ParseProgress[];
+  };
 }
\ No newline at end of file
@@ -14,6 +14,11 @@ export interface UserProfile {
   openToRoles?: string;
   createdAt?: string;
   updatedAt?: string;
+  email?: string;
+  phone?: string;
+  website?: string;
+  github?: string;
+  linkedin?: string;
 }
 
 export interface Experience {
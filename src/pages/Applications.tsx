@@ -1,5 +1,7 @@
 import { useEffect, useState } from "react";
 import { invoke } from "@tauri-apps/api/core";
+import { save } from "@tauri-apps/plugin-dialog";
+import { writeTextFile } from "@tauri-apps/plugin-fs";
 import { InlineEditable } from "../components/InlineEditable";
 import { LoadingSkeleton } from "../components/LoadingSkeleton";
 import { showToast } from "../components/Toast";
@@ -103,6 +105,44 @@ interface PaginatedApplicationList {
   total_pages: number;
 }
 
+/** Text of a rejected `invoke`; Tauri commands reject with the error string itself */
+function errorMessage(err: any): string {
+  return typeof err === "string" ? err : err?.message || String(err ?? "");
+}
+
+/**
+ * Update an application. A status change the pipeline doesn't normally allow is
+ * retried with `allow_override` after the user confirms it's a correction.
+ * Resolves to null when the user declines; rejects with the error message otherwise.
+ */
+async function updateApplicationWithOverride(
+  id: number,
+  input: Record<string, unknown>
+): Promise<Application | null> {
+  try {
+    return await invoke<Application>("update_application", {
+      id,
+      input: { ...input, allow_override: false },
+    });
+  } catch (err: any) {
+    const message = errorMessage(err);
+    if (!message.includes("Use an override")) {
+      throw new Error(message);
+    }
+    if (!confirm(`${message}\n\nChange the status anyway?`)) {
+      return null;
+    }
+    try {
+      return await invoke<Application>("update_application", {
+        id,
+        input: { ...input, allow_override: true },
+      });
+    } catch (retryErr: any) {
+      throw new Error(errorMessage(retryErr));
+    }
+  }
+}
+
 export default function Applications() {
   const [applications, setApplications] = useState<ApplicationSummary[]>([]);
   const [isLoading, setIsLoading] = useState(true);
@@ -120,7 +160,7 @@ export default function Applications() {
   useEffect(() => {
     loadApplications();
     loadAvailableJobs();
-  }, [selectedStatus, currentPage]);
+  }, [selectedStatus, currentPage, viewMode]);
 
   async function loadApplications() {
     setIsLoading(true);
@@ -132,6 +172,7 @@ export default function Applications() {
         activeOnly: true,
         page: currentPage,
         pageSize: pageSize,
+        orderBy: viewMode === "kanban" ? "board" : null,
       });
       setApplications(result.applications);
       setTotal(result.total);
@@ -143,6 +184,26 @@ export default function Applications() {
     }
   }
 
+  async function handleExportCsv() {
+    try {
+      const contents = await invoke<string>("export_applications_csv", {
+        status: selectedStatus === "all" ? null : selectedStatus,
+        jobId: null,
+        activeOnly: true,
+      });
+      const filePath = await save({
+        defaultPath: `careerbench-applications-${new Date().toISOString().split('T')[0]}.csv`,
+        filters: [{ name: "CSV", extensions: ["csv"] }],
+      });
+      if (filePath) {
+        await writeTextFile(filePath, contents);
+        showToast("Applications exported successfully", "success");
+      }
+    } catch (err: any) {
+      showToast(err?.message || "Failed to export applications", "error");
+    }
+  }
+
   function handlePageChange(newPage: number) {
     setCurrentPage(newPage);
     // Scroll to top
@@ -250,6 +311,22 @@ export default function Applications() {
               ))}
             </div>
             
+            <button
+              onClick={handleExportCsv}
+              aria-label="Export applications to CSV"
+              style={{
+                padding: "0.375rem 0.75rem",
+                backgroundColor: "#e5e7eb",
+                color: "#374151",
+                border: "none",
+                borderRadius: "0.375rem",
+                cursor: "pointer",
+                fontSize: "0.875rem"
+              }}
+            >
+              Export CSV
+            </button>
+
             {selectedStatus === "all" && (
               <div style={{ display: "flex", gap: "0.5rem", alignItems: "center" }}>
                 <span style={{ fontSize: "0.875rem", color: "#6b7280" }}>View:</span>
@@ -322,16 +399,16 @@ export default function Applications() {
                               onChange={async (e) => {
                                 e.stopPropagation();
                                 try {
-                                  await invoke<Application>("update_application", {
-                                    id: app.id,
-                                    input: { status: e.target.value as ApplicationStatus },
+                                  const updated = await updateApplicationWithOverride(app.id, {
+                                    status: e.target.value as ApplicationStatus,
                                   });
+                                  if (!updated) return;
                                   loadApplications();
                                   if (selectedApp?.application.id === app.id) {
                                     loadApplicationDetail(app.id);
                                   }
                                 } catch (err: any) {
-                                  showToast(err?.message || "Failed to update status", "error");
+                                  showToast(errorMessage(err) || "Failed to update status", "error");
                                 }
                               }}
                               onClick={(e) => e.stopPropagation()}
@@ -421,16 +498,16 @@ export default function Applications() {
                           onChange={async (e) => {
                             e.stopPropagation();
                             try {
-                              await invoke<Application>("update_application", {
-                                id: app.id,
-                                input: { status: e.target.value as ApplicationStatus },
+                              const updated = await updateApplicationWithOverride(app.id, {
+                                status: e.target.value as ApplicationStatus,
                               });
+                              if (!updated) return;
                               loadApplications();
                               if (selectedApp?.application.id === app.id) {
                                 loadApplicationDetail(app.id);
                               }
                             } catch (err: any) {
-                              alert(err?.message || "Failed to update status");
+                              alert(errorMessage(err) || "Failed to update status");
                             }
                           }}
                           onClick={(e) => e.stopPropagation()}
@@ -525,16 +602,16 @@ export default function Applications() {
                           onChange={async (e) => {
                             e.stopPropagation();
                             try {
-                              await invoke<Application>("update_application", {
-                                id: app.id,
-                                input: { status: e.target.value as ApplicationStatus },
+                              const updated = await updateApplicationWithOverride(app.id, {
+                                status: e.target.value as ApplicationStatus,
                               });
+                              if (!updated) return;
                               loadApplications();
                               if (selectedApp?.application.id === app.id) {
                                 loadApplicationDetail(app.id);
                               }
                             } catch (err: any) {
-                              alert(err?.message || "Failed to update status");
+                              alert(errorMessage(err) || "Failed to update status");
                             }
                           }}
                           onClick={(e) => e.stopPropagation()}
@@ -622,6 +699,13 @@ interface Artifact {
   updated_at: string;
 }
 
+interface KeywordCoverage {
+  matched: string[];
+  missing: string[];
+  missingRequired: string[];
+  coveragePercent: number;
+}
+
 function ApplicationDetailView({
   detail,
   onUpdate,
@@ -718,30 +802,29 @@ function ApplicationDetailView({
   }
 
   async function saveApplication() {
+    if (!detail.application.id) return;
     setIsSaving(true);
     try {
-      const updated = await invoke<Application>("update_application", {
-        id: detail.application.id,
-        input: {
-          status: formData.status,
-          channel: formData.channel,
-          priority: formData.priority,
-          date_applied: formData.date_applied,
-          next_action_date: formData.next_action_date,
-          next_action_note: formData.next_action_note,
-          notes_summary: formData.notes_summary,
-          contact_name: formData.contact_name,
-          contact_email: formData.contact_email,
-          contact_linkedin: formData.contact_linkedin,
-          location_override: formData.location_override,
-          offer_compensation: formData.offer_compensation,
-        },
+      const updated = await updateApplicationWithOverride(detail.application.id, {
+        status: formData.status,
+        channel: formData.channel,
+        priority: formData.priority,
+        date_applied: formData.date_applied,
+        next_action_date: formData.next_action_date,
+        next_action_note: formData.next_action_note,
+        notes_summary: formData.notes_summary,
+        contact_name: formData.contact_name,
+        contact_email: formData.contact_email,
+        contact_linkedin: formData.contact_linkedin,
+        location_override: formData.location_override,
+        offer_compensation: formData.offer_compensation,
       });
+      if (!updated) return;
       setFormData(updated);
       setIsEditing(false);
       onUpdate();
     } catch (err: any) {
-      showToast(err?.message || "Failed to save application", "error");
+      showToast(errorMessage(err) || "Failed to save application", "error");
     } finally {
       setIsSaving(false);
     }
@@ -757,6 +840,15 @@ function ApplicationDetailView({
     }
   }
 
+  async function unarchiveApplication() {
+    try {
+      await invoke<Application>("unarchive_application", { id: detail.application.id });
+      onUpdate();
+    } catch (err: any) {
+      showToast(err?.message || "Failed to unarchive application", "error");
+    }
+  }
+
   return (
     <div className="application-detail-view">
       <div className="detail-header">
@@ -767,9 +859,15 @@ function ApplicationDetailView({
               <button onClick={() => setIsEditing(true)} className="edit-button">
                 Edit
               </button>
-              <button onClick={archiveApplication} className="archive-button">
-                Archive
-              </button>
+              {detail.application.archived ? (
+                <button onClick={unarchiveApplication} className="archive-button">
+                  Unarchive
+                </button>
+              ) : (
+                <button onClick={archiveApplication} className="archive-button">
+                  Archive
+                </button>
+              )}
             </>
           )}
           {isEditing && (
@@ -778,7 +876,7 @@ function ApplicationDetailView({
                 Cancel
               </button>
               <button
-                onClick={saveApplication}
+                onClick={() => saveApplication()}
                 disabled={isSaving}
                 className="save-button"
               >
@@ -1475,6 +1573,7 @@ function ArtifactViewSheet({
   const [isEditing, setIsEditing] = useState(false);
   const [content, setContent] = useState(artifact.content || "");
   const [isSaving, setIsSaving] = useState(false);
+  const [coverage, setCoverage] = useState<KeywordCoverage | null>(null);
 
   // Handle Escape key to close sheet
   useEffect(() => {
@@ -1487,12 +1586,40 @@ function ArtifactViewSheet({
     return () => window.removeEventListener("keydown", handleKeyDown);
   }, [onClose, isEditing]);
 
+  async function exportArtifact(command: string, extension: string, name: string) {
+    try {
+      const path = await save({
+        defaultPath: `${artifact.title}.${extension}`,
+        filters: [{ name, extensions: [extension] }],
+      });
+      if (path) {
+        await invoke<string>(command, { artifactId: artifact.id, path });
+        showToast("Exported", "success");
+      }
+    } catch (err: any) {
+      showToast(err?.message || "Failed to export", "error");
+    }
+  }
+
+  async function checkKeywords() {
+    try {
+      const result = await invoke<KeywordCoverage>("analyze_resume_keyword_coverage", {
+        artifactId: artifact.id,
+        jobId: artifact.job_id,
+      });
+      setCoverage(result);
+    } catch (err: any) {
+      showToast(err?.message || err || "Failed to check keywords", "error");
+    }
+  }
+
   async function saveArtifact() {
     setIsSaving(true);
     try {
       await invoke<Artifact>("update_artifact", {
         id: artifact.id,
         content: content,
+        expectedUpdatedAt: artifact.updated_at,
       });
       setIsEditing(false);
       onUpdate();
@@ -1559,6 +1686,25 @@ function ArtifactViewSheet({
               <pre className="artifact-content-display">{content || "No content"}</pre>
             )}
           </div>
+          {coverage && (
+            <div className="artifact-content-section">
+              <h3>Keyword Coverage: {coverage.coveragePercent}%</h3>
+              <hr className="section-divider" />
+              {coverage.matched.length > 0 && (
+                <p>
+                  <strong>Found:</strong> {coverage.matched.join(", ")}
+                </p>
+              )}
+              {coverage.missing.length > 0 && (
+                <p>
+                  <strong>Missing:</strong>{" "}
+                  {coverage.missing
+                    .map((k) => (coverage.missingRequired.includes(k) ? `${k} (required)` : k))
+                    .join(", ")}
+                </p>
+              )}
+            </div>
+          )}
         </div>
         <div className="sheet-footer">
           {isEditing ? (
@@ -1575,9 +1721,31 @@ function ArtifactViewSheet({
               </button>
             </>
           ) : (
-            <button onClick={onClose} className="save-button">
-              Close
-            </button>
+            <>
+              {artifact.type === "Resume" && artifact.job_id && (
+                <button onClick={checkKeywords} className="cancel-button">
+                  Check Keywords
+                </button>
+              )}
+              {artifact.type === "Resume" && artifact.ai_payload && (
+                <>
+                  <button onClick={() => exportArtifact("export_resume_pdf", "pdf", "PDF")} className="cancel-button">
+                    Export PDF
+                  </button>
+                  <button onClick={() => exportArtifact("export_resume_docx", "docx", "Word")} className="cancel-button">
+                    Export DOCX
+                  </button>
+                </>
+              )}
+              {artifact.type === "CoverLetter" && artifact.ai_payload && (
+                <button onClick={() => exportArtifact("export_cover_letter_docx", "docx", "Word")} className="cancel-button">
+                  Export DOCX
+                </button>
+              )}
+              <button onClick={onClose} className="save-button">
+                Close
+              </button>
+            </>
           )}
         </div>
       </div>
@@ -1629,7 +1797,7 @@ function CreateApplicationSheet({
 
     setIsSaving(true);
     try {
-      await invoke<Application>("create_application", {
+      const created = await invoke<Application & { low_match_warning?: { score: number } | null }>("create_application", {
         input: {
           jobId: formData.job_id,
           status: formData.status,
@@ -1638,6 +1806,9 @@ function CreateApplicationSheet({
         },
       });
       showToast("Application created successfully", "success");
+      if (created.low_match_warning) {
+        showToast(`Heads up: this job is only a ${created.low_match_warning.score}% match for your skills`, "warning");
+      }
       onSuccess();
     } catch (err: any) {
       showToast(err?.message || "Failed to create application", "error");
@@ -97,7 +97,7 @@ export default function Calendar() {
     }
   }
 
-  async function handleCreateReminder(event: CalendarEvent, reminderDate: string, message: string) {
+  async function handleCreateReminder(event: CalendarEvent, reminderDate: string, message: string, recurrence: string | null) {
     try {
       await invoke("create_reminder", {
         applicationId: event.applicationId,
@@ -105,6 +105,7 @@ export default function Calendar() {
         reminderType: event.eventType,
         reminderDate: reminderDate,
         message: message,
+        recurrence: recurrence,
       });
       showToast("Reminder created successfully", "success");
       setShowReminderModal(false);
@@ -156,6 +157,7 @@ export default function Calendar() {
           endTime: endTime,
           location: null,
           notes: event.details || undefined,
+          timezone: Intl.DateTimeFormat().resolvedOptions().timeZone,
         });
 
         const fileName = `interview-${event.company || "event"}-${new Date(event.eventDate).toISOString().split('T')[0]}.ics`;
@@ -179,6 +181,26 @@ export default function Calendar() {
     }
   }
 
+  async function handleExportMonth() {
+    try {
+      const icsContent = await invoke<string>("export_calendar_ics", {
+        startDate: startDateStr,
+        endDate: endDateStr,
+        alarmMinutesBefore: null,
+      });
+      const filePath = await save({
+        defaultPath: `careerbench-${startDateStr.slice(0, 7)}.ics`,
+        filters: [{ name: "ICS", extensions: ["ics"] }],
+      });
+      if (filePath) {
+        await writeTextFile(filePath, icsContent);
+        showToast("Calendar exported successfully", "success");
+      }
+    } catch (err: any) {
+      showToast(err?.message || "Failed to export calendar", "error");
+    }
+  }
+
   const monthNames = [
     "January", "February", "March", "April", "May", "June",
     "July", "August", "September", "October", "November", "December"
@@ -208,6 +230,9 @@ export default function Calendar() {
           <button onClick={handleToday}>Today</button>
           <button onClick={handleNextMonth} aria-label="Next month">→</button>
           <h2>{monthNames[month]} {year}</h2>
+          <button onClick={handleExportMonth} title="Export this month's events as an ICS file">
+            Export ICS
+          </button>
         </div>
       </div>
 
@@ -312,6 +337,9 @@ export default function Calendar() {
                     {event.details && (
                       <p className="event-details">{event.details}</p>
                     )}
+                    {event.recurrence && (
+                      <p className="event-note">🔁 Repeats ({event.recurrence})</p>
+                    )}
                     {event.nextActionNote && (
                       <p className="event-note">📝 {event.nextActionNote}</p>
                     )}
@@ -350,11 +378,12 @@ function ReminderModal({
 }: {
   event: CalendarEvent;
   onClose: () => void;
-  onCreate: (event: CalendarEvent, reminderDate: string, message: string) => void;
+  onCreate: (event: CalendarEvent, reminderDate: string, message: string, recurrence: string | null) => void;
 }) {
   const [reminderDate, setReminderDate] = useState("");
   const [reminderTime, setReminderTime] = useState("");
   const [message, setMessage] = useState("");
+  const [recurrence, setRecurrence] = useState("");
 
   useEffect(() => {
     // Default to 1 hour before event
@@ -368,7 +397,7 @@ function ReminderModal({
   function handleSubmit(e: React.FormEvent) {
     e.preventDefault();
     const reminderDateTime = `${reminderDate}T${reminderTime}:00`;
-    onCreate(event, reminderDateTime, message);
+    onCreate(event, reminderDateTime, message, recurrence || null);
   }
 
   return (
@@ -413,6 +442,20 @@ function ReminderModal({
               required
             />
           </div>
+          <div className="form-group">
+            <label htmlFor="reminder-recurrence">Repeat</label>
+            <select
+              id="reminder-recurrence"
+              value={recurrence}
+              onChange={(e) => setRecurrence(e.target.value)}
+            >
+              <option value="">Does not repeat</option>
+              <option value="FREQ=DAILY">Daily</option>
+              <option value="FREQ=WEEKLY">Weekly</option>
+              <option value="FREQ=WEEKLY;INTERVAL=2">Every 2 weeks</option>
+              <option value="FREQ=MONTHLY">Monthly</option>
+            </select>
+          </div>
           <div className="modal-actions">
             <button type="button" onClick={onClose}>Cancel</button>
             <button type="submit">Create Reminder</button>
@@ -2,7 +2,7 @@ import { useEffect, useState } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { LoadingSkeleton } from "../components/LoadingSkeleton";
 import { showToast } from "../components/Toast";
-import type { Company, CompanyWithStats } from "../commands/types";
+import type { Company, CompanyWithStats, PaginatedCompanyList } from "../commands/types";
 import "./Companies.css";
 
 export default function Companies() {
@@ -12,18 +12,26 @@ export default function Companies() {
   const [showCompanyModal, setShowCompanyModal] = useState(false);
   const [editingCompany, setEditingCompany] = useState<Company | null>(null);
   const [searchQuery, setSearchQuery] = useState("");
+  const [currentPage, setCurrentPage] = useState(1);
+  const [pageSize] = useState(50);
+  const [totalPages, setTotalPages] = useState(1);
+  const [total, setTotal] = useState(0);
 
   useEffect(() => {
     loadCompanies();
-  }, [searchQuery]);
+  }, [searchQuery, currentPage]);
 
   async function loadCompanies() {
     setIsLoading(true);
     try {
-      const result = await invoke<CompanyWithStats[]>("get_companies_with_stats", {
+      const result = await invoke<PaginatedCompanyList>("get_companies_with_stats", {
         searchQuery: searchQuery || null,
+        page: currentPage,
+        pageSize: pageSize,
       });
-      setCompanies(result);
+      setCompanies(result.companies);
+      setTotal(result.total);
+      setTotalPages(result.total_pages);
     } catch (err: any) {
       showToast(err?.message || "Failed to load companies", "error");
     } finally {
@@ -95,14 +103,17 @@ export default function Companies() {
           type="text"
           placeholder="Search companies..."
           value={searchQuery}
-          onChange={(e) => setSearchQuery(e.target.value)}
+          onChange={(e) => {
+            setSearchQuery(e.target.value);
+            setCurrentPage(1);
+          }}
           className="search-input"
         />
       </div>
 
       <div className="companies-content">
         <div className="companies-list">
-          <h2>Companies ({companies.length})</h2>
+          <h2>Companies ({total})</h2>
           {companies.length === 0 ? (
             <div className="empty-state">
               <p>No companies found. Create your first company.</p>
@@ -155,6 +166,27 @@ export default function Companies() {
               </div>
             ))
           )}
+          {totalPages > 1 && (
+            <div className="pagination">
+              <button
+                onClick={() => setCurrentPage(currentPage - 1)}
+                disabled={currentPage === 1}
+                aria-label="Previous page"
+              >
+                Previous
+              </button>
+              <span className="pagination-info">
+                Page {currentPage} of {totalPages} ({total} total)
+              </span>
+              <button
+                onClick={() => setCurrentPage(currentPage + 1)}
+                disabled={currentPage >= totalPages}
+                aria-label="Next page"
+              >
+                Next
+              </button>
+            </div>
+          )}
         </div>
 
         {selectedCompany && (
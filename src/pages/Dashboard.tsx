@@ -43,9 +43,12 @@ interface FunnelStep {
   count: number;
 }
 
+type Granularity = "day" | "week" | "month";
+
 interface DateRange {
   start_date: string;
   end_date: string;
+  granularity: Granularity;
 }
 
 interface DashboardData {
@@ -54,6 +57,17 @@ interface DashboardData {
   activity_last_30_days: DailyActivityPoint[];
   funnel: FunnelStep[];
   date_range?: DateRange;
+  comparison?: DashboardData | null;
+  deltas?: MetricDelta[];
+}
+
+/** Change in one windowed figure since the comparison period */
+interface MetricDelta {
+  metric: string;
+  current: number;
+  previous: number;
+  change: number;
+  percent_change: number | null;
 }
 
 interface ConversionRates {
@@ -93,6 +107,45 @@ interface Insight {
   actionable: boolean;
 }
 
+// The window of the same length ending the day before `start`
+function previousPeriod(start: string, end: string): DateRange | null {
+  if (!start || !end) {
+    return null;
+  }
+  const day = 24 * 60 * 60 * 1000;
+  const startMs = Date.parse(`${start}T00:00:00Z`);
+  const length = Date.parse(`${end}T00:00:00Z`) - startMs;
+  const toDate = (ms: number) => new Date(ms).toISOString().split('T')[0];
+  return {
+    start_date: toDate(startMs - day - length),
+    end_date: toDate(startMs - day),
+    granularity: "day",
+  };
+}
+
+function formatDelta(delta: MetricDelta): string {
+  if (delta.percent_change === null) {
+    return delta.change === 0 ? "No change" : `+${delta.change}`;
+  }
+  return `${delta.percent_change >= 0 ? "+" : ""}${delta.percent_change}%`;
+}
+
+function findDelta(data: DashboardData, metric: string): MetricDelta | undefined {
+  return data.deltas?.find((delta) => delta.metric === metric);
+}
+
+// Activity labels are "YYYY-MM-DD", "YYYY-WW" (week of year) or "YYYY-MM"
+function formatActivityLabel(label: string, granularity: Granularity): string {
+  if (granularity === "week") {
+    const [year, week] = label.split("-");
+    return `Wk ${week} ${year}`;
+  }
+  if (granularity === "month") {
+    return new Date(`${label}-01T00:00:00`).toLocaleDateString('en-US', { month: 'short', year: 'numeric' });
+  }
+  return new Date(label).toLocaleDateString('en-US', { month: 'short', day: 'numeric' });
+}
+
 export default function Dashboard() {
   const [data, setData] = useState<DashboardData | null>(null);
   const [isLoading, setIsLoading] = useState(true);
@@ -110,6 +163,8 @@ export default function Dashboard() {
   const [endDate, setEndDate] = useState<string>(() => {
     return new Date().toISOString().split('T')[0];
   });
+  const [granularity, setGranularity] = useState<Granularity>("day");
+  const [compareToPrevious, setCompareToPrevious] = useState(false);
 
   async function loadDashboard() {
     setIsLoading(true);
@@ -125,6 +180,9 @@ export default function Dashboard() {
         const result = await tauriInvoke<DashboardData>("get_dashboard_data", {
           startDate: startDate || null,
           endDate: endDate || null,
+          granularity,
+          timezone: Intl.DateTimeFormat().resolvedOptions().timeZone,
+          compareTo: compareToPrevious ? previousPeriod(startDate, endDate) : null,
         });
         setData(result);
         setIsLoading(false);
@@ -151,23 +209,30 @@ export default function Dashboard() {
     }
   }
 
-  async function handleExport() {
+  async function handleExport(format: "csv" | "json" = "csv") {
     try {
-      const csv = await tauriInvoke<string>("export_dashboard_data", {
-        startDate: startDate || null,
-        endDate: endDate || null,
-      });
+      const contents = format === "json"
+        ? await tauriInvoke<string>("export_dashboard_json", {
+            startDate: startDate || null,
+            endDate: endDate || null,
+            granularity,
+            timezone: Intl.DateTimeFormat().resolvedOptions().timeZone,
+          })
+        : await tauriInvoke<string>("export_dashboard_data", {
+            startDate: startDate || null,
+            endDate: endDate || null,
+          });
 
       const filePath = await save({
-        defaultPath: `careerbench-dashboard-${new Date().toISOString().split('T')[0]}.csv`,
+        defaultPath: `careerbench-dashboard-${new Date().toISOString().split('T')[0]}.${format}`,
         filters: [{
-          name: 'CSV',
-          extensions: ['csv']
+          name: format.toUpperCase(),
+          extensions: [format]
         }]
       });
 
       if (filePath) {
-        await writeTextFile(filePath, csv);
+        await writeTextFile(filePath, contents);
         showToast("Dashboard data exported successfully", "success");
       }
     } catch (err: any) {
@@ -220,7 +285,7 @@ export default function Dashboard() {
       loadAnalytics();
     }, 500);
     return () => clearTimeout(timer);
-  }, [startDate, endDate]);
+  }, [startDate, endDate, granularity, compareToPrevious]);
 
   if (isLoading) {
     return (
@@ -281,6 +346,25 @@ export default function Dashboard() {
               min={startDate}
               max={new Date().toISOString().split('T')[0]}
             />
+            <label htmlFor="granularity">Group by:</label>
+            <select
+              id="granularity"
+              value={granularity}
+              onChange={(e) => setGranularity(e.target.value as Granularity)}
+            >
+              <option value="day">Day</option>
+              <option value="week">Week</option>
+              <option value="month">Month</option>
+            </select>
+            <label htmlFor="compare-previous">
+              <input
+                id="compare-previous"
+                type="checkbox"
+                checked={compareToPrevious}
+                onChange={(e) => setCompareToPrevious(e.target.checked)}
+              />
+              Compare to previous period
+            </label>
           </div>
           <button 
             onClick={loadDashboard}
@@ -289,12 +373,19 @@ export default function Dashboard() {
             Reload
           </button>
           <button 
-            onClick={handleExport}
+            onClick={() => handleExport("csv")}
             aria-label="Export dashboard data"
             className="export-button"
           >
             Export CSV
           </button>
+          <button 
+            onClick={() => handleExport("json")}
+            aria-label="Export dashboard data as JSON"
+            className="export-button"
+          >
+            Export JSON
+          </button>
         </div>
       </div>
 
@@ -306,6 +397,11 @@ export default function Dashboard() {
         <div className="kpi-card">
           <div className="kpi-value">{data.kpis.applications_last_30_days}</div>
           <div className="kpi-label">Applications (Date Range)</div>
+          {findDelta(data, "applications") && (
+            <div className="kpi-delta">
+              {formatDelta(findDelta(data, "applications")!)} vs previous period
+            </div>
+          )}
         </div>
         <div className="kpi-card">
           <div className="kpi-value">{data.kpis.offers_received}</div>
@@ -348,6 +444,18 @@ export default function Dashboard() {
               <Bar dataKey="count" fill="#8b5cf6" radius={[0, 4, 4, 0]} />
             </BarChart>
           </ResponsiveContainer>
+          {data.comparison && (
+            <div className="kpi-delta">
+              {data.funnel
+                .map((step) => {
+                  const delta = findDelta(data, `funnel:${step.label}`);
+                  return delta ? `${step.label} ${formatDelta(delta)}` : null;
+                })
+                .filter(Boolean)
+                .join(" · ")}{" "}
+              vs previous period
+            </div>
+          )}
         </div>
       </div>
 
@@ -356,7 +464,7 @@ export default function Dashboard() {
         <ResponsiveContainer width="100%" height={300}>
           <LineChart data={data.activity_last_30_days.map(point => ({
             ...point,
-            date: new Date(point.date).toLocaleDateString('en-US', { month: 'short', day: 'numeric' })
+            date: formatActivityLabel(point.date, data.date_range?.granularity ?? "day")
           }))}>
             <CartesianGrid strokeDasharray="3 3" />
             <XAxis 
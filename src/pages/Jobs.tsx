@@ -6,6 +6,7 @@ import { LoadingSkeleton } from "../components/LoadingSkeleton";
 import { showToast } from "../components/Toast";
 import { ProgressIndicator, ProgressStep } from "../components/ProgressIndicator";
 import { formatErrorForUser, formatErrorWithSuggestions } from "../utils/errorUtils";
+import type { UserFacingError } from "../commands/ai";
 import "./Jobs.css";
 
 interface JobSummary {
@@ -687,16 +688,22 @@ function AddJobSheet({
 
     setIsSaving(true);
     try {
-      await invoke<Job>("create_job", {
-        input: {
-          title: formData.title || null,
-          company: formData.company || null,
-          location: formData.location || null,
-          job_source: formData.job_source || null,
-          posting_url: formData.posting_url || null,
-          raw_description: formData.raw_description || null,
-        },
-      });
+      const input = {
+        title: formData.title || null,
+        company: formData.company || null,
+        location: formData.location || null,
+        job_source: formData.job_source || null,
+        posting_url: formData.posting_url || null,
+        raw_description: formData.raw_description || null,
+      };
+      const result = await invoke<{ job: Job; duplicate_of: number | null }>("create_job", { input });
+      if (result.duplicate_of !== null) {
+        const existing = [result.job.title, result.job.company].filter(Boolean).join(" at ") || "a saved job";
+        if (!confirm(`This looks like ${existing}, which is already saved. Add it anyway?`)) {
+          return;
+        }
+        await invoke("create_job", { input: { ...input, force: true } });
+      }
       showToast("Job created successfully", "success");
       onSuccess();
     } catch (err: any) {
@@ -856,6 +863,9 @@ function GenerateResumeSheet({
     length: "standard",
     focus: "IC",
     audience: "hiring_manager",
+    template: "chronological",
+    includeCertifications: false,
+    includePortfolio: false,
   });
   const [isGenerating, setIsGenerating] = useState(false);
   const [generationProgress, setGenerationProgress] = useState<string>("");
@@ -865,6 +875,8 @@ function GenerateResumeSheet({
   const [resumeContent, setResumeContent] = useState<string>("");
   const [letterContent, setLetterContent] = useState<string>("");
   const [error, setError] = useState<string | null>(null);
+  // Fix offered by the backend for the last generation error
+  const [errorRecovery, setErrorRecovery] = useState<UserFacingError | null>(null);
   const [aiSettings, setAiSettings] = useState<any>(null);
   const [isLocalAvailable, setIsLocalAvailable] = useState<boolean>(false);
   const [resumeName, setResumeName] = useState<string>("");
@@ -916,6 +928,7 @@ function GenerateResumeSheet({
   async function handleGenerate() {
     setIsGenerating(true);
     setError(null);
+    setErrorRecovery(null);
     setGenerationProgress("");
     
     // Set up progress steps based on artifact type
@@ -944,6 +957,9 @@ function GenerateResumeSheet({
             tone: options.tone,
             length: options.length,
             focus: options.focus,
+            template: options.template,
+            include_certifications: options.includeCertifications,
+            include_portfolio: options.includePortfolio,
           },
         });
         setGeneratedResume(result.resume);
@@ -1000,6 +1016,7 @@ function GenerateResumeSheet({
       
       // Set error with suggestions
       setError(formatErrorWithSuggestions(errorInfo));
+      setErrorRecovery(err?.recoveryAction ? (err as UserFacingError) : null);
       
       // Show toast with short message
       showToast(errorInfo.message, "error");
@@ -1116,7 +1133,32 @@ function GenerateResumeSheet({
                     </ul>
                   </div>
                 )}
-                {errorInfo.requiresAction && (
+                {errorRecovery?.recoveryAction && (
+                  <div style={{ marginTop: "0.75rem" }}>
+                    <button
+                      onClick={(e) => {
+                        e.preventDefault();
+                        if (errorRecovery.recoveryAction === "open_settings") {
+                          navigate("/settings");
+                        } else {
+                          // Retry, or try again once the connection is back
+                          handleGenerate();
+                        }
+                      }}
+                      className="btn-primary"
+                      disabled={isGenerating}
+                      style={{ fontSize: "0.875rem", padding: "0.375rem 0.75rem" }}
+                    >
+                      {errorRecovery.recoveryLabel || "Retry"}
+                    </button>
+                    {errorRecovery.retryAfterSeconds != null && (
+                      <span style={{ marginLeft: "0.5rem", fontSize: "0.875rem" }}>
+                        Try again in about {errorRecovery.retryAfterSeconds}s
+                      </span>
+                    )}
+                  </div>
+                )}
+                {!errorRecovery?.recoveryAction && errorInfo.requiresAction && (
                   <div style={{ marginTop: "0.75rem" }}>
                     <button
                       onClick={(e) => {
@@ -1131,7 +1173,10 @@ function GenerateResumeSheet({
                   </div>
                 )}
                 <button 
-                  onClick={() => setError(null)} 
+                  onClick={() => {
+                    setError(null);
+                    setErrorRecovery(null);
+                  }} 
                   style={{ 
                     position: "absolute", 
                     top: "0.5rem", 
@@ -1259,6 +1304,47 @@ function GenerateResumeSheet({
                   </select>
                 </div>
 
+                {(artifactType === "resume" || artifactType === "both") && (
+                  <div className="form-group">
+                    <label>Template</label>
+                    <select
+                      value={options.template}
+                      onChange={(e) =>
+                        setOptions({ ...options, template: e.target.value })
+                      }
+                    >
+                      <option value="chronological">Chronological</option>
+                      <option value="skills-first">Skills First</option>
+                      <option value="compact">Compact</option>
+                    </select>
+                  </div>
+                )}
+
+                {(artifactType === "resume" || artifactType === "both") && (
+                  <div className="form-group">
+                    <label className="checkbox-label">
+                      <input
+                        type="checkbox"
+                        checked={options.includeCertifications}
+                        onChange={(e) =>
+                          setOptions({ ...options, includeCertifications: e.target.checked })
+                        }
+                      />
+                      Include certifications
+                    </label>
+                    <label className="checkbox-label">
+                      <input
+                        type="checkbox"
+                        checked={options.includePortfolio}
+                        onChange={(e) =>
+                          setOptions({ ...options, includePortfolio: e.target.checked })
+                        }
+                      />
+                      Include portfolio
+                    </label>
+                  </div>
+                )}
+
                 {(artifactType === "cover_letter" || artifactType === "both") && (
                   <div className="form-group">
                     <label>Audience</label>
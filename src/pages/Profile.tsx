@@ -18,6 +18,11 @@ interface UserProfile {
   current_company?: string;
   seniority?: string;
   open_to_roles?: string;
+  email?: string;
+  phone?: string;
+  website?: string;
+  github?: string;
+  linkedin?: string;
 }
 
 interface Experience {
@@ -576,6 +581,23 @@ function BasicInfoSection({
           />
         </div>
 
+        {([
+          ["email", "Email", "you@example.com"],
+          ["phone", "Phone", "+1 555 010 0000"],
+          ["website", "Website", "yourname.dev"],
+          ["github", "GitHub", "github.com/yourname"],
+          ["linkedin", "LinkedIn", "linkedin.com/in/yourname"],
+        ] as const).map(([field, label, placeholder]) => (
+          <div className="form-group" key={field}>
+            <label>{label} (optional)</label>
+            <InlineEditable
+              value={profile?.[field] || ""}
+              onSave={(newValue) => onUpdate(field, newValue)}
+              placeholder={placeholder}
+            />
+          </div>
+        ))}
+
         <div className="form-group">
           <label>Pronouns (optional)</label>
           <input
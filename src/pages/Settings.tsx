@@ -1,7 +1,7 @@
 import { useEffect, useState } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import type { AiSettings, CloudProvider } from "../ai/types";
-import type { EmailAccount, CacheStats } from "../commands/types";
+import type { EmailAccount, EmailSyncResult, CacheStats, SemanticCacheConfig } from "../commands/types";
 import { LoadingSkeleton } from "../components/LoadingSkeleton";
 import { showToast } from "../components/Toast";
 import "./Settings.css";
@@ -14,6 +14,8 @@ export default function Settings() {
   const [error, setError] = useState<string | null>(null);
   const [emailAccounts, setEmailAccounts] = useState<EmailAccount[]>([]);
   const [showEmailForm, setShowEmailForm] = useState(false);
+  const [emailPassword, setEmailPassword] = useState("");
+  const [syncingAccountId, setSyncingAccountId] = useState<number | null>(null);
   const [emailFormData, setEmailFormData] = useState<Partial<EmailAccount>>({
     emailAddress: "",
     provider: "gmail",
@@ -22,6 +24,8 @@ export default function Settings() {
   });
   const [cacheStats, setCacheStats] = useState<CacheStats | null>(null);
   const [isLoadingCacheStats, setIsLoadingCacheStats] = useState(false);
+  const [cacheTtls, setCacheTtls] = useState<Record<string, number>>({});
+  const [semanticCache, setSemanticCache] = useState<SemanticCacheConfig>({ enabled: false, minSimilarity: 0.97 });
   const [isDownloadingModel, setIsDownloadingModel] = useState(false);
   const [downloadProgress, setDownloadProgress] = useState<string | null>(null);
   const [customModelUrl, setCustomModelUrl] = useState<string>("");
@@ -31,6 +35,7 @@ export default function Settings() {
     loadSettings();
     loadEmailAccounts();
     loadCacheStats();
+    loadSemanticCache();
     findModelFiles();
   }, []);
 
@@ -63,6 +68,7 @@ export default function Settings() {
     try {
       const result = await invoke<AiSettings>("get_ai_settings");
       setSettings(result);
+      setCacheTtls(await invoke<Record<string, number>>("get_cache_ttls"));
     } catch (err: any) {
       setError(err?.message || "Failed to load settings");
     } finally {
@@ -95,8 +101,9 @@ export default function Settings() {
     setError(null);
     
     try {
-      const result = await invoke<string>("test_ai_connection");
-      setTestResult({ success: true, message: result });
+      const result = await invoke<{ ok: boolean; message: string; latencyMs?: number | null }>("test_ai_connection");
+      const message = result.ok && result.latencyMs != null ? `${result.message} (${result.latencyMs} ms)` : result.message;
+      setTestResult({ success: result.ok, message });
     } catch (err: any) {
       setTestResult({ success: false, message: err?.message || "Connection test failed" });
     }
@@ -123,6 +130,24 @@ export default function Settings() {
     }
   }
 
+  async function loadSemanticCache() {
+    try {
+      setSemanticCache(await invoke<SemanticCacheConfig>("get_semantic_cache_config"));
+    } catch (err: any) {
+      console.error("Failed to load semantic cache settings:", err);
+    }
+  }
+
+  async function handleSaveSemanticCache(config: SemanticCacheConfig) {
+    try {
+      await invoke("save_semantic_cache_config", { config });
+      setSemanticCache(config);
+      showToast("Semantic cache settings saved", "success");
+    } catch (err: any) {
+      showToast(err?.message || "Failed to save semantic cache settings", "error");
+    }
+  }
+
   async function handleClearCacheByPurpose(purpose: string) {
     if (!confirm(`Are you sure you want to clear all cache entries for "${purpose}"?`)) return;
     try {
@@ -204,9 +229,10 @@ export default function Settings() {
         updatedAt: new Date().toISOString(),
       } as EmailAccount;
 
-      await invoke<number>("save_email_account", { account });
+      await invoke<number>("save_email_account", { account, password: emailPassword || null });
       showToast("Email account saved successfully", "success");
       setShowEmailForm(false);
+      setEmailPassword("");
       setEmailFormData({
         emailAddress: "",
         provider: "gmail",
@@ -230,6 +256,27 @@ export default function Settings() {
     }
   }
 
+  async function handleSyncEmailAccount(id: number) {
+    setSyncingAccountId(id);
+    try {
+      const result = await invoke<EmailSyncResult>("sync_email_account", { accountId: id });
+      const { newMessages, linked, unlinked } = result;
+      let message = newMessages === 0 ? "No new messages" : `Synced ${newMessages} new message${newMessages === 1 ? "" : "s"}`;
+      if (linked.length > 0) {
+        message += `, linked ${linked.length} thread${linked.length === 1 ? "" : "s"} to applications`;
+      }
+      if (unlinked.length > 0) {
+        message += ` (${unlinked.length} to review)`;
+      }
+      showToast(message, "success");
+      loadEmailAccounts();
+    } catch (err: any) {
+      showToast(err?.message || err || "Email sync failed", "error");
+    } finally {
+      setSyncingAccountId(null);
+    }
+  }
+
   async function handleTestEmailConnection() {
     if (!emailFormData.emailAddress) {
       showToast("Please enter an email address", "error");
@@ -394,9 +441,114 @@ export default function Settings() {
               {settings.mode === "hybrid" && "Uses cloud if API key is configured, otherwise falls back to local model if configured."}
             </p>
           </div>
+          <div className="form-group">
+            <label>Temperature</label>
+            <input
+              type="number"
+              min={0}
+              max={2}
+              step={0.1}
+              value={settings.temperature ?? ""}
+              onChange={(e) =>
+                setSettings({ ...settings, temperature: e.target.value === "" ? null : Number(e.target.value) })
+              }
+              placeholder="Default (0.3 for cloud)"
+            />
+            <p className="form-help">0 gives the most consistent output, up to 2 for more varied wording.</p>
+          </div>
+          <div className="form-group">
+            <label>Max output tokens</label>
+            <input
+              type="number"
+              min={1}
+              step={1}
+              value={settings.maxTokens ?? ""}
+              onChange={(e) =>
+                setSettings({ ...settings, maxTokens: e.target.value === "" ? null : Number(e.target.value) })
+              }
+              placeholder="Provider default"
+            />
+            <p className="form-help">Raise this if long generations are cut off.</p>
+          </div>
+          <div className="form-group">
+            <label>Parallel AI requests</label>
+            <input
+              type="number"
+              min={1}
+              step={1}
+              value={settings.maxConcurrentRequests ?? ""}
+              onChange={(e) =>
+                setSettings({ ...settings, maxConcurrentRequests: e.target.value === "" ? null : Number(e.target.value) })
+              }
+              placeholder="2"
+            />
+            <p className="form-help">Lower this if your provider reports rate limits when generating for several jobs at once.</p>
+          </div>
+          {settings.mode !== "hybrid" && (
+            <div className="form-group">
+              <label>When the provider is rate limited or unreachable</label>
+              <select
+                value={settings.fallbackMode || ""}
+                onChange={(e) =>
+                  setSettings({ ...settings, fallbackMode: (e.target.value || null) as AiSettings["fallbackMode"] })
+                }
+              >
+                <option value="">Fail the request</option>
+                {settings.mode !== "local" && <option value="local">Retry with the local model</option>}
+                {settings.mode !== "cloud" && <option value="cloud">Retry with the cloud provider</option>}
+              </select>
+            </div>
+          )}
         </div>
 
         {settings.mode === "local" && (
+          <div className="settings-section">
+            <h2>Local Backend</h2>
+            <div className="form-group">
+              <label>Run local models with</label>
+              <select
+                value={settings.localBackend || "embedded"}
+                onChange={(e) =>
+                  setSettings({ ...settings, localBackend: e.target.value as AiSettings["localBackend"] })
+                }
+              >
+                <option value="embedded">Built-in (GGUF file)</option>
+                <option value="ollama">Ollama</option>
+              </select>
+            </div>
+            {settings.localBackend === "ollama" && (
+              <>
+                <div className="form-group">
+                  <label>Ollama URL</label>
+                  <input
+                    type="text"
+                    value={settings.ollamaBaseUrl || ""}
+                    onChange={(e) =>
+                      setSettings({ ...settings, ollamaBaseUrl: e.target.value || null })
+                    }
+                    placeholder="http://localhost:11434"
+                  />
+                </div>
+                <div className="form-group">
+                  <label>Model</label>
+                  <input
+                    type="text"
+                    value={settings.ollamaModel || ""}
+                    onChange={(e) =>
+                      setSettings({ ...settings, ollamaModel: e.target.value || null })
+                    }
+                    placeholder="e.g., llama3.1, qwen2.5:7b"
+                  />
+                  <p className="form-help">
+                    The tag of a model you have pulled with <code>ollama pull</code>.
+                  </p>
+                </div>
+              </>
+            )}
+          </div>
+        )}
+
+        {settings.mode === "local" && settings.localBackend !== "ollama" && (
           <div className="settings-section">
             <h2>Local Model Configuration</h2>
             
@@ -589,6 +741,70 @@ export default function Settings() {
           </div>
         )}
 
+        {settings.mode !== "hybrid" && (
+          <div className="settings-section">
+            <h2>Models per Task</h2>
+            <p className="form-help">
+              Use a different model for some tasks, e.g. a fast one for job parsing. Leave blank to use the default model.
+            </p>
+            {[
+              ["job_parse", "Job parsing"],
+              ["resume_generation", "Resume generation"],
+              ["cover_letter_generation", "Cover letters"],
+            ].map(([purpose, label]) => (
+              <div className="form-group" key={purpose}>
+                <label>{label}</label>
+                <input
+                  type="text"
+                  value={settings.modelOverrides?.[purpose] || ""}
+                  onChange={(e) => {
+                    const modelOverrides = { ...(settings.modelOverrides || {}) };
+                    if (e.target.value.trim()) {
+                      modelOverrides[purpose] = e.target.value;
+                    } else {
+                      delete modelOverrides[purpose];
+                    }
+                    setSettings({ ...settings, modelOverrides });
+                  }}
+                  placeholder="Default model"
+                />
+              </div>
+            ))}
+          </div>
+        )}
+
+        <div className="settings-section">
+          <h2>Cache Lifetimes</h2>
+          <p className="form-help">
+            Days before a cached AI result is regenerated. Leave blank to keep the current lifetime.
+          </p>
+          {[
+            ["job_parse", "Job parsing"],
+            ["resume_generation", "Resume generation"],
+            ["cover_letter_generation", "Cover letters"],
+          ].map(([purpose, label]) => (
+            <div className="form-group" key={purpose}>
+              <label>{label}</label>
+              <input
+                type="number"
+                min={1}
+                step={1}
+                value={settings.cacheTtls?.[purpose] ?? ""}
+                onChange={(e) => {
+                  const cacheTtls = { ...(settings.cacheTtls || {}) };
+                  if (e.target.value) {
+                    cacheTtls[purpose] = Number(e.target.value);
+                  } else {
+                    delete cacheTtls[purpose];
+                  }
+                  setSettings({ ...settings, cacheTtls });
+                }}
+                placeholder={cacheTtls[purpose] ? `${cacheTtls[purpose]} days` : "Never expires"}
+              />
+            </div>
+          ))}
+        </div>
+
         <div className="settings-actions">
           <button
             onClick={handleSave}
@@ -641,6 +857,16 @@ export default function Settings() {
                   placeholder="your.email@example.com"
                 />
               </div>
+              <div className="form-group">
+                <label>App Password</label>
+                <input
+                  type="password"
+                  value={emailPassword}
+                  onChange={(e) => setEmailPassword(e.target.value)}
+                  placeholder={emailFormData.id ? "Leave blank to keep the saved password" : "App password"}
+                  autoComplete="off"
+                />
+              </div>
               <div className="form-group">
                 <label>Provider</label>
                 <select
@@ -712,8 +938,8 @@ export default function Settings() {
                 </button>
               </div>
               <p className="form-help" style={{ marginTop: "1rem", fontSize: "0.875rem", color: "#6b7280" }}>
-                <strong>Note:</strong> Full email sync requires IMAP access. For Gmail, you'll need to enable "Less secure app access" or use an App Password. 
-                Email sync functionality is currently in development.
+                <strong>Note:</strong> Email sync uses IMAP with an app password, stored in your system keychain. For Gmail and iCloud,
+                create an App Password in your account's security settings. Sync reads your inbox without marking messages as read.
               </p>
             </div>
           )}
@@ -735,12 +961,13 @@ export default function Settings() {
                   </div>
                   <div style={{ display: "flex", gap: "0.5rem" }}>
                     <button
-                      onClick={() => invoke("sync_email_account", { accountId: account.id })}
+                      onClick={() => account.id && handleSyncEmailAccount(account.id)}
                       className="btn-secondary"
                       type="button"
+                      disabled={syncingAccountId === account.id}
                       style={{ fontSize: "0.875rem", padding: "0.5rem 1rem" }}
                     >
-                      Sync
+                      {syncingAccountId === account.id ? "Syncing..." : "Sync"}
                     </button>
                     <button
                       onClick={() => account.id && handleDeleteEmailAccount(account.id)}
@@ -796,6 +1023,15 @@ export default function Settings() {
                           <span className="cache-purpose-name">{purpose}</span>
                           <div style={{ display: "flex", alignItems: "center", gap: "0.5rem" }}>
                             <span className="cache-purpose-count">{count.toLocaleString()}</span>
+                            {(() => {
+                              const stat = cacheStats.by_purpose?.find((s) => s.purpose === purpose);
+                              return stat ? (
+                                <span style={{ fontSize: "0.75rem", color: "#6b7280" }}>
+                                  {formatBytes(stat.total_bytes)}
+                                  {stat.expired_count > 0 && ` • ${stat.expired_count} expired`}
+                                </span>
+                              ) : null;
+                            })()}
                             <button
                               onClick={() => handleClearCacheByPurpose(purpose)}
                               className="btn-secondary"
@@ -865,6 +1101,35 @@ export default function Settings() {
               Failed to load cache statistics.
             </p>
           )}
+
+          <div className="form-group" style={{ marginTop: "1.5rem" }}>
+            <label>
+              <input
+                type="checkbox"
+                checked={semanticCache.enabled}
+                onChange={(e) => handleSaveSemanticCache({ ...semanticCache, enabled: e.target.checked })}
+              />{" "}
+              Reuse results for near-identical job descriptions
+            </label>
+            <small>
+              On a cache miss, compares an embedding of the description with cached ones. Needs a provider with
+              embeddings (OpenAI or Ollama).
+            </small>
+            {semanticCache.enabled && (
+              <div style={{ marginTop: "0.5rem" }}>
+                <label>Minimum similarity</label>
+                <input
+                  type="number"
+                  min={0.5}
+                  max={1}
+                  step={0.01}
+                  value={semanticCache.minSimilarity}
+                  onChange={(e) => setSemanticCache({ ...semanticCache, minSimilarity: Number(e.target.value) })}
+                  onBlur={() => handleSaveSemanticCache(semanticCache)}
+                />
+              </div>
+            )}
+          </div>
         </div>
       </div>
     </div>
@@ -17,9 +17,10 @@ export class DashboardService {
     }
   }
 
-  static async exportDashboardData(format: 'json' | 'csv'): Promise<void> {
+  static async exportDashboardData(format: 'json' | 'csv'): Promise<string> {
     try {
-      return await invoke('export_dashboard_data', { format });
+      const command = format === 'json' ? 'export_dashboard_json' : 'export_dashboard_data';
+      return await invoke<string>(command);
     } catch (error) {
       throw new ServiceError(`Failed to export dashboard data as ${format}`, error);
     }
@@ -1,11 +1,11 @@
 import { invoke } from '@tauri-apps/api/core';
-import type { Job, JobSummary, CreateJobInput, UpdateJobInput, ParsedJob } from '../commands/jobs';
+import type { Job, JobSummary, CreateJobInput, CreateJobResult, UpdateJobInput, ParsedJob } from '../commands/jobs';
 import { ServiceError } from './dashboardService';
 
 export class JobService {
-  static async createJob(input: CreateJobInput): Promise<Job> {
+  static async createJob(input: CreateJobInput): Promise<CreateJobResult> {
     try {
-      return await invoke<Job>('create_job', { input });
+      return await invoke<CreateJobResult>('create_job', { input });
     } catch (error) {
       throw new ServiceError('Failed to create job', error);
     }
@@ -19,7 +19,7 @@ export class JobService {
     }
   }
 
-  static async getJobList(options?: { search?: string | null; activeOnly?: boolean; source?: string | null }): Promise<JobSummary[]> {
+  static async getJobList(options?: { search?: string | null; activeOnly?: boolean; source?: string | null; minSalary?: number | null; tags?: string[] | null }): Promise<JobSummary[]> {
     try {
       return await invoke<JobSummary[]>('get_job_list', { options });
     } catch (error) {
@@ -141,6 +141,7 @@ export const createJobInputSchema = z.object({
   jobSource: z.string().optional(),
   postingUrl: z.string().url("Please enter a valid URL").optional().or(z.literal("")),
   rawDescription: z.string().optional(),
+  force: z.boolean().optional(),
 }).refine(
   (data) => {
     // At least one of title, company, or rawDescription must be provided
@@ -220,20 +221,30 @@ export const aiSettingsSchema = z.object({
   apiKey: z.string().optional(),
   modelName: z.string().optional(),
   localModelPath: z.string().optional(),
+  temperature: z.number().min(0).max(2).nullable().optional(),
+  seed: z.number().int().min(0).nullable().optional(),
+  maxTokens: z.number().int().min(1).nullable().optional(),
+  maxConcurrentRequests: z.number().int().min(1).nullable().optional(),
+  localBackend: z.enum(["embedded", "ollama"]).optional(),
+  ollamaBaseUrl: z.string().url().nullable().optional(),
+  ollamaModel: z.string().nullable().optional(),
+  fallbackMode: z.enum(["local", "cloud", "hybrid"]).nullable().optional(),
+  modelOverrides: z.record(z.string().min(1)).optional(),
+  cacheTtls: z.record(z.number().int().min(1, "Cache TTLs must be at least 1 day")).optional(),
 }).refine(
   (data) => {
     // If cloud mode, API key is required
     if (data.mode === "cloud" && !data.apiKey) {
       return false;
     }
-    // If local mode, local model path is required
-    if (data.mode === "local" && !data.localModelPath) {
-      return false;
+    // If local mode, a model path (or an Ollama model) is required
+    if (data.mode === "local") {
+      return data.localBackend === "ollama" ? !!data.ollamaModel?.trim() : !!data.localModelPath;
     }
     return true;
   },
   {
-    message: "Cloud mode requires an API key, and local mode requires a model path",
+    message: "Cloud mode requires an API key, and local mode requires a model path or Ollama model",
   }
 );
 